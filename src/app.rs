@@ -1,33 +1,72 @@
+pub mod clipboard;
+pub mod context_menu;
 pub mod data_farmer;
+pub mod diff;
+pub mod dirty_widgets;
+pub mod disk_bench;
+pub mod event_log;
+pub mod exec;
 pub mod filter;
+#[cfg(feature = "flame")]
+pub mod flamegraph;
 pub mod frozen_state;
 pub mod layout_manager;
+pub mod meminfo;
+pub mod multi_host;
 mod process_killer;
+pub mod saved_state;
+pub mod scroll_momentum;
 pub mod states;
+pub mod system_summary;
+pub mod threshold_editor;
+pub mod visible_columns;
 
 use std::{
     cmp::{max, min},
-    time::Instant,
+    time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::bail;
+use clipboard::Flash;
 use concat_string::concat_string;
+use context_menu::{ContextMenu, ContextMenuAction};
 use data_farmer::*;
+use diff::DiffBaseline;
+use dirty_widgets::DirtyWidgets;
+use disk_bench::DiskBenchState;
+use event_log::{EventLog, LogLevel};
+use exec::ExecOutput;
 use filter::*;
-use frozen_state::FrozenState;
+use frozen_state::{FreezeAlert, FreezeOnAlertMode, FrozenState};
 use hashbrown::HashMap;
 use layout_manager::*;
+use meminfo::MemInfoPanel;
+use multi_host::MultiHostState;
+use saved_state::SavedState;
+use scroll_momentum::ScrollMomentum;
 pub use states::*;
+use system_summary::SystemSummaryPanel;
+use threshold_editor::ThresholdEditor;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+use visible_columns::VisibleColumns;
 
+#[cfg(feature = "systemd")]
+use crate::data_collection::systemd;
 use crate::{
     canvas::components::time_chart::LegendPosition,
     constants, convert_mem_data_points, convert_swap_data_points,
-    data_collection::{processes::Pid, temperature},
+    data_collection::{
+        log_tail::LogTailBuffer, processes::Pid, synthetic::SyntheticSettings, temperature,
+        CollectionFlags,
+    },
     data_conversion::ConvertedData,
-    get_network_points,
+    export::record::ReplayState,
+    options::config::style::CanvasStyle,
     utils::data_units::DataUnit,
-    widgets::{ProcWidgetColumn, ProcWidgetMode},
+    widgets::{
+        smaps_panel::SmapsPanel, ColumnPreset, CpuWidgetTableData, ProcThresholds,
+        ProcWidgetColumn, ProcWidgetMode, ProcWidgetState,
+    },
 };
 
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
@@ -39,9 +78,22 @@ pub enum AxisScaling {
 
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct AppConfigFields {
     pub update_rate: u64,
+    /// Whether data is only collected when a key is pressed, rather than every
+    /// `update_rate` milliseconds.
+    pub update_on_key: bool,
+    /// How many percent of `update_rate` each update is randomly moved earlier
+    /// or later by.
+    pub jitter_pct: u64,
+    /// How many times in a row a subsystem can fail to be read before it's
+    /// turned off for the session.
+    pub subsystem_retry_count: u32,
+    /// The subsystems that are collected at all.
+    pub collection_flags: CollectionFlags,
+    /// The data to make up instead of collecting it, if any.
+    pub synthetic: Option<SyntheticSettings>,
     pub temperature_type: temperature::TemperatureType,
     pub use_dot: bool,
     pub cpu_left_legend: bool,
@@ -53,12 +105,30 @@ pub struct AppConfigFields {
     pub time_interval: u64,
     pub hide_time: bool,
     pub autohide_time: bool,
+    /// Whether graphs label their time axis with wall-clock times rather than
+    /// how long ago each point was.
+    pub absolute_timestamps: bool,
     pub use_old_network_legend: bool,
     pub table_gap: u16,
     pub disable_click: bool,
+    /// Whether copying values to the clipboard is disabled.
+    pub no_clipboard: bool,
     pub enable_gpu: bool,
     pub enable_cache_memory: bool,
     pub show_table_scroll_position: bool,
+    /// Whether to show the summary header above the widgets.
+    pub show_header: bool,
+    /// Whether to always show the status line, with a spinner that advances
+    /// on each update.
+    pub show_heartbeat: bool,
+    /// Whether to hide everything but the widgets, which also get shorter
+    /// titles and show alerts by flashing their borders.
+    pub quiet_mode: bool,
+    /// How each widget's borders are drawn.
+    pub canvas_style: CanvasStyle,
+    /// The fraction of scroll velocity kept each tick, or [`None`] if scrolling
+    /// has no momentum.
+    pub scroll_friction: Option<f32>,
     pub is_advanced_kill: bool,
     pub memory_legend_position: Option<LegendPosition>,
     // TODO: Remove these, move network details state-side.
@@ -67,7 +137,38 @@ pub struct AppConfigFields {
     pub network_scale_type: AxisScaling,
     pub network_use_binary_prefix: bool,
     pub retention_ms: u64,
+    /// How long a process can go uncollected before it's removed, in
+    /// milliseconds.
+    pub process_stale_threshold_ms: u64,
     pub dedicated_average_row: bool,
+    /// Whether to collect and show IPv6 traffic and addresses in the network
+    /// widget.
+    pub show_ipv6: bool,
+    /// Whether the network widgets show a graph for each interface rather
+    /// than one for all of them.
+    pub network_per_interface: bool,
+    /// Whether the graphs of each interface share the same scale.
+    pub network_shared_axis: bool,
+    /// Whether to freeze when an alert is raised, and how.
+    pub freeze_on_alert: Option<FreezeOnAlertMode>,
+    /// How many points the CPU graph is smoothed over, where 1 is no smoothing.
+    pub cpu_smooth_n: usize,
+    /// How many points the memory graph is smoothed over, where 1 is no smoothing.
+    pub mem_smooth_n: usize,
+    /// How many pages can be swapped out per second before the swap I/O line
+    /// is highlighted.
+    pub swap_out_warning: u64,
+    /// Whether to sum up the PSS of every process for the memory widget.
+    pub compute_pss: bool,
+    /// Whether to show RAM against the physical RAM even in a cgroup with a
+    /// memory limit.
+    pub ignore_cgroup_limits: bool,
+    /// Whether graphs start out showing the stats of their shown data.
+    pub show_graph_stats: bool,
+    /// Whether to check for a newer release once a day at startup.
+    pub check_for_updates: bool,
+    /// How many points the network graph is smoothed over, where 1 is no smoothing.
+    pub net_smooth_n: usize,
 }
 
 /// For filtering out information
@@ -106,13 +207,38 @@ pub struct App {
     pub dd_err: Option<String>, // FIXME: The way we do deletes is really gross.
     to_delete_process_list: Option<(String, Vec<Pid>)>,
     pub frozen_state: FrozenState,
+    /// The alert that caused the current freeze, if it was automatic.
+    pub freeze_alert: Option<FreezeAlert>,
     last_key_press: Instant,
     pub converted_data: ConvertedData,
     pub data_collection: DataCollection,
     pub delete_dialog_state: AppDeleteDialogState,
     pub help_dialog_state: AppHelpDialogState,
+    pub event_log: EventLog,
+    /// The summary of the system, collected once at startup.
+    pub system_summary: SystemSummaryPanel,
+    pub meminfo: MemInfoPanel,
+    /// Whether the graph of the load averages, opened by clicking them in the
+    /// header, is showing.
+    pub is_showing_load_graph: bool,
+    /// Where the load averages are drawn in the header, if they are.
+    pub header_load_loc: Option<((u16, u16), (u16, u16))>,
+    /// The context menu opened by right-clicking a process, if open.
+    pub context_menu: Option<ContextMenu>,
+    /// A message to briefly show in the status line, if any.
+    pub flash: Option<Flash>,
+    /// How many updates have been received, wrapping around. This advances the
+    /// heartbeat spinner in the status line.
+    pub heartbeat_tick: u8,
+    /// When the last update was received.
+    pub last_update_time: Instant,
     pub is_expanded: bool,
     pub is_force_redraw: bool,
+    /// Whether the terminal is smaller than the layout is meant for, so it's
+    /// only warned about once each time it gets too small.
+    is_terminal_too_small: bool,
+    /// The widgets whose data or state has changed since the last draw.
+    pub dirty_widgets: DirtyWidgets,
     pub is_determining_widget_boundary: bool,
     pub basic_mode_use_percent: bool,
     pub states: AppWidgetStates,
@@ -121,6 +247,41 @@ pub struct App {
     pub current_widget: BottomWidget,
     pub used_widgets: UsedWidgets,
     pub filters: DataFilters,
+
+    /// A snapshot to compare current data against, if running in diff mode.
+    pub baseline: Option<DiffBaseline>,
+    /// The state of the current replay, if running in replay mode.
+    pub replay: Option<ReplayState>,
+    /// The remote hosts being monitored, if running in multi-host mode.
+    pub multi_host: Option<MultiHostState>,
+    /// The momentum of scrolling through tables, unless it's disabled.
+    pub scroll_momentum: Option<ScrollMomentum>,
+    /// The layout that can be switched to, if running with `--minimal`.
+    pub alternate_layout: Option<AlternateLayout>,
+    /// Whether to switch to the alternate layout on the next draw.
+    pub is_switching_layout: bool,
+    /// The flame graph being captured or shown, if any.
+    #[cfg(feature = "flame")]
+    pub flame_graph: Option<flamegraph::FlameGraphState>,
+    /// Processes using less CPU than this, in percent, are hidden.
+    pub process_cpu_threshold: Option<f64>,
+    /// Processes using less memory than this, in percent, are hidden.
+    pub process_mem_threshold: Option<f64>,
+    /// How many processes are shown at most in each process widget.
+    pub process_top_n: Option<usize>,
+    /// The dialog for editing the process thresholds, if open.
+    pub threshold_editor: Option<ThresholdEditor>,
+    /// The process column presets, which can be switched to with the number
+    /// keys.
+    pub column_presets: Vec<ColumnPreset>,
+    /// The shell command run on each update for the exec widgets, if any.
+    pub run_command: Option<String>,
+    /// What the command printed the last time it ran.
+    pub exec_output: Option<ExecOutput>,
+    /// The lines kept from each file followed with `--watch_file`.
+    pub log_tails: Vec<LogTailBuffer>,
+    /// The read benchmark started with `--disk_bench`, if any.
+    pub disk_bench: Option<DiskBenchState>,
 }
 
 impl App {
@@ -129,6 +290,7 @@ impl App {
         app_config_fields: AppConfigFields, states: AppWidgetStates,
         widget_map: HashMap<u64, BottomWidget>, current_widget: BottomWidget,
         used_widgets: UsedWidgets, filters: DataFilters, is_expanded: bool,
+        baseline: Option<DiffBaseline>,
     ) -> Self {
         Self {
             awaiting_second_char: false,
@@ -136,24 +298,105 @@ impl App {
             dd_err: None,
             to_delete_process_list: None,
             frozen_state: FrozenState::default(),
+            freeze_alert: None,
             last_key_press: Instant::now(),
             converted_data: ConvertedData::default(),
             data_collection: DataCollection::default(),
             delete_dialog_state: AppDeleteDialogState::default(),
             help_dialog_state: AppHelpDialogState::default(),
+            event_log: EventLog::default(),
+            system_summary: SystemSummaryPanel::default(),
+            meminfo: MemInfoPanel::default(),
+            is_showing_load_graph: false,
+            header_load_loc: None,
+            context_menu: None,
+            flash: None,
+            heartbeat_tick: 0,
+            last_update_time: Instant::now(),
             is_expanded,
             is_force_redraw: false,
+            is_terminal_too_small: false,
+            dirty_widgets: DirtyWidgets::default(),
             is_determining_widget_boundary: false,
             basic_mode_use_percent: false,
             states,
-            app_config_fields,
             widget_map,
             current_widget,
             used_widgets,
             filters,
+            baseline,
+            replay: None,
+            multi_host: None,
+            scroll_momentum: app_config_fields.scroll_friction.map(ScrollMomentum::new),
+            alternate_layout: None,
+            is_switching_layout: false,
+            #[cfg(feature = "flame")]
+            flame_graph: None,
+            process_cpu_threshold: None,
+            process_mem_threshold: None,
+            process_top_n: None,
+            threshold_editor: None,
+            column_presets: Vec::new(),
+            run_command: None,
+            exec_output: None,
+            log_tails: Vec::new(),
+            disk_bench: None,
+            app_config_fields,
+        }
+    }
+
+    /// Freezes if an alert was just raised and freezing on alerts is enabled.
+    /// In level mode, an automatic freeze is also undone once usage is no
+    /// longer over any threshold.
+    pub fn check_freeze_on_alert(&mut self, alert: Option<String>) {
+        let Some(mode) = self.app_config_fields.freeze_on_alert else {
+            return;
+        };
+
+        if let Some(message) = alert {
+            if !self.frozen_state.is_frozen() {
+                self.frozen_state
+                    .freeze(Box::new(self.data_collection.clone()));
+                self.freeze_alert = Some(FreezeAlert::new(message));
+            }
+        } else if mode == FreezeOnAlertMode::Level
+            && self.freeze_alert.is_some()
+            && !self.event_log.is_alerting()
+        {
+            self.frozen_state.thaw();
+            self.freeze_alert = None;
+        }
+    }
+
+    /// Sets the least usage processes need to be shown in every process
+    /// widget.
+    pub fn set_process_thresholds(&mut self, thresholds: ProcThresholds) {
+        self.process_cpu_threshold = thresholds.cpu;
+        self.process_mem_threshold = thresholds.mem;
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.set_thresholds(thresholds);
         }
     }
 
+    /// Sets how many processes are shown at most in every process widget.
+    pub fn set_process_top_n(&mut self, top_n: Option<usize>) {
+        self.process_top_n = top_n;
+        for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.set_top_n(top_n);
+        }
+    }
+
+    /// The process columns shown by any process widget, which decides what's
+    /// collected for each process.
+    pub fn visible_columns(&self) -> VisibleColumns {
+        self.states
+            .proc_state
+            .widget_states
+            .values()
+            .map(ProcWidgetState::visible_columns)
+            .collect()
+    }
+
     /// Update the data in the [`App`].
     pub fn update_data(&mut self) {
         let data_source = match &self.frozen_state {
@@ -161,19 +404,24 @@ impl App {
             FrozenState::Frozen(data) => data,
         };
 
+        let is_frozen = self.frozen_state.is_frozen();
         for proc in self.states.proc_state.widget_states.values_mut() {
+            proc.pause_change_highlights(is_frozen);
             if proc.force_update_data {
-                proc.set_table_data(data_source);
+                proc.set_table_data(data_source, self.baseline.as_ref().map(|b| &b.data));
                 proc.force_update_data = false;
+                self.dirty_widgets.mark(&BottomWidgetType::Proc);
             }
         }
 
         // FIXME: Make this CPU force update less terrible.
         if self.states.cpu_state.force_update.is_some() {
-            self.converted_data.convert_cpu_data(data_source);
+            self.converted_data
+                .convert_cpu_data(data_source, self.app_config_fields.cpu_smooth_n);
             self.converted_data.load_avg_data = data_source.load_avg_harvest;
 
             self.states.cpu_state.force_update = None;
+            self.dirty_widgets.mark(&BottomWidgetType::Cpu);
         }
 
         // FIXME: This is a bit of a temp hack to move data over.
@@ -183,12 +431,26 @@ impl App {
                 cpu.update_table(data);
             }
         }
+        if self.states.temp_state.force_update.is_some() {
+            self.converted_data.convert_temp_data(
+                data_source,
+                self.app_config_fields.temperature_type,
+                self.baseline
+                    .as_ref()
+                    .and_then(|b| b.data.temperature_sensors.as_deref()),
+            );
+            for temp in self.states.temp_state.widget_states.values_mut() {
+                temp.force_data_update();
+            }
+            self.states.temp_state.force_update = None;
+        }
         {
             let data = &self.converted_data.temp_data;
             for temp in self.states.temp_state.widget_states.values_mut() {
                 if temp.force_update_data {
                     temp.set_table_data(data);
                     temp.force_update_data = false;
+                    self.dirty_widgets.mark(&BottomWidgetType::Temp);
                 }
             }
         }
@@ -198,6 +460,28 @@ impl App {
                 if disk.force_update_data {
                     disk.set_table_data(data);
                     disk.force_update_data = false;
+                    self.dirty_widgets.mark(&BottomWidgetType::Disk);
+                }
+            }
+        }
+        {
+            let data = &self.converted_data.startup_data;
+            for startup in self.states.startup_state.widget_states.values_mut() {
+                if startup.force_update_data {
+                    startup.set_table_data(data);
+                    startup.force_update_data = false;
+                    self.dirty_widgets.mark(&BottomWidgetType::Startup);
+                }
+            }
+        }
+        {
+            let data = &self.converted_data.user_proc_data;
+            let counts = &self.converted_data.process_counts;
+            for user_procs in self.states.user_proc_state.widget_states.values_mut() {
+                if user_procs.force_update_data {
+                    user_procs.set_table_data(data, counts);
+                    user_procs.force_update_data = false;
+                    self.dirty_widgets.mark(&BottomWidgetType::UserProcs);
                 }
             }
         }
@@ -219,19 +503,20 @@ impl App {
             {
                 self.converted_data.gpu_data = crate::convert_gpu_data(data_source);
             }
+            self.converted_data
+                .smooth_mem_data(self.app_config_fields.mem_smooth_n);
             self.states.mem_state.force_update = None;
+            self.dirty_widgets.mark(&BottomWidgetType::Mem);
         }
 
         if self.states.net_state.force_update.is_some() {
-            let (rx, tx) = get_network_points(
+            self.converted_data.convert_network_data(
                 data_source,
-                &self.app_config_fields.network_scale_type,
-                &self.app_config_fields.network_unit_type,
-                self.app_config_fields.network_use_binary_prefix,
+                &self.app_config_fields,
+                self.baseline.as_ref().and_then(|b| b.data.network.as_ref()),
             );
-            self.converted_data.network_data_rx = rx;
-            self.converted_data.network_data_tx = tx;
             self.states.net_state.force_update = None;
+            self.dirty_widgets.mark(&BottomWidgetType::Net);
         }
     }
 
@@ -241,7 +526,15 @@ impl App {
 
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
+        self.event_log.is_showing = false;
+        self.system_summary.is_showing = false;
+        self.meminfo.is_showing = false;
+        self.is_showing_load_graph = false;
+        if let Some(disk_bench) = &mut self.disk_bench {
+            disk_bench.is_showing_result = false;
+        }
         self.delete_dialog_state.is_showing_dd = false;
+        self.context_menu = None;
 
         // Close all searches and reset it
         self.states
@@ -258,6 +551,7 @@ impl App {
 
         // Unfreeze.
         self.frozen_state.thaw();
+        self.freeze_alert = None;
 
         // Reset zoom
         self.reset_cpu_zoom();
@@ -266,6 +560,25 @@ impl App {
 
         // Reset data
         self.data_collection.reset();
+        self.dirty_widgets.mark_all();
+    }
+
+    /// The wall-clock time of the newest point in the graphs, if their time
+    /// axis is labelled with absolute times. Replays always are, as long as
+    /// the recording has times.
+    pub fn graph_end_time(&self) -> Option<SystemTime> {
+        if let Some(replay) = &self.replay {
+            return replay.current_time();
+        }
+
+        if !self.app_config_fields.absolute_timestamps {
+            return None;
+        }
+
+        Some(match &self.frozen_state {
+            FrozenState::NotFrozen => self.data_collection.current_time,
+            FrozenState::Frozen(data) => data.current_time,
+        })
     }
 
     pub fn should_get_widget_bounds(&self) -> bool {
@@ -286,6 +599,27 @@ impl App {
             if self.help_dialog_state.is_showing_help {
                 self.help_dialog_state.is_showing_help = false;
                 self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            } else if self.event_log.is_showing {
+                self.event_log.toggle();
+            } else if self.system_summary.is_showing {
+                self.system_summary.toggle();
+            } else if self.meminfo.is_showing {
+                self.meminfo.toggle();
+            } else if self.is_showing_load_graph {
+                self.is_showing_load_graph = false;
+            } else if self.is_showing_disk_bench_result() {
+                if let Some(disk_bench) = &mut self.disk_bench {
+                    disk_bench.is_showing_result = false;
+                }
+            } else if self.context_menu.is_some() {
+                self.context_menu = None;
+            } else if self.threshold_editor.is_some() {
+                self.threshold_editor = None;
+            } else if self.is_showing_flame_graph() {
+                #[cfg(feature = "flame")]
+                {
+                    self.flame_graph = None;
+                }
             } else {
                 self.close_dd();
             }
@@ -304,6 +638,40 @@ impl App {
                             pws.is_sort_open = false;
                             self.is_force_redraw = true;
                             return;
+                        } else if pws.thread_panel.is_open() {
+                            pws.thread_panel.close();
+                            self.is_force_redraw = true;
+                            return;
+                        } else if pws.rlimit_panel.is_open() {
+                            pws.rlimit_panel.close();
+                            self.is_force_redraw = true;
+                            return;
+                        } else if pws.smaps_panel.is_filtering {
+                            pws.smaps_panel.set_filtering(false);
+                            self.is_force_redraw = true;
+                            return;
+                        } else if pws.smaps_panel.is_open() {
+                            pws.smaps_panel.close();
+                            self.is_force_redraw = true;
+                            return;
+                        } else if pws.security_panel.is_open() {
+                            pws.security_panel.close();
+                            self.is_force_redraw = true;
+                            return;
+                        }
+                    }
+                }
+                BottomWidgetType::Net => {
+                    if let Some(net_widget_state) = self
+                        .states
+                        .net_state
+                        .widget_states
+                        .get_mut(&self.current_widget.widget_id)
+                    {
+                        if net_widget_state.nic_stats_panel.is_open() {
+                            net_widget_state.nic_stats_panel.close();
+                            self.is_force_redraw = true;
+                            return;
                         }
                     }
                 }
@@ -358,7 +726,28 @@ impl App {
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.event_log.is_showing
+            || self.system_summary.is_showing
+            || self.meminfo.is_showing
+            || self.is_showing_load_graph
+            || self.is_showing_disk_bench_result()
+            || self.delete_dialog_state.is_showing_dd
+            || self.context_menu.is_some()
+            || self.threshold_editor.is_some()
+            || self.is_showing_flame_graph()
+    }
+
+    /// Whether the flame graph overlay is open.
+    pub fn is_showing_flame_graph(&self) -> bool {
+        #[cfg(feature = "flame")]
+        {
+            self.flame_graph.is_some()
+        }
+        #[cfg(not(feature = "flame"))]
+        {
+            false
+        }
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -366,9 +755,13 @@ impl App {
     }
 
     pub fn on_tab(&mut self) {
-        // Allow usage whilst only in processes
+        // Allow usage whilst only in processes, or to filter the event log.
 
-        if !self.ignore_normal_keybinds() {
+        if self.event_log.is_showing {
+            self.event_log.cycle_filter();
+        } else if let Some(editor) = &mut self.threshold_editor {
+            editor.toggle_field();
+        } else if !self.ignore_normal_keybinds() {
             if let BottomWidgetType::Proc = self.current_widget.widget_type {
                 if let Some(proc_widget_state) = self
                     .states
@@ -393,8 +786,13 @@ impl App {
                                 _ => 0,
                             },
                     ) {
-                        proc_widget_state.proc_search.search_state.is_enabled = true;
-                        self.move_widget_selection(&WidgetDirection::Down);
+                        // While open, the memory map panel is filtered instead.
+                        if proc_widget_state.smaps_panel.is_open() {
+                            proc_widget_state.smaps_panel.set_filtering(true);
+                        } else {
+                            proc_widget_state.proc_search.search_state.is_enabled = true;
+                            self.move_widget_selection(&WidgetDirection::Down);
+                        }
                         self.is_force_redraw = true;
                     }
                 }
@@ -403,6 +801,20 @@ impl App {
         }
     }
 
+    /// The memory map panel of the current process widget, if characters are
+    /// being typed into its filter.
+    fn filtering_smaps_panel(&mut self) -> Option<&mut SmapsPanel> {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.states
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+                .map(|pws| &mut pws.smaps_panel)
+                .filter(|panel| panel.is_filtering)
+        } else {
+            None
+        }
+    }
+
     pub fn toggle_sort_menu(&mut self) {
         let widget_id = self.current_widget.widget_id
             - match &self.current_widget.widget_type {
@@ -427,6 +839,75 @@ impl App {
         }
     }
 
+    /// Moves the column selected in the sort menu one place to the left or
+    /// right, and saves the new order so it's restored on the next run.
+    pub fn move_sort_column(&mut self, right: bool) {
+        if let Some(pws) = self
+            .states
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id - 2)
+        {
+            if pws.move_sort_table_column(right) {
+                let mut saved_state = SavedState::load();
+                saved_state.process_columns = pws.column_order();
+                if let Err(err) = saved_state.save() {
+                    self.event_log.push(
+                        LogLevel::Warn,
+                        format!("Couldn't save the column order: {err}"),
+                    );
+                }
+                self.is_force_redraw = true;
+            }
+        }
+    }
+
+    /// Switches the selected process widget to the column preset at `index`.
+    pub fn switch_column_preset(&mut self, index: usize) {
+        if !matches!(self.current_widget.widget_type, BottomWidgetType::Proc) {
+            return;
+        }
+
+        if let (Some(preset), Some(pws)) = (
+            self.column_presets.get(index),
+            self.states
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id),
+        ) {
+            pws.set_columns(&preset.widget_columns(), Some(preset.name.clone()));
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Saves the shown columns of the process widget whose sort menu is
+    /// selected as a new column preset, which is kept between runs.
+    pub fn save_column_preset(&mut self) {
+        if !matches!(self.current_widget.widget_type, BottomWidgetType::ProcSort) {
+            return;
+        }
+
+        if let Some(pws) = self
+            .states
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id - 2)
+        {
+            let name = format!("preset {}", self.column_presets.len() + 1);
+            let preset = ColumnPreset::new(name.clone(), pws.shown_columns());
+            pws.set_preset_name(Some(name.clone()));
+
+            let mut saved_state = SavedState::load();
+            saved_state.column_presets.push(preset.clone());
+            if let Err(err) = saved_state.save() {
+                self.event_log.push(
+                    LogLevel::Warn,
+                    format!("Couldn't save the column preset: {err}"),
+                );
+            }
+            self.column_presets.push(preset);
+            self.flash = Some(Flash::new(format!("Saved the columns as {name}")));
+            self.is_force_redraw = true;
+        }
+    }
+
     pub fn invert_sort(&mut self) {
         match &self.current_widget.widget_type {
             BottomWidgetType::Proc | BottomWidgetType::ProcSort => {
@@ -466,6 +947,73 @@ impl App {
         }
     }
 
+    /// Cycles the network widgets through showing rates in bits with SI and
+    /// binary prefixes, then bytes with SI and binary prefixes.
+    pub fn cycle_network_units(&mut self) {
+        if !matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Net | BottomWidgetType::BasicNet
+        ) {
+            return;
+        }
+
+        let config = &mut self.app_config_fields;
+        if config.network_use_binary_prefix {
+            config.network_unit_type = match config.network_unit_type {
+                DataUnit::Bit => DataUnit::Byte,
+                DataUnit::Byte => DataUnit::Bit,
+            };
+        }
+        config.network_use_binary_prefix = !config.network_use_binary_prefix;
+
+        self.states.net_state.force_update = Some(self.current_widget.widget_id);
+    }
+
+    /// Switches the network widgets between one graph for all interfaces and
+    /// one graph for each.
+    pub fn toggle_network_per_interface(&mut self) {
+        if !matches!(self.current_widget.widget_type, BottomWidgetType::Net) {
+            return;
+        }
+
+        let config = &mut self.app_config_fields;
+        config.network_per_interface = !config.network_per_interface;
+
+        self.states.net_state.force_update = Some(self.current_widget.widget_id);
+        self.is_force_redraw = true;
+    }
+
+    /// Cycles the temperature unit from Celsius to Fahrenheit to Kelvin, and
+    /// saves it so it's restored on the next run. Stored readings are
+    /// converted right away rather than waiting for the next harvest.
+    pub fn cycle_temperature_type(&mut self) {
+        if self.replay.is_some() || self.multi_host.is_some() {
+            return;
+        }
+
+        let from = self.app_config_fields.temperature_type;
+        let to = from.next();
+        self.app_config_fields.temperature_type = to;
+
+        self.data_collection.convert_temperatures(from, to);
+        if let FrozenState::Frozen(data) = &mut self.frozen_state {
+            data.convert_temperatures(from, to);
+        }
+        for temp in self.states.temp_state.widget_states.values_mut() {
+            temp.set_temperature_type(to);
+        }
+        self.states.temp_state.force_update = Some(self.current_widget.widget_id);
+
+        let mut saved_state = SavedState::load();
+        saved_state.temperature_type = Some(to);
+        if let Err(err) = saved_state.save() {
+            self.event_log.push(
+                LogLevel::Warn,
+                format!("Couldn't save the temperature unit: {err}"),
+            );
+        }
+    }
+
     pub fn toggle_ignore_case(&mut self) {
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
@@ -560,19 +1108,216 @@ impl App {
                 self.delete_dialog_state.is_showing_dd = false;
             }
             self.is_force_redraw = true;
+        } else if let Some(context_menu) = self.context_menu.take() {
+            if let Some(action) = context_menu.selected_action() {
+                self.run_context_menu_action(action);
+            }
+            self.is_force_redraw = true;
+        } else if let Some(editor) = &mut self.threshold_editor {
+            match editor.parse() {
+                Ok((cpu, mem)) => {
+                    self.threshold_editor = None;
+                    self.set_process_thresholds(ProcThresholds::new(cpu, mem));
+                }
+                Err(err) => editor.error = Some(err),
+            }
+            self.is_force_redraw = true;
+        } else if let Some(panel) = self.filtering_smaps_panel() {
+            panel.set_filtering(false);
+            self.is_force_redraw = true;
+        } else if self.meminfo.is_filtering {
+            self.meminfo.set_filtering(false);
+            self.is_force_redraw = true;
         } else if !self.is_in_dialog() {
-            if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
-                if let Some(proc_widget_state) = self
-                    .states
-                    .proc_state
-                    .widget_states
-                    .get_mut(&(self.current_widget.widget_id - 2))
+            match self.current_widget.widget_type {
+                BottomWidgetType::ProcSort => {
+                    if let Some(proc_widget_state) = self
+                        .states
+                        .proc_state
+                        .widget_states
+                        .get_mut(&(self.current_widget.widget_id - 2))
+                    {
+                        proc_widget_state.use_sort_table_value();
+                        self.move_widget_selection(&WidgetDirection::Right);
+                        self.is_force_redraw = true;
+                    }
+                }
+                // Threads are read from this machine, so they aren't available
+                // for replays or remote hosts.
+                BottomWidgetType::Proc if self.replay.is_none() && self.multi_host.is_none() => {
+                    if let Some(proc_widget_state) = self
+                        .states
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_widget_state.toggle_thread_panel();
+                        self.is_force_redraw = true;
+                    }
+                }
+                BottomWidgetType::Mem => {
+                    if let Some(mem_widget_state) = self
+                        .states
+                        .mem_state
+                        .widget_states
+                        .get_mut(&self.current_widget.widget_id)
+                    {
+                        mem_widget_state.toggle_swap_devices();
+                        self.is_force_redraw = true;
+                    }
+                }
+                // Hardware statistics are read from this machine, so they
+                // aren't available for replays or remote hosts.
+                BottomWidgetType::Net if self.replay.is_none() && self.multi_host.is_none() => {
+                    let interfaces = self.nic_stats_interfaces();
+                    if let Some(net_widget_state) = self
+                        .states
+                        .net_state
+                        .widget_states
+                        .get_mut(&self.current_widget.widget_id)
+                    {
+                        net_widget_state.toggle_nic_stats_panel(&interfaces);
+                        self.is_force_redraw = true;
+                    }
+                }
+                // Units are changed on this machine, so they can't be for
+                // replays or remote hosts.
+                #[cfg(feature = "systemd")]
+                BottomWidgetType::Startup if self.replay.is_none() && self.multi_host.is_none() => {
+                    self.toggle_selected_unit();
+                    self.is_force_redraw = true;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Enables the selected user unit if it's disabled, or disables it if
+    /// it's enabled, showing how that went.
+    #[cfg(feature = "systemd")]
+    fn toggle_selected_unit(&mut self) {
+        let Some((unit, enabled)) = self
+            .states
+            .startup_state
+            .get_widget_state(self.current_widget.widget_id)
+            .and_then(|state| state.table.current_item())
+            .map(|row| (row.unit.to_string(), !row.enabled))
+        else {
+            return;
+        };
+
+        let message = match systemd::set_unit_enabled(&unit, enabled) {
+            Ok(()) => {
+                // Show the change now, rather than on the next time the units are read.
+                if let Some(user_unit) = self
+                    .data_collection
+                    .user_units
+                    .iter_mut()
+                    .find(|user_unit| user_unit.name == unit)
                 {
-                    proc_widget_state.use_sort_table_value();
-                    self.move_widget_selection(&WidgetDirection::Right);
+                    user_unit.enabled = enabled;
+                }
+                self.converted_data
+                    .convert_startup_data(&self.data_collection);
+                for startup in self.states.startup_state.widget_states.values_mut() {
+                    startup.force_data_update();
+                }
+
+                if enabled {
+                    format!("Enabled {unit}")
+                } else {
+                    format!("Disabled {unit}")
+                }
+            }
+            Err(err) => format!("Couldn't change {unit}: {err}"),
+        };
+        self.flash = Some(Flash::new(message));
+    }
+
+    /// Handles the terminal being resized to `width` by `height`, which
+    /// redraws everything and warns if it's now too small for the widgets to
+    /// fit well.
+    pub fn on_resize(&mut self, width: u16, height: u16) {
+        self.is_force_redraw = true;
+        self.dirty_widgets.mark_all();
+
+        let is_too_small =
+            width < constants::MIN_TERMINAL_WIDTH || height < constants::MIN_TERMINAL_HEIGHT;
+        if is_too_small && !self.is_terminal_too_small {
+            let message = format!(
+                "The terminal is {width}x{height}, smaller than {}x{}, so some widgets may be cut off",
+                constants::MIN_TERMINAL_WIDTH,
+                constants::MIN_TERMINAL_HEIGHT
+            );
+            self.event_log.push(LogLevel::Warn, message.clone());
+            self.flash = Some(Flash::new(message));
+        }
+        self.is_terminal_too_small = is_too_small;
+    }
+
+    /// Toggles whether the selected CPU or memory graph shows how fast usage
+    /// changes, rather than the usage itself.
+    pub fn toggle_delta_mode(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => {
+                let graph_id = match self.current_widget.widget_type {
+                    BottomWidgetType::CpuLegend => widget_id - 1,
+                    _ => widget_id,
+                };
+                if let Some(cpu_widget_state) =
+                    self.states.cpu_state.widget_states.get_mut(&graph_id)
+                {
+                    cpu_widget_state.toggle_delta();
+                    self.is_force_redraw = true;
+                }
+            }
+            BottomWidgetType::Mem => {
+                if let Some(mem_widget_state) =
+                    self.states.mem_state.widget_states.get_mut(&widget_id)
+                {
+                    mem_widget_state.toggle_delta();
+                    self.is_force_redraw = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Toggles whether the selected CPU, memory, or network graph shows the
+    /// stats of what it's showing.
+    pub fn toggle_graph_stats(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => {
+                let graph_id = match self.current_widget.widget_type {
+                    BottomWidgetType::CpuLegend => widget_id - 1,
+                    _ => widget_id,
+                };
+                if let Some(cpu_widget_state) =
+                    self.states.cpu_state.widget_states.get_mut(&graph_id)
+                {
+                    cpu_widget_state.toggle_stats();
+                    cpu_widget_state.update_table(&self.converted_data.cpu_data);
+                    self.is_force_redraw = true;
+                }
+            }
+            BottomWidgetType::Mem => {
+                if let Some(mem_widget_state) =
+                    self.states.mem_state.widget_states.get_mut(&widget_id)
+                {
+                    mem_widget_state.toggle_stats();
+                    self.is_force_redraw = true;
+                }
+            }
+            BottomWidgetType::Net => {
+                if let Some(net_widget_state) =
+                    self.states.net_state.widget_states.get_mut(&widget_id)
+                {
+                    net_widget_state.toggle_stats();
                     self.is_force_redraw = true;
                 }
             }
+            _ => {}
         }
     }
 
@@ -624,7 +1369,15 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if let Some(editor) = &mut self.threshold_editor {
+            editor.backspace();
+        } else if self.meminfo.is_filtering {
+            self.meminfo.pop_filter();
+            self.is_force_redraw = true;
+        } else if let Some(panel) = self.filtering_smaps_panel() {
+            panel.pop_filter();
+            self.is_force_redraw = true;
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
                 .states
@@ -705,6 +1458,16 @@ impl App {
             self.decrement_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_up(1);
+        } else if self.system_summary.is_showing {
+            self.system_summary.scroll_up(1);
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_up(1);
+        } else if let Some(context_menu) = &mut self.context_menu {
+            context_menu.select_previous();
+        } else if let Some(editor) = &mut self.threshold_editor {
+            editor.toggle_field();
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_right_key();
@@ -726,6 +1489,16 @@ impl App {
             self.increment_position_count();
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_down(1);
+        } else if self.system_summary.is_showing {
+            self.system_summary.scroll_down(1);
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_down(1);
+        } else if let Some(context_menu) = &mut self.context_menu {
+            context_menu.select_next();
+        } else if let Some(editor) = &mut self.threshold_editor {
+            editor.toggle_field();
         } else if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_os = "windows")]
             self.on_left_key();
@@ -745,6 +1518,28 @@ impl App {
     pub fn on_left_key(&mut self) {
         if !self.is_in_dialog() {
             match self.current_widget.widget_type {
+                BottomWidgetType::Net => {
+                    let interfaces = self.nic_stats_interfaces();
+                    if let Some(net_widget_state) = self
+                        .states
+                        .net_state
+                        .widget_states
+                        .get_mut(&self.current_widget.widget_id)
+                    {
+                        net_widget_state.switch_nic_stats_interface(&interfaces, false);
+                    }
+                }
+                BottomWidgetType::Proc => {
+                    if let Some(proc_widget_state) = self
+                        .states
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if proc_widget_state.table.scroll_columns(-1) {
+                            proc_widget_state.force_rerender = true;
+                        }
+                    }
+                }
                 BottomWidgetType::ProcSearch => {
                     let is_in_search_widget = self.is_in_search_widget();
                     if let Some(proc_widget_state) = self
@@ -775,6 +1570,16 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::LogTail => {
+                    if let Some(log_tail_widget_state) = self
+                        .states
+                        .log_tail_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        let tab = log_tail_widget_state.selected_tab.saturating_sub(1);
+                        log_tail_widget_state.select_tab(tab);
+                    }
+                }
                 _ => {}
             }
         } else if self.delete_dialog_state.is_showing_dd {
@@ -806,6 +1611,28 @@ impl App {
     pub fn on_right_key(&mut self) {
         if !self.is_in_dialog() {
             match self.current_widget.widget_type {
+                BottomWidgetType::Net => {
+                    let interfaces = self.nic_stats_interfaces();
+                    if let Some(net_widget_state) = self
+                        .states
+                        .net_state
+                        .widget_states
+                        .get_mut(&self.current_widget.widget_id)
+                    {
+                        net_widget_state.switch_nic_stats_interface(&interfaces, true);
+                    }
+                }
+                BottomWidgetType::Proc => {
+                    if let Some(proc_widget_state) = self
+                        .states
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if proc_widget_state.table.scroll_columns(1) {
+                            proc_widget_state.force_rerender = true;
+                        }
+                    }
+                }
                 BottomWidgetType::ProcSearch => {
                     let is_in_search_widget = self.is_in_search_widget();
                     if let Some(proc_widget_state) = self
@@ -839,6 +1666,19 @@ impl App {
                         }
                     }
                 }
+                BottomWidgetType::LogTail => {
+                    let num_tabs = self.log_tails.len();
+                    if let Some(log_tail_widget_state) = self
+                        .states
+                        .log_tail_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if log_tail_widget_state.selected_tab + 1 < num_tabs {
+                            let tab = log_tail_widget_state.selected_tab + 1;
+                            log_tail_widget_state.select_tab(tab);
+                        }
+                    }
+                }
                 _ => {}
             }
         } else if self.delete_dialog_state.is_showing_dd {
@@ -884,6 +1724,13 @@ impl App {
             let current = &mut self.help_dialog_state.scroll_state.current_scroll_index;
             let amount = self.help_dialog_state.height;
             *current = current.saturating_sub(amount);
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_up(self.event_log.height.into());
+        } else if self.system_summary.is_showing {
+            self.system_summary
+                .scroll_up(self.system_summary.height().into());
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_up(self.meminfo.height().into());
         } else if self.current_widget.widget_type.is_widget_table() {
             if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
                 &self.current_widget.top_left_corner,
@@ -894,6 +1741,12 @@ impl App {
                 let height = brc_y - tlc_y - 2 * border_offset - header_offset;
                 self.change_position_count(-(height as i64));
             }
+        } else if matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Exec | BottomWidgetType::LogTail
+        ) {
+            let height = self.text_widget_height();
+            self.change_position_count(-height);
         }
     }
 
@@ -912,6 +1765,13 @@ impl App {
             let amount = self.help_dialog_state.height;
 
             self.help_scroll_to_or_max(current + amount);
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_down(self.event_log.height.into());
+        } else if self.system_summary.is_showing {
+            self.system_summary
+                .scroll_down(self.system_summary.height().into());
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_down(self.meminfo.height().into());
         } else if self.current_widget.widget_type.is_widget_table() {
             if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
                 &self.current_widget.top_left_corner,
@@ -922,6 +1782,12 @@ impl App {
                 let height = brc_y - tlc_y - 2 * border_offset - header_offset;
                 self.change_position_count(height as i64);
             }
+        } else if matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Exec | BottomWidgetType::LogTail
+        ) {
+            let height = self.text_widget_height();
+            self.change_position_count(height);
         }
     }
 
@@ -931,6 +1797,13 @@ impl App {
             let amount = self.help_dialog_state.height / 2;
 
             *current = current.saturating_sub(amount);
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_up((self.event_log.height / 2).into());
+        } else if self.system_summary.is_showing {
+            self.system_summary
+                .scroll_up((self.system_summary.height() / 2).into());
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_up((self.meminfo.height() / 2).into());
         } else if self.current_widget.widget_type.is_widget_table() {
             if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
                 &self.current_widget.top_left_corner,
@@ -941,6 +1814,12 @@ impl App {
                 let height = brc_y - tlc_y - 2 * border_offset - header_offset;
                 self.change_position_count(-(height as i64) / 2);
             }
+        } else if matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Exec | BottomWidgetType::LogTail
+        ) {
+            let height = self.text_widget_height();
+            self.change_position_count(-height / 2);
         }
     }
 
@@ -950,6 +1829,14 @@ impl App {
             let amount = self.help_dialog_state.height / 2;
 
             self.help_scroll_to_or_max(current + amount);
+        } else if self.event_log.is_showing {
+            self.event_log
+                .scroll_down((self.event_log.height / 2).into());
+        } else if self.system_summary.is_showing {
+            self.system_summary
+                .scroll_down((self.system_summary.height() / 2).into());
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_down((self.meminfo.height() / 2).into());
         } else if self.current_widget.widget_type.is_widget_table() {
             if let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) = (
                 &self.current_widget.top_left_corner,
@@ -960,6 +1847,12 @@ impl App {
                 let height = brc_y - tlc_y - 2 * border_offset - header_offset;
                 self.change_position_count(height as i64 / 2);
             }
+        } else if matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Exec | BottomWidgetType::LogTail
+        ) {
+            let height = self.text_widget_height();
+            self.change_position_count(height / 2);
         }
     }
 
@@ -1121,6 +2014,59 @@ impl App {
         // FIXME: This should handle errors.
     }
 
+    /// Opens the flame graph overlay for the selected process. Sampling is
+    /// started by the main loop, which has the event sender.
+    #[cfg(feature = "flame")]
+    fn start_flame_graph(&mut self) {
+        // Stacks can only be sampled from processes on this machine.
+        if self.replay.is_some() || self.multi_host.is_some() {
+            return;
+        }
+
+        if let Some(current) = self
+            .states
+            .proc_state
+            .get_widget_state(self.current_widget.widget_id)
+            .and_then(|pws| pws.table.current_item())
+        {
+            self.flame_graph = Some(flamegraph::FlameGraphState::new(
+                current.pid,
+                current.id.to_string(),
+            ));
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Switches the selected process widget between showing CPU usage per
+    /// core and of all cores.
+    fn toggle_cpu_per_core(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .states
+                .proc_state
+                .widget_states
+                .get_mut(&self.current_widget.widget_id)
+            {
+                proc_widget_state.toggle_cpu_per_core();
+            }
+        }
+    }
+
+    /// Whether the overlay with the result of `--disk_bench` is shown.
+    pub fn is_showing_disk_bench_result(&self) -> bool {
+        self.disk_bench
+            .as_ref()
+            .is_some_and(|disk_bench| disk_bench.is_showing_result)
+    }
+
+    /// Opens or closes the overlay with every field of `/proc/meminfo`.
+    pub fn toggle_meminfo(&mut self) {
+        if self.meminfo.is_showing || !self.is_in_dialog() {
+            self.meminfo.toggle();
+            self.is_force_redraw = true;
+        }
+    }
+
     pub fn on_char_key(&mut self, caught_char: char) {
         // Skip control code chars
         if caught_char.is_control() {
@@ -1139,6 +2085,12 @@ impl App {
             }
             self.last_key_press = current_key_press_inst;
 
+            if let Some(panel) = self.filtering_smaps_panel() {
+                panel.push_filter(caught_char);
+                self.is_force_redraw = true;
+                return;
+            }
+
             if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
                 let is_in_search_widget = self.is_in_search_widget();
                 if let Some(proc_widget_state) = self
@@ -1191,6 +2143,38 @@ impl App {
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
                 _ => {}
             }
+        } else if let Some(editor) = &mut self.threshold_editor {
+            editor.insert(caught_char);
+        } else if self.event_log.is_showing {
+            match caught_char {
+                'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
+                'E' => {
+                    self.event_log.toggle();
+                    self.is_force_redraw = true;
+                }
+                _ => {}
+            }
+        } else if self.system_summary.is_showing {
+            match caught_char {
+                'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
+                'y' => self.copy_to_clipboard(self.system_summary.text()),
+                'i' => {
+                    self.system_summary.toggle();
+                    self.is_force_redraw = true;
+                }
+                _ => {}
+            }
+        } else if self.meminfo.is_showing {
+            if self.meminfo.is_filtering {
+                self.meminfo.push_filter(caught_char);
+            } else {
+                match caught_char {
+                    'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
+                    '/' => self.meminfo.set_filtering(true),
+                    _ => {}
+                }
+            }
+            self.is_force_redraw = true;
         } else if self.delete_dialog_state.is_showing_dd {
             match caught_char {
                 'h' => self.on_left_key(),
@@ -1226,6 +2210,11 @@ impl App {
     // FIXME: Refactor this system...
     fn handle_char(&mut self, caught_char: char) {
         match caught_char {
+            '1'..='9' => {
+                if let Some(digit) = caught_char.to_digit(10) {
+                    self.switch_column_preset(digit as usize - 1);
+                }
+            }
             '/' => {
                 self.on_slash();
             }
@@ -1275,6 +2264,7 @@ impl App {
             'j' => self.on_down_key(),
             'f' => {
                 self.frozen_state.toggle(&self.data_collection); // TODO: Thawing should force a full data refresh and redraw immediately.
+                self.freeze_alert = None;
             }
             'c' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
@@ -1332,6 +2322,28 @@ impl App {
                     }
                 }
             }
+            'T' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if let Some(proc_widget_state) = self
+                        .states
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_widget_state.toggle_highlight_top();
+                    }
+                }
+            }
+            'V' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if let Some(proc_widget_state) = self
+                        .states
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_widget_state.toggle_change_sort();
+                    }
+                }
+            }
             'n' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
@@ -1377,6 +2389,33 @@ impl App {
                 self.help_dialog_state.is_showing_help = true;
                 self.is_force_redraw = true;
             }
+            '>' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.threshold_editor = Some(ThresholdEditor::new(
+                        self.process_cpu_threshold,
+                        self.process_mem_threshold,
+                    ));
+                    self.is_force_redraw = true;
+                }
+            }
+            'E' => {
+                self.event_log.toggle();
+                self.is_force_redraw = true;
+            }
+            'i' => {
+                self.system_summary.toggle();
+                self.is_force_redraw = true;
+            }
+            '[' => {
+                if let Some(multi_host) = &mut self.multi_host {
+                    multi_host.focus_prev();
+                }
+            }
+            ']' => {
+                if let Some(multi_host) = &mut self.multi_host {
+                    multi_host.focus_next();
+                }
+            }
             'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
             'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
@@ -1403,6 +2442,8 @@ impl App {
             '-' => self.on_minus(),
             '=' => self.reset_zoom(),
             'e' => self.toggle_expand_widget(),
+            'v' => self.switch_layout(),
+            'y' => self.copy_selected_value(false),
             's' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     self.toggle_sort_menu()
@@ -1416,8 +2457,52 @@ impl App {
                     self.is_force_redraw = true;
                 }
             }
+            'b' => {
+                // Memory maps are read from this machine, like threads.
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if self.replay.is_none() && self.multi_host.is_none() {
+                        if let Some(proc_widget_state) = self
+                            .states
+                            .proc_state
+                            .get_mut_widget_state(self.current_widget.widget_id)
+                        {
+                            proc_widget_state.toggle_smaps_panel();
+                            self.is_force_redraw = true;
+                        }
+                    }
+                }
+            }
+            'x' => {
+                // Security features are read from this machine, like threads.
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if self.replay.is_none() && self.multi_host.is_none() {
+                        if let Some(proc_widget_state) = self
+                            .states
+                            .proc_state
+                            .get_mut_widget_state(self.current_widget.widget_id)
+                        {
+                            proc_widget_state.toggle_security_panel();
+                            self.is_force_redraw = true;
+                        }
+                    }
+                }
+            }
             'u' => {
-                if let Some(disk) = self
+                // Limits are read from this machine, like threads.
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if self.replay.is_none() && self.multi_host.is_none() {
+                        if let Some(proc_widget_state) = self
+                            .states
+                            .proc_state
+                            .get_mut_widget_state(self.current_widget.widget_id)
+                        {
+                            proc_widget_state.toggle_rlimit_panel();
+                            self.is_force_redraw = true;
+                        }
+                    }
+                } else if let BottomWidgetType::Temp = self.current_widget.widget_type {
+                    self.cycle_temperature_type();
+                } else if let Some(disk) = self
                     .states
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
@@ -1443,8 +2528,19 @@ impl App {
                     disk.set_index(7);
                 }
             }
+            #[cfg(feature = "flame")]
+            'F' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.start_flame_graph();
+                }
+            }
             'I' => self.invert_sort(),
             '%' => self.toggle_percentages(),
+            'o' => self.toggle_cpu_per_core(),
+            'U' => self.cycle_network_units(),
+            'N' => self.toggle_network_per_interface(),
+            'R' => self.toggle_delta_mode(),
+            'a' => self.toggle_graph_stats(),
             _ => {}
         }
 
@@ -1494,6 +2590,16 @@ impl App {
         }
     }
 
+    /// Switches between the minimal and full layouts, if running with
+    /// `--minimal`.
+    fn switch_layout(&mut self) {
+        if self.alternate_layout.is_some() && !self.ignore_normal_keybinds() {
+            self.is_switching_layout = true;
+            self.is_expanded = false;
+            self.is_force_redraw = true;
+        }
+    }
+
     fn expand_widget(&mut self) {
         // TODO: [BASIC] Expansion in basic mode.
         if !self.ignore_normal_keybinds() && !self.app_config_fields.use_basic_mode {
@@ -2008,6 +3114,24 @@ impl App {
                         disk_widget_state.table.scroll_to_first();
                     }
                 }
+                BottomWidgetType::Startup => {
+                    if let Some(startup_widget_state) = self
+                        .states
+                        .startup_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        startup_widget_state.table.scroll_to_first();
+                    }
+                }
+                BottomWidgetType::UserProcs => {
+                    if let Some(user_proc_widget_state) = self
+                        .states
+                        .user_proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        user_proc_widget_state.table.scroll_to_first();
+                    }
+                }
                 BottomWidgetType::CpuLegend => {
                     if let Some(cpu_widget_state) = self
                         .states
@@ -2017,12 +3141,40 @@ impl App {
                         cpu_widget_state.table.scroll_to_first();
                     }
                 }
+                BottomWidgetType::Exec => {
+                    if let Some(exec_widget_state) = self
+                        .states
+                        .exec_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        exec_widget_state.scroll_to_first();
+                    }
+                }
+                BottomWidgetType::LogTail => {
+                    if let Some(log_tail_widget_state) = self
+                        .states
+                        .log_tail_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        let num_lines = self
+                            .log_tails
+                            .get(log_tail_widget_state.selected_tab)
+                            .map_or(0, |log_tail| log_tail.lines.len());
+                        log_tail_widget_state.scroll_to_first(num_lines);
+                    }
+                }
 
                 _ => {}
             }
             self.reset_multi_tap_keys();
         } else if self.help_dialog_state.is_showing_help {
             self.help_dialog_state.scroll_state.current_scroll_index = 0;
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_to_oldest();
+        } else if self.system_summary.is_showing {
+            self.system_summary.scroll_to_top();
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_to_top();
         } else if self.delete_dialog_state.is_showing_dd {
             self.delete_dialog_state.selected_signal = KillSignal::Cancel;
         }
@@ -2052,20 +3204,42 @@ impl App {
                 BottomWidgetType::Temp => {
                     if let Some(temp_widget_state) = self
                         .states
-                        .temp_state
+                        .temp_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        temp_widget_state.table.scroll_to_last();
+                    }
+                }
+                BottomWidgetType::Disk => {
+                    if let Some(disk_widget_state) = self
+                        .states
+                        .disk_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        if !self.converted_data.disk_data.is_empty() {
+                            disk_widget_state.table.scroll_to_last();
+                        }
+                    }
+                }
+                BottomWidgetType::Startup => {
+                    if let Some(startup_widget_state) = self
+                        .states
+                        .startup_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        temp_widget_state.table.scroll_to_last();
+                        if !self.converted_data.startup_data.is_empty() {
+                            startup_widget_state.table.scroll_to_last();
+                        }
                     }
                 }
-                BottomWidgetType::Disk => {
-                    if let Some(disk_widget_state) = self
+                BottomWidgetType::UserProcs => {
+                    if let Some(user_proc_widget_state) = self
                         .states
-                        .disk_state
+                        .user_proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        if !self.converted_data.disk_data.is_empty() {
-                            disk_widget_state.table.scroll_to_last();
+                        if !self.converted_data.user_proc_data.is_empty() {
+                            user_proc_widget_state.table.scroll_to_last();
                         }
                     }
                 }
@@ -2078,12 +3252,37 @@ impl App {
                         cpu_widget_state.table.scroll_to_last();
                     }
                 }
+                BottomWidgetType::Exec => {
+                    let num_lines = self.exec_output.as_ref().map_or(0, ExecOutput::num_lines);
+                    if let Some(exec_widget_state) = self
+                        .states
+                        .exec_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        exec_widget_state.scroll_to_last(num_lines);
+                    }
+                }
+                BottomWidgetType::LogTail => {
+                    if let Some(log_tail_widget_state) = self
+                        .states
+                        .log_tail_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        log_tail_widget_state.scroll_to_last();
+                    }
+                }
                 _ => {}
             }
             self.reset_multi_tap_keys();
         } else if self.help_dialog_state.is_showing_help {
             self.help_dialog_state.scroll_state.current_scroll_index =
                 self.help_dialog_state.scroll_state.max_scroll_index;
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_to_newest();
+        } else if self.system_summary.is_showing {
+            self.system_summary.scroll_to_bottom();
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_to_bottom();
         } else if self.delete_dialog_state.is_showing_dd {
             self.delete_dialog_state.selected_signal = KillSignal::Kill(MAX_PROCESS_SIGNAL);
         }
@@ -2097,42 +3296,133 @@ impl App {
         self.change_position_count(1);
     }
 
-    fn change_position_count(&mut self, amount: i64) {
-        if !self.ignore_normal_keybinds() {
-            match self.current_widget.widget_type {
-                BottomWidgetType::Proc => {
-                    self.change_process_position(amount);
+    /// Returns the new position, if it changed.
+    fn change_position_count(&mut self, amount: i64) -> Option<usize> {
+        if self.ignore_normal_keybinds() {
+            return None;
+        }
+
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self.change_process_position(amount),
+            BottomWidgetType::ProcSort => self.change_process_sort_position(amount),
+            BottomWidgetType::Temp => self.change_temp_position(amount),
+            BottomWidgetType::Disk => self.change_disk_position(amount),
+            BottomWidgetType::Startup => self.change_startup_position(amount),
+            BottomWidgetType::UserProcs => self.change_user_proc_position(amount),
+            BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
+            BottomWidgetType::Exec => self.change_exec_position(amount),
+            BottomWidgetType::LogTail => self.change_log_tail_position(amount),
+            BottomWidgetType::Net => self.change_nic_stats_position(amount),
+            _ => None,
+        }
+    }
+
+    /// Scrolls a table by the given amount, or adds to its momentum if that's
+    /// enabled.
+    fn scroll_table(&mut self, amount: i64) {
+        match &mut self.scroll_momentum {
+            Some(momentum) => momentum.push(amount as f32, Instant::now()),
+            None => {
+                self.change_position_count(amount);
+            }
+        }
+    }
+
+    /// Whether the heartbeat is shown in the status line. It isn't in quiet
+    /// mode, which has no status line, or when replaying or monitoring other
+    /// hosts, as there are no local updates then.
+    pub fn is_showing_heartbeat(&self) -> bool {
+        self.app_config_fields.show_heartbeat
+            && !self.app_config_fields.quiet_mode
+            && self.replay.is_none()
+            && self.multi_host.is_none()
+    }
+
+    /// Notes that an update was received, advancing the heartbeat.
+    pub fn on_update_received(&mut self) {
+        self.heartbeat_tick = self.heartbeat_tick.wrapping_add(1);
+        self.last_update_time = Instant::now();
+    }
+
+    /// How long can pass without an update before it's late.
+    fn late_update_limit(&self) -> Duration {
+        Duration::from_millis(self.app_config_fields.update_rate) * constants::LATE_UPDATE_PERIODS
+    }
+
+    /// How long it's been since the last update, if that's over
+    /// [`LATE_UPDATE_PERIODS`](constants::LATE_UPDATE_PERIODS) times the
+    /// update rate. Updates are never late when they're only collected on a
+    /// keypress.
+    pub fn late_update_age(&self) -> Option<Duration> {
+        let age = self.last_update_time.elapsed();
+
+        (!self.app_config_fields.update_on_key && age > self.late_update_limit()).then_some(age)
+    }
+
+    /// How long until the heartbeat has to be redrawn without an update, which
+    /// is when the update is late, or when its age reaches the next second if
+    /// it already is.
+    pub fn until_heartbeat_change(&self) -> Option<Duration> {
+        if !self.is_showing_heartbeat() || self.app_config_fields.update_on_key {
+            return None;
+        }
+
+        let age = self.last_update_time.elapsed();
+        let limit = self.late_update_limit();
+        Some(if age > limit {
+            Duration::from_secs(age.as_secs() + 1).saturating_sub(age)
+        } else {
+            // Go just past the limit, so it's late when checked.
+            limit - age + Duration::from_millis(1)
+        })
+    }
+
+    /// Moves the current table if its scroll momentum is due a tick. Returns
+    /// whether anything needs to be redrawn.
+    pub fn on_scroll_tick(&mut self) -> bool {
+        let Some(step) = self
+            .scroll_momentum
+            .as_mut()
+            .and_then(|momentum| momentum.tick(Instant::now()))
+        else {
+            return false;
+        };
+
+        // Move a row at a time so the position stops at either end of the list,
+        // rather than skipping the move entirely if it would go past it.
+        let mut has_moved = false;
+        for _ in 0..step.unsigned_abs() {
+            if self.change_position_count(step.signum()).is_none() {
+                if let Some(momentum) = &mut self.scroll_momentum {
+                    momentum.stop();
                 }
-                BottomWidgetType::ProcSort => self.change_process_sort_position(amount),
-                BottomWidgetType::Temp => self.change_temp_position(amount),
-                BottomWidgetType::Disk => self.change_disk_position(amount),
-                BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
-                _ => {}
+                break;
             }
+            has_moved = true;
         }
+
+        has_moved
     }
 
-    fn change_process_sort_position(&mut self, num_to_change_by: i64) {
-        if let Some(proc_widget_state) = self
-            .states
+    fn change_process_sort_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        self.states
             .proc_state
             .get_mut_widget_state(self.current_widget.widget_id - 2)
-        {
-            proc_widget_state
-                .sort_table
-                .increment_position(num_to_change_by);
-        }
+            .and_then(|proc_widget_state| {
+                proc_widget_state
+                    .sort_table
+                    .increment_position(num_to_change_by)
+            })
     }
 
-    fn change_cpu_legend_position(&mut self, num_to_change_by: i64) {
-        if let Some(cpu_widget_state) = self
-            .states
+    fn change_cpu_legend_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        self.states
             .cpu_state
             .widget_states
             .get_mut(&(self.current_widget.widget_id - 1))
-        {
-            cpu_widget_state.table.increment_position(num_to_change_by);
-        }
+            .and_then(|cpu_widget_state| {
+                cpu_widget_state.table.increment_position(num_to_change_by)
+            })
     }
 
     /// Returns the new position.
@@ -2142,34 +3432,137 @@ impl App {
             .proc_state
             .get_mut_widget_state(self.current_widget.widget_id)
         {
-            proc_widget_state.table.increment_position(num_to_change_by)
+            // While open, the limit and memory map panels are scrolled instead,
+            // as they're for the process that was selected.
+            if proc_widget_state.rlimit_panel.is_open() {
+                proc_widget_state
+                    .rlimit_panel
+                    .table
+                    .increment_position(num_to_change_by)
+            } else if proc_widget_state.smaps_panel.is_open() {
+                proc_widget_state
+                    .smaps_panel
+                    .table
+                    .increment_position(num_to_change_by)
+            } else {
+                proc_widget_state.table.increment_position(num_to_change_by)
+            }
         } else {
             None
         }
     }
 
-    fn change_temp_position(&mut self, num_to_change_by: i64) {
-        if let Some(temp_widget_state) = self
-            .states
+    fn change_temp_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        self.states
             .temp_state
             .widget_states
             .get_mut(&self.current_widget.widget_id)
-        {
-            temp_widget_state.table.increment_position(num_to_change_by);
-        }
+            .and_then(|temp_widget_state| {
+                temp_widget_state.table.increment_position(num_to_change_by)
+            })
     }
 
-    fn change_disk_position(&mut self, num_to_change_by: i64) {
-        if let Some(disk_widget_state) = self
-            .states
+    fn change_disk_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        self.states
             .disk_state
             .widget_states
             .get_mut(&self.current_widget.widget_id)
-        {
-            disk_widget_state.table.increment_position(num_to_change_by);
+            .and_then(|disk_widget_state| {
+                disk_widget_state.table.increment_position(num_to_change_by)
+            })
+    }
+
+    fn change_exec_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        let num_lines = self.exec_output.as_ref().map_or(0, ExecOutput::num_lines);
+        self.states
+            .exec_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+            .and_then(|exec_widget_state| exec_widget_state.scroll_by(num_to_change_by, num_lines))
+    }
+
+    fn change_nic_stats_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        self.states
+            .net_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+            .filter(|net_widget_state| net_widget_state.nic_stats_panel.is_open())
+            .and_then(|net_widget_state| {
+                net_widget_state
+                    .nic_stats_panel
+                    .table
+                    .increment_position(num_to_change_by)
+            })
+    }
+
+    /// The names of the interfaces whose hardware statistics can be shown.
+    fn nic_stats_interfaces(&self) -> Vec<String> {
+        self.data_collection
+            .network_harvest
+            .interfaces
+            .iter()
+            .map(|interface| interface.name.clone())
+            .collect()
+    }
+
+    fn change_log_tail_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        let log_tails = &self.log_tails;
+        self.states
+            .log_tail_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+            .and_then(|log_tail_widget_state| {
+                let num_lines = log_tails
+                    .get(log_tail_widget_state.selected_tab)
+                    .map_or(0, |log_tail| log_tail.lines.len());
+                log_tail_widget_state.scroll_by(num_to_change_by, num_lines)
+            })
+    }
+
+    /// The height of the current exec or log tail widget's text, for paging
+    /// through it.
+    fn text_widget_height(&self) -> i64 {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::LogTail => self
+                .states
+                .log_tail_state
+                .widget_states
+                .get(&widget_id)
+                .map_or(0, |log_tail_widget_state| {
+                    log_tail_widget_state.height.into()
+                }),
+            _ => self
+                .states
+                .exec_state
+                .widget_states
+                .get(&widget_id)
+                .map_or(0, |exec_widget_state| exec_widget_state.height.into()),
         }
     }
 
+    fn change_startup_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        self.states
+            .startup_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+            .and_then(|startup_widget_state| {
+                startup_widget_state
+                    .table
+                    .increment_position(num_to_change_by)
+            })
+    }
+
+    fn change_user_proc_position(&mut self, num_to_change_by: i64) -> Option<usize> {
+        self.states
+            .user_proc_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+            .and_then(|user_proc_widget_state| {
+                user_proc_widget_state
+                    .table
+                    .increment_position(num_to_change_by)
+            })
+    }
+
     fn help_scroll_up(&mut self) {
         if self.help_dialog_state.scroll_state.current_scroll_index > 0 {
             self.help_dialog_state.scroll_state.current_scroll_index -= 1;
@@ -2203,10 +3596,23 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_up(1);
+        } else if self.system_summary.is_showing {
+            self.system_summary.scroll_up(1);
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_up(1);
+        } else if let Some(context_menu) = &mut self.context_menu {
+            context_menu.select_previous();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_in();
         } else if self.current_widget.widget_type.is_widget_table() {
-            self.decrement_position_count();
+            self.scroll_table(-1);
+        } else if matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Exec | BottomWidgetType::LogTail
+        ) {
+            self.change_position_count(-1);
         }
     }
 
@@ -2220,10 +3626,23 @@ impl App {
         }
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.event_log.is_showing {
+            self.event_log.scroll_down(1);
+        } else if self.system_summary.is_showing {
+            self.system_summary.scroll_down(1);
+        } else if self.meminfo.is_showing {
+            self.meminfo.scroll_down(1);
+        } else if let Some(context_menu) = &mut self.context_menu {
+            context_menu.select_next();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_out();
         } else if self.current_widget.widget_type.is_widget_table() {
-            self.increment_position_count();
+            self.scroll_table(1);
+        } else if matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Exec | BottomWidgetType::LogTail
+        ) {
+            self.change_position_count(1);
         }
     }
 
@@ -2504,6 +3923,34 @@ impl App {
         // TODO: [MOUSE] double click functionality...?  We would do this above all
         // other actions and SC if needed.
 
+        // If the context menu is open, a click either picks one of its actions or
+        // closes it.
+        if let Some(context_menu) = self.context_menu.take() {
+            if let Some(index) = context_menu.action_at(x, y) {
+                self.run_context_menu_action(context_menu.items[index]);
+            } else if context_menu.contains(x, y) {
+                // Clicking on the border does nothing.
+                self.context_menu = Some(context_menu);
+                return;
+            }
+            self.is_force_redraw = true;
+            return;
+        }
+
+        // Clicking anywhere closes the load average graph, and clicking the load
+        // averages in the header opens it.
+        if self.is_showing_load_graph {
+            self.is_showing_load_graph = false;
+            self.is_force_redraw = true;
+            return;
+        } else if let Some(((tlc_x, tlc_y), (brc_x, brc_y))) = self.header_load_loc {
+            if !self.is_in_dialog() && (x >= tlc_x && y >= tlc_y) && (x < brc_x && y < brc_y) {
+                self.is_showing_load_graph = true;
+                self.is_force_redraw = true;
+                return;
+            }
+        }
+
         // Short circuit if we're in basic table... we might have to handle the basic
         // table arrow case here...
 
@@ -2644,7 +4091,9 @@ impl App {
                     | BottomWidgetType::ProcSort
                     | BottomWidgetType::CpuLegend
                     | BottomWidgetType::Temp
-                    | BottomWidgetType::Disk => {
+                    | BottomWidgetType::Disk
+                    | BottomWidgetType::Startup
+                    | BottomWidgetType::UserProcs => {
                         // Get our index...
                         let clicked_entry = y - *tlc_y;
                         let header_offset = self.header_offset(&self.current_widget);
@@ -2742,6 +4191,36 @@ impl App {
                                         }
                                     }
                                 }
+                                BottomWidgetType::Startup => {
+                                    if let Some(startup_widget_state) = self
+                                        .states
+                                        .startup_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            startup_widget_state.table.ratatui_selected()
+                                        {
+                                            self.change_startup_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::UserProcs => {
+                                    if let Some(user_proc_widget_state) = self
+                                        .states
+                                        .user_proc_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            user_proc_widget_state.table.ratatui_selected()
+                                        {
+                                            self.change_user_proc_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
                                 _ => {}
                             }
                         } else {
@@ -2783,6 +4262,29 @@ impl App {
                                             }
                                         }
                                     }
+                                    BottomWidgetType::Startup => {
+                                        if let Some(startup) = self
+                                            .states
+                                            .startup_state
+                                            .get_mut_widget_state(self.current_widget.widget_id)
+                                        {
+                                            if startup.table.try_select_location(x, y).is_some() {
+                                                startup.force_data_update();
+                                            }
+                                        }
+                                    }
+                                    BottomWidgetType::UserProcs => {
+                                        if let Some(user_procs) = self
+                                            .states
+                                            .user_proc_state
+                                            .get_mut_widget_state(self.current_widget.widget_id)
+                                        {
+                                            if user_procs.table.try_select_location(x, y).is_some()
+                                            {
+                                                user_procs.force_data_update();
+                                            }
+                                        }
+                                    }
                                     _ => (),
                                 }
                             }
@@ -2814,12 +4316,282 @@ impl App {
                             }
                         }
                     }
+                    BottomWidgetType::LogTail => {
+                        if let Some(log_tail_widget_state) = self
+                            .states
+                            .log_tail_state
+                            .get_mut_widget_state(self.current_widget.widget_id)
+                        {
+                            let clicked_tab = log_tail_widget_state
+                                .tab_click_locs
+                                .iter()
+                                .flatten()
+                                .position(|((tlc_x, tlc_y), (brc_x, brc_y))| {
+                                    (x >= *tlc_x && y >= *tlc_y) && (x <= *brc_x && y <= *brc_y)
+                                });
+                            if let Some(tab) = clicked_tab {
+                                log_tail_widget_state.select_tab(tab);
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
         }
     }
 
+    /// Selects the process row that was right-clicked and opens a context menu
+    /// for it. Right-clicking anywhere while the menu is open closes it.
+    pub fn on_right_mouse_up(&mut self, x: u16, y: u16) {
+        if self.context_menu.take().is_some() {
+            self.is_force_redraw = true;
+            return;
+        }
+
+        if self.is_in_dialog() {
+            return;
+        }
+
+        let Some(widget) = self
+            .widget_map
+            .values()
+            .find(|widget| {
+                matches!(widget.widget_type, BottomWidgetType::Proc)
+                    && matches!(
+                        (widget.top_left_corner, widget.bottom_right_corner),
+                        (Some((tlc_x, tlc_y)), Some((brc_x, brc_y)))
+                            if (x >= tlc_x && y >= tlc_y) && (x < brc_x && y < brc_y)
+                    )
+            })
+            .cloned()
+        else {
+            return;
+        };
+
+        let (Some((_tlc_x, tlc_y)), Some((_brc_x, brc_y))) =
+            (widget.top_left_corner, widget.bottom_right_corner)
+        else {
+            return;
+        };
+        let border_offset = u16::from(self.is_drawing_border());
+        let first_row = tlc_y + border_offset + self.header_offset(&widget);
+
+        // Ignore clicks on the header or the bottom border.
+        if y < first_row || y >= brc_y - border_offset {
+            return;
+        }
+
+        let widget_id = widget.widget_id;
+        self.current_widget = widget;
+
+        let Some(proc_widget_state) = self.states.proc_state.get_mut_widget_state(widget_id) else {
+            return;
+        };
+        let Some(visual_index) = proc_widget_state.table.ratatui_selected() else {
+            return;
+        };
+        let clicked_index = usize::from(y - first_row);
+        proc_widget_state
+            .table
+            .increment_position(clicked_index as i64 - visual_index as i64);
+
+        // Don't open the menu for clicks past the last row.
+        if proc_widget_state.table.ratatui_selected() != Some(clicked_index) {
+            self.is_force_redraw = true;
+            return;
+        }
+
+        let mut items = vec![ContextMenuAction::SendSignal];
+
+        // Like with Enter, threads are only available for processes on this
+        // machine, and not when grouping.
+        if self.replay.is_none()
+            && self.multi_host.is_none()
+            && !matches!(proc_widget_state.mode, ProcWidgetMode::Grouped)
+        {
+            items.push(ContextMenuAction::ViewThreads);
+        }
+        if !self.app_config_fields.no_clipboard {
+            items.extend([ContextMenuAction::CopyPid, ContextMenuAction::CopyName]);
+        }
+
+        self.context_menu = Some(ContextMenu::new(x, y, items));
+        self.is_force_redraw = true;
+    }
+
+    /// Runs an action picked from the context menu on the selected process.
+    fn run_context_menu_action(&mut self, action: ContextMenuAction) {
+        match action {
+            ContextMenuAction::SendSignal => self.start_killing_process(),
+            ContextMenuAction::ViewThreads => {
+                if let Some(proc_widget_state) = self
+                    .states
+                    .proc_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    if proc_widget_state.thread_panel.pid
+                        != proc_widget_state.table.current_item().map(|p| p.pid)
+                    {
+                        proc_widget_state.toggle_thread_panel();
+                    }
+                }
+            }
+            ContextMenuAction::CopyPid | ContextMenuAction::CopyName => {
+                if let Some(process) = self
+                    .states
+                    .proc_state
+                    .get_widget_state(self.current_widget.widget_id)
+                    .and_then(|proc_widget_state| proc_widget_state.table.current_item())
+                {
+                    let text = if action == ContextMenuAction::CopyPid {
+                        process.pid.to_string()
+                    } else {
+                        process.id.to_string()
+                    };
+                    self.copy_to_clipboard(text);
+                }
+            }
+        }
+    }
+
+    /// Copies the selected entry of the current widget to the clipboard. This
+    /// is the full command line for processes, or otherwise the entry's main
+    /// value. If `full_row` is set, all of the entry's cells are copied
+    /// instead, separated by tabs.
+    pub fn copy_selected_value(&mut self, full_row: bool) {
+        let widget_id = self.current_widget.widget_id;
+
+        let text =
+            match self.current_widget.widget_type {
+                BottomWidgetType::Proc => {
+                    self.states.proc_state.get_widget_state(widget_id).and_then(
+                        |proc_widget_state| {
+                            if full_row {
+                                return proc_widget_state.table.current_row_text();
+                            }
+
+                            let process = proc_widget_state.table.current_item()?;
+
+                            // Grouped entries don't have a single command line, and data from
+                            // remote hosts isn't kept locally.
+                            let command =
+                                if matches!(proc_widget_state.mode, ProcWidgetMode::Grouped)
+                                    || self.multi_host.is_some()
+                                {
+                                    None
+                                } else {
+                                    self.data_collection
+                                        .process_data
+                                        .process_harvest
+                                        .get(&process.pid)
+                                        .map(|harvest| harvest.command.clone())
+                                };
+
+                            Some(command.unwrap_or_else(|| process.id.to_string()))
+                        },
+                    )
+                }
+                BottomWidgetType::Temp => {
+                    self.states.temp_state.get_widget_state(widget_id).and_then(
+                        |temp_widget_state| {
+                            if full_row {
+                                temp_widget_state.table.current_row_text()
+                            } else {
+                                temp_widget_state
+                                    .table
+                                    .current_item()
+                                    .map(|sensor| sensor.temperature().to_string())
+                            }
+                        },
+                    )
+                }
+                BottomWidgetType::Disk => {
+                    self.states.disk_state.get_widget_state(widget_id).and_then(
+                        |disk_widget_state| {
+                            if full_row {
+                                disk_widget_state.table.current_row_text()
+                            } else {
+                                disk_widget_state
+                                    .table
+                                    .current_item()
+                                    .map(|disk| disk.name.to_string())
+                            }
+                        },
+                    )
+                }
+                BottomWidgetType::Startup => self
+                    .states
+                    .startup_state
+                    .get_widget_state(widget_id)
+                    .and_then(|startup_widget_state| {
+                        if full_row {
+                            startup_widget_state.table.current_row_text()
+                        } else {
+                            startup_widget_state
+                                .table
+                                .current_item()
+                                .map(|unit| unit.unit.to_string())
+                        }
+                    }),
+                BottomWidgetType::UserProcs => self
+                    .states
+                    .user_proc_state
+                    .get_widget_state(widget_id)
+                    .and_then(|user_proc_widget_state| {
+                        if full_row {
+                            user_proc_widget_state.table.current_row_text()
+                        } else {
+                            user_proc_widget_state
+                                .table
+                                .current_item()
+                                .map(|count| count.username.clone())
+                        }
+                    }),
+                BottomWidgetType::CpuLegend => self
+                    .states
+                    .cpu_state
+                    .get_widget_state(widget_id - 1)
+                    .and_then(|cpu_widget_state| {
+                        if full_row {
+                            cpu_widget_state.table.current_row_text()
+                        } else {
+                            match cpu_widget_state.table.current_item()? {
+                                CpuWidgetTableData::All => None,
+                                CpuWidgetTableData::Entry { last_entry, .. } => {
+                                    Some(format!("{last_entry:.1}%"))
+                                }
+                            }
+                        }
+                    }),
+                _ => None,
+            };
+
+        if let Some(text) = text {
+            self.copy_to_clipboard(text);
+        }
+    }
+
+    /// Copies text to the clipboard, unless disabled, and says so in the status
+    /// line. The text is also added to the event log in case the terminal
+    /// doesn't support setting the clipboard.
+    fn copy_to_clipboard(&mut self, text: String) {
+        if self.app_config_fields.no_clipboard {
+            self.flash = Some(Flash::new("Copying is disabled with --no_clipboard"));
+            return;
+        }
+
+        match clipboard::copy_to_clipboard(&text) {
+            Ok(()) => {
+                self.flash = Some(Flash::new("Copied!"));
+                self.event_log
+                    .push(LogLevel::Info, format!("Copied to the clipboard: {text}"));
+            }
+            Err(err) => {
+                self.flash = Some(Flash::new(format!("Failed to copy: {err}")));
+            }
+        }
+    }
+
     fn is_drawing_border(&self) -> bool {
         self.is_expanded || !self.app_config_fields.use_basic_mode
     }
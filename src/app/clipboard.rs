@@ -0,0 +1,67 @@
+//! Copying values to the clipboard, and the message shown after doing so.
+
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use crate::utils::general::base64;
+
+/// How long a [`Flash`] message is shown for.
+const FLASH_DURATION: Duration = Duration::from_secs(2);
+
+/// A message shown briefly in the status line, e.g. after copying something.
+#[derive(Debug)]
+pub struct Flash {
+    pub message: String,
+    shown_at: Instant,
+    duration: Duration,
+}
+
+impl Flash {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self::lasting(message, FLASH_DURATION)
+    }
+
+    /// A message that's shown for longer or shorter than usual.
+    pub fn lasting(message: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            message: message.into(),
+            shown_at: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Whether the message should still be shown.
+    pub fn is_showing(&self) -> bool {
+        self.shown_at.elapsed() < self.duration
+    }
+
+    /// How long until the message should be hidden.
+    pub fn until_hidden(&self) -> Duration {
+        self.duration.saturating_sub(self.shown_at.elapsed())
+    }
+}
+
+/// Copies text to the clipboard of the terminal using an OSC 52 escape
+/// sequence, so this also works over SSH. Terminals that don't support it
+/// just ignore it.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "{}", osc52(text))?;
+    stdout.flush()
+}
+
+fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encodes_osc52() {
+        assert_eq!(osc52("btm"), "\x1b]52;c;YnRt\x07");
+    }
+}
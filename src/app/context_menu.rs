@@ -0,0 +1,178 @@
+//! A popup menu of actions for a process, opened by right-clicking its row.
+
+use tui::layout::Rect;
+
+/// An action that can be picked from a [`ContextMenu`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuAction {
+    SendSignal,
+    ViewThreads,
+    CopyPid,
+    CopyName,
+}
+
+impl ContextMenuAction {
+    /// The text shown for the action in the menu.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContextMenuAction::SendSignal => "Send Signal…",
+            ContextMenuAction::ViewThreads => "View Threads",
+            ContextMenuAction::CopyPid => "Copy PID",
+            ContextMenuAction::CopyName => "Copy Name",
+        }
+    }
+}
+
+/// The state of an open context menu.
+#[derive(Debug)]
+pub struct ContextMenu {
+    /// The position of the click that opened the menu.
+    pub x: u16,
+    pub y: u16,
+    pub items: Vec<ContextMenuAction>,
+    pub selected: usize,
+    /// Where the menu was last drawn, for handling clicks on it.
+    pub draw_loc: Option<Rect>,
+}
+
+impl ContextMenu {
+    pub fn new(x: u16, y: u16, items: Vec<ContextMenuAction>) -> Self {
+        Self {
+            x,
+            y,
+            items,
+            selected: 0,
+            draw_loc: None,
+        }
+    }
+
+    /// The currently selected action.
+    pub fn selected_action(&self) -> Option<ContextMenuAction> {
+        self.items.get(self.selected).copied()
+    }
+
+    /// Selects the previous action, wrapping around to the last one.
+    pub fn select_previous(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+    }
+
+    /// Selects the next action, wrapping around to the first one.
+    pub fn select_next(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    /// Returns the action at the given position, if the menu was drawn there.
+    pub fn action_at(&self, x: u16, y: u16) -> Option<usize> {
+        let draw_loc = self.draw_loc?;
+        let inner_top = draw_loc.y + 1;
+
+        if x > draw_loc.x && x < draw_loc.right().saturating_sub(1) && y >= inner_top {
+            let index = usize::from(y - inner_top);
+            (index < self.items.len()).then_some(index)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the given position is on the menu, including its border.
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        self.draw_loc.is_some_and(|draw_loc| {
+            x >= draw_loc.x && x < draw_loc.right() && y >= draw_loc.y && y < draw_loc.bottom()
+        })
+    }
+
+    /// Where to draw the menu within `bounds`. It opens below and to the right
+    /// of the click, but flips up or to the left if it would otherwise go past
+    /// the edge.
+    pub fn area(&self, bounds: Rect) -> Rect {
+        let label_width = self
+            .items
+            .iter()
+            .map(|item| item.label().chars().count())
+            .max()
+            .unwrap_or_default();
+
+        // Leave room for the border and a space of padding on each side.
+        let width = (label_width as u16 + 4).min(bounds.width);
+        let height = (self.items.len() as u16 + 2).min(bounds.height);
+
+        let x = if self.x.saturating_add(width) <= bounds.right() {
+            self.x
+        } else {
+            (self.x + 1).saturating_sub(width)
+        };
+        let y = if self.y.saturating_add(height) <= bounds.bottom() {
+            self.y
+        } else {
+            (self.y + 1).saturating_sub(height)
+        };
+
+        Rect::new(
+            x.clamp(bounds.x, bounds.right() - width),
+            y.clamp(bounds.y, bounds.bottom() - height),
+            width,
+            height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn menu(x: u16, y: u16) -> ContextMenu {
+        ContextMenu::new(
+            x,
+            y,
+            vec![
+                ContextMenuAction::SendSignal,
+                ContextMenuAction::ViewThreads,
+                ContextMenuAction::CopyPid,
+                ContextMenuAction::CopyName,
+            ],
+        )
+    }
+
+    #[test]
+    fn area_flips_near_edges() {
+        let bounds = Rect::new(0, 0, 80, 24);
+
+        assert_eq!(menu(10, 5).area(bounds), Rect::new(10, 5, 16, 6));
+        assert_eq!(menu(75, 5).area(bounds), Rect::new(60, 5, 16, 6));
+        assert_eq!(menu(10, 22).area(bounds), Rect::new(10, 17, 16, 6));
+        assert_eq!(menu(79, 23).area(bounds), Rect::new(64, 18, 16, 6));
+
+        // Too small to fit the menu at all.
+        assert_eq!(
+            menu(3, 2).area(Rect::new(0, 0, 10, 4)),
+            Rect::new(0, 0, 10, 4)
+        );
+    }
+
+    #[test]
+    fn clicks_on_items() {
+        let mut menu = menu(10, 5);
+        assert_eq!(menu.action_at(11, 6), None);
+
+        menu.draw_loc = Some(menu.area(Rect::new(0, 0, 80, 24)));
+        assert_eq!(menu.action_at(11, 6), Some(0));
+        assert_eq!(menu.action_at(20, 9), Some(3));
+        assert_eq!(menu.action_at(10, 6), None);
+        assert_eq!(menu.action_at(11, 10), None);
+        assert!(menu.contains(10, 10));
+        assert!(!menu.contains(26, 5));
+    }
+
+    #[test]
+    fn selection_wraps() {
+        let mut menu = menu(0, 0);
+        menu.select_previous();
+        assert_eq!(menu.selected_action(), Some(ContextMenuAction::CopyName));
+        menu.select_next();
+        assert_eq!(menu.selected_action(), Some(ContextMenuAction::SendSignal));
+    }
+}
@@ -13,16 +13,26 @@
 //! memory usage and higher CPU usage - you will be trying to process more and
 //! more points as this is used!
 
-use std::{collections::BTreeMap, time::Instant, vec::Vec};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant, SystemTime},
+    vec::Vec,
+};
 
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 #[cfg(feature = "battery")]
 use crate::data_collection::batteries;
+#[cfg(feature = "gpu")]
+use crate::data_collection::gpu::GpuUsage;
+#[cfg(feature = "systemd")]
+use crate::data_collection::systemd;
 use crate::{
     data_collection::{
-        cpu, disks, memory, network,
-        processes::{Pid, ProcessHarvest},
+        cpu, disks,
+        error::{DataError, DataErrors, Subsystem},
+        memory, network,
+        processes::{Pid, PinnedProcess, ProcessHarvest},
         temperature, Data,
     },
     dec_bytes_per_second_string,
@@ -30,10 +40,22 @@ use crate::{
 
 pub type Value = f64;
 
+/// How many samples of per-process history are kept, e.g. for sparklines.
+pub const PROCESS_HISTORY_LEN: usize = 10;
+
+/// How many readings of each temperature sensor are kept, e.g. for sparklines.
+pub const TEMP_HISTORY_LEN: usize = 60;
+
+/// How many readings of the 1 minute load average are kept for the header's
+/// sparkline.
+pub const LOAD_AVG_HISTORY_LEN: usize = 60;
+
 #[derive(Debug, Default, Clone)]
 pub struct TimedData {
     pub rx_data: Value,
     pub tx_data: Value,
+    /// The RX and TX rates of each network interface, by name.
+    pub net_interface_data: Vec<(String, Value, Value)>,
     pub cpu_data: Vec<Value>,
     pub load_avg_data: [f32; 3],
     pub mem_data: Option<Value>,
@@ -56,10 +78,20 @@ pub struct ProcessData {
 
     /// PIDs corresponding to processes that have no parents.
     pub orphan_pids: Vec<Pid>,
+
+    /// The last [`PROCESS_HISTORY_LEN`] CPU usage percentages of each process.
+    pub cpu_history: HashMap<Pid, VecDeque<f64>>,
+
+    /// The last [`PROCESS_HISTORY_LEN`] memory usage values of each process,
+    /// in bytes.
+    pub mem_history: HashMap<Pid, VecDeque<u64>>,
+
+    /// When each process was last collected.
+    pub last_seen: HashMap<Pid, Instant>,
 }
 
 impl ProcessData {
-    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
+    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>, harvested_time: Instant) {
         self.process_parent_mapping.clear();
 
         // Reverse as otherwise the pid mappings are in the wrong order.
@@ -81,7 +113,57 @@ impl ProcessData {
             .map(|process| (process.pid, process))
             .collect();
         self.process_harvest = process_pid_map;
+        for pid in self.process_harvest.keys() {
+            self.last_seen.insert(*pid, harvested_time);
+        }
+
+        self.cpu_history
+            .retain(|pid, _| self.process_harvest.contains_key(pid));
+        self.mem_history
+            .retain(|pid, _| self.process_harvest.contains_key(pid));
+        for (pid, process) in &self.process_harvest {
+            push_history(
+                self.cpu_history.entry(*pid).or_default(),
+                process.cpu_usage_percent.into(),
+                PROCESS_HISTORY_LEN,
+            );
+            push_history(
+                self.mem_history.entry(*pid).or_default(),
+                process.mem_usage_bytes,
+                PROCESS_HISTORY_LEN,
+            );
+        }
+
+        self.update_orphan_pids();
+    }
 
+    /// Removes every process that wasn't collected within `threshold` of `now`,
+    /// along with its history. Processes are normally replaced as a whole by
+    /// each harvest, but this catches those left behind when a harvest arrives
+    /// without a process list, so they don't linger as zero-usage entries.
+    fn prune_stale_processes(&mut self, now: Instant, threshold: Duration) {
+        self.last_seen
+            .retain(|_, last_seen| now.saturating_duration_since(*last_seen) <= threshold);
+
+        let old_len = self.process_harvest.len();
+        self.process_harvest
+            .retain(|pid, _| self.last_seen.contains_key(pid));
+        if self.process_harvest.len() == old_len {
+            return;
+        }
+
+        self.cpu_history
+            .retain(|pid, _| self.process_harvest.contains_key(pid));
+        self.mem_history
+            .retain(|pid, _| self.process_harvest.contains_key(pid));
+        self.process_parent_mapping.retain(|pid, children| {
+            children.retain(|child| self.process_harvest.contains_key(child));
+            self.process_harvest.contains_key(pid)
+        });
+        self.update_orphan_pids();
+    }
+
+    fn update_orphan_pids(&mut self) {
         // We collect all processes that either:
         // - Do not have a parent PID (that is, they are orphan processes)
         // - Have a parent PID but we don't have the parent (we promote them as orphans)
@@ -96,6 +178,14 @@ impl ProcessData {
     }
 }
 
+/// Adds a sample to a history, dropping the oldest one if it's full.
+fn push_history<T>(history: &mut VecDeque<T>, value: T, max_len: usize) {
+    if history.len() >= max_len {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
 /// AppCollection represents the pooled data stored within the main app
 /// thread.  Basically stores a (occasionally cleaned) record of the data
 /// collected, and what is needed to convert into a displayable form.
@@ -109,68 +199,159 @@ impl ProcessData {
 #[derive(Debug, Clone)]
 pub struct DataCollection {
     pub current_instant: Instant,
+
+    /// The wall-clock time of [`Self::current_instant`].
+    pub current_time: SystemTime,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
     pub network_harvest: network::NetworkHarvest,
+    /// How much of the kernel's socket pools is in use.
+    pub sockstat: Option<network::SockstatSummary>,
     pub memory_harvest: memory::MemHarvest,
     #[cfg(not(target_os = "windows"))]
     pub cache_harvest: memory::MemHarvest,
     pub swap_harvest: memory::MemHarvest,
+    pub swap_devices: Vec<memory::SwapDevice>,
+    pub swap_io: Option<memory::SwapIOData>,
+    /// The memory limits of the cgroup bottom runs in, if RAM is shown against
+    /// them.
+    pub cgroup_memory: Option<memory::CgroupMemoryLimits>,
+    /// How fragmented the free memory of each zone is.
+    pub buddy_zones: Vec<memory::BuddyZone>,
+    /// The total proportional set size of every readable process, in bytes.
+    pub total_pss: Option<u64>,
     pub cpu_harvest: cpu::CpuHarvest,
+    pub cpu_times_harvest: Option<cpu::CpuTimesHarvest>,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
+    /// The last [`LOAD_AVG_HISTORY_LEN`] readings of the 1 minute load average.
+    pub load_avg_history: VecDeque<f64>,
+    pub sched_tasks_harvest: Option<cpu::SchedTasksHarvest>,
+    /// The processes that can only run on some of the cores.
+    pub pinned_processes: Vec<PinnedProcess>,
     pub process_data: ProcessData,
+    /// How many cores the CPU usage of processes is shared between, if known.
+    pub num_cpus: Option<usize>,
+    /// How many inotify watches each user can have, if it's been read.
+    pub max_inotify_watches: Option<u32>,
     pub disk_harvest: Vec<disks::DiskHarvest>,
+    /// How worn out the SSD each disk is on is, by disk name. This is only read
+    /// every so often, so it's kept until the next read.
+    pub disk_endurance: HashMap<String, disks::SmartEndurance>,
     pub io_harvest: disks::IoHarvest,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
     pub io_labels: Vec<(String, String)>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
+    /// Maps each core with a temperature sensor of its own to the index of
+    /// that sensor in `temp_harvest`.
+    pub core_temp_sensors: HashMap<usize, usize>,
+    /// The last [`TEMP_HISTORY_LEN`] readings of each temperature sensor and
+    /// when they were taken, keyed by the sensor's name.
+    pub temp_history: HashMap<String, VecDeque<(Instant, Value)>>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryHarvest>,
     #[cfg(feature = "zfs")]
     pub arc_harvest: memory::MemHarvest,
     #[cfg(feature = "gpu")]
     pub gpu_harvest: Vec<(String, memory::MemHarvest)>,
+    /// The usage of each GPU in `gpu_harvest`, in the same order.
+    #[cfg(feature = "gpu")]
+    pub gpu_usage: Vec<GpuUsage>,
+    /// The user units started at login. These are only read every so often,
+    /// so they're kept until the next read.
+    #[cfg(feature = "systemd")]
+    pub user_units: Vec<systemd::UserUnit>,
+    /// The subsystems that couldn't be read in the last collection.
+    pub collection_errors: Vec<DataError>,
 }
 
 impl Default for DataCollection {
     fn default() -> Self {
         DataCollection {
             current_instant: Instant::now(),
+            current_time: SystemTime::now(),
             timed_data_vec: Vec::default(),
             network_harvest: network::NetworkHarvest::default(),
+            sockstat: None,
             memory_harvest: memory::MemHarvest::default(),
             #[cfg(not(target_os = "windows"))]
             cache_harvest: memory::MemHarvest::default(),
             swap_harvest: memory::MemHarvest::default(),
+            swap_devices: Vec::default(),
+            swap_io: None,
+            cgroup_memory: None,
+            buddy_zones: Vec::default(),
+            total_pss: None,
             cpu_harvest: cpu::CpuHarvest::default(),
+            cpu_times_harvest: None,
             load_avg_harvest: cpu::LoadAvgHarvest::default(),
+            load_avg_history: VecDeque::default(),
+            sched_tasks_harvest: None,
+            pinned_processes: Vec::default(),
             process_data: Default::default(),
+            num_cpus: None,
+            max_inotify_watches: None,
             disk_harvest: Vec::default(),
+            disk_endurance: HashMap::default(),
             io_harvest: disks::IoHarvest::default(),
             io_labels_and_prev: Vec::default(),
             io_labels: Vec::default(),
             temp_harvest: Vec::default(),
+            core_temp_sensors: HashMap::default(),
+            temp_history: HashMap::default(),
             #[cfg(feature = "battery")]
             battery_harvest: Vec::default(),
             #[cfg(feature = "zfs")]
             arc_harvest: memory::MemHarvest::default(),
             #[cfg(feature = "gpu")]
             gpu_harvest: Vec::default(),
+            #[cfg(feature = "gpu")]
+            gpu_usage: Vec::default(),
+            #[cfg(feature = "systemd")]
+            user_units: Vec::default(),
+            collection_errors: Vec::default(),
         }
     }
 }
 
 impl DataCollection {
+    /// The error from the last collection of any of the given subsystems.
+    pub fn collection_error(&self, subsystems: &[Subsystem]) -> Option<&DataError> {
+        self.collection_errors
+            .iter()
+            .find(|error| subsystems.contains(&error.subsystem))
+    }
+
+    /// How many times in a row each kind of data has failed to be read.
+    pub fn error_counts(&self) -> DataErrors {
+        DataErrors::new(&self.collection_errors)
+    }
+
     pub fn reset(&mut self) {
         self.timed_data_vec = Vec::default();
         self.network_harvest = network::NetworkHarvest::default();
+        self.sockstat = None;
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
+        self.swap_devices = Vec::default();
+        self.swap_io = None;
+        self.cgroup_memory = None;
+        self.buddy_zones = Vec::default();
+        self.total_pss = None;
         self.cpu_harvest = cpu::CpuHarvest::default();
+        self.cpu_times_harvest = None;
+        self.load_avg_history = VecDeque::default();
+        self.sched_tasks_harvest = None;
+        self.pinned_processes = Vec::default();
         self.process_data = Default::default();
+        self.num_cpus = None;
+        self.max_inotify_watches = None;
         self.disk_harvest = Vec::default();
+        self.disk_endurance = HashMap::default();
         self.io_harvest = disks::IoHarvest::default();
         self.io_labels_and_prev = Vec::default();
         self.temp_harvest = Vec::default();
+        self.core_temp_sensors = HashMap::default();
+        self.temp_history = HashMap::default();
+        self.collection_errors = Vec::default();
         #[cfg(feature = "battery")]
         {
             self.battery_harvest = Vec::default();
@@ -182,6 +363,11 @@ impl DataCollection {
         #[cfg(feature = "gpu")]
         {
             self.gpu_harvest = Vec::default();
+            self.gpu_usage = Vec::default();
+        }
+        #[cfg(feature = "systemd")]
+        {
+            self.user_units = Vec::default();
         }
     }
 
@@ -218,9 +404,30 @@ impl DataCollection {
             self.eat_network(network, &mut new_entry);
         }
 
+        if let Some(sockstat) = harvested_data.sockstat {
+            self.sockstat = Some(sockstat);
+        }
+
         // Memory, Swap
         if let (Some(memory), Some(swap)) = (harvested_data.memory, harvested_data.swap) {
             self.eat_memory_and_swap(memory, swap, &mut new_entry);
+            self.cgroup_memory = harvested_data.cgroup_memory;
+        }
+
+        if let Some(swap_devices) = harvested_data.swap_devices {
+            self.swap_devices = swap_devices;
+        }
+
+        if let Some(buddy_zones) = harvested_data.buddy_zones {
+            self.buddy_zones = buddy_zones;
+        }
+
+        if let Some(swap_io) = harvested_data.swap_io {
+            self.swap_io = Some(swap_io);
+        }
+
+        if let Some(total_pss) = harvested_data.total_pss {
+            self.total_pss = Some(total_pss);
         }
 
         // Cache memory
@@ -236,7 +443,11 @@ impl DataCollection {
 
         #[cfg(feature = "gpu")]
         if let Some(gpu) = harvested_data.gpu {
-            self.eat_gpu(gpu, &mut new_entry);
+            self.eat_gpu(
+                gpu,
+                harvested_data.gpu_usage.unwrap_or_default(),
+                &mut new_entry,
+            );
         }
 
         // CPU
@@ -244,14 +455,30 @@ impl DataCollection {
             self.eat_cpu(cpu, &mut new_entry);
         }
 
+        if let Some(cpu_times) = harvested_data.cpu_times {
+            self.cpu_times_harvest = Some(cpu_times);
+        }
+
         // Load average
         if let Some(load_avg) = harvested_data.load_avg {
             self.eat_load_avg(load_avg, &mut new_entry);
         }
 
+        if let Some(sched_tasks) = harvested_data.sched_tasks {
+            self.sched_tasks_harvest = Some(sched_tasks);
+        }
+
+        if let Some(pinned_processes) = harvested_data.pinned_processes {
+            self.pinned_processes = pinned_processes;
+        }
+
         // Temp
         if let Some(temperature_sensors) = harvested_data.temperature_sensors {
-            self.eat_temp(temperature_sensors);
+            self.eat_temp(temperature_sensors, harvested_time);
+        }
+
+        if let Some(core_temp_sensors) = harvested_data.core_temp_sensors {
+            self.core_temp_sensors = core_temp_sensors;
         }
 
         // Disks
@@ -261,9 +488,21 @@ impl DataCollection {
             }
         }
 
+        if let Some(disk_endurance) = harvested_data.disk_endurance {
+            self.disk_endurance = disk_endurance;
+        }
+
         // Processes
         if let Some(list_of_processes) = harvested_data.list_of_processes {
-            self.eat_proc(list_of_processes);
+            self.eat_proc(list_of_processes, harvested_time);
+        }
+
+        if let Some(num_cpus) = harvested_data.num_cpus {
+            self.num_cpus = Some(num_cpus);
+        }
+
+        if let Some(max_inotify_watches) = harvested_data.max_inotify_watches {
+            self.max_inotify_watches = Some(max_inotify_watches);
         }
 
         #[cfg(feature = "battery")]
@@ -274,8 +513,18 @@ impl DataCollection {
             }
         }
 
+        #[cfg(feature = "systemd")]
+        if let Some(user_units) = harvested_data.user_units {
+            self.user_units = user_units;
+        }
+
+        self.collection_errors = harvested_data.errors;
+
         // And we're done eating.  Update time and push the new entry!
         self.current_instant = harvested_time;
+        self.current_time = SystemTime::now()
+            .checked_sub(harvested_time.elapsed())
+            .unwrap_or_else(SystemTime::now);
         self.timed_data_vec.push((harvested_time, new_entry));
     }
 
@@ -313,6 +562,18 @@ impl DataCollection {
             new_entry.tx_data = network.tx as f64;
         }
 
+        new_entry.net_interface_data = network
+            .interfaces
+            .iter()
+            .map(|interface| {
+                (
+                    interface.name.clone(),
+                    interface.rx as f64,
+                    interface.tx as f64,
+                )
+            })
+            .collect();
+
         // In addition copy over latest data for easy reference
         self.network_harvest = network;
     }
@@ -331,10 +592,50 @@ impl DataCollection {
         new_entry.load_avg_data = load_avg;
 
         self.load_avg_harvest = load_avg;
+        if self.load_avg_history.len() == LOAD_AVG_HISTORY_LEN {
+            self.load_avg_history.pop_front();
+        }
+        self.load_avg_history.push_back(load_avg[0].into());
     }
 
-    fn eat_temp(&mut self, temperature_sensors: Vec<temperature::TempHarvest>) {
+    fn eat_temp(
+        &mut self, temperature_sensors: Vec<temperature::TempHarvest>, harvested_time: Instant,
+    ) {
         self.temp_harvest = temperature_sensors;
+
+        self.temp_history
+            .retain(|name, _| self.temp_harvest.iter().any(|sensor| &sensor.name == name));
+
+        // Sensors can share a name, in which case only the first one is kept.
+        let mut seen = HashSet::new();
+        for sensor in &self.temp_harvest {
+            if let Some(temperature) = sensor.temperature {
+                if seen.insert(sensor.name.as_str()) {
+                    push_history(
+                        self.temp_history
+                            .entry_ref(sensor.name.as_str())
+                            .or_default(),
+                        (harvested_time, temperature.into()),
+                        TEMP_HISTORY_LEN,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Converts the stored temperatures from one unit to another, so they can
+    /// be shown in the new unit before the next harvest arrives.
+    pub fn convert_temperatures(
+        &mut self, from: temperature::TemperatureType, to: temperature::TemperatureType,
+    ) {
+        let convert = |temp: f32| to.convert_temp_unit(from.to_celsius(temp));
+
+        for sensor in &mut self.temp_harvest {
+            sensor.temperature = sensor.temperature.map(convert);
+        }
+        for (_, temp) in self.temp_history.values_mut().flatten() {
+            *temp = convert(*temp as f32).into();
+        }
     }
 
     fn eat_disks(
@@ -446,8 +747,15 @@ impl DataCollection {
         self.io_harvest = io;
     }
 
-    fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>) {
-        self.process_data.ingest(list_of_processes);
+    fn eat_proc(&mut self, list_of_processes: Vec<ProcessHarvest>, harvested_time: Instant) {
+        self.process_data.ingest(list_of_processes, harvested_time);
+    }
+
+    /// Removes processes that haven't been collected in the last `threshold`,
+    /// as of the latest harvest.
+    pub fn prune_stale_processes(&mut self, threshold: Duration) {
+        self.process_data
+            .prune_stale_processes(self.current_instant, threshold);
     }
 
     #[cfg(feature = "battery")]
@@ -462,13 +770,80 @@ impl DataCollection {
     }
 
     #[cfg(feature = "gpu")]
-    fn eat_gpu(&mut self, gpu: Vec<(String, memory::MemHarvest)>, new_entry: &mut TimedData) {
+    fn eat_gpu(
+        &mut self, gpu: Vec<(String, memory::MemHarvest)>, mut usage: Vec<GpuUsage>,
+        new_entry: &mut TimedData,
+    ) {
         // Note this only pre-calculates the data points - the names will be
         // within the local copy of gpu_harvest. Since it's all sequential
         // it probably doesn't matter anyways.
         gpu.iter().for_each(|data| {
             new_entry.gpu_data.push(data.1.use_percent);
         });
+        usage.resize(gpu.len(), GpuUsage::default());
         self.gpu_harvest = gpu;
+        self.gpu_usage = usage;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(pid: Pid, parent_pid: Option<Pid>) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            parent_pid,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn prune_stale_processes() {
+        let mut data = ProcessData::default();
+        let start = Instant::now();
+        let threshold = Duration::from_secs(2);
+
+        data.ingest(vec![process(1, None), process(2, Some(1))], start);
+        data.prune_stale_processes(start + Duration::from_secs(2), threshold);
+        assert_eq!(data.process_harvest.len(), 2);
+
+        // Process 2 was seen recently enough, but 1 wasn't.
+        data.last_seen.insert(2, start + Duration::from_secs(1));
+        data.prune_stale_processes(start + Duration::from_secs(3), threshold);
+        assert_eq!(data.process_harvest.keys().collect::<Vec<_>>(), [&2]);
+        assert!(!data.cpu_history.contains_key(&1));
+        assert!(data.process_parent_mapping.is_empty());
+        assert_eq!(data.orphan_pids, [2]);
+    }
+
+    #[test]
+    fn convert_temperatures() {
+        use temperature::{TempHarvest, TemperatureType};
+
+        let mut data = DataCollection::default();
+        data.eat_temp(
+            vec![TempHarvest {
+                name: "cpu".to_string(),
+                temperature: Some(100.0),
+            }],
+            Instant::now(),
+        );
+        data.convert_temperatures(TemperatureType::Celsius, TemperatureType::Fahrenheit);
+
+        assert_eq!(data.temp_harvest[0].temperature, Some(212.0));
+        assert_eq!(data.temp_history["cpu"][0].1, 212.0);
+    }
+
+    #[test]
+    fn load_avg_history() {
+        let mut data = DataCollection::default();
+        for i in 0..LOAD_AVG_HISTORY_LEN + 5 {
+            data.eat_load_avg([i as f32, 0.0, 0.0], &mut TimedData::default());
+        }
+
+        assert_eq!(data.load_avg_history.len(), LOAD_AVG_HISTORY_LEN);
+        assert_eq!(data.load_avg_history.front(), Some(&5.0));
+        assert_eq!(data.load_avg_harvest, [64.0, 0.0, 0.0]);
     }
 }
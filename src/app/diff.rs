@@ -0,0 +1,119 @@
+//! Comparing current data against a saved snapshot, in diff mode.
+
+use std::time::SystemTime;
+
+use crate::data_collection::Data;
+
+/// The default change, in percentage points, that a value has to exceed to be
+/// highlighted in diff mode.
+pub const DEFAULT_DIFF_THRESHOLD: f64 = 5.0;
+
+/// A snapshot to compare current data against.
+pub struct DiffBaseline {
+    pub data: Data,
+    /// When the snapshot was saved, if known.
+    pub time: Option<SystemTime>,
+    /// Changes of at most this many percentage points aren't highlighted.
+    pub threshold: f64,
+}
+
+impl DiffBaseline {
+    /// The time the snapshot was saved as `YYYY-MM-DD HH:MM:SS` in UTC, if
+    /// known.
+    pub fn time_label(&self) -> Option<String> {
+        self.time.map(|time| {
+            humantime::format_rfc3339_seconds(time)
+                .to_string()
+                .replace('T', " ")
+                .trim_end_matches('Z')
+                .to_string()
+        })
+    }
+
+    /// Names the snapshot being compared against, for widget titles.
+    pub fn title_label(&self) -> String {
+        match self.time_label() {
+            Some(time) => format!("vs {time} UTC"),
+            None => "vs snapshot".to_string(),
+        }
+    }
+}
+
+/// How a value changed compared to its baseline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DiffChange {
+    /// The value didn't change by more than the threshold.
+    #[default]
+    Unchanged,
+    Improved,
+    Regressed,
+}
+
+impl DiffChange {
+    /// Compares a value against its baseline. Whether a change is an
+    /// improvement depends on the metric, e.g. lower CPU usage is better,
+    /// but more free memory is too.
+    pub fn new(baseline: f64, current: f64, threshold: f64, lower_is_better: bool) -> Self {
+        let change = current - baseline;
+
+        if change.abs() <= threshold {
+            DiffChange::Unchanged
+        } else if (change < 0.0) == lower_is_better {
+            DiffChange::Improved
+        } else {
+            DiffChange::Regressed
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn diff_change() {
+        assert_eq!(
+            DiffChange::new(34.0, 67.0, 5.0, true),
+            DiffChange::Regressed
+        );
+        assert_eq!(DiffChange::new(67.0, 34.0, 5.0, true), DiffChange::Improved);
+        assert_eq!(
+            DiffChange::new(34.0, 67.0, 5.0, false),
+            DiffChange::Improved
+        );
+        assert_eq!(
+            DiffChange::new(34.0, 38.0, 5.0, true),
+            DiffChange::Unchanged
+        );
+        assert_eq!(
+            DiffChange::new(34.0, 29.0, 5.0, true),
+            DiffChange::Unchanged
+        );
+        assert_eq!(
+            DiffChange::new(34.0, 35.0, 0.0, true),
+            DiffChange::Regressed
+        );
+    }
+
+    #[test]
+    fn baseline_time_label() {
+        let baseline = DiffBaseline {
+            data: Data::default(),
+            time: Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            threshold: DEFAULT_DIFF_THRESHOLD,
+        };
+        assert_eq!(
+            baseline.time_label().as_deref(),
+            Some("2023-11-14 22:13:20")
+        );
+        assert_eq!(baseline.title_label(), "vs 2023-11-14 22:13:20 UTC");
+
+        let baseline = DiffBaseline {
+            time: None,
+            ..baseline
+        };
+        assert_eq!(baseline.title_label(), "vs snapshot");
+    }
+}
@@ -0,0 +1,82 @@
+use hashbrown::HashSet;
+
+use super::layout_manager::BottomWidgetType;
+
+/// Which kinds of widgets have changed since the last draw, so the others can
+/// be left as they were.
+#[derive(Debug)]
+pub struct DirtyWidgets {
+    all: bool,
+    widgets: HashSet<BottomWidgetType>,
+}
+
+impl Default for DirtyWidgets {
+    /// Everything starts out dirty, as nothing has been drawn yet.
+    fn default() -> Self {
+        Self {
+            all: true,
+            widgets: HashSet::default(),
+        }
+    }
+}
+
+impl DirtyWidgets {
+    /// The type of widget that is drawn for `widget_type`, as the process
+    /// search and sort menus are drawn with their process widget, and the CPU
+    /// legend with its graph.
+    fn drawn_as(widget_type: &BottomWidgetType) -> BottomWidgetType {
+        match widget_type {
+            BottomWidgetType::ProcSearch | BottomWidgetType::ProcSort => BottomWidgetType::Proc,
+            BottomWidgetType::CpuLegend => BottomWidgetType::Cpu,
+            other => other.clone(),
+        }
+    }
+
+    /// Marks widgets of `widget_type` as needing a redraw.
+    pub fn mark(&mut self, widget_type: &BottomWidgetType) {
+        if !self.all {
+            self.widgets.insert(Self::drawn_as(widget_type));
+        }
+    }
+
+    /// Marks every widget as needing a redraw.
+    pub fn mark_all(&mut self) {
+        self.all = true;
+        self.widgets.clear();
+    }
+
+    /// Whether widgets of `widget_type` need a redraw.
+    pub fn is_dirty(&self, widget_type: &BottomWidgetType) -> bool {
+        self.all || self.widgets.contains(&Self::drawn_as(widget_type))
+    }
+
+    /// Marks everything as drawn.
+    pub fn clear(&mut self) {
+        self.all = false;
+        self.widgets.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn marking() {
+        let mut dirty = DirtyWidgets::default();
+        assert!(dirty.is_dirty(&BottomWidgetType::Disk));
+
+        dirty.clear();
+        assert!(!dirty.is_dirty(&BottomWidgetType::Proc));
+
+        dirty.mark(&BottomWidgetType::ProcSort);
+        assert!(dirty.is_dirty(&BottomWidgetType::Proc));
+        assert!(!dirty.is_dirty(&BottomWidgetType::Cpu));
+
+        dirty.mark(&BottomWidgetType::CpuLegend);
+        assert!(dirty.is_dirty(&BottomWidgetType::Cpu));
+
+        dirty.mark_all();
+        assert!(dirty.is_dirty(&BottomWidgetType::Temp));
+    }
+}
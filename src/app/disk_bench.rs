@@ -0,0 +1,143 @@
+//! Running the `--disk_bench` read benchmark in the background, and keeping
+//! how far it's gotten for the disk widget and the overlay shown when it ends.
+
+use std::{sync::mpsc::Sender, thread, time::Instant};
+
+use crate::{
+    data_collection::disk_bench::{
+        run_benchmark_with_progress, BenchmarkResult, BENCHMARK_DURATION,
+    },
+    data_conversion::{dec_bytes_per_second_string, dec_bytes_string},
+    event::BottomEvent,
+};
+
+/// The label shown wherever the benchmark is.
+pub const BENCHMARK_LABEL: &str = "BENCHMARK MODE - DO NOT USE ON PRODUCTION SYSTEMS";
+
+/// The benchmark of a single device.
+#[derive(Debug)]
+pub struct DiskBenchState {
+    pub device: String,
+    pub started: Instant,
+    /// The last progress reported, or the result once it's done.
+    pub result: Option<BenchmarkResult>,
+    /// Whether the overlay with the result is shown.
+    pub is_showing_result: bool,
+}
+
+impl DiskBenchState {
+    pub fn new(device: String) -> Self {
+        Self {
+            device,
+            started: Instant::now(),
+            result: None,
+            is_showing_result: false,
+        }
+    }
+
+    /// Starts reading the device in the background, sending its progress and
+    /// result to the main thread.
+    pub(crate) fn start(&mut self, sender: Sender<BottomEvent>) {
+        let device = self.device.clone();
+        self.started = Instant::now();
+
+        thread::spawn(move || {
+            let result = run_benchmark_with_progress(&device, BENCHMARK_DURATION, |progress| {
+                let _ = sender.send(BottomEvent::DiskBench(Box::new(progress.clone())));
+            });
+            let _ = sender.send(BottomEvent::DiskBench(Box::new(result)));
+        });
+    }
+
+    /// Keeps the latest progress, showing the overlay once it's done.
+    pub fn update(&mut self, result: BenchmarkResult) {
+        if result.is_finished {
+            self.is_showing_result = true;
+        }
+        self.result = Some(result);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.result
+            .as_ref()
+            .is_some_and(|result| result.is_finished)
+    }
+
+    /// Describes how the benchmark is going, or how it went.
+    pub fn lines(&self) -> Vec<String> {
+        let Some(result) = &self.result else {
+            return vec![format!("Reading {}...", self.device)];
+        };
+
+        let mode = if result.is_direct {
+            "O_DIRECT"
+        } else {
+            "page cache"
+        };
+        let mut lines = vec![
+            format!(
+                "Sequential read of {}: {} ({mode})",
+                self.device,
+                dec_bytes_per_second_string(result.throughput() as u64)
+            ),
+            format!(
+                "{} read in {:.1}s{}",
+                dec_bytes_string(result.bytes_read),
+                result.elapsed.as_secs_f64(),
+                if result.is_finished { "" } else { "..." }
+            ),
+        ];
+        if let Some(error) = &result.error {
+            lines.push(format!("Stopped early: {error}"));
+        }
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::mpsc, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn result_opens_overlay() {
+        let (sender, receiver) = mpsc::channel();
+        let mut state = DiskBenchState::new("/this/does/not/exist".to_string());
+        state.start(sender);
+
+        let BottomEvent::DiskBench(result) = receiver.recv().unwrap() else {
+            panic!("expected the benchmark's result");
+        };
+        assert!(!state.is_showing_result);
+
+        state.update(*result);
+        assert!(state.is_finished());
+        assert!(state.is_showing_result);
+        assert!(state.lines().last().unwrap().starts_with("Stopped early"));
+        assert!(state.result.unwrap().error.is_some());
+    }
+
+    #[test]
+    fn progress_lines() {
+        let mut state = DiskBenchState::new("/dev/sda".to_string());
+        assert_eq!(state.lines(), vec!["Reading /dev/sda...".to_string()]);
+
+        state.update(BenchmarkResult {
+            device: "/dev/sda".to_string(),
+            bytes_read: 500_000_000,
+            elapsed: Duration::from_millis(2500),
+            is_direct: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            state.lines(),
+            vec![
+                "Sequential read of /dev/sda: 200MB/s (O_DIRECT)".to_string(),
+                "500MB read in 2.5s...".to_string(),
+            ]
+        );
+        assert!(!state.is_showing_result);
+    }
+}
@@ -0,0 +1,474 @@
+//! A log of significant system events, such as processes starting or exiting,
+//! or usage crossing a threshold.
+
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use hashbrown::{HashMap, HashSet};
+
+use crate::data_collection::{
+    cpu::{CpuData, CpuDataType},
+    processes::Pid,
+    temperature::TemperatureType,
+    Data,
+};
+
+/// The maximum number of entries kept in the log.
+pub const MAX_LOG_ENTRIES: usize = 500;
+
+/// Total CPU usage percentage at which a warning is logged.
+pub(crate) const HIGH_CPU_THRESHOLD: f64 = 90.0;
+
+/// Disk usage percentage at which a warning is logged.
+pub(crate) const DISK_WARN_THRESHOLD: f64 = 90.0;
+
+/// Disk usage percentage at which a critical entry is logged.
+pub(crate) const DISK_CRIT_THRESHOLD: f64 = 95.0;
+
+/// Temperature in degrees Celsius at which a critical entry is logged.
+pub(crate) const HIGH_TEMP_THRESHOLD_CELSIUS: f32 = 90.0;
+
+/// The severity of a [`LogEntry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum LogLevel {
+    #[default]
+    Info,
+    Warn,
+    Crit,
+}
+
+impl LogLevel {
+    /// The next level to filter by, wrapping back around to [`LogLevel::Info`].
+    fn next(self) -> Self {
+        match self {
+            LogLevel::Info => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Crit,
+            LogLevel::Crit => LogLevel::Info,
+        }
+    }
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogLevel::Info => write!(f, "INFO"),
+            LogLevel::Warn => write!(f, "WARN"),
+            LogLevel::Crit => write!(f, "CRIT"),
+        }
+    }
+}
+
+/// A single event in the [`EventLog`].
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: Instant,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// Tracks what has been seen so far, so that only changes are logged.
+#[derive(Debug, Default)]
+struct Watched {
+    /// Whether we've seen any data yet; the first update only seeds the state.
+    is_seeded: bool,
+    processes: HashMap<Pid, String>,
+    is_cpu_high: bool,
+    /// The disks over a threshold, and the highest level logged for them.
+    full_disks: HashMap<String, LogLevel>,
+    hot_sensors: HashSet<String>,
+}
+
+/// Which kinds of usage are currently over a threshold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActiveAlerts {
+    pub cpu: bool,
+    pub disk: bool,
+    pub temp: bool,
+}
+
+/// A scrollable log of the last [`MAX_LOG_ENTRIES`] system events.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    entries: VecDeque<LogEntry>,
+    watched: Watched,
+
+    /// Whether the log is currently being shown.
+    pub is_showing: bool,
+
+    /// Only entries at this level or above are shown.
+    pub min_level: LogLevel,
+
+    /// How many shown entries we are scrolled up from the newest one. If this
+    /// is zero, new entries are followed as they come in.
+    pub scroll_offset: usize,
+
+    /// The height of the log's drawn area, used for paging.
+    pub height: u16,
+}
+
+impl EventLog {
+    /// Adds a new entry, dropping the oldest one if the log is full.
+    pub fn push(&mut self, level: LogLevel, message: impl Into<String>) {
+        if self.entries.len() >= MAX_LOG_ENTRIES {
+            if let Some(removed) = self.entries.pop_front() {
+                if removed.level >= self.min_level {
+                    self.scroll_offset = self.scroll_offset.min(self.num_shown().saturating_sub(1));
+                }
+            }
+        }
+
+        // Don't move the view if the user has scrolled up to look at something.
+        if level >= self.min_level && self.scroll_offset > 0 {
+            self.scroll_offset += 1;
+        }
+
+        self.entries.push_back(LogEntry {
+            timestamp: Instant::now(),
+            level,
+            message: message.into(),
+        });
+    }
+
+    /// The entries that pass the current level filter, from oldest to newest.
+    pub fn shown(&self) -> impl DoubleEndedIterator<Item = &LogEntry> + '_ {
+        self.entries
+            .iter()
+            .filter(|entry| entry.level >= self.min_level)
+    }
+
+    /// The number of entries that pass the current level filter.
+    pub fn num_shown(&self) -> usize {
+        self.shown().count()
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_showing = !self.is_showing;
+        self.scroll_offset = 0;
+    }
+
+    /// Cycles the level filter.
+    pub fn cycle_filter(&mut self) {
+        self.min_level = self.min_level.next();
+        self.scroll_offset = 0;
+    }
+
+    /// Scrolls back towards older entries.
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll_offset = (self.scroll_offset + amount).min(self.num_shown().saturating_sub(1));
+    }
+
+    /// Scrolls forward towards newer entries.
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(amount);
+    }
+
+    pub fn scroll_to_oldest(&mut self) {
+        self.scroll_offset = self.num_shown().saturating_sub(1);
+    }
+
+    pub fn scroll_to_newest(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    /// Whether any usage is currently over a threshold.
+    pub fn is_alerting(&self) -> bool {
+        self.active_alerts() != ActiveAlerts::default()
+    }
+
+    /// Which kinds of usage are currently over a threshold.
+    pub fn active_alerts(&self) -> ActiveAlerts {
+        ActiveAlerts {
+            cpu: self.watched.is_cpu_high,
+            disk: !self.watched.full_disks.is_empty(),
+            temp: !self.watched.hot_sensors.is_empty(),
+        }
+    }
+
+    /// Checks newly collected data for anything worth logging. Returns a
+    /// description of the first alert raised by this data, if usage crossed a
+    /// threshold.
+    pub fn check_data(&mut self, data: &Data, temperature_type: TemperatureType) -> Option<String> {
+        let is_seeded = self.watched.is_seeded;
+        let mut alert = None;
+
+        if let Some(processes) = &data.list_of_processes {
+            let mut current = HashMap::with_capacity(processes.len());
+            for process in processes {
+                if is_seeded && !self.watched.processes.contains_key(&process.pid) {
+                    self.push(
+                        LogLevel::Info,
+                        format!("Process '{}' ({}) started", process.name, process.pid),
+                    );
+                }
+                current.insert(process.pid, process.name.clone());
+            }
+
+            let mut exited = self
+                .watched
+                .processes
+                .iter()
+                .filter(|(pid, _)| !current.contains_key(*pid))
+                .map(|(pid, name)| (*pid, name.clone()))
+                .collect::<Vec<_>>();
+            exited.sort_unstable_by_key(|(pid, _)| *pid);
+            for (pid, name) in exited {
+                self.push(LogLevel::Info, format!("Process '{name}' ({pid}) exited"));
+            }
+
+            self.watched.processes = current;
+        }
+
+        if let Some(cpu) = &data.cpu {
+            if let Some(usage) = total_cpu_usage(cpu) {
+                let is_high = usage >= HIGH_CPU_THRESHOLD;
+
+                if is_high && !self.watched.is_cpu_high {
+                    self.push(LogLevel::Warn, format!("CPU usage is high ({usage:.1}%)"));
+                    alert.get_or_insert_with(|| format!("CPU exceeded {HIGH_CPU_THRESHOLD:.0}%"));
+                } else if !is_high && self.watched.is_cpu_high {
+                    self.push(LogLevel::Info, "CPU usage is back to normal");
+                }
+                self.watched.is_cpu_high = is_high;
+            }
+        }
+
+        if let Some(disks) = &data.disks {
+            for disk in disks {
+                let (Some(used), Some(total)) = (disk.used_space, disk.total_space) else {
+                    continue;
+                };
+                if total == 0 {
+                    continue;
+                }
+
+                let percent = used as f64 / total as f64 * 100.0;
+                let threshold = if percent >= DISK_CRIT_THRESHOLD {
+                    Some((LogLevel::Crit, DISK_CRIT_THRESHOLD))
+                } else if percent >= DISK_WARN_THRESHOLD {
+                    Some((LogLevel::Warn, DISK_WARN_THRESHOLD))
+                } else {
+                    None
+                };
+
+                let previous = self.watched.full_disks.get(&disk.mount_point).copied();
+                match threshold {
+                    Some((level, threshold))
+                        if previous.map_or(true, |previous| level > previous) =>
+                    {
+                        self.push(
+                            level,
+                            format!("Disk '{}' is {percent:.1}% full", disk.mount_point),
+                        );
+                        alert.get_or_insert_with(|| {
+                            format!("Disk '{}' exceeded {threshold:.0}%", disk.mount_point)
+                        });
+                        self.watched
+                            .full_disks
+                            .insert(disk.mount_point.clone(), level);
+                    }
+                    Some(_) => {}
+                    None => {
+                        self.watched.full_disks.remove(&disk.mount_point);
+                    }
+                }
+            }
+        }
+
+        if let Some(sensors) = &data.temperature_sensors {
+            let threshold = temperature_type.convert_temp_unit(HIGH_TEMP_THRESHOLD_CELSIUS);
+
+            for sensor in sensors {
+                let Some(temperature) = sensor.temperature else {
+                    continue;
+                };
+
+                if temperature >= threshold {
+                    if self.watched.hot_sensors.insert(sensor.name.clone()) {
+                        self.push(
+                            LogLevel::Crit,
+                            format!(
+                                "Sensor '{}' is at {temperature:.0}{temperature_type}",
+                                sensor.name
+                            ),
+                        );
+                        alert.get_or_insert_with(|| {
+                            format!(
+                                "Sensor '{}' exceeded {threshold:.0}{temperature_type}",
+                                sensor.name
+                            )
+                        });
+                    }
+                } else if self.watched.hot_sensors.remove(&sensor.name) {
+                    self.push(
+                        LogLevel::Info,
+                        format!("Sensor '{}' has cooled down", sensor.name),
+                    );
+                }
+            }
+        }
+
+        self.watched.is_seeded = true;
+
+        alert
+    }
+}
+
+/// The total CPU usage, averaged over each core.
+pub(crate) fn total_cpu_usage(cpu: &[CpuData]) -> Option<f64> {
+    let cores = cpu
+        .iter()
+        .filter(|cpu| matches!(cpu.data_type, CpuDataType::Cpu(_)))
+        .map(|cpu| cpu.cpu_usage)
+        .collect::<Vec<_>>();
+
+    (!cores.is_empty()).then(|| cores.iter().sum::<f64>() / cores.len() as f64)
+}
+
+/// Formats how long ago an entry was logged.
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+
+    if secs == 0 {
+        "now".to_string()
+    } else if secs < 60 {
+        format!("{secs}s ago")
+    } else if secs < 3600 {
+        format!("{}m {}s ago", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m ago", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::{disks::DiskHarvest, processes::ProcessHarvest};
+
+    fn processes(pids: &[Pid]) -> Data {
+        Data {
+            list_of_processes: Some(
+                pids.iter()
+                    .map(|&pid| ProcessHarvest {
+                        pid,
+                        name: format!("p{pid}"),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn entries_are_bounded() {
+        let mut log = EventLog::default();
+        for i in 0..MAX_LOG_ENTRIES + 10 {
+            log.push(LogLevel::Info, i.to_string());
+        }
+
+        assert_eq!(log.num_shown(), MAX_LOG_ENTRIES);
+        assert_eq!(log.shown().next().unwrap().message, "10");
+    }
+
+    #[test]
+    fn no_follow_when_scrolled() {
+        let mut log = EventLog::default();
+        for i in 0..10 {
+            log.push(LogLevel::Info, i.to_string());
+        }
+
+        log.scroll_up(3);
+        log.push(LogLevel::Info, "new");
+        assert_eq!(log.scroll_offset, 4);
+
+        log.scroll_to_newest();
+        log.push(LogLevel::Info, "newer");
+        assert_eq!(log.scroll_offset, 0);
+    }
+
+    #[test]
+    fn level_filter() {
+        let mut log = EventLog::default();
+        log.push(LogLevel::Info, "a");
+        log.push(LogLevel::Warn, "b");
+        log.push(LogLevel::Crit, "c");
+
+        assert_eq!(log.num_shown(), 3);
+        log.cycle_filter();
+        assert_eq!(log.num_shown(), 2);
+        log.cycle_filter();
+        assert_eq!(log.num_shown(), 1);
+        log.cycle_filter();
+        assert_eq!(log.num_shown(), 3);
+    }
+
+    #[test]
+    fn process_changes() {
+        let mut log = EventLog::default();
+
+        log.check_data(&processes(&[1, 2]), TemperatureType::Celsius);
+        assert_eq!(log.num_shown(), 0);
+
+        log.check_data(&processes(&[1, 3]), TemperatureType::Celsius);
+        let messages = log.shown().map(|e| e.message.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec!["Process 'p3' (3) started", "Process 'p2' (2) exited"]
+        );
+    }
+
+    #[test]
+    fn disk_thresholds() {
+        let disk = |used| Data {
+            disks: Some(vec![DiskHarvest {
+                name: "sda".into(),
+                mount_point: "/".into(),
+                #[cfg(target_os = "windows")]
+                volume_name: None,
+                free_space: Some(100 - used),
+                used_space: Some(used),
+                total_space: Some(100),
+            }]),
+            ..Default::default()
+        };
+
+        let mut log = EventLog::default();
+        assert_eq!(log.check_data(&disk(50), TemperatureType::Celsius), None);
+        assert_eq!(
+            log.check_data(&disk(91), TemperatureType::Celsius)
+                .as_deref(),
+            Some("Disk '/' exceeded 90%")
+        );
+        assert_eq!(log.check_data(&disk(92), TemperatureType::Celsius), None);
+        assert_eq!(
+            log.check_data(&disk(96), TemperatureType::Celsius)
+                .as_deref(),
+            Some("Disk '/' exceeded 95%")
+        );
+        assert!(log.is_alerting());
+        assert_eq!(
+            log.active_alerts(),
+            ActiveAlerts {
+                disk: true,
+                ..Default::default()
+            }
+        );
+
+        let levels = log.shown().map(|e| e.level).collect::<Vec<_>>();
+        assert_eq!(levels, vec![LogLevel::Warn, LogLevel::Crit]);
+
+        log.check_data(&disk(50), TemperatureType::Celsius);
+        assert!(!log.is_alerting());
+    }
+
+    #[test]
+    fn age_format() {
+        assert_eq!(format_age(Duration::from_millis(500)), "now");
+        assert_eq!(format_age(Duration::from_secs(12)), "12s ago");
+        assert_eq!(format_age(Duration::from_secs(62)), "1m 2s ago");
+        assert_eq!(format_age(Duration::from_secs(3720)), "1h 2m ago");
+    }
+}
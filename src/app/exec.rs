@@ -0,0 +1,189 @@
+//! Running a shell command on each update and keeping what it printed, like
+//! `watch`, for the exec widget.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    event::BottomEvent,
+    export::hooks::{run_shell_command, shell_command, CommandError, CommandOutput},
+};
+
+/// The default time the command can run for before it's killed.
+pub const DEFAULT_EXEC_TIMEOUT_MS: u64 = 2000;
+
+/// How many lines of stdout and of stderr are kept from each run.
+const MAX_EXEC_LINES: usize = 1000;
+
+/// How the last run of the command ended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExecStatus {
+    /// The command exited, with its exit code unless it was killed by a signal.
+    Exited(Option<i32>),
+    /// The command was killed for taking too long.
+    TimedOut,
+    /// The command couldn't be run at all.
+    Failed(String),
+}
+
+/// What the last run of the command printed.
+#[derive(Clone, Debug)]
+pub struct ExecOutput {
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+    pub status: ExecStatus,
+    /// When the run finished.
+    pub finished_at: SystemTime,
+}
+
+impl ExecOutput {
+    fn new(result: Result<CommandOutput, CommandError>, timeout: Duration) -> Self {
+        let (stdout, stderr, status) = match result {
+            Ok(CommandOutput {
+                status,
+                stdout,
+                stderr,
+            }) => (
+                last_lines(&stdout),
+                last_lines(&stderr),
+                ExecStatus::Exited(status.code()),
+            ),
+            Err(CommandError::Timeout) => (
+                Vec::new(),
+                vec![format!(
+                    "Killed after taking longer than {}ms",
+                    timeout.as_millis()
+                )],
+                ExecStatus::TimedOut,
+            ),
+            Err(CommandError::Io(err)) => (
+                Vec::new(),
+                vec![err.to_string()],
+                ExecStatus::Failed(err.to_string()),
+            ),
+        };
+
+        Self {
+            stdout,
+            stderr,
+            status,
+            finished_at: SystemTime::now(),
+        }
+    }
+
+    /// How many lines are shown, with stderr after stdout.
+    pub fn num_lines(&self) -> usize {
+        self.stdout.len() + self.stderr.len()
+    }
+
+    /// A short description of how the run ended, for the widget title.
+    pub fn status_text(&self) -> String {
+        match &self.status {
+            ExecStatus::Exited(Some(code)) => format!("exit {code}"),
+            ExecStatus::Exited(None) => "killed".to_string(),
+            ExecStatus::TimedOut => "timed out".to_string(),
+            ExecStatus::Failed(_) => "failed".to_string(),
+        }
+    }
+}
+
+/// The last [`MAX_EXEC_LINES`] lines of `text`, without trailing whitespace.
+fn last_lines(text: &str) -> Vec<String> {
+    let lines = text.lines().map(str::trim_end).collect::<Vec<_>>();
+    let start = lines.len().saturating_sub(MAX_EXEC_LINES);
+
+    lines[start..].iter().map(|line| line.to_string()).collect()
+}
+
+/// Runs the command in the background and sends what it printed to the main
+/// thread.
+pub(crate) struct ExecRunner {
+    command: Arc<str>,
+    timeout: Duration,
+    /// Whether the last run hasn't finished yet, in which case another one
+    /// isn't started.
+    is_running: Arc<AtomicBool>,
+    sender: Sender<BottomEvent>,
+}
+
+impl ExecRunner {
+    pub(crate) fn new(command: &str, timeout: Duration, sender: Sender<BottomEvent>) -> Self {
+        Self {
+            command: command.into(),
+            timeout,
+            is_running: Arc::default(),
+            sender,
+        }
+    }
+
+    /// Starts a run of the command, unless the last one is still going.
+    pub(crate) fn run(&self) {
+        if self.is_running.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let command = self.command.clone();
+        let is_running = self.is_running.clone();
+        let timeout = self.timeout;
+        let sender = self.sender.clone();
+
+        thread::spawn(move || {
+            let result = run_shell_command(shell_command(&command), None, timeout);
+            is_running.store(false, Ordering::Release);
+
+            let _ = sender.send(BottomEvent::ExecOutput(Box::new(ExecOutput::new(
+                result, timeout,
+            ))));
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn only_last_lines_are_kept() {
+        let text = (0..MAX_EXEC_LINES + 5)
+            .map(|i| format!("line {i}  "))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = last_lines(&text);
+
+        assert_eq!(lines.len(), MAX_EXEC_LINES);
+        assert_eq!(lines[0], "line 5");
+        assert_eq!(
+            lines.last().unwrap(),
+            &format!("line {}", MAX_EXEC_LINES + 4)
+        );
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn runs_command() {
+        use std::sync::mpsc;
+
+        let timeout = Duration::from_secs(5);
+        let (sender, receiver) = mpsc::channel();
+        let runner = ExecRunner::new("echo out; echo err >&2; exit 2", timeout, sender);
+        runner.run();
+
+        match receiver.recv_timeout(timeout) {
+            Ok(BottomEvent::ExecOutput(output)) => {
+                assert_eq!(output.stdout, vec!["out"]);
+                assert_eq!(output.stderr, vec!["err"]);
+                assert_eq!(output.status, ExecStatus::Exited(Some(2)));
+                assert_eq!(output.status_text(), "exit 2");
+                assert_eq!(output.num_lines(), 2);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+}
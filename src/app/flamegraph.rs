@@ -0,0 +1,247 @@
+//! Approximate flame graphs of where a process spends its time, built from a
+//! short burst of stack samples. See the `flame` feature.
+//!
+//! Stacks are kept "folded", as in Brendan Gregg's flame graph tools: each
+//! distinct stack from the root down, with how many samples it was seen in.
+
+use std::{collections::BTreeMap, sync::mpsc::Sender, thread, time::Duration};
+
+use crate::{data_collection::processes::Pid, event::BottomEvent};
+
+/// How many samples are taken for a flame graph.
+pub const FLAME_GRAPH_SAMPLES: usize = 100;
+
+/// How long sampling takes.
+pub const FLAME_GRAPH_DURATION: Duration = Duration::from_secs(1);
+
+/// Where the code for a frame lives, which decides its colour.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FrameKind {
+    Kernel,
+    User,
+    /// Code in anonymous executable memory, which is usually JIT-compiled.
+    Jit,
+}
+
+/// A single frame of a stack.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Frame {
+    pub name: String,
+    pub kind: FrameKind,
+}
+
+impl Frame {
+    pub fn new(name: impl Into<String>, kind: FrameKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+        }
+    }
+}
+
+/// Sampled stacks, folded so that identical ones are only stored once.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FoldedStacks {
+    stacks: BTreeMap<Vec<Frame>, u64>,
+    total: u64,
+}
+
+impl FoldedStacks {
+    /// Adds one sample of a stack, given from the root down. Empty stacks are
+    /// skipped.
+    pub fn add(&mut self, stack: Vec<Frame>) {
+        if stack.is_empty() {
+            return;
+        }
+
+        *self.stacks.entry(stack).or_default() += 1;
+        self.total += 1;
+    }
+
+    /// The number of samples added.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The stacks in the folded text format, one per line, e.g.
+    /// `main;read;[k] do_sys_poll 12`.
+    pub fn to_folded(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, count)| {
+                let names = stack
+                    .iter()
+                    .map(|frame| frame.name.as_str())
+                    .collect::<Vec<_>>();
+                format!("{} {count}\n", names.join(";"))
+            })
+            .collect()
+    }
+
+    /// Lays the flame graph out in the given number of columns. Each row is a
+    /// depth of the stacks, starting at the root, and each frame's width is
+    /// proportional to the number of samples it was in. Frames too narrow to
+    /// get a column are dropped.
+    pub fn layout(&self, width: u16) -> Vec<Vec<FlameBar>> {
+        let mut rows: Vec<Vec<FlameBar>> = Vec::new();
+        if self.total == 0 {
+            return rows;
+        }
+
+        let column = |samples: u64| (samples * u64::from(width) / self.total) as u16;
+
+        // Stacks are sorted, so stacks sharing a prefix are next to each
+        // other and each frame can be merged with the one before it in its row.
+        let mut samples_before = 0;
+        for (stack, count) in &self.stacks {
+            let start = column(samples_before);
+            samples_before += count;
+            let end = column(samples_before);
+
+            for (depth, frame) in stack.iter().enumerate() {
+                if rows.len() <= depth {
+                    rows.push(Vec::new());
+                }
+                let row = &mut rows[depth];
+
+                match row.last_mut() {
+                    Some(bar)
+                        if bar.frame == *frame
+                            && bar.start + bar.width == start
+                            && bar.stack_prefix == stack[..depth] =>
+                    {
+                        bar.width = end - bar.start;
+                    }
+                    _ => row.push(FlameBar {
+                        frame: frame.clone(),
+                        start,
+                        width: end - start,
+                        stack_prefix: stack[..depth].to_vec(),
+                    }),
+                }
+            }
+        }
+
+        for row in &mut rows {
+            row.retain(|bar| bar.width > 0);
+        }
+        rows.retain(|row| !row.is_empty());
+
+        rows
+    }
+}
+
+/// A frame in a laid out flame graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlameBar {
+    pub frame: Frame,
+    /// The first column of the frame.
+    pub start: u16,
+    pub width: u16,
+    /// The frames beneath this one, so that only frames with the same callers
+    /// are merged.
+    stack_prefix: Vec<Frame>,
+}
+
+/// What the flame graph overlay is showing.
+#[derive(Debug)]
+pub enum FlameGraphStatus {
+    Capturing,
+    Done(FoldedStacks),
+    Failed(String),
+}
+
+/// The flame graph overlay for a process.
+#[derive(Debug)]
+pub struct FlameGraphState {
+    pub pid: Pid,
+    pub name: String,
+    pub status: FlameGraphStatus,
+    /// Whether sampling still needs to be started.
+    pub is_pending: bool,
+}
+
+impl FlameGraphState {
+    pub fn new(pid: Pid, name: String) -> Self {
+        Self {
+            pid,
+            name,
+            status: FlameGraphStatus::Capturing,
+            is_pending: true,
+        }
+    }
+}
+
+/// Samples the stacks of a process in the background, sending the result to
+/// the main thread when done.
+pub fn start_capture(pid: Pid, sender: Sender<BottomEvent>) {
+    thread::spawn(move || {
+        #[cfg(target_os = "linux")]
+        let result = crate::data_collection::processes::sample_stacks(
+            pid,
+            FLAME_GRAPH_SAMPLES,
+            FLAME_GRAPH_DURATION,
+        );
+        #[cfg(not(target_os = "linux"))]
+        let result = Err("Flame graphs are only supported on Linux.".to_string());
+
+        let _ = sender.send(BottomEvent::FlameGraph(pid, result));
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stack(names: &[&str]) -> Vec<Frame> {
+        names
+            .iter()
+            .map(|name| Frame::new(*name, FrameKind::User))
+            .collect()
+    }
+
+    #[test]
+    fn folding() {
+        let mut stacks = FoldedStacks::default();
+        stacks.add(stack(&["main", "work"]));
+        stacks.add(stack(&["main", "work"]));
+        stacks.add(stack(&["main", "idle"]));
+        stacks.add(Vec::new());
+
+        assert_eq!(stacks.total(), 3);
+        assert_eq!(stacks.to_folded(), "main;idle 1\nmain;work 2\n");
+    }
+
+    #[test]
+    fn layout() {
+        let mut stacks = FoldedStacks::default();
+        stacks.add(stack(&["main", "idle"]));
+        for _ in 0..2 {
+            stacks.add(stack(&["main", "work", "read"]));
+        }
+        stacks.add(stack(&["other"]));
+
+        let rows = stacks
+            .layout(40)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|bar| (bar.frame.name, bar.start, bar.width))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![("main".to_string(), 0, 30), ("other".to_string(), 30, 10)],
+                vec![("idle".to_string(), 0, 10), ("work".to_string(), 10, 20)],
+                vec![("read".to_string(), 10, 20)],
+            ]
+        );
+
+        // Frames that would be less than a column wide are dropped.
+        assert_eq!(stacks.layout(2)[1].len(), 1);
+        assert!(FoldedStacks::default().layout(40).is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::{str::FromStr, time::SystemTime};
+
 use super::DataCollection;
 
 /// The [`FrozenState`] indicates whether the application state should be
@@ -44,3 +46,73 @@ impl FrozenState {
         }
     }
 }
+
+/// When to automatically freeze if an alert is raised.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FreezeOnAlertMode {
+    /// Stay frozen only while usage is over a threshold.
+    Level,
+    /// Stay frozen after each alert until manually unfrozen.
+    #[default]
+    Edge,
+}
+
+impl FromStr for FreezeOnAlertMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().trim() {
+            "level" => Ok(Self::Level),
+            "edge" => Ok(Self::Edge),
+            _ => Err(format!("'{s}' is not a valid freeze on alert mode")),
+        }
+    }
+}
+
+/// The alert that caused an automatic freeze.
+pub struct FreezeAlert {
+    pub message: String,
+    pub time: SystemTime,
+}
+
+impl FreezeAlert {
+    pub fn new(message: String) -> Self {
+        Self {
+            message,
+            time: SystemTime::now(),
+        }
+    }
+
+    /// The time of the alert as `HH:MM:SS` in UTC.
+    pub fn clock_time(&self) -> String {
+        let timestamp = humantime::format_rfc3339_seconds(self.time).to_string();
+        timestamp
+            .get(11..19)
+            .map(str::to_string)
+            .unwrap_or(timestamp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn freeze_on_alert_modes() {
+        assert_eq!("level".parse(), Ok(FreezeOnAlertMode::Level));
+        assert_eq!("Edge".parse(), Ok(FreezeOnAlertMode::Edge));
+        assert!("both".parse::<FreezeOnAlertMode>().is_err());
+    }
+
+    #[test]
+    fn alert_clock_time() {
+        let alert = FreezeAlert {
+            message: String::new(),
+            time: UNIX_EPOCH + Duration::from_secs(14 * 3600 + 23 * 60 + 7),
+        };
+
+        assert_eq!(alert.clock_time(), "14:23:07");
+    }
+}
@@ -10,6 +10,14 @@ pub struct BottomLayout {
     pub total_row_height_ratio: u32,
 }
 
+/// A second layout that can be switched to at runtime, along with the widget
+/// to select when switching to it.
+#[derive(Clone, Debug)]
+pub struct AlternateLayout {
+    pub layout: BottomLayout,
+    pub widget_id: u64,
+}
+
 // Represents a start and end coordinate in some dimension.
 type LineSegment = (u32, u32);
 
@@ -18,6 +26,15 @@ type ColumnRowMappings = (u32, BTreeMap<LineSegment, WidgetMappings>);
 type ColumnMappings = (u32, BTreeMap<LineSegment, ColumnRowMappings>);
 
 impl BottomLayout {
+    /// Every widget in the layout.
+    pub fn widgets_mut(&mut self) -> impl Iterator<Item = &mut BottomWidget> {
+        self.rows
+            .iter_mut()
+            .flat_map(|row| &mut row.children)
+            .flat_map(|col| &mut col.children)
+            .flat_map(|col_row| &mut col_row.children)
+    }
+
     pub fn get_movement_mappings(&mut self) {
         #[expect(clippy::suspicious_operation_groupings)] // Have to enable this, clippy really doesn't like me doing this with tuples...
         fn is_intersecting(a: LineSegment, b: LineSegment) -> bool {
@@ -943,12 +960,19 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    Startup,
+    Exec,
+    UserProcs,
+    LogTail,
 }
 
 impl BottomWidgetType {
     pub fn is_widget_table(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Disk | Proc | ProcSort | Temp | CpuLegend)
+        matches!(
+            self,
+            Disk | Proc | ProcSort | Temp | CpuLegend | Startup | UserProcs
+        )
     }
 
     pub fn is_widget_graph(&self) -> bool {
@@ -966,6 +990,10 @@ impl BottomWidgetType {
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            Startup => "Startup",
+            Exec => "Exec",
+            UserProcs => "User Processes",
+            LogTail => "Log Tail",
             _ => "",
         }
     }
@@ -983,6 +1011,10 @@ impl std::str::FromStr for BottomWidgetType {
             "proc" | "process" | "processes" => Ok(BottomWidgetType::Proc),
             "temp" | "temperature" => Ok(BottomWidgetType::Temp),
             "disk" => Ok(BottomWidgetType::Disk),
+            "startup" => Ok(BottomWidgetType::Startup),
+            "exec" => Ok(BottomWidgetType::Exec),
+            "user_procs" | "nproc" => Ok(BottomWidgetType::UserProcs),
+            "log_tail" => Ok(BottomWidgetType::LogTail),
             "empty" => Ok(BottomWidgetType::Empty),
             #[cfg(feature = "battery")]
             "battery" | "batt" => Ok(BottomWidgetType::Battery),
@@ -1006,6 +1038,14 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|          startup         |
++--------------------------+
+|           exec           |
++--------------------------+
+|    user_procs, nproc     |
++--------------------------+
+|         log_tail         |
++--------------------------+
 |       batt, battery      |
 +--------------------------+
 |           empty          |
@@ -1032,6 +1072,14 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|          startup         |
++--------------------------+
+|           exec           |
++--------------------------+
+|    user_procs, nproc     |
++--------------------------+
+|         log_tail         |
++--------------------------+
 |           empty          |
 +--------------------------+
                 ",
@@ -1051,6 +1099,10 @@ pub struct UsedWidgets {
     pub use_net: bool,
     pub use_proc: bool,
     pub use_disk: bool,
+    /// Whether to read the SMART data of SSDs, for how worn out they are.
+    pub use_smart: bool,
     pub use_temp: bool,
     pub use_battery: bool,
+    pub use_startup: bool,
+    pub use_user_procs: bool,
 }
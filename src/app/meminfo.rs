@@ -0,0 +1,196 @@
+//! The overlay listing every field of `/proc/meminfo`, which is read again on
+//! every update while it's shown.
+
+use crate::data_collection::memory::{full_meminfo, MemInfoEntry};
+
+/// A scrollable, filterable overlay with the fields of [`full_meminfo`].
+#[derive(Debug, Default)]
+pub struct MemInfoPanel {
+    entries: Vec<MemInfoEntry>,
+
+    /// Whether the overlay is currently being shown.
+    pub is_showing: bool,
+
+    /// How many lines are scrolled past at the top.
+    pub scroll_offset: usize,
+
+    /// Only fields with a name or description containing this, ignoring case,
+    /// are shown.
+    pub filter: String,
+
+    /// Whether typed characters go to the filter.
+    pub is_filtering: bool,
+
+    /// The height of the overlay's drawn area, used for paging.
+    height: u16,
+}
+
+impl MemInfoPanel {
+    pub fn toggle(&mut self) {
+        self.is_showing = !self.is_showing;
+        self.scroll_offset = 0;
+        self.filter.clear();
+        self.is_filtering = false;
+
+        if self.is_showing {
+            self.refresh();
+        } else {
+            self.entries.clear();
+        }
+    }
+
+    /// Reads the fields again, if the overlay is shown.
+    pub fn refresh(&mut self) {
+        if self.is_showing {
+            self.set_entries(full_meminfo());
+        }
+    }
+
+    fn set_entries(&mut self, entries: Vec<MemInfoEntry>) {
+        self.entries = entries;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll());
+    }
+
+    /// The lines of the fields matching the filter.
+    pub fn lines(&self) -> Vec<String> {
+        let filter = self.filter.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.key.to_lowercase().contains(&filter)
+                    || entry.description.to_lowercase().contains(&filter)
+            })
+            .map(entry_line)
+            .collect()
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Sets the height of the drawn area, so the overlay can't be scrolled
+    /// past its last line.
+    pub fn set_height(&mut self, height: u16) {
+        self.height = height;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll());
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines().len().saturating_sub(self.height.into())
+    }
+
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(amount);
+    }
+
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll_offset = (self.scroll_offset + amount).min(self.max_scroll());
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_offset = self.max_scroll();
+    }
+
+    pub fn push_filter(&mut self, c: char) {
+        self.filter.push(c);
+        self.scroll_offset = 0;
+    }
+
+    pub fn pop_filter(&mut self) {
+        self.filter.pop();
+        self.scroll_offset = 0;
+    }
+
+    /// Starts or stops typing into the filter.
+    pub fn set_filtering(&mut self, is_filtering: bool) {
+        self.is_filtering = is_filtering;
+    }
+}
+
+/// Shows a field in bytes, KiB, and MiB at once, followed by what it means.
+/// Fields that aren't sizes just show their number.
+fn entry_line(entry: &MemInfoEntry) -> String {
+    let value = if entry.is_bytes {
+        let bytes = entry.value_bytes as f64;
+        format!(
+            "{:>16} B {:>14.1} KiB {:>11.1} MiB",
+            entry.value_bytes,
+            bytes / 1024.0,
+            bytes / 1024.0 / 1024.0
+        )
+    } else {
+        format!("{:>16}", entry.value_bytes)
+    };
+
+    format!("{:<18}{value:<51}  {}", entry.key, entry.description)
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(key: &str, value_bytes: u64, is_bytes: bool) -> MemInfoEntry {
+        MemInfoEntry {
+            key: key.to_string(),
+            value_bytes,
+            is_bytes,
+            description: if is_bytes {
+                "RAM left completely unused"
+            } else {
+                ""
+            },
+        }
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(
+            entry_line(&entry("MemFree", 2 * 1024 * 1024, true)),
+            "MemFree                    2097152 B         2048.0 KiB         2.0 MiB  \
+            RAM left completely unused"
+        );
+        assert_eq!(
+            entry_line(&entry("HugePages_Total", 4, false)),
+            "HugePages_Total                  4"
+        );
+    }
+
+    #[test]
+    fn filter() {
+        let mut panel = MemInfoPanel {
+            is_showing: true,
+            ..Default::default()
+        };
+        panel.set_entries(vec![
+            entry("MemTotal", 1024, false),
+            entry("MemFree", 1024, true),
+            entry("SwapFree", 1024, false),
+        ]);
+        assert_eq!(panel.lines().len(), 3);
+
+        for c in "free".chars() {
+            panel.push_filter(c);
+        }
+        assert_eq!(panel.lines().len(), 2);
+
+        // Descriptions are searched too.
+        panel.filter = "unused".to_string();
+        assert_eq!(panel.lines().len(), 1);
+
+        panel.filter.clear();
+        panel.set_height(1);
+        panel.scroll_down(10);
+        assert_eq!(panel.scroll_offset, 2);
+
+        panel.toggle();
+        assert!(!panel.is_showing);
+        assert_eq!(panel.scroll_offset, 0);
+        assert!(panel.lines().is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+//! State for monitoring several remote bottom instances side by side.
+
+use crate::data_collection::Data;
+
+/// Whether a remote host is connected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    /// The host hasn't been connected to yet.
+    Connecting,
+    Connected,
+    /// The connection to the host was lost, or couldn't be made.
+    Disconnected,
+}
+
+/// A remote bottom instance being monitored.
+pub struct RemoteHost {
+    pub address: String,
+    /// The latest data received from the host, if any.
+    pub data: Option<Box<Data>>,
+    pub status: ConnectionStatus,
+}
+
+impl RemoteHost {
+    fn new(address: String) -> Self {
+        Self {
+            address,
+            data: None,
+            status: ConnectionStatus::Connecting,
+        }
+    }
+
+    /// The name of the host, which is its address without the port.
+    pub fn name(&self) -> &str {
+        host_name(&self.address)
+    }
+}
+
+/// Gets the host name from an address, dropping the port and the brackets
+/// around IPv6 addresses.
+pub fn host_name(address: &str) -> &str {
+    let host = match address.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => address,
+    };
+
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+/// All hosts being monitored, and which one is focused.
+pub struct MultiHostState {
+    pub hosts: Vec<RemoteHost>,
+    pub focused: usize,
+}
+
+impl MultiHostState {
+    pub fn new(addresses: Vec<String>) -> Self {
+        Self {
+            hosts: addresses.into_iter().map(RemoteHost::new).collect(),
+            focused: 0,
+        }
+    }
+
+    /// Stores new data from the host at the given index.
+    pub fn update(&mut self, index: usize, data: Box<Data>) {
+        if let Some(host) = self.hosts.get_mut(index) {
+            host.data = Some(data);
+            host.status = ConnectionStatus::Connected;
+        }
+    }
+
+    /// Marks the host at the given index as disconnected. Its last data is
+    /// kept so it can still be shown.
+    pub fn disconnect(&mut self, index: usize) {
+        if let Some(host) = self.hosts.get_mut(index) {
+            host.status = ConnectionStatus::Disconnected;
+        }
+    }
+
+    /// Moves the focus to the next host, wrapping around.
+    pub fn focus_next(&mut self) {
+        if !self.hosts.is_empty() {
+            self.focused = (self.focused + 1) % self.hosts.len();
+        }
+    }
+
+    /// Moves the focus to the previous host, wrapping around.
+    pub fn focus_prev(&mut self) {
+        if !self.hosts.is_empty() {
+            self.focused = (self.focused + self.hosts.len() - 1) % self.hosts.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn host_names() {
+        let state = MultiHostState::new(vec![
+            "host1:4567".into(),
+            "10.0.0.2:80".into(),
+            "[::1]:4567".into(),
+            "host2".into(),
+        ]);
+        let names = state.hosts.iter().map(RemoteHost::name).collect::<Vec<_>>();
+
+        assert_eq!(names, vec!["host1", "10.0.0.2", "::1", "host2"]);
+    }
+
+    #[test]
+    fn focus_wraps() {
+        let mut state = MultiHostState::new(vec!["a:1".into(), "b:1".into(), "c:1".into()]);
+
+        state.focus_prev();
+        assert_eq!(state.focused, 2);
+        state.focus_next();
+        assert_eq!(state.focused, 0);
+        state.focus_next();
+        assert_eq!(state.focused, 1);
+    }
+
+    #[test]
+    fn disconnect_keeps_data() {
+        let mut state = MultiHostState::new(vec!["a:1".into()]);
+        assert_eq!(state.hosts[0].status, ConnectionStatus::Connecting);
+
+        state.update(0, Box::default());
+        assert_eq!(state.hosts[0].status, ConnectionStatus::Connected);
+
+        state.disconnect(0);
+        assert_eq!(state.hosts[0].status, ConnectionStatus::Disconnected);
+        assert!(state.hosts[0].data.is_some());
+
+        // Out of range indices are ignored.
+        state.update(1, Box::default());
+        state.disconnect(1);
+    }
+}
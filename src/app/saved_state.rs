@@ -0,0 +1,94 @@
+//! State that's kept between runs, such as the order of the process widget's
+//! columns and the temperature unit. It's stored as JSON in the user's state directory, falling back to
+//! the local data directory on platforms without one.
+
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data_collection::temperature::TemperatureType,
+    widgets::{ColumnPreset, ProcColumn},
+};
+
+const STATE_FILE_NAME: &str = "state.json";
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedState {
+    /// The order of the process widget's columns, if they were ever moved.
+    #[serde(default)]
+    pub process_columns: Vec<ProcColumn>,
+    /// The temperature unit last picked at runtime.
+    #[serde(default)]
+    pub temperature_type: Option<TemperatureType>,
+    /// The process column presets saved at runtime.
+    #[serde(default)]
+    pub column_presets: Vec<ColumnPreset>,
+    /// When the daily update check last ran, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub last_update_check: Option<u64>,
+}
+
+impl SavedState {
+    fn path() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("bottom").join(STATE_FILE_NAME))
+    }
+
+    /// Reads the saved state, or returns the default if there is none or it
+    /// can't be read.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|state| serde_json::from_str(&state).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the state, replacing whatever was saved before.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!("no state directory found"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let state = SavedState {
+            process_columns: vec![
+                ProcColumn::Pid,
+                ProcColumn::Name,
+                ProcColumn::ReadPerSecond,
+                ProcColumn::CpuPercent,
+                ProcColumn::MemPercent,
+                ProcColumn::CpuDelta,
+            ],
+            temperature_type: Some(TemperatureType::Kelvin),
+            column_presets: vec![ColumnPreset::new(
+                "preset 1",
+                vec![ProcColumn::Pid, ProcColumn::State],
+            )],
+            last_update_check: Some(1_700_000_000),
+        };
+        let json = serde_json::to_string(&state).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"process_columns":["PID","Name","R/s","CPU%","Mem%","ΔCPU%"],"temperature_type":"kelvin","column_presets":[{"name":"preset 1","columns":["PID","State"]}],"last_update_check":1700000000}"#
+        );
+        assert_eq!(serde_json::from_str::<SavedState>(&json).unwrap(), state);
+        assert_eq!(
+            serde_json::from_str::<SavedState>("{}").unwrap(),
+            SavedState::default()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Momentum for scrolling through tables with the mouse wheel.
+
+use std::time::{Duration, Instant};
+
+use crate::constants::TICK_RATE_IN_MILLISECONDS;
+
+/// The default fraction of velocity kept after each tick.
+pub const DEFAULT_SCROLL_FRICTION: f32 = 0.8;
+
+const TICK_RATE: Duration = Duration::from_millis(TICK_RATE_IN_MILLISECONDS);
+
+/// Velocities smaller than this round to no movement, so the scroll stops.
+const MIN_VELOCITY: f32 = 0.5;
+
+/// Tracks how fast a table is being scrolled. Each wheel event adds to the
+/// velocity, which then decays every tick.
+#[derive(Debug, Clone)]
+pub struct ScrollMomentum {
+    /// How many rows to move per tick; negative values scroll up.
+    velocity: f32,
+    /// The fraction of velocity kept after each tick.
+    friction: f32,
+    last_tick: Instant,
+}
+
+impl ScrollMomentum {
+    pub fn new(friction: f32) -> Self {
+        Self {
+            velocity: 0.0,
+            friction,
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Whether the scroll is still moving.
+    pub fn is_moving(&self) -> bool {
+        self.velocity.abs() >= MIN_VELOCITY
+    }
+
+    /// Adds a wheel event, where a positive amount scrolls down. Scrolling the
+    /// other way stops the current scroll first.
+    pub fn push(&mut self, amount: f32, now: Instant) {
+        if self.velocity * amount < 0.0 {
+            self.velocity = 0.0;
+        }
+
+        // Start moving right away, rather than waiting for the next tick.
+        if !self.is_moving() {
+            self.last_tick = now.checked_sub(TICK_RATE).unwrap_or(now);
+        }
+
+        self.velocity += amount;
+    }
+
+    /// Stops scrolling, e.g. when the end of the list is reached.
+    pub fn stop(&mut self) {
+        self.velocity = 0.0;
+    }
+
+    /// How long until the next tick is due, if the scroll is moving.
+    pub fn until_next_tick(&self, now: Instant) -> Option<Duration> {
+        self.is_moving()
+            .then(|| TICK_RATE.saturating_sub(now.saturating_duration_since(self.last_tick)))
+    }
+
+    /// If a tick is due, returns how many rows to move by and decays the
+    /// velocity.
+    pub fn tick(&mut self, now: Instant) -> Option<i64> {
+        if !self.is_moving() || now.saturating_duration_since(self.last_tick) < TICK_RATE {
+            return None;
+        }
+
+        let step = self.velocity.round() as i64;
+        self.last_tick = now;
+        self.velocity *= self.friction;
+        if !self.is_moving() {
+            self.stop();
+        }
+
+        Some(step)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Runs ticks until the scroll stops, returning each step.
+    fn run(momentum: &mut ScrollMomentum, mut now: Instant) -> Vec<i64> {
+        let mut steps = vec![];
+        while let Some(wait) = momentum.until_next_tick(now) {
+            now += wait;
+            steps.extend(momentum.tick(now));
+        }
+        steps
+    }
+
+    #[test]
+    fn decays_until_stopped() {
+        let now = Instant::now();
+        let mut momentum = ScrollMomentum::new(0.5);
+        assert!(!momentum.is_moving());
+        assert_eq!(momentum.until_next_tick(now), None);
+
+        momentum.push(4.0, now);
+        assert_eq!(momentum.until_next_tick(now), Some(Duration::ZERO));
+        assert_eq!(run(&mut momentum, now), vec![4, 2, 1, 1]);
+        assert!(!momentum.is_moving());
+    }
+
+    #[test]
+    fn waits_for_ticks() {
+        let now = Instant::now();
+        let mut momentum = ScrollMomentum::new(0.9);
+
+        momentum.push(-1.0, now);
+        assert_eq!(momentum.tick(now), Some(-1));
+        assert_eq!(momentum.tick(now), None);
+        assert_eq!(momentum.until_next_tick(now), Some(TICK_RATE));
+        assert_eq!(momentum.tick(now + TICK_RATE), Some(-1));
+    }
+
+    #[test]
+    fn successive_events_accumulate() {
+        let now = Instant::now();
+        let mut momentum = ScrollMomentum::new(0.8);
+
+        momentum.push(1.0, now);
+        momentum.push(1.0, now);
+        momentum.push(1.0, now);
+        assert_eq!(momentum.tick(now), Some(3));
+
+        // Changing direction drops what was built up.
+        momentum.push(-1.0, now);
+        assert_eq!(momentum.tick(now + TICK_RATE), Some(-1));
+
+        momentum.stop();
+        assert_eq!(momentum.tick(now + TICK_RATE * 2), None);
+    }
+}
@@ -9,8 +9,9 @@ use crate::{
     app::layout_manager::BottomWidgetType,
     constants,
     widgets::{
-        query::ProcessQuery, BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState,
-        NetWidgetState, ProcWidgetState, TempWidgetState,
+        query::ProcessQuery, BatteryWidgetState, CpuWidgetState, DiskTableWidget, ExecWidgetState,
+        LogTailWidgetState, MemWidgetState, NetWidgetState, ProcWidgetState, StartupWidgetState,
+        TempWidgetState, UserProcWidgetState,
     },
 };
 
@@ -22,6 +23,10 @@ pub struct AppWidgetStates {
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: BatteryState,
+    pub startup_state: StartupState,
+    pub exec_state: ExecState,
+    pub user_proc_state: UserProcState,
+    pub log_tail_state: LogTailState,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
 }
 
@@ -335,12 +340,16 @@ impl MemState {
 }
 
 pub struct TempState {
+    pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, TempWidgetState>,
 }
 
 impl TempState {
     pub fn init(widget_states: HashMap<u64, TempWidgetState>) -> Self {
-        TempState { widget_states }
+        TempState {
+            force_update: None,
+            widget_states,
+        }
     }
 
     pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut TempWidgetState> {
@@ -369,6 +378,56 @@ impl DiskState {
         self.widget_states.get(&widget_id)
     }
 }
+pub struct StartupState {
+    pub widget_states: HashMap<u64, StartupWidgetState>,
+}
+
+impl StartupState {
+    pub fn init(widget_states: HashMap<u64, StartupWidgetState>) -> Self {
+        StartupState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut StartupWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&StartupWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct UserProcState {
+    pub widget_states: HashMap<u64, UserProcWidgetState>,
+}
+
+impl UserProcState {
+    pub fn init(widget_states: HashMap<u64, UserProcWidgetState>) -> Self {
+        UserProcState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut UserProcWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&UserProcWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct LogTailState {
+    pub widget_states: HashMap<u64, LogTailWidgetState>,
+}
+
+impl LogTailState {
+    pub fn init(widget_states: HashMap<u64, LogTailWidgetState>) -> Self {
+        LogTailState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut LogTailWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+}
+
 pub struct BasicTableWidgetState {
     // Since this is intended (currently) to only be used for ONE widget, that's
     // how it's going to be written.  If we want to allow for multiple of these,
@@ -395,6 +454,20 @@ impl BatteryState {
     }
 }
 
+pub struct ExecState {
+    pub widget_states: HashMap<u64, ExecWidgetState>,
+}
+
+impl ExecState {
+    pub fn init(widget_states: HashMap<u64, ExecWidgetState>) -> Self {
+        ExecState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut ExecWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
@@ -0,0 +1,156 @@
+//! The panel showing a summary of the system, which is collected once at
+//! startup.
+
+use crate::{data_collection::system_info::SystemSummary, utils::data_prefixes::get_binary_bytes};
+
+/// A read-only, scrollable panel with the lines of a [`SystemSummary`].
+#[derive(Debug, Default)]
+pub struct SystemSummaryPanel {
+    lines: Vec<String>,
+
+    /// Whether the panel is currently being shown.
+    pub is_showing: bool,
+
+    /// How many lines are scrolled past at the top.
+    pub scroll_offset: usize,
+
+    /// The height of the panel's drawn area, used for paging.
+    height: u16,
+}
+
+impl SystemSummaryPanel {
+    pub fn new(summary: &SystemSummary) -> Self {
+        Self {
+            lines: summary_lines(summary),
+            ..Default::default()
+        }
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// The whole summary as text, for copying.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Sets the height of the drawn area, so the panel can't be scrolled past
+    /// its last line.
+    pub fn set_height(&mut self, height: u16) {
+        self.height = height;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll());
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_showing = !self.is_showing;
+        self.scroll_offset = 0;
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.height.into())
+    }
+
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(amount);
+    }
+
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll_offset = (self.scroll_offset + amount).min(self.max_scroll());
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_offset = self.max_scroll();
+    }
+}
+
+fn summary_lines(summary: &SystemSummary) -> Vec<String> {
+    let unknown = |value: Option<usize>| value.map_or("unknown".to_string(), |v| v.to_string());
+    let (memory, unit) = get_binary_bytes(summary.total_memory);
+
+    let mut lines = vec![
+        summary.uname.clone(),
+        String::new(),
+        format!("Architecture:   {}", summary.arch),
+        format!("Sockets:        {}", unknown(summary.sockets)),
+        format!("Cores:          {}", unknown(summary.cores)),
+        format!("Threads:        {}", summary.threads),
+        format!("Total RAM:      {memory:.1} {unit}"),
+    ];
+
+    if let Some(cmdline) = &summary.kernel_cmdline {
+        lines.push(format!("Kernel cmdline: {cmdline}"));
+    }
+
+    if !summary.limits.is_empty() {
+        let limit = |limit: Option<u64>| limit.map_or("unlimited".to_string(), |l| l.to_string());
+        lines.push(String::new());
+        lines.push(format!(
+            "{:<26}{:>20}{:>20}  Units",
+            "Limit", "Soft", "Hard"
+        ));
+        lines.extend(summary.limits.iter().map(|entry| {
+            format!(
+                "{:<26}{:>20}{:>20}  {}",
+                entry.name,
+                limit(entry.soft),
+                limit(entry.hard),
+                entry.units
+            )
+            .trim_end()
+            .to_string()
+        }));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::processes::RlimitEntry;
+
+    #[test]
+    fn summary() {
+        let summary = SystemSummary {
+            uname: "Linux host 6.1.0 #1 SMP x86_64".to_string(),
+            arch: "x86_64".to_string(),
+            sockets: None,
+            cores: Some(4),
+            threads: 8,
+            total_memory: 16 * 1024 * 1024 * 1024,
+            kernel_cmdline: Some("quiet".to_string()),
+            limits: vec![RlimitEntry {
+                name: "Max open files".to_string(),
+                soft: Some(1024),
+                hard: None,
+                units: "files".to_string(),
+                usage: None,
+            }],
+        };
+        let mut panel = SystemSummaryPanel::new(&summary);
+
+        let lines = panel.lines();
+        assert_eq!(lines[0], summary.uname);
+        assert!(lines.contains(&"Sockets:        unknown".to_string()));
+        assert!(lines.contains(&"Total RAM:      16.0 GiB".to_string()));
+        assert!(lines.last().unwrap().starts_with("Max open files"));
+        assert!(lines.last().unwrap().ends_with("unlimited  files"));
+        assert_eq!(panel.text().lines().count(), lines.len());
+
+        panel.set_height(5);
+        panel.scroll_down(100);
+        assert_eq!(panel.scroll_offset, panel.lines().len() - 5);
+        panel.scroll_up(1);
+        panel.toggle();
+        assert_eq!(panel.scroll_offset, 0);
+    }
+}
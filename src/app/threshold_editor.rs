@@ -0,0 +1,104 @@
+//! The dialog for editing the least CPU and memory usage processes need to be
+//! shown.
+
+/// A threshold that can be edited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThresholdField {
+    Cpu,
+    Mem,
+}
+
+/// The state of an open threshold editor. Thresholds are edited as text, and
+/// only parsed once applied.
+#[derive(Debug)]
+pub struct ThresholdEditor {
+    pub cpu: String,
+    pub mem: String,
+    pub selected: ThresholdField,
+    /// Why the thresholds couldn't be applied, if they couldn't.
+    pub error: Option<String>,
+}
+
+impl ThresholdEditor {
+    /// Opens the editor with the current thresholds filled in.
+    pub fn new(cpu: Option<f64>, mem: Option<f64>) -> Self {
+        let text = |threshold: Option<f64>| threshold.map(|t| t.to_string()).unwrap_or_default();
+
+        Self {
+            cpu: text(cpu),
+            mem: text(mem),
+            selected: ThresholdField::Cpu,
+            error: None,
+        }
+    }
+
+    fn selected_text(&mut self) -> &mut String {
+        match self.selected {
+            ThresholdField::Cpu => &mut self.cpu,
+            ThresholdField::Mem => &mut self.mem,
+        }
+    }
+
+    /// Switches to editing the other threshold.
+    pub fn toggle_field(&mut self) {
+        self.selected = match self.selected {
+            ThresholdField::Cpu => ThresholdField::Mem,
+            ThresholdField::Mem => ThresholdField::Cpu,
+        };
+    }
+
+    /// Types a character into the selected threshold. Only digits and a
+    /// decimal point can be typed.
+    pub fn insert(&mut self, c: char) {
+        if c.is_ascii_digit() || c == '.' {
+            self.selected_text().push(c);
+            self.error = None;
+        }
+    }
+
+    /// Deletes the last character of the selected threshold.
+    pub fn backspace(&mut self) {
+        self.selected_text().pop();
+        self.error = None;
+    }
+
+    /// Parses the CPU and memory thresholds. An empty threshold is the same
+    /// as none.
+    pub fn parse(&self) -> Result<(Option<f64>, Option<f64>), String> {
+        let parse = |text: &str, name: &str| {
+            if text.is_empty() {
+                Ok(None)
+            } else {
+                text.parse::<f64>()
+                    .map(Some)
+                    .map_err(|_| format!("{name} isn't a number."))
+            }
+        };
+
+        Ok((parse(&self.cpu, "CPU%")?, parse(&self.mem, "Mem%")?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn editing() {
+        let mut editor = ThresholdEditor::new(Some(1.5), None);
+        assert_eq!(editor.cpu, "1.5");
+        assert_eq!(editor.mem, "");
+
+        editor.backspace();
+        editor.insert('x');
+        editor.toggle_field();
+        editor.insert('0');
+        editor.insert('.');
+        editor.insert('5');
+        assert_eq!(editor.parse(), Ok((Some(1.0), Some(0.5))));
+
+        editor.toggle_field();
+        editor.insert('.');
+        assert!(editor.parse().is_err());
+    }
+}
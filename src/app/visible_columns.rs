@@ -0,0 +1,72 @@
+//! Which process columns are shown, so that the data only they need can be
+//! skipped otherwise.
+
+use bitflags::bitflags;
+
+use crate::widgets::ProcWidgetColumn;
+
+bitflags! {
+    /// The process columns that are shown, for those that need data that's
+    /// expensive to collect for every process. Columns whose data is always
+    /// collected, like the CPU and memory usage, have no flag.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct VisibleColumns: u8 {
+        /// The minor and major page fault columns.
+        const PAGE_FAULTS = 1 << 0;
+        const WCHAN = 1 << 1;
+        /// Any of the namespace columns.
+        const NAMESPACES = 1 << 2;
+        /// The cgroup CPU quota and throttling columns.
+        const CGROUPS = 1 << 3;
+        const CONTAINERS = 1 << 4;
+        const PSS = 1 << 5;
+        /// The TCP connection count column.
+        const TCP = 1 << 6;
+        /// The inotify watch count column.
+        const INOTIFY = 1 << 7;
+    }
+}
+
+impl VisibleColumns {
+    /// The flag needed by a column, if any.
+    pub fn from_column(column: &ProcWidgetColumn) -> Self {
+        match column {
+            ProcWidgetColumn::MinorFaults | ProcWidgetColumn::MajorFaults => Self::PAGE_FAULTS,
+            ProcWidgetColumn::WChan => Self::WCHAN,
+            ProcWidgetColumn::PidNamespace
+            | ProcWidgetColumn::NetNamespace
+            | ProcWidgetColumn::MntNamespace
+            | ProcWidgetColumn::UserNamespace => Self::NAMESPACES,
+            ProcWidgetColumn::CpuQuota | ProcWidgetColumn::CpuThrottled => Self::CGROUPS,
+            ProcWidgetColumn::Container => Self::CONTAINERS,
+            ProcWidgetColumn::Pss => Self::PSS,
+            ProcWidgetColumn::TcpConnections => Self::TCP,
+            ProcWidgetColumn::InotifyWatches => Self::INOTIFY,
+            _ => Self::empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_columns() {
+        let visible = [
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::MajorFaults,
+            ProcWidgetColumn::MinorFaults,
+            ProcWidgetColumn::NetNamespace,
+        ]
+        .iter()
+        .map(VisibleColumns::from_column)
+        .collect::<VisibleColumns>();
+
+        assert_eq!(
+            visible,
+            VisibleColumns::PAGE_FAULTS | VisibleColumns::NAMESPACES
+        );
+        assert!(!visible.contains(VisibleColumns::WCHAN));
+    }
+}
@@ -0,0 +1,92 @@
+//! Timing how long data collection takes, for comparing collection
+//! performance between builds or systems.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    app::{AppConfigFields, DataFilters},
+    data_collection::Data,
+    export,
+};
+
+/// How many ticks are timed.
+const BENCH_TICKS: usize = 20;
+
+/// How long to wait between ticks, so that rates have something to compare
+/// against as they would when running normally.
+const BENCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The mean, fastest, and slowest of some durations.
+fn summarize(times: &[Duration]) -> Option<(Duration, Duration, Duration)> {
+    let min = *times.iter().min()?;
+    let max = *times.iter().max()?;
+    let mean = times.iter().sum::<Duration>() / times.len() as u32;
+
+    Some((mean, min, max))
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Collects everything for [`BENCH_TICKS`] ticks, printing how long each one
+/// took and a summary at the end.
+pub(crate) fn run(app_config_fields: &AppConfigFields, filters: DataFilters) -> anyhow::Result<()> {
+    let mut data_state = export::snapshot_collector(app_config_fields, filters);
+    data_state.init(app_config_fields.collection_flags);
+
+    let mut times = Vec::with_capacity(BENCH_TICKS);
+    for tick in 1..=BENCH_TICKS {
+        thread::sleep(BENCH_INTERVAL);
+
+        let start = Instant::now();
+        data_state.update_data();
+        let elapsed = start.elapsed();
+
+        let processes = data_state
+            .data
+            .list_of_processes
+            .as_ref()
+            .map_or(0, Vec::len);
+        println!(
+            "Tick {tick:>2}: {:>8.2}ms ({processes} processes)",
+            millis(elapsed)
+        );
+
+        times.push(elapsed);
+        data_state.data = Data::default();
+    }
+
+    if let Some((mean, min, max)) = summarize(&times) {
+        println!(
+            "\nMean {:.2}ms, min {:.2}ms, max {:.2}ms over {BENCH_TICKS} ticks",
+            millis(mean),
+            millis(min),
+            millis(max)
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let times = [10, 30, 20].map(Duration::from_millis);
+        assert_eq!(
+            summarize(&times),
+            Some((
+                Duration::from_millis(20),
+                Duration::from_millis(10),
+                Duration::from_millis(30)
+            ))
+        );
+        assert_eq!(summarize(&[]), None);
+    }
+}
@@ -3,10 +3,14 @@ mod dialogs;
 mod drawing_utils;
 mod widgets;
 
+use std::borrow::Cow;
+
 use itertools::izip;
 use tui::{
     backend::Backend,
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
     text::Span,
     widgets::Paragraph,
     Frame, Terminal,
@@ -14,16 +18,24 @@ use tui::{
 
 use crate::{
     app::{
-        layout_manager::{BottomColRow, BottomLayout, BottomWidgetType, IntermediaryConstraint},
+        clipboard::Flash,
+        layout_manager::{
+            BottomColRow, BottomLayout, BottomWidget, BottomWidgetType, IntermediaryConstraint,
+        },
         App,
     },
+    canvas::{
+        drawing_utils::{heartbeat_frame, quiet_title},
+        widgets::header::{header_height, shown_data},
+    },
     constants::*,
-    options::config::style::ColourPalette,
+    options::config::style::{BorderStyle, CanvasStyle, ColourPalette},
 };
 
 /// Handles the canvas' state.
 pub struct Painter {
     pub colours: ColourPalette,
+    canvas_style: CanvasStyle,
     previous_height: u16,
     previous_width: u16,
 
@@ -34,6 +46,16 @@ pub struct Painter {
     layout_constraints: Vec<Vec<Vec<Vec<LayoutConstraint>>>>,
     derived_widget_draw_locs: Vec<Vec<Vec<Vec<Rect>>>>,
     widget_layout: BottomLayout,
+
+    /// The last frame drawn, if it was just the widget layout and nothing over
+    /// it, so widgets that haven't changed can be copied from it rather than
+    /// drawn again.
+    previous_frame: Option<Buffer>,
+    /// The widget that was selected when the last frame was drawn.
+    previous_widget_id: u64,
+
+    /// Whether to only draw the widgets, with shorter titles.
+    quiet_mode: bool,
 }
 
 /// The constraints of a widget relative to its parent.
@@ -47,11 +69,36 @@ pub enum LayoutConstraint {
 }
 
 impl Painter {
-    pub fn init(layout: BottomLayout, styling: ColourPalette) -> anyhow::Result<Self> {
+    pub fn init(
+        layout: BottomLayout, styling: ColourPalette, canvas_style: CanvasStyle, quiet_mode: bool,
+    ) -> anyhow::Result<Self> {
+        let mut painter = Painter {
+            colours: styling,
+            canvas_style,
+            previous_height: 0,
+            previous_width: 0,
+            row_constraints: Vec::default(),
+            col_constraints: Vec::default(),
+            col_row_constraints: Vec::default(),
+            layout_constraints: Vec::default(),
+            widget_layout: layout,
+            derived_widget_draw_locs: Vec::default(),
+            previous_frame: None,
+            previous_widget_id: 0,
+            quiet_mode,
+        };
+        painter.init_layout_constraints();
+
+        Ok(painter)
+    }
+
+    /// Initializes the base layouts from the current widget layout.
+    fn init_layout_constraints(&mut self) {
         // Now for modularity; we have to also initialize the base layouts!
         // We want to do this ONCE and reuse; after this we can just construct
         // based on the console size.
 
+        let layout = &self.widget_layout;
         let mut row_constraints = Vec::new();
         let mut col_constraints = Vec::new();
         let mut col_row_constraints = Vec::new();
@@ -130,19 +177,28 @@ impl Painter {
             col_constraints.push(new_col_constraints);
         });
 
-        let painter = Painter {
-            colours: styling,
-            previous_height: 0,
-            previous_width: 0,
-            row_constraints,
-            col_constraints,
-            col_row_constraints,
-            layout_constraints,
-            widget_layout: layout,
-            derived_widget_draw_locs: Vec::default(),
+        self.row_constraints = row_constraints;
+        self.col_constraints = col_constraints;
+        self.col_row_constraints = col_row_constraints;
+        self.layout_constraints = layout_constraints;
+        self.derived_widget_draw_locs = Vec::default();
+    }
+
+    /// Swaps the current layout with the alternate one, selecting the widget
+    /// that was last selected in it.
+    fn switch_layout(&mut self, app_state: &mut App) {
+        let Some(alternate) = &mut app_state.alternate_layout else {
+            return;
         };
 
-        Ok(painter)
+        std::mem::swap(&mut self.widget_layout, &mut alternate.layout);
+        let widget_id =
+            std::mem::replace(&mut alternate.widget_id, app_state.current_widget.widget_id);
+        self.init_layout_constraints();
+
+        if let Some(widget) = app_state.widget_map.get(&widget_id) {
+            app_state.current_widget = widget.clone();
+        }
     }
 
     /// Determines the border style.
@@ -155,12 +211,65 @@ impl Painter {
         }
     }
 
-    fn draw_frozen_indicator(&self, f: &mut Frame<'_>, draw_loc: Rect) {
-        f.render_widget(
-            Paragraph::new(Span::styled(
+    /// The lines the borders of a widget of `widget_type` are drawn with.
+    pub fn widget_border(&self, widget_type: &BottomWidgetType) -> BorderStyle {
+        self.canvas_style.border_style(widget_type)
+    }
+
+    /// The lines the borders of dialogs are drawn with.
+    pub fn dialog_border(&self) -> BorderStyle {
+        self.canvas_style.dialog_border_style()
+    }
+
+    /// The title of a widget, which is only its first word in quiet mode.
+    pub fn widget_title<'a>(&self, title: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
+        let title = title.into();
+        if self.quiet_mode {
+            quiet_title(&title).into()
+        } else {
+            title
+        }
+    }
+
+    /// The border of a widget of `widget_type` in quiet mode while usage it
+    /// shows is over a threshold. As there's no status line to show alerts in
+    /// then, the border flashes by being shown on every other update.
+    pub fn alert_border_style(
+        &self, app_state: &App, widget_type: &BottomWidgetType,
+    ) -> Option<Style> {
+        if !self.quiet_mode || app_state.heartbeat_tick % 2 == 1 {
+            return None;
+        }
+
+        let alerts = app_state.event_log.active_alerts();
+        let is_alerting = match widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::BasicCpu => alerts.cpu,
+            BottomWidgetType::Disk => alerts.disk,
+            BottomWidgetType::Temp => alerts.temp,
+            _ => false,
+        };
+
+        is_alerting.then_some(self.colours.warning_text_style)
+    }
+
+    fn draw_frozen_indicator(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let indicator = match &app_state.freeze_alert {
+            Some(alert) => Span::styled(
+                format!(
+                    "FROZEN: {} at {} UTC — press 'f' to resume",
+                    alert.message,
+                    alert.clock_time()
+                ),
+                self.colours.warning_text_style,
+            ),
+            None => Span::styled(
                 "Frozen, press 'f' to unfreeze",
                 self.colours.selected_text_style,
-            )),
+            ),
+        };
+
+        f.render_widget(
+            Paragraph::new(indicator),
             Layout::default()
                 .horizontal_margin(1)
                 .constraints([Constraint::Length(1)])
@@ -168,13 +277,156 @@ impl Painter {
         )
     }
 
+    /// Draws the line at the bottom of the screen, which shows the replay timeline
+    /// when replaying, or otherwise whether we're frozen or waiting for a keypress
+    /// to refresh. Messages like the one
+    /// after copying something briefly take its place. The heartbeat, if shown,
+    /// is on the right, after the count of errors from reading data.
+    fn draw_status_line(&self, f: &mut Frame<'_>, app_state: &App, mut draw_loc: Rect) {
+        if app_state.is_showing_heartbeat() {
+            let heartbeat = self.heartbeat(app_state);
+            let [rest, heartbeat_loc] = Layout::horizontal([
+                Constraint::Min(0),
+                Constraint::Length(heartbeat.width() as u16 + 1),
+            ])
+            .areas(draw_loc);
+
+            f.render_widget(Paragraph::new(heartbeat), heartbeat_loc);
+            draw_loc = rest;
+        }
+
+        if let Some(badge) = self.error_badge(app_state) {
+            let [rest, badge_loc] = Layout::horizontal([
+                Constraint::Min(0),
+                Constraint::Length(badge.width() as u16 + 1),
+            ])
+            .areas(draw_loc);
+
+            f.render_widget(Paragraph::new(badge), badge_loc);
+            draw_loc = rest;
+        }
+
+        let is_frozen = app_state.frozen_state.is_frozen();
+
+        if let Some(flash) = app_state.flash.as_ref().filter(|flash| flash.is_showing()) {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    flash.message.as_str(),
+                    self.colours.selected_text_style,
+                )),
+                Layout::default()
+                    .horizontal_margin(1)
+                    .constraints([Constraint::Length(1)])
+                    .split(draw_loc)[0],
+            );
+            return;
+        }
+
+        match &app_state.replay {
+            Some(replay) => self.draw_replay_timeline(f, replay, is_frozen, draw_loc),
+            None if is_frozen => self.draw_frozen_indicator(f, app_state, draw_loc),
+            None if app_state.app_config_fields.update_on_key => f.render_widget(
+                Paragraph::new(Span::styled(
+                    "Press any key to refresh",
+                    self.colours.selected_text_style,
+                )),
+                Layout::default()
+                    .horizontal_margin(1)
+                    .constraints([Constraint::Length(1)])
+                    .split(draw_loc)[0],
+            ),
+            None => {}
+        }
+    }
+
+    /// How many times in a row data has failed to be read, if it has. This is
+    /// a warning, unless something has failed so often it was turned off.
+    /// The event log lists what failed.
+    fn error_badge(&self, app_state: &App) -> Option<Span<'static>> {
+        let data = shown_data(app_state);
+        let total = data.error_counts().total();
+        if total == 0 {
+            return None;
+        }
+
+        let style = if data.collection_errors.iter().any(|error| error.disabled) {
+            self.colours.invalid_query_style
+        } else {
+            self.colours.warning_text_style
+        };
+
+        Some(Span::styled(format!("\u{26a0}{total}"), style))
+    }
+
+    /// The spinner showing that updates are being received, or how long ago the
+    /// last one was if it's late.
+    fn heartbeat(&self, app_state: &App) -> Span<'static> {
+        match app_state.late_update_age() {
+            Some(age) => Span::styled(
+                format!("\u{2717} Last update: {}s ago", age.as_secs()),
+                self.colours.warning_text_style,
+            ),
+            None => Span::styled(
+                heartbeat_frame(app_state.heartbeat_tick).to_string(),
+                self.colours.text_style,
+            ),
+        }
+    }
+
     pub fn draw_data<B: Backend>(
         &mut self, terminal: &mut Terminal<B>, app_state: &mut App,
     ) -> Result<(), std::io::Error> {
         use BottomWidgetType::*;
 
-        terminal.draw(|f| {
-            let (terminal_size, frozen_draw_loc) = if app_state.frozen_state.is_frozen() {
+        if std::mem::take(&mut app_state.is_switching_layout) {
+            self.switch_layout(app_state);
+            self.previous_frame = None;
+        }
+
+        // Anything over the widgets, or a different layout of them, means the
+        // last frame can't be reused.
+        let is_plain_layout = !(app_state.help_dialog_state.is_showing_help
+            || app_state.event_log.is_showing
+            || app_state.delete_dialog_state.is_showing_dd
+            || app_state.is_showing_flame_graph()
+            || app_state.multi_host.is_some()
+            || app_state.is_expanded
+            || app_state.app_config_fields.use_basic_mode
+            || app_state.context_menu.is_some()
+            || app_state.threshold_editor.is_some()
+            || app_state.system_summary.is_showing
+            || app_state.meminfo.is_showing
+            || app_state.is_showing_load_graph
+            || app_state.is_showing_disk_bench_result());
+        let previous_frame = self.previous_frame.take().filter(|_| {
+            is_plain_layout && self.previous_widget_id == app_state.current_widget.widget_id
+        });
+
+        // The selected widget is always drawn, as it may be showing the cursor.
+        app_state
+            .dirty_widgets
+            .mark(&app_state.current_widget.widget_type);
+
+        // Flashing borders change on every update, even if nothing else did.
+        if self.quiet_mode && app_state.event_log.is_alerting() {
+            for widget_type in [
+                BottomWidgetType::Cpu,
+                BottomWidgetType::Disk,
+                BottomWidgetType::Temp,
+            ] {
+                app_state.dirty_widgets.mark(&widget_type);
+            }
+        }
+
+        let frame = terminal.draw(|f| {
+            let (terminal_size, status_draw_loc) = if !self.quiet_mode
+                && (app_state.frozen_state.is_frozen()
+                    || app_state.is_showing_heartbeat()
+                    || app_state.replay.is_some()
+                    || app_state.app_config_fields.update_on_key
+                    || app_state.flash.as_ref().is_some_and(Flash::is_showing)
+                    || self.error_badge(app_state).is_some())
+            {
                 // TODO: Remove built-in cache?
                 let split_loc = Layout::default()
                     .constraints([Constraint::Min(0), Constraint::Length(1)])
@@ -183,6 +435,20 @@ impl Painter {
             } else {
                 (f.area(), None)
             };
+            let terminal_size =
+                if app_state.app_config_fields.show_header && app_state.multi_host.is_none() {
+                    let split_loc = Layout::default()
+                        .constraints([
+                            Constraint::Length(header_height(app_state)),
+                            Constraint::Min(0),
+                        ])
+                        .split(terminal_size);
+                    self.draw_header(f, app_state, split_loc[0]);
+                    split_loc[1]
+                } else {
+                    app_state.header_load_loc = None;
+                    terminal_size
+                };
             let terminal_height = terminal_size.height;
             let terminal_width = terminal_size.width;
 
@@ -243,6 +509,34 @@ impl Painter {
                     .split(vertical_dialog_chunk[1]);
 
                 self.draw_help_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.event_log.is_showing {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(80),
+                        Constraint::Percentage(10),
+                    ])
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(if terminal_width < 100 {
+                        [
+                            Constraint::Percentage(0),
+                            Constraint::Percentage(100),
+                            Constraint::Percentage(0),
+                        ]
+                    } else {
+                        [
+                            Constraint::Percentage(15),
+                            Constraint::Percentage(70),
+                            Constraint::Percentage(15),
+                        ]
+                    })
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_event_log_dialog(f, app_state, middle_dialog_chunk[1]);
             } else if app_state.delete_dialog_state.is_showing_dd {
                 let dd_text = self.get_dd_spans(app_state);
 
@@ -283,9 +577,14 @@ impl Painter {
                 // This is a bit nasty, but it works well... I guess.
                 app_state.delete_dialog_state.is_showing_dd =
                     self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
+            } else if app_state.is_showing_flame_graph() {
+                #[cfg(feature = "flame")]
+                self.draw_flame_graph(f, app_state, terminal_size);
+            } else if app_state.multi_host.is_some() {
+                self.draw_multi_host(f, app_state, terminal_size);
             } else if app_state.is_expanded {
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let rect = Layout::default()
@@ -318,6 +617,30 @@ impl Painter {
                         rect[0],
                         app_state.current_widget.widget_id,
                     ),
+                    Startup => self.draw_startup_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Exec => self.draw_exec_output(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    UserProcs => self.draw_user_proc_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    LogTail => self.draw_log_tail(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     Net => self.draw_network_graph(
                         f,
                         app_state,
@@ -347,8 +670,8 @@ impl Painter {
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 let actual_cpu_data_len = app_state.converted_data.cpu_data.len().saturating_sub(1);
@@ -372,8 +695,20 @@ impl Painter {
 
                 let mut mem_rows = 1;
 
+                if app_state.converted_data.pss_label.is_some() {
+                    mem_rows += 1; // add row for PSS
+                }
+
+                if app_state.converted_data.cgroup_memory_label.is_some() {
+                    mem_rows += 1; // add row for the cgroup's limits
+                }
+
                 if app_state.converted_data.swap_labels.is_some() {
                     mem_rows += 1; // add row for swap
+
+                    if app_state.converted_data.swap_io_label.is_some() {
+                        mem_rows += 1; // add row for swap I/O
+                    }
                 }
 
                 #[cfg(feature = "zfs")]
@@ -466,8 +801,8 @@ impl Painter {
                 }
             } else {
                 // Draws using the passed in (or default) layout.
-                if let Some(frozen_draw_loc) = frozen_draw_loc {
-                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                if let Some(status_draw_loc) = status_draw_loc {
+                    self.draw_status_line(f, app_state, status_draw_loc);
                 }
 
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
@@ -685,6 +1020,10 @@ impl Painter {
                     )
                     .collect();
                 } else {
+                    let previous_frame = previous_frame.as_ref().filter(|frame| {
+                        frame.area == f.area() && !app_state.should_get_widget_bounds()
+                    });
+
                     self.widget_layout
                         .rows
                         .iter()
@@ -692,17 +1031,61 @@ impl Painter {
                         .flat_map(|col| &col.children)
                         .zip(self.derived_widget_draw_locs.iter().flatten().flatten())
                         .for_each(|(widgets, widget_draw_locs)| {
-                            self.draw_widgets_with_constraints(
-                                f,
-                                app_state,
-                                widgets,
-                                widget_draw_locs,
-                            );
+                            if let Some(previous_frame) = previous_frame {
+                                self.draw_changed_widgets(
+                                    f,
+                                    app_state,
+                                    widgets,
+                                    widget_draw_locs,
+                                    previous_frame,
+                                );
+                            } else {
+                                self.draw_widgets_with_constraints(
+                                    f,
+                                    app_state,
+                                    widgets,
+                                    widget_draw_locs,
+                                );
+                            }
                         });
                 }
             }
+
+            if let Some(context_menu) = &mut app_state.context_menu {
+                self.draw_context_menu(f, context_menu, f.area());
+            }
+
+            if let Some(editor) = &app_state.threshold_editor {
+                self.draw_threshold_editor(f, editor, f.area());
+            }
+
+            if app_state.system_summary.is_showing {
+                self.draw_system_summary_dialog(f, &mut app_state.system_summary, f.area());
+            }
+
+            if app_state.meminfo.is_showing {
+                self.draw_meminfo_dialog(f, &mut app_state.meminfo, f.area());
+            }
+
+            if app_state.is_showing_load_graph {
+                self.draw_load_graph_dialog(f, app_state, f.area());
+            }
+
+            if let Some(disk_bench) = app_state
+                .disk_bench
+                .as_ref()
+                .filter(|disk_bench| disk_bench.is_showing_result)
+            {
+                self.draw_disk_bench_dialog(f, disk_bench, f.area());
+            }
         })?;
 
+        if is_plain_layout {
+            let mut buffer = previous_frame.unwrap_or_default();
+            buffer.clone_from(frame.buffer);
+            self.previous_frame = Some(buffer);
+        }
+
         if let Some(updated_current_widget) = app_state
             .widget_map
             .get(&app_state.current_widget.widget_id)
@@ -710,8 +1093,10 @@ impl Painter {
             app_state.current_widget = updated_current_widget.clone();
         }
 
+        self.previous_widget_id = app_state.current_widget.widget_id;
         app_state.is_force_redraw = false;
         app_state.is_determining_widget_boundary = false;
+        app_state.dirty_widgets.clear();
 
         Ok(())
     }
@@ -720,20 +1105,52 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut App, widgets: &BottomColRow,
         widget_draw_locs: &[Rect],
     ) {
-        use BottomWidgetType::*;
         for (widget, draw_loc) in widgets.children.iter().zip(widget_draw_locs) {
-            if draw_loc.width >= 2 && draw_loc.height >= 2 {
-                match &widget.widget_type {
-                    Cpu => self.draw_cpu(f, app_state, *draw_loc, widget.widget_id),
-                    Mem => self.draw_memory_graph(f, app_state, *draw_loc, widget.widget_id),
-                    Net => self.draw_network(f, app_state, *draw_loc, widget.widget_id),
-                    Temp => self.draw_temp_table(f, app_state, *draw_loc, widget.widget_id),
-                    Disk => self.draw_disk_table(f, app_state, *draw_loc, widget.widget_id),
-                    Proc => self.draw_process(f, app_state, *draw_loc, true, widget.widget_id),
-                    Battery => self.draw_battery(f, app_state, *draw_loc, true, widget.widget_id),
-                    _ => {}
+            self.draw_widget(f, app_state, widget, *draw_loc);
+        }
+    }
+
+    /// Like [`Self::draw_widgets_with_constraints`], but widgets that haven't
+    /// changed since `previous_frame` are copied from it instead.
+    fn draw_changed_widgets(
+        &self, f: &mut Frame<'_>, app_state: &mut App, widgets: &BottomColRow,
+        widget_draw_locs: &[Rect], previous_frame: &Buffer,
+    ) {
+        for (widget, draw_loc) in widgets.children.iter().zip(widget_draw_locs) {
+            if app_state.dirty_widgets.is_dirty(&widget.widget_type) {
+                self.draw_widget(f, app_state, widget, *draw_loc);
+            } else {
+                let buffer = f.buffer_mut();
+                for position in draw_loc.positions() {
+                    if let (Some(cell), Some(previous)) =
+                        (buffer.cell_mut(position), previous_frame.cell(position))
+                    {
+                        cell.clone_from(previous);
+                    }
                 }
             }
         }
     }
+
+    fn draw_widget(
+        &self, f: &mut Frame<'_>, app_state: &mut App, widget: &BottomWidget, draw_loc: Rect,
+    ) {
+        use BottomWidgetType::*;
+        if draw_loc.width >= 2 && draw_loc.height >= 2 {
+            match &widget.widget_type {
+                Cpu => self.draw_cpu(f, app_state, draw_loc, widget.widget_id),
+                Mem => self.draw_memory_graph(f, app_state, draw_loc, widget.widget_id),
+                Net => self.draw_network(f, app_state, draw_loc, widget.widget_id),
+                Temp => self.draw_temp_table(f, app_state, draw_loc, widget.widget_id),
+                Disk => self.draw_disk_table(f, app_state, draw_loc, widget.widget_id),
+                Startup => self.draw_startup_table(f, app_state, draw_loc, widget.widget_id),
+                Exec => self.draw_exec_output(f, app_state, draw_loc, widget.widget_id),
+                UserProcs => self.draw_user_proc_table(f, app_state, draw_loc, widget.widget_id),
+                LogTail => self.draw_log_tail(f, app_state, draw_loc, widget.widget_id),
+                Proc => self.draw_process(f, app_state, draw_loc, true, widget.widget_id),
+                Battery => self.draw_battery(f, app_state, draw_loc, true, widget.widget_id),
+                _ => {}
+            }
+        }
+    }
 }
@@ -6,7 +6,7 @@ pub mod sortable;
 pub mod state;
 pub mod styling;
 
-use std::{convert::TryInto, marker::PhantomData};
+use std::{convert::TryInto, marker::PhantomData, num::NonZeroU16};
 
 pub use column::*;
 pub use data_type::*;
@@ -147,10 +147,71 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         self.data.get(self.state.current_index)
     }
 
+    /// Returns the cells of the current item in full, separated by tabs.
+    /// Hidden columns are skipped.
+    pub fn current_row_text(&self) -> Option<String> {
+        let item = self.current_item()?;
+
+        Some(
+            self.columns
+                .iter()
+                .filter(|column| !column.is_hidden())
+                .map(|column| {
+                    item.to_cell(column.inner(), NonZeroU16::MAX)
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join("\t"),
+        )
+    }
+
     /// Returns ratatui's internal selection.
     pub fn ratatui_selected(&self) -> Option<usize> {
         self.state.table_state.selected()
     }
+
+    /// The indices of the shown columns that aren't pinned in place.
+    fn scrollable_columns(&self) -> impl Iterator<Item = usize> + '_ {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_hidden() && !column.is_pinned())
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the indices of the columns to try and draw: all pinned columns,
+    /// followed by the unpinned ones from the current horizontal offset.
+    pub fn columns_to_draw(&mut self) -> Vec<usize> {
+        let num_scrollable = self.scrollable_columns().count();
+        self.state.column_offset = self
+            .state
+            .column_offset
+            .clamp_upper(num_scrollable.saturating_sub(1));
+
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_hidden() && column.is_pinned())
+            .map(|(index, _)| index)
+            .chain(self.scrollable_columns().skip(self.state.column_offset))
+            .collect()
+    }
+
+    /// Scrolls the unpinned columns horizontally by a positive/negative number
+    /// of columns. Scrolling right stops once the last column is shown. Returns
+    /// whether the offset changed.
+    pub fn scroll_columns(&mut self, change: i64) -> bool {
+        let current = self.state.column_offset;
+        let proposed = if change < 0 {
+            current.saturating_sub(change.unsigned_abs() as usize)
+        } else {
+            let max_change = self.state.columns_cut_off;
+            current + (change as usize).clamp_upper(max_change)
+        };
+
+        self.state.column_offset = proposed;
+        proposed != current
+    }
 }
 
 #[cfg(test)]
@@ -166,9 +227,9 @@ mod test {
 
     impl DataToCell<&'static str> for TestType {
         fn to_cell(
-            &self, _column: &&'static str, _calculated_width: NonZeroU16,
+            &self, column: &&'static str, _calculated_width: NonZeroU16,
         ) -> Option<Cow<'static, str>> {
-            None
+            (*column != "empty").then(|| format!("{column}{}", self.index).into())
         }
 
         fn column_widths<C: DataTableColumn<&'static str>>(
@@ -186,6 +247,7 @@ mod test {
         let columns = [Column::hard("a", 10), Column::hard("b", 10)];
         let props = DataTableProps {
             title: Some("test".into()),
+            footer: None,
             table_gap: 1,
             left_to_right: false,
             is_basic: false,
@@ -249,4 +311,68 @@ mod test {
         assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
     }
+
+    #[test]
+    fn test_current_row_text() {
+        let columns = [
+            Column::hard("a", 10),
+            Column::hard("empty", 10),
+            Column::hard("b", 10),
+        ];
+        let props = DataTableProps {
+            title: None,
+            footer: None,
+            table_gap: 1,
+            left_to_right: true,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_current_entry_when_unfocused: false,
+        };
+
+        let mut table = DataTable::new(columns, props, DataTableStyling::default());
+        assert_eq!(table.current_row_text(), None);
+
+        table.set_data((0..=2).map(|index| TestType { index }).collect::<Vec<_>>());
+        table.set_position(1);
+        assert_eq!(table.current_row_text().as_deref(), Some("a1\t\tb1"));
+    }
+
+    #[test]
+    fn test_column_scrolling() {
+        let columns = [
+            Column::hard("a", 10),
+            Column::hard("b", 10),
+            Column::hard("c", 10),
+        ];
+        let props = DataTableProps {
+            title: Some("test".into()),
+            footer: None,
+            table_gap: 1,
+            left_to_right: true,
+            is_basic: false,
+            show_table_scroll_position: true,
+            show_current_entry_when_unfocused: false,
+        };
+        let styling = DataTableStyling::default();
+
+        let mut table = DataTable::<TestType, _>::new(columns, props, styling);
+        assert_eq!(table.columns_to_draw(), vec![0, 1, 2]);
+
+        // Nothing is cut off, so there's nothing to scroll to.
+        assert!(!table.scroll_columns(1));
+        assert!(!table.scroll_columns(-1));
+
+        table.state.columns_cut_off = 1;
+        assert!(table.scroll_columns(5));
+        assert_eq!(table.state.column_offset, 1);
+        assert_eq!(table.columns_to_draw(), vec![1, 2]);
+
+        assert!(table.scroll_columns(-5));
+        assert_eq!(table.state.column_offset, 0);
+
+        // The offset is clamped if columns are removed.
+        table.state.column_offset = 10;
+        assert_eq!(table.columns_to_draw(), vec![2]);
+        assert_eq!(table.state.column_offset, 2);
+    }
 }
@@ -62,6 +62,12 @@ pub trait DataTableColumn<H: ColumnHeader> {
 
     fn is_hidden(&self) -> bool;
 
+    /// Whether this column stays in place when the table is scrolled
+    /// horizontally. Defaults to `false`.
+    fn is_pinned(&self) -> bool {
+        false
+    }
+
     /// The actually displayed "header".
     fn header(&self) -> Cow<'static, str>;
 
@@ -136,139 +142,137 @@ impl<H: ColumnHeader> Column<H> {
             is_hidden: false,
         }
     }
-}
 
-pub trait CalculateColumnWidths<H> {
-    /// Calculates widths for the columns of this table, given the current width
-    /// when called.
-    ///
-    /// * `total_width` is the total width on the canvas that the columns can
-    ///   try and work with.
-    /// * `left_to_right` is whether to size from left-to-right (`true`) or
-    ///   right-to-left (`false`).
-    fn calculate_column_widths(&self, total_width: u16, left_to_right: bool) -> Vec<NonZeroU16>;
+    pub fn set_is_hidden(&mut self, is_hidden: bool) {
+        self.is_hidden = is_hidden;
+    }
 }
 
-impl<H, C> CalculateColumnWidths<H> for [C]
+/// Calculates widths for the given columns, given the current width when
+/// called.
+///
+/// * `total_width` is the total width on the canvas that the columns can try
+///   and work with.
+/// * `left_to_right` is whether to size from left-to-right (`true`) or
+///   right-to-left (`false`).
+pub fn calculate_widths<'a, H, C>(
+    columns: impl DoubleEndedIterator<Item = &'a C>, total_width: u16, left_to_right: bool,
+) -> Vec<NonZeroU16>
 where
     H: ColumnHeader,
-    C: DataTableColumn<H>,
+    C: DataTableColumn<H> + 'a,
 {
-    fn calculate_column_widths(&self, total_width: u16, left_to_right: bool) -> Vec<NonZeroU16> {
-        use itertools::Either;
+    use itertools::Either;
 
-        const COLUMN_SPACING: u16 = 1;
+    const COLUMN_SPACING: u16 = 1;
 
-        #[inline]
-        fn stop_allocating_space(desired: u16, available: u16) -> bool {
-            desired > available || desired == 0
-        }
+    #[inline]
+    fn stop_allocating_space(desired: u16, available: u16) -> bool {
+        desired > available || desired == 0
+    }
 
-        let mut total_width_left = total_width;
-        let mut calculated_widths = vec![];
-        let columns = if left_to_right {
-            Either::Left(self.iter())
-        } else {
-            Either::Right(self.iter().rev())
-        };
-
-        for column in columns {
-            if column.is_hidden() {
-                continue;
-            }
+    let mut total_width_left = total_width;
+    let mut calculated_widths = vec![];
+    let columns = if left_to_right {
+        Either::Left(columns)
+    } else {
+        Either::Right(columns.rev())
+    };
+
+    for column in columns {
+        if column.is_hidden() {
+            continue;
+        }
 
-            match &column.bounds() {
-                ColumnWidthBounds::Soft {
-                    desired,
-                    max_percentage,
-                } => {
-                    let min_width = column.header_len() as u16;
-                    if min_width > total_width_left {
-                        break;
-                    }
+        match &column.bounds() {
+            ColumnWidthBounds::Soft {
+                desired,
+                max_percentage,
+            } => {
+                let min_width = column.header_len() as u16;
+                if min_width > total_width_left {
+                    break;
+                }
 
-                    let soft_limit = max(
-                        if let Some(max_percentage) = max_percentage {
-                            ((*max_percentage * f32::from(total_width)).ceil()) as u16
-                        } else {
-                            *desired
-                        },
-                        min_width,
-                    );
-                    let space_taken = min(min(soft_limit, *desired), total_width_left);
-
-                    if stop_allocating_space(space_taken, total_width_left) {
-                        break;
+                let soft_limit = max(
+                    if let Some(max_percentage) = max_percentage {
+                        ((*max_percentage * f32::from(total_width)).ceil()) as u16
                     } else {
-                        total_width_left =
-                            total_width_left.saturating_sub(space_taken + COLUMN_SPACING);
-
-                        // SAFETY: This is safe as we call `stop_allocating_space` which checks that
-                        // the value pushed is greater than zero.
-                        unsafe {
-                            calculated_widths.push(NonZeroU16::new_unchecked(space_taken));
-                        }
+                        *desired
+                    },
+                    min_width,
+                );
+                let space_taken = min(min(soft_limit, *desired), total_width_left);
+
+                if stop_allocating_space(space_taken, total_width_left) {
+                    break;
+                } else {
+                    total_width_left =
+                        total_width_left.saturating_sub(space_taken + COLUMN_SPACING);
+
+                    // SAFETY: This is safe as we call `stop_allocating_space` which checks that
+                    // the value pushed is greater than zero.
+                    unsafe {
+                        calculated_widths.push(NonZeroU16::new_unchecked(space_taken));
                     }
                 }
-                ColumnWidthBounds::Hard(width) => {
-                    let min_width = *width;
-                    if stop_allocating_space(min_width, total_width_left) {
-                        break;
-                    } else {
-                        total_width_left =
-                            total_width_left.saturating_sub(min_width + COLUMN_SPACING);
-
-                        // SAFETY: This is safe as we call `stop_allocating_space` which checks that
-                        // the value pushed is greater than zero.
-                        unsafe {
-                            calculated_widths.push(NonZeroU16::new_unchecked(min_width));
-                        }
+            }
+            ColumnWidthBounds::Hard(width) => {
+                let min_width = *width;
+                if stop_allocating_space(min_width, total_width_left) {
+                    break;
+                } else {
+                    total_width_left = total_width_left.saturating_sub(min_width + COLUMN_SPACING);
+
+                    // SAFETY: This is safe as we call `stop_allocating_space` which checks that
+                    // the value pushed is greater than zero.
+                    unsafe {
+                        calculated_widths.push(NonZeroU16::new_unchecked(min_width));
                     }
                 }
-                ColumnWidthBounds::FollowHeader => {
-                    let min_width = column.header_len() as u16;
-                    if stop_allocating_space(min_width, total_width_left) {
-                        break;
-                    } else {
-                        total_width_left =
-                            total_width_left.saturating_sub(min_width + COLUMN_SPACING);
-
-                        // SAFETY: This is safe as we call `stop_allocating_space` which checks that
-                        // the value pushed is greater than zero.
-                        unsafe {
-                            calculated_widths.push(NonZeroU16::new_unchecked(min_width));
-                        }
+            }
+            ColumnWidthBounds::FollowHeader => {
+                let min_width = column.header_len() as u16;
+                if stop_allocating_space(min_width, total_width_left) {
+                    break;
+                } else {
+                    total_width_left = total_width_left.saturating_sub(min_width + COLUMN_SPACING);
+
+                    // SAFETY: This is safe as we call `stop_allocating_space` which checks that
+                    // the value pushed is greater than zero.
+                    unsafe {
+                        calculated_widths.push(NonZeroU16::new_unchecked(min_width));
                     }
                 }
             }
         }
+    }
 
-        if !calculated_widths.is_empty() {
-            if !left_to_right {
-                calculated_widths.reverse();
-            }
-
-            // Redistribute remaining space.
-            let mut num_dist = calculated_widths.len() as u16;
-            let amount_per_slot = total_width_left / num_dist; // Safe from DBZ by above empty check.
-            total_width_left %= num_dist;
+    if !calculated_widths.is_empty() {
+        if !left_to_right {
+            calculated_widths.reverse();
+        }
 
-            for width in calculated_widths.iter_mut() {
-                if num_dist == 0 {
-                    break;
-                }
+        // Redistribute remaining space.
+        let mut num_dist = calculated_widths.len() as u16;
+        let amount_per_slot = total_width_left / num_dist; // Safe from DBZ by above empty check.
+        total_width_left %= num_dist;
 
-                if total_width_left > 0 {
-                    *width = width.saturating_add(amount_per_slot + 1);
-                    total_width_left -= 1;
-                } else {
-                    *width = width.saturating_add(amount_per_slot);
-                }
+        for width in calculated_widths.iter_mut() {
+            if num_dist == 0 {
+                break;
+            }
 
-                num_dist -= 1;
+            if total_width_left > 0 {
+                *width = width.saturating_add(amount_per_slot + 1);
+                total_width_left -= 1;
+            } else {
+                *width = width.saturating_add(amount_per_slot);
             }
-        }
 
-        calculated_widths
+            num_dist -= 1;
+        }
     }
+
+    calculated_widths
 }
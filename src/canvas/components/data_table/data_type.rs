@@ -1,6 +1,9 @@
 use std::{borrow::Cow, num::NonZeroU16};
 
-use tui::widgets::Row;
+use tui::{
+    text::Line,
+    widgets::{Cell, Row},
+};
 
 use super::{ColumnHeader, DataTableColumn};
 use crate::canvas::Painter;
@@ -14,6 +17,17 @@ where
     /// [`DataTable`](super::DataTable).
     fn to_cell(&self, column: &H, calculated_width: NonZeroU16) -> Option<Cow<'static, str>>;
 
+    /// Like [`DataToCell::to_cell`], but for cells that mix several styles. If
+    /// [`None`] is returned, [`DataToCell::to_cell`] is used instead.
+    ///
+    /// The default implementation always returns [`None`].
+    #[inline(always)]
+    fn to_styled_cell(
+        &self, _column: &H, _calculated_width: NonZeroU16, _painter: &Painter,
+    ) -> Option<Line<'static>> {
+        None
+    }
+
     /// Apply styling to the generated [`Row`] of cells.
     ///
     /// The default implementation just returns the `row` that is passed in.
@@ -22,6 +36,15 @@ where
         row
     }
 
+    /// Apply styling to a single cell of the given column, which takes
+    /// precedence over the styling of its row.
+    ///
+    /// The default implementation just returns the `cell` that is passed in.
+    #[inline(always)]
+    fn style_cell<'a>(&self, _column: &H, cell: Cell<'a>, _painter: &Painter) -> Cell<'a> {
+        cell
+    }
+
     /// Returns the desired column widths in light of having seen data.
     fn column_widths<C: DataTableColumn<H>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
@@ -7,20 +7,18 @@ use concat_string::concat_string;
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Row, Table},
+    widgets::{Block, Borders, Cell, Row, Table},
     Frame,
 };
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::{
-    CalculateColumnWidths, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataToCell,
+    calculate_widths, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataToCell,
     SortType,
 };
 use crate::{
-    app::layout_manager::BottomWidget,
-    canvas::Painter,
-    constants::{SIDE_BORDERS, TABLE_GAP_HEIGHT_LIMIT},
-    utils::strings::truncate_to_text,
+    app::layout_manager::BottomWidget, canvas::Painter, constants::TABLE_GAP_HEIGHT_LIMIT,
+    options::config::style::BorderStyle, utils::strings::truncate_to_text,
 };
 
 pub enum SelectionState {
@@ -47,6 +45,8 @@ pub struct DrawInfo {
     pub force_redraw: bool,
     pub recalculate_column_widths: bool,
     pub selection_state: SelectionState,
+    /// The lines the table's border is drawn with.
+    pub border: BorderStyle,
 }
 
 impl DrawInfo {
@@ -67,29 +67,31 @@ where
     S: SortType,
     C: DataTableColumn<H>,
 {
-    fn block<'a>(&self, draw_info: &'a DrawInfo, data_len: usize) -> Block<'a> {
-        let border_style = match draw_info.selection_state {
-            SelectionState::NotSelected => self.styling.border_style,
-            SelectionState::Selected | SelectionState::Expanded => {
-                self.styling.highlighted_border_style
-            }
-        };
+    fn block<'a>(&self, draw_info: &'a DrawInfo, data_len: usize, painter: &Painter) -> Block<'a> {
+        let border_style =
+            self.styling
+                .alert_border_style
+                .unwrap_or(match draw_info.selection_state {
+                    SelectionState::NotSelected => self.styling.border_style,
+                    SelectionState::Selected | SelectionState::Expanded => {
+                        self.styling.highlighted_border_style
+                    }
+                });
 
         if !self.props.is_basic {
-            let block = Block::default()
-                .borders(Borders::ALL)
+            let block = draw_info
+                .border
+                .block(draw_info.is_on_widget())
                 .border_style(border_style);
 
-            if let Some(title) = self.generate_title(draw_info, data_len) {
+            if let Some(title) = self.generate_title(draw_info, data_len, painter) {
                 block.title(title)
             } else {
                 block
             }
         } else if draw_info.is_on_widget() {
             // Implies it is basic mode but selected.
-            Block::default()
-                .borders(SIDE_BORDERS)
-                .border_style(border_style)
+            draw_info.border.side_block().border_style(border_style)
         } else {
             Block::default().borders(Borders::NONE)
         }
@@ -97,9 +99,10 @@ where
 
     /// Generates a title, given the available space.
     pub fn generate_title<'a>(
-        &self, draw_info: &'a DrawInfo, total_items: usize,
+        &self, draw_info: &'a DrawInfo, total_items: usize, painter: &Painter,
     ) -> Option<Line<'a>> {
         self.props.title.as_ref().map(|title| {
+            let title = painter.widget_title(title.as_ref());
             let current_index = self.state.current_index.saturating_add(1);
             let draw_loc = draw_info.loc;
             let title_style = self.styling.title_style;
@@ -124,11 +127,12 @@ where
             };
 
             if draw_info.is_expanded() {
-                let title_base = concat_string!(title, "── Esc to go back ");
-                let lines = "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                let horizontal = draw_info.border.horizontal();
+                let title_base = concat_string!(title, horizontal, horizontal, " Esc to go back ");
+                let lines = horizontal.repeat(usize::from(draw_loc.width).saturating_sub(
                     UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2,
                 ));
-                let esc = concat_string!("─", lines, "─ Esc to go back ");
+                let esc = concat_string!(horizontal, lines, horizontal, " Esc to go back ");
                 Line::from(vec![
                     Span::styled(title, title_style),
                     Span::styled(esc, border_style),
@@ -139,6 +143,46 @@ where
         })
     }
 
+    /// Returns an indicator of which unpinned columns are shown, if the table
+    /// can be scrolled horizontally, e.g. `◂ 3-8 of 15 ▸`.
+    fn column_scroll_indicator(&self) -> Option<Span<'static>> {
+        let offset = self.state.column_offset;
+        let cut_off = self.state.columns_cut_off;
+        if self.props.is_basic || (offset == 0 && cut_off == 0) {
+            return None;
+        }
+
+        let num_shown = self
+            .state
+            .visible_columns
+            .iter()
+            .filter(|&&index| !self.columns[index].is_pinned())
+            .count();
+        let first = (offset + 1).to_string();
+        let last = (offset + num_shown).to_string();
+        let total = (offset + num_shown + cut_off).to_string();
+        let left = if offset > 0 { "◂ " } else { "" };
+        let right = if cut_off > 0 { " ▸" } else { "" };
+
+        Some(Span::styled(
+            concat_string!(" ", left, first, "-", last, " of ", total, right, " "),
+            self.styling.title_style,
+        ))
+    }
+
+    /// Returns the footer to show on the bottom border, if there is one.
+    /// Basic tables have no border to show it on.
+    fn footer(&self) -> Option<Span<'static>> {
+        if self.props.is_basic {
+            return None;
+        }
+
+        self.props
+            .footer
+            .as_ref()
+            .map(|footer| Span::styled(footer.to_string(), self.styling.title_style))
+    }
+
     pub fn draw(
         &mut self, f: &mut Frame<'_>, draw_info: &DrawInfo, widget: Option<&mut BottomWidget>,
         painter: &Painter,
@@ -151,7 +195,7 @@ where
             .direction(Direction::Horizontal)
             .split(draw_loc)[0];
 
-        let block = self.block(draw_info, self.data.len());
+        let mut block = self.block(draw_info, self.data.len(), painter);
 
         let (inner_width, inner_height) = {
             let inner_rect = block.inner(margined_draw_loc);
@@ -180,9 +224,24 @@ where
                         }
                     });
 
-                self.state.calculated_widths = self
-                    .columns
-                    .calculate_column_widths(inner_width, self.props.left_to_right);
+                let mut visible_columns = self.columns_to_draw();
+                let calculated_widths = calculate_widths(
+                    visible_columns.iter().map(|&index| &self.columns[index]),
+                    inner_width,
+                    self.props.left_to_right,
+                );
+
+                // Only keep the columns that actually fit.
+                let num_cut_off = visible_columns.len() - calculated_widths.len();
+                if self.props.left_to_right {
+                    visible_columns.truncate(calculated_widths.len());
+                } else {
+                    visible_columns.drain(..num_cut_off);
+                }
+
+                self.state.calculated_widths = calculated_widths;
+                self.state.visible_columns = visible_columns;
+                self.state.columns_cut_off = num_cut_off;
 
                 // Update draw loc in widget map
                 if let Some(widget) = widget {
@@ -192,6 +251,13 @@ where
                 }
             }
 
+            if let Some(footer) = self.footer() {
+                block = block.title_bottom(Line::from(footer).left_aligned());
+            }
+            if let Some(indicator) = self.column_scroll_indicator() {
+                block = block.title_bottom(Line::from(indicator).right_aligned());
+            }
+
             let show_header = inner_height > 1;
             let header_height = u16::from(show_header);
             let table_gap = if !show_header || draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
@@ -220,16 +286,26 @@ where
                     self.state
                         .table_state
                         .select(Some(self.state.current_index.saturating_sub(start)));
+                    let visible_columns = &self.state.visible_columns;
 
                     self.data[start..end].iter().map(|data_row| {
                         let row = Row::new(
-                            columns
+                            visible_columns
                                 .iter()
                                 .zip(&self.state.calculated_widths)
-                                .filter_map(|(column, &width)| {
+                                .filter_map(|(&index, &width)| {
+                                    let column = &columns[index];
                                     data_row
-                                        .to_cell(column.inner(), width)
-                                        .map(|content| truncate_to_text(&content, width.get()))
+                                        .to_styled_cell(column.inner(), width, painter)
+                                        .map(Cell::from)
+                                        .or_else(|| {
+                                            data_row.to_cell(column.inner(), width).map(|content| {
+                                                Cell::from(truncate_to_text(&content, width.get()))
+                                            })
+                                        })
+                                        .map(|cell| {
+                                            data_row.style_cell(column.inner(), cell, painter)
+                                        })
                                 }),
                         );
 
@@ -239,7 +315,11 @@ where
 
                 let headers = self
                     .sort_type
-                    .build_header(columns, &self.state.calculated_widths)
+                    .build_header(
+                        columns,
+                        &self.state.visible_columns,
+                        &self.state.calculated_widths,
+                    )
                     .style(self.styling.header_style)
                     .bottom_margin(table_gap);
 
@@ -4,6 +4,9 @@ pub struct DataTableProps {
     /// An optional title for the table.
     pub title: Option<Cow<'static, str>>,
 
+    /// An optional note shown on the bottom border of the table.
+    pub footer: Option<Cow<'static, str>>,
+
     /// The size of the gap between the header and rows.
     pub table_gap: u16,
 
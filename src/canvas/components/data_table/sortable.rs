@@ -57,17 +57,18 @@ pub struct Sortable {
 /// Note that the trait is [sealed](https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed),
 /// and therefore only [`Unsortable`] and [`Sortable`] can implement it.
 pub trait SortType: private::Sealed {
-    /// Constructs the table header.
-    fn build_header<H, C>(&self, columns: &[C], widths: &[NonZeroU16]) -> Row<'_>
+    /// Constructs the table header, given the indices of the columns being
+    /// drawn and their widths.
+    fn build_header<H, C>(&self, columns: &[C], visible: &[usize], widths: &[NonZeroU16]) -> Row<'_>
     where
         H: ColumnHeader,
         C: DataTableColumn<H>,
     {
         Row::new(
-            columns
+            visible
                 .iter()
                 .zip(widths)
-                .map(|(c, &width)| truncate_to_text(&c.header(), width.get())),
+                .map(|(&index, &width)| truncate_to_text(&columns[index].header(), width.get())),
         )
     }
 }
@@ -84,7 +85,7 @@ mod private {
 impl SortType for Unsortable {}
 
 impl SortType for Sortable {
-    fn build_header<H, C>(&self, columns: &[C], widths: &[NonZeroU16]) -> Row<'_>
+    fn build_header<H, C>(&self, columns: &[C], visible: &[usize], widths: &[NonZeroU16]) -> Row<'_>
     where
         H: ColumnHeader,
         C: DataTableColumn<H>,
@@ -92,27 +93,22 @@ impl SortType for Sortable {
         const UP_ARROW: &str = "▲";
         const DOWN_ARROW: &str = "▼";
 
-        Row::new(
-            columns
-                .iter()
-                .zip(widths)
-                .enumerate()
-                .map(|(index, (c, &width))| {
-                    if index == self.sort_index {
-                        let arrow = match self.order {
-                            SortOrder::Ascending => UP_ARROW,
-                            SortOrder::Descending => DOWN_ARROW,
-                        };
-                        // TODO: I think I can get away with removing the truncate_to_text call
-                        // since I almost always bind to at least the header
-                        // size... TODO: Or should we instead truncate but
-                        // ALWAYS leave the arrow at the end?
-                        truncate_to_text(&concat_string!(c.header(), arrow), width.get())
-                    } else {
-                        truncate_to_text(&c.header(), width.get())
-                    }
-                }),
-        )
+        Row::new(visible.iter().zip(widths).map(|(&index, &width)| {
+            let c = &columns[index];
+            if index == self.sort_index {
+                let arrow = match self.order {
+                    SortOrder::Ascending => UP_ARROW,
+                    SortOrder::Descending => DOWN_ARROW,
+                };
+                // TODO: I think I can get away with removing the truncate_to_text call
+                // since I almost always bind to at least the header
+                // size... TODO: Or should we instead truncate but
+                // ALWAYS leave the arrow at the end?
+                truncate_to_text(&concat_string!(c.header(), arrow), width.get())
+            } else {
+                truncate_to_text(&c.header(), width.get())
+            }
+        }))
     }
 }
 
@@ -137,6 +133,10 @@ pub struct SortColumn<T> {
     /// Marks that this column is currently "hidden", and should *always* be
     /// skipped.
     pub is_hidden: bool,
+
+    /// Marks that this column stays in place when the table is scrolled
+    /// horizontally.
+    pub is_pinned: bool,
 }
 
 impl<D, T> DataTableColumn<T> for SortColumn<T>
@@ -168,6 +168,11 @@ where
         self.is_hidden
     }
 
+    #[inline]
+    fn is_pinned(&self) -> bool {
+        self.is_pinned
+    }
+
     fn header(&self) -> Cow<'static, str> {
         self.inner.header()
     }
@@ -189,6 +194,7 @@ where
             inner,
             bounds: ColumnWidthBounds::FollowHeader,
             is_hidden: false,
+            is_pinned: false,
             default_order: SortOrder::default(),
         }
     }
@@ -200,6 +206,7 @@ where
             inner,
             bounds: ColumnWidthBounds::Hard(width),
             is_hidden: false,
+            is_pinned: false,
             default_order: SortOrder::const_default(),
         }
     }
@@ -214,6 +221,7 @@ where
                 max_percentage,
             },
             is_hidden: false,
+            is_pinned: false,
             default_order: SortOrder::const_default(),
         }
     }
@@ -337,10 +345,12 @@ where
             })
             .collect_vec();
 
-        match range.binary_search(&needle) {
+        let position = match range.binary_search(&needle) {
             Ok(index) => Some(index),
             Err(index) => index.checked_sub(1),
-        }
+        };
+
+        position.and_then(|position| self.state.visible_columns.get(position).copied())
     }
 }
 
@@ -407,6 +417,7 @@ mod test {
         let props = {
             let inner = DataTableProps {
                 title: Some("test".into()),
+                footer: None,
                 table_gap: 1,
                 left_to_right: false,
                 is_basic: false,
@@ -541,4 +552,40 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn test_pinned_columns() {
+        let mut hidden = SortColumn::new(ColumnType::Data);
+        hidden.is_hidden = true;
+        let mut pinned = SortColumn::new(ColumnType::Index);
+        pinned.is_pinned = true;
+
+        let columns = [
+            SortColumn::new(ColumnType::Data),
+            hidden,
+            SortColumn::new(ColumnType::Data),
+            pinned,
+        ];
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: None,
+                footer: None,
+                table_gap: 0,
+                left_to_right: true,
+                is_basic: false,
+                show_table_scroll_position: false,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 0,
+            order: SortOrder::Descending,
+        };
+
+        let mut table = DataTable::new_sortable(columns, props, DataTableStyling::default());
+        assert_eq!(table.columns_to_draw(), vec![3, 0, 2]);
+
+        // Pinned columns stay in place when scrolled.
+        table.state.columns_cut_off = 1;
+        assert!(table.scroll_columns(1));
+        assert_eq!(table.columns_to_draw(), vec![3, 2]);
+    }
 }
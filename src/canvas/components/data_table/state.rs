@@ -29,6 +29,16 @@ pub struct DataTableState {
     /// The calculated widths.
     pub calculated_widths: Vec<NonZeroU16>,
 
+    /// The indices of the columns being drawn, in the same order as
+    /// `calculated_widths`.
+    pub visible_columns: Vec<usize>,
+
+    /// How many unpinned columns are scrolled past horizontally.
+    pub column_offset: usize,
+
+    /// How many unpinned columns didn't fit after the last one drawn.
+    pub columns_cut_off: usize,
+
     /// The current inner [`Rect`].
     pub inner_rect: Rect,
 }
@@ -40,6 +50,9 @@ impl Default for DataTableState {
             current_index: 0,
             scroll_direction: ScrollDirection::Down,
             calculated_widths: vec![],
+            visible_columns: vec![],
+            column_offset: 0,
+            columns_cut_off: 0,
             table_state: TableState::default(),
             inner_rect: Rect::default(),
         }
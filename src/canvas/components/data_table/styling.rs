@@ -10,6 +10,9 @@ pub struct DataTableStyling {
     pub text_style: Style,
     pub highlighted_text_style: Style,
     pub title_style: Style,
+    /// Used for the border instead while set, like when flashing it for an
+    /// alert.
+    pub alert_border_style: Option<Style>,
 }
 
 impl DataTableStyling {
@@ -21,6 +24,7 @@ impl DataTableStyling {
             text_style: colours.text_style,
             highlighted_text_style: colours.selected_text_style,
             title_style: colours.widget_title_style,
+            alert_border_style: None,
         }
     }
 }
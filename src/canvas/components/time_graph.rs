@@ -1,4 +1,7 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
 
 use concat_string::concat_string;
 use tui::{
@@ -6,7 +9,7 @@ use tui::{
     style::Style,
     symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, Borders, GraphType},
+    widgets::GraphType,
     Frame,
 };
 use unicode_segmentation::UnicodeSegmentation;
@@ -14,6 +17,7 @@ use unicode_segmentation::UnicodeSegmentation;
 use super::time_chart::{
     Axis, Dataset, LegendPosition, Point, TimeChart, DEFAULT_LEGEND_CONSTRAINTS,
 };
+use crate::options::config::style::BorderStyle;
 
 /// Represents the data required by the [`TimeGraph`].
 pub struct GraphData<'a> {
@@ -30,6 +34,10 @@ pub struct TimeGraph<'a> {
     /// Whether to hide the time/x-labels.
     pub hide_x_labels: bool,
 
+    /// The wall-clock time of the newest point, if the x-labels should be
+    /// absolute times rather than how long ago each point was.
+    pub end_time: Option<SystemTime>,
+
     /// The min and max y boundaries.
     pub y_bounds: [f64; 2],
 
@@ -42,6 +50,12 @@ pub struct TimeGraph<'a> {
     /// The border style.
     pub border_style: Style,
 
+    /// The lines the border is drawn with.
+    pub border: BorderStyle,
+
+    /// Whether this graph is selected.
+    pub is_selected: bool,
+
     /// The graph title.
     pub title: Cow<'a, str>,
 
@@ -62,15 +76,73 @@ pub struct TimeGraph<'a> {
     pub marker: Marker,
 }
 
+/// Time ranges longer than this have dates in their absolute x-labels.
+const DATE_LABEL_THRESHOLD_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Formats a wall-clock time in UTC as `HH:MM:SS`, or as `MM-DD HH:MM` if the
+/// date is needed.
+pub(crate) fn clock_label(time: SystemTime, with_date: bool) -> String {
+    let timestamp = humantime::format_rfc3339_seconds(time).to_string();
+    let label = if with_date {
+        timestamp
+            .get(5..16)
+            .map(|label| label.replacen('T', " ", 1))
+    } else {
+        timestamp.get(11..19).map(str::to_string)
+    };
+
+    label.unwrap_or(timestamp)
+}
+
+/// Where along the x-axis each of `count` labels is drawn, from 0 at the left
+/// edge to 1 at the right. [`TimeChart`] puts the first and last labels at the
+/// edges and centres the rest in equal slices, so absolute times have to be
+/// taken at those points to line up with the data under them.
+fn x_label_positions(count: usize) -> Vec<f64> {
+    (0..count)
+        .map(|index| {
+            if index == 0 {
+                0.0
+            } else if index == count - 1 {
+                1.0
+            } else {
+                (index as f64 + 0.5) / count as f64
+            }
+        })
+        .collect()
+}
+
 impl TimeGraph<'_> {
-    /// Generates the [`Axis`] for the x-axis.
-    fn generate_x_axis(&self) -> Axis<'_> {
+    /// Generates the absolute time x-labels, with as many as fit in `width`.
+    fn absolute_x_labels(&self, end_time: SystemTime, width: u16) -> Vec<Span<'_>> {
+        let range_ms = self.x_bounds[1].saturating_sub(self.x_bounds[0]);
+        let with_date = range_ms > DATE_LABEL_THRESHOLD_MS;
+        let label_width = if with_date { 24 } else { 20 };
+        let count = usize::from(width / label_width).clamp(2, 5);
+
+        x_label_positions(count)
+            .into_iter()
+            .map(|position| {
+                let ago = Duration::from_millis(((1.0 - position) * range_ms as f64) as u64);
+                let time = end_time.checked_sub(ago).unwrap_or(end_time);
+                Span::styled(clock_label(time, with_date), self.graph_style)
+            })
+            .collect()
+    }
+
+    /// Generates the [`Axis`] for the x-axis, given the width it's drawn in.
+    fn generate_x_axis(&self, width: u16) -> Axis<'_> {
         // Due to how we display things, we need to adjust the time bound values.
         let time_start = -(self.x_bounds[1] as f64);
         let adjusted_x_bounds = [time_start, 0.0];
 
         if self.hide_x_labels {
             Axis::default().bounds(adjusted_x_bounds)
+        } else if let Some(end_time) = self.end_time {
+            Axis::default()
+                .bounds(adjusted_x_bounds)
+                .labels(self.absolute_x_labels(end_time, width))
+                .style(self.graph_style)
         } else {
             let xb_one = (self.x_bounds[1] / 1000).to_string();
             let xb_zero = (self.x_bounds[0] / 1000).to_string();
@@ -104,16 +176,18 @@ impl TimeGraph<'_> {
     /// space.
     fn generate_title(&self, draw_loc: Rect) -> Line<'_> {
         if self.is_expanded {
-            let title_base = concat_string!(self.title, "── Esc to go back ");
+            let horizontal = self.border.horizontal();
+            let title_base = concat_string!(self.title, horizontal, horizontal, " Esc to go back ");
             Line::from(vec![
                 Span::styled(self.title.as_ref(), self.title_style),
                 Span::styled(
                     concat_string!(
-                        "─",
-                        "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        horizontal,
+                        horizontal.repeat(usize::from(draw_loc.width).saturating_sub(
                             UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2
                         )),
-                        "─ Esc to go back "
+                        horizontal,
+                        " Esc to go back "
                     ),
                     self.border_style,
                 ),
@@ -133,15 +207,16 @@ impl TimeGraph<'_> {
     /// - Expects `graph_data`, which represents *what* data to draw, and
     ///   various details like style and optional legends.
     pub fn draw_time_graph(&self, f: &mut Frame<'_>, draw_loc: Rect, graph_data: &[GraphData<'_>]) {
-        let x_axis = self.generate_x_axis();
+        let x_axis = self.generate_x_axis(draw_loc.width);
         let y_axis = self.generate_y_axis();
 
         // This is some ugly manual loop unswitching. Maybe unnecessary.
         // TODO: Optimize this step. Cut out unneeded points.
         let data = graph_data.iter().map(create_dataset).collect();
-        let block = Block::default()
+        let block = self
+            .border
+            .block(self.is_selected)
             .title(self.generate_title(draw_loc))
-            .borders(Borders::ALL)
             .border_style(self.border_style);
 
         f.render_widget(
@@ -151,6 +226,7 @@ impl TimeGraph<'_> {
                 .y_axis(y_axis)
                 .marker(self.marker)
                 .legend_style(self.graph_style)
+                .legend_border_set(self.border.inner_border_set())
                 .legend_position(self.legend_position)
                 .hidden_legend_constraints(
                     self.legend_constraints
@@ -183,7 +259,10 @@ fn create_dataset<'a>(data: &'a GraphData<'a>) -> Dataset<'a> {
 
 #[cfg(test)]
 mod test {
-    use std::borrow::Cow;
+    use std::{
+        borrow::Cow,
+        time::{Duration, UNIX_EPOCH},
+    };
 
     use tui::{
         layout::Rect,
@@ -192,8 +271,8 @@ mod test {
         text::{Line, Span},
     };
 
-    use super::TimeGraph;
-    use crate::canvas::components::time_chart::Axis;
+    use super::{clock_label, x_label_positions, TimeGraph};
+    use crate::{canvas::components::time_chart::Axis, options::config::style::BorderStyle};
 
     const Y_LABELS: [Cow<'static, str>; 3] = [
         Cow::Borrowed("0%"),
@@ -206,10 +285,13 @@ mod test {
             title: " Network ".into(),
             x_bounds: [0, 15000],
             hide_x_labels: false,
+            end_time: None,
             y_bounds: [0.0, 100.5],
             y_labels: &Y_LABELS,
             graph_style: Style::default().fg(Color::Red),
             border_style: Style::default().fg(Color::Blue),
+            border: BorderStyle::Single,
+            is_selected: false,
             is_expanded: false,
             title_style: Style::default().fg(Color::Cyan),
             legend_position: None,
@@ -222,7 +304,7 @@ mod test {
     fn time_graph_gen_x_axis() {
         let tg = create_time_graph();
         let style = Style::default().fg(Color::Red);
-        let x_axis = tg.generate_x_axis();
+        let x_axis = tg.generate_x_axis(80);
 
         let actual = Axis::default()
             .bounds([-15000.0, 0.0])
@@ -233,6 +315,38 @@ mod test {
         assert_eq!(x_axis.style, actual.style);
     }
 
+    #[test]
+    fn time_graph_gen_absolute_x_axis() {
+        let mut tg = create_time_graph();
+        let style = Style::default().fg(Color::Red);
+        tg.end_time = Some(UNIX_EPOCH + Duration::from_secs(86400 * 10 + 14 * 3600 + 23 * 60 + 7));
+
+        fn labels<'a>(tg: &'a TimeGraph<'_>, width: u16) -> Vec<Span<'a>> {
+            tg.generate_x_axis(width).labels.unwrap()
+        }
+        assert_eq!(
+            labels(&tg, 30),
+            vec![
+                Span::styled("14:22:52", style),
+                Span::styled("14:23:07", style)
+            ]
+        );
+        assert_eq!(labels(&tg, 60)[1], Span::styled("14:22:59", style));
+        assert_eq!(labels(&tg, 1000).len(), 5);
+
+        tg.x_bounds = [0, 2 * 24 * 60 * 60 * 1000];
+        assert_eq!(labels(&tg, 30)[0], Span::styled("01-09 14:23", style));
+    }
+
+    #[test]
+    fn absolute_labels() {
+        let time = UNIX_EPOCH + Duration::from_secs(86400 * 40 + 3600 + 62);
+        assert_eq!(clock_label(time, false), "01:01:02");
+        assert_eq!(clock_label(time, true), "02-10 01:01");
+        assert_eq!(x_label_positions(2), vec![0.0, 1.0]);
+        assert_eq!(x_label_positions(3), vec![0.0, 0.5, 1.0]);
+    }
+
     #[test]
     fn time_graph_gen_y_axis() {
         let tg = create_time_graph();
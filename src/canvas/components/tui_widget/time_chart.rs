@@ -381,6 +381,8 @@ pub struct TimeChart<'a> {
     style: Style,
     /// The legend's style.
     legend_style: Style,
+    /// The lines the legend's border is drawn with.
+    legend_border_set: symbols::border::Set,
     /// Constraints used to determine whether the legend should be shown or not
     hidden_legend_constraints: (Constraint, Constraint),
     /// The position detnermine where the legenth is shown or hide regaurdless
@@ -401,6 +403,7 @@ impl<'a> TimeChart<'a> {
             y_axis: Axis::default(),
             style: Style::default(),
             legend_style: Style::default(),
+            legend_border_set: symbols::border::PLAIN,
             datasets,
             hidden_legend_constraints: (Constraint::Ratio(1, 4), Constraint::Ratio(1, 4)),
             legend_position: Some(LegendPosition::default()),
@@ -435,6 +438,13 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Sets the lines the legend's border is drawn with.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn legend_border_set(mut self, legend_border_set: symbols::border::Set) -> TimeChart<'a> {
+        self.legend_border_set = legend_border_set;
+        self
+    }
+
     /// Sets the X [`Axis`]
     ///
     /// The default is an empty [`Axis`], i.e. only a line.
@@ -817,6 +827,7 @@ impl Widget for TimeChart<'_> {
             buf.set_style(legend_area, original_style);
             Block::default()
                 .borders(Borders::ALL)
+                .border_set(self.legend_border_set)
                 .border_style(self.legend_style)
                 .render(legend_area, buf);
 
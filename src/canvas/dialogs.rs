@@ -1,2 +1,11 @@
+pub mod context_menu;
 pub mod dd_dialog;
+pub mod disk_bench_dialog;
+pub mod event_log_dialog;
+#[cfg(feature = "flame")]
+pub mod flame_graph;
 pub mod help_dialog;
+pub mod load_graph_dialog;
+pub mod meminfo_dialog;
+pub mod system_summary_dialog;
+pub mod threshold_dialog;
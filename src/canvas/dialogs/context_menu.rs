@@ -0,0 +1,38 @@
+use tui::{
+    layout::Rect,
+    text::Line,
+    widgets::{Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::{app::context_menu::ContextMenu, canvas::Painter};
+
+impl Painter {
+    /// Draws the context menu on top of everything else, within `bounds`.
+    pub fn draw_context_menu(
+        &self, f: &mut Frame<'_>, context_menu: &mut ContextMenu, bounds: Rect,
+    ) {
+        let draw_loc = context_menu.area(bounds);
+        context_menu.draw_loc = Some(draw_loc);
+
+        let items = context_menu
+            .items
+            .iter()
+            .map(|item| ListItem::new(Line::from(format!(" {} ", item.label()))))
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .block(
+                self.dialog_border()
+                    .block(false)
+                    .border_style(self.colours.highlighted_border_style),
+            )
+            .style(self.colours.text_style)
+            .highlight_style(self.colours.selected_text_style);
+
+        let mut state = ListState::default().with_selected(Some(context_menu.selected));
+
+        f.render_widget(Clear, draw_loc);
+        f.render_stateful_widget(list, draw_loc, &mut state);
+    }
+}
@@ -4,7 +4,7 @@ use std::cmp::min;
 use tui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Paragraph, Wrap},
     Frame,
 };
 
@@ -353,13 +353,15 @@ impl Painter {
         &self, f: &mut Frame<'_>, dd_text: Option<Text<'_>>, app_state: &mut App, draw_loc: Rect,
     ) -> bool {
         if let Some(dd_text) = dd_text {
+            let border = self.dialog_border();
+            let horizontal = border.horizontal();
             let dd_title = if app_state.dd_err.is_some() {
                 Line::from(vec![
                     Span::styled(" Error ", self.colours.widget_title_style),
                     Span::styled(
                         format!(
-                            "─{}─ Esc to close ",
-                            "─".repeat(
+                            "{horizontal}{}{horizontal} Esc to close ",
+                            horizontal.repeat(
                                 usize::from(draw_loc.width)
                                     .saturating_sub(DD_ERROR_BASE.chars().count() + 2)
                             )
@@ -372,8 +374,8 @@ impl Painter {
                     Span::styled(" Confirm Kill Process ", self.colours.widget_title_style),
                     Span::styled(
                         format!(
-                            "─{}─ Esc to close ",
-                            "─".repeat(
+                            "{horizontal}{}{horizontal} Esc to close ",
+                            horizontal.repeat(
                                 usize::from(draw_loc.width)
                                     .saturating_sub(DD_BASE.chars().count() + 2)
                             )
@@ -386,10 +388,10 @@ impl Painter {
             f.render_widget(
                 Paragraph::new(dd_text)
                     .block(
-                        Block::default()
+                        border
+                            .block(false)
                             .title(dd_title)
                             .style(self.colours.border_style)
-                            .borders(Borders::ALL)
                             .border_style(self.colours.border_style),
                     )
                     .style(self.colours.text_style)
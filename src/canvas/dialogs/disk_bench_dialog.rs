@@ -0,0 +1,66 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::disk_bench::{DiskBenchState, BENCHMARK_LABEL},
+    canvas::Painter,
+};
+
+/// How wide the dialog is, if the terminal is wide enough.
+const WIDTH: u16 = 70;
+const CLOSE_TEXT: &str = " Esc to close ";
+
+impl Painter {
+    /// Draws the result of the `--disk_bench` benchmark on top of everything
+    /// else, centred within `bounds`.
+    pub fn draw_disk_bench_dialog(
+        &self, f: &mut Frame<'_>, disk_bench: &DiskBenchState, bounds: Rect,
+    ) {
+        let mut lines = vec![Line::from(Span::styled(
+            BENCHMARK_LABEL,
+            self.colours.warning_text_style,
+        ))];
+        lines.extend(
+            disk_bench
+                .lines()
+                .into_iter()
+                .map(|line| Line::from(Span::styled(line, self.colours.text_style))),
+        );
+
+        let width = WIDTH.min(bounds.width);
+        let height = (lines.len() as u16).saturating_add(2).min(bounds.height);
+        let draw_loc = Rect::new(
+            bounds.x + (bounds.width - width) / 2,
+            bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        );
+
+        let border = self.dialog_border();
+        let horizontal = border.horizontal();
+        let title_base = " Disk Benchmark ";
+        let repeat_len = usize::from(width).saturating_sub(title_base.len() + CLOSE_TEXT.len() + 4);
+        let title = Line::from(vec![
+            Span::styled(title_base, self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "{horizontal}{}{horizontal}{CLOSE_TEXT}",
+                    horizontal.repeat(repeat_len)
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let block = border
+            .block(false)
+            .title(title)
+            .border_style(self.colours.highlighted_border_style);
+
+        f.render_widget(Clear, draw_loc);
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+    }
+}
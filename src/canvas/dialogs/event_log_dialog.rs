@@ -0,0 +1,131 @@
+use tui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::{
+        event_log::{format_age, LogLevel},
+        App,
+    },
+    canvas::{widgets::header::shown_data, Painter},
+};
+
+const CLOSE_TEXT: &str = " Esc to close ";
+
+impl Painter {
+    /// The subsystems that failed to be read in the last collection, with how
+    /// many times in a row each has failed and why.
+    fn collection_error_lines(&self, app_state: &App) -> Vec<Line<'static>> {
+        shown_data(app_state)
+            .collection_errors
+            .iter()
+            .map(|error| {
+                let (status, style) = if error.disabled {
+                    ("turned off", self.colours.invalid_query_style)
+                } else {
+                    ("failing", self.colours.warning_text_style)
+                };
+                let times = if error.count == 1 { "time" } else { "times" };
+
+                Line::from(vec![
+                    Span::styled(format!("[{status}] "), style),
+                    Span::styled(
+                        format!(
+                            "{}: {} {times} in a row: ",
+                            error.subsystem,
+                            error.count.max(1)
+                        ),
+                        self.colours.text_style,
+                    ),
+                    Span::styled(error.message.clone(), self.colours.disabled_text_style),
+                ])
+            })
+            .collect()
+    }
+
+    /// Draws the event log, with the newest entries at the bottom. Any
+    /// subsystems that are failing to be read are listed above the entries.
+    pub fn draw_event_log_dialog(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let error_lines = self.collection_error_lines(app_state);
+        let event_log = &mut app_state.event_log;
+
+        let border = self.dialog_border();
+        let horizontal = border.horizontal();
+        let title_base = format!(
+            " Event Log {horizontal}{horizontal} Filter: {} (Tab) ",
+            event_log.min_level
+        );
+        let repeat_len = usize::from(draw_loc.width)
+            .saturating_sub(title_base.chars().count() + CLOSE_TEXT.len() + 4);
+        let title = Line::from(vec![
+            Span::styled(title_base, self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "{horizontal}{}{horizontal}{CLOSE_TEXT}",
+                    horizontal.repeat(repeat_len)
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let block = border
+            .block(false)
+            .title(title)
+            .style(self.colours.border_style)
+            .border_style(self.colours.border_style);
+
+        let inner = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        // The errors take up to half of the dialog, with a blank line after.
+        let error_height = if error_lines.is_empty() {
+            0
+        } else {
+            (error_lines.len() as u16 + 1).min(inner.height / 2)
+        };
+        let [errors_loc, entries_loc] =
+            Layout::vertical([Constraint::Length(error_height), Constraint::Min(0)]).areas(inner);
+        f.render_widget(Paragraph::new(error_lines), errors_loc);
+
+        let height = entries_loc.height;
+        event_log.height = height;
+
+        let lines = if event_log.num_shown() == 0 {
+            vec![Line::from(Span::styled(
+                "No events yet.",
+                self.colours.disabled_text_style,
+            ))]
+        } else {
+            let mut lines = event_log
+                .shown()
+                .rev()
+                .skip(event_log.scroll_offset)
+                .take(height.into())
+                .map(|entry| {
+                    let level_style = match entry.level {
+                        LogLevel::Info => self.colours.text_style,
+                        LogLevel::Warn => self.colours.warning_text_style,
+                        LogLevel::Crit => self.colours.invalid_query_style,
+                    };
+
+                    Line::from(vec![
+                        Span::styled(format!("[{}] ", entry.level), level_style),
+                        Span::styled(
+                            format!("{:>10} ", format_age(entry.timestamp.elapsed())),
+                            self.colours.disabled_text_style,
+                        ),
+                        Span::styled(entry.message.clone(), self.colours.text_style),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            lines.reverse();
+
+            lines
+        };
+
+        f.render_widget(Paragraph::new(lines), entries_loc);
+    }
+}
@@ -0,0 +1,132 @@
+use tui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::{
+        flamegraph::{FlameBar, FlameGraphStatus, FrameKind},
+        App,
+    },
+    canvas::Painter,
+};
+
+const CLOSE_TEXT: &str = " Esc to close ";
+
+impl Painter {
+    /// Draws the flame graph of a process over the whole screen, with the
+    /// outermost frames at the bottom.
+    pub fn draw_flame_graph(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let Some(flame_graph) = &app_state.flame_graph else {
+            return;
+        };
+
+        let border = self.dialog_border();
+        let horizontal = border.horizontal();
+        let title_base = match &flame_graph.status {
+            FlameGraphStatus::Done(stacks) => format!(
+                " Flame Graph {horizontal}{horizontal} {} ({}) {horizontal}{horizontal} {} samples ",
+                flame_graph.name,
+                flame_graph.pid,
+                stacks.total()
+            ),
+            _ => format!(
+                " Flame Graph {horizontal}{horizontal} {} ({}) ",
+                flame_graph.name, flame_graph.pid
+            ),
+        };
+        let repeat_len = usize::from(draw_loc.width)
+            .saturating_sub(title_base.chars().count() + CLOSE_TEXT.len() + 4);
+        let title = Line::from(vec![
+            Span::styled(title_base, self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "{horizontal}{}{horizontal}{CLOSE_TEXT}",
+                    horizontal.repeat(repeat_len)
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let block = border
+            .block(false)
+            .title(title)
+            .style(self.colours.border_style)
+            .border_style(self.colours.border_style);
+        let inner = block.inner(draw_loc);
+
+        let lines = match &flame_graph.status {
+            FlameGraphStatus::Capturing => vec![Line::from(Span::styled(
+                "Sampling stacks...",
+                self.colours.text_style,
+            ))],
+            FlameGraphStatus::Failed(err) => vec![Line::from(Span::styled(
+                err.clone(),
+                self.colours.invalid_query_style,
+            ))],
+            FlameGraphStatus::Done(stacks) if stacks.total() == 0 => {
+                vec![Line::from(Span::styled(
+                    "No stacks were sampled.",
+                    self.colours.disabled_text_style,
+                ))]
+            }
+            FlameGraphStatus::Done(stacks) => {
+                let rows = stacks.layout(inner.width);
+                let height = usize::from(inner.height);
+
+                // Deep stacks are cut off at the top.
+                let mut lines = rows
+                    .iter()
+                    .take(height)
+                    .map(|row| self.flame_graph_row(row))
+                    .collect::<Vec<_>>();
+                lines.extend(std::iter::repeat(Line::default()).take(height - lines.len()));
+                lines.reverse();
+
+                lines
+            }
+        };
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            draw_loc,
+        );
+    }
+
+    /// Draws one depth of a flame graph. Neighbouring frames alternate between
+    /// two shades so they can be told apart.
+    fn flame_graph_row(&self, row: &[FlameBar]) -> Line<'static> {
+        let mut spans = Vec::with_capacity(row.len() * 2);
+        let mut column = 0;
+
+        for (index, bar) in row.iter().enumerate() {
+            if bar.start > column {
+                spans.push(Span::raw(" ".repeat(usize::from(bar.start - column))));
+            }
+            column = bar.start + bar.width;
+
+            let colour = match (bar.frame.kind, index % 2 == 0) {
+                (FrameKind::Kernel, true) => Color::Blue,
+                (FrameKind::Kernel, false) => Color::LightBlue,
+                (FrameKind::User, true) => Color::Green,
+                (FrameKind::User, false) => Color::LightGreen,
+                (FrameKind::Jit, true) => Color::Yellow,
+                (FrameKind::Jit, false) => Color::LightYellow,
+            };
+            let width = usize::from(bar.width);
+            let label = bar.frame.name.chars().take(width).collect::<String>();
+
+            spans.push(Span::styled(
+                format!("{label:<width$}"),
+                Style::default().fg(Color::Black).bg(colour),
+            ));
+        }
+
+        Line::from(spans)
+    }
+}
@@ -3,7 +3,7 @@ use std::cmp::{max, min};
 use tui::{
     layout::{Alignment, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Paragraph, Wrap},
     Frame,
 };
 use unicode_width::UnicodeWidthStr;
@@ -43,12 +43,14 @@ impl Painter {
     pub fn draw_help_dialog(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
         let styled_help_text = self.help_text_lines();
 
+        let border = self.dialog_border();
+        let horizontal = border.horizontal();
         let help_title = Line::from(vec![
             Span::styled(" Help ", self.colours.widget_title_style),
             Span::styled(
                 format!(
-                    "─{}─ Esc to close ",
-                    "─".repeat(
+                    "{horizontal}{}{horizontal} Esc to close ",
+                    horizontal.repeat(
                         usize::from(draw_loc.width).saturating_sub(HELP_BASE.chars().count() + 2)
                     )
                 ),
@@ -56,10 +58,10 @@ impl Painter {
             ),
         ]);
 
-        let block = Block::default()
+        let block = border
+            .block(false)
             .title(help_title)
             .style(self.colours.border_style)
-            .borders(Borders::ALL)
             .border_style(self.colours.border_style);
 
         if app_state.should_get_widget_bounds() {
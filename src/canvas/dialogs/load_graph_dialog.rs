@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use tui::{layout::Rect, style::Style, symbols::Marker, widgets::Clear, Frame};
+
+use crate::{
+    app::App,
+    canvas::{
+        components::{
+            time_chart::LegendPosition,
+            time_graph::{GraphData, TimeGraph},
+        },
+        widgets::header::shown_data,
+        Painter,
+    },
+    constants::TIME_LABEL_HEIGHT_LIMIT,
+    data_conversion::convert_load_avg_points,
+};
+
+impl Painter {
+    /// Draws the history of the 1, 5, and 15 minute load averages over all of
+    /// `draw_loc`, going as far back as data is kept.
+    pub fn draw_load_graph_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let data = shown_data(app_state);
+        let points = convert_load_avg_points(data);
+
+        let oldest = points[0].first().map_or(0.0, |(time, _)| -time);
+        let x_bounds = [0, ((oldest / 1000.0).ceil() as u64).max(1) * 1000];
+
+        let max = points
+            .iter()
+            .flatten()
+            .map(|(_, load)| *load)
+            .fold(0.0, f64::max);
+        let y_max = (max * 1.1).max(1.0);
+        let y_bounds = [0.0, y_max];
+        let y_labels = [Cow::Borrowed("0"), Cow::Owned(format!("{y_max:.1}"))];
+
+        let styles = &self.colours.cpu_colour_styles;
+        let graph_data = points
+            .iter()
+            .zip(["1 min", "5 min", "15 min"])
+            .zip(data.load_avg_harvest)
+            .enumerate()
+            .map(|(index, ((points, name), load))| GraphData {
+                points,
+                style: if styles.is_empty() {
+                    Style::default()
+                } else {
+                    styles[index % styles.len()]
+                },
+                name: Some(format!("{name}: {load:.2}").into()),
+            })
+            .collect::<Vec<_>>();
+
+        let marker = if app_state.app_config_fields.use_dot {
+            Marker::Dot
+        } else {
+            Marker::Braille
+        };
+
+        f.render_widget(Clear, draw_loc);
+        TimeGraph {
+            x_bounds,
+            hide_x_labels: app_state.app_config_fields.hide_time
+                || draw_loc.height < TIME_LABEL_HEIGHT_LIMIT,
+            end_time: app_state.graph_end_time(),
+            y_bounds,
+            y_labels: &y_labels,
+            graph_style: self.colours.graph_style,
+            border_style: self.colours.highlighted_border_style,
+            border: self.dialog_border(),
+            is_selected: true,
+            title: " Load Average ".into(),
+            is_expanded: true,
+            title_style: self.colours.widget_title_style,
+            legend_position: Some(LegendPosition::TopLeft),
+            legend_constraints: None,
+            marker,
+        }
+        .draw_time_graph(f, draw_loc, &graph_data);
+    }
+}
@@ -0,0 +1,65 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{app::meminfo::MemInfoPanel, canvas::Painter};
+
+const CLOSE_TEXT: &str = " / to filter, Esc to close ";
+
+#[cfg(target_os = "macos")]
+const SOURCE: &str = "vm_stat";
+#[cfg(not(target_os = "macos"))]
+const SOURCE: &str = "/proc/meminfo";
+
+impl Painter {
+    /// Draws every field of `/proc/meminfo` over everything else, taking up
+    /// all of `draw_loc`.
+    pub fn draw_meminfo_dialog(&self, f: &mut Frame<'_>, panel: &mut MemInfoPanel, draw_loc: Rect) {
+        let border = self.dialog_border();
+        let horizontal = border.horizontal();
+        let title_base = if panel.is_filtering || !panel.filter.is_empty() {
+            format!(" Memory details ({SOURCE}) [/{}] ", panel.filter)
+        } else {
+            format!(" Memory details ({SOURCE}) ")
+        };
+        let repeat_len = usize::from(draw_loc.width)
+            .saturating_sub(title_base.chars().count() + CLOSE_TEXT.len() + 4);
+        let title = Line::from(vec![
+            Span::styled(title_base, self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "{horizontal}{}{horizontal}{CLOSE_TEXT}",
+                    horizontal.repeat(repeat_len)
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let block = border
+            .block(false)
+            .title(title)
+            .border_style(self.colours.highlighted_border_style);
+
+        panel.set_height(block.inner(draw_loc).height);
+        let lines = panel.lines();
+        let lines = if lines.is_empty() {
+            vec![Line::from(Span::styled(
+                "No fields to show",
+                self.colours.text_style,
+            ))]
+        } else {
+            lines
+                .into_iter()
+                .skip(panel.scroll_offset)
+                .take(panel.height().into())
+                .map(|line| Line::from(Span::styled(line, self.colours.text_style)))
+                .collect()
+        };
+
+        f.render_widget(Clear, draw_loc);
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+    }
+}
@@ -0,0 +1,63 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{app::system_summary::SystemSummaryPanel, canvas::Painter};
+
+/// How wide the panel is, if the terminal is wide enough.
+const WIDTH: u16 = 80;
+const CLOSE_TEXT: &str = " y to copy, Esc to close ";
+
+impl Painter {
+    /// Draws the system summary on top of everything else, centred within
+    /// `bounds` and as tall as it needs to be or can be.
+    pub fn draw_system_summary_dialog(
+        &self, f: &mut Frame<'_>, panel: &mut SystemSummaryPanel, bounds: Rect,
+    ) {
+        let width = WIDTH.min(bounds.width);
+        let height = (panel.lines().len() as u16)
+            .saturating_add(2)
+            .min(bounds.height);
+        let draw_loc = Rect::new(
+            bounds.x + (bounds.width - width) / 2,
+            bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        );
+
+        let border = self.dialog_border();
+        let horizontal = border.horizontal();
+        let title_base = " System Summary ";
+        let repeat_len = usize::from(width).saturating_sub(title_base.len() + CLOSE_TEXT.len() + 4);
+        let title = Line::from(vec![
+            Span::styled(title_base, self.colours.widget_title_style),
+            Span::styled(
+                format!(
+                    "{horizontal}{}{horizontal}{CLOSE_TEXT}",
+                    horizontal.repeat(repeat_len)
+                ),
+                self.colours.border_style,
+            ),
+        ]);
+
+        let block = border
+            .block(false)
+            .title(title)
+            .border_style(self.colours.highlighted_border_style);
+
+        panel.set_height(block.inner(draw_loc).height);
+        let lines = panel
+            .lines()
+            .iter()
+            .skip(panel.scroll_offset)
+            .take(panel.height().into())
+            .map(|line| Line::from(Span::styled(line.as_str(), self.colours.text_style)))
+            .collect::<Vec<_>>();
+
+        f.render_widget(Clear, draw_loc);
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+    }
+}
@@ -0,0 +1,72 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::threshold_editor::{ThresholdEditor, ThresholdField},
+    canvas::Painter,
+};
+
+const WIDTH: u16 = 44;
+const HEIGHT: u16 = 6;
+
+impl Painter {
+    /// Draws the process threshold editor on top of everything else, centred
+    /// within `bounds`.
+    pub fn draw_threshold_editor(&self, f: &mut Frame<'_>, editor: &ThresholdEditor, bounds: Rect) {
+        let width = WIDTH.min(bounds.width);
+        let height = HEIGHT.min(bounds.height);
+        let draw_loc = Rect::new(
+            bounds.x + (bounds.width - width) / 2,
+            bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        );
+
+        let field = |label: &str, text: &str, this: ThresholdField| {
+            let style = if editor.selected == this {
+                self.colours.selected_text_style
+            } else {
+                self.colours.text_style
+            };
+            Line::from(vec![
+                Span::styled(format!(" {label}: "), self.colours.text_style),
+                Span::styled(format!("{text}_"), style),
+            ])
+        };
+
+        let hint = match &editor.error {
+            Some(err) => Line::from(Span::styled(
+                format!(" {err}"),
+                self.colours.invalid_query_style,
+            )),
+            None => Line::from(Span::styled(
+                " Enter to apply, empty or 0 for none",
+                self.colours.border_style,
+            )),
+        };
+
+        let text = vec![
+            field("Min CPU%", &editor.cpu, ThresholdField::Cpu),
+            field("Min Mem%", &editor.mem, ThresholdField::Mem),
+            Line::default(),
+            hint,
+        ];
+
+        let paragraph = Paragraph::new(text).block(
+            self.dialog_border()
+                .block(false)
+                .title(Span::styled(
+                    " Process Thresholds ",
+                    self.colours.widget_title_style,
+                ))
+                .border_style(self.colours.highlighted_border_style),
+        );
+
+        f.render_widget(Clear, draw_loc);
+        f.render_widget(paragraph, draw_loc);
+    }
+}
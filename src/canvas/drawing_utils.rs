@@ -1,6 +1,20 @@
-use std::{cmp::min, time::Instant};
+use std::{borrow::Cow, cmp::min, time::Instant};
 
-use tui::layout::Rect;
+use tui::{
+    layout::{Margin, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    canvas::components::{
+        time_chart::{LegendPosition, Point},
+        time_graph::GraphData,
+    },
+    utils::stats::StatSummary,
+};
 
 /// Calculate how many bars are to be drawn within basic mode's components.
 pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize) -> usize {
@@ -30,11 +44,157 @@ pub fn should_hide_x_label(
     }
 }
 
+/// Returns a graph's title, marking it with how many points it's smoothed over
+/// if smoothing is on.
+pub fn graph_title(name: &str, smooth_n: usize) -> String {
+    if smooth_n > 1 {
+        format!(" {name} ~{smooth_n} ")
+    } else {
+        format!(" {name} ")
+    }
+}
+
+/// Shortens a widget's title to its first word for quiet mode, keeping the
+/// padding around it, e.g. ` Processes (diff) [io] ` to ` Processes `.
+pub fn quiet_title(title: &str) -> String {
+    match title.split_whitespace().next() {
+        Some(word) => format!(" {} ", word.trim_end_matches(':')),
+        None => title.to_string(),
+    }
+}
+
+/// Returns the y bounds and labels of a graph of deltas, centred on zero so
+/// increases and decreases get the same room. The range is at least
+/// `min_range`, so a flat graph doesn't zoom in on noise.
+pub fn delta_y_axis(
+    deltas: &[(Vec<Point>, Vec<Point>)], min_range: f64, format: impl Fn(f64) -> String,
+) -> ([f64; 2], Vec<Cow<'static, str>>) {
+    let range = deltas
+        .iter()
+        .flat_map(|(increases, decreases)| increases.iter().chain(decreases))
+        .map(|(_, value)| value.abs())
+        .fold(min_range, f64::max);
+
+    (
+        [-range, range],
+        vec![
+            format!("-{}", format(range)).into(),
+            format(0.0).into(),
+            format!("+{}", format(range)).into(),
+        ],
+    )
+}
+
+/// Draws deltas that have been split by sign, with increases and decreases in
+/// different styles.
+pub fn delta_graph_data(
+    deltas: &[(Vec<Point>, Vec<Point>)], increase_style: Style, decrease_style: Style,
+) -> Vec<GraphData<'_>> {
+    deltas
+        .iter()
+        .flat_map(|(increases, decreases)| {
+            [
+                GraphData {
+                    points: increases,
+                    style: increase_style,
+                    name: None,
+                },
+                GraphData {
+                    points: decreases,
+                    style: decrease_style,
+                    name: None,
+                },
+            ]
+        })
+        .collect()
+}
+
+/// The frames of the heartbeat spinner in the status line.
+const HEARTBEAT_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// The frame of the heartbeat spinner shown after `tick` updates.
+pub fn heartbeat_frame(tick: u8) -> char {
+    HEARTBEAT_FRAMES[usize::from(tick) % HEARTBEAT_FRAMES.len()]
+}
+
+/// Describes `stats` on one line for a graph's stats overlay, like
+/// `RAM avg 45% min 40% max 50% sd 2%`.
+pub fn stats_text(name: &str, stats: &StatSummary, format: impl Fn(f64) -> String) -> String {
+    format!(
+        "{name} avg {} min {} max {} sd {}",
+        format(stats.mean),
+        format(stats.min),
+        format(stats.max),
+        format(stats.stddev)
+    )
+}
+
+/// Where a stats overlay of `width` by `height` is drawn on the graph at
+/// `graph_loc`: in the top-right corner, inside the borders and below a legend
+/// of `legend_rows` rows if there's one there. Returns [`None`] if it would
+/// cover more than a fifth of the graph or half of its width.
+pub fn stats_overlay_loc(
+    graph_loc: Rect, width: u16, height: u16, legend_position: Option<LegendPosition>,
+    legend_rows: u16,
+) -> Option<Rect> {
+    let inner = graph_loc.inner(Margin::new(1, 1));
+    let skip_rows = match legend_position {
+        Some(LegendPosition::Top | LegendPosition::TopRight) => legend_rows,
+        _ => 0,
+    };
+
+    let area = u32::from(width) * u32::from(height);
+    let max_area = u32::from(inner.width) * u32::from(inner.height) / 5;
+    if width == 0 || width > inner.width / 2 || area > max_area {
+        return None;
+    }
+
+    let y = inner.y.checked_add(skip_rows)?;
+    if y.saturating_add(height) > inner.bottom() {
+        return None;
+    }
+
+    Some(Rect::new(inner.right() - width, y, width, height))
+}
+
+/// Draws `lines` of stats over the top-right corner of the graph at
+/// `graph_loc`, if there's room for them. See [`stats_overlay_loc`].
+pub fn draw_stats_overlay(
+    f: &mut Frame<'_>, graph_loc: Rect, lines: Vec<Line<'_>>,
+    legend_position: Option<LegendPosition>, legend_rows: u16,
+) {
+    let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let height = lines.len() as u16;
+
+    if let Some(loc) = stats_overlay_loc(graph_loc, width, height, legend_position, legend_rows) {
+        f.render_widget(Clear, loc);
+        f.render_widget(Paragraph::new(lines).right_aligned(), loc);
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use super::*;
 
+    #[test]
+    fn quiet_titles() {
+        assert_eq!(quiet_title(" Processes (diff) [io] "), " Processes ");
+        assert_eq!(quiet_title(" Log: /var/log/syslog "), " Log ");
+        assert_eq!(quiet_title(" CPU ~4 "), " CPU ");
+        assert_eq!(quiet_title(""), "");
+    }
+
+    #[test]
+    fn heartbeat_frames() {
+        assert_eq!(heartbeat_frame(0), '⠋');
+        assert_eq!(heartbeat_frame(1), '⠙');
+        assert_eq!(heartbeat_frame(10), '⠋');
+
+        // Every update still moves the spinner when the tick wraps around.
+        assert_ne!(heartbeat_frame(255), heartbeat_frame(0));
+    }
+
     #[test]
     fn test_calculate_basic_use_bars() {
         // Testing various breakpoints and edge cases.
@@ -49,6 +209,38 @@ mod test {
         assert_eq!(calculate_basic_use_bars(150.0, 15), 15);
     }
 
+    #[test]
+    fn test_stats_overlay_loc() {
+        let graph = Rect::new(0, 0, 102, 22);
+
+        assert_eq!(
+            stats_overlay_loc(graph, 40, 2, None, 4),
+            Some(Rect::new(61, 1, 40, 2))
+        );
+        assert_eq!(
+            stats_overlay_loc(graph, 40, 2, Some(LegendPosition::TopRight), 4),
+            Some(Rect::new(61, 5, 40, 2))
+        );
+        assert_eq!(
+            stats_overlay_loc(graph, 40, 2, Some(LegendPosition::BottomRight), 4),
+            Some(Rect::new(61, 1, 40, 2))
+        );
+
+        // Too much of the graph, or of its width.
+        assert_eq!(stats_overlay_loc(graph, 45, 9, None, 0), None);
+        assert_eq!(
+            stats_overlay_loc(Rect::new(0, 0, 62, 40), 40, 2, None, 0),
+            None
+        );
+        assert_eq!(stats_overlay_loc(graph, 0, 2, None, 0), None);
+    }
+
+    #[test]
+    fn test_graph_title() {
+        assert_eq!(graph_title("CPU", 1), " CPU ");
+        assert_eq!(graph_title("CPU", 3), " CPU ~3 ");
+    }
+
     #[test]
     fn test_should_hide_x_label() {
         use std::time::{Duration, Instant};
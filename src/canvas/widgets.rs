@@ -1,10 +1,18 @@
 pub mod battery_display;
+pub mod collection_error;
 pub mod cpu_basic;
 pub mod cpu_graph;
 pub mod disk_table;
+pub mod exec_output;
+pub mod header;
+pub mod log_tail;
 pub mod mem_basic;
 pub mod mem_graph;
+pub mod multi_host;
 pub mod network_basic;
 pub mod network_graph;
 pub mod process_table;
+pub mod replay_timeline;
+pub mod startup_table;
 pub mod temperature_table;
+pub mod user_proc_table;
@@ -8,7 +8,7 @@ use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    app::App,
+    app::{layout_manager::BottomWidgetType, App},
     canvas::{drawing_utils::calculate_basic_use_bars, Painter},
     constants::*,
     data_conversion::BatteryDuration,
@@ -38,14 +38,16 @@ impl Painter {
                 app_state.app_config_fields.table_gap
             };
 
+            let border = self.widget_border(&BottomWidgetType::Battery);
             let title = if app_state.is_expanded {
                 const TITLE_BASE: &str = " Battery ── Esc to go back ";
+                let horizontal = border.horizontal();
                 Line::from(vec![
                     Span::styled(" Battery ", self.colours.widget_title_style),
                     Span::styled(
                         format!(
-                            "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                            "{horizontal}{}{horizontal} Esc to go back ",
+                            horizontal.repeat(usize::from(draw_loc.width).saturating_sub(
                                 UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
                             ))
                         ),
@@ -57,13 +59,13 @@ impl Painter {
             };
 
             let battery_block = if draw_border {
-                Block::default()
+                border
+                    .block(is_on_widget)
                     .title(title)
-                    .borders(Borders::ALL)
                     .border_style(border_style)
             } else if is_on_widget {
-                Block::default()
-                    .borders(SIDE_BORDERS)
+                border
+                    .side_block()
                     .border_style(self.colours.highlighted_border_style)
             } else {
                 Block::default().borders(Borders::NONE)
@@ -0,0 +1,66 @@
+use tui::{
+    layout::{Alignment, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::{widgets::header::shown_data, Painter},
+    data_collection::error::Subsystem,
+};
+
+impl Painter {
+    /// Draws a placeholder in place of a widget if any of the subsystems it
+    /// shows couldn't be read, returning whether it did. The widget's own data
+    /// would only be out of date, so it's better to say why.
+    pub fn draw_collection_error(
+        &self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, widget_id: u64, title: &str,
+        subsystems: &[Subsystem],
+    ) -> bool {
+        let Some(error) = shown_data(app_state).collection_error(subsystems) else {
+            return false;
+        };
+
+        let summary = if error.disabled {
+            format!("{} subsystem disabled (see logs)", error.subsystem)
+        } else {
+            "⚠ error reading data".to_string()
+        };
+        let lines = vec![
+            Line::from(Span::styled(summary, self.colours.warning_text_style)),
+            Line::from(Span::styled(
+                error.message.as_str(),
+                self.colours.text_style,
+            )),
+        ];
+
+        let block = if app_state.app_config_fields.use_basic_mode {
+            Block::default().borders(Borders::NONE)
+        } else {
+            let widget_type = app_state
+                .widget_map
+                .get(&widget_id)
+                .map_or(&BottomWidgetType::Empty, |widget| &widget.widget_type);
+
+            self.widget_border(widget_type)
+                .block(widget_id == app_state.current_widget.widget_id)
+                .title(Span::styled(
+                    self.widget_title(title),
+                    self.colours.widget_title_style,
+                ))
+                .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+        };
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+
+        true
+    }
+}
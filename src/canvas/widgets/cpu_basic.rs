@@ -1,27 +1,50 @@
 use std::cmp::min;
 
+use hashbrown::HashMap;
+
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
-    widgets::Block,
+    widgets::Paragraph,
     Frame,
 };
 
 use crate::{
-    app::App,
+    app::{layout_manager::BottomWidgetType, App},
     canvas::{
         components::pipe_gauge::{LabelLimit, PipeGauge},
         Painter,
     },
-    constants::*,
-    data_collection::cpu::CpuDataType,
+    data_collection::{
+        cpu::CpuDataType,
+        error::Subsystem,
+        processes::{affinity_map, PinnedProcess},
+    },
     data_conversion::CpuWidgetData,
+    utils::strings::truncate_to_text,
 };
 
+/// How many names of pinned processes are shown below a core.
+const MAX_PINNED_NAMES: usize = 3;
+
+/// How many characters of each pinned process' name are shown.
+const PINNED_NAME_LEN: usize = 8;
+
 impl Painter {
     /// Inspired by htop.
     pub fn draw_basic_cpu(
         &self, f: &mut Frame<'_>, app_state: &mut App, mut draw_loc: Rect, widget_id: u64,
     ) {
+        if self.draw_collection_error(
+            f,
+            app_state,
+            draw_loc,
+            widget_id,
+            " CPU ",
+            &[Subsystem::Cpu],
+        ) {
+            return;
+        }
+
         // Skip the first element, it's the "all" element
         if app_state.converted_data.cpu_data.len() > 1 {
             let cpu_data: &[CpuWidgetData] = &app_state.converted_data.cpu_data[1..];
@@ -38,8 +61,8 @@ impl Painter {
 
             if app_state.current_widget.widget_id == widget_id {
                 f.render_widget(
-                    Block::default()
-                        .borders(SIDE_BORDERS)
+                    self.widget_border(&BottomWidgetType::BasicCpu)
+                        .side_block()
                         .border_style(self.colours.highlighted_border_style),
                     draw_loc,
                 );
@@ -82,7 +105,20 @@ impl Painter {
                     .direction(Direction::Horizontal)
                     .split(draw_loc);
 
-                let mut gauge_info = cpu_data.iter().map(|cpu| self.cpu_info(cpu));
+                // Cores with a temperature sensor of their own show it before their
+                // usage.
+                let gauge_info = cpu_data.iter().map(|cpu| {
+                    let (start_label, inner_label, ratio, style) = self.cpu_info(cpu);
+                    match core_temperature(app_state, cpu) {
+                        Some(temperature) => (
+                            start_label,
+                            format!("{temperature} {inner_label}"),
+                            ratio,
+                            style,
+                        ),
+                        None => (start_label, inner_label, ratio, style),
+                    }
+                });
 
                 // Very ugly way to sync the gauge limit across all gauges.
                 let hide_parts = columns
@@ -98,27 +134,46 @@ impl Painter {
                     })
                     .unwrap_or_default();
 
-                let num_entries = cpu_data.len();
-                let mut row_counter = num_entries;
-                for (itx, column) in columns.iter().enumerate() {
-                    if REQUIRED_COLUMNS > itx {
-                        let to_divide = REQUIRED_COLUMNS - itx;
-                        let num_taken = min(
-                            remaining_height,
-                            (row_counter / to_divide) + usize::from(row_counter % to_divide != 0),
-                        );
-                        row_counter -= num_taken;
-                        let chunk = (&mut gauge_info).take(num_taken);
-
-                        let rows = Layout::default()
-                            .direction(Direction::Vertical)
-                            .constraints(vec![Constraint::Length(1); remaining_height])
-                            .horizontal_margin(1)
-                            .split(*column);
-
-                        for ((start_label, inner_label, ratio, style), row) in
-                            chunk.zip(rows.iter())
-                        {
+                // Cores with processes pinned to them get a line with their names
+                // below their bar, if there's room for all of them.
+                let pinned_labels = pinned_labels(&app_state.data_collection.pinned_processes);
+                let labels = cpu_data
+                    .iter()
+                    .map(|cpu| match cpu {
+                        CpuWidgetData::Entry {
+                            data_type: CpuDataType::Cpu(index),
+                            ..
+                        } => pinned_labels.get(index),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let heights = labels
+                    .iter()
+                    .map(|label| 1 + usize::from(label.is_some()))
+                    .collect::<Vec<_>>();
+
+                let (column_sizes, labels) =
+                    match stack_columns(&heights, REQUIRED_COLUMNS, remaining_height) {
+                        Some(sizes) if !pinned_labels.is_empty() => (sizes, labels),
+                        _ => (
+                            even_columns(cpu_data.len(), REQUIRED_COLUMNS, remaining_height),
+                            vec![None; cpu_data.len()],
+                        ),
+                    };
+                let mut entries = gauge_info.zip(labels);
+
+                for (column, num_taken) in columns.iter().zip(column_sizes) {
+                    let rows = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints(vec![Constraint::Length(1); remaining_height])
+                        .horizontal_margin(1)
+                        .split(*column);
+                    let mut rows = rows.iter();
+
+                    for ((start_label, inner_label, ratio, style), label) in
+                        (&mut entries).take(num_taken)
+                    {
+                        if let Some(row) = rows.next() {
                             f.render_widget(
                                 PipeGauge::default()
                                     .gauge_style(style)
@@ -130,6 +185,13 @@ impl Painter {
                                 *row,
                             );
                         }
+
+                        if let (Some(label), Some(row)) = (label, rows.next()) {
+                            f.render_widget(
+                                Paragraph::new(truncate_to_text(label, row.width)).style(style),
+                                *row,
+                            );
+                        }
                     }
                 }
             }
@@ -205,3 +267,130 @@ fn maybe_split_avg(
 
     (cpu_data, avg_data)
 }
+
+/// The temperature of a core, like `72°C`, if it has a sensor of its own.
+fn core_temperature(app_state: &App, cpu: &CpuWidgetData) -> Option<String> {
+    let CpuWidgetData::Entry {
+        data_type: CpuDataType::Cpu(index),
+        ..
+    } = cpu
+    else {
+        return None;
+    };
+
+    let data = &app_state.data_collection;
+    let sensor = data.temp_harvest.get(*data.core_temp_sensors.get(index)?)?;
+
+    Some(format!(
+        "{:.0}{}",
+        sensor.temperature?, app_state.app_config_fields.temperature_type
+    ))
+}
+
+/// The line shown below each core with processes pinned to it, listing the
+/// first [`MAX_PINNED_NAMES`] of them and how many more there are.
+fn pinned_labels(pinned: &[PinnedProcess]) -> HashMap<usize, String> {
+    let names = pinned
+        .iter()
+        .map(|process| (process.pid, process.name.as_str()))
+        .collect::<HashMap<_, _>>();
+
+    affinity_map(pinned)
+        .into_iter()
+        .map(|(core, pids)| {
+            let mut label = pids
+                .iter()
+                .take(MAX_PINNED_NAMES)
+                .filter_map(|pid| names.get(pid))
+                .map(|name| unicode_ellipsis::truncate_str(name, PINNED_NAME_LEN))
+                .fold(String::from("\u{2514}"), |label, name| label + " " + &name);
+            if pids.len() > MAX_PINNED_NAMES {
+                label += &format!(" +{}", pids.len() - MAX_PINNED_NAMES);
+            }
+
+            (core, label)
+        })
+        .collect()
+}
+
+/// Splits `num_entries` single-row entries as evenly as possible over the
+/// columns, giving how many go in each.
+fn even_columns(num_entries: usize, num_columns: usize, max_height: usize) -> Vec<usize> {
+    let mut remaining = num_entries;
+
+    (0..num_columns)
+        .map(|itx| {
+            let to_divide = num_columns - itx;
+            let num_taken = min(
+                max_height,
+                (remaining / to_divide) + usize::from(remaining % to_divide != 0),
+            );
+            remaining -= num_taken;
+            num_taken
+        })
+        .collect()
+}
+
+/// Fills the columns in order with entries of the given heights, keeping the
+/// columns as short as possible, and gives how many entries go in each. This
+/// is [`None`] if they don't all fit.
+fn stack_columns(heights: &[usize], num_columns: usize, max_height: usize) -> Option<Vec<usize>> {
+    let total = heights.iter().sum::<usize>();
+    let shortest = total.div_ceil(num_columns).max(1);
+
+    (shortest..=max_height).find_map(|column_height| {
+        let mut sizes = vec![0; num_columns];
+        let (mut column, mut used) = (0, 0);
+
+        for &height in heights {
+            if used + height > column_height {
+                column += 1;
+                used = 0;
+            }
+            if column >= num_columns || height > column_height {
+                return None;
+            }
+
+            sizes[column] += 1;
+            used += height;
+        }
+
+        Some(sizes)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn labels() {
+        let pinned = |pid, name: &str, cores: &[usize]| PinnedProcess {
+            pid,
+            name: name.to_string(),
+            cores: cores.to_vec(),
+        };
+        let labels = pinned_labels(&[
+            pinned(1, "nginx", &[0]),
+            pinned(2, "postgres-worker", &[0]),
+            pinned(3, "redis", &[0, 1]),
+            pinned(4, "dpdk", &[0]),
+        ]);
+
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[&0], "\u{2514} nginx postgre\u{2026} dpdk +1");
+        assert_eq!(labels[&1], "\u{2514} redis");
+    }
+
+    #[test]
+    fn columns() {
+        assert_eq!(even_columns(10, 4, 5), vec![3, 3, 2, 2]);
+        assert_eq!(even_columns(10, 4, 2), vec![2, 2, 2, 2]);
+
+        // The entries with labels take two rows, and stay in one column.
+        assert_eq!(stack_columns(&[1, 2, 1, 1], 2, 5), Some(vec![2, 2]));
+        assert_eq!(stack_columns(&[2, 2, 2, 2], 4, 2), Some(vec![1, 1, 1, 1]));
+        assert_eq!(stack_columns(&[2, 2, 2, 2, 1], 4, 2), None);
+        assert_eq!(stack_columns(&[], 4, 2), Some(vec![0, 0, 0, 0]));
+    }
+}
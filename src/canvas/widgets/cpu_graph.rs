@@ -7,16 +7,20 @@ use tui::{
 };
 
 use crate::{
-    app::{layout_manager::WidgetDirection, App},
+    app::{
+        layout_manager::{BottomWidgetType, WidgetDirection},
+        App,
+    },
     canvas::{
         components::{
             data_table::{DrawInfo, SelectionState},
             time_graph::{GraphData, TimeGraph},
         },
-        drawing_utils::should_hide_x_label,
+        drawing_utils::{delta_graph_data, delta_y_axis, graph_title, should_hide_x_label},
         Painter,
     },
-    data_conversion::CpuWidgetData,
+    data_collection::error::Subsystem,
+    data_conversion::{delta_points, split_by_sign, CpuWidgetData},
     widgets::CpuWidgetState,
 };
 
@@ -25,7 +29,26 @@ const ALL_POSITION: usize = 0;
 
 impl Painter {
     pub fn draw_cpu(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64) {
-        let legend_width = (draw_loc.width as f64 * 0.15) as u16;
+        if self.draw_collection_error(
+            f,
+            app_state,
+            draw_loc,
+            widget_id,
+            " CPU ",
+            &[Subsystem::Cpu],
+        ) {
+            return;
+        }
+
+        // The legend is wider when it has room for the stats of each entry.
+        let show_stats = app_state
+            .states
+            .cpu_state
+            .widget_states
+            .get(&widget_id)
+            .is_some_and(|state| state.show_stats);
+        let legend_ratio = if show_stats { 0.35 } else { 0.15 };
+        let legend_width = (draw_loc.width as f64 * legend_ratio) as u16;
 
         if legend_width < 6 {
             // Skip drawing legend
@@ -178,13 +201,17 @@ impl Painter {
     fn draw_cpu_graph(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        let end_time = app_state.graph_end_time();
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
+        let alert_border_style = self.alert_border_style(app_state, &BottomWidgetType::Cpu);
 
         if let Some(cpu_widget_state) = app_state.states.cpu_state.widget_states.get_mut(&widget_id)
         {
             let cpu_data = &app_state.converted_data.cpu_data;
-            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let border_style = alert_border_style.unwrap_or_else(|| {
+                self.get_border_style(widget_id, app_state.current_widget.widget_id)
+            });
             let x_bounds = [0, cpu_widget_state.current_display_time];
             let hide_x_labels = should_hide_x_label(
                 app_state.app_config_fields.hide_time,
@@ -193,27 +220,60 @@ impl Painter {
                 draw_loc,
             );
 
-            let points = self.generate_points(
+            let mut points = self.generate_points(
                 cpu_widget_state,
                 cpu_data,
                 app_state.app_config_fields.show_average_cpu,
             );
 
+            // In delta mode, usage is shown as how many percentage points it
+            // changes by per second.
+            let deltas = if cpu_widget_state.is_delta {
+                points
+                    .iter()
+                    .map(|data| split_by_sign(&delta_points(data.points, 1.0)))
+                    .collect::<Vec<_>>()
+            } else {
+                vec![]
+            };
+            let (y_bounds, y_labels) = if cpu_widget_state.is_delta {
+                points = delta_graph_data(
+                    &deltas,
+                    self.colours.regressed_text_style,
+                    self.colours.improved_text_style,
+                );
+                delta_y_axis(&deltas, 1.0, |range| format!("{range:.1}%/s"))
+            } else {
+                (Y_BOUNDS, Y_LABELS.to_vec())
+            };
+
             // TODO: Maybe hide load avg if too long? Or maybe the CPU part.
-            let title = {
+            let title: Cow<'_, str> = {
+                let name = graph_title(
+                    if cpu_widget_state.is_delta {
+                        "CPU Δ/s"
+                    } else {
+                        "CPU"
+                    },
+                    app_state.app_config_fields.cpu_smooth_n,
+                );
+
                 #[cfg(target_family = "unix")]
                 {
                     let load_avg = app_state.converted_data.load_avg_data;
                     let load_avg_str = format!(
-                        "─ {:.2} {:.2} {:.2} ",
-                        load_avg[0], load_avg[1], load_avg[2]
+                        "{} {:.2} {:.2} {:.2} ",
+                        self.widget_border(&BottomWidgetType::Cpu).horizontal(),
+                        load_avg[0],
+                        load_avg[1],
+                        load_avg[2]
                     );
 
-                    concat_string::concat_string!(" CPU ", load_avg_str).into()
+                    concat_string::concat_string!(name, load_avg_str).into()
                 }
                 #[cfg(not(target_family = "unix"))]
                 {
-                    " CPU ".into()
+                    name.into()
                 }
             };
 
@@ -226,11 +286,14 @@ impl Painter {
             TimeGraph {
                 x_bounds,
                 hide_x_labels,
-                y_bounds: Y_BOUNDS,
-                y_labels: &Y_LABELS,
+                end_time,
+                y_bounds,
+                y_labels: &y_labels,
                 graph_style: self.colours.graph_style,
                 border_style,
-                title,
+                border: self.widget_border(&BottomWidgetType::Cpu),
+                is_selected: widget_id == app_state.current_widget.widget_id,
+                title: self.widget_title(title),
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_position: None,
@@ -262,6 +325,7 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::CpuLegend),
             };
 
             cpu_widget_state.table.draw(
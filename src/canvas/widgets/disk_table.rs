@@ -1,18 +1,47 @@
-use tui::{layout::Rect, Frame};
+use tui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
 
 use crate::{
-    app,
+    app::{self, disk_bench::BENCHMARK_LABEL, layout_manager::BottomWidgetType},
     canvas::{
         components::data_table::{DrawInfo, SelectionState},
         Painter,
     },
+    data_collection::error::Subsystem,
 };
 
 impl Painter {
     pub fn draw_disk_table(
         &self, f: &mut Frame<'_>, app_state: &mut app::App, draw_loc: Rect, widget_id: u64,
     ) {
+        if self.draw_collection_error(
+            f,
+            app_state,
+            draw_loc,
+            widget_id,
+            " Disks ",
+            &[Subsystem::Disks, Subsystem::DiskIo],
+        ) {
+            return;
+        }
+
+        let draw_loc = if app_state.disk_bench.is_some() {
+            let [table_loc, bench_loc] = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(4)])
+                .areas(draw_loc);
+            self.draw_disk_bench(f, app_state, bench_loc, widget_id);
+            table_loc
+        } else {
+            draw_loc
+        };
+
         let recalculate_column_widths = app_state.should_get_widget_bounds();
+        let alert_border_style = self.alert_border_style(app_state, &BottomWidgetType::Disk);
         if let Some(disk_widget_state) = app_state
             .states
             .disk_state
@@ -26,8 +55,10 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Disk),
             };
 
+            disk_widget_state.table.styling.alert_border_style = alert_border_style;
             disk_widget_state.table.draw(
                 f,
                 &draw_info,
@@ -36,4 +67,29 @@ impl Painter {
             );
         }
     }
+
+    /// Draws how the `--disk_bench` benchmark is going below the disk table.
+    fn draw_disk_bench(
+        &self, f: &mut Frame<'_>, app_state: &app::App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let Some(disk_bench) = &app_state.disk_bench else {
+            return;
+        };
+
+        let block = self
+            .widget_border(&BottomWidgetType::Disk)
+            .block(widget_id == app_state.current_widget.widget_id)
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+            .title(Span::styled(
+                format!(" {BENCHMARK_LABEL} "),
+                self.colours.warning_text_style,
+            ));
+        let lines = disk_bench
+            .lines()
+            .into_iter()
+            .map(|line| Line::from(Span::styled(line, self.colours.text_style)))
+            .collect::<Vec<_>>();
+
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+    }
 }
@@ -0,0 +1,106 @@
+use tui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::{exec::ExecStatus, layout_manager::BottomWidgetType, App},
+    canvas::{components::time_graph::clock_label, Painter},
+};
+
+impl Painter {
+    /// Draws what the `--exec` command printed the last time it ran, with
+    /// stderr below stdout.
+    pub fn draw_exec_output(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let Some(exec_widget_state) = app_state.states.exec_state.get_mut_widget_state(widget_id)
+        else {
+            return;
+        };
+
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+        let border = self.widget_border(&BottomWidgetType::Exec);
+
+        let command = app_state.run_command.as_deref().unwrap_or_default();
+        let mut title = vec![Span::styled(
+            self.widget_title(format!(" Exec: {command} ")),
+            self.colours.widget_title_style,
+        )];
+        if let Some(output) = &app_state.exec_output {
+            let status_style = if output.status == ExecStatus::Exited(Some(0)) {
+                self.colours.widget_title_style
+            } else {
+                self.colours.invalid_query_style
+            };
+            title.push(Span::styled(border.horizontal(), border_style));
+            title.push(Span::styled(
+                format!(
+                    " {} at {} ",
+                    output.status_text(),
+                    clock_label(output.finished_at, false)
+                ),
+                status_style,
+            ));
+        }
+        if app_state.is_expanded {
+            title.push(Span::styled(
+                format!("{} Esc to go back ", border.horizontal()),
+                border_style,
+            ));
+        }
+
+        let block = border
+            .block(is_on_widget)
+            .title(Line::from(title))
+            .border_style(border_style);
+
+        let height = block.inner(draw_loc).height;
+        exec_widget_state.height = height;
+
+        let lines = match &app_state.exec_output {
+            Some(output) => {
+                exec_widget_state.clamp(output.num_lines());
+
+                let stdout = output
+                    .stdout
+                    .iter()
+                    .map(|line| Line::from(Span::styled(line.as_str(), self.colours.text_style)));
+                let stderr = output.stderr.iter().map(|line| {
+                    Line::from(Span::styled(
+                        line.as_str(),
+                        self.colours.invalid_query_style,
+                    ))
+                });
+
+                stdout
+                    .chain(stderr)
+                    .skip(exec_widget_state.scroll_offset)
+                    .take(height.into())
+                    .collect()
+            }
+            None => vec![Line::from(Span::styled(
+                "Waiting for the command to finish...",
+                self.colours.disabled_text_style,
+            ))],
+        };
+
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+
+        if should_get_widget_bounds {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
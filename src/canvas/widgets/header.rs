@@ -0,0 +1,281 @@
+use tui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    app::{data_farmer::DataCollection, frozen_state::FrozenState, App},
+    canvas::Painter,
+    data_collection::{
+        cpu::CpuDataType,
+        processes::{ProcessHarvest, ProcessState},
+    },
+    data_conversion::{binary_byte_string, convert_load_avg_sparkline},
+};
+
+/// How many processes are in each state.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TaskCounts {
+    total: usize,
+    running: usize,
+    sleeping: usize,
+    stopped: usize,
+    zombie: usize,
+}
+
+impl TaskCounts {
+    fn count<'a>(processes: impl IntoIterator<Item = &'a ProcessHarvest>) -> Self {
+        processes
+            .into_iter()
+            .fold(Self::default(), |mut counts, process| {
+                counts.total += 1;
+                match ProcessState::from(process.process_state.1) {
+                    ProcessState::Running => counts.running += 1,
+                    ProcessState::Sleeping | ProcessState::DiskSleep | ProcessState::Idle => {
+                        counts.sleeping += 1
+                    }
+                    ProcessState::Stopped | ProcessState::TracingStop => counts.stopped += 1,
+                    ProcessState::Zombie => counts.zombie += 1,
+                    _ => {}
+                }
+                counts
+            })
+    }
+}
+
+/// How loaded the system is, relative to how many CPUs it has.
+#[derive(Debug, PartialEq, Eq)]
+enum LoadLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl LoadLevel {
+    /// Under one runnable task per CPU is low, and over two is high.
+    fn of(load: f64, num_cpus: usize) -> Self {
+        let per_cpu = load / num_cpus as f64;
+        if per_cpu < 1.0 {
+            LoadLevel::Low
+        } else if per_cpu <= 2.0 {
+            LoadLevel::Medium
+        } else {
+            LoadLevel::High
+        }
+    }
+}
+
+/// The data being shown, which is the frozen data if frozen.
+pub fn shown_data(app_state: &App) -> &DataCollection {
+    match &app_state.frozen_state {
+        FrozenState::NotFrozen => &app_state.data_collection,
+        FrozenState::Frozen(data) => data,
+    }
+}
+
+/// How many lines the header takes up, which includes the load averages if
+/// they're collected.
+pub fn header_height(app_state: &App) -> u16 {
+    if shown_data(app_state).load_avg_history.is_empty() {
+        3
+    } else {
+        4
+    }
+}
+
+impl Painter {
+    /// Draws a summary of CPU, memory, swap, and process states, similar to
+    /// the one at the top of `top`, with the load averages below it if they're
+    /// collected.
+    pub fn draw_header(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let data = shown_data(app_state);
+
+        let mut lines = vec![
+            self.header_line("CPU", cpu_summary(data)),
+            self.header_line("Mem", mem_summary(data)),
+            self.header_line("Swap", swap_and_task_summary(data)),
+        ];
+
+        let load_loc = (!data.load_avg_history.is_empty() && draw_loc.height > 3).then(|| {
+            lines.push(self.load_line(data));
+            (
+                (draw_loc.x, draw_loc.y + 3),
+                (draw_loc.x + draw_loc.width, draw_loc.y + 4),
+            )
+        });
+
+        f.render_widget(Paragraph::new(lines), draw_loc);
+        app_state.header_load_loc = load_loc;
+    }
+
+    /// The load averages, coloured by how loaded the system is, followed by the
+    /// task counts and a sparkline of the recent 1 minute load average.
+    fn load_line(&self, data: &DataCollection) -> Line<'static> {
+        let num_cpus = data
+            .cpu_harvest
+            .iter()
+            .filter(|cpu| matches!(cpu.data_type, CpuDataType::Cpu(_)))
+            .count();
+        let load_style = |load: f64| -> Style {
+            if num_cpus == 0 {
+                return self.colours.text_style;
+            }
+
+            // These are the same green, yellow, and red as the battery levels.
+            match LoadLevel::of(load, num_cpus) {
+                LoadLevel::Low => self.colours.high_battery,
+                LoadLevel::Medium => self.colours.medium_battery,
+                LoadLevel::High => self.colours.low_battery,
+            }
+        };
+
+        let [one, five, fifteen] = data.load_avg_harvest.map(f64::from);
+        let mut spans = vec![
+            Span::styled(format!("{:<5}", "Load"), self.colours.table_header_style),
+            Span::styled(format!("{one:.2}"), load_style(one)),
+            Span::styled(", ", self.colours.text_style),
+            Span::styled(format!("{five:.2}"), load_style(five)),
+            Span::styled(", ", self.colours.text_style),
+            Span::styled(format!("{fifteen:.2}"), load_style(fifteen)),
+        ];
+        if let Some(tasks) = &data.sched_tasks_harvest {
+            spans.push(Span::styled(
+                format!(" | {}/{} tasks running", tasks.running, tasks.total),
+                self.colours.text_style,
+            ));
+        }
+        spans.push(Span::styled(
+            format!("  {}", convert_load_avg_sparkline(&data.load_avg_history)),
+            load_style(one),
+        ));
+
+        Line::from(spans)
+    }
+
+    fn header_line(&self, label: &'static str, summary: String) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("{label:<5}"), self.colours.table_header_style),
+            Span::styled(summary, self.colours.text_style),
+        ])
+    }
+}
+
+fn cpu_summary(data: &DataCollection) -> String {
+    if let Some(times) = &data.cpu_times_harvest {
+        return format!(
+            "{:5.1}% total, {:5.1}% us, {:5.1}% sy, {:5.1}% id, {:5.1}% wa, {:5.1}% st",
+            100.0 - times.idle,
+            times.user,
+            times.system,
+            times.idle,
+            times.iowait,
+            times.steal
+        );
+    }
+
+    // Without a breakdown, just show the total from the average, or from the
+    // individual cores if the average isn't collected.
+    let total = match data
+        .cpu_harvest
+        .iter()
+        .find(|cpu| matches!(cpu.data_type, CpuDataType::Avg))
+    {
+        Some(avg) => avg.cpu_usage,
+        None if data.cpu_harvest.is_empty() => 0.0,
+        None => {
+            data.cpu_harvest
+                .iter()
+                .map(|cpu| cpu.cpu_usage)
+                .sum::<f64>()
+                / data.cpu_harvest.len() as f64
+        }
+    };
+
+    format!("{total:5.1}% total")
+}
+
+fn mem_summary(data: &DataCollection) -> String {
+    let memory = &data.memory_harvest;
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let cache = data.cache_harvest.used_bytes;
+        format!(
+            "{} total, {} used, {} free, {} buff/cache",
+            binary_byte_string(memory.total_bytes),
+            binary_byte_string(memory.used_bytes),
+            binary_byte_string(
+                memory
+                    .total_bytes
+                    .saturating_sub(memory.used_bytes)
+                    .saturating_sub(cache)
+            ),
+            binary_byte_string(cache)
+        )
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        format!(
+            "{} total, {} used, {} free",
+            binary_byte_string(memory.total_bytes),
+            binary_byte_string(memory.used_bytes),
+            binary_byte_string(memory.total_bytes.saturating_sub(memory.used_bytes))
+        )
+    }
+}
+
+fn swap_and_task_summary(data: &DataCollection) -> String {
+    let swap = &data.swap_harvest;
+    let tasks = TaskCounts::count(data.process_data.process_harvest.values());
+
+    format!(
+        "{} total, {} used, {} free | Tasks: {} total, {} running, {} sleeping, {} stopped, {} zombie",
+        binary_byte_string(swap.total_bytes),
+        binary_byte_string(swap.used_bytes),
+        binary_byte_string(swap.total_bytes.saturating_sub(swap.used_bytes)),
+        tasks.total,
+        tasks.running,
+        tasks.sleeping,
+        tasks.stopped,
+        tasks.zombie
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn load_levels() {
+        assert_eq!(LoadLevel::of(3.9, 4), LoadLevel::Low);
+        assert_eq!(LoadLevel::of(4.0, 4), LoadLevel::Medium);
+        assert_eq!(LoadLevel::of(8.0, 4), LoadLevel::Medium);
+        assert_eq!(LoadLevel::of(8.1, 4), LoadLevel::High);
+    }
+
+    #[test]
+    fn task_counts() {
+        let processes = ['R', 'S', 'S', 'D', 'I', 'T', 't', 'Z', 'X']
+            .into_iter()
+            .map(|state| ProcessHarvest {
+                process_state: (String::new(), state),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            TaskCounts::count(&processes),
+            TaskCounts {
+                total: 9,
+                running: 1,
+                sleeping: 4,
+                stopped: 2,
+                zombie: 1,
+            }
+        );
+    }
+}
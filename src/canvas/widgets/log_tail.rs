@@ -0,0 +1,148 @@
+use tui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Paragraph, Tabs},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::Painter,
+    widgets::log_tail::visible_rows,
+};
+
+impl Painter {
+    /// Draws the last lines of a file followed with `--watch_file`, with a tab
+    /// for each file if there's more than one.
+    pub fn draw_log_tail(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let Some(log_tail_widget_state) = app_state
+            .states
+            .log_tail_state
+            .get_mut_widget_state(widget_id)
+        else {
+            return;
+        };
+        let Some(log_tail) = app_state.log_tails.get(log_tail_widget_state.selected_tab) else {
+            return;
+        };
+
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+        let border = self.widget_border(&BottomWidgetType::LogTail);
+        let horizontal = border.horizontal();
+
+        let mut title = vec![Span::styled(
+            self.widget_title(format!(" Log: {} ", log_tail.path.display())),
+            self.colours.widget_title_style,
+        )];
+        if log_tail_widget_state.scroll_back > 0 {
+            title.push(Span::styled(
+                format!(
+                    "{horizontal} {} lines below ",
+                    log_tail_widget_state.scroll_back
+                ),
+                border_style,
+            ));
+        }
+        if let Some(err) = &log_tail.error {
+            title.push(Span::styled(horizontal, border_style));
+            title.push(Span::styled(
+                format!(" {err} "),
+                self.colours.invalid_query_style,
+            ));
+        }
+        if app_state.is_expanded {
+            title.push(Span::styled(
+                format!("{horizontal} Esc to go back "),
+                border_style,
+            ));
+        }
+
+        let block = border
+            .block(is_on_widget)
+            .title(Line::from(title))
+            .border_style(border_style);
+        let inner_loc = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        let text_loc = if app_state.log_tails.len() > 1 {
+            let [tab_loc, text_loc] = Layout::default()
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .direction(Direction::Vertical)
+                .areas(inner_loc);
+
+            let names = app_state
+                .log_tails
+                .iter()
+                .map(|log_tail| log_tail.name())
+                .collect::<Vec<_>>();
+            f.render_widget(
+                Tabs::new(names.iter().map(|name| Line::from(name.as_str())))
+                    .divider(tui::symbols::line::VERTICAL)
+                    .style(self.colours.text_style)
+                    .highlight_style(self.colours.selected_text_style)
+                    .select(log_tail_widget_state.selected_tab),
+                tab_loc,
+            );
+
+            if should_get_widget_bounds {
+                // Each label has a space on either side, and the tabs are
+                // separated by a divider.
+                let mut current_x = tab_loc.x + 1;
+                let current_y = tab_loc.y;
+                let tab_click_locs = names
+                    .iter()
+                    .map(|name| {
+                        let width = UnicodeWidthStr::width(name.as_str()) as u16;
+                        let loc = ((current_x, current_y), (current_x + width, current_y));
+                        current_x += width + 3;
+                        loc
+                    })
+                    .collect();
+                log_tail_widget_state.tab_click_locs = Some(tab_click_locs);
+            }
+
+            text_loc
+        } else {
+            log_tail_widget_state.tab_click_locs = None;
+            inner_loc
+        };
+
+        log_tail_widget_state.height = text_loc.height;
+
+        let lines = if log_tail.lines.is_empty() {
+            vec![Line::from(Span::styled(
+                "Waiting for lines...",
+                self.colours.disabled_text_style,
+            ))]
+        } else {
+            visible_rows(
+                &log_tail.lines,
+                log_tail_widget_state.scroll_back,
+                text_loc.width,
+                text_loc.height,
+            )
+            .into_iter()
+            .map(|row| Line::from(Span::styled(row, self.colours.text_style)))
+            .collect()
+        };
+
+        f.render_widget(Paragraph::new(lines), text_loc);
+
+        if should_get_widget_bounds {
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
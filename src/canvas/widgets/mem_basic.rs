@@ -1,26 +1,55 @@
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
-    widgets::Block,
+    style::Style,
+    widgets::Paragraph,
     Frame,
 };
 
 use crate::{
-    app::App,
+    app::{layout_manager::BottomWidgetType, App},
     canvas::{components::pipe_gauge::PipeGauge, Painter},
-    constants::*,
+    data_collection::error::Subsystem,
 };
 
 impl Painter {
+    /// The style of the swap I/O line, which is highlighted while more pages
+    /// are being swapped out than the warning threshold allows.
+    pub(crate) fn swap_io_style(&self, app_state: &App) -> Style {
+        match &app_state.data_collection.swap_io {
+            Some(swap_io)
+                if swap_io.swap_out_pages_per_sec
+                    > app_state.app_config_fields.swap_out_warning =>
+            {
+                self.colours.warning_text_style
+            }
+            _ => self.colours.swap_style,
+        }
+    }
+
     pub fn draw_basic_memory(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        if self.draw_collection_error(
+            f,
+            app_state,
+            draw_loc,
+            widget_id,
+            " Memory ",
+            &[Subsystem::Memory],
+        ) {
+            return;
+        }
+
         let mem_data = &app_state.converted_data.mem_data;
         let mut draw_widgets: Vec<PipeGauge<'_>> = Vec::new();
+        // Lines of text shown under a gauge, like the swap I/O line, along with
+        // how many gauges come before them.
+        let mut text_rows: Vec<(usize, &str, Style)> = Vec::new();
 
         if app_state.current_widget.widget_id == widget_id {
             f.render_widget(
-                Block::default()
-                    .borders(SIDE_BORDERS)
+                self.widget_border(&BottomWidgetType::BasicMem)
+                    .side_block()
                     .border_style(self.colours.highlighted_border_style),
                 draw_loc,
             );
@@ -54,6 +83,14 @@ impl Painter {
                 .gauge_style(self.colours.ram_style),
         );
 
+        if let Some(pss_label) = &app_state.converted_data.pss_label {
+            text_rows.push((draw_widgets.len(), pss_label, self.colours.ram_style));
+        }
+
+        if let Some(cgroup_label) = &app_state.converted_data.cgroup_memory_label {
+            text_rows.push((draw_widgets.len(), cgroup_label, self.colours.ram_style));
+        }
+
         #[cfg(not(target_os = "windows"))]
         {
             if let Some((_, label_frac)) = &app_state.converted_data.cache_labels {
@@ -103,6 +140,14 @@ impl Painter {
                     .label_style(self.colours.swap_style)
                     .gauge_style(self.colours.swap_style),
             );
+
+            if let Some(swap_io_label) = &app_state.converted_data.swap_io_label {
+                text_rows.push((
+                    draw_widgets.len(),
+                    swap_io_label,
+                    self.swap_io_style(app_state),
+                ));
+            }
         }
 
         #[cfg(feature = "zfs")]
@@ -174,8 +219,9 @@ impl Painter {
             }
         }
 
+        let num_rows = draw_widgets.len() + text_rows.len();
         let margined_loc = Layout::default()
-            .constraints(vec![Constraint::Length(1); draw_widgets.len()])
+            .constraints(vec![Constraint::Length(1); num_rows])
             .direction(Direction::Vertical)
             .horizontal_margin(1)
             .split(draw_loc);
@@ -184,9 +230,20 @@ impl Painter {
             .into_iter()
             .enumerate()
             .for_each(|(index, widget)| {
-                f.render_widget(widget, margined_loc[index]);
+                let lines_above = text_rows
+                    .iter()
+                    .filter(|(gauges_above, _, _)| *gauges_above <= index)
+                    .count();
+                f.render_widget(widget, margined_loc[index + lines_above]);
             });
 
+        for (lines_above, (gauges_above, label, style)) in text_rows.into_iter().enumerate() {
+            f.render_widget(
+                Paragraph::new(label).style(style),
+                margined_loc[gauges_above + lines_above],
+            );
+        }
+
         // Update draw loc in widget map
         if app_state.should_get_widget_bounds() {
             if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
@@ -1,27 +1,96 @@
 use std::borrow::Cow;
 
 use tui::{
-    layout::{Constraint, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     symbols::Marker,
+    text::{Line, Span, Text},
+    widgets::{Row, Table},
     Frame,
 };
 
 use crate::{
-    app::App,
+    app::{layout_manager::BottomWidgetType, App},
     canvas::{
         components::time_graph::{GraphData, TimeGraph},
-        drawing_utils::should_hide_x_label,
+        drawing_utils::{
+            delta_graph_data, delta_y_axis, draw_stats_overlay, graph_title, should_hide_x_label,
+            stats_text,
+        },
         Painter,
     },
+    data_collection::{error::Subsystem, memory::FRAGMENTATION_ORDER},
+    data_conversion::{delta_points, split_by_sign},
+    utils::{
+        data_prefixes::get_binary_bytes,
+        stats::{summarize, visible_values},
+    },
 };
 
+/// Formats a rate of bytes per second for the delta mode's labels.
+fn bytes_per_second(bytes: f64) -> String {
+    let (value, unit) = get_binary_bytes(bytes.abs() as u64);
+    format!("{value:.1}{unit}/s")
+}
+
 impl Painter {
     pub fn draw_memory_graph(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        if self.draw_collection_error(
+            f,
+            app_state,
+            draw_loc,
+            widget_id,
+            " Memory ",
+            &[Subsystem::Memory],
+        ) {
+            return;
+        }
+
+        let end_time = app_state.graph_end_time();
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
+        let show_swap_devices = app_state
+            .states
+            .mem_state
+            .widget_states
+            .get(&widget_id)
+            .is_some_and(|state| state.show_swap_devices);
+        let draw_loc = if app_state.is_expanded && !app_state.converted_data.buddy_zones.is_empty()
+        {
+            // Room for the borders, header, and a row per zone.
+            let rows = app_state.converted_data.buddy_zones.len() as u16;
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length((rows + 3).min(draw_loc.height / 3)),
+                ])
+                .split(draw_loc);
+
+            self.draw_buddy_zones(f, app_state, chunks[1], widget_id);
+            chunks[0]
+        } else {
+            draw_loc
+        };
+        let graph_loc = if show_swap_devices {
+            // Room for the borders, header, and a row per device.
+            let rows = app_state.converted_data.swap_devices.len().max(1) as u16;
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length((rows + 3).min(draw_loc.height / 2)),
+                ])
+                .split(draw_loc);
+
+            self.draw_swap_devices(f, app_state, chunks[1], widget_id);
+            chunks[0]
+        } else {
+            draw_loc
+        };
+
         if let Some(mem_widget_state) = app_state.states.mem_state.widget_states.get_mut(&widget_id)
         {
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
@@ -30,9 +99,39 @@ impl Painter {
                 app_state.app_config_fields.hide_time,
                 app_state.app_config_fields.autohide_time,
                 &mut mem_widget_state.autohide_timer,
-                draw_loc,
+                graph_loc,
             );
-            let points = {
+            // In delta mode, only RAM is shown, as how many bytes of it are
+            // used more or less per second.
+            let total_bytes = app_state.data_collection.memory_harvest.total_bytes as f64;
+            let deltas = if mem_widget_state.is_delta {
+                vec![split_by_sign(&delta_points(
+                    &app_state.converted_data.mem_data,
+                    total_bytes / 100.0,
+                ))]
+            } else {
+                vec![]
+            };
+
+            let points = if mem_widget_state.is_delta {
+                let mut points = delta_graph_data(
+                    &deltas,
+                    self.colours.regressed_text_style,
+                    self.colours.improved_text_style,
+                );
+                if let (Some(data), Some(((_, increase), (_, decrease)))) = (
+                    points.first_mut(),
+                    deltas
+                        .first()
+                        .and_then(|(increases, decreases)| increases.last().zip(decreases.last())),
+                ) {
+                    let rate = increase + decrease;
+                    let sign = if rate < 0.0 { "-" } else { "+" };
+                    data.name = Some(format!("RAM:{sign}{}", bytes_per_second(rate)).into());
+                }
+
+                points
+            } else {
                 let mut size = 1;
                 if app_state.converted_data.swap_labels.is_some() {
                     size += 1; // add capacity for SWAP
@@ -52,7 +151,17 @@ impl Painter {
 
                 let mut points = Vec::with_capacity(size);
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.mem_labels {
-                    let mem_label = format!("RAM:{label_percent}{label_frac}");
+                    let mut mem_label = format!("RAM:{label_percent}{label_frac}");
+                    for label in [
+                        &app_state.converted_data.pss_label,
+                        &app_state.converted_data.cgroup_memory_label,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        mem_label.push(' ');
+                        mem_label.push_str(label);
+                    }
                     points.push(GraphData {
                         points: &app_state.converted_data.mem_data,
                         style: self.colours.ram_style,
@@ -91,8 +200,11 @@ impl Painter {
                         let mut color_index = 0;
                         let gpu_styles = &self.colours.gpu_colours;
                         gpu_data.iter().for_each(|gpu| {
-                            let gpu_label =
+                            let mut gpu_label =
                                 format!("{}:{}{}", gpu.name, gpu.mem_percent, gpu.mem_total);
+                            if let Some(usage) = &gpu.usage {
+                                gpu_label.push_str(&format!(" ({usage})"));
+                            }
                             let style = {
                                 if gpu_styles.is_empty() {
                                     tui::style::Style::default()
@@ -123,21 +235,61 @@ impl Painter {
                 Marker::Braille
             };
 
+            let (y_bounds, y_labels) = if mem_widget_state.is_delta {
+                delta_y_axis(&deltas, 1024.0, bytes_per_second)
+            } else {
+                (Y_BOUNDS, Y_LABELS.to_vec())
+            };
+            let name = if mem_widget_state.is_delta {
+                "Memory Δ/s"
+            } else {
+                "Memory"
+            };
+
             TimeGraph {
                 x_bounds,
                 hide_x_labels,
-                y_bounds: Y_BOUNDS,
-                y_labels: &Y_LABELS,
+                end_time,
+                y_bounds,
+                y_labels: &y_labels,
                 graph_style: self.colours.graph_style,
                 border_style,
-                title: " Memory ".into(),
+                border: self.widget_border(&BottomWidgetType::Mem),
+                is_selected: widget_id == app_state.current_widget.widget_id,
+                title: self
+                    .widget_title(graph_title(name, app_state.app_config_fields.mem_smooth_n)),
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_position: app_state.app_config_fields.memory_legend_position,
                 legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
                 marker,
             }
-            .draw_time_graph(f, draw_loc, &points);
+            .draw_time_graph(f, graph_loc, &points);
+
+            // Each entry is named by the start of its legend label, like "RAM".
+            if mem_widget_state.show_stats && !mem_widget_state.is_delta {
+                let lines = points
+                    .iter()
+                    .filter_map(|data| {
+                        let values = visible_values(data.points, x_bounds[1]);
+                        let name = data.name.as_deref()?.split(':').next()?;
+                        (!values.is_empty()).then(|| {
+                            let text = stats_text(name, &summarize(&values), |value| {
+                                format!("{value:.1}%")
+                            });
+                            Line::styled(text, data.style)
+                        })
+                    })
+                    .collect();
+
+                draw_stats_overlay(
+                    f,
+                    graph_loc,
+                    lines,
+                    app_state.app_config_fields.memory_legend_position,
+                    points.len() as u16 + 2,
+                );
+            }
         }
 
         if app_state.should_get_widget_bounds() {
@@ -149,4 +301,119 @@ impl Painter {
             }
         }
     }
+
+    /// Draws a table of how fragmented the free memory of each zone is, with
+    /// the most fragmented zones highlighted.
+    fn draw_buddy_zones(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, widget_id: u64) {
+        const HEADERS: [&str; 5] = ["Node", "Zone", "Free", "Large", "Frag index"];
+        const COLUMN_WIDTH: u16 = 10;
+
+        let rows = app_state
+            .converted_data
+            .buddy_zones
+            .iter()
+            .map(|zone| {
+                let row = Row::new([
+                    zone.node.as_str(),
+                    zone.zone.as_str(),
+                    zone.free.as_str(),
+                    zone.large_free.as_str(),
+                    zone.index.as_str(),
+                ]);
+                if zone.is_fragmented {
+                    row.style(self.colours.warning_text_style)
+                } else {
+                    row
+                }
+            })
+            .collect::<Vec<_>>();
+        let widths = [Constraint::Length(COLUMN_WIDTH); HEADERS.len()];
+
+        let block = self
+            .widget_border(&BottomWidgetType::Mem)
+            .block(widget_id == app_state.current_widget.widget_id)
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+            .title(Span::styled(
+                format!(" Fragmentation (order {FRAGMENTATION_ORDER}) "),
+                self.colours.widget_title_style,
+            ));
+
+        f.render_widget(
+            Table::new(rows, widths)
+                .header(Row::new(HEADERS).style(self.colours.table_header_style))
+                .block(block)
+                .style(self.colours.text_style),
+            draw_loc,
+        );
+    }
+
+    /// Draws a table of each swap device, with a compression ratio column if
+    /// any of them are zram devices, and how quickly pages are being swapped in
+    /// its title.
+    fn draw_swap_devices(
+        &self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, widget_id: u64,
+    ) {
+        const HEADERS: [&str; 7] = [
+            "Device", "Type", "Size", "Used", "Free", "Priority", "Ratio",
+        ];
+        const COLUMN_WIDTH: u16 = 10;
+
+        let devices = &app_state.converted_data.swap_devices;
+        let has_zram = devices
+            .iter()
+            .any(|device| device.compression_ratio.is_some());
+        let num_columns = if has_zram {
+            HEADERS.len()
+        } else {
+            HEADERS.len() - 1
+        };
+
+        let rows = if devices.is_empty() {
+            vec![Row::new(["No swap devices"])]
+        } else {
+            devices
+                .iter()
+                .map(|device| {
+                    Row::new(
+                        [
+                            Text::raw(device.path.as_str()),
+                            Text::raw(device.kind.as_str()),
+                            Text::raw(device.total.as_str()),
+                            Text::styled(device.used.as_str(), self.colours.swap_style),
+                            Text::raw(device.free.as_str()),
+                            Text::raw(device.priority.as_str()),
+                            Text::raw(device.compression_ratio.as_deref().unwrap_or("")),
+                        ]
+                        .into_iter()
+                        .take(num_columns),
+                    )
+                })
+                .collect()
+        };
+        let widths = std::iter::once(Constraint::Min(COLUMN_WIDTH))
+            .chain(std::iter::repeat(Constraint::Length(COLUMN_WIDTH)).take(num_columns - 1))
+            .collect::<Vec<_>>();
+
+        let mut block = self
+            .widget_border(&BottomWidgetType::Mem)
+            .block(widget_id == app_state.current_widget.widget_id)
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id));
+        if let Some(swap_io_label) = &app_state.converted_data.swap_io_label {
+            block = block.title(Span::styled(
+                format!(" {swap_io_label} "),
+                self.swap_io_style(app_state),
+            ));
+        }
+
+        f.render_widget(
+            Table::new(rows, &widths)
+                .header(
+                    Row::new(HEADERS.into_iter().take(num_columns))
+                        .style(self.colours.table_header_style),
+                )
+                .block(block)
+                .style(self.colours.text_style),
+            draw_loc,
+        );
+    }
 }
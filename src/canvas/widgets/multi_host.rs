@@ -0,0 +1,197 @@
+use tui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    app::{
+        multi_host::{ConnectionStatus, MultiHostState, RemoteHost},
+        App,
+    },
+    canvas::{components::pipe_gauge::PipeGauge, Painter},
+    data_collection::{cpu::CpuDataType, Data},
+    data_conversion::{binary_byte_string, convert_mem_label, dec_bytes_per_second_string},
+    utils::general::partial_ordering_desc,
+};
+
+impl Painter {
+    /// Draws each remote host side by side, in its own column.
+    pub fn draw_multi_host(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let Some(multi_host) = &app_state.multi_host else {
+            return;
+        };
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                multi_host
+                    .hosts
+                    .iter()
+                    .map(|_| Constraint::Ratio(1, multi_host.hosts.len() as u32))
+                    .collect::<Vec<_>>(),
+            )
+            .split(draw_loc);
+
+        for (index, (host, column)) in multi_host.hosts.iter().zip(columns.iter()).enumerate() {
+            self.draw_host_panel(f, multi_host, index, host, *column);
+        }
+    }
+
+    fn draw_host_panel(
+        &self, f: &mut Frame<'_>, multi_host: &MultiHostState, index: usize, host: &RemoteHost,
+        draw_loc: Rect,
+    ) {
+        let border_style = if index == multi_host.focused {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+        let block = self
+            .canvas_style
+            .border_style
+            .block(index == multi_host.focused)
+            .title(Span::styled(
+                format!(" {} ", host.name()),
+                self.colours.widget_title_style,
+            ))
+            .border_style(border_style);
+        let inner = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        match &host.data {
+            Some(data) => self.draw_host_data(f, data, inner),
+            None if host.status == ConnectionStatus::Connecting => {
+                f.render_widget(
+                    Paragraph::new(Span::styled(
+                        format!("Connecting to {}...", host.address),
+                        self.colours.disabled_text_style,
+                    )),
+                    inner,
+                );
+            }
+            None => {}
+        }
+
+        if host.status == ConnectionStatus::Disconnected {
+            let overlay = Rect {
+                y: inner.y + inner.height.saturating_sub(3) / 2,
+                height: inner.height.min(3),
+                ..inner
+            };
+            f.render_widget(Clear, overlay);
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    "DISCONNECTED",
+                    self.colours.invalid_query_style,
+                ))
+                .alignment(Alignment::Center)
+                .block(
+                    self.dialog_border()
+                        .block(false)
+                        .border_style(self.colours.invalid_query_style),
+                ),
+                overlay,
+            );
+        }
+    }
+
+    /// Draws a host's data: gauges for CPU and memory usage, network rates,
+    /// and the processes using the most CPU.
+    fn draw_host_data(&self, f: &mut Frame<'_>, data: &Data, draw_loc: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .split(draw_loc);
+
+        let avg_cpu = data
+            .cpu
+            .iter()
+            .flatten()
+            .find(|cpu| matches!(cpu.data_type, CpuDataType::Avg))
+            .map_or(0.0, |cpu| cpu.cpu_usage);
+        f.render_widget(
+            PipeGauge::default()
+                .ratio(avg_cpu / 100.0)
+                .start_label("CPU")
+                .inner_label(format!("{avg_cpu:3.0}%"))
+                .label_style(self.colours.avg_cpu_colour)
+                .gauge_style(self.colours.avg_cpu_colour),
+            chunks[0],
+        );
+
+        for (harvest, label, style, chunk) in [
+            (&data.memory, "RAM", self.colours.ram_style, chunks[1]),
+            (&data.swap, "SWP", self.colours.swap_style, chunks[2]),
+        ] {
+            let harvest = harvest.clone().unwrap_or_default();
+            let fraction_label = convert_mem_label(&harvest)
+                .map(|(_, fraction)| fraction.trim().to_string())
+                .unwrap_or_else(|| "0.0B/0.0B".to_string());
+
+            f.render_widget(
+                PipeGauge::default()
+                    .ratio(harvest.use_percent.unwrap_or(0.0) / 100.0)
+                    .start_label(label)
+                    .inner_label(fraction_label)
+                    .label_style(style)
+                    .gauge_style(style),
+                chunk,
+            );
+        }
+
+        let (rx, tx) = data
+            .network
+            .as_ref()
+            .map_or((0, 0), |network| (network.rx, network.tx));
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("RX: {:<12}", dec_bytes_per_second_string(rx / 8)),
+                    self.colours.rx_style,
+                ),
+                Span::styled(
+                    format!("TX: {}", dec_bytes_per_second_string(tx / 8)),
+                    self.colours.tx_style,
+                ),
+            ])),
+            chunks[3],
+        );
+
+        let mut processes = data.list_of_processes.iter().flatten().collect::<Vec<_>>();
+        processes.sort_by(|a, b| partial_ordering_desc(a.cpu_usage_percent, b.cpu_usage_percent));
+
+        let name_width = usize::from(draw_loc.width).saturating_sub(16).max(4);
+        let header = Line::from(Span::styled(
+            format!("{:<name_width$} {:>6} {:>8}", "Name", "CPU%", "Mem"),
+            self.colours.table_header_style,
+        ));
+        let rows = processes
+            .into_iter()
+            .take(chunks[5].height.saturating_sub(1).into())
+            .map(|process| {
+                let name = process.name.chars().take(name_width).collect::<String>();
+                Line::from(Span::styled(
+                    format!(
+                        "{name:<name_width$} {:>5.1}% {:>8}",
+                        process.cpu_usage_percent,
+                        binary_byte_string(process.mem_usage_bytes)
+                    ),
+                    self.colours.text_style,
+                ))
+            });
+
+        f.render_widget(
+            Paragraph::new(std::iter::once(header).chain(rows).collect::<Vec<_>>()),
+            chunks[5],
+        );
+    }
+}
@@ -5,7 +5,10 @@ use tui::{
     Frame,
 };
 
-use crate::{app::App, canvas::Painter, constants::*};
+use crate::{
+    app::{layout_manager::BottomWidgetType, App},
+    canvas::Painter,
+};
 
 impl Painter {
     pub fn draw_basic_network(
@@ -30,8 +33,8 @@ impl Painter {
 
         if app_state.current_widget.widget_id == widget_id {
             f.render_widget(
-                Block::default()
-                    .borders(SIDE_BORDERS)
+                self.widget_border(&BottomWidgetType::BasicNet)
+                    .side_block()
                     .border_style(self.colours.highlighted_border_style),
                 draw_loc,
             );
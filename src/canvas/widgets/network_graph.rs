@@ -1,36 +1,87 @@
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
     symbols::Marker,
-    text::Text,
-    widgets::{Block, Borders, Row, Table},
+    text::{Line, Span, Text},
+    widgets::{Cell, Row, Table},
     Frame,
 };
 
 use crate::{
-    app::{App, AxisScaling},
+    app::{layout_manager::BottomWidgetType, App, AxisScaling},
     canvas::{
         components::{
+            data_table::{DrawInfo, SelectionState},
             time_chart::Point,
             time_graph::{GraphData, TimeGraph},
         },
-        drawing_utils::should_hide_x_label,
+        drawing_utils::{draw_stats_overlay, graph_title, should_hide_x_label, stats_text},
         Painter,
     },
-    utils::{data_prefixes::*, data_units::DataUnit, general::partial_ordering},
+    data_conversion::{format_bandwidth, unscale_network_value},
+    utils::{
+        data_prefixes::*,
+        data_units::DataUnit,
+        general::partial_ordering,
+        stats::{summarize, visible_values},
+    },
 };
 
 impl Painter {
     pub fn draw_network(
-        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+        &self, f: &mut Frame<'_>, app_state: &mut App, widget_loc: Rect, widget_id: u64,
     ) {
-        if app_state.app_config_fields.use_old_network_legend {
-            const LEGEND_HEIGHT: u16 = 4;
+        let is_nic_stats_open = app_state
+            .states
+            .net_state
+            .widget_states
+            .get(&widget_id)
+            .is_some_and(|net_widget_state| net_widget_state.nic_stats_panel.is_open());
+        let draw_loc = if is_nic_stats_open {
+            let [graph_loc, panel_loc] = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(widget_loc);
+            self.draw_nic_stats_panel(f, app_state, panel_loc, widget_id);
+            graph_loc
+        } else {
+            widget_loc
+        };
+        let draw_loc = match &app_state.converted_data.sockstat {
+            Some(sockstat) if app_state.is_expanded => {
+                // Room for the borders, header, and a row per address family.
+                let rows = sockstat.rows.len() as u16;
+                let [graph_loc, sockstat_loc] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Min(0),
+                        Constraint::Length((rows + 3).min(draw_loc.height / 3)),
+                    ])
+                    .areas(draw_loc);
+
+                self.draw_sockstat(f, app_state, sockstat_loc, widget_id);
+                graph_loc
+            }
+            _ => draw_loc,
+        };
+
+        if app_state.app_config_fields.network_per_interface
+            && !app_state.converted_data.network_interfaces.is_empty()
+        {
+            self.draw_network_interfaces(f, app_state, draw_loc, widget_id);
+        } else if app_state.app_config_fields.use_old_network_legend {
+            // The IPv6 traffic gets its own row.
+            let legend_height = if app_state.converted_data.ipv6.is_some() {
+                5
+            } else {
+                4
+            };
             let network_chunk = Layout::default()
                 .direction(Direction::Vertical)
                 .margin(0)
                 .constraints([
-                    Constraint::Length(draw_loc.height.saturating_sub(LEGEND_HEIGHT)),
-                    Constraint::Length(LEGEND_HEIGHT),
+                    Constraint::Length(draw_loc.height.saturating_sub(legend_height)),
+                    Constraint::Length(legend_height),
                 ])
                 .split(draw_loc);
 
@@ -45,17 +96,95 @@ impl Painter {
             // Note that in both cases, we always go to the same widget id so it's fine to
             // do it like this lol.
             if let Some(network_widget) = app_state.widget_map.get_mut(&widget_id) {
-                network_widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
-                network_widget.bottom_right_corner =
-                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+                network_widget.top_left_corner = Some((widget_loc.x, widget_loc.y));
+                network_widget.bottom_right_corner = Some((
+                    widget_loc.x + widget_loc.width,
+                    widget_loc.y + widget_loc.height,
+                ));
             }
         }
     }
 
+    /// Draws a table of how many sockets of each protocol are in use, with
+    /// unusually many orphaned or `TIME_WAIT` TCP sockets highlighted.
+    fn draw_sockstat(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect, widget_id: u64) {
+        const HEADERS: [&str; 7] = ["", "TCP", "Orphaned", "TIME_WAIT", "UDP", "RAW", "Mem"];
+        const COLUMN_WIDTH: u16 = 10;
+
+        let Some(sockstat) = &app_state.converted_data.sockstat else {
+            return;
+        };
+        let highlight = |is_high: bool| {
+            if is_high {
+                self.colours.warning_text_style
+            } else {
+                Style::default()
+            }
+        };
+
+        let rows = sockstat
+            .rows
+            .iter()
+            .map(|row| {
+                Row::new([
+                    Cell::from(row.family),
+                    Cell::from(row.tcp.as_str()),
+                    Cell::from(row.orphaned.as_str()).style(highlight(row.is_orphaned_high)),
+                    Cell::from(row.time_wait.as_str()).style(highlight(row.is_time_wait_high)),
+                    Cell::from(row.udp.as_str()),
+                    Cell::from(row.raw.as_str()),
+                    Cell::from(row.mem.as_str()),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let widths = [Constraint::Length(COLUMN_WIDTH); HEADERS.len()];
+
+        let block = self
+            .widget_border(&BottomWidgetType::Net)
+            .block(widget_id == app_state.current_widget.widget_id)
+            .border_style(self.get_border_style(widget_id, app_state.current_widget.widget_id))
+            .title(Span::styled(
+                format!(" Socket Stats ({} used) ", sockstat.sockets_used),
+                self.colours.widget_title_style,
+            ));
+
+        f.render_widget(
+            Table::new(rows, widths)
+                .header(Row::new(HEADERS).style(self.colours.table_header_style))
+                .block(block)
+                .style(self.colours.text_style),
+            draw_loc,
+        );
+    }
+
+    /// Draws the hardware statistics of the interface picked with the panel.
+    fn draw_nic_stats_panel(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        if let Some(net_widget_state) = app_state.states.net_state.widget_states.get_mut(&widget_id)
+        {
+            let draw_info = DrawInfo {
+                loc: draw_loc,
+                force_redraw: app_state.is_force_redraw,
+                recalculate_column_widths: should_get_widget_bounds,
+                selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Net),
+            };
+
+            net_widget_state
+                .nic_stats_panel
+                .table
+                .draw(f, &draw_info, None, self);
+        }
+    }
+
     pub fn draw_network_graph(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
         hide_legend: bool,
     ) {
+        let end_time = app_state.graph_end_time();
         if let Some(network_widget_state) =
             app_state.states.net_state.widget_states.get_mut(&widget_id)
         {
@@ -103,7 +232,7 @@ impl Painter {
             };
 
             // TODO: Add support for clicking on legend to only show that value on chart.
-            let points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+            let mut points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
                 vec![
                     GraphData {
                         points: network_data_rx,
@@ -147,20 +276,49 @@ impl Painter {
                 ]
             };
 
+            if let Some(ipv6) = &app_state.converted_data.ipv6 {
+                points.push(GraphData {
+                    points: &[],
+                    style: self.colours.text_style,
+                    name: Some(
+                        format!("IPv6 RX: {:7} TX: {:7}", ipv6.rx_display, ipv6.tx_display).into(),
+                    ),
+                });
+                if let Some(address) = &ipv6.address {
+                    points.push(GraphData {
+                        points: &[],
+                        style: self.colours.text_style,
+                        name: Some(address.into()),
+                    });
+                }
+            }
+
             let marker = if app_state.app_config_fields.use_dot {
                 Marker::Dot
             } else {
                 Marker::Braille
             };
 
+            // Rates are compared against the baseline's, if it has any.
+            let name = match &app_state.baseline {
+                Some(baseline) if baseline.data.network.is_some() => {
+                    format!("Network ({})", baseline.title_label())
+                }
+                _ => "Network".to_string(),
+            };
+
             TimeGraph {
                 x_bounds,
                 hide_x_labels,
+                end_time,
                 y_bounds,
                 y_labels: &y_labels,
                 graph_style: self.colours.graph_style,
                 border_style,
-                title: " Network ".into(),
+                border: self.widget_border(&BottomWidgetType::Net),
+                is_selected: widget_id == app_state.current_widget.widget_id,
+                title: self
+                    .widget_title(graph_title(&name, app_state.app_config_fields.net_smooth_n)),
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_position: app_state.app_config_fields.network_legend_position,
@@ -168,6 +326,149 @@ impl Painter {
                 marker,
             }
             .draw_time_graph(f, draw_loc, &points);
+
+            if network_widget_state.show_stats {
+                let config = &app_state.app_config_fields;
+                let lines = [
+                    ("RX", network_data_rx, self.colours.rx_style),
+                    ("TX", network_data_tx, self.colours.tx_style),
+                ]
+                .into_iter()
+                .filter_map(|(name, data, style)| {
+                    // Stats are of the rates themselves, not how they're scaled on the graph.
+                    let values = visible_values(data, x_bounds[1])
+                        .into_iter()
+                        .map(|value| {
+                            unscale_network_value(
+                                value,
+                                &config.network_scale_type,
+                                &config.network_unit_type,
+                                config.network_use_binary_prefix,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+
+                    (!values.is_empty()).then(|| {
+                        let text = stats_text(name, &summarize(&values), |value| {
+                            format_bandwidth(
+                                value as u64,
+                                &config.network_unit_type,
+                                config.network_use_binary_prefix,
+                            )
+                        });
+                        Line::styled(text, style)
+                    })
+                })
+                .collect();
+
+                let legend_rows = if hide_legend {
+                    0
+                } else {
+                    points.len() as u16 + 2
+                };
+                draw_stats_overlay(
+                    f,
+                    draw_loc,
+                    lines,
+                    config.network_legend_position,
+                    legend_rows,
+                );
+            }
+        }
+    }
+
+    /// Draws a graph for each network interface, stacked vertically. Each
+    /// graph has its own axis unless they share one, and only as many as fit
+    /// are drawn.
+    fn draw_network_interfaces(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let end_time = app_state.graph_end_time();
+        const MIN_GRAPH_HEIGHT: u16 = 6;
+
+        if let Some(network_widget_state) =
+            app_state.states.net_state.widget_states.get_mut(&widget_id)
+        {
+            let config = &app_state.app_config_fields;
+            let interfaces = &app_state.converted_data.network_interfaces;
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let x_bounds = [0, network_widget_state.current_display_time];
+            let hide_x_labels = should_hide_x_label(
+                config.hide_time,
+                config.autohide_time,
+                &mut network_widget_state.autohide_timer,
+                draw_loc,
+            );
+            let marker = if config.use_dot {
+                Marker::Dot
+            } else {
+                Marker::Braille
+            };
+
+            let num_shown =
+                usize::from((draw_loc.height / MIN_GRAPH_HEIGHT).max(1)).min(interfaces.len());
+            let num_hidden = interfaces.len() - num_shown;
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![Constraint::Ratio(1, num_shown as u32); num_shown])
+                .split(draw_loc);
+
+            for (index, (interface, chunk)) in interfaces.iter().zip(chunks.iter()).enumerate() {
+                let max_entry = if interface.y_max > 0.0 {
+                    interface.y_max
+                } else {
+                    calculate_missing_max(
+                        &config.network_scale_type,
+                        config.network_use_binary_prefix,
+                    )
+                };
+                let (max_range, labels) = adjust_network_data_point(
+                    max_entry,
+                    &config.network_scale_type,
+                    &config.network_unit_type,
+                    config.network_use_binary_prefix,
+                );
+                let y_labels = labels.iter().map(|label| label.into()).collect::<Vec<_>>();
+
+                let points = [
+                    GraphData {
+                        points: &interface.rx_points,
+                        style: self.colours.rx_style,
+                        name: Some(format!("RX: {}", interface.rx_display).into()),
+                    },
+                    GraphData {
+                        points: &interface.tx_points,
+                        style: self.colours.tx_style,
+                        name: Some(format!("TX: {}", interface.tx_display).into()),
+                    },
+                ];
+
+                // The last graph says how many interfaces didn't fit.
+                let name = if num_hidden > 0 && index == num_shown - 1 {
+                    format!("Network: {} (+{num_hidden} more)", interface.name)
+                } else {
+                    format!("Network: {}", interface.name)
+                };
+
+                TimeGraph {
+                    x_bounds,
+                    hide_x_labels,
+                    end_time,
+                    y_bounds: [0.0, max_range],
+                    y_labels: &y_labels,
+                    graph_style: self.colours.graph_style,
+                    border_style,
+                    border: self.widget_border(&BottomWidgetType::Net),
+                    is_selected: widget_id == app_state.current_widget.widget_id,
+                    title: self.widget_title(graph_title(&name, config.net_smooth_n)),
+                    is_expanded: app_state.is_expanded && index == 0,
+                    title_style: self.colours.widget_title_style,
+                    legend_position: config.network_legend_position,
+                    legend_constraints: Some((Constraint::Ratio(1, 1), Constraint::Ratio(3, 4))),
+                    marker,
+                }
+                .draw_time_graph(f, *chunk, &points);
+            }
         }
     }
 
@@ -175,6 +476,7 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         const NETWORK_HEADERS: [&str; 4] = ["RX", "TX", "Total RX", "Total TX"];
+        const IPV6_HEADERS: [&str; 6] = ["", "RX", "TX", "Total RX", "Total TX", "IPv6 Addr"];
 
         let rx_display = &app_state.converted_data.rx_display;
         let tx_display = &app_state.converted_data.tx_display;
@@ -182,64 +484,95 @@ impl Painter {
         let total_tx_display = &app_state.converted_data.total_tx_display;
 
         // Gross but I need it to work...
-        let total_network = vec![Row::new([
+        let total_network = [
             Text::styled(rx_display, self.colours.rx_style),
             Text::styled(tx_display, self.colours.tx_style),
             Text::styled(total_rx_display, self.colours.total_rx_style),
             Text::styled(total_tx_display, self.colours.total_tx_style),
-        ])];
+        ];
+
+        // With IPv6 traffic, each row is labelled and the address goes at the end.
+        let (rows, headers, widths) = if let Some(ipv6) = &app_state.converted_data.ipv6 {
+            const LABEL_WIDTH: u16 = 5;
+
+            let rows = vec![
+                Row::new(
+                    std::iter::once(Text::raw("All"))
+                        .chain(total_network)
+                        .chain(std::iter::once(Text::raw(""))),
+                ),
+                Row::new([
+                    Text::raw("IPv6"),
+                    Text::styled(&ipv6.rx_display, self.colours.rx_style),
+                    Text::styled(&ipv6.tx_display, self.colours.tx_style),
+                    Text::styled(&ipv6.total_rx_display, self.colours.total_rx_style),
+                    Text::styled(&ipv6.total_tx_display, self.colours.total_tx_style),
+                    Text::raw(ipv6.address.as_deref().unwrap_or("")),
+                ]),
+            ];
+            let width = draw_loc.width.saturating_sub(2 + LABEL_WIDTH) / 5;
+            let widths = std::iter::once(LABEL_WIDTH)
+                .chain(std::iter::repeat(width).take(5))
+                .map(Constraint::Length)
+                .collect::<Vec<_>>();
+
+            (rows, IPV6_HEADERS.to_vec(), widths)
+        } else {
+            let widths = std::iter::repeat(draw_loc.width.saturating_sub(2) / 4)
+                .take(4)
+                .map(Constraint::Length)
+                .collect::<Vec<_>>();
+
+            (
+                vec![Row::new(total_network)],
+                NETWORK_HEADERS.to_vec(),
+                widths,
+            )
+        };
 
         // Draw
         f.render_widget(
-            Table::new(
-                total_network,
-                &((std::iter::repeat(draw_loc.width.saturating_sub(2) / 4))
-                    .take(4)
-                    .map(Constraint::Length)
-                    .collect::<Vec<_>>()),
-            )
-            .header(Row::new(NETWORK_HEADERS).style(self.colours.table_header_style))
-            .block(Block::default().borders(Borders::ALL).border_style(
-                if app_state.current_widget.widget_id == widget_id {
-                    self.colours.highlighted_border_style
-                } else {
-                    self.colours.border_style
-                },
-            ))
-            .style(self.colours.text_style),
+            Table::new(rows, &widths)
+                .header(Row::new(headers).style(self.colours.table_header_style))
+                .block(
+                    self.widget_border(&BottomWidgetType::Net)
+                        .block(app_state.current_widget.widget_id == widget_id)
+                        .border_style(
+                            self.get_border_style(widget_id, app_state.current_widget.widget_id),
+                        ),
+                )
+                .style(self.colours.text_style),
             draw_loc,
         );
     }
 }
 
-/// Returns the max data point and time given a time.
-fn get_max_entry(
-    rx: &[Point], tx: &[Point], time_start: f64, network_scale_type: &AxisScaling,
-    network_use_binary_prefix: bool,
-) -> Point {
-    /// Determines a "fake" max value in circumstances where we couldn't find
-    /// one from the data.
-    fn calculate_missing_max(
-        network_scale_type: &AxisScaling, network_use_binary_prefix: bool,
-    ) -> f64 {
-        match network_scale_type {
-            AxisScaling::Log => {
-                if network_use_binary_prefix {
-                    LOG_KIBI_LIMIT
-                } else {
-                    LOG_KILO_LIMIT
-                }
+/// Determines a "fake" max value in circumstances where we couldn't find
+/// one from the data.
+fn calculate_missing_max(network_scale_type: &AxisScaling, network_use_binary_prefix: bool) -> f64 {
+    match network_scale_type {
+        AxisScaling::Log => {
+            if network_use_binary_prefix {
+                LOG_KIBI_LIMIT
+            } else {
+                LOG_KILO_LIMIT
             }
-            AxisScaling::Linear => {
-                if network_use_binary_prefix {
-                    KIBI_LIMIT_F64
-                } else {
-                    KILO_LIMIT_F64
-                }
+        }
+        AxisScaling::Linear => {
+            if network_use_binary_prefix {
+                KIBI_LIMIT_F64
+            } else {
+                KILO_LIMIT_F64
             }
         }
     }
+}
 
+/// Returns the max data point and time given a time.
+fn get_max_entry(
+    rx: &[Point], tx: &[Point], time_start: f64, network_scale_type: &AxisScaling,
+    network_use_binary_prefix: bool,
+) -> Point {
     // First, let's shorten our ranges to actually look.  We can abuse the fact that
     // our rx and tx arrays are sorted, so we can short-circuit our search to
     // filter out only the relevant data points...
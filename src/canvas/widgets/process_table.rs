@@ -8,12 +8,13 @@ use tui::{
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{App, AppSearchState},
+    app::{layout_manager::BottomWidgetType, App, AppSearchState},
     canvas::{
         components::data_table::{DrawInfo, SelectionState},
         Painter,
     },
-    constants::*,
+    data_collection::error::Subsystem,
+    widgets::security_panel::SECURITY_PANEL_HEIGHT,
 };
 
 const SORT_MENU_WIDTH: u16 = 7;
@@ -26,9 +27,24 @@ impl Painter {
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     ) {
+        if self.draw_collection_error(
+            f,
+            app_state,
+            draw_loc,
+            widget_id,
+            " Processes ",
+            &[Subsystem::Processes],
+        ) {
+            return;
+        }
+
         if let Some(proc_widget_state) = app_state.states.proc_state.widget_states.get(&widget_id) {
             let search_height = if draw_border { 5 } else { 3 };
             let is_sort_open = proc_widget_state.is_sort_open;
+            let is_thread_panel_open = proc_widget_state.thread_panel.is_open();
+            let is_rlimit_panel_open = proc_widget_state.rlimit_panel.is_open();
+            let is_smaps_panel_open = proc_widget_state.smaps_panel.is_open();
+            let is_security_panel_open = proc_widget_state.security_panel.is_open();
 
             let mut proc_draw_loc = draw_loc;
             if proc_widget_state.is_search_enabled() {
@@ -57,6 +73,49 @@ impl Painter {
                 self.draw_sort_table(f, app_state, processes_chunk[0], widget_id + 2);
             }
 
+            if is_thread_panel_open {
+                let processes_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(proc_draw_loc);
+                proc_draw_loc = processes_chunk[0];
+
+                self.draw_thread_panel(f, app_state, processes_chunk[1], widget_id);
+            }
+
+            if is_rlimit_panel_open {
+                let processes_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(proc_draw_loc);
+                proc_draw_loc = processes_chunk[0];
+
+                self.draw_rlimit_panel(f, app_state, processes_chunk[1], widget_id);
+            }
+
+            if is_smaps_panel_open {
+                let processes_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(proc_draw_loc);
+                proc_draw_loc = processes_chunk[0];
+
+                self.draw_smaps_panel(f, app_state, processes_chunk[1], widget_id);
+            }
+
+            if is_security_panel_open {
+                let processes_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Min(0),
+                        Constraint::Length(SECURITY_PANEL_HEIGHT),
+                    ])
+                    .split(proc_draw_loc);
+                proc_draw_loc = processes_chunk[0];
+
+                self.draw_security_panel(f, app_state, processes_chunk[1], widget_id);
+            }
+
             self.draw_processes_table(f, app_state, proc_draw_loc, widget_id);
         }
 
@@ -95,6 +154,7 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Proc),
             };
 
             proc_widget_state.table.draw(
@@ -106,6 +166,121 @@ impl Painter {
         }
     }
 
+    /// Draws the threads of the selected process.
+    /// - `widget_id` represents the widget ID of the process widget itself.
+    fn draw_thread_panel(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        if let Some(proc_widget_state) = app_state
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&widget_id)
+        {
+            let draw_info = DrawInfo {
+                loc: draw_loc,
+                force_redraw: app_state.is_force_redraw,
+                recalculate_column_widths: should_get_widget_bounds
+                    || proc_widget_state.force_rerender,
+                selection_state: SelectionState::new(app_state.is_expanded, false),
+                border: self.widget_border(&BottomWidgetType::Proc),
+            };
+
+            proc_widget_state
+                .thread_panel
+                .table
+                .draw(f, &draw_info, None, self);
+        }
+    }
+
+    /// Draws the resource limits of the selected process.
+    /// - `widget_id` represents the widget ID of the process widget itself.
+    fn draw_rlimit_panel(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        if let Some(proc_widget_state) = app_state
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&widget_id)
+        {
+            let draw_info = DrawInfo {
+                loc: draw_loc,
+                force_redraw: app_state.is_force_redraw,
+                recalculate_column_widths: should_get_widget_bounds
+                    || proc_widget_state.force_rerender,
+                selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Proc),
+            };
+
+            proc_widget_state
+                .rlimit_panel
+                .table
+                .draw(f, &draw_info, None, self);
+        }
+    }
+
+    /// Draws the file-backed memory regions of the selected process.
+    /// - `widget_id` represents the widget ID of the process widget itself.
+    fn draw_smaps_panel(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        if let Some(proc_widget_state) = app_state
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&widget_id)
+        {
+            let draw_info = DrawInfo {
+                loc: draw_loc,
+                force_redraw: app_state.is_force_redraw,
+                recalculate_column_widths: should_get_widget_bounds
+                    || proc_widget_state.force_rerender,
+                selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Proc),
+            };
+
+            proc_widget_state
+                .smaps_panel
+                .table
+                .draw(f, &draw_info, None, self);
+        }
+    }
+
+    /// Draws the memory safety features of the selected process.
+    /// - `widget_id` represents the widget ID of the process widget itself.
+    fn draw_security_panel(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let should_get_widget_bounds = app_state.should_get_widget_bounds();
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        if let Some(proc_widget_state) = app_state
+            .states
+            .proc_state
+            .widget_states
+            .get_mut(&widget_id)
+        {
+            let draw_info = DrawInfo {
+                loc: draw_loc,
+                force_redraw: app_state.is_force_redraw,
+                recalculate_column_widths: should_get_widget_bounds
+                    || proc_widget_state.force_rerender,
+                selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Proc),
+            };
+
+            proc_widget_state
+                .security_panel
+                .table
+                .draw(f, &draw_info, None, self);
+        }
+    }
+
     /// Draws the process search field.
     /// - `widget_id` represents the widget ID of the search box itself --- NOT
     ///   the process widget state that is stored.
@@ -249,6 +424,7 @@ impl Painter {
             )));
             search_text.push(option_text);
 
+            let border = self.widget_border(&BottomWidgetType::ProcSearch);
             let current_border_style =
                 if proc_widget_state.proc_search.search_state.is_invalid_search {
                     self.colours.invalid_query_style
@@ -263,7 +439,7 @@ impl Painter {
                     const TITLE_BASE: &str = " Esc to close ";
                     let repeat_num =
                         usize::from(draw_loc.width).saturating_sub(TITLE_BASE.chars().count() + 2);
-                    format!("{} Esc to close ", "─".repeat(repeat_num))
+                    format!("{} Esc to close ", border.horizontal().repeat(repeat_num))
                 } else {
                     String::new()
                 },
@@ -271,14 +447,12 @@ impl Painter {
             );
 
             let process_search_block = if draw_border {
-                Block::default()
+                border
+                    .block(is_on_widget)
                     .title(title)
-                    .borders(Borders::ALL)
                     .border_style(current_border_style)
             } else if is_on_widget {
-                Block::default()
-                    .borders(SIDE_BORDERS)
-                    .border_style(current_border_style)
+                border.side_block().border_style(current_border_style)
             } else {
                 Block::default().borders(Borders::NONE)
             };
@@ -332,6 +506,7 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Proc),
             };
 
             pws.sort_table.draw(
@@ -0,0 +1,71 @@
+use tui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{canvas::Painter, export::record::ReplayState};
+
+/// Formats milliseconds as `m:ss`.
+fn format_position(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+impl Painter {
+    /// Draws a timeline showing how far into a replay we are.
+    pub fn draw_replay_timeline(
+        &self, f: &mut Frame<'_>, replay: &ReplayState, is_frozen: bool, draw_loc: Rect,
+    ) {
+        let draw_loc = Layout::default()
+            .horizontal_margin(1)
+            .constraints([Constraint::Length(1)])
+            .split(draw_loc)[0];
+
+        let status = if is_frozen {
+            "Paused, press 'f' to resume ".to_string()
+        } else {
+            format!("Replaying ({}x) ", replay.speed)
+        };
+        let position = format_position(replay.position_ms);
+        let length = format_position(replay.length_ms);
+
+        let used_width = status.len() + position.len() + length.len() + 2;
+        let bar_width = usize::from(draw_loc.width).saturating_sub(used_width);
+        let filled_width = (bar_width as u64 * replay.position_ms.min(replay.length_ms))
+            .checked_div(replay.length_ms)
+            .map_or(bar_width, |width| width as usize);
+
+        let line = Line::from(vec![
+            Span::styled(status, self.colours.selected_text_style),
+            Span::styled(position, self.colours.text_style),
+            Span::raw(" "),
+            Span::styled(
+                "━".repeat(filled_width),
+                self.colours.highlighted_border_style,
+            ),
+            Span::styled(
+                "─".repeat(bar_width - filled_width),
+                self.colours.border_style,
+            ),
+            Span::raw(" "),
+            Span::styled(length, self.colours.text_style),
+        ]);
+
+        f.render_widget(Paragraph::new(line), draw_loc);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn position_format() {
+        assert_eq!(format_position(0), "0:00");
+        assert_eq!(format_position(12_345), "0:12");
+        assert_eq!(format_position(90_000), "1:30");
+        assert_eq!(format_position(3_600_000), "60:00");
+    }
+}
@@ -1,18 +1,56 @@
-use tui::{layout::Rect, Frame};
+use std::borrow::Cow;
+
+use tui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    symbols::Marker,
+    Frame,
+};
 
 use crate::{
-    app,
+    app::{self, layout_manager::BottomWidgetType},
     canvas::{
-        components::data_table::{DrawInfo, SelectionState},
+        components::{
+            data_table::{DrawInfo, SelectionState},
+            time_chart::LegendPosition,
+            time_graph::{GraphData, TimeGraph},
+        },
         Painter,
     },
+    constants::TIME_LABEL_HEIGHT_LIMIT,
+    data_collection::error::Subsystem,
 };
 
 impl Painter {
     pub fn draw_temp_table(
         &self, f: &mut Frame<'_>, app_state: &mut app::App, draw_loc: Rect, widget_id: u64,
     ) {
+        if self.draw_collection_error(
+            f,
+            app_state,
+            draw_loc,
+            widget_id,
+            " Temperatures ",
+            &[Subsystem::Temperature],
+        ) {
+            return;
+        }
+
+        // When expanded, the history of each sensor is drawn as a graph too.
+        let draw_loc = if app_state.is_expanded {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(60), Constraint::Min(0)])
+                .split(draw_loc);
+            self.draw_temp_graph(f, app_state, chunks[0], widget_id);
+
+            chunks[1]
+        } else {
+            draw_loc
+        };
+
         let recalculate_column_widths = app_state.should_get_widget_bounds();
+        let alert_border_style = self.alert_border_style(app_state, &BottomWidgetType::Temp);
         if let Some(temp_widget_state) = app_state
             .states
             .temp_state
@@ -26,8 +64,10 @@ impl Painter {
                 force_redraw: app_state.is_force_redraw,
                 recalculate_column_widths,
                 selection_state: SelectionState::new(app_state.is_expanded, is_on_widget),
+                border: self.widget_border(&BottomWidgetType::Temp),
             };
 
+            temp_widget_state.table.styling.alert_border_style = alert_border_style;
             temp_widget_state.table.draw(
                 f,
                 &draw_info,
@@ -36,4 +76,81 @@ impl Painter {
             );
         }
     }
+
+    /// Draws the recent readings of each temperature sensor.
+    fn draw_temp_graph(
+        &self, f: &mut Frame<'_>, app_state: &app::App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let end_time = app_state.graph_end_time();
+        let temp_data = &app_state.converted_data.temp_data;
+
+        // Show however long the history goes back, in whole seconds.
+        let oldest = temp_data
+            .iter()
+            .filter_map(|sensor| sensor.history.first())
+            .map(|(time, _)| -time)
+            .fold(0.0, f64::max);
+        let x_bounds = [0, ((oldest / 1000.0).ceil() as u64).max(1) * 1000];
+
+        let (min, max) = temp_data.iter().flat_map(|sensor| &sensor.history).fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), (_, temp)| (min.min(*temp), max.max(*temp)),
+        );
+        let y_bounds = if min <= max {
+            [(min - 1.0).floor(), (max + 1.0).ceil()]
+        } else {
+            [0.0, 100.0]
+        };
+
+        let unit = temp_data
+            .first()
+            .map(|sensor| sensor.temperature_type)
+            .unwrap_or_default();
+        let y_labels = [
+            Cow::Owned(format!("{:.0}{unit}", y_bounds[0])),
+            Cow::Owned(format!("{:.0}{unit}", y_bounds[1])),
+        ];
+
+        let styles = &self.colours.cpu_colour_styles;
+        let points = temp_data
+            .iter()
+            .filter(|sensor| !sensor.history.is_empty())
+            .enumerate()
+            .map(|(index, sensor)| GraphData {
+                points: &sensor.history,
+                style: if styles.is_empty() {
+                    Style::default()
+                } else {
+                    styles[index % styles.len()]
+                },
+                name: Some(format!("{}: {}", sensor.sensor, sensor.temperature()).into()),
+            })
+            .collect::<Vec<_>>();
+
+        let marker = if app_state.app_config_fields.use_dot {
+            Marker::Dot
+        } else {
+            Marker::Braille
+        };
+
+        TimeGraph {
+            x_bounds,
+            hide_x_labels: app_state.app_config_fields.hide_time
+                || draw_loc.height < TIME_LABEL_HEIGHT_LIMIT,
+            end_time,
+            y_bounds,
+            y_labels: &y_labels,
+            graph_style: self.colours.graph_style,
+            border_style: self.get_border_style(widget_id, app_state.current_widget.widget_id),
+            border: self.widget_border(&BottomWidgetType::Temp),
+            is_selected: widget_id == app_state.current_widget.widget_id,
+            title: self.widget_title(" Temperature History "),
+            is_expanded: true,
+            title_style: self.colours.widget_title_style,
+            legend_position: Some(LegendPosition::TopRight),
+            legend_constraints: None,
+            marker,
+        }
+        .draw_time_graph(f, draw_loc, &points);
+    }
 }
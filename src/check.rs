@@ -0,0 +1,498 @@
+//! A health check mode, which collects a single sample, compares it against
+//! thresholds, and exits with a status code that scripts and monitoring
+//! systems can act on.
+
+use std::{sync::mpsc, thread, time::Duration};
+
+use itertools::Itertools;
+
+use crate::{
+    app::{
+        event_log::{
+            total_cpu_usage, DISK_CRIT_THRESHOLD, DISK_WARN_THRESHOLD, HIGH_CPU_THRESHOLD,
+            HIGH_TEMP_THRESHOLD_CELSIUS,
+        },
+        layout_manager::UsedWidgets,
+        AppConfigFields, DataFilters,
+    },
+    data_collection::{temperature::TemperatureType, Data},
+    export::collect_once,
+};
+
+/// How long to wait for data to be collected by default before giving up.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The result of a check, from best to worst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Crit,
+    Unknown,
+}
+
+impl CheckStatus {
+    /// The exit code for the status, which is also what Nagios expects from
+    /// plugins.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CheckStatus::Ok => 0,
+            CheckStatus::Warn => 1,
+            CheckStatus::Crit => 2,
+            CheckStatus::Unknown => 3,
+        }
+    }
+
+    fn name(self, format: CheckFormat) -> &'static str {
+        match (self, format) {
+            (CheckStatus::Ok, _) => "OK",
+            (CheckStatus::Warn, CheckFormat::Plain) => "WARN",
+            (CheckStatus::Warn, CheckFormat::Nagios) => "WARNING",
+            (CheckStatus::Crit, CheckFormat::Plain) => "CRIT",
+            (CheckStatus::Crit, CheckFormat::Nagios) => "CRITICAL",
+            (CheckStatus::Unknown, _) => "UNKNOWN",
+        }
+    }
+}
+
+/// How the result of a check is printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CheckFormat {
+    /// A single summary line.
+    #[default]
+    Plain,
+    /// A summary line with performance data, as expected from Nagios and
+    /// Icinga plugins.
+    Nagios,
+}
+
+/// The values at which a reading is a warning or critical.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Thresholds {
+    pub warn: Option<f64>,
+    pub crit: Option<f64>,
+}
+
+impl Thresholds {
+    fn is_set(&self) -> bool {
+        self.warn.is_some() || self.crit.is_some()
+    }
+
+    fn status(&self, value: f64) -> CheckStatus {
+        if self.crit.is_some_and(|crit| value >= crit) {
+            CheckStatus::Crit
+        } else if self.warn.is_some_and(|warn| value >= warn) {
+            CheckStatus::Warn
+        } else {
+            CheckStatus::Ok
+        }
+    }
+
+    /// The threshold that was crossed to get a status.
+    fn crossed(&self, status: CheckStatus) -> Option<f64> {
+        match status {
+            CheckStatus::Warn => self.warn,
+            CheckStatus::Crit => self.crit,
+            CheckStatus::Ok | CheckStatus::Unknown => None,
+        }
+    }
+
+    /// Fills in any threshold that wasn't set.
+    fn or(self, warn: Option<f64>, crit: Option<f64>) -> Self {
+        Self {
+            warn: self.warn.or(warn),
+            crit: self.crit.or(crit),
+        }
+    }
+}
+
+/// What to check, and how.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckSettings {
+    pub cpu: Thresholds,
+    pub mem: Thresholds,
+    pub disk: Thresholds,
+    pub temp: Thresholds,
+    pub format: CheckFormat,
+    pub timeout: Duration,
+}
+
+impl CheckSettings {
+    /// Fills in any threshold that wasn't set with the one alerts are raised
+    /// at, if there is one.
+    fn with_alert_defaults(self, temperature_type: TemperatureType) -> Self {
+        let temp_crit = temperature_type.convert_temp_unit(HIGH_TEMP_THRESHOLD_CELSIUS);
+
+        Self {
+            cpu: self.cpu.or(Some(HIGH_CPU_THRESHOLD), None),
+            disk: self
+                .disk
+                .or(Some(DISK_WARN_THRESHOLD), Some(DISK_CRIT_THRESHOLD)),
+            temp: self.temp.or(None, Some(temp_crit.into())),
+            ..self
+        }
+    }
+}
+
+/// A single value that was checked.
+#[derive(Debug, PartialEq)]
+struct Reading {
+    label: String,
+    value: f64,
+    unit: String,
+    thresholds: Thresholds,
+}
+
+impl Reading {
+    fn new(label: String, value: f64, unit: impl Into<String>, thresholds: Thresholds) -> Self {
+        Self {
+            label,
+            value,
+            unit: unit.into(),
+            thresholds,
+        }
+    }
+
+    fn status(&self) -> CheckStatus {
+        self.thresholds.status(self.value)
+    }
+
+    fn describe(&self) -> String {
+        let Reading {
+            label, value, unit, ..
+        } = self;
+
+        match self.thresholds.crossed(self.status()) {
+            Some(threshold) => format!("{label} {value:.0}{unit} >= {threshold:.0}{unit}"),
+            None => format!("{label} {value:.0}{unit}"),
+        }
+    }
+
+    /// The reading as Nagios performance data, with percentages bounded from
+    /// 0 to 100.
+    fn perf_data(&self) -> String {
+        let label = self.label.to_lowercase();
+        let label = if label.contains([' ', '=', '\'']) {
+            format!("'{}'", label.replace('\'', "''"))
+        } else {
+            label
+        };
+
+        let optional =
+            |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+        let (unit, bounds) = if self.unit == "%" {
+            ("%", ";0;100")
+        } else {
+            ("", "")
+        };
+
+        format!(
+            "{label}={:.1}{unit};{};{}{bounds}",
+            self.value,
+            optional(self.thresholds.warn),
+            optional(self.thresholds.crit)
+        )
+    }
+}
+
+/// The values to check from a sample. CPU and memory usage are always
+/// included, while for disks and temperatures, only the fullest disk and the
+/// hottest sensor are checked.
+fn readings(
+    data: &Data, settings: &CheckSettings, temperature_type: TemperatureType,
+) -> Vec<Reading> {
+    let mut readings = Vec::with_capacity(4);
+
+    if let Some(usage) = data.cpu.as_deref().and_then(total_cpu_usage) {
+        readings.push(Reading::new("CPU".into(), usage, "%", settings.cpu));
+    }
+
+    if let Some(usage) = data.memory.as_ref().and_then(|memory| memory.use_percent) {
+        readings.push(Reading::new("MEM".into(), usage, "%", settings.mem));
+    }
+
+    if settings.disk.is_set() {
+        let fullest = data
+            .disks
+            .iter()
+            .flatten()
+            .filter_map(|disk| match (disk.used_space, disk.total_space) {
+                (Some(used), Some(total)) if total > 0 => {
+                    Some((&disk.mount_point, used as f64 / total as f64 * 100.0))
+                }
+                _ => None,
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((mount_point, usage)) = fullest {
+            readings.push(Reading::new(
+                format!("DISK {mount_point}"),
+                usage,
+                "%",
+                settings.disk,
+            ));
+        }
+    }
+
+    if settings.temp.is_set() {
+        let hottest = data
+            .temperature_sensors
+            .iter()
+            .flatten()
+            .filter_map(|sensor| Some((&sensor.name, sensor.temperature?)))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((name, temperature)) = hottest {
+            readings.push(Reading::new(
+                format!("TEMP {name}"),
+                temperature.into(),
+                temperature_type.to_string(),
+                settings.temp,
+            ));
+        }
+    }
+
+    readings
+}
+
+/// The overall status of some readings, which is the worst of them, and a
+/// line summarizing them. If anything isn't OK, only those readings are
+/// described.
+fn summarize(readings: &[Reading], format: CheckFormat) -> (CheckStatus, String) {
+    let status = readings
+        .iter()
+        .map(Reading::status)
+        .max()
+        .unwrap_or(CheckStatus::Ok);
+
+    let description = readings
+        .iter()
+        .filter(|reading| status == CheckStatus::Ok || reading.status() != CheckStatus::Ok)
+        .map(Reading::describe)
+        .join(", ");
+
+    let line = match format {
+        CheckFormat::Plain => format!("{}: {description}", status.name(format)),
+        CheckFormat::Nagios => format!(
+            "BOTTOM {} - {description} | {}",
+            status.name(format),
+            readings.iter().map(Reading::perf_data).join(" ")
+        ),
+    };
+
+    (status, line)
+}
+
+/// Collects a sample, prints a summary of how it compares against the
+/// thresholds, and returns the overall status. If collection doesn't finish
+/// within the timeout, the status is [`CheckStatus::Unknown`].
+pub(crate) fn run(
+    settings: CheckSettings, app_config_fields: &AppConfigFields, filters: DataFilters,
+) -> CheckStatus {
+    let temperature_type = app_config_fields.temperature_type;
+    let settings = settings.with_alert_defaults(temperature_type);
+
+    let used_widgets = UsedWidgets {
+        use_cpu: true,
+        use_mem: true,
+        use_disk: settings.disk.is_set(),
+        use_temp: settings.temp.is_set(),
+        ..Default::default()
+    };
+
+    // Collect on another thread so that we can give up if it hangs, e.g. on
+    // an unresponsive network mount.
+    let (sender, receiver) = mpsc::channel();
+    let app_config_fields = app_config_fields.clone();
+    thread::spawn(move || {
+        let _ = sender.send(collect_once(&app_config_fields, filters, used_widgets));
+    });
+
+    let (status, line) = match receiver.recv_timeout(settings.timeout) {
+        Ok(data) => summarize(
+            &readings(&data, &settings, temperature_type),
+            settings.format,
+        ),
+        Err(_) => {
+            let status = CheckStatus::Unknown;
+            let reason = format!(
+                "data collection took longer than {}",
+                humantime::format_duration(settings.timeout)
+            );
+            let line = match settings.format {
+                CheckFormat::Plain => format!("{}: {reason}", status.name(settings.format)),
+                CheckFormat::Nagios => {
+                    format!("BOTTOM {} - {reason}", status.name(settings.format))
+                }
+            };
+
+            (status, line)
+        }
+    };
+
+    println!("{line}");
+
+    status
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::{
+        cpu::{CpuData, CpuDataType},
+        disks::DiskHarvest,
+        memory::MemHarvest,
+        temperature::TempHarvest,
+    };
+
+    fn settings() -> CheckSettings {
+        CheckSettings {
+            cpu: Thresholds::default(),
+            mem: Thresholds::default(),
+            disk: Thresholds::default(),
+            temp: Thresholds::default(),
+            format: CheckFormat::Plain,
+            timeout: DEFAULT_CHECK_TIMEOUT,
+        }
+    }
+
+    fn data() -> Data {
+        Data {
+            cpu: Some(vec![
+                CpuData {
+                    data_type: CpuDataType::Avg,
+                    cpu_usage: 0.0,
+                },
+                CpuData {
+                    data_type: CpuDataType::Cpu(0),
+                    cpu_usage: 20.0,
+                },
+                CpuData {
+                    data_type: CpuDataType::Cpu(1),
+                    cpu_usage: 26.0,
+                },
+            ]),
+            memory: Some(MemHarvest {
+                used_bytes: 67,
+                total_bytes: 100,
+                use_percent: Some(67.0),
+            }),
+            disks: Some(vec![
+                DiskHarvest {
+                    name: "sda1".into(),
+                    mount_point: "/".into(),
+                    free_space: Some(55),
+                    used_space: Some(45),
+                    total_space: Some(100),
+                    #[cfg(target_os = "windows")]
+                    volume_name: None,
+                },
+                DiskHarvest {
+                    name: "sda2".into(),
+                    mount_point: "/home".into(),
+                    free_space: Some(8),
+                    used_space: Some(92),
+                    total_space: Some(100),
+                    #[cfg(target_os = "windows")]
+                    volume_name: None,
+                },
+            ]),
+            temperature_sensors: Some(vec![TempHarvest {
+                name: "k10temp".into(),
+                temperature: Some(52.0),
+            }]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn thresholds() {
+        let thresholds = Thresholds {
+            warn: Some(80.0),
+            crit: Some(90.0),
+        };
+
+        assert_eq!(thresholds.status(79.9), CheckStatus::Ok);
+        assert_eq!(thresholds.status(80.0), CheckStatus::Warn);
+        assert_eq!(thresholds.status(95.0), CheckStatus::Crit);
+        assert_eq!(Thresholds::default().status(100.0), CheckStatus::Ok);
+    }
+
+    #[test]
+    fn defaults_to_alert_thresholds() {
+        let user = CheckSettings {
+            cpu: Thresholds {
+                warn: Some(80.0),
+                crit: None,
+            },
+            ..settings()
+        };
+
+        let settings = user.with_alert_defaults(TemperatureType::Celsius);
+        assert_eq!(settings.cpu.warn, Some(80.0));
+        assert_eq!(settings.mem, Thresholds::default());
+        assert_eq!(settings.disk.crit, Some(DISK_CRIT_THRESHOLD));
+        assert_eq!(settings.temp.crit, Some(90.0));
+    }
+
+    #[test]
+    fn summary_lines() {
+        let settings = CheckSettings {
+            cpu: Thresholds {
+                warn: Some(80.0),
+                crit: None,
+            },
+            mem: Thresholds {
+                warn: Some(90.0),
+                crit: None,
+            },
+            ..settings()
+        };
+
+        let readings = readings(&data(), &settings, TemperatureType::Celsius);
+        assert_eq!(
+            summarize(&readings, CheckFormat::Plain),
+            (CheckStatus::Ok, "OK: CPU 23%, MEM 67%".to_string())
+        );
+
+        let settings = settings.with_alert_defaults(TemperatureType::Celsius);
+        let readings = super::readings(&data(), &settings, TemperatureType::Celsius);
+        assert_eq!(
+            summarize(&readings, CheckFormat::Plain),
+            (CheckStatus::Warn, "WARN: DISK /home 92% >= 90%".to_string())
+        );
+        assert_eq!(
+            summarize(&readings, CheckFormat::Nagios),
+            (
+                CheckStatus::Warn,
+                "BOTTOM WARNING - DISK /home 92% >= 90% | cpu=23.0%;80;;0;100 mem=67.0%;90;;0;100 \
+                'disk /home'=92.0%;90;95;0;100 'temp k10temp'=52.0;;90"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn worst_status_wins() {
+        let settings = CheckSettings {
+            mem: Thresholds {
+                warn: Some(50.0),
+                crit: Some(60.0),
+            },
+            disk: Thresholds {
+                warn: Some(90.0),
+                crit: None,
+            },
+            ..settings()
+        };
+
+        let readings = readings(&data(), &settings, TemperatureType::Celsius);
+        assert_eq!(
+            summarize(&readings, CheckFormat::Plain),
+            (
+                CheckStatus::Crit,
+                "CRIT: MEM 67% >= 60%, DISK /home 92% >= 90%".to_string()
+            )
+        );
+    }
+}
@@ -12,6 +12,21 @@ pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+// How often scroll momentum moves a table
+pub const TICK_RATE_IN_MILLISECONDS: u64 = 30;
+// The smallest terminal that everything is laid out for
+pub const MIN_TERMINAL_WIDTH: u16 = 80;
+pub const MIN_TERMINAL_HEIGHT: u16 = 24;
+
+// Major page faults per second before a process is highlighted
+pub const DEFAULT_MAJOR_FAULT_THRESHOLD: u64 = 100;
+// Open TCP connections before a process is highlighted
+pub const DEFAULT_TCP_CONNECTION_THRESHOLD: u64 = 1000;
+pub const DEFAULT_CHANGE_THRESHOLD: f32 = 5.0;
+// How many update periods a process can go uncollected before it's removed
+pub const STALE_PROCESS_UPDATE_PERIODS: u64 = 2;
+// How many update periods can pass without an update before the heartbeat shows it's late
+pub const LATE_UPDATE_PERIODS: u32 = 3;
 
 // Limits for when we should stop showing table gaps/labels (anything less means
 // not shown)
@@ -22,7 +37,7 @@ pub const TIME_LABEL_HEIGHT_LIMIT: u16 = 7;
 pub const SIDE_BORDERS: Borders = Borders::LEFT.union(Borders::RIGHT);
 
 // Help text
-const HELP_CONTENTS_TEXT: [&str; 10] = [
+const HELP_CONTENTS_TEXT: [&str; 11] = [
     "Either scroll or press the number key to go to the corresponding help menu section:",
     "1 - General",
     "2 - CPU widget",
@@ -33,11 +48,12 @@ const HELP_CONTENTS_TEXT: [&str; 10] = [
     "7 - Disk widget",
     "8 - Battery widget",
     "9 - Basic memory widget",
+    "10 - Startup widget",
 ];
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub(crate) const GENERAL_HELP_TEXT: [&str; 32] = [
+pub(crate) const GENERAL_HELP_TEXT: [&str; 42] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
@@ -60,24 +76,36 @@ pub(crate) const GENERAL_HELP_TEXT: [&str; 32] = [
     "Up, k            Move up within widget",
     "Right, l         Move right within widget",
     "?                Open help menu",
+    "E                Open the event log and any collection errors",
+    "i                Show a summary of the system, like uname -a",
+    "Ctrl-m, Alt-m    Show every field of /proc/meminfo",
+    "[, ]             Focus the previous/next host when monitoring remote hosts",
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
     "e                Toggle expanding the currently selected widget",
+    "v                Switch between the minimal and full layouts when using --minimal",
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
     "=                Reset zoom",
+    "U                Cycle network rates between bits/bytes and SI/binary prefixes",
+    "N                Toggle showing a network graph for each interface",
     "PgUp, PgDown     Scroll up/down a table by a page",
     "Ctrl-u, Ctrl-d   Scroll up/down a table by half a page",
+    "y                Copy the selected entry's command line or main value",
+    "Ctrl-Shift-c     Copy all of the selected entry's columns, separated by tabs",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
+    "Right click      Opens a menu of actions for the clicked process",
 ];
 
-const CPU_HELP_TEXT: [&str; 2] = [
+const CPU_HELP_TEXT: [&str; 4] = [
     "2 - CPU widget",
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
+    "R                Toggle showing how fast usage changes per second, also on the memory graph",
+    "a                Toggle showing the average, min, max, and SD, also on the memory/network graphs",
 ];
 
-const PROCESS_HELP_TEXT: [&str; 17] = [
+const PROCESS_HELP_TEXT: [&str; 28] = [
     "3 - Process widget",
     "dd, F9           Kill the selected process",
     "c                Sort by CPU usage, press again to reverse",
@@ -90,8 +118,19 @@ const PROCESS_HELP_TEXT: [&str; 17] = [
     "s, F6            Open process sort widget",
     "I                Invert current sort",
     "%                Toggle between values and percentages for memory usage",
+    "o                Toggle showing CPU usage as a percentage of one core or of all of them",
     "t, F5            Toggle tree mode",
+    "T                Toggle highlighting the top consumer of the sorted column",
+    "V                Sort by how much the sorted column changed, for a few updates",
+    "Enter            Toggle showing the threads of the selected process",
+    "u                Toggle showing the resource limits of the selected process",
+    "b                Toggle showing the memory mapped from files by the selected process",
+    "x                Toggle showing the memory safety features of the selected process",
+    "F                Sample the selected process and show a flame graph",
+    ">                Set the least CPU and memory usage for processes to be shown",
+    "1-9              Switch to a column preset",
     "+, -, click      Collapse/expand a branch while in tree mode",
+    "Left, Right      Scroll the columns sideways if they don't all fit",
     "click on header  Sorts the entries by that column, click again to invert the sort",
     "C                Sort by GPU usage, press again to reverse",
     "M                Sort by GPU memory usage, press again to reverse",
@@ -151,19 +190,23 @@ const SEARCH_HELP_TEXT: [&str; 51] = [
     "TiB              ex: read > 1 tib",
 ];
 
-const SORT_HELP_TEXT: [&str; 6] = [
+const SORT_HELP_TEXT: [&str; 9] = [
     "5 - Sort widget",
     "Down, 'j'        Scroll down in list",
     "Up, 'k'          Scroll up in list",
     "Mouse scroll     Scroll through sort widget",
     "Esc              Close the sort widget",
     "Enter            Sort by current selected column",
+    "Ctrl-Left        Move the selected column left",
+    "Ctrl-Right       Move the selected column right",
+    "Ctrl-s           Save the shown columns as a new column preset",
 ];
 
-const TEMP_HELP_WIDGET: [&str; 3] = [
+const TEMP_HELP_WIDGET: [&str; 4] = [
     "6 - Temperature widget",
     "'s'              Sort by sensor name, press again to reverse",
     "'t'              Sort by temperature, press again to reverse",
+    "'u'              Cycle the unit between Celsius, Fahrenheit, and Kelvin",
 ];
 
 const DISK_HELP_WIDGET: [&str; 9] = [
@@ -189,6 +232,11 @@ const BASIC_MEM_HELP_TEXT: [&str; 2] = [
     "%                Toggle between values and percentages for memory usage",
 ];
 
+const STARTUP_HELP_TEXT: [&str; 2] = [
+    "10 - Startup widget",
+    "Enter            Enable or disable the selected unit",
+];
+
 pub(crate) const HELP_TEXT: [&[&str]; HELP_CONTENTS_TEXT.len()] = [
     &HELP_CONTENTS_TEXT,
     &GENERAL_HELP_TEXT,
@@ -200,6 +248,7 @@ pub(crate) const HELP_TEXT: [&[&str]; HELP_CONTENTS_TEXT.len()] = [
     &DISK_HELP_WIDGET,
     &BATTERY_HELP_TEXT,
     &BASIC_MEM_HELP_TEXT,
+    &STARTUP_HELP_TEXT,
 ];
 
 pub(crate) const DEFAULT_LAYOUT: &str = r#"
@@ -256,10 +305,29 @@ pub(crate) const DEFAULT_BATTERY_LAYOUT: &str = r#"
     default=true
 "#;
 
+/// A smaller layout used with `--minimal`: a large CPU graph next to the memory
+/// graph, with the processes below.
+pub(crate) const MINIMAL_LAYOUT: &str = r#"
+[[row]]
+  ratio=2
+  [[row.child]]
+    ratio=3
+    type="cpu"
+  [[row.child]]
+    ratio=2
+    type="mem"
+[[row]]
+  ratio=1
+  [[row.child]]
+    type="proc"
+    default=true
+"#;
+
 // TODO: Eventually deprecate this, or grab from a file.
 pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bottom. All of the settings are commented
 # out by default; if you wish to change them uncomment and modify as you see
-# fit.
+# fit. Settings commented out with a space after the # are examples, rather
+# than defaults.
 
 # This group of options represents a command-line option. Flags explicitly
 # added when running (ie: btm -a) will override this config file if an option
@@ -312,16 +380,28 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Hides the time scale.
 #hide_time = false
 
+# Hides the time scale in graphs after briefly showing it when zooming in or out.
+#autohide_time = false
+
+# Whether the time scale shows wall-clock times in UTC ("absolute") or how long ago ("relative").
+#graph_timestamps = "relative"
+
 # Override layout default widget
-#default_widget_type = "proc"
-#default_widget_count = 1
+# default_widget_type = "proc"
+# default_widget_count = 1
 
 # Expand selected widget upon starting the app
-#expanded = true
+#expanded = false
 
 # Use basic mode
 #basic = false
 
+# Moves the average CPU usage entry to its own row when using basic mode.
+#average_cpu_row = false
+
+# Start with a minimal layout of just the CPU, memory, and process widgets. Press v to switch to the full layout.
+#minimal = false
+
 # Use the old network legend style
 #use_old_network_legend = false
 
@@ -334,21 +414,57 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Disable mouse clicks
 #disable_click = false
 
+# Disables copying values to the clipboard.
+#no_clipboard = false
+
+# Skips collecting processes, network, disks, or temperatures, leaving their widgets out.
+#no_proc = false
+#no_net = false
+#no_disk = false
+#no_temp = false
+
 # Show memory values in the processes widget as values by default
 #process_memory_as_value = false
 
 # Show tree mode by default in the processes widget.
 #tree = false
 
+# Briefly highlights processes whose CPU or memory usage changed a lot since the previous update.
+#highlight_changes = false
+
 # Shows an indicator in table widgets tracking where in the list you are.
 #show_table_scroll_position = false
 
+# Shows a top-style summary header of CPU, memory, swap, and task counts above the widgets.
+#show_header = false
+
+# Always shows the status line, with a spinner that advances on each update.
+#show_heartbeat = false
+
+# Hides the status line and header, and shortens widget titles, leaving everything else to the widgets.
+#quiet = false
+
+# Only collects new data when a key is pressed, instead of every update rate.
+#update_on_key = false
+
+# Disables momentum when scrolling tables with the mouse wheel.
+#no_scroll_momentum = false
+
+# How much scroll momentum is kept each tick, from 0 up to (but not including) 1. Higher values scroll further.
+#scroll_friction = 0.8
+
 # Show processes as their commands by default in the process widget.
 #process_command = false
 
 # Displays the network widget with binary prefixes.
 #network_use_binary_prefix = false
 
+# Shows IPv6 traffic and the primary IPv6 address in the network widget, on Linux.
+#show_ipv6 = true
+
+# Scales the graphs of each network interface to the same maximum, rather than each to its own.
+#network_shared_axis = false
+
 # Displays the network widget using bytes.
 #network_use_bytes = false
 
@@ -373,100 +489,207 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Where to place the legend for the network widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #network_legend = "top-right"
 
+# How many data points to average over when drawing the CPU, memory, and network graphs, to smooth them out. Defaults to 1, which is no smoothing.
+#cpu_avg_n = 1
+#mem_avg_n = 1
+#net_avg_n = 1
+
+# Freezes the display when an alert is raised, such as CPU, disk, or temperature usage crossing a threshold.
+#freeze_on_alert = false
+# Either "edge" (default), which stays frozen until manually unfrozen, or "level", which only stays frozen while usage is over the threshold.
+#freeze_on_alert_mode = "edge"
+
+# How many pages can be swapped out per second before the memory widget highlights its swap I/O line. Defaults to 0,
+# so any swapping out is highlighted. Swap I/O is only shown on Linux.
+#swap_out_warning = 0
+
+# Whether the memory widget shows the total proportional set size (PSS) of every process, which splits shared memory
+# between the processes sharing it. This means reading every process' smaps_rollup, so it's only read every 5 seconds.
+# Only supported on Linux.
+#compute_pss = false
+
+# Whether RAM is shown against the physical RAM even when bottom runs in a cgroup with a lower memory limit, like in a
+# container. By default, the cgroup's limit is shown as the total instead. Only supported on Linux.
+#ignore_cgroup_limits = false
+
+# Whether the CPU, memory, and network graphs start out showing the average, minimum, maximum, and standard deviation
+# of what's shown. Each graph's stats can also be toggled with 'a'.
+#show_graph_stats = false
+
+# Whether to check GitHub for a newer version of bottom once a day at startup, showing a message in the status line if
+# there is one. Nothing is downloaded or installed.
+#check_for_updates = false
+
 
 # Processes widget configuration
 #[processes]
-# The columns shown by the process widget. The following columns are supported (the GPU columns are only available if the GPU feature is enabled when built):
-# PID, Name, CPU%, Mem%, R/s, W/s, T.Read, T.Write, User, State, Time, GMem%, GPU%
-#columns = ["PID", "Name", "CPU%", "Mem%", "R/s", "W/s", "T.Read", "T.Write", "User", "State", "GMem%", "GPU%"]
+# The columns shown by the process widget. The following columns are supported (the GPU columns are only available if the GPU feature is enabled when built,
+# and the page fault, wait channel, namespace, cgroup quota, container, TCP, and inotify columns are only supported on Linux):
+# PID, Name, CPU%, Mem%, R/s, W/s, T.Read, T.Write, User, State, Time, MinFlt/s, MajFlt/s, CPU Hist, Mem Hist, WChan,
+# PID NS, NET NS, MNT NS, USER NS, Quota, Thrtl%, Container, TCP, Inotify, GMem%, GPU%
+# Defaults to PID through Time, along with GMem% once a GPU is seen and Container once a process in a container is seen.
+# columns = ["PID", "Name", "CPU%", "Mem%", "R/s", "W/s", "T.Read", "T.Write", "User", "State", "GMem%", "GPU%"]
+# Processes with more major page faults per second than this are highlighted. Defaults to 100.
+#major_fault_threshold = 100
+# Processes with more TCP connections open than this are highlighted. Defaults to 1000.
+#tcp_connection_threshold = 1000
+# How long a process can go uncollected before it's removed, in milliseconds. Defaults to twice the update rate.
+# stale_threshold_ms = 2000
+# When highlighting changes, how many percentage points the CPU or memory usage of a process has to change by. Both default to 5.
+#cpu_change_threshold = 5.0
+#mem_change_threshold = 5.0
+# How many updates sorting by change lasts for after pressing V. Defaults to 5.
+#change_sort_ticks = 5
+# The columns whose top consumer is highlighted after pressing T. Defaults to the column being sorted by.
+# highlight_top = ["CPU%", "Mem%"]
+# The columns that stay in place when scrolling the process widget sideways with left/right.
+# pinned_columns = ["PID", "Name"]
+# The column to sort by at first, one of pid, name, cpu, mem, io_read, io_write, user, state, or runtime. Defaults to cpu.
+# sort_by = "mem"
+# The order to sort in at first, "asc" or "desc". Defaults to the order of the sorted column.
+# sort_direction = "desc"
+# A regex to filter processes by at first. It can be edited or cleared in the search bar.
+# default_filter = "nginx|postgres"
+# Hide processes using less CPU or memory than these, in percent, at first. Press '>' to change them.
+# threshold_cpu = 1.0
+# threshold_mem = 0.5
+
+# Named sets of process columns, which can be switched between with the number keys in the order they're defined.
+#[presets]
+# sysadmin = ["PID", "Name", "CPU%", "Mem%", "State"]
+# io = ["PID", "Name", "R/s", "W/s", "T.Read", "T.Write"]
 
 
 # CPU widget configuration
 #[cpu]
 # One of "all" (default), "average"/"avg"
-#default = "average"
+#default = "all"
 
 
 # Disk widget configuration
 #[disk]
 # The columns shown by the process widget. The following columns are supported:
-# Disk, Mount, Used, Free, Total, Used%, Free%, R/s, W/s
+# Disk, Mount, Used, Free, Total, Used%, Free%, R/s, W/s, Wear
 #columns = ["Disk", "Mount", "Used", "Free", "Total", "Used%", "R/s", "W/s"]
 
 # By default, there are no disk name filters enabled. These can be turned on to filter out specific data entries if you
 # don't want to see them. An example use case is provided below.
-#[disk.name_filter]
+# [disk.name_filter]
 # Whether to ignore any matches. Defaults to true.
-#is_list_ignored = true
+# is_list_ignored = true
 
 # A list of filters to try and match.
-#list = ["/dev/sda\\d+", "/dev/nvme0n1p2"]
+# list = ["/dev/sda\\d+", "/dev/nvme0n1p2"]
 
 # Whether to use regex. Defaults to false.
-#regex = true
+# regex = true
 
 # Whether to be case-sensitive. Defaults to false.
-#case_sensitive = false
+# case_sensitive = false
 
 # Whether to be require matching the whole word. Defaults to false.
-#whole_word = false
+# whole_word = false
 
 # By default, there are no mount name filters enabled. An example use case is provided below.
-#[disk.mount_filter]
+# [disk.mount_filter]
 # Whether to ignore any matches. Defaults to true.
-#is_list_ignored = true
+# is_list_ignored = true
 
 # A list of filters to try and match.
-#list = ["/mnt/.*", "/boot"]
+# list = ["/mnt/.*", "/boot"]
 
 # Whether to use regex. Defaults to false.
-#regex = true
+# regex = true
 
 # Whether to be case-sensitive. Defaults to false.
-#case_sensitive = false
+# case_sensitive = false
 
 # Whether to be require matching the whole word. Defaults to false.
-#whole_word = false
+# whole_word = false
 
 
 # Temperature widget configuration
 #[temperature]
 # By default, there are no temperature sensor filters enabled. An example use case is provided below.
-#[temperature.sensor_filter]
+# [temperature.sensor_filter]
 # Whether to ignore any matches. Defaults to true.
-#is_list_ignored = true
+# is_list_ignored = true
 
 # A list of filters to try and match.
-#list = ["cpu", "wifi"]
+# list = ["cpu", "wifi"]
 
 # Whether to use regex. Defaults to false.
-#regex = false
+# regex = false
 
 # Whether to be case-sensitive. Defaults to false.
-#case_sensitive = false
+# case_sensitive = false
 
 # Whether to be require matching the whole word. Defaults to false.
-#whole_word = false
+# whole_word = false
 
 
 # Network widget configuration
 #[network]
 # By default, there are no network interface filters enabled. An example use case is provided below.
-#[network.interface_filter]
+# [network.interface_filter]
 # Whether to ignore any matches. Defaults to true.
-#is_list_ignored = true
+# is_list_ignored = true
 
 # A list of filters to try and match.
-#list = ["virbr0.*"]
+# list = ["virbr0.*"]
 
 # Whether to use regex. Defaults to false.
-#regex = true
+# regex = true
 
 # Whether to be case-sensitive. Defaults to false.
-#case_sensitive = false
+# case_sensitive = false
 
 # Whether to be require matching the whole word. Defaults to false.
-#whole_word = false
+# whole_word = false
+
+
+# Diff mode configuration
+#[diff]
+# A JSON snapshot file (e.g. created with --export) to compare current data against.
+# baseline_file = "snapshot.json"
+# How many percentage points a value has to change by to be highlighted.
+#threshold = 5.0
+
+
+# InfluxDB export configuration
+#[influxdb]
+# The InfluxDB server to send collected data to, with an http:// or https:// URL.
+# url = "http://localhost:8086"
+# The API token, organization, and bucket to write data with.
+# token = "my-token"
+# org = "my-org"
+# bucket = "bottom"
+# The maximum number of points sent per request. Defaults to 5000.
+#batch_size = 5000
+
+# Hooks, which are shell commands sent the collected data as JSON on stdin
+#[hooks]
+# Runs on every data update.
+# on_update = "/path/to/script.sh"
+# Runs when an alert is raised, with the alert in the BOTTOM_ALERT environment variable.
+# on_alert = ["/path/to/notify.sh"]
+# How long a hook can run for in milliseconds before it's killed. Defaults to 500.
+#timeout_ms = 500
+
+
+# When data is collected
+#[timing]
+# How many percent of the refresh rate to randomly move each update earlier or later by, between 0 and 100, so
+# that many instances started together don't all collect data at once. Defaults to 0.
+#jitter_pct = 0
+# How many times in a row a part of data collection, like temperature sensors, can fail before it's turned off
+# until bottom is restarted. Defaults to 3.
+#subsystem_retry_count = 3
+
+
+# Settings for each kind of widget, by the name used for its type in the layout, over the ones in [styles.widgets].
+#[widgets]
+#cpu = { border_style = "double" }
 
 
 # These are all the components that support custom theming.  Note that colour support
@@ -517,10 +740,18 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #[styles.widgets]
 #border_color = "gray"
 #selected_border_color = "light blue"
+# One of "single" (default), "double", "rounded", "bold", "ascii", or "none" to only leave space around widgets.
+#border_style = "single"
 #widget_title = {color = "gray"}
 #text = {color = "gray"}
 #selected_text = {color = "black", bg_color = "light blue"}
 #disabled_text = {color = "dark gray"}
+#highlighted_text = {color = "green"}
+#warning_text = {color = "255, 135, 0"}
+#top_consumer_text = {color = "light magenta"}
+#improved_text = {color = "green"}
+#regressed_text = {color = "red"}
+#stopped_text = {color = "yellow"}
 
 # Layout - layouts follow a pattern like this:
 # [[row]] represents a row in the application.
@@ -592,20 +823,10 @@ mod test {
     #[test]
     #[cfg(feature = "default")]
     fn check_default_config() {
-        use regex::Regex;
-
-        use crate::options::Config;
-
-        let default_config = Regex::new(r"(?m)^#([a-zA-Z\[])")
-            .unwrap()
-            .replace_all(CONFIG_TEXT, "$1");
-
-        let default_config = Regex::new(r"(?m)^#(\s\s+)([a-zA-Z\[])")
-            .unwrap()
-            .replace_all(&default_config, "$2");
+        use crate::options::{generated_config, Config};
 
         let _config: Config =
-            toml_edit::de::from_str(&default_config).expect("can parse default config");
+            toml_edit::de::from_str(&generated_config()).expect("can parse default config");
 
         // TODO: Check this.
         // assert_eq!(config, Config::default());
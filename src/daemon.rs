@@ -0,0 +1,266 @@
+//! Running in the background without the interface, with `--daemon`.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    os::fd::AsRawFd,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::Context;
+
+use crate::{
+    app::{visible_columns::VisibleColumns, App},
+    create_collection_thread,
+    event::BottomEvent,
+    export::{
+        self,
+        influxdb::InfluxDbExporter,
+        ipc::{IpcServer, IpcSocketGuard},
+        record::Recorder,
+        ws_server::WsServer,
+        Exporters,
+    },
+    options::{
+        args::BottomArgs, config::Config, get_influxdb_settings, get_ipc_socket_mode,
+        get_or_create_config, get_record_max_size, get_ws_address, get_ws_tls_config, init_app,
+    },
+    utils::cancellation_token::CancellationToken,
+};
+
+/// Set by the `SIGHUP` handler to ask for the config to be reloaded.
+static RELOAD: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn request_reload(_: libc::c_int) {
+    RELOAD.store(true, Ordering::Relaxed);
+}
+
+/// Removes the PID file when dropped.
+struct PidFile {
+    path: PathBuf,
+}
+
+impl PidFile {
+    fn new(path: PathBuf) -> anyhow::Result<Self> {
+        fs::write(&path, format!("{}\n", std::process::id()))
+            .with_context(|| format!("Could not write the PID file '{}'.", path.display()))?;
+
+        Ok(Self { path })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Detaches from the terminal and collects data until terminated, reloading
+/// the config on `SIGHUP`.
+pub(crate) fn run(args: BottomArgs) -> anyhow::Result<()> {
+    // Anything wrong with the settings is reported before detaching, while
+    // there's still a terminal to show it on.
+    let (mut config, mut app) = load(&args)?;
+    get_influxdb_settings(&args, &config)?;
+    get_ws_tls_config(&args)?;
+    if args.general.ipc_socket.is_some() {
+        get_ipc_socket_mode(&args)?;
+    }
+    let log_file = match &args.general.log_file {
+        Some(path) => Some(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Could not open the log file '{}'.", path.display()))?,
+        ),
+        None => None,
+    };
+    let pid_path = args
+        .general
+        .pid_file
+        .as_deref()
+        .map(std::path::absolute)
+        .transpose()?;
+
+    detach(log_file.as_ref()).context("Could not start the daemon.")?;
+    let _pid_file = pid_path.map(PidFile::new).transpose()?;
+
+    let (sender, receiver) = mpsc::channel();
+    {
+        let sender = sender.clone();
+        ctrlc::set_handler(move || {
+            let _ = sender.send(BottomEvent::Terminate);
+        })?;
+    }
+
+    // This has to come after ctrlc's handler, which would otherwise treat
+    // SIGHUP as another way to terminate.
+    //
+    // SAFETY: The handler only stores to an atomic, which is signal-safe.
+    unsafe {
+        libc::signal(
+            libc::SIGHUP,
+            request_reload as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+
+    while collect(&args, &config, &app, &sender, &receiver)? {
+        match load(&args) {
+            Ok(loaded) => (config, app) = loaded,
+            Err(err) => eprintln!("Could not reload the config, keeping the old one: {err:#}"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the config and sets up everything it affects.
+fn load(args: &BottomArgs) -> anyhow::Result<(Config, App)> {
+    let config = get_or_create_config(
+        args.general.config_location.as_deref(),
+        !args.general.no_system_config,
+    )?;
+    let (app, _, _) = init_app(args.clone(), config.clone())?;
+
+    Ok((config, app))
+}
+
+/// Detaches from the terminal with a double fork, so the daemon isn't a
+/// session leader and can't pick up a controlling terminal again. Output is
+/// sent to the log file if there is one, or discarded otherwise.
+///
+/// The working directory is kept, so relative paths in the config and
+/// arguments still work when reloading.
+fn detach(log_file: Option<&File>) -> io::Result<()> {
+    /// Forks, with the parent exiting right away.
+    fn fork_and_exit_parent() -> io::Result<()> {
+        // SAFETY: No other threads have been started yet.
+        match unsafe { libc::fork() } {
+            -1 => Err(io::Error::last_os_error()),
+            0 => Ok(()),
+            // SAFETY: Exiting without running destructors, as the child owns
+            // everything now.
+            _ => unsafe { libc::_exit(0) },
+        }
+    }
+
+    fork_and_exit_parent()?;
+    // SAFETY: We're not a process group leader after forking.
+    if unsafe { libc::setsid() } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    fork_and_exit_parent()?;
+
+    let null = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/null")?;
+    let output = log_file.unwrap_or(&null);
+    for (file, fd) in [
+        (&null, libc::STDIN_FILENO),
+        (output, libc::STDOUT_FILENO),
+        (output, libc::STDERR_FILENO),
+    ] {
+        // SAFETY: Both file descriptors are valid.
+        if unsafe { libc::dup2(file.as_raw_fd(), fd) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects data and writes it to stderr until terminated or asked to reload,
+/// returning whether to reload. Exporters get the chance to send what they
+/// have queued before this returns.
+fn collect(
+    args: &BottomArgs, config: &Config, app: &App, sender: &Sender<BottomEvent>,
+    receiver: &Receiver<BottomEvent>,
+) -> anyhow::Result<bool> {
+    let cancellation_token = Arc::new(CancellationToken::default());
+    let (exporters, _ipc_socket_guard) = start_exporters(args, config, app, &cancellation_token)?;
+
+    // Nothing controls the collection thread besides cancelling it.
+    let (_control_sender, control_receiver) = mpsc::channel();
+    let collection_thread = create_collection_thread(
+        sender.clone(),
+        control_receiver,
+        cancellation_token.clone(),
+        &app.app_config_fields,
+        app.filters.clone(),
+        export::all_widgets(&app.app_config_fields),
+        VisibleColumns::all(),
+        exporters,
+    );
+
+    let reload = loop {
+        if RELOAD.swap(false, Ordering::Relaxed) {
+            break true;
+        }
+
+        match receiver.recv_timeout(Duration::from_millis(100)) {
+            Ok(BottomEvent::Update(data)) => {
+                let mut stderr = io::stderr().lock();
+                if serde_json::to_writer(&mut stderr, &data).is_ok() {
+                    let _ = writeln!(stderr);
+                }
+            }
+            Ok(BottomEvent::Terminate) | Err(RecvTimeoutError::Disconnected) => break false,
+            Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+        }
+    };
+
+    cancellation_token.cancel();
+    let _ = collection_thread.join();
+
+    Ok(reload)
+}
+
+/// Starts the exporters set in the arguments and config. Unlike with the
+/// interface, hooks aren't run.
+fn start_exporters(
+    args: &BottomArgs, config: &Config, app: &App, cancellation_token: &Arc<CancellationToken>,
+) -> anyhow::Result<(Exporters, Option<IpcSocketGuard>)> {
+    let recorder = match &args.general.record {
+        Some(path) => Some(Recorder::new(path, get_record_max_size(args)?)?),
+        None => None,
+    };
+    let influxdb = get_influxdb_settings(args, config)?
+        .map(|settings| InfluxDbExporter::new(settings, cancellation_token.clone()))
+        .transpose()?;
+    let ws_server = match get_ws_address(args) {
+        Some(address) => Some(WsServer::new(
+            address,
+            args.general.ws_token.clone(),
+            app.app_config_fields.update_rate,
+            get_ws_tls_config(args)?,
+            cancellation_token.clone(),
+        )?),
+        None => None,
+    };
+    let (ipc_server, ipc_socket_guard) = match &args.general.ipc_socket {
+        Some(path) => {
+            let mode = get_ipc_socket_mode(args)?;
+            let (server, guard) = IpcServer::new(path, mode, cancellation_token.clone())?;
+            (Some(server), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    Ok((
+        Exporters {
+            recorder,
+            influxdb,
+            ws_server,
+            ipc_server,
+        },
+        ipc_socket_guard,
+    ))
+}
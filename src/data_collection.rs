@@ -6,40 +6,112 @@ pub mod nvidia;
 #[cfg(feature = "battery")]
 pub mod batteries;
 
+pub mod cgroups;
+#[cfg(target_os = "linux")]
+pub mod containers;
 pub mod cpu;
+pub mod disk_bench;
 pub mod disks;
 pub mod error;
+#[cfg(any(feature = "nvidia", feature = "amd-gpu"))]
+pub mod gpu;
+pub mod log_tail;
 pub mod memory;
 pub mod network;
 pub mod processes;
+pub mod synthetic;
+pub mod system_info;
+#[cfg(feature = "systemd")]
+pub mod systemd;
 pub mod temperature;
 
 use std::time::{Duration, Instant};
 
-#[cfg(any(target_os = "linux", feature = "gpu"))]
+use bitflags::bitflags;
+#[cfg(any(target_os = "linux", feature = "nvidia", feature = "amd-gpu"))]
 use hashbrown::HashMap;
 #[cfg(not(target_os = "windows"))]
 use processes::Pid;
 #[cfg(feature = "battery")]
 use starship_battery::{Battery, Manager};
 
-use self::temperature::TemperatureType;
+use serde::{Deserialize, Serialize};
+
+use self::{
+    error::{DataError, Subsystem, SubsystemRetries},
+    temperature::TemperatureType,
+};
 use super::DataFilters;
-use crate::app::layout_manager::UsedWidgets;
+use crate::app::{
+    layout_manager::{BottomWidgetType, UsedWidgets},
+    visible_columns::VisibleColumns,
+};
+
+/// GPU data harvested from a single vendor backend.
+#[cfg(any(feature = "nvidia", feature = "amd-gpu"))]
+pub struct GpusData {
+    pub memory: Option<Vec<(String, memory::MemHarvest)>>,
+    /// The usage of each GPU in `memory`, in the same order.
+    pub usage: Option<Vec<gpu::GpuUsage>>,
+    pub temperature: Option<Vec<temperature::TempHarvest>>,
+    pub procs: Option<(u64, Vec<HashMap<u32, (u64, u32)>>)>,
+}
 
-#[derive(Clone, Debug)]
+/// A single snapshot of collected data. This can be serialized to and from JSON;
+/// note the collection time is not kept across serialization.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Data {
+    #[serde(skip, default = "Instant::now")]
     pub collection_time: Instant,
     pub cpu: Option<cpu::CpuHarvest>,
+    /// A breakdown of what the CPU time was spent on. Only collected on Linux.
+    pub cpu_times: Option<cpu::CpuTimesHarvest>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
+    /// The running and total task counts. Only collected on Linux.
+    pub sched_tasks: Option<cpu::SchedTasksHarvest>,
+    /// The processes that can only run on some of the cores. Only collected on
+    /// Linux, and only for the basic CPU widget.
+    pub pinned_processes: Option<Vec<processes::PinnedProcess>>,
     pub memory: Option<memory::MemHarvest>,
     #[cfg(not(target_os = "windows"))]
     pub cache: Option<memory::MemHarvest>,
     pub swap: Option<memory::MemHarvest>,
+    /// Each swap device in use. Only collected on Linux.
+    pub swap_devices: Option<Vec<memory::SwapDevice>>,
+    /// How quickly pages are being swapped. Only collected on Linux.
+    pub swap_io: Option<memory::SwapIOData>,
+    /// The memory limits of the cgroup bottom runs in, which `memory` is
+    /// against if set. Only collected on Linux.
+    #[serde(default)]
+    pub cgroup_memory: Option<memory::CgroupMemoryLimits>,
+    /// How fragmented the free memory of each zone is. Only collected on
+    /// Linux.
+    #[serde(default)]
+    pub buddy_zones: Option<Vec<memory::BuddyZone>>,
+    /// The proportional set size of every readable process, in bytes. Only
+    /// collected on Linux, and only if PSS is computed.
+    pub total_pss: Option<u64>,
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
+    /// Maps each core with a temperature sensor of its own to the index of
+    /// that sensor in `temperature_sensors`. Only collected on Linux.
+    pub core_temp_sensors: Option<HashMap<usize, usize>>,
     pub network: Option<network::NetworkHarvest>,
+    /// How much of the kernel's socket pools is in use. Only collected on
+    /// Linux.
+    #[serde(default)]
+    pub sockstat: Option<network::SockstatSummary>,
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
+    /// How many cores the CPU usage of processes is shared between. Only
+    /// collected along with processes.
+    #[serde(default)]
+    pub num_cpus: Option<usize>,
+    /// How many inotify watches each user can have. Only collected on Linux,
+    /// and only if the inotify column is shown.
+    pub max_inotify_watches: Option<u32>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
+    /// How worn out the SSD each disk is on is, by disk name. Only collected
+    /// on Linux, every so often.
+    pub disk_endurance: Option<HashMap<String, disks::SmartEndurance>>,
     pub io: Option<disks::IoHarvest>,
     #[cfg(feature = "battery")]
     pub list_of_batteries: Option<Vec<batteries::BatteryHarvest>>,
@@ -47,6 +119,15 @@ pub struct Data {
     pub arc: Option<memory::MemHarvest>,
     #[cfg(feature = "gpu")]
     pub gpu: Option<Vec<(String, memory::MemHarvest)>>,
+    /// How busy each GPU in `gpu` is, and how much power it's drawing.
+    #[cfg(feature = "gpu")]
+    #[serde(default)]
+    pub gpu_usage: Option<Vec<gpu::GpuUsage>>,
+    #[cfg(feature = "systemd")]
+    pub user_units: Option<Vec<systemd::UserUnit>>,
+    /// The subsystems that couldn't be read this time, or have been turned off.
+    #[serde(default)]
+    pub errors: Vec<DataError>,
 }
 
 impl Default for Data {
@@ -54,14 +135,27 @@ impl Default for Data {
         Data {
             collection_time: Instant::now(),
             cpu: None,
+            cpu_times: None,
             load_avg: None,
+            sched_tasks: None,
+            pinned_processes: None,
             memory: None,
             #[cfg(not(target_os = "windows"))]
             cache: None,
             swap: None,
+            swap_devices: None,
+            swap_io: None,
+            cgroup_memory: None,
+            buddy_zones: None,
+            sockstat: None,
+            total_pss: None,
             temperature_sensors: None,
+            core_temp_sensors: None,
             list_of_processes: None,
+            num_cpus: None,
+            max_inotify_watches: None,
             disks: None,
+            disk_endurance: None,
             io: None,
             network: None,
             #[cfg(feature = "battery")]
@@ -70,6 +164,11 @@ impl Default for Data {
             arc: None,
             #[cfg(feature = "gpu")]
             gpu: None,
+            #[cfg(feature = "gpu")]
+            gpu_usage: None,
+            #[cfg(feature = "systemd")]
+            user_units: None,
+            errors: Vec::new(),
         }
     }
 }
@@ -78,12 +177,23 @@ impl Data {
     pub fn cleanup(&mut self) {
         self.io = None;
         self.temperature_sensors = None;
+        self.core_temp_sensors = None;
         self.list_of_processes = None;
         self.disks = None;
+        self.disk_endurance = None;
         self.memory = None;
         self.swap = None;
+        self.swap_devices = None;
+        self.swap_io = None;
+        self.cgroup_memory = None;
+        self.buddy_zones = None;
+        self.sockstat = None;
+        self.total_pss = None;
         self.cpu = None;
+        self.cpu_times = None;
         self.load_avg = None;
+        self.sched_tasks = None;
+        self.pinned_processes = None;
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -95,6 +205,7 @@ impl Data {
         #[cfg(feature = "gpu")]
         {
             self.gpu = None;
+            self.gpu_usage = None;
         }
     }
 }
@@ -137,6 +248,48 @@ impl Default for SysinfoSource {
     }
 }
 
+bitflags! {
+    /// The subsystems that may be collected at all, for environments where
+    /// some of them can't be read, like containers with a partial `/proc`.
+    /// Those that are off are never collected, whatever the layout needs.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct CollectionFlags: u8 {
+        const PROCESSES = 1 << 0;
+        const NETWORK = 1 << 1;
+        const DISKS = 1 << 2;
+        const TEMPERATURE = 1 << 3;
+    }
+}
+
+impl Default for CollectionFlags {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl CollectionFlags {
+    /// Whether the data a widget shows is collected. Widgets that need a
+    /// subsystem that's off are left out of the layout.
+    pub fn collects(self, widget_type: &BottomWidgetType) -> bool {
+        match widget_type {
+            BottomWidgetType::Proc | BottomWidgetType::UserProcs => self.contains(Self::PROCESSES),
+            BottomWidgetType::Net | BottomWidgetType::BasicNet => self.contains(Self::NETWORK),
+            BottomWidgetType::Disk => self.contains(Self::DISKS),
+            BottomWidgetType::Temp => self.contains(Self::TEMPERATURE),
+            _ => true,
+        }
+    }
+
+    /// Turns off the parts of `used_widgets` whose subsystems are off.
+    pub fn mask(self, used_widgets: &mut UsedWidgets) {
+        used_widgets.use_proc &= self.contains(Self::PROCESSES);
+        used_widgets.use_net &= self.contains(Self::NETWORK);
+        used_widgets.use_disk &= self.contains(Self::DISKS);
+        used_widgets.use_smart &= self.contains(Self::DISKS);
+        used_widgets.use_temp &= self.contains(Self::TEMPERATURE);
+    }
+}
+
 #[derive(Debug)]
 pub struct DataCollector {
     pub data: Data,
@@ -149,7 +302,12 @@ pub struct DataCollector {
     total_tx: u64,
     show_average_cpu: bool,
     widgets_to_harvest: UsedWidgets,
+    collection_flags: CollectionFlags,
     filters: DataFilters,
+    /// Makes up the data instead of collecting it, if set.
+    synthetic: Option<synthetic::SyntheticCollector>,
+    /// Turns off the subsystems that keep failing.
+    retries: SubsystemRetries,
 
     #[cfg(target_os = "linux")]
     pid_mapping: HashMap<Pid, processes::PrevProcDetails>,
@@ -157,6 +315,41 @@ pub struct DataCollector {
     prev_idle: f64,
     #[cfg(target_os = "linux")]
     prev_non_idle: f64,
+    #[cfg(target_os = "linux")]
+    prev_cpu_times: Option<cpu::linux::CpuTimes>,
+    /// The pages swapped in and out as of the last collection.
+    #[cfg(target_os = "linux")]
+    prev_swap_pages: Option<(u64, u64)>,
+    #[cfg(target_os = "linux")]
+    visible_columns: VisibleColumns,
+    /// Whether to compute the total PSS for the memory widget.
+    #[cfg(target_os = "linux")]
+    compute_pss: bool,
+    /// Whether to find the processes pinned to some of the cores.
+    #[cfg(target_os = "linux")]
+    collect_affinity: bool,
+    #[cfg(target_os = "linux")]
+    last_pss_time: Option<Instant>,
+    /// Whether to show RAM against the physical RAM even in a cgroup with a
+    /// memory limit.
+    #[cfg(target_os = "linux")]
+    ignore_cgroup_limits: bool,
+    /// The hard memory limit of the cgroup bottom runs in, read once at the
+    /// start.
+    #[cfg(target_os = "linux")]
+    cgroup_memory_limit: Option<u64>,
+    #[cfg(target_os = "linux")]
+    cgroups: cgroups::CgroupCollector,
+    #[cfg(target_os = "linux")]
+    containers: containers::ContainerNames,
+    #[cfg(target_os = "linux")]
+    collect_ipv6: bool,
+    #[cfg(target_os = "linux")]
+    last_smart_time: Option<Instant>,
+    #[cfg(target_os = "linux")]
+    total_rx_v6: u64,
+    #[cfg(target_os = "linux")]
+    total_tx_v6: u64,
 
     #[cfg(feature = "battery")]
     battery_manager: Option<Manager>,
@@ -170,6 +363,9 @@ pub struct DataCollector {
     gpu_pids: Option<Vec<HashMap<u32, (u64, u32)>>>,
     #[cfg(feature = "gpu")]
     gpus_total_mem: Option<u64>,
+
+    #[cfg(feature = "systemd")]
+    last_user_units_time: Option<Instant>,
 }
 
 impl DataCollector {
@@ -187,6 +383,34 @@ impl DataCollector {
             prev_idle: 0_f64,
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
+            #[cfg(target_os = "linux")]
+            prev_cpu_times: None,
+            #[cfg(target_os = "linux")]
+            prev_swap_pages: None,
+            #[cfg(target_os = "linux")]
+            visible_columns: VisibleColumns::empty(),
+            #[cfg(target_os = "linux")]
+            compute_pss: false,
+            #[cfg(target_os = "linux")]
+            collect_affinity: false,
+            #[cfg(target_os = "linux")]
+            last_pss_time: None,
+            #[cfg(target_os = "linux")]
+            ignore_cgroup_limits: false,
+            #[cfg(target_os = "linux")]
+            cgroup_memory_limit: None,
+            #[cfg(target_os = "linux")]
+            cgroups: cgroups::CgroupCollector::default(),
+            #[cfg(target_os = "linux")]
+            containers: containers::ContainerNames::default(),
+            #[cfg(target_os = "linux")]
+            collect_ipv6: false,
+            #[cfg(target_os = "linux")]
+            last_smart_time: None,
+            #[cfg(target_os = "linux")]
+            total_rx_v6: 0,
+            #[cfg(target_os = "linux")]
+            total_tx_v6: 0,
             temperature_type: TemperatureType::Celsius,
             use_current_cpu_total: false,
             unnormalized_cpu: false,
@@ -195,21 +419,42 @@ impl DataCollector {
             total_tx: 0,
             show_average_cpu: false,
             widgets_to_harvest: UsedWidgets::default(),
+            collection_flags: CollectionFlags::all(),
             #[cfg(feature = "battery")]
             battery_manager: None,
             #[cfg(feature = "battery")]
             battery_list: None,
             filters,
+            synthetic: None,
+            retries: SubsystemRetries::new(DEFAULT_SUBSYSTEM_RETRY_COUNT),
             #[cfg(target_family = "unix")]
             user_table: Default::default(),
             #[cfg(feature = "gpu")]
             gpu_pids: None,
             #[cfg(feature = "gpu")]
             gpus_total_mem: None,
+            #[cfg(feature = "systemd")]
+            last_user_units_time: None,
         }
     }
 
-    pub fn init(&mut self) {
+    /// Starts collecting, with only the subsystems in `collection_flags`.
+    pub fn init(&mut self, collection_flags: CollectionFlags) {
+        self.collection_flags = collection_flags;
+        collection_flags.mask(&mut self.widgets_to_harvest);
+
+        // Both of these read every process.
+        #[cfg(target_os = "linux")]
+        if !collection_flags.contains(CollectionFlags::PROCESSES) {
+            self.compute_pss = false;
+            self.collect_affinity = false;
+        }
+
+        #[cfg(target_os = "linux")]
+        if !self.ignore_cgroup_limits {
+            self.cgroup_memory_limit = memory::cgroup::detect_cgroup_memory_limit();
+        }
+
         #[cfg(feature = "battery")]
         {
             if self.widgets_to_harvest.use_battery {
@@ -234,7 +479,8 @@ impl DataCollector {
         self.data.cleanup();
     }
 
-    pub fn set_data_collection(&mut self, used_widgets: UsedWidgets) {
+    pub fn set_data_collection(&mut self, mut used_widgets: UsedWidgets) {
+        self.collection_flags.mask(&mut used_widgets);
         self.widgets_to_harvest = used_widgets;
     }
 
@@ -252,6 +498,97 @@ impl DataCollector {
 
     pub fn set_show_average_cpu(&mut self, show_average_cpu: bool) {
         self.show_average_cpu = show_average_cpu;
+
+        if let Some(synthetic) = &mut self.synthetic {
+            synthetic.set_show_average_cpu(show_average_cpu);
+        }
+    }
+
+    /// Sets how many times in a row a subsystem can fail before it's no longer
+    /// read.
+    pub fn set_subsystem_retry_count(&mut self, retry_count: u32) {
+        self.retries = SubsystemRetries::new(retry_count);
+    }
+
+    /// Sets whether to make up the data instead of collecting it.
+    pub fn set_synthetic(&mut self, settings: Option<synthetic::SyntheticSettings>) {
+        self.synthetic = settings.map(|settings| {
+            let mut synthetic = synthetic::SyntheticCollector::new(settings);
+            synthetic.set_show_average_cpu(self.show_average_cpu);
+            synthetic
+        });
+    }
+
+    /// Sets which process columns are shown, so that the data for hidden
+    /// ones isn't collected. This only affects Linux, where that data is
+    /// supported.
+    pub fn set_visible_columns(&mut self, visible_columns: VisibleColumns) {
+        #[cfg(target_os = "linux")]
+        {
+            self.visible_columns = visible_columns;
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = visible_columns;
+        }
+    }
+
+    /// Sets whether to compute the total PSS for the memory widget. This is
+    /// only supported on Linux.
+    pub fn set_compute_pss(&mut self, compute_pss: bool) {
+        #[cfg(target_os = "linux")]
+        {
+            self.compute_pss = compute_pss;
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = compute_pss;
+        }
+    }
+
+    /// Sets whether to show RAM against the physical RAM even in a cgroup with
+    /// a memory limit. This only affects Linux, and must be set before
+    /// [`Self::init`].
+    pub fn set_ignore_cgroup_limits(&mut self, ignore_cgroup_limits: bool) {
+        #[cfg(target_os = "linux")]
+        {
+            self.ignore_cgroup_limits = ignore_cgroup_limits;
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = ignore_cgroup_limits;
+        }
+    }
+
+    /// Sets whether to find the processes pinned to some of the cores, which
+    /// the basic CPU widget shows. This is only supported on Linux.
+    pub fn set_collect_affinity(&mut self, collect_affinity: bool) {
+        #[cfg(target_os = "linux")]
+        {
+            self.collect_affinity = collect_affinity;
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = collect_affinity;
+        }
+    }
+
+    /// Sets whether to collect IPv6 traffic and addresses. This is only
+    /// supported on Linux.
+    pub fn set_collect_ipv6(&mut self, collect_ipv6: bool) {
+        #[cfg(target_os = "linux")]
+        {
+            self.collect_ipv6 = collect_ipv6;
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = collect_ipv6;
+        }
     }
 
     /// Refresh sysinfo data. We use sysinfo for the following data:
@@ -321,9 +658,16 @@ impl DataCollector {
     }
 
     pub fn update_data(&mut self) {
+        if let Some(synthetic) = &mut self.synthetic {
+            synthetic.update_data();
+            self.data = std::mem::take(&mut synthetic.data);
+            return;
+        }
+
         self.refresh_sysinfo_data();
 
         self.data.collection_time = Instant::now();
+        self.data.errors = self.retries.disabled_errors();
 
         self.update_cpu_usage();
         self.update_memory_usage();
@@ -336,9 +680,14 @@ impl DataCollector {
         self.update_gpus(); // update_gpus before procs for gpu_pids but after temps for appending
 
         self.update_processes();
+        #[cfg(target_os = "linux")]
+        self.update_total_pss();
         self.update_network_usage();
         self.update_disks();
 
+        #[cfg(feature = "systemd")]
+        self.update_user_units();
+
         // Update times for future reference.
         self.last_collection_time = self.data.collection_time;
     }
@@ -347,12 +696,30 @@ impl DataCollector {
     #[inline]
     fn update_gpus(&mut self) {
         if self.widgets_to_harvest.use_gpu {
+            let mut backends = Vec::with_capacity(2);
+
             #[cfg(feature = "nvidia")]
-            if let Some(data) = nvidia::get_nvidia_vecs(
+            backends.extend(nvidia::get_nvidia_vecs(
                 &self.temperature_type,
                 &self.filters.temp_filter,
                 &self.widgets_to_harvest,
-            ) {
+            ));
+
+            #[cfg(feature = "amd-gpu")]
+            backends.extend(gpu::amd::get_amd_vecs(
+                &self.temperature_type,
+                &self.filters.temp_filter,
+                &self.widgets_to_harvest,
+            ));
+
+            // Results from every active backend are concatenated, so mixed AMD/NVIDIA
+            // systems show all of their cards.
+            let mut gpu_mem: Option<Vec<(String, memory::MemHarvest)>> = None;
+            let mut gpu_usage: Option<Vec<gpu::GpuUsage>> = None;
+            let mut gpu_pids: Option<Vec<HashMap<u32, (u64, u32)>>> = None;
+            let mut gpus_total_mem: Option<u64> = None;
+
+            for data in backends {
                 if let Some(mut temp) = data.temperature {
                     if let Some(sensors) = &mut self.data.temperature_sensors {
                         sensors.append(&mut temp);
@@ -360,23 +727,64 @@ impl DataCollector {
                         self.data.temperature_sensors = Some(temp);
                     }
                 }
-                if let Some(mem) = data.memory {
-                    self.data.gpu = Some(mem);
+                if let Some(mut mem) = data.memory {
+                    // Usage is kept in the same order as memory, so each card's
+                    // usage stays with it.
+                    let mut usage = data.usage.unwrap_or_default();
+                    usage.resize(mem.len(), gpu::GpuUsage::default());
+
+                    gpu_mem.get_or_insert_with(Vec::new).append(&mut mem);
+                    gpu_usage.get_or_insert_with(Vec::new).append(&mut usage);
                 }
-                if let Some(proc) = data.procs {
-                    self.gpu_pids = Some(proc.1);
-                    self.gpus_total_mem = Some(proc.0);
+                if let Some((total_mem, mut procs)) = data.procs {
+                    gpu_pids.get_or_insert_with(Vec::new).append(&mut procs);
+                    *gpus_total_mem.get_or_insert(0) += total_mem;
                 }
             }
+
+            if gpu_mem.is_some() {
+                self.data.gpu = gpu_mem;
+                self.data.gpu_usage = gpu_usage;
+            }
+            if gpu_pids.is_some() {
+                self.gpu_pids = gpu_pids;
+                self.gpus_total_mem = gpus_total_mem;
+            }
         }
     }
 
     #[inline]
     fn update_cpu_usage(&mut self) {
-        if self.widgets_to_harvest.use_cpu {
-            self.data.cpu = cpu::get_cpu_data_list(&self.sys.system, self.show_average_cpu).ok();
+        if self.widgets_to_harvest.use_cpu && !self.retries.is_disabled(Subsystem::Cpu) {
+            self.data.cpu = self.retries.record(
+                Subsystem::Cpu,
+                cpu::get_cpu_data_list(&self.sys.system, self.show_average_cpu),
+                &mut self.data.errors,
+            );
 
-            #[cfg(target_family = "unix")]
+            #[cfg(target_os = "linux")]
+            if let Some(cpu_times) = cpu::linux::CpuTimes::read() {
+                self.data.cpu_times = self.prev_cpu_times.map(|prev| cpu_times.usage_since(&prev));
+                self.prev_cpu_times = Some(cpu_times);
+            }
+
+            #[cfg(target_os = "linux")]
+            match cpu::linux::read_load_avg() {
+                Some((load_avg, sched_tasks)) => {
+                    self.data.load_avg = Some(load_avg);
+                    self.data.sched_tasks = Some(sched_tasks);
+                }
+                None => self.data.load_avg = Some(cpu::get_load_avg()),
+            }
+
+            #[cfg(target_os = "linux")]
+            if self.collect_affinity {
+                let num_cores = self.sys.system.cpus().len();
+                self.data.pinned_processes = Some(processes::pinned_processes(num_cores));
+            }
+
+            // Elsewhere, this uses getloadavg(3).
+            #[cfg(all(target_family = "unix", not(target_os = "linux")))]
             {
                 self.data.load_avg = Some(cpu::get_load_avg());
             }
@@ -385,34 +793,59 @@ impl DataCollector {
 
     #[inline]
     fn update_processes(&mut self) {
-        if self.widgets_to_harvest.use_proc {
-            if let Ok(mut process_list) = self.get_processes() {
+        if self.widgets_to_harvest.use_proc && !self.retries.is_disabled(Subsystem::Processes) {
+            let result = self.get_processes();
+            if let Some(mut process_list) =
+                self.retries
+                    .record(Subsystem::Processes, result, &mut self.data.errors)
+            {
                 // NB: To avoid duplicate sorts on rerenders/events, we sort the processes by
                 // PID here. We also want to avoid re-sorting *again* later on
                 // if we're sorting by PID, since we already did it here!
                 process_list.sort_unstable_by_key(|p| p.pid);
+
+                let num_cpus = self.sys.system.cpus().len();
+                for process in &mut process_list {
+                    process.set_cpu_usage_modes(self.unnormalized_cpu, num_cpus);
+                }
+                self.data.num_cpus = Some(num_cpus);
                 self.data.list_of_processes = Some(process_list);
             }
+
+            #[cfg(target_os = "linux")]
+            if self.visible_columns.contains(VisibleColumns::INOTIFY) {
+                self.data.max_inotify_watches = processes::max_user_watches();
+            }
         }
     }
 
     #[inline]
     fn update_temps(&mut self) {
-        if self.widgets_to_harvest.use_temp {
+        if self.widgets_to_harvest.use_temp && !self.retries.is_disabled(Subsystem::Temperature) {
             #[cfg(not(target_os = "linux"))]
-            if let Ok(data) = temperature::get_temperature_data(
-                &self.sys.temps,
-                &self.temperature_type,
-                &self.filters.temp_filter,
+            if let Some(data) = self.retries.record(
+                Subsystem::Temperature,
+                temperature::get_temperature_data(
+                    &self.sys.temps,
+                    &self.temperature_type,
+                    &self.filters.temp_filter,
+                ),
+                &mut self.data.errors,
             ) {
                 self.data.temperature_sensors = data;
             }
 
             #[cfg(target_os = "linux")]
-            if let Ok(data) =
-                temperature::get_temperature_data(&self.temperature_type, &self.filters.temp_filter)
-            {
-                self.data.temperature_sensors = data;
+            if let Some(data) = self.retries.record(
+                Subsystem::Temperature,
+                temperature::get_temperature_data(
+                    &self.temperature_type,
+                    &self.filters.temp_filter,
+                ),
+                &mut self.data.errors,
+            ) {
+                (self.data.temperature_sensors, self.data.core_temp_sensors) =
+                    data.map(|data| (data.sensors, data.core_sensors)).unzip();
             }
         }
     }
@@ -420,7 +853,16 @@ impl DataCollector {
     #[inline]
     fn update_memory_usage(&mut self) {
         if self.widgets_to_harvest.use_mem {
-            self.data.memory = memory::get_ram_usage(&self.sys.system);
+            // sysinfo reports no memory at all if it couldn't be read.
+            if !self.retries.is_disabled(Subsystem::Memory) {
+                self.data.memory = self.retries.record(
+                    Subsystem::Memory,
+                    memory::get_ram_usage(&self.sys.system)
+                        .filter(|memory| memory.total_bytes > 0)
+                        .ok_or("Could not read memory usage."),
+                    &mut self.data.errors,
+                );
+            }
 
             #[cfg(not(target_os = "windows"))]
             if self.widgets_to_harvest.use_cache {
@@ -429,6 +871,14 @@ impl DataCollector {
 
             self.data.swap = memory::get_swap_usage(&self.sys.system);
 
+            #[cfg(target_os = "linux")]
+            {
+                self.apply_cgroup_memory_limit();
+                self.data.swap_devices = memory::swaps::get_swap_devices();
+                self.data.buddy_zones = Some(memory::buddyinfo());
+                self.update_swap_io();
+            }
+
             #[cfg(feature = "zfs")]
             {
                 self.data.arc = memory::arc::get_arc_usage();
@@ -436,6 +886,98 @@ impl DataCollector {
         }
     }
 
+    /// Shows RAM against the memory limit of the cgroup bottom runs in, if it
+    /// has one below the physical RAM.
+    #[cfg(target_os = "linux")]
+    fn apply_cgroup_memory_limit(&mut self) {
+        let physical_total_bytes = self.sys.system.total_memory();
+        let Some(max_bytes) = self
+            .cgroup_memory_limit
+            .filter(|&max_bytes| max_bytes > 0 && max_bytes < physical_total_bytes)
+        else {
+            return;
+        };
+
+        let (used_bytes, high_bytes) = memory::cgroup::get_cgroup_memory_usage();
+        let Some(used_bytes) =
+            used_bytes.or_else(|| self.data.memory.as_ref().map(|memory| memory.used_bytes))
+        else {
+            return;
+        };
+
+        self.data.memory = Some(memory::MemHarvest {
+            used_bytes,
+            total_bytes: max_bytes,
+            use_percent: Some(used_bytes as f64 / max_bytes as f64 * 100.0),
+        });
+        self.data.cgroup_memory = Some(memory::CgroupMemoryLimits {
+            max_bytes,
+            high_bytes: high_bytes.filter(|&high_bytes| high_bytes < max_bytes),
+            physical_total_bytes,
+        });
+    }
+
+    /// Works out how quickly pages are being swapped from how many were swapped
+    /// since the last collection.
+    #[cfg(target_os = "linux")]
+    fn update_swap_io(&mut self) {
+        let Some((swap_in, swap_out)) = memory::vmstat::get_swap_page_counts() else {
+            return;
+        };
+
+        if let Some((prev_in, prev_out)) = self.prev_swap_pages {
+            let elapsed = self
+                .data
+                .collection_time
+                .duration_since(self.last_collection_time)
+                .as_secs_f64();
+
+            if elapsed > 0.0 {
+                let rate = |current: u64, prev: u64| {
+                    (current.saturating_sub(prev) as f64 / elapsed).round() as u64
+                };
+
+                self.data.swap_io = Some(memory::SwapIOData {
+                    swap_in_pages_per_sec: rate(swap_in, prev_in),
+                    swap_out_pages_per_sec: rate(swap_out, prev_out),
+                    page_size: rustix::param::page_size() as u64,
+                });
+            }
+        }
+
+        self.prev_swap_pages = Some((swap_in, swap_out));
+    }
+
+    /// Whether it's been [`PSS_REFRESH_TIME`] since PSS was last read, as it
+    /// means reading `smaps_rollup` for every process.
+    #[cfg(target_os = "linux")]
+    fn is_pss_due(&self) -> bool {
+        const PSS_REFRESH_TIME: Duration = Duration::from_secs(5);
+
+        self.last_pss_time.map_or(true, |last| {
+            self.data.collection_time.duration_since(last) >= PSS_REFRESH_TIME
+        })
+    }
+
+    /// Sums up the PSS of every process when it's due, reusing what was read
+    /// for the process list if there is one.
+    #[cfg(target_os = "linux")]
+    fn update_total_pss(&mut self) {
+        let reads_pss = self.compute_pss || self.visible_columns.contains(VisibleColumns::PSS);
+        if !reads_pss || !self.is_pss_due() {
+            return;
+        }
+
+        if self.compute_pss && self.widgets_to_harvest.use_mem {
+            self.data.total_pss = Some(match &self.data.list_of_processes {
+                Some(processes) => processes.iter().filter_map(|p| p.pss_bytes).sum(),
+                None => processes::total_pss(),
+            });
+        }
+
+        self.last_pss_time = Some(self.data.collection_time);
+    }
+
     #[inline]
     fn update_network_usage(&mut self) {
         let current_instant = self.data.collection_time;
@@ -450,9 +992,29 @@ impl DataCollector {
                 &self.filters.net_filter,
             );
 
+            #[cfg(target_os = "linux")]
+            let ipv6 = if self.collect_ipv6 {
+                network::ipv6::get_ipv6_data(
+                    self.last_collection_time,
+                    &mut self.total_rx_v6,
+                    &mut self.total_tx_v6,
+                    current_instant,
+                    &self.filters.net_filter,
+                )
+            } else {
+                None
+            };
+            #[cfg(not(target_os = "linux"))]
+            let ipv6 = None;
+
             self.total_rx = net_data.total_rx;
             self.total_tx = net_data.total_tx;
-            self.data.network = Some(net_data);
+            self.data.network = Some(network::NetworkHarvest { ipv6, ..net_data });
+
+            #[cfg(target_os = "linux")]
+            {
+                self.data.sockstat = Some(network::sockstat::read());
+            }
         }
     }
 
@@ -467,11 +1029,65 @@ impl DataCollector {
         }
     }
 
+    /// Updates the user units every [`USER_UNITS_REFRESH_TIME`], as it means
+    /// running `systemctl`.
+    #[inline]
+    #[cfg(feature = "systemd")]
+    fn update_user_units(&mut self) {
+        const USER_UNITS_REFRESH_TIME: Duration = Duration::from_secs(10);
+
+        if self.widgets_to_harvest.use_startup {
+            let now = self.data.collection_time;
+            let is_due = self.last_user_units_time.map_or(true, |last| {
+                now.duration_since(last) >= USER_UNITS_REFRESH_TIME
+            });
+
+            if is_due {
+                self.data.user_units = systemd::user_units();
+                self.last_user_units_time = Some(now);
+            }
+        }
+    }
+
     #[inline]
     fn update_disks(&mut self) {
         if self.widgets_to_harvest.use_disk {
-            self.data.disks = disks::get_disk_usage(self).ok();
-            self.data.io = disks::get_io_usage().ok();
+            if !self.retries.is_disabled(Subsystem::Disks) {
+                let result = disks::get_disk_usage(self);
+                self.data.disks =
+                    self.retries
+                        .record(Subsystem::Disks, result, &mut self.data.errors);
+            }
+
+            if !self.retries.is_disabled(Subsystem::DiskIo) {
+                self.data.io = self.retries.record(
+                    Subsystem::DiskIo,
+                    disks::get_io_usage(),
+                    &mut self.data.errors,
+                );
+            }
+
+            #[cfg(target_os = "linux")]
+            self.update_disk_endurance();
+        }
+    }
+
+    /// Reads the SMART data of the SSDs every [`SMART_REFRESH_TIME`], as it
+    /// barely changes and means sending commands to each drive.
+    #[cfg(target_os = "linux")]
+    fn update_disk_endurance(&mut self) {
+        const SMART_REFRESH_TIME: Duration = Duration::from_secs(600);
+
+        let now = self.data.collection_time;
+        let is_due = self
+            .last_smart_time
+            .map_or(true, |last| now.duration_since(last) >= SMART_REFRESH_TIME);
+
+        if self.widgets_to_harvest.use_smart && is_due {
+            if let Some(disks) = &self.data.disks {
+                self.data.disk_endurance = Some(disks::get_disk_endurance(disks));
+                self.last_smart_time = Some(now);
+            }
         }
     }
 
@@ -486,6 +1102,10 @@ impl DataCollector {
     }
 }
 
+/// How many times in a row a subsystem can fail before it's no longer read, if
+/// not set in the config file.
+pub const DEFAULT_SUBSYSTEM_RETRY_COUNT: u32 = 3;
+
 /// We set a sleep duration between 10ms and 250ms, ideally sysinfo's
 /// [`sysinfo::MINIMUM_CPU_UPDATE_INTERVAL`] + 1.
 ///
@@ -0,0 +1,363 @@
+//! Data collection for the CPU limits of cgroups. This is only supported on
+//! Linux.
+
+#[cfg(target_os = "linux")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[cfg(target_os = "linux")]
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Processes using more than this fraction of their cgroup's CPU quota are
+/// highlighted.
+pub const NEAR_QUOTA_RATIO: f64 = 0.9;
+
+/// The CPU quota of a cgroup, and how much of it is used. All values are
+/// percentages in the same units as the CPU usage of processes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CgroupCpu {
+    /// The most CPU the cgroup's processes are allowed to use together.
+    pub quota_percent: f64,
+
+    /// How much CPU the cgroup's processes are using together.
+    pub usage_percent: f64,
+
+    /// How much CPU time the cgroup spent throttled since the last update.
+    pub throttled_percent: f64,
+}
+
+impl CgroupCpu {
+    /// How much of the quota is used, where 1.0 is all of it.
+    pub fn usage_ratio(&self) -> f64 {
+        if self.quota_percent > 0.0 {
+            self.usage_percent / self.quota_percent
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether the usage is close enough to the quota to be throttled soon.
+    pub fn is_near_quota(&self) -> bool {
+        self.usage_ratio() > NEAR_QUOTA_RATIO
+    }
+}
+
+/// A cgroup in the hierarchy with the CPU controller, by its path.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Cgroup {
+    /// Whether it's in a cgroup v1 hierarchy rather than the unified one.
+    v1: bool,
+    /// The path relative to the root of the hierarchy, like
+    /// `/system.slice/docker.service`.
+    path: String,
+}
+
+#[cfg(target_os = "linux")]
+impl Cgroup {
+    /// Parses the cgroup with the CPU controller from the contents of
+    /// `/proc/<PID>/cgroup`. A cgroup v1 hierarchy with the CPU controller is
+    /// preferred over the unified one, as the controller can only be in one.
+    pub(crate) fn from_proc_cgroup(contents: &str) -> Option<Cgroup> {
+        let mut unified = None;
+
+        for line in contents.lines() {
+            let mut parts = line.splitn(3, ':');
+            let (Some(id), Some(controllers), Some(path)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+
+            if controllers.split(',').any(|controller| controller == "cpu") {
+                return Some(Cgroup {
+                    v1: true,
+                    path: path.to_string(),
+                });
+            } else if id == "0" && controllers.is_empty() {
+                unified = Some(Cgroup {
+                    v1: false,
+                    path: path.to_string(),
+                });
+            }
+        }
+
+        unified
+    }
+
+    /// The path of the cgroup relative to the root of its hierarchy.
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The directory of the cgroup, given the root of all hierarchies.
+    fn dir(&self, root: &Path) -> PathBuf {
+        let root = if self.v1 {
+            root.join("cpu")
+        } else {
+            root.into()
+        };
+        root.join(self.path.trim_start_matches('/'))
+    }
+
+    /// The cgroup and each of its ancestors, up to and including the root.
+    fn ancestors(&self) -> impl Iterator<Item = Cgroup> + '_ {
+        Path::new(&self.path).ancestors().map(|path| Cgroup {
+            v1: self.v1,
+            path: path.to_string_lossy().into_owned(),
+        })
+    }
+}
+
+/// Reads a value from a `key value` line in a file like `cpu.stat`.
+#[cfg(target_os = "linux")]
+fn stat_value(contents: &str, key: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (line_key, value) = line.split_once(' ')?;
+        (line_key == key).then(|| value.trim().parse().ok())?
+    })
+}
+
+/// Parses the CPU quota of a cgroup v2 `cpu.max` file as a number of CPUs.
+/// This is `max` if there is no quota.
+#[cfg(target_os = "linux")]
+fn parse_cpu_max(contents: &str) -> Option<f64> {
+    let (quota, period) = contents.trim().split_once(' ')?;
+    let quota: f64 = quota.parse().ok()?;
+    let period: f64 = period.parse().ok()?;
+
+    (period > 0.0).then_some(quota / period)
+}
+
+/// Keeps track of the CPU quotas of cgroups, and how much they were throttled.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub(crate) struct CgroupCollector {
+    /// Where all cgroup hierarchies are mounted, usually `/sys/fs/cgroup`.
+    root: PathBuf,
+    /// The total time each quota-limited cgroup spent throttled as of the last
+    /// update.
+    prev_throttled: HashMap<Cgroup, Duration>,
+    last_update: Option<Instant>,
+}
+
+#[cfg(target_os = "linux")]
+impl Default for CgroupCollector {
+    fn default() -> Self {
+        Self::new("/sys/fs/cgroup".into())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl CgroupCollector {
+    pub(crate) fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            prev_throttled: HashMap::default(),
+            last_update: None,
+        }
+    }
+
+    /// Reads the CPU quota of a single cgroup as a number of CPUs, if it has
+    /// one.
+    fn read_quota(&self, cgroup: &Cgroup) -> Option<f64> {
+        let dir = cgroup.dir(&self.root);
+
+        if cgroup.v1 {
+            let quota: f64 = fs::read_to_string(dir.join("cpu.cfs_quota_us"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            let period: f64 = fs::read_to_string(dir.join("cpu.cfs_period_us"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+
+            // A quota of -1 means there is none.
+            (quota > 0.0 && period > 0.0).then_some(quota / period)
+        } else {
+            parse_cpu_max(&fs::read_to_string(dir.join("cpu.max")).ok()?)
+        }
+    }
+
+    /// Reads the total time a cgroup spent throttled.
+    fn read_throttled(&self, cgroup: &Cgroup) -> Option<Duration> {
+        let stat = fs::read_to_string(cgroup.dir(&self.root).join("cpu.stat")).ok()?;
+
+        if cgroup.v1 {
+            stat_value(&stat, "throttled_time").map(Duration::from_nanos)
+        } else {
+            stat_value(&stat, "throttled_usec").map(Duration::from_micros)
+        }
+    }
+
+    /// Finds the cgroup with the lowest CPU quota out of a cgroup and its
+    /// ancestors, as that is the one limiting it, along with the quota.
+    fn limiting_cgroup(&self, cgroup: &Cgroup) -> Option<(Cgroup, f64)> {
+        cgroup
+            .ancestors()
+            .filter_map(|ancestor| {
+                let quota = self.read_quota(&ancestor)?;
+                Some((ancestor, quota))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    /// Returns the CPU quota limiting each of the given cgroups and how much of
+    /// it is used, keyed by their paths. `usage` is the CPU usage of each
+    /// process along with its cgroup, and `percent_per_cpu` is what a CPU's
+    /// worth of usage is as a percentage. Cgroups without a quota are left out.
+    pub(crate) fn update<'a>(
+        &mut self, usage: impl Iterator<Item = (&'a Cgroup, f64)>, percent_per_cpu: f64,
+        now: Instant,
+    ) -> HashMap<Cgroup, CgroupCpu> {
+        let mut limits: HashMap<Cgroup, Option<(Cgroup, f64)>> = HashMap::default();
+        let mut total_usage: HashMap<Cgroup, f64> = HashMap::default();
+
+        for (cgroup, cpu_usage) in usage {
+            if !limits.contains_key(cgroup) {
+                limits.insert(cgroup.clone(), self.limiting_cgroup(cgroup));
+            }
+
+            if let Some(Some((limiting, _))) = limits.get(cgroup) {
+                *total_usage.entry(limiting.clone()).or_default() += cpu_usage;
+            }
+        }
+
+        let elapsed = self
+            .last_update
+            .map(|last_update| now.saturating_duration_since(last_update).as_secs_f64())
+            .unwrap_or_default();
+        let prev_throttled = std::mem::take(&mut self.prev_throttled);
+        self.last_update = Some(now);
+
+        let mut cpu: HashMap<Cgroup, CgroupCpu> = HashMap::default();
+        for (limiting, quota) in limits.values().flatten() {
+            if cpu.contains_key(limiting) {
+                continue;
+            }
+
+            let throttled = self.read_throttled(limiting);
+            let throttled_percent = match (throttled, prev_throttled.get(limiting)) {
+                (Some(throttled), Some(prev)) if elapsed > 0.0 => {
+                    throttled.saturating_sub(*prev).as_secs_f64() / elapsed * percent_per_cpu
+                }
+                _ => 0.0,
+            };
+            if let Some(throttled) = throttled {
+                self.prev_throttled.insert(limiting.clone(), throttled);
+            }
+
+            cpu.insert(
+                limiting.clone(),
+                CgroupCpu {
+                    quota_percent: quota * percent_per_cpu,
+                    usage_percent: total_usage.get(limiting).copied().unwrap_or_default(),
+                    throttled_percent,
+                },
+            );
+        }
+
+        limits
+            .into_iter()
+            .filter_map(|(cgroup, limit)| {
+                let (limiting, _) = limit?;
+                Some((cgroup, *cpu.get(&limiting)?))
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::*;
+
+    fn cgroup(v1: bool, path: &str) -> Cgroup {
+        Cgroup {
+            v1,
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_proc_cgroup() {
+        assert_eq!(
+            Cgroup::from_proc_cgroup("0::/system.slice/docker-abc.scope\n"),
+            Some(cgroup(false, "/system.slice/docker-abc.scope"))
+        );
+        assert_eq!(
+            Cgroup::from_proc_cgroup(
+                "12:memory:/docker/abc\n4:cpu,cpuacct:/docker/abc\n0::/docker/abc\n"
+            ),
+            Some(cgroup(true, "/docker/abc"))
+        );
+        assert_eq!(
+            Cgroup::from_proc_cgroup("1:name=systemd:/init.scope\n"),
+            None
+        );
+        assert_eq!(Cgroup::from_proc_cgroup(""), None);
+    }
+
+    #[test]
+    fn parse_cpu_files() {
+        assert_eq!(parse_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cpu_max("150000 100000\n"), Some(1.5));
+
+        let stat = "usage_usec 100\nthrottled_usec 2500\nnr_throttled 3\n";
+        assert_eq!(stat_value(stat, "throttled_usec"), Some(2500));
+        assert_eq!(stat_value(stat, "throttled_time"), None);
+    }
+
+    #[test]
+    fn quota_and_throttling() {
+        let root = tempfile::tempdir().unwrap();
+        let write = |path: &str, contents: &str| {
+            let path = root.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        };
+
+        // The slice limits the service more than the service does itself.
+        write("limited.slice/cpu.max", "100000 100000\n");
+        write("limited.slice/cpu.stat", "throttled_usec 0\n");
+        write("limited.slice/web.service/cpu.max", "200000 100000\n");
+        write("unlimited.slice/cpu.max", "max 100000\n");
+
+        let web = cgroup(false, "/limited.slice/web.service");
+        let slice = cgroup(false, "/limited.slice");
+        let unlimited = cgroup(false, "/unlimited.slice");
+
+        let mut collector = CgroupCollector::new(root.path().into());
+        let now = Instant::now();
+        let limits = collector.update(
+            [(&web, 50.0), (&slice, 40.0), (&unlimited, 10.0)].into_iter(),
+            100.0,
+            now,
+        );
+
+        assert_eq!(limits.get(&unlimited), None);
+        let cpu = &limits[&web];
+        assert_eq!(cpu.quota_percent, 100.0);
+        assert_eq!(cpu.usage_percent, 90.0);
+        assert_eq!(cpu.throttled_percent, 0.0);
+        assert!(!cpu.is_near_quota());
+        assert_eq!(limits[&slice], limits[&web]);
+
+        // Throttled for a quarter of the two seconds since.
+        write("limited.slice/cpu.stat", "throttled_usec 500000\n");
+        let limits = collector.update(
+            [(&web, 95.0)].into_iter(),
+            100.0,
+            now + Duration::from_secs(2),
+        );
+        let cpu = &limits[&web];
+        assert_eq!(cpu.throttled_percent, 25.0);
+        assert!(cpu.is_near_quota());
+    }
+}
@@ -0,0 +1,209 @@
+//! Finding which container a process is in from its cgroup. This is only
+//! supported on Linux.
+//!
+//! Docker and containerd both put each container in its own cgroup, named
+//! after the container's ID:
+//!
+//! - Docker uses `/docker/<ID>`, or `/system.slice/docker-<ID>.scope` with
+//!   the systemd cgroup driver.
+//! - containerd uses `/<namespace>/<ID>`, or `cri-containerd-<ID>.scope` when
+//!   run by Kubernetes with the systemd cgroup driver.
+//!
+//! Docker's container names are read from its state directory, which usually
+//! needs root. Otherwise, and for containerd, the short ID is shown instead.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use hashbrown::HashMap;
+
+use super::processes::Pid;
+
+/// How many characters of a container's ID are shown without its name, as in
+/// `docker ps`.
+const SHORT_ID_LEN: usize = 12;
+
+/// The container runtimes that can be recognised from cgroup paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Runtime {
+    Docker,
+    Containerd,
+}
+
+/// Finds the runtime and ID of the container a cgroup belongs to, if any. The
+/// innermost match is used, for containers running inside others.
+fn container_id(cgroup_path: &str) -> Option<(Runtime, &str)> {
+    let components = cgroup_path
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>();
+
+    components
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(index, component)| {
+            let scope = component.strip_suffix(".scope").unwrap_or(component);
+            let (runtime, id) = if let Some(id) = scope.strip_prefix("docker-") {
+                (Runtime::Docker, id)
+            } else if let Some(id) = scope.strip_prefix("cri-containerd-") {
+                (Runtime::Containerd, id)
+            } else if index > 0 && components[index - 1] == "docker" {
+                (Runtime::Docker, scope)
+            } else {
+                // Any other namespace, including Kubernetes' pod cgroups.
+                (Runtime::Containerd, scope)
+            };
+
+            is_container_id(id).then_some((runtime, id))
+        })
+}
+
+/// Whether this looks like a container ID, which is 64 hex digits.
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+fn short_id(id: &str) -> String {
+    id[..SHORT_ID_LEN].to_string()
+}
+
+/// Finds the names of the containers processes are in, caching them so each
+/// container's name is only looked up once.
+#[derive(Debug)]
+pub(crate) struct ContainerNames {
+    /// Where Docker keeps the state of its containers, usually
+    /// `/var/lib/docker/containers`.
+    docker_root: PathBuf,
+    /// The cgroup path each process was last seen in, and its container.
+    by_pid: HashMap<Pid, (String, Option<String>)>,
+    /// The name of each Docker container by its ID, or the short ID if it
+    /// couldn't be read.
+    docker_names: HashMap<String, String>,
+}
+
+impl Default for ContainerNames {
+    fn default() -> Self {
+        Self::new("/var/lib/docker/containers".into())
+    }
+}
+
+impl ContainerNames {
+    pub(crate) fn new(docker_root: PathBuf) -> Self {
+        Self {
+            docker_root,
+            by_pid: HashMap::default(),
+            docker_names: HashMap::default(),
+        }
+    }
+
+    /// Returns the name of the container that a process is in, given the path
+    /// of its cgroup, or its short ID if the name isn't known.
+    pub(crate) fn pid_to_container_name(&mut self, pid: Pid, cgroup_path: &str) -> Option<String> {
+        if let Some((path, name)) = self.by_pid.get(&pid) {
+            if path == cgroup_path {
+                return name.clone();
+            }
+        }
+
+        let name = container_id(cgroup_path).map(|(runtime, id)| match runtime {
+            Runtime::Docker => self
+                .docker_names
+                .entry(id.to_string())
+                .or_insert_with(|| read_docker_name(&self.docker_root, id))
+                .clone(),
+            Runtime::Containerd => short_id(id),
+        });
+        self.by_pid
+            .insert(pid, (cgroup_path.to_string(), name.clone()));
+
+        name
+    }
+
+    /// Forgets a process that no longer exists.
+    pub(crate) fn remove(&mut self, pid: &Pid) {
+        self.by_pid.remove(pid);
+    }
+}
+
+/// Reads the name of a Docker container from its config, falling back to its
+/// short ID.
+fn read_docker_name(docker_root: &Path, id: &str) -> String {
+    fs::read_to_string(docker_root.join(id).join("config.v2.json"))
+        .ok()
+        .and_then(|config| {
+            let config: serde_json::Value = serde_json::from_str(&config).ok()?;
+            let name = config.get("Name")?.as_str()?.trim_start_matches('/');
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .unwrap_or_else(|| short_id(id))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ID: &str = "3f4e8b2a9c1d7e6f5a4b3c2d1e0f9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f";
+
+    #[test]
+    fn container_ids() {
+        assert_eq!(
+            container_id(&format!("/docker/{ID}")),
+            Some((Runtime::Docker, ID))
+        );
+        assert_eq!(
+            container_id(&format!("/system.slice/docker-{ID}.scope")),
+            Some((Runtime::Docker, ID))
+        );
+        assert_eq!(
+            container_id(&format!("/k8s.io/{ID}")),
+            Some((Runtime::Containerd, ID))
+        );
+        assert_eq!(
+            container_id(&format!(
+                "/kubepods.slice/kubepods-besteffort.slice/cri-containerd-{ID}.scope"
+            )),
+            Some((Runtime::Containerd, ID))
+        );
+        assert_eq!(container_id("/system.slice/docker.service"), None);
+        assert_eq!(container_id("/user.slice/user-1000.slice"), None);
+        assert_eq!(container_id("/"), None);
+    }
+
+    #[test]
+    fn names() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = ID.replace('3', "4");
+        fs::create_dir(dir.path().join(ID)).unwrap();
+        fs::write(
+            dir.path().join(ID).join("config.v2.json"),
+            r#"{"ID": "ignored", "Name": "/web"}"#,
+        )
+        .unwrap();
+
+        let mut names = ContainerNames::new(dir.path().into());
+        assert_eq!(
+            names.pid_to_container_name(1, &format!("/docker/{ID}")),
+            Some("web".to_string())
+        );
+        // Without a config, the short ID is used instead.
+        assert_eq!(
+            names.pid_to_container_name(2, &format!("/docker/{other}")),
+            Some(other[..12].to_string())
+        );
+        assert_eq!(
+            names.pid_to_container_name(3, &format!("/k8s.io/{ID}")),
+            Some(ID[..12].to_string())
+        );
+        assert_eq!(names.pid_to_container_name(4, "/init.scope"), None);
+
+        // Names are cached, even if the config changes.
+        fs::remove_dir_all(dir.path().join(ID)).unwrap();
+        assert_eq!(
+            names.pid_to_container_name(5, &format!("/docker/{ID}")),
+            Some("web".to_string())
+        );
+    }
+}
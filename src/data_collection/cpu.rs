@@ -3,18 +3,42 @@
 pub mod sysinfo;
 pub use self::sysinfo::*;
 
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+use serde::{Deserialize, Serialize};
+
 pub type LoadAvgHarvest = [f32; 3];
 
-#[derive(Debug, Clone, Copy)]
+/// How many tasks are runnable and how many exist in total, as counted by the
+/// scheduler. Only collected on Linux.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchedTasksHarvest {
+    pub running: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum CpuDataType {
     Avg,
     Cpu(usize),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuData {
     pub data_type: CpuDataType,
     pub cpu_usage: f64,
 }
 
 pub type CpuHarvest = Vec<CpuData>;
+
+/// The percentage of CPU time spent in each state over the last interval,
+/// across all CPUs.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CpuTimesHarvest {
+    pub user: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub steal: f64,
+}
@@ -0,0 +1,161 @@
+//! Linux-specific CPU data collection, which reads `/proc/stat` to break down
+//! what the CPU time was spent on, and `/proc/loadavg` for the load averages.
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+};
+
+use super::{CpuTimesHarvest, LoadAvgHarvest, SchedTasksHarvest};
+
+/// The cumulative time spent by all CPUs in each state since boot, in clock
+/// ticks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTimes {
+    user: f64,
+    system: f64,
+    idle: f64,
+    iowait: f64,
+    steal: f64,
+}
+
+impl CpuTimes {
+    /// Parses the aggregate `cpu` line of `/proc/stat`.
+    fn from_line(line: &str) -> Option<Self> {
+        let mut values = line
+            .strip_prefix("cpu ")?
+            .split_whitespace()
+            .map(|value| value.parse::<f64>().unwrap_or(0.0));
+        let mut next = || values.next().unwrap_or(0.0);
+
+        let user = next();
+        let nice = next();
+        let system = next();
+        let idle = next();
+        let iowait = next();
+        let irq = next();
+        let softirq = next();
+        let steal = next();
+
+        // Like with processes, guest/guest_nice are skipped as they're already
+        // counted as part of user/nice.
+        Some(Self {
+            user: user + nice,
+            system: system + irq + softirq,
+            idle,
+            iowait,
+            steal,
+        })
+    }
+
+    /// Reads the current CPU times.
+    pub fn read() -> Option<Self> {
+        let mut line = String::new();
+        BufReader::new(File::open("/proc/stat").ok()?)
+            .read_line(&mut line)
+            .ok()?;
+
+        Self::from_line(&line)
+    }
+
+    fn total(&self) -> f64 {
+        self.user + self.system + self.idle + self.iowait + self.steal
+    }
+
+    /// Returns what percentage of the time between `prev` and these times was
+    /// spent in each state.
+    pub fn usage_since(&self, prev: &Self) -> CpuTimesHarvest {
+        let total = self.total() - prev.total();
+        let percent = |current: f64, prev: f64| {
+            if total > 0.0 {
+                ((current - prev) / total * 100.0).clamp(0.0, 100.0)
+            } else {
+                0.0
+            }
+        };
+
+        CpuTimesHarvest {
+            user: percent(self.user, prev.user),
+            system: percent(self.system, prev.system),
+            idle: percent(self.idle, prev.idle),
+            iowait: percent(self.iowait, prev.iowait),
+            steal: percent(self.steal, prev.steal),
+        }
+    }
+}
+
+/// Parses `/proc/loadavg`, e.g. `0.52 0.61 0.70 2/1234 56789`, into the 1, 5,
+/// and 15 minute load averages and the running and total task counts.
+fn parse_load_avg(text: &str) -> Option<(LoadAvgHarvest, SchedTasksHarvest)> {
+    let mut fields = text.split_whitespace();
+    let mut next_load = || fields.next()?.parse::<f32>().ok();
+    let load_avg = [next_load()?, next_load()?, next_load()?];
+
+    let (running, total) = fields.next()?.split_once('/')?;
+    let tasks = SchedTasksHarvest {
+        running: running.parse().ok()?,
+        total: total.parse().ok()?,
+    };
+
+    Some((load_avg, tasks))
+}
+
+/// Reads the current load averages and task counts.
+pub fn read_load_avg() -> Option<(LoadAvgHarvest, SchedTasksHarvest)> {
+    parse_load_avg(&fs::read_to_string("/proc/loadavg").ok()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_loadavg() {
+        assert_eq!(
+            parse_load_avg("0.52 0.61 0.70 2/1234 56789\n"),
+            Some((
+                [0.52, 0.61, 0.70],
+                SchedTasksHarvest {
+                    running: 2,
+                    total: 1234,
+                }
+            ))
+        );
+
+        assert_eq!(parse_load_avg("0.52 0.61"), None);
+        assert_eq!(parse_load_avg("0.52 0.61 0.70 1234"), None);
+    }
+
+    #[test]
+    fn parse_stat_line() {
+        let times = CpuTimes::from_line("cpu  100 20 30 400 5 6 7 8 9 10\n").unwrap();
+        assert_eq!(
+            times,
+            CpuTimes {
+                user: 120.0,
+                system: 43.0,
+                idle: 400.0,
+                iowait: 5.0,
+                steal: 8.0,
+            }
+        );
+
+        assert!(CpuTimes::from_line("cpu0 100 20 30 400 5 6 7 8 9 10").is_none());
+    }
+
+    #[test]
+    fn usage_between_reads() {
+        let prev = CpuTimes::from_line("cpu 100 0 100 700 50 0 0 50").unwrap();
+        let current = CpuTimes::from_line("cpu 150 0 120 820 55 0 0 55").unwrap();
+        let usage = current.usage_since(&prev);
+
+        assert_eq!(usage.user, 25.0);
+        assert_eq!(usage.system, 10.0);
+        assert_eq!(usage.idle, 60.0);
+        assert_eq!(usage.iowait, 2.5);
+        assert_eq!(usage.steal, 2.5);
+
+        // No time passing shouldn't divide by zero.
+        assert_eq!(current.usage_since(&current).idle, 0.0);
+    }
+}
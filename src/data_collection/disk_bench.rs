@@ -0,0 +1,187 @@
+//! A sequential read benchmark of a disk, for `--disk_bench`. The device is
+//! only ever opened for reading, and what's read is thrown away.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read},
+    time::{Duration, Instant},
+};
+
+/// How long the benchmark reads for.
+pub const BENCHMARK_DURATION: Duration = Duration::from_secs(5);
+
+/// How much is read at a time.
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// What the buffer is aligned to, as `O_DIRECT` needs reads into memory that's
+/// aligned to the device's block size.
+const ALIGNMENT: usize = 4096;
+
+/// How often progress is reported while the benchmark runs.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How far a benchmark has gotten, or how it ended.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BenchmarkResult {
+    pub device: String,
+    pub bytes_read: u64,
+    pub elapsed: Duration,
+    /// Whether reads bypassed the page cache with `O_DIRECT`. Otherwise, parts
+    /// of the device that were cached are read from memory instead.
+    pub is_direct: bool,
+    /// Whether the benchmark is over, as opposed to a report of its progress.
+    pub is_finished: bool,
+    /// Why the benchmark was stopped early, if it was.
+    pub error: Option<String>,
+}
+
+impl BenchmarkResult {
+    /// How many bytes were read per second.
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.bytes_read as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Opens the device for reading, bypassing the page cache where it can.
+fn open_device(device: &str) -> io::Result<(File, bool)> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        if let Ok(file) = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(device)
+        {
+            return Ok((file, true));
+        }
+    }
+
+    OpenOptions::new()
+        .read(true)
+        .open(device)
+        .map(|file| (file, false))
+}
+
+/// Reads the device sequentially for [`BENCHMARK_DURATION`], or until its end.
+/// The disk widget uses [`run_benchmark_with_progress`] instead, to show how
+/// far it's gotten.
+#[allow(dead_code)]
+pub fn run_benchmark(device: &str) -> BenchmarkResult {
+    run_benchmark_with_progress(device, BENCHMARK_DURATION, |_| {})
+}
+
+/// Reads the device sequentially for `duration`, or until its end, calling
+/// `on_progress` with how far it's gotten every so often.
+pub fn run_benchmark_with_progress(
+    device: &str, duration: Duration, mut on_progress: impl FnMut(&BenchmarkResult),
+) -> BenchmarkResult {
+    let mut result = BenchmarkResult {
+        device: device.to_string(),
+        ..Default::default()
+    };
+
+    let (mut file, is_direct) = match open_device(device) {
+        Ok(opened) => opened,
+        Err(err) => {
+            result.is_finished = true;
+            result.error = Some(err.to_string());
+            return result;
+        }
+    };
+    result.is_direct = is_direct;
+
+    let mut buffer = vec![0; BLOCK_SIZE + ALIGNMENT];
+    let offset = buffer.as_ptr().align_offset(ALIGNMENT);
+    let block = &mut buffer[offset..offset + BLOCK_SIZE];
+
+    let start = Instant::now();
+    let mut last_progress = start;
+    while start.elapsed() < duration {
+        match file.read(block) {
+            Ok(0) => break,
+            Ok(read) => result.bytes_read += read as u64,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            // Some files can be opened with O_DIRECT but not read with it,
+            // like those on tmpfs, so read them through the page cache.
+            Err(err)
+                if result.is_direct
+                    && result.bytes_read == 0
+                    && err.kind() == io::ErrorKind::InvalidInput =>
+            {
+                match OpenOptions::new().read(true).open(device) {
+                    Ok(buffered) => {
+                        file = buffered;
+                        result.is_direct = false;
+                    }
+                    Err(err) => {
+                        result.error = Some(err.to_string());
+                        break;
+                    }
+                }
+            }
+            Err(err) => {
+                result.error = Some(err.to_string());
+                break;
+            }
+        }
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            result.elapsed = start.elapsed();
+            on_progress(&result);
+        }
+    }
+
+    result.elapsed = start.elapsed();
+    result.is_finished = true;
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn reads_whole_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&vec![1; 3 * BLOCK_SIZE]).unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_str().unwrap();
+
+        let result = run_benchmark(path);
+        assert_eq!(result.error, None);
+        assert_eq!(result.bytes_read, 3 * BLOCK_SIZE as u64);
+        assert!(result.is_finished);
+        assert!(result.elapsed < BENCHMARK_DURATION);
+
+        // The file is only ever read.
+        assert_eq!(std::fs::read(path).unwrap(), vec![1; 3 * BLOCK_SIZE]);
+    }
+
+    #[test]
+    fn missing_device() {
+        let result = run_benchmark("/this/does/not/exist");
+        assert!(result.is_finished);
+        assert!(result.error.is_some());
+        assert_eq!(result.bytes_read, 0);
+        assert_eq!(result.throughput(), 0.0);
+    }
+
+    #[test]
+    fn throughput() {
+        let result = BenchmarkResult {
+            bytes_read: 500,
+            elapsed: Duration::from_millis(250),
+            ..Default::default()
+        };
+        assert_eq!(result.throughput(), 2000.0);
+    }
+}
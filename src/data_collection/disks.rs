@@ -14,9 +14,11 @@ cfg_if! {
         mod windows;
         pub(crate) use self::windows::*;
     } else if #[cfg(target_os = "linux")] {
+        mod smart;
         mod unix;
         #[cfg(feature = "zfs")]
         mod zfs_io_counters;
+        pub(crate) use self::smart::get_disk_endurance;
         pub(crate) use self::unix::*;
     } else if #[cfg(target_os = "macos")] {
         mod unix;
@@ -29,10 +31,11 @@ cfg_if! {
 
 use cfg_if::cfg_if;
 use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
 use crate::app::filter::Filter;
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct DiskHarvest {
     pub name: String,
     pub mount_point: String,
@@ -47,7 +50,26 @@ pub struct DiskHarvest {
     pub total_space: Option<u64>,
 }
 
-#[derive(Clone, Debug)]
+/// How worn out an SSD is, from its SMART data. This is only collected on
+/// Linux.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SmartEndurance {
+    /// How much the host has written to the drive, in terabytes.
+    pub host_written_tb: f64,
+
+    /// How much was actually written to the NAND, in terabytes, if the drive
+    /// reports it. NVMe drives don't.
+    pub nand_written_tb: Option<f64>,
+
+    /// The write amplification factor, which is how many bytes are written
+    /// to the NAND for each byte the host writes.
+    pub waf: Option<f64>,
+
+    /// How much of the drive's rated endurance is left, in percent.
+    pub remaining_life_pct: Option<u8>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IoData {
     pub read_bytes: u64,
     pub write_bytes: u64,
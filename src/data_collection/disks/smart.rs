@@ -0,0 +1,500 @@
+//! Reads how worn out SSDs are from their SMART data, using the NVMe SMART log
+//! for NVMe drives, and ATA commands sent through `SG_IO` for SATA drives.
+//!
+//! Both need access to the drive's device file, which usually means running as
+//! root. Hard drives are skipped, as they don't wear out in the same way, and
+//! reading their SMART data could wake them up.
+
+use std::{fs, fs::File, os::fd::AsRawFd, path::Path};
+
+use hashbrown::HashMap;
+
+use super::{DiskHarvest, SmartEndurance};
+
+/// `_IOWR('N', 0x41, struct nvme_admin_cmd)`.
+const NVME_IOCTL_ADMIN_CMD: u64 = 0xC048_4E41;
+const NVME_GET_LOG_PAGE: u8 = 0x02;
+const NVME_LOG_SMART: u32 = 0x02;
+
+const SG_IO: u64 = 0x2285;
+const SG_DXFER_FROM_DEV: i32 = -3;
+
+const ATA_PASS_THROUGH_16: u8 = 0x85;
+const ATA_IDENTIFY_DEVICE: u8 = 0xEC;
+const ATA_SMART: u8 = 0xB0;
+const ATA_SMART_READ_DATA: u8 = 0xD0;
+
+/// The size of the NVMe SMART log, and of ATA data blocks.
+const BLOCK_LEN: usize = 512;
+
+const GIB: u64 = 1 << 30;
+
+/// `struct nvme_admin_cmd` from `linux/nvme_ioctl.h`.
+#[repr(C)]
+#[derive(Default)]
+struct NvmeAdminCmd {
+    opcode: u8,
+    flags: u8,
+    rsvd1: u16,
+    nsid: u32,
+    cdw2: u32,
+    cdw3: u32,
+    metadata: u64,
+    addr: u64,
+    metadata_len: u32,
+    data_len: u32,
+    cdw10: u32,
+    cdw11: u32,
+    cdw12: u32,
+    cdw13: u32,
+    cdw14: u32,
+    cdw15: u32,
+    timeout_ms: u32,
+    result: u32,
+}
+
+/// `struct sg_io_hdr` from `scsi/sg.h`.
+#[repr(C)]
+struct SgIoHdr {
+    interface_id: i32,
+    dxfer_direction: i32,
+    cmd_len: u8,
+    mx_sb_len: u8,
+    iovec_count: u16,
+    dxfer_len: u32,
+    dxferp: *mut libc::c_void,
+    cmdp: *mut u8,
+    sbp: *mut u8,
+    timeout: u32,
+    flags: u32,
+    pack_id: i32,
+    usr_ptr: *mut libc::c_void,
+    status: u8,
+    masked_status: u8,
+    msg_status: u8,
+    sb_len_wr: u8,
+    host_status: u16,
+    driver_status: u16,
+    resid: i32,
+    duration: u32,
+    info: u32,
+}
+
+/// A SMART counter, as the attribute it's in and the bytes each unit of it is.
+#[derive(Debug, Clone, Copy)]
+struct Counter {
+    id: u8,
+    unit: u64,
+}
+
+/// How a vendor's drives count what was written to their NAND.
+#[derive(Debug, Clone, Copy)]
+enum NandWrites {
+    /// They don't.
+    Unknown,
+    /// In a counter of its own.
+    Counter(Counter),
+    /// As the pages written for the host and by the flash translation layer,
+    /// whose ratio is the write amplification.
+    Pages { host: u8, ftl: u8 },
+}
+
+/// The SMART attributes a vendor's SATA SSDs use.
+#[derive(Debug, Clone, Copy)]
+struct VendorAttributes {
+    /// How the model names of the vendor's drives start.
+    models: &'static [&'static str],
+    host_writes: Counter,
+    nand_writes: NandWrites,
+    /// The attribute whose normalized value is the percentage of life left.
+    life_left: Option<u8>,
+}
+
+/// The host writes in LBAs, as most drives count them.
+const LBAS_WRITTEN: Counter = Counter { id: 241, unit: 512 };
+
+/// The attributes of each vendor, as SMART attribute numbering isn't
+/// standardized. Drives not listed get [`DEFAULT_ATTRIBUTES`].
+const VENDOR_ATTRIBUTES: &[VendorAttributes] = &[
+    VendorAttributes {
+        models: &["INTEL", "SSDSC"],
+        host_writes: Counter {
+            id: 241,
+            unit: 32 << 20,
+        },
+        nand_writes: NandWrites::Counter(Counter { id: 249, unit: GIB }),
+        life_left: Some(233),
+    },
+    VendorAttributes {
+        models: &["KINGSTON", "SandForce"],
+        host_writes: Counter { id: 241, unit: GIB },
+        nand_writes: NandWrites::Counter(Counter { id: 233, unit: GIB }),
+        life_left: Some(231),
+    },
+    VendorAttributes {
+        models: &["SanDisk"],
+        host_writes: Counter { id: 241, unit: GIB },
+        nand_writes: NandWrites::Counter(Counter { id: 233, unit: GIB }),
+        life_left: None,
+    },
+    VendorAttributes {
+        models: &["Crucial", "CT", "Micron", "MTFD"],
+        host_writes: Counter { id: 246, unit: 512 },
+        nand_writes: NandWrites::Pages {
+            host: 247,
+            ftl: 248,
+        },
+        life_left: Some(202),
+    },
+    VendorAttributes {
+        models: &["Samsung"],
+        host_writes: LBAS_WRITTEN,
+        nand_writes: NandWrites::Unknown,
+        life_left: Some(177),
+    },
+];
+
+const DEFAULT_ATTRIBUTES: VendorAttributes = VendorAttributes {
+    models: &[],
+    host_writes: LBAS_WRITTEN,
+    nand_writes: NandWrites::Unknown,
+    life_left: Some(231),
+};
+
+/// Picks the attributes to read for a drive by its model name.
+fn vendor_attributes(model: &str) -> &'static VendorAttributes {
+    VENDOR_ATTRIBUTES
+        .iter()
+        .find(|vendor| vendor.models.iter().any(|prefix| model.starts_with(prefix)))
+        .unwrap_or(&DEFAULT_ATTRIBUTES)
+}
+
+/// A single entry of the SMART attribute table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Attribute {
+    id: u8,
+    value: u8,
+    raw: u64,
+}
+
+/// Parses the attribute table of the data returned by `SMART READ DATA`,
+/// which has 30 entries of 12 bytes after a 2 byte version.
+fn parse_attributes(data: &[u8]) -> Vec<Attribute> {
+    data.get(2..362)
+        .unwrap_or_default()
+        .chunks_exact(12)
+        .filter(|entry| entry[0] != 0)
+        .map(|entry| {
+            let mut raw = [0; 8];
+            raw[..6].copy_from_slice(&entry[5..11]);
+
+            Attribute {
+                id: entry[0],
+                value: entry[3],
+                raw: u64::from_le_bytes(raw),
+            }
+        })
+        .collect()
+}
+
+/// Gets the model name from the data returned by `IDENTIFY DEVICE`, which is
+/// in words 27 to 46 with the bytes of each word swapped.
+fn parse_model(identify: &[u8]) -> String {
+    let model = identify
+        .get(54..94)
+        .unwrap_or_default()
+        .chunks_exact(2)
+        .flat_map(|word| [word[1], word[0]])
+        .collect::<Vec<_>>();
+
+    String::from_utf8_lossy(&model)
+        .trim_matches(|c: char| c == ' ' || c == '\0')
+        .to_string()
+}
+
+fn to_tb(bytes: f64) -> f64 {
+    bytes / 1e12
+}
+
+/// Works out the endurance of a SATA drive from its SMART attributes.
+fn sata_endurance(model: &str, attributes: &[Attribute]) -> Option<SmartEndurance> {
+    let vendor = vendor_attributes(model);
+    let find = |id: u8| attributes.iter().find(|attribute| attribute.id == id);
+    // Raw values are up to 48 bits, which can overflow a u64 once multiplied
+    // by units of a GiB, so bytes are counted as floats.
+    let count = |counter: Counter| {
+        find(counter.id).map(|attribute| attribute.raw as f64 * counter.unit as f64)
+    };
+
+    let host_bytes = count(vendor.host_writes)?;
+    let nand_bytes = match vendor.nand_writes {
+        NandWrites::Unknown => None,
+        NandWrites::Counter(counter) => count(counter),
+        NandWrites::Pages { host, ftl } => {
+            let (host, ftl) = (find(host)?.raw as f64, find(ftl)?.raw as f64);
+            (host > 0.0).then(|| host_bytes * (host + ftl) / host)
+        }
+    };
+
+    Some(SmartEndurance {
+        host_written_tb: to_tb(host_bytes),
+        nand_written_tb: nand_bytes.map(to_tb),
+        waf: nand_bytes
+            .filter(|_| host_bytes > 0.0)
+            .map(|nand_bytes| nand_bytes / host_bytes),
+        remaining_life_pct: vendor
+            .life_left
+            .and_then(find)
+            .map(|attribute| attribute.value.min(100)),
+    })
+}
+
+/// Works out the endurance of an NVMe drive from its SMART log. The data
+/// written is counted in units of 1000 blocks of 512 bytes, and there's no
+/// standard count of NAND writes.
+fn nvme_endurance(log: &[u8]) -> Option<SmartEndurance> {
+    let percentage_used = *log.get(5)?;
+    let units_written = u128::from_le_bytes(log.get(48..64)?.try_into().ok()?);
+
+    Some(SmartEndurance {
+        host_written_tb: to_tb(units_written as f64 * 512_000.0),
+        nand_written_tb: None,
+        waf: None,
+        remaining_life_pct: Some(100 - percentage_used.min(100)),
+    })
+}
+
+/// Reads the SMART log of an NVMe drive.
+fn read_nvme_log(device: &File) -> Option<[u8; BLOCK_LEN]> {
+    let mut log = [0u8; BLOCK_LEN];
+    let mut cmd = NvmeAdminCmd {
+        opcode: NVME_GET_LOG_PAGE,
+        nsid: u32::MAX,
+        addr: log.as_mut_ptr() as u64,
+        data_len: BLOCK_LEN as u32,
+        cdw10: ((BLOCK_LEN as u32 / 4 - 1) << 16) | NVME_LOG_SMART,
+        ..Default::default()
+    };
+
+    // SAFETY: The command points to a buffer of the length it gives.
+    let result = unsafe { libc::ioctl(device.as_raw_fd(), NVME_IOCTL_ADMIN_CMD as _, &mut cmd) };
+    (result == 0).then_some(log)
+}
+
+/// Sends an ATA command that reads a block from the drive through `SG_IO`.
+fn read_ata_block(
+    device: &File, command: u8, features: u8, lba: [u8; 3],
+) -> Option<[u8; BLOCK_LEN]> {
+    let mut data = [0u8; BLOCK_LEN];
+    let mut sense = [0u8; 32];
+
+    // PIO data-in, reading one block, with the length in the sector count.
+    let mut cdb = [0u8; 16];
+    cdb[0] = ATA_PASS_THROUGH_16;
+    cdb[1] = 4 << 1;
+    cdb[2] = 0x0E;
+    cdb[4] = features;
+    cdb[6] = 1;
+    cdb[8] = lba[0];
+    cdb[10] = lba[1];
+    cdb[12] = lba[2];
+    cdb[14] = command;
+
+    let mut header = SgIoHdr {
+        interface_id: i32::from(b'S'),
+        dxfer_direction: SG_DXFER_FROM_DEV,
+        cmd_len: cdb.len() as u8,
+        mx_sb_len: sense.len() as u8,
+        iovec_count: 0,
+        dxfer_len: BLOCK_LEN as u32,
+        dxferp: data.as_mut_ptr().cast(),
+        cmdp: cdb.as_mut_ptr(),
+        sbp: sense.as_mut_ptr(),
+        timeout: 5000,
+        flags: 0,
+        pack_id: 0,
+        usr_ptr: std::ptr::null_mut(),
+        status: 0,
+        masked_status: 0,
+        msg_status: 0,
+        sb_len_wr: 0,
+        host_status: 0,
+        driver_status: 0,
+        resid: 0,
+        duration: 0,
+        info: 0,
+    };
+
+    // SAFETY: The header points to buffers of the lengths it gives.
+    let result = unsafe { libc::ioctl(device.as_raw_fd(), SG_IO as _, &mut header) };
+    let is_ok =
+        result == 0 && header.status == 0 && header.host_status == 0 && header.driver_status == 0;
+
+    is_ok.then_some(data)
+}
+
+fn read_sata_endurance(device: &File) -> Option<SmartEndurance> {
+    let identify = read_ata_block(device, ATA_IDENTIFY_DEVICE, 0, [0; 3])?;
+    let smart = read_ata_block(device, ATA_SMART, ATA_SMART_READ_DATA, [0, 0x4F, 0xC2])?;
+
+    sata_endurance(&parse_model(&identify), &parse_attributes(&smart))
+}
+
+/// Gets the whole disk a block device is on, e.g. `nvme0n1` for
+/// `/dev/nvme0n1p2`.
+fn whole_disk(name: &str) -> Option<String> {
+    let name = name.strip_prefix("/dev/")?;
+    let path = Path::new("/sys/class/block").join(name);
+
+    if path.join("partition").exists() {
+        let parent = fs::canonicalize(path).ok()?.parent()?.to_path_buf();
+        Some(parent.file_name()?.to_str()?.to_string())
+    } else {
+        path.exists().then(|| name.to_string())
+    }
+}
+
+/// Reads the endurance of the SSD that a whole disk is.
+fn read_endurance(disk: &str) -> Option<SmartEndurance> {
+    let rotational = fs::read_to_string(format!("/sys/block/{disk}/queue/rotational")).ok()?;
+    if rotational.trim() != "0" {
+        return None;
+    }
+
+    let device = File::open(format!("/dev/{disk}")).ok()?;
+    if disk.starts_with("nvme") {
+        nvme_endurance(&read_nvme_log(&device)?)
+    } else if disk.starts_with("sd") {
+        read_sata_endurance(&device)
+    } else {
+        None
+    }
+}
+
+/// Reads the endurance of the SSDs the disks are on, by disk name. Disks
+/// whose SMART data can't be read are left out.
+pub(crate) fn get_disk_endurance(disks: &[DiskHarvest]) -> HashMap<String, SmartEndurance> {
+    let mut by_disk: HashMap<String, Option<SmartEndurance>> = HashMap::new();
+
+    disks
+        .iter()
+        .filter_map(|harvest| {
+            let disk = whole_disk(&harvest.name)?;
+            let endurance = by_disk
+                .entry(disk)
+                .or_insert_with_key(|disk| read_endurance(disk));
+
+            Some((harvest.name.clone(), (*endurance)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn smart_data(attributes: &[(u8, u8, u64)]) -> Vec<u8> {
+        let mut data = vec![0; BLOCK_LEN];
+        for (itx, (id, value, raw)) in attributes.iter().enumerate() {
+            let entry = &mut data[2 + itx * 12..2 + (itx + 1) * 12];
+            entry[0] = *id;
+            entry[3] = *value;
+            entry[5..11].copy_from_slice(&raw.to_le_bytes()[..6]);
+        }
+
+        data
+    }
+
+    #[test]
+    fn attributes() {
+        let data = smart_data(&[(241, 100, 0x0102_0304_0506), (177, 95, 10)]);
+        assert_eq!(
+            parse_attributes(&data),
+            vec![
+                Attribute {
+                    id: 241,
+                    value: 100,
+                    raw: 0x0102_0304_0506
+                },
+                Attribute {
+                    id: 177,
+                    value: 95,
+                    raw: 10
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn model() {
+        let mut identify = vec![0; BLOCK_LEN];
+        identify[54..64].copy_from_slice(b"aSsmnu g  ");
+
+        assert_eq!(parse_model(&identify), "Samsung");
+    }
+
+    #[test]
+    fn vendors() {
+        assert_eq!(
+            vendor_attributes("INTEL SSDSC2BB480G4").host_writes.unit,
+            32 << 20
+        );
+        assert!(matches!(
+            vendor_attributes("CT500MX500SSD1").nand_writes,
+            NandWrites::Pages { .. }
+        ));
+        assert_eq!(vendor_attributes("Some SSD").life_left, Some(231));
+    }
+
+    #[test]
+    fn sata() {
+        // SandForce drives count both in GiB.
+        let data = smart_data(&[(241, 100, 1000), (233, 100, 1500), (231, 93, 0)]);
+        let endurance = sata_endurance("KINGSTON SA400S37240G", &parse_attributes(&data)).unwrap();
+        assert_eq!(endurance.waf, Some(1.5));
+        assert_eq!(endurance.remaining_life_pct, Some(93));
+        assert!((endurance.host_written_tb - 1.073741824).abs() < 1e-9);
+
+        // Crucial drives count pages instead.
+        let data = smart_data(&[
+            (246, 100, 2_000_000_000),
+            (247, 100, 300),
+            (248, 100, 150),
+            (202, 97, 3),
+        ]);
+        let endurance = sata_endurance("CT500MX500SSD1", &parse_attributes(&data)).unwrap();
+        assert_eq!(endurance.waf, Some(1.5));
+        assert_eq!(endurance.remaining_life_pct, Some(97));
+
+        // Without a NAND count, there's no write amplification.
+        let data = smart_data(&[(241, 100, 2_000_000_000), (177, 99, 5)]);
+        let endurance = sata_endurance("Samsung SSD 860 EVO", &parse_attributes(&data)).unwrap();
+        assert_eq!(endurance.waf, None);
+        assert_eq!(endurance.remaining_life_pct, Some(99));
+        assert_eq!(endurance.host_written_tb, 1.024);
+
+        assert!(sata_endurance("Samsung SSD 860 EVO", &[]).is_none());
+
+        // The largest raw values don't overflow when counted in GiB.
+        let data = smart_data(&[(241, 100, 0xFFFF_FFFF_FFFF), (233, 100, 0xFFFF_FFFF_FFFF)]);
+        let endurance = sata_endurance("KINGSTON SA400S37240G", &parse_attributes(&data)).unwrap();
+        assert_eq!(endurance.waf, Some(1.0));
+        assert!((endurance.host_written_tb - 302_231_454_903.7).abs() < 1.0);
+    }
+
+    #[test]
+    fn nvme() {
+        let mut log = vec![0; BLOCK_LEN];
+        log[5] = 3;
+        log[48..56].copy_from_slice(&10_000_000u64.to_le_bytes());
+
+        let endurance = nvme_endurance(&log).unwrap();
+        assert_eq!(endurance.remaining_life_pct, Some(97));
+        assert_eq!(endurance.host_written_tb, 5.12);
+        assert_eq!(endurance.waf, None);
+
+        // Drives can be used past their rated endurance.
+        log[5] = 120;
+        assert_eq!(nvme_endurance(&log).unwrap().remaining_life_pct, Some(0));
+    }
+}
@@ -1,4 +1,6 @@
 use anyhow::anyhow;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
 
 /// An error to do with data collection.
 #[derive(Debug)]
@@ -39,8 +41,312 @@ impl From<std::io::Error> for CollectionError {
     }
 }
 
+impl From<anyhow::Error> for CollectionError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::General(err)
+    }
+}
+
 impl From<&'static str> for CollectionError {
     fn from(msg: &'static str) -> Self {
         Self::General(anyhow!(msg))
     }
 }
+
+/// A part of data collection that can fail on its own. Each is collected
+/// independently, so one that's broken, like unreadable temperature sensors,
+/// doesn't stop the rest from being collected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    Cpu,
+    Memory,
+    Processes,
+    Temperature,
+    Disks,
+    DiskIo,
+}
+
+impl Subsystem {
+    /// The name used for the subsystem in the logs, and when a [`DataError`]
+    /// is serialized.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Subsystem::Cpu => "cpu",
+            Subsystem::Memory => "memory",
+            Subsystem::Processes => "processes",
+            Subsystem::Temperature => "temperature",
+            Subsystem::Disks => "disks",
+            Subsystem::DiskIo => "disk_io",
+        }
+    }
+}
+
+impl std::fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An error from reading a subsystem, which is sent along with the rest of the
+/// data so the affected widget can show it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataError {
+    pub subsystem: Subsystem,
+    pub message: String,
+    /// Whether the subsystem has failed too many times in a row, and so is no
+    /// longer read.
+    pub disabled: bool,
+    /// How many times in a row the subsystem has failed.
+    #[serde(default)]
+    pub count: u32,
+}
+
+/// How many times in a row each kind of data has failed to be read, for the
+/// badge in the status line. A successful read starts its count over. Kinds
+/// read by more than one subsystem, like disks, add up their counts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DataErrors {
+    pub cpu_errors: u32,
+    pub mem_errors: u32,
+    /// Both disk usage and disk I/O.
+    pub disk_errors: u32,
+    pub temp_errors: u32,
+    /// Network usage is read through sysinfo, which doesn't say when it
+    /// couldn't read an interface, so this stays at zero for now.
+    pub net_errors: u32,
+    pub proc_errors: u32,
+}
+
+impl DataErrors {
+    /// Counts the errors of the last collection.
+    pub fn new(errors: &[DataError]) -> Self {
+        let mut counts = Self::default();
+        for error in errors {
+            let count = match error.subsystem {
+                Subsystem::Cpu => &mut counts.cpu_errors,
+                Subsystem::Memory => &mut counts.mem_errors,
+                Subsystem::Disks | Subsystem::DiskIo => &mut counts.disk_errors,
+                Subsystem::Temperature => &mut counts.temp_errors,
+                Subsystem::Processes => &mut counts.proc_errors,
+            };
+            // Errors in recordings made before errors were counted have no
+            // count, but still happened at least once.
+            *count = count.saturating_add(error.count.max(1));
+        }
+
+        counts
+    }
+
+    /// The failures in a row of every kind of data added up, which is what the
+    /// badge shows. This isn't how many kinds are failing: two that have each
+    /// failed three times in a row make six.
+    pub fn total(&self) -> u32 {
+        [
+            self.cpu_errors,
+            self.mem_errors,
+            self.disk_errors,
+            self.temp_errors,
+            self.net_errors,
+            self.proc_errors,
+        ]
+        .into_iter()
+        .fold(0, u32::saturating_add)
+    }
+}
+
+/// The failures in a row of a single subsystem.
+#[derive(Debug)]
+struct Failures {
+    count: u32,
+    last_message: String,
+}
+
+/// Tracks how many times in a row each [`Subsystem`] has failed, so that one
+/// that keeps failing can be turned off for the rest of the session.
+#[derive(Debug)]
+pub struct SubsystemRetries {
+    retry_count: u32,
+    failures: HashMap<Subsystem, Failures>,
+}
+
+impl SubsystemRetries {
+    pub fn new(retry_count: u32) -> Self {
+        Self {
+            retry_count,
+            failures: HashMap::default(),
+        }
+    }
+
+    /// Whether the subsystem has failed too many times in a row to be read.
+    pub fn is_disabled(&self, subsystem: Subsystem) -> bool {
+        self.failures
+            .get(&subsystem)
+            .is_some_and(|failures| failures.count >= self.retry_count)
+    }
+
+    /// Errors for each subsystem that's been turned off, so their widgets keep
+    /// showing why.
+    pub fn disabled_errors(&self) -> Vec<DataError> {
+        self.failures
+            .iter()
+            .filter(|(_, failures)| failures.count >= self.retry_count)
+            .map(|(subsystem, failures)| DataError {
+                subsystem: *subsystem,
+                message: failures.last_message.clone(),
+                disabled: true,
+                count: failures.count,
+            })
+            .collect()
+    }
+
+    /// Returns the result of reading a subsystem if it was read, or otherwise
+    /// logs the error and adds it to `errors`. A subsystem that isn't supported
+    /// on this platform isn't counted as failing.
+    pub fn record<T, E: Into<CollectionError>>(
+        &mut self, subsystem: Subsystem, result: Result<T, E>, errors: &mut Vec<DataError>,
+    ) -> Option<T> {
+        let err = match result.map_err(Into::into) {
+            Ok(value) => {
+                self.failures.remove(&subsystem);
+                return Some(value);
+            }
+            Err(CollectionError::Unsupported) => return None,
+            Err(err) => err,
+        };
+
+        let message = err.to_string();
+        crate::warn!("Failed to read {subsystem}: {message}");
+
+        let failures = self.failures.entry(subsystem).or_insert(Failures {
+            count: 0,
+            last_message: String::new(),
+        });
+        failures.count += 1;
+        failures.last_message = message.clone();
+
+        let disabled = failures.count >= self.retry_count;
+        if disabled {
+            crate::error!(
+                "Stopped reading {subsystem} after {} failures in a row.",
+                failures.count
+            );
+        }
+
+        errors.push(DataError {
+            subsystem,
+            message,
+            disabled,
+            count: failures.count,
+        });
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retries() {
+        let mut retries = SubsystemRetries::new(2);
+        let mut errors = vec![];
+
+        let failure = || -> CollectionResult<u32> { Err("no sensors".into()) };
+        assert_eq!(
+            retries.record(Subsystem::Temperature, failure(), &mut errors),
+            None
+        );
+        assert!(!errors[0].disabled);
+        assert!(!retries.is_disabled(Subsystem::Temperature));
+
+        // A success in between starts the count over.
+        assert_eq!(
+            retries.record(
+                Subsystem::Temperature,
+                Ok::<_, CollectionError>(1),
+                &mut errors
+            ),
+            Some(1)
+        );
+        retries.record(Subsystem::Temperature, failure(), &mut errors);
+        assert!(!retries.is_disabled(Subsystem::Temperature));
+
+        retries.record(Subsystem::Temperature, failure(), &mut errors);
+        assert!(errors[2].disabled);
+        assert!(retries.is_disabled(Subsystem::Temperature));
+        assert!(!retries.is_disabled(Subsystem::Cpu));
+        assert_eq!(
+            retries.disabled_errors(),
+            vec![DataError {
+                subsystem: Subsystem::Temperature,
+                message: "no sensors".to_string(),
+                disabled: true,
+                count: 2,
+            }]
+        );
+
+        let unsupported = || -> CollectionResult<u32> { Err(CollectionError::Unsupported) };
+        retries.record(Subsystem::Disks, unsupported(), &mut errors);
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn subsystem_names() {
+        // Recordings made before subsystems were serialized as such used their
+        // names, so those must stay the same.
+        for subsystem in [
+            Subsystem::Cpu,
+            Subsystem::Memory,
+            Subsystem::Processes,
+            Subsystem::Temperature,
+            Subsystem::Disks,
+            Subsystem::DiskIo,
+        ] {
+            let name = format!("\"{}\"", subsystem.name());
+            assert_eq!(serde_json::to_string(&subsystem).unwrap(), name);
+            assert_eq!(serde_json::from_str::<Subsystem>(&name).unwrap(), subsystem);
+        }
+    }
+
+    #[test]
+    fn error_counts() {
+        let mut retries = SubsystemRetries::new(5);
+        let mut errors = vec![];
+
+        let failure = || -> CollectionResult<u32> { Err("unreadable".into()) };
+        for _ in 0..3 {
+            errors.clear();
+            retries.record(Subsystem::Temperature, failure(), &mut errors);
+            retries.record(Subsystem::Disks, failure(), &mut errors);
+            retries.record(Subsystem::DiskIo, failure(), &mut errors);
+        }
+        assert_eq!(
+            DataErrors::new(&errors),
+            DataErrors {
+                temp_errors: 3,
+                disk_errors: 6,
+                ..Default::default()
+            }
+        );
+        assert_eq!(DataErrors::new(&errors).total(), 9);
+
+        // Reading a subsystem again starts its count over.
+        errors.clear();
+        retries.record(
+            Subsystem::Temperature,
+            Ok::<_, CollectionError>(1),
+            &mut errors,
+        );
+        retries.record(Subsystem::Disks, failure(), &mut errors);
+        assert_eq!(
+            DataErrors::new(&errors),
+            DataErrors {
+                disk_errors: 4,
+                ..Default::default()
+            }
+        );
+        assert_eq!(DataErrors::new(&[]).total(), 0);
+    }
+}
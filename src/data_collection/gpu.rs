@@ -0,0 +1,86 @@
+//! Helpers shared by the GPU backends.
+
+#[cfg(feature = "amd-gpu")]
+pub mod amd;
+
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// How busy a GPU is and how much power it's drawing. Either can be missing
+/// if the card or its driver doesn't report it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GpuUsage {
+    /// The percentage of the last sample period the GPU was busy.
+    pub utilization: Option<u32>,
+    /// The average power drawn, in watts.
+    pub power_watts: Option<f64>,
+}
+
+impl GpuUsage {
+    /// A short description for the memory widget's legend, like `87% busy,
+    /// 120W`, or [`None`] if nothing was reported.
+    pub fn summary(&self) -> Option<String> {
+        let parts = [
+            self.utilization
+                .map(|utilization| format!("{utilization}% busy")),
+            self.power_watts.map(|power| format!("{power:.0}W")),
+        ];
+        let parts = parts.into_iter().flatten().collect::<Vec<_>>();
+
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// The GPU memory allocated by a process in bytes, summed over all GPUs, or
+/// [`None`] if it has nothing allocated on any of them. A process can be
+/// listed for a GPU with no memory if it only shows up in the utilization
+/// stats.
+pub fn process_gpu_mem(gpus: &[HashMap<u32, (u64, u32)>], pid: u32) -> Option<u64> {
+    gpus.iter()
+        .filter_map(|gpu| gpu.get(&pid).map(|(mem, _)| *mem))
+        .filter(|mem| *mem > 0)
+        .reduce(|a, b| a + b)
+}
+
+/// The GPU utilization of a process, summed over all GPUs.
+pub fn process_gpu_util(gpus: &[HashMap<u32, (u64, u32)>], pid: u32) -> u32 {
+    gpus.iter()
+        .filter_map(|gpu| gpu.get(&pid).map(|(_, util)| *util))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn process_sums() {
+        let gpus: Vec<HashMap<u32, (u64, u32)>> = vec![
+            [(1, (100, 5)), (2, (0, 10))].into_iter().collect(),
+            [(1, (50, 2)), (3, (0, 0))].into_iter().collect(),
+        ];
+
+        assert_eq!(process_gpu_mem(&gpus, 1), Some(150));
+        assert_eq!(process_gpu_mem(&gpus, 2), None);
+        assert_eq!(process_gpu_mem(&gpus, 3), None);
+        assert_eq!(process_gpu_mem(&gpus, 4), None);
+        assert_eq!(process_gpu_util(&gpus, 1), 7);
+        assert_eq!(process_gpu_util(&gpus, 2), 10);
+    }
+
+    #[test]
+    fn usage_summaries() {
+        let usage = GpuUsage {
+            utilization: Some(87),
+            power_watts: Some(120.4),
+        };
+        assert_eq!(usage.summary().as_deref(), Some("87% busy, 120W"));
+
+        let usage = GpuUsage {
+            power_watts: None,
+            ..usage
+        };
+        assert_eq!(usage.summary().as_deref(), Some("87% busy"));
+        assert_eq!(GpuUsage::default().summary(), None);
+    }
+}
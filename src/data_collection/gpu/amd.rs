@@ -0,0 +1,291 @@
+//! GPU data collection for AMD cards via the ROCm SMI library.
+//!
+//! The library is loaded at runtime rather than linked, so that builds with this
+//! backend still work on systems without ROCm installed.
+
+use std::{
+    ffi::{c_char, CStr},
+    sync::OnceLock,
+};
+
+use hashbrown::HashMap;
+use libloading::Library;
+
+use crate::{
+    app::{filter::Filter, layout_manager::UsedWidgets},
+    data_collection::{
+        gpu::GpuUsage,
+        memory::MemHarvest,
+        temperature::{TempHarvest, TemperatureType},
+        GpusData,
+    },
+};
+
+/// Library names to try, in order.
+const LIBRARY_NAMES: [&str; 3] = [
+    "librocm_smi64.so",
+    "librocm_smi64.so.1",
+    "/opt/rocm/lib/librocm_smi64.so",
+];
+
+type RsmiStatus = u32;
+
+const RSMI_STATUS_SUCCESS: RsmiStatus = 0;
+const RSMI_MEM_TYPE_VRAM: u32 = 0;
+const RSMI_TEMP_TYPE_EDGE: u32 = 0;
+const RSMI_TEMP_CURRENT: u32 = 0;
+/// The first power sensor of a device, which is the only one most have.
+const RSMI_POWER_SENSOR: u32 = 0;
+
+/// Mirrors `rsmi_process_info_t`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+#[allow(dead_code)]
+struct RsmiProcessInfo {
+    process_id: u32,
+    pasid: u32,
+    vram_usage: u64,
+    sdma_usage: u64,
+    cu_occupancy: u32,
+}
+
+/// The subset of the ROCm SMI library that we use. The function pointers are only
+/// valid while `_lib` is alive. Utilization and power are optional, so older
+/// versions of the library without them can still be used.
+struct RocmSmi {
+    _lib: Library,
+    num_monitor_devices: unsafe extern "C" fn(*mut u32) -> RsmiStatus,
+    dev_name_get: unsafe extern "C" fn(u32, *mut c_char, usize) -> RsmiStatus,
+    dev_memory_total_get: unsafe extern "C" fn(u32, u32, *mut u64) -> RsmiStatus,
+    dev_memory_usage_get: unsafe extern "C" fn(u32, u32, *mut u64) -> RsmiStatus,
+    dev_temp_metric_get: unsafe extern "C" fn(u32, u32, u32, *mut i64) -> RsmiStatus,
+    compute_process_info_get: unsafe extern "C" fn(*mut RsmiProcessInfo, *mut u32) -> RsmiStatus,
+    dev_busy_percent_get: Option<unsafe extern "C" fn(u32, *mut u32) -> RsmiStatus>,
+    dev_power_ave_get: Option<unsafe extern "C" fn(u32, u32, *mut u64) -> RsmiStatus>,
+}
+
+impl RocmSmi {
+    /// Loads and initializes the ROCm SMI library, returning `None` if it isn't
+    /// available.
+    fn load() -> Option<Self> {
+        // SAFETY: Loading ROCm SMI runs its initializers, which have no special
+        // requirements. Symbol types match the signatures in `rocm_smi.h`.
+        unsafe {
+            let lib = LIBRARY_NAMES
+                .iter()
+                .find_map(|name| Library::new(name).ok())?;
+
+            let init = *lib
+                .get::<unsafe extern "C" fn(u64) -> RsmiStatus>(b"rsmi_init\0")
+                .ok()?;
+
+            let smi = RocmSmi {
+                num_monitor_devices: *lib.get(b"rsmi_num_monitor_devices\0").ok()?,
+                dev_name_get: *lib.get(b"rsmi_dev_name_get\0").ok()?,
+                dev_memory_total_get: *lib.get(b"rsmi_dev_memory_total_get\0").ok()?,
+                dev_memory_usage_get: *lib.get(b"rsmi_dev_memory_usage_get\0").ok()?,
+                dev_temp_metric_get: *lib.get(b"rsmi_dev_temp_metric_get\0").ok()?,
+                compute_process_info_get: *lib.get(b"rsmi_compute_process_info_get\0").ok()?,
+                dev_busy_percent_get: lib.get(b"rsmi_dev_busy_percent_get\0").ok().map(|f| *f),
+                dev_power_ave_get: lib.get(b"rsmi_dev_power_ave_get\0").ok().map(|f| *f),
+                _lib: lib,
+            };
+
+            if init(0) != RSMI_STATUS_SUCCESS {
+                return None;
+            }
+
+            Some(smi)
+        }
+    }
+
+    fn device_count(&self) -> Option<u32> {
+        let mut count = 0;
+        // SAFETY: `count` is a valid pointer for the duration of the call.
+        let status = unsafe { (self.num_monitor_devices)(&mut count) };
+        (status == RSMI_STATUS_SUCCESS).then_some(count)
+    }
+
+    fn name(&self, index: u32) -> Option<String> {
+        let mut buffer = [0 as c_char; 256];
+        // SAFETY: The buffer length passed in matches the buffer.
+        let status = unsafe { (self.dev_name_get)(index, buffer.as_mut_ptr(), buffer.len()) };
+        if status != RSMI_STATUS_SUCCESS {
+            return None;
+        }
+
+        // SAFETY: ROCm SMI NUL-terminates the name within the given length.
+        let name = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        Some(name.to_string_lossy().into_owned())
+    }
+
+    /// Returns the total and used VRAM in bytes.
+    fn memory(&self, index: u32) -> Option<(u64, u64)> {
+        let mut total = 0;
+        let mut used = 0;
+        // SAFETY: Both out pointers are valid for the duration of the calls.
+        unsafe {
+            if (self.dev_memory_total_get)(index, RSMI_MEM_TYPE_VRAM, &mut total)
+                != RSMI_STATUS_SUCCESS
+                || (self.dev_memory_usage_get)(index, RSMI_MEM_TYPE_VRAM, &mut used)
+                    != RSMI_STATUS_SUCCESS
+            {
+                return None;
+            }
+        }
+
+        Some((total, used))
+    }
+
+    /// Returns the edge temperature in degrees Celsius.
+    fn temperature(&self, index: u32) -> Option<f32> {
+        let mut millidegrees = 0;
+        // SAFETY: `millidegrees` is a valid pointer for the duration of the call.
+        let status = unsafe {
+            (self.dev_temp_metric_get)(
+                index,
+                RSMI_TEMP_TYPE_EDGE,
+                RSMI_TEMP_CURRENT,
+                &mut millidegrees,
+            )
+        };
+
+        (status == RSMI_STATUS_SUCCESS).then_some(millidegrees as f32 / 1000.0)
+    }
+
+    /// Returns the percentage of time the GPU was busy.
+    fn busy_percent(&self, index: u32) -> Option<u32> {
+        let busy_percent_get = self.dev_busy_percent_get?;
+        let mut percent = 0;
+        // SAFETY: `percent` is a valid pointer for the duration of the call.
+        let status = unsafe { busy_percent_get(index, &mut percent) };
+        (status == RSMI_STATUS_SUCCESS).then_some(percent)
+    }
+
+    /// Returns the average power drawn by the GPU in watts.
+    fn power(&self, index: u32) -> Option<f64> {
+        let power_ave_get = self.dev_power_ave_get?;
+        let mut microwatts = 0;
+        // SAFETY: `microwatts` is a valid pointer for the duration of the call.
+        let status = unsafe { power_ave_get(index, RSMI_POWER_SENSOR, &mut microwatts) };
+        (status == RSMI_STATUS_SUCCESS).then_some(microwatts as f64 / 1_000_000.0)
+    }
+
+    /// Returns the VRAM usage of every process using any AMD GPU, keyed by PID.
+    fn processes(&self) -> HashMap<u32, (u64, u32)> {
+        let mut procs = HashMap::new();
+        let mut num_items = 0;
+
+        // SAFETY: Passing a null buffer only queries the process count.
+        let status =
+            unsafe { (self.compute_process_info_get)(std::ptr::null_mut(), &mut num_items) };
+        if status != RSMI_STATUS_SUCCESS || num_items == 0 {
+            return procs;
+        }
+
+        let mut buffer = vec![RsmiProcessInfo::default(); num_items as usize];
+        // SAFETY: `num_items` matches the length of `buffer`.
+        let status =
+            unsafe { (self.compute_process_info_get)(buffer.as_mut_ptr(), &mut num_items) };
+        if status != RSMI_STATUS_SUCCESS {
+            return procs;
+        }
+
+        for proc in buffer.iter().take(num_items as usize) {
+            procs.insert(proc.process_id, (proc.vram_usage, 0));
+        }
+
+        procs
+    }
+}
+
+// SAFETY: ROCm SMI is thread-safe after initialization.
+unsafe impl Send for RocmSmi {}
+// SAFETY: ROCm SMI is thread-safe after initialization.
+unsafe impl Sync for RocmSmi {}
+
+static ROCM_SMI: OnceLock<Option<RocmSmi>> = OnceLock::new();
+
+/// Returns the GPU data from AMD cards.
+#[inline]
+pub fn get_amd_vecs(
+    temp_type: &TemperatureType, filter: &Option<Filter>, widgets_to_harvest: &UsedWidgets,
+) -> Option<GpusData> {
+    let smi = ROCM_SMI.get_or_init(RocmSmi::load).as_ref()?;
+    let num_gpu = smi.device_count()?;
+
+    let mut temp_vec = Vec::with_capacity(num_gpu as usize);
+    let mut mem_vec = Vec::with_capacity(num_gpu as usize);
+    let mut usage_vec = Vec::with_capacity(num_gpu as usize);
+    let mut total_mem = 0;
+
+    for i in 0..num_gpu {
+        let memory = smi.memory(i);
+
+        if let Some(name) = smi.name(i) {
+            if widgets_to_harvest.use_mem {
+                if let Some((total, used)) = memory {
+                    mem_vec.push((
+                        name.clone(),
+                        MemHarvest {
+                            total_bytes: total,
+                            used_bytes: used,
+                            use_percent: if total == 0 {
+                                None
+                            } else {
+                                Some(used as f64 / total as f64 * 100.0)
+                            },
+                        },
+                    ));
+                    usage_vec.push(GpuUsage {
+                        utilization: smi.busy_percent(i),
+                        power_watts: smi.power(i),
+                    });
+                }
+            }
+
+            if widgets_to_harvest.use_temp && Filter::optional_should_keep(filter, &name) {
+                temp_vec.push(TempHarvest {
+                    name,
+                    temperature: smi
+                        .temperature(i)
+                        .map(|temperature| temp_type.convert_temp_unit(temperature)),
+                });
+            }
+        }
+
+        if let Some((total, _)) = memory {
+            total_mem += total;
+        }
+    }
+
+    let procs = if widgets_to_harvest.use_proc {
+        let procs = smi.processes();
+        if !procs.is_empty() {
+            Some((total_mem, vec![procs]))
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    Some(GpusData {
+        memory: if !mem_vec.is_empty() {
+            Some(mem_vec)
+        } else {
+            None
+        },
+        usage: if !usage_vec.is_empty() {
+            Some(usage_vec)
+        } else {
+            None
+        },
+        temperature: if !temp_vec.is_empty() {
+            Some(temp_vec)
+        } else {
+            None
+        },
+        procs,
+    })
+}
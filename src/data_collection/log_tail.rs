@@ -0,0 +1,353 @@
+//! Following files as lines are written to them, like `tail -F`, for the log
+//! tail widget.
+//!
+//! Files are polled for new lines rather than watched, like everything else
+//! that's collected. A file that's replaced or truncated, as when logs are
+//! rotated, is read again from the start.
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File, Metadata},
+    io::{self, Read, Seek, SeekFrom},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// The default number of lines kept from each file to scroll back through.
+pub const DEFAULT_LOG_TAIL_LINES: usize = 500;
+
+/// How often the files are checked for new lines.
+pub const LOG_TAIL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How many lines are shown from a file that already had some when it was
+/// first opened.
+const INITIAL_LINES: usize = 20;
+
+/// The most that's read from a file at once. If more than this was written
+/// since the last read, the lines before it are skipped.
+const MAX_READ_BYTES: u64 = 1024 * 1024;
+
+/// What's changed in a followed file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogTailUpdate {
+    /// New lines were written.
+    Lines(Vec<String>),
+    /// The file couldn't be read.
+    Error(String),
+}
+
+/// Identifies a file, to tell when it's been replaced by another at the same
+/// path. Only Unix platforms can tell files apart, so only truncation is
+/// noticed elsewhere.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileId(u64, u64);
+
+impl FileId {
+    #[cfg(target_family = "unix")]
+    fn of(metadata: &Metadata) -> Option<Self> {
+        use std::os::unix::fs::MetadataExt;
+
+        Some(FileId(metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(target_family = "unix"))]
+    fn of(_metadata: &Metadata) -> Option<Self> {
+        None
+    }
+}
+
+/// An open file being followed.
+struct OpenFile {
+    file: File,
+    id: Option<FileId>,
+    /// How far into the file has been read.
+    position: u64,
+}
+
+/// Follows a file, reading the lines written to it since the last read.
+pub struct LogTail {
+    path: PathBuf,
+    file: Option<OpenFile>,
+    /// The end of the last read if it wasn't a whole line, to finish with the
+    /// next read.
+    partial: Vec<u8>,
+    /// Whether the file has been opened before, after which it's read from
+    /// the start whenever it's reopened.
+    has_opened: bool,
+    last_error: Option<String>,
+}
+
+impl LogTail {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: None,
+            partial: Vec::new(),
+            has_opened: false,
+            last_error: None,
+        }
+    }
+
+    /// Checks the file for new lines, returning them if there are any, or an
+    /// error if one happened that hasn't been returned yet.
+    pub fn poll(&mut self) -> Option<LogTailUpdate> {
+        match self.read_new_lines() {
+            Ok(lines) => {
+                self.last_error = None;
+                (!lines.is_empty()).then_some(LogTailUpdate::Lines(lines))
+            }
+            Err(err) => {
+                let err = err.to_string();
+                if self.last_error.as_ref() == Some(&err) {
+                    None
+                } else {
+                    self.last_error = Some(err.clone());
+                    Some(LogTailUpdate::Error(err))
+                }
+            }
+        }
+    }
+
+    fn read_new_lines(&mut self) -> io::Result<Vec<String>> {
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // A rotated file may be gone for a moment before it's replaced,
+            // so keep reading what's left of the old one in the meantime.
+            Err(err) => {
+                return match &mut self.file {
+                    Some(open) => read_lines(open, &mut self.partial, None),
+                    None => Err(err),
+                };
+            }
+        };
+
+        let id = FileId::of(&metadata);
+        let is_replaced = self.file.as_ref().is_some_and(|open| open.id != id);
+        let is_truncated = self
+            .file
+            .as_ref()
+            .is_some_and(|open| metadata.len() < open.position);
+        if is_replaced || is_truncated {
+            self.file = None;
+            self.partial.clear();
+        }
+
+        let open = match &mut self.file {
+            Some(open) => open,
+            None => {
+                let file = File::open(&self.path)?;
+                let is_reopen = self.has_opened;
+                self.has_opened = true;
+                let open = self.file.insert(OpenFile {
+                    file,
+                    id,
+                    position: 0,
+                });
+
+                if !is_reopen {
+                    // Rather than the whole file, start with its last lines.
+                    let lines = read_lines(open, &mut self.partial, Some(metadata.len()))?;
+                    let start = lines.len().saturating_sub(INITIAL_LINES);
+                    return Ok(lines[start..].to_vec());
+                }
+
+                open
+            }
+        };
+
+        read_lines(open, &mut self.partial, Some(metadata.len()))
+    }
+}
+
+/// Reads the whole lines written after `open.position`, keeping the end of the
+/// read in `partial` if it isn't a whole line. If more than [`MAX_READ_BYTES`]
+/// were written, only the last lines are read.
+fn read_lines(
+    open: &mut OpenFile, partial: &mut Vec<u8>, len: Option<u64>,
+) -> io::Result<Vec<String>> {
+    let len = match len {
+        Some(len) => len,
+        None => open.file.metadata()?.len(),
+    };
+    let mut is_skipping = false;
+    if len.saturating_sub(open.position) > MAX_READ_BYTES {
+        open.position = len - MAX_READ_BYTES;
+        partial.clear();
+        is_skipping = true;
+    }
+
+    open.file.seek(SeekFrom::Start(open.position))?;
+    let mut buffer = Vec::new();
+    open.file
+        .by_ref()
+        .take(MAX_READ_BYTES)
+        .read_to_end(&mut buffer)?;
+    open.position += buffer.len() as u64;
+
+    partial.extend_from_slice(&buffer);
+    let Some(end) = partial.iter().rposition(|&byte| byte == b'\n') else {
+        // Don't keep an endless line around.
+        if partial.len() as u64 > MAX_READ_BYTES {
+            partial.clear();
+        }
+        return Ok(Vec::new());
+    };
+    let rest = partial.split_off(end + 1);
+    let lines = std::mem::replace(partial, rest);
+
+    let mut lines = lines[..end]
+        .split(|&byte| byte == b'\n')
+        .map(|line| {
+            String::from_utf8_lossy(line)
+                .trim_end_matches('\r')
+                .replace('\t', "    ")
+        })
+        .collect::<Vec<_>>();
+    if is_skipping {
+        // The first line was probably cut off.
+        lines.remove(0);
+    }
+
+    Ok(lines)
+}
+
+/// The lines kept from a followed file, up to a limit.
+#[derive(Debug)]
+pub struct LogTailBuffer {
+    pub path: PathBuf,
+    pub lines: VecDeque<String>,
+    /// Why the file couldn't be read the last time it was checked, if it
+    /// couldn't.
+    pub error: Option<String>,
+    max_lines: usize,
+}
+
+impl LogTailBuffer {
+    pub fn new(path: PathBuf, max_lines: usize) -> Self {
+        Self {
+            path,
+            lines: VecDeque::new(),
+            error: None,
+            max_lines,
+        }
+    }
+
+    /// The name of the file, for its tab.
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or(self.path.as_os_str())
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Applies an update, returning how many lines were added.
+    pub fn update(&mut self, update: LogTailUpdate) -> usize {
+        match update {
+            LogTailUpdate::Lines(lines) => {
+                self.error = None;
+                let num_lines = lines.len();
+                self.lines.extend(lines);
+                let excess = self.lines.len().saturating_sub(self.max_lines);
+                self.lines.drain(..excess);
+
+                num_lines
+            }
+            LogTailUpdate::Error(err) => {
+                self.error = Some(err);
+                0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::OpenOptions, io::Write, path::Path};
+
+    use super::*;
+
+    fn append(path: &Path, text: &str) {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn lines(update: Option<LogTailUpdate>) -> Vec<String> {
+        match update {
+            Some(LogTailUpdate::Lines(lines)) => lines,
+            Some(LogTailUpdate::Error(err)) => panic!("unexpected error: {err}"),
+            None => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn follows_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let mut tail = LogTail::new(path.clone());
+
+        // A missing file is an error, which is only returned once.
+        assert!(matches!(tail.poll(), Some(LogTailUpdate::Error(_))));
+        assert_eq!(tail.poll(), None);
+
+        let existing = (0..30).map(|i| format!("old {i}\n")).collect::<String>();
+        append(&path, &existing);
+        let initial = lines(tail.poll());
+        assert_eq!(initial.len(), INITIAL_LINES);
+        assert_eq!(initial[0], "old 10");
+
+        // Lines are only returned once they're finished.
+        append(&path, "new 1\r\nnew");
+        assert_eq!(lines(tail.poll()), vec!["new 1"]);
+        append(&path, " 2\n");
+        assert_eq!(lines(tail.poll()), vec!["new 2"]);
+        assert_eq!(tail.poll(), None);
+    }
+
+    #[test]
+    fn handles_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        append(&path, "first\n");
+        let mut tail = LogTail::new(path.clone());
+        assert_eq!(lines(tail.poll()), vec!["first"]);
+
+        // Truncated, as with logrotate's copytruncate.
+        fs::write(&path, "").unwrap();
+        assert_eq!(tail.poll(), None);
+        append(&path, "second\n");
+        assert_eq!(lines(tail.poll()), vec!["second"]);
+
+        // Replaced with a new file, after the last lines were written to the
+        // old one.
+        #[cfg(target_family = "unix")]
+        {
+            let rotated = dir.path().join("app.log.1");
+            fs::rename(&path, &rotated).unwrap();
+            append(&rotated, "last old\n");
+            assert_eq!(lines(tail.poll()), vec!["last old"]);
+            append(&path, "third\nfourth\n");
+            assert_eq!(lines(tail.poll()), vec!["third", "fourth"]);
+        }
+    }
+
+    #[test]
+    fn buffer() {
+        let mut buffer = LogTailBuffer::new(PathBuf::from("/var/log/app.log"), 3);
+        assert_eq!(buffer.name(), "app.log");
+
+        let update = LogTailUpdate::Lines((0..5).map(|i| i.to_string()).collect());
+        assert_eq!(buffer.update(update), 5);
+        assert_eq!(buffer.lines, ["2", "3", "4"]);
+
+        buffer.update(LogTailUpdate::Error("gone".to_string()));
+        assert_eq!(buffer.error.as_deref(), Some("gone"));
+        buffer.update(LogTailUpdate::Lines(vec!["5".to_string()]));
+        assert_eq!(buffer.error, None);
+        assert_eq!(buffer.lines, ["3", "4", "5"]);
+    }
+}
@@ -1,9 +1,11 @@
 //! Memory data collection.
 
+pub use self::meminfo::{full_meminfo, MemInfoEntry};
 #[cfg(not(target_os = "windows"))]
 pub(crate) use self::sysinfo::get_cache_usage;
 pub(crate) use self::sysinfo::{get_ram_usage, get_swap_usage};
 
+pub mod meminfo;
 pub mod sysinfo;
 // cfg_if::cfg_if! {
 //     if #[cfg(target_os = "windows")] {
@@ -15,10 +17,174 @@ pub mod sysinfo;
 #[cfg(feature = "zfs")]
 pub mod arc;
 
-#[derive(Debug, Clone, Default)]
+#[cfg(target_os = "linux")]
+pub mod buddyinfo;
+#[cfg(target_os = "linux")]
+pub(crate) use self::buddyinfo::buddyinfo;
+
+#[cfg(target_os = "linux")]
+pub mod cgroup;
+
+#[cfg(target_os = "linux")]
+pub mod swaps;
+
+#[cfg(target_os = "linux")]
+pub mod vmstat;
+
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MemHarvest {
     pub used_bytes: u64,
     pub total_bytes: u64,
     pub use_percent: Option<f64>, /* TODO: Might be find to just make this an f64, and any
                                    * consumer checks NaN. */
 }
+
+/// The memory limits of the cgroup bottom runs in, which RAM usage is shown
+/// against instead of the physical RAM. Only collected on Linux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CgroupMemoryLimits {
+    /// The hard limit, past which processes in the cgroup are killed.
+    pub max_bytes: u64,
+    /// The soft limit, past which the cgroup is throttled, if it's below the
+    /// hard limit.
+    pub high_bytes: Option<u64>,
+    pub physical_total_bytes: u64,
+}
+
+/// The order of the blocks that fragmentation is measured for, which are 2 MiB
+/// with 4 KiB pages, like transparent huge pages.
+pub const FRAGMENTATION_ORDER: usize = 9;
+
+/// Zones with a fragmentation index above this are flagged.
+pub const HIGH_FRAGMENTATION_INDEX: f64 = 0.7;
+
+/// The free memory of a single zone of a NUMA node, by the size of the blocks
+/// it's in. Only collected on Linux.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuddyZone {
+    pub node: u32,
+    /// The zone's name, like `DMA32` or `Normal`.
+    pub zone: String,
+    /// How many free blocks of 2^order pages there are, by order.
+    pub free_blocks: Vec<u64>,
+    /// The size of a page in bytes.
+    pub page_size: u64,
+}
+
+impl BuddyZone {
+    pub fn free_pages(&self) -> u64 {
+        self.free_blocks
+            .iter()
+            .enumerate()
+            .map(|(order, count)| count << order)
+            .sum()
+    }
+
+    pub fn free_bytes(&self) -> u64 {
+        self.free_pages() * self.page_size
+    }
+
+    /// The order that fragmentation is measured for, which is
+    /// [`FRAGMENTATION_ORDER`] unless the zone doesn't have blocks that large.
+    pub fn order(&self) -> usize {
+        FRAGMENTATION_ORDER.min(self.free_blocks.len().saturating_sub(1))
+    }
+
+    /// How much of the free memory is in blocks of at least [`Self::order`]
+    /// pages, where 1.0 is all of it, or [`None`] if there's none free.
+    pub fn large_free_ratio(&self) -> Option<f64> {
+        let free_pages = self.free_pages();
+        let large_pages: u64 = self
+            .free_blocks
+            .iter()
+            .enumerate()
+            .skip(self.order())
+            .map(|(order, count)| count << order)
+            .sum();
+
+        (free_pages > 0).then(|| large_pages as f64 / free_pages as f64)
+    }
+
+    /// The fragmentation index of an allocation of [`Self::order`] pages, as
+    /// the kernel's `extfrag_index` works it out. Towards 0, it would fail for
+    /// a lack of memory, and towards 1, because the free memory is
+    /// fragmented. This is [`None`] if it wouldn't fail, as there's a block
+    /// large enough.
+    pub fn fragmentation_index(&self) -> Option<f64> {
+        let order = self.order();
+        let total_blocks: u64 = self.free_blocks.iter().sum();
+        let suitable_blocks: u64 = self.free_blocks.iter().skip(order).sum();
+
+        if total_blocks == 0 {
+            Some(0.0)
+        } else if suitable_blocks > 0 {
+            None
+        } else {
+            let requested = (1_u64 << order) as f64;
+            Some(1.0 - (1.0 + self.free_pages() as f64 / requested) / total_blocks as f64)
+        }
+    }
+
+    /// Whether the fragmentation index is over [`HIGH_FRAGMENTATION_INDEX`].
+    pub fn is_fragmented(&self) -> bool {
+        self.fragmentation_index()
+            .is_some_and(|index| index > HIGH_FRAGMENTATION_INDEX)
+    }
+}
+
+/// How quickly pages are being swapped in and out. Only collected on Linux.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapIOData {
+    pub swap_in_pages_per_sec: u64,
+    pub swap_out_pages_per_sec: u64,
+    /// The size of a page in bytes.
+    pub page_size: u64,
+}
+
+impl SwapIOData {
+    /// How many bytes are being swapped in and out per second.
+    pub fn bytes_per_sec(&self) -> (u64, u64) {
+        (
+            self.swap_in_pages_per_sec * self.page_size,
+            self.swap_out_pages_per_sec * self.page_size,
+        )
+    }
+}
+
+/// What backs a swap device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwapKind {
+    Partition,
+    File,
+    /// A compressed block device in RAM.
+    Zram,
+    Other,
+}
+
+impl Display for SwapKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SwapKind::Partition => "partition",
+            SwapKind::File => "file",
+            SwapKind::Zram => "zram",
+            SwapKind::Other => "other",
+        })
+    }
+}
+
+/// A single swap device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapDevice {
+    pub path: String,
+    pub kind: SwapKind,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    /// Devices with a higher priority are used first.
+    pub priority: i64,
+    /// How many times smaller the data is once compressed, for zram devices.
+    pub compression_ratio: Option<f64>,
+}
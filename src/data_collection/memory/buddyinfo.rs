@@ -0,0 +1,105 @@
+//! Gets how fragmented the free memory of each zone is on Linux, from
+//! `/proc/buddyinfo`.
+
+use std::fs;
+
+use super::BuddyZone;
+
+/// Reads every zone in `/proc/buddyinfo`, or none if it can't be read.
+pub(crate) fn buddyinfo() -> Vec<BuddyZone> {
+    let page_size = rustix::param::page_size() as u64;
+
+    fs::read_to_string("/proc/buddyinfo")
+        .map(|buddyinfo| parse_buddyinfo(&buddyinfo, page_size).collect())
+        .unwrap_or_default()
+}
+
+/// Parses each zone in `/proc/buddyinfo`. Each line is the node and zone,
+/// followed by how many free blocks of 2^0, 2^1, and so on pages there are,
+/// like `Node 0, zone   Normal   3309   7108   1297 ...`.
+fn parse_buddyinfo(buddyinfo: &str, page_size: u64) -> impl Iterator<Item = BuddyZone> + '_ {
+    buddyinfo.lines().filter_map(move |line| {
+        let (node, rest) = line.strip_prefix("Node ")?.split_once(',')?;
+        let mut fields = rest.trim_start().strip_prefix("zone")?.split_whitespace();
+        let zone = fields.next()?.to_string();
+        let free_blocks = fields
+            .map(|count| count.parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+
+        Some(BuddyZone {
+            node: node.trim().parse().ok()?,
+            zone,
+            free_blocks,
+            page_size,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zones() {
+        let buddyinfo = "\
+Node 0, zone      DMA      0      0      0      0      0      0      0      0      1      1      3
+Node 0, zone   Normal  33099   7108   1297    320    333    198     50     19      3      2      0
+Node 1, zone   Normal      1      x
+";
+        let zones = parse_buddyinfo(buddyinfo, 4096).collect::<Vec<_>>();
+
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].node, 0);
+        assert_eq!(zones[0].zone, "DMA");
+        assert_eq!(zones[0].free_blocks.len(), 11);
+        assert_eq!(zones[0].free_pages(), 256 + 512 + 3 * 1024);
+        assert_eq!(zones[1].zone, "Normal");
+        assert_eq!(zones[1].free_blocks[..3], [33099, 7108, 1297]);
+    }
+
+    fn zone(free_blocks: Vec<u64>) -> BuddyZone {
+        BuddyZone {
+            node: 0,
+            zone: "Normal".to_string(),
+            free_blocks,
+            page_size: 4096,
+        }
+    }
+
+    #[test]
+    fn fragmentation() {
+        // Plenty of free memory, but none of it in large enough blocks.
+        let fragmented = zone(vec![1000, 500, 100, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let index = fragmented.fragmentation_index().unwrap();
+        // 1 - (1 + 2400 / 512) / 1600, like the kernel's 996 thousandths.
+        assert!((index - 0.99644).abs() < 1e-5);
+        assert!(fragmented.is_fragmented());
+        assert_eq!(fragmented.large_free_ratio(), Some(0.0));
+        assert_eq!(fragmented.free_bytes(), 2400 * 4096);
+
+        // A block is large enough, so an allocation wouldn't fail.
+        let unfragmented = zone(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1]);
+        assert_eq!(unfragmented.fragmentation_index(), None);
+        assert!(!unfragmented.is_fragmented());
+        assert_eq!(unfragmented.large_free_ratio(), Some(1.0));
+
+        // Nothing free at all, so it'd fail for a lack of memory.
+        let empty = zone(vec![0; 11]);
+        assert_eq!(empty.fragmentation_index(), Some(0.0));
+        assert_eq!(empty.large_free_ratio(), None);
+
+        // Zones with fewer orders are measured at their largest.
+        assert_eq!(zone(vec![4, 1]).order(), 1);
+        assert_eq!(
+            zone(vec![4, 0]).fragmentation_index(),
+            Some(1.0 - 3.0 / 4.0)
+        );
+    }
+
+    #[test]
+    fn own_buddyinfo() {
+        assert!(buddyinfo()
+            .iter()
+            .all(|zone| !zone.zone.is_empty() && zone.page_size > 0));
+    }
+}
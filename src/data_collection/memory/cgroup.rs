@@ -0,0 +1,76 @@
+//! Gets the memory limits of the cgroup bottom runs in on Linux, like those of
+//! a container, from `/sys/fs/cgroup`.
+
+use std::{fs, path::Path};
+
+/// Where the cgroup bottom runs in is mounted. In a container, this is the
+/// container's own cgroup.
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// The hard limit of a cgroup, in cgroup v2 and then v1.
+const MAX_FILES: [&str; 2] = ["memory.max", "memory/memory.limit_in_bytes"];
+
+/// The soft limit of a cgroup, past which it's throttled and reclaimed from.
+const HIGH_FILES: [&str; 2] = ["memory.high", "memory/memory.soft_limit_in_bytes"];
+
+/// How much memory a cgroup uses, counted the same way as its limits.
+const CURRENT_FILES: [&str; 2] = ["memory.current", "memory/memory.usage_in_bytes"];
+
+/// Parses a limit or usage in bytes. This is `max` if there is no limit.
+fn parse_bytes(contents: &str) -> Option<u64> {
+    contents.trim().parse().ok()
+}
+
+/// Reads the first of `files` that exists and has a value.
+fn read_bytes(root: &Path, files: [&str; 2]) -> Option<u64> {
+    files.iter().find_map(|file| {
+        fs::read_to_string(root.join(file))
+            .ok()
+            .and_then(|contents| parse_bytes(&contents))
+    })
+}
+
+/// Returns the hard memory limit of the cgroup bottom runs in, if it has one.
+/// In cgroup v1, a cgroup with no limit has a huge one instead, so limits are
+/// only worth showing if they're below the physical RAM.
+pub(crate) fn detect_cgroup_memory_limit() -> Option<u64> {
+    read_bytes(Path::new(CGROUP_ROOT), MAX_FILES)
+}
+
+/// Returns how much memory the cgroup bottom runs in uses, and its soft limit
+/// if it has one.
+pub(crate) fn get_cgroup_memory_usage() -> (Option<u64>, Option<u64>) {
+    let root = Path::new(CGROUP_ROOT);
+    (
+        read_bytes(root, CURRENT_FILES),
+        read_bytes(root, HIGH_FILES),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn limits() {
+        assert_eq!(parse_bytes("max\n"), None);
+        assert_eq!(parse_bytes("536870912\n"), Some(536870912));
+
+        let root = tempfile::tempdir().unwrap();
+        assert_eq!(read_bytes(root.path(), MAX_FILES), None);
+
+        // cgroup v1 keeps each controller in its own hierarchy.
+        fs::create_dir(root.path().join("memory")).unwrap();
+        fs::write(
+            root.path().join("memory/memory.limit_in_bytes"),
+            "1073741824\n",
+        )
+        .unwrap();
+        assert_eq!(read_bytes(root.path(), MAX_FILES), Some(1073741824));
+
+        fs::write(root.path().join("memory.max"), "536870912\n").unwrap();
+        fs::write(root.path().join("memory.high"), "max\n").unwrap();
+        assert_eq!(read_bytes(root.path(), MAX_FILES), Some(536870912));
+        assert_eq!(read_bytes(root.path(), HIGH_FILES), None);
+    }
+}
@@ -0,0 +1,326 @@
+//! Every field of `/proc/meminfo` on Linux, or of `vm_stat` on macOS, for the
+//! memory details overlay.
+
+/// A single field, like `MemFree` or `Pages free`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemInfoEntry {
+    pub key: String,
+    /// The value in bytes, or just the number for fields that count something
+    /// else, like huge pages.
+    pub value_bytes: u64,
+    /// Whether [`Self::value_bytes`] is a size in bytes.
+    pub is_bytes: bool,
+    /// What the field means, or empty if it's not a known one.
+    pub description: &'static str,
+}
+
+/// What the fields of `/proc/meminfo` mean, from the kernel's
+/// `Documentation/filesystems/proc.rst`.
+#[cfg(target_os = "linux")]
+const MEMINFO_DESCRIPTIONS: &[(&str, &str)] = &[
+    (
+        "MemTotal",
+        "Usable RAM, less reserved memory and the kernel binary",
+    ),
+    ("MemFree", "RAM left completely unused"),
+    (
+        "MemAvailable",
+        "Estimate of RAM available for new programs without swapping",
+    ),
+    ("Buffers", "Temporary storage for raw disk blocks"),
+    (
+        "Cached",
+        "Page cache for files read from disk, less SwapCached",
+    ),
+    (
+        "SwapCached",
+        "Memory swapped out and back in, but still in the swap file",
+    ),
+    (
+        "Active",
+        "Memory used recently, and not reclaimed unless needed",
+    ),
+    (
+        "Inactive",
+        "Memory used less recently, and more likely to be reclaimed",
+    ),
+    ("Active(anon)", "Recently used memory not backed by a file"),
+    (
+        "Inactive(anon)",
+        "Less recently used memory not backed by a file",
+    ),
+    ("Active(file)", "Recently used page cache"),
+    ("Inactive(file)", "Less recently used page cache"),
+    (
+        "Unevictable",
+        "Memory that can't be paged out, like mlocked pages",
+    ),
+    ("Mlocked", "Memory locked with mlock()"),
+    ("SwapTotal", "Total swap space"),
+    ("SwapFree", "Unused swap space"),
+    ("Zswap", "Memory used by the zswap compressed cache"),
+    ("Zswapped", "Swapped out memory held by zswap"),
+    ("Dirty", "Memory waiting to be written back to disk"),
+    ("Writeback", "Memory being written back to disk"),
+    (
+        "AnonPages",
+        "Memory not backed by a file, mapped into page tables",
+    ),
+    ("Mapped", "Files mapped into memory, like libraries"),
+    ("Shmem", "Shared memory and tmpfs"),
+    (
+        "KReclaimable",
+        "Kernel memory that can be reclaimed under pressure",
+    ),
+    ("Slab", "In-kernel data structure caches"),
+    (
+        "SReclaimable",
+        "Part of Slab that might be reclaimed, like caches",
+    ),
+    ("SUnreclaim", "Part of Slab that can't be reclaimed"),
+    ("KernelStack", "Memory used by kernel stacks"),
+    ("ShadowCallStack", "Memory used by shadow call stacks"),
+    (
+        "PageTables",
+        "Memory used by the lowest level of page tables",
+    ),
+    (
+        "SecPageTables",
+        "Memory used by secondary page tables, like KVM's",
+    ),
+    ("NFS_Unstable", "Always zero, as of Linux 6.x"),
+    ("Bounce", "Memory used for block device bounce buffers"),
+    (
+        "WritebackTmp",
+        "Memory used by FUSE for temporary writeback buffers",
+    ),
+    (
+        "CommitLimit",
+        "Total memory that can be allocated with strict overcommit",
+    ),
+    (
+        "Committed_AS",
+        "Memory the system has committed to, used or not",
+    ),
+    ("VmallocTotal", "Total size of the vmalloc address space"),
+    ("VmallocUsed", "Part of the vmalloc address space in use"),
+    (
+        "VmallocChunk",
+        "Largest free block of the vmalloc address space",
+    ),
+    ("Percpu", "Memory used by per-CPU allocations"),
+    (
+        "HardwareCorrupted",
+        "Memory the kernel found to be corrupted",
+    ),
+    (
+        "AnonHugePages",
+        "Memory not backed by a file, in transparent huge pages",
+    ),
+    ("ShmemHugePages", "Shared memory and tmpfs in huge pages"),
+    (
+        "ShmemPmdMapped",
+        "Shared memory mapped into userspace with huge pages",
+    ),
+    ("FileHugePages", "Page cache in huge pages"),
+    (
+        "FilePmdMapped",
+        "Page cache mapped into userspace with huge pages",
+    ),
+    (
+        "CmaTotal",
+        "Memory reserved for the contiguous memory allocator",
+    ),
+    (
+        "CmaFree",
+        "Free part of the contiguous memory allocator's reserve",
+    ),
+    (
+        "Unaccepted",
+        "Memory not yet accepted by the guest, on confidential VMs",
+    ),
+    ("HugePages_Total", "Number of huge pages in the pool"),
+    ("HugePages_Free", "Number of huge pages not yet allocated"),
+    (
+        "HugePages_Rsvd",
+        "Number of huge pages reserved but not yet allocated",
+    ),
+    (
+        "HugePages_Surp",
+        "Number of huge pages over the configured pool size",
+    ),
+    ("Hugepagesize", "Size of the default huge pages"),
+    ("Hugetlb", "Memory used by huge pages of every size"),
+    ("DirectMap4k", "Kernel memory mapped with 4 KiB pages"),
+    ("DirectMap2M", "Kernel memory mapped with 2 MiB pages"),
+    ("DirectMap1G", "Kernel memory mapped with 1 GiB pages"),
+];
+
+/// What the fields of `vm_stat` mean.
+#[cfg(target_os = "macos")]
+const VM_STAT_DESCRIPTIONS: &[(&str, &str)] = &[
+    ("Pages free", "RAM left completely unused"),
+    ("Pages active", "Memory used recently"),
+    (
+        "Pages inactive",
+        "Memory used less recently, and likely to be reclaimed",
+    ),
+    (
+        "Pages speculative",
+        "Pages read ahead that haven't been used yet",
+    ),
+    ("Pages throttled", "Pages waiting to be written back"),
+    (
+        "Pages wired down",
+        "Memory that can't be paged out, like the kernel's",
+    ),
+    ("Pages purgeable", "Memory apps have said can be discarded"),
+    (
+        "Pages stored in compressor",
+        "Memory held compressed by the compressor",
+    ),
+    (
+        "Pages occupied by compressor",
+        "RAM used to hold the compressed memory",
+    ),
+    ("File-backed pages", "Page cache for files read from disk"),
+    ("Anonymous pages", "Memory not backed by a file"),
+];
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn description(descriptions: &[(&str, &'static str)], key: &str) -> &'static str {
+    descriptions
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map_or("", |(_, description)| description)
+}
+
+/// Parses `/proc/meminfo`, which has one field per line like `MemFree:
+/// 1234 kB`. Fields without a unit are counts.
+#[cfg(target_os = "linux")]
+fn parse_meminfo(meminfo: &str) -> Vec<MemInfoEntry> {
+    meminfo
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let mut parts = value.split_whitespace();
+            let value: u64 = parts.next()?.parse().ok()?;
+            let is_bytes = parts.next() == Some("kB");
+
+            Some(MemInfoEntry {
+                key: key.to_string(),
+                value_bytes: if is_bytes { value * 1024 } else { value },
+                is_bytes,
+                description: description(MEMINFO_DESCRIPTIONS, key),
+            })
+        })
+        .collect()
+}
+
+/// Parses the output of `vm_stat`, which starts with the page size and then
+/// has one count of pages per line like `Pages free:    12345.`. Counters of
+/// events, like page faults, are kept as counts.
+#[cfg(target_os = "macos")]
+fn parse_vm_stat(vm_stat: &str) -> Vec<MemInfoEntry> {
+    let mut lines = vm_stat.lines();
+    let page_size = lines
+        .next()
+        .and_then(|header| header.split("page size of ").nth(1))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|size| size.parse::<u64>().ok())
+        .unwrap_or(4096);
+
+    lines
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let key = key.trim().trim_matches('"');
+            let value: u64 = value.trim().trim_end_matches('.').parse().ok()?;
+            let description = description(VM_STAT_DESCRIPTIONS, key);
+            let is_bytes = !description.is_empty();
+
+            Some(MemInfoEntry {
+                key: key.to_string(),
+                value_bytes: if is_bytes { value * page_size } else { value },
+                is_bytes,
+                description,
+            })
+        })
+        .collect()
+}
+
+/// Reads every field of `/proc/meminfo`, or of `vm_stat` on macOS. This is
+/// empty if they can't be read, or on other systems.
+pub fn full_meminfo() -> Vec<MemInfoEntry> {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/meminfo")
+            .map(|meminfo| parse_meminfo(&meminfo))
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("vm_stat")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| parse_vm_stat(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn meminfo() {
+        let entries = parse_meminfo(
+            "MemTotal:       16307204 kB\nMemFree:         1093456 kB\nHugePages_Total:       4\n\
+            Bogus line\nNewField:             12 kB\n",
+        );
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            entries[0],
+            MemInfoEntry {
+                key: "MemTotal".to_string(),
+                value_bytes: 16307204 * 1024,
+                is_bytes: true,
+                description: "Usable RAM, less reserved memory and the kernel binary",
+            }
+        );
+        assert_eq!(entries[2].value_bytes, 4);
+        assert!(!entries[2].is_bytes);
+        assert_eq!(entries[3].description, "");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn own_meminfo() {
+        let entries = full_meminfo();
+        assert!(entries
+            .iter()
+            .any(|entry| entry.key == "MemTotal" && entry.value_bytes > 0));
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn vm_stat() {
+        let entries = parse_vm_stat(
+            "Mach Virtual Memory Statistics: (page size of 16384 bytes)\n\
+            Pages free:                               12345.\n\
+            \"Translation faults\":                  999.\n",
+        );
+
+        assert_eq!(entries[0].value_bytes, 12345 * 16384);
+        assert!(!entries[1].is_bytes);
+        assert_eq!(entries[1].key, "Translation faults");
+    }
+}
@@ -0,0 +1,112 @@
+//! Gets the swap devices in use on Linux, from `/proc/swaps`.
+
+use std::{fs, path::Path};
+
+use super::{SwapDevice, SwapKind};
+
+pub(crate) fn get_swap_devices() -> Option<Vec<SwapDevice>> {
+    let swaps = fs::read_to_string("/proc/swaps").ok()?;
+
+    Some(
+        parse_swaps(&swaps)
+            .map(|mut device| {
+                if device.kind == SwapKind::Zram {
+                    device.compression_ratio = zram_name(&device.path).and_then(|name| {
+                        let mm_stat = Path::new("/sys/block").join(name).join("mm_stat");
+                        fs::read_to_string(mm_stat)
+                            .ok()
+                            .and_then(|mm_stat| compression_ratio(&mm_stat))
+                    });
+                }
+                device
+            })
+            .collect(),
+    )
+}
+
+/// Parses each swap device listed in `/proc/swaps`, skipping the header.
+fn parse_swaps(swaps: &str) -> impl Iterator<Item = SwapDevice> + '_ {
+    // Each line is the path, type, size and used space in KiB, and priority.
+    // Spaces in the path are escaped as `\040`.
+    swaps.lines().skip(1).filter_map(|line| {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let [path, kind, size, used, priority] = fields.as_slice() else {
+            return None;
+        };
+
+        let path = path.replace("\\040", " ");
+        let kind = if zram_name(&path).is_some() {
+            SwapKind::Zram
+        } else {
+            match *kind {
+                "partition" => SwapKind::Partition,
+                "file" => SwapKind::File,
+                _ => SwapKind::Other,
+            }
+        };
+
+        Some(SwapDevice {
+            path,
+            kind,
+            total_bytes: size.parse::<u64>().ok()? * 1024,
+            used_bytes: used.parse::<u64>().ok()? * 1024,
+            priority: priority.parse().ok()?,
+            compression_ratio: None,
+        })
+    })
+}
+
+/// Returns the name of the zram device at a path, like `zram0`.
+fn zram_name(path: &str) -> Option<&str> {
+    path.strip_prefix("/dev/")
+        .filter(|name| name.starts_with("zram"))
+}
+
+/// Gets how much the data in a zram device was compressed by from its
+/// `mm_stat` file, whose first two fields are the original and compressed
+/// sizes in bytes.
+fn compression_ratio(mm_stat: &str) -> Option<f64> {
+    let mut fields = mm_stat.split_whitespace();
+    let original: u64 = fields.next()?.parse().ok()?;
+    let compressed: u64 = fields.next()?.parse().ok()?;
+
+    (compressed > 0).then(|| original as f64 / compressed as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn swaps() {
+        let swaps = "\
+Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority
+/dev/nvme0n1p3                          partition\t8388604\t\t1024\t\t-2
+/swap\\040file                           file\t\t2097148\t\t0\t\t-3
+/dev/zram0                              partition\t4194300\t\t2048\t\t100
+";
+        let devices = parse_swaps(swaps).collect::<Vec<_>>();
+
+        assert_eq!(devices.len(), 3);
+        assert_eq!(devices[0].path, "/dev/nvme0n1p3");
+        assert_eq!(devices[0].kind, SwapKind::Partition);
+        assert_eq!(devices[0].total_bytes, 8388604 * 1024);
+        assert_eq!(devices[0].used_bytes, 1024 * 1024);
+        assert_eq!(devices[0].priority, -2);
+        assert_eq!(devices[1].path, "/swap file");
+        assert_eq!(devices[1].kind, SwapKind::File);
+        assert_eq!(devices[2].kind, SwapKind::Zram);
+        assert_eq!(devices[2].priority, 100);
+    }
+
+    #[test]
+    fn zram() {
+        assert_eq!(zram_name("/dev/zram1"), Some("zram1"));
+        assert_eq!(zram_name("/dev/sda2"), None);
+
+        let mm_stat =
+            "  8192000  2048000  2500000        0  2600000        0        0        0        0\n";
+        assert_eq!(compression_ratio(mm_stat), Some(4.0));
+        assert_eq!(compression_ratio("0 0 0"), None);
+    }
+}
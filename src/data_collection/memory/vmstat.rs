@@ -0,0 +1,46 @@
+//! Gets how many pages have been swapped in and out on Linux, from
+//! `/proc/vmstat`.
+
+use std::fs;
+
+/// The total number of pages swapped in and out since boot.
+pub(crate) fn get_swap_page_counts() -> Option<(u64, u64)> {
+    let vmstat = fs::read_to_string("/proc/vmstat").ok()?;
+    parse_swap_page_counts(&vmstat)
+}
+
+/// Parses the `pswpin` and `pswpout` counters out of `/proc/vmstat`, which has
+/// one counter per line as its name and value.
+fn parse_swap_page_counts(vmstat: &str) -> Option<(u64, u64)> {
+    let mut swap_in = None;
+    let mut swap_out = None;
+
+    for line in vmstat.lines() {
+        match line.split_once(' ') {
+            Some(("pswpin", value)) => swap_in = value.trim().parse().ok(),
+            Some(("pswpout", value)) => swap_out = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some((swap_in?, swap_out?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vmstat() {
+        let vmstat = "\
+nr_free_pages 1187144
+pgpgin 9203440
+pgpgout 15776712
+pswpin 1024
+pswpout 2048
+pgalloc_dma 934
+";
+        assert_eq!(parse_swap_page_counts(vmstat), Some((1024, 2048)));
+        assert_eq!(parse_swap_page_counts("pswpin 1\n"), None);
+    }
+}
@@ -1,20 +1,111 @@
 //! Data collection for network usage/IO.
 
+#[cfg(all(target_os = "linux", feature = "ethtool"))]
+pub mod ethtool;
+#[cfg(target_os = "linux")]
+pub mod ipv6;
+#[cfg(target_os = "linux")]
+pub mod sockets;
+#[cfg(target_os = "linux")]
+pub mod sockstat;
 pub mod sysinfo;
 pub use self::sysinfo::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 /// All units in bits.
 pub struct NetworkHarvest {
     pub rx: u64,
     pub tx: u64,
     pub total_rx: u64,
     pub total_tx: u64,
+    /// The part of the traffic sent over IPv6, which is included in the
+    /// above. Only collected on Linux.
+    #[serde(default)]
+    pub ipv6: Option<Ipv6Harvest>,
+    /// The traffic of each interface kept by the filter, sorted by name.
+    #[serde(default)]
+    pub interfaces: Vec<InterfaceHarvest>,
 }
 
 impl NetworkHarvest {
     pub fn first_run_cleanup(&mut self) {
         self.rx = 0;
         self.tx = 0;
+        if let Some(ipv6) = &mut self.ipv6 {
+            ipv6.rx = 0;
+            ipv6.tx = 0;
+        }
+        for interface in &mut self.interfaces {
+            interface.rx = 0;
+            interface.tx = 0;
+        }
+    }
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+/// The traffic of a single interface, in bits per second like
+/// [`NetworkHarvest`].
+pub struct InterfaceHarvest {
+    pub name: String,
+    pub rx: u64,
+    pub tx: u64,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+/// IPv6 traffic, in bits like [`NetworkHarvest`].
+pub struct Ipv6Harvest {
+    pub rx: u64,
+    pub tx: u64,
+    pub total_rx: u64,
+    pub total_tx: u64,
+    /// The address of the first interface with a global address, or with a
+    /// link-local one if none have any.
+    pub address: Option<String>,
+}
+
+/// More orphaned TCP sockets than this is unusual, and a sign that the orphan
+/// limit may soon be hit.
+pub const HIGH_ORPHANED_TCP: u64 = 10_000;
+
+/// More TCP sockets in `TIME_WAIT` than this is a sign of many short-lived
+/// connections, which can run out of ports.
+pub const HIGH_TIME_WAIT_TCP: u64 = 30_000;
+
+/// How many sockets of each protocol are in use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocketCounts {
+    pub tcp: u64,
+    pub udp: u64,
+    pub raw: u64,
+}
+
+/// How much of the kernel's socket pools is in use, from `/proc/net/sockstat`
+/// and `/proc/net/sockstat6`. Only collected on Linux.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SockstatSummary {
+    /// How many sockets of any kind are open.
+    pub sockets_used: u64,
+    /// The IPv4 sockets in use.
+    pub ipv4: SocketCounts,
+    /// The IPv6 sockets in use, or [`None`] if IPv6 is disabled.
+    pub ipv6: Option<SocketCounts>,
+    /// TCP sockets no longer attached to a file descriptor, but still closing.
+    pub tcp_orphaned: u64,
+    pub tcp_time_wait: u64,
+    /// The memory used by TCP and UDP socket buffers, over both IPv4 and IPv6.
+    pub mem_bytes: u64,
+}
+
+impl SockstatSummary {
+    /// Whether there are more orphaned TCP sockets than [`HIGH_ORPHANED_TCP`].
+    pub fn is_orphaned_high(&self) -> bool {
+        self.tcp_orphaned > HIGH_ORPHANED_TCP
+    }
+
+    /// Whether there are more TCP sockets in `TIME_WAIT` than
+    /// [`HIGH_TIME_WAIT_TCP`].
+    pub fn is_time_wait_high(&self) -> bool {
+        self.tcp_time_wait > HIGH_TIME_WAIT_TCP
     }
 }
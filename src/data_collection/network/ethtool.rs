@@ -0,0 +1,217 @@
+//! Hardware statistics of network interfaces, read from their drivers with the
+//! `SIOCETHTOOL` ioctl like `ethtool -S` does.
+//!
+//! The statistics are different for every driver, so they're kept as named by
+//! the driver. Some drivers need `CAP_NET_ADMIN` to read them; without it, or
+//! for interfaces without a driver that has any, none are returned.
+
+use std::{
+    collections::HashMap,
+    io, mem,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    ptr,
+};
+
+const ETHTOOL_GDRVINFO: u32 = 0x03;
+const ETHTOOL_GSTRINGS: u32 = 0x1b;
+const ETHTOOL_GSTATS: u32 = 0x1d;
+
+/// The string set of the statistics' names.
+const ETH_SS_STATS: u32 = 1;
+const ETH_GSTRING_LEN: usize = 32;
+
+/// `struct ethtool_drvinfo` from `linux/ethtool.h`.
+#[repr(C)]
+#[allow(
+    dead_code,
+    reason = "the unread fields are needed to match the kernel's layout"
+)]
+struct DrvInfo {
+    cmd: u32,
+    driver: [u8; 32],
+    version: [u8; 32],
+    fw_version: [u8; 32],
+    bus_info: [u8; 32],
+    erom_version: [u8; 32],
+    reserved2: [u8; 12],
+    n_priv_flags: u32,
+    n_stats: u32,
+    testinfo_len: u32,
+    eedump_len: u32,
+    regdump_len: u32,
+}
+
+/// Converts a NUL-padded string from the kernel.
+fn kernel_string(bytes: &[u8]) -> String {
+    let len = bytes
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// Splits the names returned by `ETHTOOL_GSTRINGS` into `count` strings.
+fn parse_names(bytes: &[u8], count: usize) -> Vec<String> {
+    bytes
+        .chunks_exact(ETH_GSTRING_LEN)
+        .take(count)
+        .map(kernel_string)
+        .collect()
+}
+
+/// A socket to send ethtool requests for an interface through.
+struct EthtoolSocket {
+    socket: OwnedFd,
+    request: libc::ifreq,
+}
+
+impl EthtoolSocket {
+    fn new(iface: &str) -> io::Result<Self> {
+        let name = iface.as_bytes();
+        if name.is_empty() || name.len() >= libc::IFNAMSIZ || name.contains(&0) {
+            return Err(io::ErrorKind::InvalidInput.into());
+        }
+
+        // SAFETY: `socket` has no preconditions, and the result is checked.
+        let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: The file descriptor was just opened, and nothing else owns it.
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: `ifreq` is plain data, for which all zeroes is valid.
+        let mut request: libc::ifreq = unsafe { mem::zeroed() };
+        for (dst, src) in request.ifr_name.iter_mut().zip(name) {
+            *dst = *src as libc::c_char;
+        }
+
+        Ok(Self { socket, request })
+    }
+
+    /// Sends the ethtool command at the start of `data`, which the driver
+    /// fills in.
+    ///
+    /// # Safety
+    ///
+    /// `data` has to point to a struct of the size the command expects.
+    unsafe fn send<T>(&mut self, data: *mut T) -> io::Result<()> {
+        self.request.ifr_ifru.ifru_data = data.cast();
+
+        // SAFETY: The request has a valid interface name, and the caller
+        // ensures the data is as large as the command needs.
+        let result = unsafe {
+            libc::ioctl(
+                self.socket.as_raw_fd(),
+                libc::SIOCETHTOOL as _,
+                ptr::addr_of_mut!(self.request),
+            )
+        };
+        if result == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn driver_info(&mut self) -> io::Result<DrvInfo> {
+        // SAFETY: `DrvInfo` is plain data, for which all zeroes is valid.
+        let mut info: DrvInfo = unsafe { mem::zeroed() };
+        info.cmd = ETHTOOL_GDRVINFO;
+
+        // SAFETY: `ETHTOOL_GDRVINFO` takes a `struct ethtool_drvinfo`.
+        unsafe { self.send(&mut info) }?;
+
+        Ok(info)
+    }
+
+    fn stat_names(&mut self, count: usize) -> io::Result<Vec<String>> {
+        // `struct ethtool_gstrings`, which is three `u32`s followed by the names.
+        const HEADER_LEN: usize = 3;
+        let mut buffer = vec![0u32; HEADER_LEN + count * ETH_GSTRING_LEN / 4];
+        buffer[0] = ETHTOOL_GSTRINGS;
+        buffer[1] = ETH_SS_STATS;
+        buffer[2] = count as u32;
+
+        // SAFETY: The buffer has room for `count` names after the header.
+        unsafe { self.send(buffer.as_mut_ptr()) }?;
+
+        let count = (buffer[2] as usize).min(count);
+        let names = &buffer[HEADER_LEN..];
+        // SAFETY: The names are within the buffer, and any bytes are valid `u8`s.
+        let bytes =
+            unsafe { std::slice::from_raw_parts(names.as_ptr().cast::<u8>(), names.len() * 4) };
+
+        Ok(parse_names(bytes, count))
+    }
+
+    fn stat_values(&mut self, count: usize) -> io::Result<Vec<u64>> {
+        // `struct ethtool_stats`, which is two `u32`s followed by the values.
+        let mut buffer = vec![0u64; 1 + count];
+        let header = buffer.as_mut_ptr().cast::<u32>();
+        // SAFETY: The first `u64` has room for both `u32`s, and is aligned for them.
+        unsafe {
+            header.write(ETHTOOL_GSTATS);
+            header.add(1).write(count as u32);
+        }
+
+        // SAFETY: The buffer has room for `count` values after the header.
+        unsafe { self.send(buffer.as_mut_ptr()) }?;
+
+        // SAFETY: As above.
+        let count = (unsafe { header.add(1).read() } as usize).min(count);
+        buffer.truncate(1 + count);
+        buffer.remove(0);
+
+        Ok(buffer)
+    }
+}
+
+/// The name of the driver of `iface`, e.g. `e1000e`, if it can be read.
+pub fn get_nic_driver(iface: &str) -> Option<String> {
+    let info = EthtoolSocket::new(iface).ok()?.driver_info().ok()?;
+    let driver = kernel_string(&info.driver);
+
+    (!driver.is_empty()).then_some(driver)
+}
+
+fn read_nic_stats(iface: &str) -> io::Result<HashMap<String, u64>> {
+    let mut socket = EthtoolSocket::new(iface)?;
+    let count = socket.driver_info()?.n_stats as usize;
+    if count == 0 {
+        return Ok(HashMap::new());
+    }
+
+    let names = socket.stat_names(count)?;
+    let values = socket.stat_values(count)?;
+
+    Ok(names.into_iter().zip(values).collect())
+}
+
+/// Gets every hardware statistic of `iface` by the name its driver gives it.
+/// This is empty if they can't be read, e.g. without `CAP_NET_ADMIN`.
+pub fn get_nic_stats(iface: &str) -> HashMap<String, u64> {
+    read_nic_stats(iface).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names() {
+        let mut bytes = vec![0; ETH_GSTRING_LEN * 3];
+        bytes[..10].copy_from_slice(b"rx_packets");
+        bytes[ETH_GSTRING_LEN..ETH_GSTRING_LEN + 13].copy_from_slice(b" tx_queue_0: ");
+
+        assert_eq!(parse_names(&bytes, 2), vec!["rx_packets", " tx_queue_0: "]);
+        assert_eq!(parse_names(&bytes, 5).len(), 3);
+    }
+
+    #[test]
+    fn missing_interface() {
+        assert!(get_nic_stats("not-an-iface").is_empty());
+        assert!(get_nic_stats("much-too-long-to-be-an-interface").is_empty());
+        assert_eq!(get_nic_driver("not-an-iface"), None);
+    }
+}
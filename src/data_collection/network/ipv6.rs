@@ -0,0 +1,177 @@
+//! Gets IPv6 traffic and addresses on Linux.
+//!
+//! Traffic is read from the per-interface counters in `/proc/net/dev_snmp6`,
+//! and addresses from `/proc/net/if_inet6`.
+
+use std::{fs, net::Ipv6Addr, path::Path, time::Instant};
+
+use super::Ipv6Harvest;
+use crate::app::filter::Filter;
+
+/// The scope of a globally reachable address.
+const SCOPE_GLOBAL: u8 = 0x00;
+/// The scope of an address that is only reachable on the same link.
+const SCOPE_LINK: u8 = 0x20;
+
+pub fn get_ipv6_data(
+    prev_net_access_time: Instant, prev_net_rx: &mut u64, prev_net_tx: &mut u64,
+    curr_time: Instant, filter: &Option<Filter>,
+) -> Option<Ipv6Harvest> {
+    let (total_rx, total_tx) = read_traffic(Path::new("/proc/net/dev_snmp6"), filter)?;
+    let address = fs::read_to_string("/proc/net/if_inet6")
+        .ok()
+        .and_then(|if_inet6| primary_address(&if_inet6, filter));
+
+    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+
+    let (rx, tx) = if elapsed_time == 0.0 {
+        (0, 0)
+    } else {
+        (
+            ((total_rx.saturating_sub(*prev_net_rx)) as f64 / elapsed_time) as u64,
+            ((total_tx.saturating_sub(*prev_net_tx)) as f64 / elapsed_time) as u64,
+        )
+    };
+
+    *prev_net_rx = total_rx;
+    *prev_net_tx = total_tx;
+    Some(Ipv6Harvest {
+        rx,
+        tx,
+        total_rx,
+        total_tx,
+        address: address.map(|address| address.to_string()),
+    })
+}
+
+/// Sums the IPv6 bits received and sent by each interface kept by the filter,
+/// or returns [`None`] if IPv6 is unavailable.
+fn read_traffic(dir: &Path, filter: &Option<Filter>) -> Option<(u64, u64)> {
+    let mut total_rx = 0;
+    let mut total_tx = 0;
+
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !Filter::optional_should_keep(filter, &name) {
+            continue;
+        }
+
+        if let Ok(snmp6) = fs::read_to_string(entry.path()) {
+            let (rx, tx) = parse_octets(&snmp6);
+            total_rx += rx * 8;
+            total_tx += tx * 8;
+        }
+    }
+
+    Some((total_rx, total_tx))
+}
+
+/// Parses the bytes received and sent from an interface's `dev_snmp6` file.
+fn parse_octets(snmp6: &str) -> (u64, u64) {
+    let mut rx = 0;
+    let mut tx = 0;
+
+    for line in snmp6.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(key), Some(value)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        match key {
+            "Ip6InOctets" => rx = value.parse().unwrap_or(0),
+            "Ip6OutOctets" => tx = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    (rx, tx)
+}
+
+/// Picks the address to show from `/proc/net/if_inet6` out of those on
+/// interfaces kept by the filter, preferring global addresses over link-local
+/// ones. Loopback addresses are skipped.
+fn primary_address(if_inet6: &str, filter: &Option<Filter>) -> Option<Ipv6Addr> {
+    let mut link_local = None;
+
+    // Each line is the address, interface index, prefix length, scope, flags,
+    // and interface name, with all but the name in hex.
+    for line in if_inet6.lines() {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let [address, _, _, scope, _, name] = fields.as_slice() else {
+            continue;
+        };
+        if !Filter::optional_should_keep(filter, name) {
+            continue;
+        }
+
+        let (Ok(address), Ok(scope)) = (
+            u128::from_str_radix(address, 16),
+            u8::from_str_radix(scope, 16),
+        ) else {
+            continue;
+        };
+        let address = Ipv6Addr::from(address);
+
+        match scope {
+            SCOPE_GLOBAL => return Some(address),
+            SCOPE_LINK => {
+                link_local.get_or_insert(address);
+            }
+            _ => {}
+        }
+    }
+
+    link_local
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const IF_INET6: &str = "\
+00000000000000000000000000000001 01 80 10 80       lo
+fe80000000000000021122fffe334455 02 40 20 80     eth0
+20010db8000000000000000000000042 03 40 00 00    wlan0
+";
+
+    #[test]
+    fn octets() {
+        let snmp6 = "Ip6InReceives \t\t\t1234\nIp6InOctets \t\t\t5000\nIp6OutOctets \t\t\t300\n";
+        assert_eq!(parse_octets(snmp6), (5000, 300));
+        assert_eq!(parse_octets(""), (0, 0));
+    }
+
+    #[test]
+    fn addresses() {
+        assert_eq!(
+            primary_address(IF_INET6, &None),
+            Some("2001:db8::42".parse().unwrap())
+        );
+
+        let no_wlan = Some(Filter::new(true, vec![regex::Regex::new("wlan").unwrap()]));
+        assert_eq!(
+            primary_address(IF_INET6, &no_wlan),
+            Some("fe80::211:22ff:fe33:4455".parse().unwrap())
+        );
+
+        let loopback = IF_INET6.lines().next().unwrap();
+        assert_eq!(primary_address(loopback, &None), None);
+    }
+
+    #[test]
+    fn traffic() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("eth0"),
+            "Ip6InOctets 100\nIp6OutOctets 10\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("lo"), "Ip6InOctets 5\nIp6OutOctets 5\n").unwrap();
+
+        assert_eq!(read_traffic(dir.path(), &None), Some((840, 120)));
+
+        let no_lo = Some(Filter::new(true, vec![regex::Regex::new("^lo$").unwrap()]));
+        assert_eq!(read_traffic(dir.path(), &no_lo), Some((800, 80)));
+        assert_eq!(read_traffic(&dir.path().join("missing"), &None), None);
+    }
+}
@@ -0,0 +1,109 @@
+//! The TCP connections processes have open, found by matching the socket
+//! inodes in `/proc/net/tcp` against the file descriptors of each process.
+//!
+//! Only sockets in bottom's own network namespace are listed there, so
+//! connections made from other namespaces, like those of containers, aren't
+//! counted.
+
+use std::fs;
+
+use hashbrown::HashSet;
+
+use crate::data_collection::processes::Pid;
+
+/// The state of a listening socket in `/proc/net/tcp`.
+const TCP_LISTEN: &str = "0A";
+
+/// Adds the inodes of the connections in a `/proc/net/tcp` or `/proc/net/tcp6`
+/// file. Listening sockets are skipped, as are sockets without an inode, like
+/// those in `TIME_WAIT`, which no process has open anymore.
+fn parse_connection_inodes(text: &str, inodes: &mut HashSet<u64>) {
+    for line in text.lines().skip(1) {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let (Some(state), Some(inode)) = (fields.get(3), fields.get(9)) else {
+            continue;
+        };
+
+        if *state != TCP_LISTEN {
+            if let Ok(inode @ 1..) = inode.parse() {
+                inodes.insert(inode);
+            }
+        }
+    }
+}
+
+/// The inodes of every TCP connection, over both IPv4 and IPv6. This is read
+/// once for all processes, so the files only have to be parsed once.
+pub fn tcp_connection_inodes() -> HashSet<u64> {
+    let mut inodes = HashSet::new();
+    for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(text) = fs::read_to_string(path) {
+            parse_connection_inodes(&text, &mut inodes);
+        }
+    }
+
+    inodes
+}
+
+/// Gets the inode of a socket from the target of a file descriptor's link,
+/// e.g. `socket:[12345]`.
+fn socket_inode(target: &str) -> Option<u64> {
+    target
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Counts how many of `inodes` a process has open. This is [`None`] if its
+/// file descriptors can't be read, e.g. because it belongs to another user.
+pub fn sockets_for_pid(pid: Pid, inodes: &HashSet<u64>) -> Option<u32> {
+    let entries = fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+
+    let count = entries
+        .flatten()
+        .filter_map(|entry| fs::read_link(entry.path()).ok())
+        .filter_map(|target| socket_inode(target.to_str()?))
+        .filter(|inode| inodes.contains(inode))
+        .count();
+
+    Some(count as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn connection_inodes() {
+        let text = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1538 00000000:0000 0A 00000000:00000000 00:00000000 00000000   113        0 23456 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1538 0100007F:D2A4 01 00000000:00000000 00:00000000 00000000   113        0 34567 1 0000000000000000 20 4 30 10 -1
+   2: 0100007F:D2A4 0100007F:1538 06 00000000:00000000 03:00001234 00000000     0        0 0 3 0000000000000000
+   3: 0100007F:D2A6 0100007F:1538 08 00000000:00000000 00:00000000 00000000  1000        0 45678 1 0000000000000000 20 4 30 10 -1
+";
+        let mut inodes = HashSet::new();
+        parse_connection_inodes(text, &mut inodes);
+
+        assert_eq!(inodes, HashSet::from([34567, 45678]));
+    }
+
+    #[test]
+    fn socket_inodes() {
+        assert_eq!(socket_inode("socket:[12345]"), Some(12345));
+        assert_eq!(socket_inode("pipe:[12345]"), None);
+        assert_eq!(socket_inode("/dev/null"), None);
+    }
+
+    #[test]
+    fn own_sockets() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let _client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let _server = listener.accept().unwrap();
+
+        // Both ends of the connection are open in this process, while the
+        // listener isn't counted.
+        let count = sockets_for_pid(std::process::id() as Pid, &tcp_connection_inodes());
+        assert!(count.is_some_and(|count| count >= 2));
+    }
+}
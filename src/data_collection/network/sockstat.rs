@@ -0,0 +1,116 @@
+//! Gets how much of the kernel's socket pools is in use on Linux, from
+//! `/proc/net/sockstat` and `/proc/net/sockstat6`.
+
+use std::fs;
+
+use super::{SocketCounts, SockstatSummary};
+
+/// Reads the summary of socket use, which is all zeroes if it can't be read.
+/// The IPv6 counts are left out if IPv6 is disabled.
+pub fn read() -> SockstatSummary {
+    let page_size = rustix::param::page_size() as u64;
+    let sockstat = fs::read_to_string("/proc/net/sockstat").unwrap_or_default();
+    let sockstat6 = fs::read_to_string("/proc/net/sockstat6").ok();
+
+    parse_sockstat(&sockstat, sockstat6.as_deref(), page_size)
+}
+
+/// Gets a field of a protocol's line, like `inuse` from `TCP: inuse 25 orphan
+/// 0 tw 10 alloc 30 mem 5`. Each line is the protocol followed by pairs of
+/// names and values.
+fn field(text: &str, protocol: &str, name: &str) -> Option<u64> {
+    text.lines().find_map(|line| {
+        let rest = line.strip_prefix(protocol)?.strip_prefix(':')?;
+        let mut parts = rest.split_whitespace();
+
+        std::iter::from_fn(|| Some((parts.next()?, parts.next()?)))
+            .find(|(key, _)| *key == name)
+            .and_then(|(_, value)| value.parse().ok())
+    })
+}
+
+/// Parses `sockstat`, and `sockstat6` if IPv6 is enabled. Socket memory is in
+/// pages, and shared between IPv4 and IPv6, so it's only in `sockstat`.
+fn parse_sockstat(sockstat: &str, sockstat6: Option<&str>, page_size: u64) -> SockstatSummary {
+    let get = |protocol, name| field(sockstat, protocol, name).unwrap_or(0);
+    let mem_pages = get("TCP", "mem") + get("UDP", "mem");
+
+    SockstatSummary {
+        sockets_used: get("sockets", "used"),
+        ipv4: SocketCounts {
+            tcp: get("TCP", "inuse"),
+            udp: get("UDP", "inuse"),
+            raw: get("RAW", "inuse"),
+        },
+        ipv6: sockstat6.map(|sockstat6| {
+            let get = |protocol| field(sockstat6, protocol, "inuse").unwrap_or(0);
+            SocketCounts {
+                tcp: get("TCP6"),
+                udp: get("UDP6"),
+                raw: get("RAW6"),
+            }
+        }),
+        tcp_orphaned: get("TCP", "orphan"),
+        tcp_time_wait: get("TCP", "tw"),
+        mem_bytes: mem_pages * page_size,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let sockstat = "\
+sockets: used 1234
+TCP: inuse 25 orphan 12001 tw 10 alloc 30 mem 5
+UDP: inuse 7 mem 3
+UDPLITE: inuse 0
+RAW: inuse 1
+FRAG: inuse 0 memory 0
+";
+        let sockstat6 = "\
+TCP6: inuse 4
+UDP6: inuse 2
+UDPLITE6: inuse 0
+RAW6: inuse 0
+FRAG6: inuse 0 memory 0
+";
+        let summary = parse_sockstat(sockstat, Some(sockstat6), 4096);
+
+        assert_eq!(
+            summary,
+            SockstatSummary {
+                sockets_used: 1234,
+                ipv4: SocketCounts {
+                    tcp: 25,
+                    udp: 7,
+                    raw: 1,
+                },
+                ipv6: Some(SocketCounts {
+                    tcp: 4,
+                    udp: 2,
+                    raw: 0,
+                }),
+                tcp_orphaned: 12001,
+                tcp_time_wait: 10,
+                mem_bytes: 8 * 4096,
+            }
+        );
+        assert!(summary.is_orphaned_high());
+        assert!(!summary.is_time_wait_high());
+
+        // UDPLITE shouldn't be mistaken for UDP, and IPv6 can be disabled.
+        let summary = parse_sockstat("UDPLITE: inuse 9\nUDP: inuse 3 mem 1\n", None, 4096);
+        assert_eq!(summary.ipv4.udp, 3);
+        assert_eq!(summary.ipv6, None);
+        assert_eq!(parse_sockstat("", None, 4096), SockstatSummary::default());
+    }
+
+    #[test]
+    fn own_sockstat() {
+        let _listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        assert!(read().sockets_used > 0);
+    }
+}
@@ -4,17 +4,25 @@ use std::time::Instant;
 
 use sysinfo::Networks;
 
-use super::NetworkHarvest;
+use super::{InterfaceHarvest, NetworkHarvest};
 use crate::app::filter::Filter;
 
-// TODO: Eventually make it so that this thing also takes individual usage into
-// account, so we can show per-interface!
 pub fn get_network_data(
     networks: &Networks, prev_net_access_time: Instant, prev_net_rx: &mut u64,
     prev_net_tx: &mut u64, curr_time: Instant, filter: &Option<Filter>,
 ) -> NetworkHarvest {
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut interfaces = Vec::new();
+
+    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+    let rate = |bytes: u64| {
+        if elapsed_time == 0.0 {
+            0
+        } else {
+            (bytes as f64 * 8.0 / elapsed_time) as u64
+        }
+    };
 
     for (name, network) in networks {
         let to_keep = if let Some(filter) = filter {
@@ -26,10 +34,16 @@ pub fn get_network_data(
         if to_keep {
             total_rx += network.total_received() * 8;
             total_tx += network.total_transmitted() * 8;
+
+            // These are the bytes since the last refresh.
+            interfaces.push(InterfaceHarvest {
+                name: name.clone(),
+                rx: rate(network.received()),
+                tx: rate(network.transmitted()),
+            });
         }
     }
-
-    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+    interfaces.sort_unstable_by(|a, b| a.name.cmp(&b.name));
 
     let (rx, tx) = if elapsed_time == 0.0 {
         (0, 0)
@@ -47,5 +61,7 @@ pub fn get_network_data(
         tx,
         total_rx,
         total_tx,
+        ipv6: None,
+        interfaces,
     }
 }
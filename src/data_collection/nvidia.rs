@@ -8,19 +8,15 @@ use nvml_wrapper::{
 use crate::{
     app::{filter::Filter, layout_manager::UsedWidgets},
     data_collection::{
+        gpu::GpuUsage,
         memory::MemHarvest,
         temperature::{TempHarvest, TemperatureType},
+        GpusData,
     },
 };
 
 pub static NVML_DATA: OnceLock<Result<Nvml, NvmlError>> = OnceLock::new();
 
-pub struct GpusData {
-    pub memory: Option<Vec<(String, MemHarvest)>>,
-    pub temperature: Option<Vec<TempHarvest>>,
-    pub procs: Option<(u64, Vec<HashMap<u32, (u64, u32)>>)>,
-}
-
 /// Returns the GPU data from NVIDIA cards.
 #[inline]
 pub fn get_nvidia_vecs(
@@ -30,6 +26,7 @@ pub fn get_nvidia_vecs(
         if let Ok(num_gpu) = nvml.device_count() {
             let mut temp_vec = Vec::with_capacity(num_gpu as usize);
             let mut mem_vec = Vec::with_capacity(num_gpu as usize);
+            let mut usage_vec = Vec::with_capacity(num_gpu as usize);
             let mut proc_vec = Vec::with_capacity(num_gpu as usize);
             let mut total_mem = 0;
 
@@ -50,6 +47,17 @@ pub fn get_nvidia_vecs(
                                         },
                                     },
                                 ));
+                                usage_vec.push(GpuUsage {
+                                    utilization: device
+                                        .utilization_rates()
+                                        .ok()
+                                        .map(|utilization| utilization.gpu),
+                                    // NVML reports power in milliwatts.
+                                    power_watts: device
+                                        .power_usage()
+                                        .ok()
+                                        .map(|power| f64::from(power) / 1000.0),
+                                });
                             }
                         }
 
@@ -147,6 +155,11 @@ pub fn get_nvidia_vecs(
                 } else {
                     None
                 },
+                usage: if !usage_vec.is_empty() {
+                    Some(usage_vec)
+                } else {
+                    None
+                },
                 temperature: if !temp_vec.is_empty() {
                     Some(temp_vec)
                 } else {
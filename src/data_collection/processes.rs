@@ -31,9 +31,16 @@ cfg_if! {
     }
 }
 
+mod state;
+mod user_count;
+
 use std::{borrow::Cow, time::Duration};
 
-use super::{error::CollectionResult, DataCollector};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+pub use self::{state::*, user_count::*};
+use super::{cgroups::CgroupCpu, error::CollectionResult, DataCollector};
 
 cfg_if! {
     if #[cfg(target_family = "windows")] {
@@ -45,7 +52,35 @@ cfg_if! {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+/// The namespaces a process belongs to, as the inode numbers of its
+/// `/proc/<PID>/ns/` links. An inode of 0 means the namespace is unknown. This
+/// is only collected on Linux.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessNamespaces {
+    pub pid_ns: u64,
+    pub net_ns: u64,
+    pub mnt_ns: u64,
+    pub user_ns: u64,
+}
+
+impl ProcessNamespaces {
+    /// Keeps only the namespaces shared with another set of namespaces,
+    /// marking the rest as unknown.
+    pub fn intersect(&mut self, other: &ProcessNamespaces) {
+        for (ns, other_ns) in [
+            (&mut self.pid_ns, other.pid_ns),
+            (&mut self.net_ns, other.net_ns),
+            (&mut self.mnt_ns, other.mnt_ns),
+            (&mut self.user_ns, other.user_ns),
+        ] {
+            if *ns != other_ns {
+                *ns = 0;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProcessHarvest {
     /// The pid of the process.
     pub pid: Pid,
@@ -56,6 +91,15 @@ pub struct ProcessHarvest {
     /// CPU usage as a percentage.
     pub cpu_usage_percent: f32,
 
+    /// CPU usage as a percentage of a single core, so a process keeping one
+    /// core busy is at 100%, whether or not `cpu_usage_percent` is normalized.
+    #[serde(default)]
+    pub cpu_usage_percent_per_core: f32,
+
+    /// CPU usage as a percentage of all cores together.
+    #[serde(default)]
+    pub cpu_usage_percent_of_total: f32,
+
     /// Memory usage as a percentage.
     pub mem_usage_percent: f32,
 
@@ -83,9 +127,51 @@ pub struct ProcessHarvest {
     /// The current state of the process (e.g. zombie, asleep).
     pub process_state: (String, char),
 
+    /// The number of threads in the process, if known. This is only collected
+    /// on Linux.
+    pub num_threads: Option<u32>,
+
     /// Cumulative process uptime.
     pub time: Duration,
 
+    /// Minor page faults per second.
+    pub minflt_per_sec: f64,
+
+    /// Major page faults per second.
+    pub majflt_per_sec: f64,
+
+    /// The kernel function the process is waiting in, if any. This is only
+    /// collected on Linux.
+    pub wchan: Option<String>,
+
+    /// How long the process has been waiting in its current wait channel.
+    pub wchan_time: Duration,
+
+    /// The namespaces the process belongs to.
+    pub namespaces: ProcessNamespaces,
+
+    /// The CPU quota of the cgroup the process is in, if it has one. This is
+    /// only collected on Linux.
+    pub cgroup_cpu: Option<CgroupCpu>,
+
+    /// The name or short ID of the container the process is in, if any. This
+    /// is only collected on Linux.
+    pub container: Option<String>,
+
+    /// The proportional set size in bytes, if it was read. This is only
+    /// collected on Linux, and only if the PSS column is shown or PSS is
+    /// computed for the memory widget.
+    pub pss_bytes: Option<u64>,
+
+    /// How many TCP connections the process has open, if they were counted.
+    /// This is only collected on Linux, and only if the TCP column is shown.
+    pub tcp_connections: Option<u32>,
+
+    /// How many inotify watches the process has, over all of its inotify
+    /// instances. This is only collected on Linux, and only if the inotify
+    /// column is shown.
+    pub inotify_watches: Option<u32>,
+
     /// This is the *effective* user ID of the process. This is only used on
     /// Unix platforms.
     #[cfg(target_family = "unix")]
@@ -112,15 +198,48 @@ pub struct ProcessHarvest {
 }
 
 impl ProcessHarvest {
+    /// Fills in the CPU usage both per core and of all cores from
+    /// `cpu_usage_percent`, which is per core if `is_per_core` is set.
+    pub(crate) fn set_cpu_usage_modes(&mut self, is_per_core: bool, num_cpus: usize) {
+        let num_cpus = num_cpus.max(1) as f32;
+        (
+            self.cpu_usage_percent_per_core,
+            self.cpu_usage_percent_of_total,
+        ) = if is_per_core {
+            (self.cpu_usage_percent, self.cpu_usage_percent / num_cpus)
+        } else {
+            (self.cpu_usage_percent * num_cpus, self.cpu_usage_percent)
+        };
+    }
+
     pub(crate) fn add(&mut self, rhs: &ProcessHarvest) {
         self.cpu_usage_percent += rhs.cpu_usage_percent;
+        self.cpu_usage_percent_per_core += rhs.cpu_usage_percent_per_core;
+        self.cpu_usage_percent_of_total += rhs.cpu_usage_percent_of_total;
         self.mem_usage_bytes += rhs.mem_usage_bytes;
         self.mem_usage_percent += rhs.mem_usage_percent;
         self.read_bytes_per_sec += rhs.read_bytes_per_sec;
         self.write_bytes_per_sec += rhs.write_bytes_per_sec;
         self.total_read_bytes += rhs.total_read_bytes;
         self.total_write_bytes += rhs.total_write_bytes;
+        self.num_threads = self
+            .num_threads
+            .zip(rhs.num_threads)
+            .map(|(lhs, rhs)| lhs + rhs);
         self.time = self.time.max(rhs.time);
+        self.minflt_per_sec += rhs.minflt_per_sec;
+        self.majflt_per_sec += rhs.majflt_per_sec;
+        if self.wchan != rhs.wchan {
+            self.wchan = None;
+        }
+        self.wchan_time = self.wchan_time.min(rhs.wchan_time);
+        self.namespaces.intersect(&rhs.namespaces);
+        if self.cgroup_cpu != rhs.cgroup_cpu {
+            self.cgroup_cpu = None;
+        }
+        if self.container != rhs.container {
+            self.container = None;
+        }
         #[cfg(feature = "gpu")]
         {
             self.gpu_mem += rhs.gpu_mem;
@@ -130,13 +249,180 @@ impl ProcessHarvest {
     }
 }
 
+/// A single thread of a process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadInfo {
+    /// The thread ID.
+    pub tid: Pid,
+
+    /// The name of the thread.
+    pub name: String,
+
+    /// The current state of the thread, represented by a char.
+    pub state: char,
+
+    /// The total time the thread has been scheduled for.
+    pub cpu_time: Duration,
+}
+
+/// Reads the threads of the given process on demand, rather than as part of
+/// regular collection. This is only supported on Linux, and returns nothing
+/// elsewhere.
+pub fn collect_threads(pid: Pid) -> Vec<ThreadInfo> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            linux::collect_threads(pid)
+        } else {
+            let _ = pid;
+            vec![]
+        }
+    }
+}
+
+/// A soft and hard resource limit of a process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RlimitEntry {
+    /// What is limited, like `Max open files`.
+    pub name: String,
+
+    /// The soft limit, or [`None`] if unlimited.
+    pub soft: Option<u64>,
+
+    /// The hard limit, or [`None`] if unlimited.
+    pub hard: Option<u64>,
+
+    /// The units of the limits, if any.
+    pub units: String,
+
+    /// How much of the limited resource the process currently uses, if known.
+    pub usage: Option<u64>,
+}
+
+/// Reads the resource limits of the given process on demand, rather than as
+/// part of regular collection. This is only supported on Linux, and returns
+/// nothing elsewhere.
+pub fn read_rlimits(pid: Pid) -> Vec<RlimitEntry> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            linux::read_rlimits(pid)
+        } else {
+            let _ = pid;
+            vec![]
+        }
+    }
+}
+
+/// The memory usage of a mapped region of a process, in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmapRegion {
+    /// What backs the region. This is a path for file-backed regions, a
+    /// pseudo-path like `[heap]` or `[stack]`, or empty for anonymous ones.
+    pub path: String,
+
+    pub rss: u64,
+
+    /// The proportional set size, where shared pages are split between the
+    /// processes sharing them.
+    pub pss: u64,
+
+    pub shared_clean: u64,
+    pub shared_dirty: u64,
+    pub private_clean: u64,
+    pub private_dirty: u64,
+}
+
+/// Reads the memory regions of the given process on demand, like
+/// [`read_rlimits`]. This is only supported on Linux, and returns nothing
+/// elsewhere.
+pub fn read_smaps(pid: Pid) -> Vec<SmapRegion> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            linux::read_smaps(pid)
+        } else {
+            let _ = pid;
+            vec![]
+        }
+    }
+}
+
+/// Which memory safety features a process has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecurityFlags {
+    /// Whether the addresses the process maps things at are randomized.
+    pub aslr: bool,
+
+    /// Whether the executable was built with stack canaries, or [`None`] if
+    /// that can't be told, like for statically linked executables.
+    pub stack_canary: Option<bool>,
+
+    /// Whether the stack isn't executable.
+    pub nx: bool,
+
+    /// Whether the executable is position-independent, so ASLR moves it too
+    /// rather than only its libraries, stack, and heap.
+    pub pie: bool,
+}
+
+/// Reads the memory safety features of the given process on demand, like
+/// [`read_rlimits`]. This is [`None`] if they can't be read, and is only
+/// supported on Linux.
+pub fn read_security_flags(pid: Pid) -> Option<SecurityFlags> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            linux::read_security_flags(pid)
+        } else {
+            let _ = pid;
+            None
+        }
+    }
+}
+
+/// A process that may only run on some of the cores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedProcess {
+    pub pid: Pid,
+    pub name: String,
+
+    /// The cores the process may run on, in order.
+    pub cores: Vec<usize>,
+}
+
+/// Finds the processes that can't run on all of the first `num_cores` cores.
+/// This is only supported on Linux, and returns nothing elsewhere.
+pub fn pinned_processes(num_cores: usize) -> Vec<PinnedProcess> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            linux::pinned_processes(num_cores)
+        } else {
+            let _ = num_cores;
+            vec![]
+        }
+    }
+}
+
+/// Inverts the affinity of pinned processes, giving the PIDs of the processes
+/// pinned to each core. Processes pinned to fewer cores come first, as they're
+/// the ones most likely to be keeping a core busy.
+pub fn affinity_map(pinned: &[PinnedProcess]) -> HashMap<usize, Vec<Pid>> {
+    let mut pinned = pinned.iter().collect::<Vec<_>>();
+    pinned.sort_by_key(|process| (process.cores.len(), process.pid));
+
+    let mut map: HashMap<usize, Vec<Pid>> = HashMap::new();
+    for process in pinned {
+        for &core in &process.cores {
+            map.entry(core).or_default().push(process.pid);
+        }
+    }
+
+    map
+}
+
 impl DataCollector {
     pub(crate) fn get_processes(&mut self) -> CollectionResult<Vec<ProcessHarvest>> {
         cfg_if! {
             if #[cfg(target_os = "linux")] {
                 let time_diff = self.data.collection_time
-                    .duration_since(self.last_collection_time)
-                    .as_secs();
+                    .duration_since(self.last_collection_time);
 
                 linux_process_data(
                     self,
@@ -150,3 +436,38 @@ impl DataCollector {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn affinity_map_order() {
+        let pinned = |pid, cores: &[usize]| PinnedProcess {
+            pid,
+            name: pid.to_string(),
+            cores: cores.to_vec(),
+        };
+        let map = affinity_map(&[pinned(3, &[0, 1]), pinned(2, &[1]), pinned(1, &[0, 1])]);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&0], vec![1, 3]);
+        assert_eq!(map[&1], vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn cpu_usage_modes() {
+        let mut process = ProcessHarvest {
+            cpu_usage_percent: 6.25,
+            ..Default::default()
+        };
+        process.set_cpu_usage_modes(false, 16);
+        assert_eq!(process.cpu_usage_percent_per_core, 100.0);
+        assert_eq!(process.cpu_usage_percent_of_total, 6.25);
+
+        process.cpu_usage_percent = 100.0;
+        process.set_cpu_usage_modes(true, 16);
+        assert_eq!(process.cpu_usage_percent_per_core, 100.0);
+        assert_eq!(process.cpu_usage_percent_of_total, 6.25);
+    }
+}
@@ -1,29 +1,65 @@
 //! Process data collection for Linux.
 
+mod affinity;
+mod inotify;
+mod limits;
 mod process;
+mod security;
+mod smaps;
+#[cfg(feature = "flame")]
+mod stack_sampler;
 
 use std::{
     fs::{self, File},
     io::{BufRead, BufReader},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+pub(crate) use affinity::pinned_processes;
 use hashbrown::HashSet;
+pub(crate) use inotify::{count_inotify_watches, max_user_watches};
+pub(crate) use limits::read_rlimits;
 use process::*;
-use sysinfo::ProcessStatus;
-
-use super::{Pid, ProcessHarvest, UserTable};
-use crate::data_collection::{error::CollectionResult, DataCollector};
+pub(crate) use security::read_security_flags;
+use smaps::read_pss;
+pub(crate) use smaps::{read_smaps, total_pss};
+#[cfg(feature = "flame")]
+pub use stack_sampler::sample_stacks;
+
+use super::{Pid, ProcessHarvest, ProcessState, ThreadInfo, UserTable};
+#[cfg(feature = "gpu")]
+use crate::data_collection::gpu::{process_gpu_mem, process_gpu_util};
+use crate::{
+    app::visible_columns::VisibleColumns,
+    data_collection::{
+        cgroups::Cgroup,
+        error::CollectionResult,
+        network::sockets::{sockets_for_pid, tcp_connection_inodes},
+        DataCollector,
+    },
+};
 
 /// Maximum character length of a `/proc/<PID>/stat`` process name.
 /// If it's equal or greater, then we instead refer to the command for the name.
 const MAX_STAT_NAME_LEN: usize = 15;
 
+/// How much is allocated up front for the buffer that each process' files are
+/// read into. This fits most `stat`, `io`, and `cmdline` files, so the buffer
+/// rarely has to grow.
+const PROC_BUFFER_CAPACITY: usize = 4096;
+
 #[derive(Debug, Clone, Default)]
 pub struct PrevProcDetails {
     total_read_bytes: u64,
     total_write_bytes: u64,
     cpu_time: u64,
+    minflt: u64,
+    majflt: u64,
+    wchan: Option<String>,
+    /// When the process started waiting in its current wait channel.
+    wchan_since: Option<Instant>,
+    /// The last proportional set size read, as it's only read every so often.
+    pss: Option<u64>,
 }
 
 /// Given `/proc/stat` file contents, determine the idle and non-idle values of
@@ -129,15 +165,39 @@ fn get_linux_cpu_usage(
     }
 }
 
+/// Returns how many page faults happened per second since the last harvest,
+/// using the exact time between them so rates aren't skewed by sub-second
+/// update intervals.
+fn fault_rate(faults: u64, prev_faults: u64, time_difference: Duration) -> f64 {
+    faults.saturating_sub(prev_faults) as f64 / time_difference.as_secs_f64()
+}
+
+/// Returns when the process started waiting in the given wait channel, which
+/// is carried over from the previous harvest if it hasn't changed.
+fn get_wchan_since(
+    prev_proc: &PrevProcDetails, wchan: &Option<String>, now: Instant,
+) -> Option<Instant> {
+    match wchan {
+        Some(_) if prev_proc.wchan == *wchan => prev_proc.wchan_since.or(Some(now)),
+        Some(_) => Some(now),
+        None => None,
+    }
+}
+
+/// Returns the harvested process and the details to compare against on the next
+/// harvest.
 fn read_proc(
     prev_proc: &PrevProcDetails, process: Process, args: ReadProcArgs, user_table: &mut UserTable,
-) -> CollectionResult<(ProcessHarvest, u64)> {
+) -> CollectionResult<(ProcessHarvest, PrevProcDetails)> {
     let Process {
         pid: _,
         uid,
         stat,
         io,
         cmdline,
+        wchan,
+        namespaces,
+        cgroup: _,
     } = process;
 
     let ReadProcArgs {
@@ -145,8 +205,11 @@ fn read_proc(
         cpu_usage,
         cpu_fraction,
         total_memory,
-        time_difference_in_secs,
+        time_difference,
         uptime,
+        visible_columns,
+        collection_time,
+        read_pss: should_read_pss,
     } = args;
 
     let (command, name) = {
@@ -181,7 +244,7 @@ fn read_proc(
 
     let process_state_char = stat.state;
     let process_state = (
-        ProcessStatus::from(process_state_char).to_string(),
+        ProcessState::from(process_state_char).to_string(),
         process_state_char,
     );
     let (cpu_usage_percent, new_process_times) = get_linux_cpu_usage(
@@ -195,6 +258,8 @@ fn read_proc(
     let mem_usage_bytes = stat.rss_bytes();
     let mem_usage_percent = (mem_usage_bytes as f64 / total_memory as f64 * 100.0) as f32;
 
+    let time_difference_in_secs = time_difference.as_secs();
+
     // This can fail if permission is denied!
     let (total_read_bytes, total_write_bytes, read_bytes_per_sec, write_bytes_per_sec) =
         if let Ok(io) = io {
@@ -223,13 +288,29 @@ fn read_proc(
             (0, 0, 0, 0)
         };
 
+    let (minflt_per_sec, majflt_per_sec) =
+        if visible_columns.contains(VisibleColumns::PAGE_FAULTS) && !time_difference.is_zero() {
+            (
+                fault_rate(stat.minflt, prev_proc.minflt, time_difference),
+                fault_rate(stat.majflt, prev_proc.majflt, time_difference),
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+    let wchan_since = get_wchan_since(prev_proc, &wchan, collection_time);
+    let wchan_time = wchan_since.map_or(Duration::ZERO, |since| {
+        collection_time.saturating_duration_since(since)
+    });
+
+    let pss = if should_read_pss {
+        read_pss(process.pid)
+    } else {
+        prev_proc.pss
+    };
+
     let user = uid
-        .and_then(|uid| {
-            user_table
-                .get_uid_to_username_mapping(uid)
-                .map(Into::into)
-                .ok()
-        })
+        .map(|uid| user_table.get_username_or_uid(uid).into())
         .unwrap_or_else(|| "N/A".into());
 
     let time = if let Ok(ticks_per_sec) = u32::try_from(rustix::param::clock_ticks_per_second()) {
@@ -247,6 +328,9 @@ fn read_proc(
             pid: process.pid,
             parent_pid,
             cpu_usage_percent,
+            // Both are filled in once every process is collected.
+            cpu_usage_percent_per_core: 0.0,
+            cpu_usage_percent_of_total: 0.0,
             mem_usage_percent,
             mem_usage_bytes,
             name,
@@ -256,9 +340,20 @@ fn read_proc(
             total_read_bytes,
             total_write_bytes,
             process_state,
+            num_threads: Some(stat.num_threads),
             uid,
             user,
             time,
+            minflt_per_sec,
+            majflt_per_sec,
+            wchan: wchan.clone(),
+            wchan_time,
+            namespaces,
+            cgroup_cpu: None,
+            container: None,
+            pss_bytes: pss,
+            tcp_connections: None,
+            inotify_watches: None,
             #[cfg(feature = "gpu")]
             gpu_mem: 0,
             #[cfg(feature = "gpu")]
@@ -266,7 +361,16 @@ fn read_proc(
             #[cfg(feature = "gpu")]
             gpu_util: 0,
         },
-        new_process_times,
+        PrevProcDetails {
+            total_read_bytes,
+            total_write_bytes,
+            cpu_time: new_process_times,
+            minflt: stat.minflt,
+            majflt: stat.majflt,
+            wchan,
+            wchan_since,
+            pss,
+        },
     ))
 }
 
@@ -291,14 +395,22 @@ pub(crate) struct ReadProcArgs {
     pub(crate) cpu_usage: f64,
     pub(crate) cpu_fraction: f64,
     pub(crate) total_memory: u64,
-    pub(crate) time_difference_in_secs: u64,
+    /// The time since the last harvest.
+    pub(crate) time_difference: Duration,
     pub(crate) uptime: u64,
+    pub(crate) visible_columns: VisibleColumns,
+    pub(crate) collection_time: Instant,
+    /// Whether to read the proportional set size this time, rather than
+    /// keeping the last one.
+    pub(crate) read_pss: bool,
 }
 
 pub(crate) fn linux_process_data(
-    collector: &mut DataCollector, time_difference_in_secs: u64,
+    collector: &mut DataCollector, time_difference: Duration,
 ) -> CollectionResult<Vec<ProcessHarvest>> {
     let total_memory = collector.total_memory();
+    let read_pss = collector.is_pss_due()
+        && (collector.compute_pss || collector.visible_columns.contains(VisibleColumns::PSS));
     let prev_proc = PrevProc {
         prev_idle: &mut collector.prev_idle,
         prev_non_idle: &mut collector.prev_non_idle,
@@ -350,29 +462,52 @@ pub(crate) fn linux_process_data(
         cpu_usage,
         cpu_fraction,
         total_memory,
-        time_difference_in_secs,
+        time_difference,
         uptime: sysinfo::System::uptime(),
+        visible_columns: collector.visible_columns,
+        collection_time: collector.data.collection_time,
+        read_pss,
     };
 
-    let process_vector: Vec<ProcessHarvest> = pids
+    let collect_cgroups = args.visible_columns.contains(VisibleColumns::CGROUPS);
+    let collect_containers = args.visible_columns.contains(VisibleColumns::CONTAINERS);
+    // The connections are listed once, and then matched against each process.
+    let tcp_inodes = args
+        .visible_columns
+        .contains(VisibleColumns::TCP)
+        .then(tcp_connection_inodes);
+    let collect_inotify = args.visible_columns.contains(VisibleColumns::INOTIFY);
+    let mut buffer = String::with_capacity(PROC_BUFFER_CAPACITY);
+    let (mut process_vector, cgroups): (Vec<ProcessHarvest>, Vec<Option<Cgroup>>) = pids
         .filter_map(|pid_path| {
-            if let Ok(process) = Process::from_path(pid_path) {
+            if let Ok(mut process) = Process::from_path(
+                pid_path,
+                &mut buffer,
+                args.visible_columns.contains(VisibleColumns::WCHAN),
+                args.visible_columns.contains(VisibleColumns::NAMESPACES),
+                collect_cgroups || collect_containers,
+            ) {
                 let pid = process.pid;
+                let cgroup = process.cgroup.take();
+                let container = if collect_containers {
+                    cgroup.as_ref().and_then(|cgroup| {
+                        collector
+                            .containers
+                            .pid_to_container_name(pid, cgroup.path())
+                    })
+                } else {
+                    None
+                };
                 let prev_proc_details = pid_mapping.entry(pid).or_default();
 
-                #[cfg_attr(not(feature = "gpu"), expect(unused_mut))]
-                if let Ok((mut process_harvest, new_process_times)) =
+                if let Ok((mut process_harvest, new_proc_details)) =
                     read_proc(prev_proc_details, process, args, user_table)
                 {
                     #[cfg(feature = "gpu")]
                     if let Some(gpus) = &collector.gpu_pids {
-                        gpus.iter().for_each(|gpu| {
-                            // add mem/util for all gpus to pid
-                            if let Some((mem, util)) = gpu.get(&(pid as u32)) {
-                                process_harvest.gpu_mem += mem;
-                                process_harvest.gpu_util += util;
-                            }
-                        });
+                        process_harvest.gpu_mem =
+                            process_gpu_mem(gpus, pid as u32).unwrap_or_default();
+                        process_harvest.gpu_util = process_gpu_util(gpus, pid as u32);
                         if let Some(gpu_total_mem) = &collector.gpus_total_mem {
                             process_harvest.gpu_mem_percent =
                                 (process_harvest.gpu_mem as f64 / *gpu_total_mem as f64 * 100.0)
@@ -380,30 +515,112 @@ pub(crate) fn linux_process_data(
                         }
                     }
 
-                    prev_proc_details.cpu_time = new_process_times;
-                    prev_proc_details.total_read_bytes = process_harvest.total_read_bytes;
-                    prev_proc_details.total_write_bytes = process_harvest.total_write_bytes;
+                    process_harvest.container = container;
+                    process_harvest.tcp_connections = tcp_inodes
+                        .as_ref()
+                        .and_then(|inodes| sockets_for_pid(pid, inodes));
+                    process_harvest.inotify_watches = if collect_inotify {
+                        count_inotify_watches(pid)
+                    } else {
+                        None
+                    };
+                    *prev_proc_details = new_proc_details;
 
                     pids_to_clear.remove(&pid);
-                    return Some(process_harvest);
+                    return Some((process_harvest, cgroup));
                 }
             }
 
             None
         })
-        .collect();
+        .unzip();
+
+    if collect_cgroups {
+        // Quotas are compared against the CPU usage of processes, so they have
+        // to be in the same units.
+        let percent_per_cpu = if unnormalized_cpu {
+            100.0
+        } else {
+            100.0 / collector.sys.system.cpus().len().max(1) as f64
+        };
+
+        let usage = process_vector
+            .iter()
+            .zip(&cgroups)
+            .filter_map(|(process, cgroup)| {
+                Some((cgroup.as_ref()?, process.cpu_usage_percent.into()))
+            });
+        let limits = collector
+            .cgroups
+            .update(usage, percent_per_cpu, args.collection_time);
+
+        for (process, cgroup) in process_vector.iter_mut().zip(&cgroups) {
+            process.cgroup_cpu = cgroup
+                .as_ref()
+                .and_then(|cgroup| limits.get(cgroup).copied());
+        }
+    }
 
     pids_to_clear.iter().for_each(|pid| {
         pid_mapping.remove(pid);
+        collector.containers.remove(pid);
     });
 
     Ok(process_vector)
 }
 
+/// Reads the threads of a process from `/proc/<PID>/task/`. Threads that exit
+/// while being read are skipped.
+pub(crate) fn collect_threads(pid: Pid) -> Vec<ThreadInfo> {
+    let Ok(entries) = fs::read_dir(format!("/proc/{pid}/task")) else {
+        return vec![];
+    };
+    let ticks_per_sec = rustix::param::clock_ticks_per_second() as f64;
+    let mut buffer = String::new();
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let tid: Pid = entry.file_name().to_str()?.parse().ok()?;
+            let path = entry.path();
+
+            buffer.clear();
+            let stat = Stat::from_file(File::open(path.join("stat")).ok()?, &mut buffer).ok()?;
+            let name = match fs::read_to_string(path.join("comm")) {
+                Ok(comm) => comm.trim_end().to_string(),
+                Err(_) => stat.comm,
+            };
+            let cpu_time = if ticks_per_sec > 0.0 {
+                Duration::from_secs_f64((stat.utime + stat.stime) as f64 / ticks_per_sec)
+            } else {
+                Duration::ZERO
+            };
+
+            Some(ThreadInfo {
+                tid,
+                name,
+                state: stat.state,
+                cpu_time,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_collect_threads() {
+        let pid = std::process::id() as Pid;
+        let threads = collect_threads(pid);
+
+        // The main thread shares the process' ID.
+        assert!(threads.iter().any(|thread| thread.tid == pid));
+        assert!(threads.iter().all(|thread| !thread.name.is_empty()));
+        assert!(collect_threads(-1).is_empty());
+    }
+
     #[test]
     fn test_proc_cpu_parse() {
         assert_eq!(
@@ -442,4 +659,30 @@ mod tests {
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 10 values"
         );
     }
+
+    #[test]
+    fn test_wchan_since() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(5);
+        let futex = Some("futex_wait".to_string());
+
+        let mut prev = PrevProcDetails::default();
+        assert_eq!(get_wchan_since(&prev, &futex, start), Some(start));
+
+        prev.wchan = futex.clone();
+        prev.wchan_since = Some(start);
+        assert_eq!(get_wchan_since(&prev, &futex, later), Some(start));
+        assert_eq!(
+            get_wchan_since(&prev, &Some("do_select".to_string()), later),
+            Some(later)
+        );
+        assert_eq!(get_wchan_since(&prev, &None, later), None);
+    }
+
+    #[test]
+    fn test_fault_rate() {
+        assert_eq!(fault_rate(150, 100, Duration::from_millis(500)), 100.0);
+        assert_eq!(fault_rate(400, 100, Duration::from_millis(1500)), 200.0);
+        assert_eq!(fault_rate(100, 150, Duration::from_secs(1)), 0.0);
+    }
 }
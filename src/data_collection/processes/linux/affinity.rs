@@ -0,0 +1,96 @@
+//! Reads which cores processes are allowed to run on, to find the ones that
+//! have been pinned to some of them.
+
+use std::{fs, mem};
+
+use crate::data_collection::processes::{Pid, PinnedProcess};
+
+/// The flag in `/proc/<PID>/stat` set for kernel threads.
+const PF_KTHREAD: u64 = 0x0020_0000;
+
+/// Gets the name of a process and whether it's a kernel thread from the
+/// contents of its `stat` file.
+fn parse_stat(stat: &str) -> Option<(&str, bool)> {
+    let (start, end) = (stat.find('(')?, stat.rfind(')')?);
+    let name = stat.get(start + 1..end)?;
+
+    // The flags are the seventh field after the name.
+    let flags: u64 = stat
+        .get(end + 1..)?
+        .split_whitespace()
+        .nth(6)?
+        .parse()
+        .ok()?;
+
+    Some((name, flags & PF_KTHREAD != 0))
+}
+
+/// Gets the cores a process may run on, out of the first `num_cores`.
+fn read_affinity(pid: Pid, num_cores: usize) -> Option<Vec<usize>> {
+    // SAFETY: `cpu_set_t` is plain data, for which all zeroes is valid.
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+
+    // SAFETY: The set is as large as the size given, and the result is checked.
+    let result =
+        unsafe { libc::sched_getaffinity(pid, mem::size_of::<libc::cpu_set_t>(), &mut set) };
+    if result == -1 {
+        return None;
+    }
+
+    Some(
+        (0..num_cores.min(libc::CPU_SETSIZE as usize))
+            // SAFETY: The core is within the set.
+            .filter(|&core| unsafe { libc::CPU_ISSET(core, &set) })
+            .collect(),
+    )
+}
+
+/// Reads the affinity of every process that can be read, keeping those that
+/// can't run on all of the first `num_cores` cores. Kernel threads are
+/// skipped, as many of them are bound to each core on every system.
+pub(crate) fn pinned_processes(num_cores: usize) -> Vec<PinnedProcess> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return vec![];
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<Pid>().ok())
+        .filter_map(|pid| {
+            let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+            let (name, is_kernel_thread) = parse_stat(&stat)?;
+            if is_kernel_thread {
+                return None;
+            }
+
+            let cores = read_affinity(pid, num_cores)?;
+            (cores.len() < num_cores).then(|| PinnedProcess {
+                pid,
+                name: name.to_string(),
+                cores,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stat_names() {
+        let stat = "1234 (my (odd) name) S 1 1234 1234 0 -1 4194560 100 0 0 0 1 2 0 0 20 0 1 0 100 1000 10";
+        assert_eq!(parse_stat(stat), Some(("my (odd) name", false)));
+
+        let stat = "12 (ksoftirqd/0) S 2 0 0 0 -1 69238849 0 0 0 0 0 5 0 0 20 0 1 0 10 0 0";
+        assert_eq!(parse_stat(stat), Some(("ksoftirqd/0", true)));
+    }
+
+    #[test]
+    fn own_affinity() {
+        // This process can run on at least one core, and they're in order.
+        let cores = read_affinity(std::process::id() as Pid, 1024).unwrap();
+        assert!(!cores.is_empty());
+        assert!(cores.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
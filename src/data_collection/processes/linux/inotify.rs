@@ -0,0 +1,78 @@
+//! How many inotify watches processes have, which count against the per-user
+//! limit in `/proc/sys/fs/inotify/max_user_watches`.
+//!
+//! Each inotify instance a process has open is a file descriptor linked to
+//! `anon_inode:inotify`, and its `fdinfo` file has a line for every watch.
+
+use std::fs;
+
+use crate::data_collection::processes::Pid;
+
+/// The target of the link of a file descriptor that's an inotify instance.
+const INOTIFY_TARGET: &str = "anon_inode:inotify";
+
+/// Counts the watches in the `fdinfo` file of an inotify instance.
+fn count_watches(fdinfo: &str) -> u32 {
+    fdinfo
+        .lines()
+        .filter(|line| line.starts_with("inotify wd:"))
+        .count() as u32
+}
+
+/// Counts the watches over all of a process's inotify instances. This is
+/// [`None`] if its file descriptors can't be read, e.g. because it belongs to
+/// another user.
+pub(crate) fn count_inotify_watches(pid: Pid) -> Option<u32> {
+    let entries = fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+
+    let count = entries
+        .flatten()
+        .filter(|entry| {
+            fs::read_link(entry.path()).is_ok_and(|target| target.as_os_str() == INOTIFY_TARGET)
+        })
+        .filter_map(|entry| {
+            let fd = entry.file_name();
+            fs::read_to_string(format!("/proc/{pid}/fdinfo/{}", fd.to_string_lossy())).ok()
+        })
+        .map(|fdinfo| count_watches(&fdinfo))
+        .sum();
+
+    Some(count)
+}
+
+/// The most inotify watches each user can have at once.
+pub(crate) fn max_user_watches() -> Option<u32> {
+    fs::read_to_string("/proc/sys/fs/inotify/max_user_watches")
+        .ok()
+        .and_then(|limit| limit.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod test {
+    use rustix::fs::inotify;
+
+    use super::*;
+
+    #[test]
+    fn watches() {
+        let fdinfo = "pos:\t0
+flags:\t02004000
+mnt_id:\t15
+ino:\t1057
+inotify wd:2 ino:2a1 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:a102000000000000
+inotify wd:1 ino:2 sdev:800001 mask:fc6 ignored_mask:0 fhandle-bytes:8 fhandle-type:1 f_handle:0200000000000000
+";
+        assert_eq!(count_watches(fdinfo), 2);
+        assert_eq!(count_watches("pos:\t0\nflags:\t02\n"), 0);
+    }
+
+    #[test]
+    fn own_watches() {
+        let dir = std::env::temp_dir();
+        let instance = inotify::init(inotify::CreateFlags::CLOEXEC).unwrap();
+        inotify::add_watch(&instance, &dir, inotify::WatchFlags::CREATE).unwrap();
+
+        let count = count_inotify_watches(std::process::id() as Pid);
+        assert!(count.is_some_and(|count| count >= 1));
+    }
+}
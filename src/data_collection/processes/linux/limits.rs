@@ -0,0 +1,110 @@
+//! Reads the resource limits of a process from `/proc/<PID>/limits`.
+
+use std::fs;
+
+use crate::data_collection::processes::{Pid, RlimitEntry};
+
+/// Reads the resource limits of a process, along with how many open files and
+/// threads it has, as those are the limits near which a process usually runs
+/// into trouble.
+pub(crate) fn read_rlimits(pid: Pid) -> Vec<RlimitEntry> {
+    let Ok(limits) = fs::read_to_string(format!("/proc/{pid}/limits")) else {
+        return vec![];
+    };
+    let count_entries = |dir: &str| {
+        fs::read_dir(format!("/proc/{pid}/{dir}"))
+            .ok()
+            .map(|entries| entries.count() as u64)
+    };
+
+    parse_limits(&limits)
+        .into_iter()
+        .map(|mut entry| {
+            entry.usage = match entry.name.as_str() {
+                "Max open files" => count_entries("fd"),
+                // This limit is for the whole user, but threads of this
+                // process count towards it.
+                "Max processes" => count_entries("task"),
+                _ => None,
+            };
+            entry
+        })
+        .collect()
+}
+
+/// Parses the contents of a `limits` file, which is a table with the limit's
+/// name, soft and hard limits, and units, aligned to the columns of the header.
+fn parse_limits(limits: &str) -> Vec<RlimitEntry> {
+    let mut lines = limits.lines();
+    let Some(header) = lines.next() else {
+        return vec![];
+    };
+    let (Some(soft_start), Some(hard_start), Some(units_start)) = (
+        header.find("Soft Limit"),
+        header.find("Hard Limit"),
+        header.find("Units"),
+    ) else {
+        return vec![];
+    };
+
+    let field = |line: &str, start: usize, end: usize| -> String {
+        line.get(start..end.min(line.len()))
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    let limit = |value: String| match value.as_str() {
+        "unlimited" => None,
+        value => value.parse().ok(),
+    };
+
+    lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| RlimitEntry {
+            name: field(line, 0, soft_start),
+            soft: limit(field(line, soft_start, hard_start)),
+            hard: limit(field(line, hard_start, units_start)),
+            units: field(line, units_start, line.len()),
+            usage: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn limits() {
+        let limits = "\
+Limit                     Soft Limit           Hard Limit           Units     
+Max cpu time              unlimited            unlimited            seconds   
+Max stack size            8388608              unlimited            bytes     
+Max processes             127416               127416               processes 
+Max open files            1024                 524288               files     
+Max locked memory         8388608              8388608              bytes     
+Max realtime timeout      unlimited            unlimited            us        
+";
+        let entries = parse_limits(limits);
+
+        assert_eq!(entries.len(), 6);
+        assert_eq!(
+            entries[0],
+            RlimitEntry {
+                name: "Max cpu time".to_string(),
+                soft: None,
+                hard: None,
+                units: "seconds".to_string(),
+                usage: None,
+            }
+        );
+        assert_eq!(entries[1].soft, Some(8388608));
+        assert_eq!(entries[1].hard, None);
+        assert_eq!(entries[3].name, "Max open files");
+        assert_eq!(entries[3].soft, Some(1024));
+        assert_eq!(entries[3].hard, Some(524288));
+        assert_eq!(entries[5].units, "us");
+
+        assert!(parse_limits("").is_empty());
+    }
+}
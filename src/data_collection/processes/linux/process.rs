@@ -3,7 +3,7 @@
 
 use std::{
     fs::File,
-    io::{self, BufRead, BufReader, Read},
+    io::{self, Read},
     path::PathBuf,
     sync::OnceLock,
 };
@@ -16,7 +16,10 @@ use rustix::{
     path::Arg,
 };
 
-use crate::data_collection::processes::Pid;
+use crate::data_collection::{
+    cgroups::Cgroup,
+    processes::{Pid, ProcessNamespaces},
+};
 
 static PAGESIZE: OnceLock<u64> = OnceLock::new();
 
@@ -41,6 +44,14 @@ pub(crate) struct Stat {
     /// The parent process PID.
     pub ppid: Pid,
 
+    /// The number of minor faults the process has made, which have not
+    /// required loading a memory page from disk.
+    pub minflt: u64,
+
+    /// The number of major faults the process has made, which have required
+    /// loading a memory page from disk.
+    pub majflt: u64,
+
     /// The amount of time this process has been scheduled in user mode in clock
     /// ticks.
     pub utime: u64,
@@ -49,6 +60,9 @@ pub(crate) struct Stat {
     /// clock ticks.
     pub stime: u64,
 
+    /// The number of threads in the process.
+    pub num_threads: u32,
+
     /// The resident set size, or the number of pages the process has in real
     /// memory.
     pub rss: u64,
@@ -59,7 +73,7 @@ pub(crate) struct Stat {
 
 impl Stat {
     #[inline]
-    fn from_file(mut f: File, buffer: &mut String) -> anyhow::Result<Stat> {
+    pub(crate) fn from_file(mut f: File, buffer: &mut String) -> anyhow::Result<Stat> {
         // Since this is just one line, we can read it all at once. However, since it
         // might have non-utf8 characters, we can't just use read_to_string.
         f.read_to_end(unsafe { buffer.as_mut_vec() })?;
@@ -86,15 +100,25 @@ impl Stat {
             .ok_or_else(|| anyhow!("missing state"))?;
         let ppid: Pid = next_part(&mut rest)?.parse()?;
 
-        // Skip 9 fields until utime (pgrp, session, tty_nr, tpgid, flags, minflt,
-        // cminflt, majflt, cmajflt).
-        let mut rest = rest.skip(9);
+        // Skip 5 fields until minflt (pgrp, session, tty_nr, tpgid, flags).
+        let mut rest = rest.skip(5);
+        let minflt: u64 = next_part(&mut rest)?.parse()?;
+
+        // Skip one field until majflt (cminflt).
+        let mut rest = rest.skip(1);
+        let majflt: u64 = next_part(&mut rest)?.parse()?;
+
+        // Skip one field until utime (cmajflt).
+        let mut rest = rest.skip(1);
         let utime: u64 = next_part(&mut rest)?.parse()?;
         let stime: u64 = next_part(&mut rest)?.parse()?;
 
-        // Skip 6 fields until starttime (cutime, cstime, priority, nice, num_threads,
-        // itrealvalue).
-        let mut rest = rest.skip(6);
+        // Skip 4 fields until num_threads (cutime, cstime, priority, nice).
+        let mut rest = rest.skip(4);
+        let num_threads: u32 = next_part(&mut rest)?.parse()?;
+
+        // Skip one field until starttime (itrealvalue).
+        let mut rest = rest.skip(1);
         let start_time: u64 = next_part(&mut rest)?.parse()?;
 
         // Skip one field until rss (vsize)
@@ -105,8 +129,11 @@ impl Stat {
             comm,
             state,
             ppid,
+            minflt,
+            majflt,
             utime,
             stime,
+            num_threads,
             rss,
             start_time,
         })
@@ -130,69 +157,39 @@ pub(crate) struct Io {
 
 impl Io {
     #[inline]
-    fn from_file(f: File, buffer: &mut String) -> anyhow::Result<Io> {
-        const NUM_FIELDS: u16 = 0; // Make sure to update this if you want more fields!
-        enum Fields {
-            ReadBytes,
-            WriteBytes,
-        }
+    fn from_file(mut f: File, buffer: &mut String) -> anyhow::Result<Io> {
+        // The file is small, so read it all at once into the shared buffer
+        // rather than allocating a reader for it.
+        f.read_to_string(buffer)?;
 
-        let mut read_fields = 0;
-        let mut reader = BufReader::new(f);
-
-        let mut read_bytes = 0;
-        let mut write_bytes = 0;
-
-        // This saves us from doing a string allocation on each iteration compared to
-        // `lines()`.
-        while let Ok(bytes) = reader.read_line(buffer) {
-            if bytes > 0 {
-                if buffer.is_empty() {
-                    // Empty, no need to clear.
-                    continue;
-                }
-
-                let mut parts = buffer.split_whitespace();
-
-                if let Some(field) = parts.next() {
-                    let curr_field = match field {
-                        "read_bytes:" => Fields::ReadBytes,
-                        "write_bytes:" => Fields::WriteBytes,
-                        _ => {
-                            buffer.clear();
-                            continue;
-                        }
-                    };
-
-                    if let Some(value) = parts.next() {
-                        let value = value.parse::<u64>()?;
-                        match curr_field {
-                            Fields::ReadBytes => {
-                                read_bytes = value;
-                                read_fields += 1;
-                            }
-                            Fields::WriteBytes => {
-                                write_bytes = value;
-                                read_fields += 1;
-                            }
-                        }
-                    }
-                }
+        Io::parse(buffer)
+    }
 
-                // Quick short circuit if we have already read all the required fields.
-                if read_fields == NUM_FIELDS {
-                    break;
-                }
+    fn parse(contents: &str) -> anyhow::Result<Io> {
+        let mut read_bytes = None;
+        let mut write_bytes = None;
 
-                buffer.clear();
-            } else {
+        for line in contents.lines() {
+            let mut parts = line.split_whitespace();
+            let field = match parts.next() {
+                Some("read_bytes:") => &mut read_bytes,
+                Some("write_bytes:") => &mut write_bytes,
+                _ => continue,
+            };
+
+            if let Some(value) = parts.next() {
+                *field = Some(value.parse::<u64>()?);
+            }
+
+            // Quick short circuit if we have already read all the required fields.
+            if read_bytes.is_some() && write_bytes.is_some() {
                 break;
             }
         }
 
         Ok(Io {
-            read_bytes,
-            write_bytes,
+            read_bytes: read_bytes.unwrap_or_default(),
+            write_bytes: write_bytes.unwrap_or_default(),
         })
     }
 }
@@ -206,6 +203,13 @@ pub(crate) struct Process {
     pub stat: Stat,
     pub io: anyhow::Result<Io>,
     pub cmdline: anyhow::Result<Vec<String>>,
+    /// The kernel function the process is waiting in, if it's waiting in one
+    /// and it was read.
+    pub wchan: Option<String>,
+    /// The namespaces of the process, if they were read.
+    pub namespaces: ProcessNamespaces,
+    /// The cgroup of the process with the CPU controller, if it was read.
+    pub cgroup: Option<Cgroup>,
 }
 
 #[inline]
@@ -223,9 +227,17 @@ impl Process {
     /// methods. Therefore, this struct is only useful for either fields
     /// that are unlikely to change, or are short-lived and
     /// will be discarded quickly.
-    pub(crate) fn from_path(pid_path: PathBuf) -> anyhow::Result<Process> {
-        // TODO: Pass in a buffer vec/string to share?
-
+    ///
+    /// The wait channel, namespaces, and cgroup are only read if
+    /// `read_wchan`, `read_namespaces`, and `read_cgroup` are set
+    /// respectively.
+    ///
+    /// Files are read into `buffer`, which is cleared first. Sharing one
+    /// buffer between processes avoids allocating for each file.
+    pub(crate) fn from_path(
+        pid_path: PathBuf, buffer: &mut String, read_wchan: bool, read_namespaces: bool,
+        read_cgroup: bool,
+    ) -> anyhow::Result<Process> {
         let fd = rustix::fs::openat(
             rustix::fs::CWD,
             &pid_path,
@@ -254,18 +266,39 @@ impl Process {
         };
 
         let mut root = pid_path;
-        let mut buffer = String::new();
+        buffer.clear();
 
         // NB: Whenever you add a new stat, make sure to pop the root and clear the
         // buffer!
         let stat =
-            open_at(&mut root, "stat", &fd).and_then(|file| Stat::from_file(file, &mut buffer))?;
-        reset(&mut root, &mut buffer);
+            open_at(&mut root, "stat", &fd).and_then(|file| Stat::from_file(file, buffer))?;
+        reset(&mut root, buffer);
+
+        let cmdline = cmdline(&mut root, &fd, buffer);
+        reset(&mut root, buffer);
+
+        let io = open_at(&mut root, "io", &fd).and_then(|file| Io::from_file(file, buffer));
+        reset(&mut root, buffer);
+
+        let wchan = if read_wchan {
+            let wchan = wchan(&mut root, &fd, buffer);
+            reset(&mut root, buffer);
+            wchan
+        } else {
+            None
+        };
 
-        let cmdline = cmdline(&mut root, &fd, &mut buffer);
-        reset(&mut root, &mut buffer);
+        let namespaces = if read_namespaces {
+            namespaces(&fd)
+        } else {
+            ProcessNamespaces::default()
+        };
 
-        let io = open_at(&mut root, "io", &fd).and_then(|file| Io::from_file(file, &mut buffer));
+        let cgroup = if read_cgroup {
+            cgroup(&mut root, &fd, buffer)
+        } else {
+            None
+        };
 
         Ok(Process {
             pid,
@@ -273,6 +306,9 @@ impl Process {
             stat,
             io,
             cmdline,
+            wchan,
+            namespaces,
+            cgroup,
         })
     }
 }
@@ -296,6 +332,58 @@ fn cmdline(root: &mut PathBuf, fd: &OwnedFd, buffer: &mut String) -> anyhow::Res
         .map_err(Into::into)
 }
 
+/// Reads the wait channel. This is "0" if the process isn't waiting, which is
+/// returned as [`None`].
+#[inline]
+fn wchan(root: &mut PathBuf, fd: &OwnedFd, buffer: &mut String) -> Option<String> {
+    open_at(root, "wchan", fd)
+        .ok()
+        .and_then(|mut file| file.read_to_string(buffer).ok())
+        .and_then(|_| {
+            let wchan = buffer.trim();
+            if wchan.is_empty() || wchan == "0" {
+                None
+            } else {
+                Some(wchan.to_string())
+            }
+        })
+}
+
+/// Reads the cgroup of the process with the CPU controller.
+#[inline]
+fn cgroup(root: &mut PathBuf, fd: &OwnedFd, buffer: &mut String) -> Option<Cgroup> {
+    open_at(root, "cgroup", fd)
+        .ok()
+        .and_then(|mut file| file.read_to_string(buffer).ok())
+        .and_then(|_| Cgroup::from_proc_cgroup(buffer))
+}
+
+/// Reads the inode numbers of the namespaces of a process. Any that can't be
+/// read, usually due to permissions, are 0.
+#[inline]
+fn namespaces(fd: &OwnedFd) -> ProcessNamespaces {
+    let inode = |link: &str| {
+        rustix::fs::readlinkat(fd, link, vec![])
+            .ok()
+            .and_then(|target| parse_namespace_link(&target.to_string_lossy()))
+            .unwrap_or(0)
+    };
+
+    ProcessNamespaces {
+        pid_ns: inode("ns/pid"),
+        net_ns: inode("ns/net"),
+        mnt_ns: inode("ns/mnt"),
+        user_ns: inode("ns/user"),
+    }
+}
+
+/// Parses the inode number from a namespace link's target, which looks like
+/// `net:[4026531840]`.
+fn parse_namespace_link(target: &str) -> Option<u64> {
+    let (_, inode) = target.split_once(":[")?;
+    inode.strip_suffix(']')?.parse().ok()
+}
+
 /// Opens a path. Note that this function takes in a mutable root - this will
 /// mutate it to avoid allocations. You probably will want to pop the most
 /// recent child after if you need to use the buffer again.
@@ -306,3 +394,31 @@ fn open_at(root: &mut PathBuf, child: &str, fd: &OwnedFd) -> anyhow::Result<File
 
     Ok(File::from(new_fd))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_io() {
+        let io = Io::parse(
+            "rchar: 323934931\nwchar: 323929600\nsyscr: 632687\nsyscw: 632675\n\
+             read_bytes: 4096\nwrite_bytes: 323932160\ncancelled_write_bytes: 0\n",
+        )
+        .unwrap();
+        assert_eq!(io.read_bytes, 4096);
+        assert_eq!(io.write_bytes, 323932160);
+
+        let io = Io::parse("rchar: 1\n").unwrap();
+        assert_eq!((io.read_bytes, io.write_bytes), (0, 0));
+        assert!(Io::parse("read_bytes: abc\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_namespace_link() {
+        assert_eq!(parse_namespace_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_namespace_link("user:[1]"), Some(1));
+        assert_eq!(parse_namespace_link("net:4026531840"), None);
+        assert_eq!(parse_namespace_link("net:[abc]"), None);
+    }
+}
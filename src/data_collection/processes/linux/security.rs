@@ -0,0 +1,300 @@
+//! Reads which memory safety features a process has, like `checksec --proc`.
+//!
+//! Whether the executable is position-independent and uses stack canaries is
+//! read from the ELF headers of `/proc/<PID>/exe`, and whether the stack is
+//! executable from `/proc/<PID>/maps`. Both need the same access as reading the
+//! process's memory, so for other users' processes they need root.
+
+use std::{
+    fs::{self, File},
+    os::unix::fs::FileExt,
+};
+
+use crate::data_collection::processes::{Pid, SecurityFlags};
+
+/// The personality flag set by `setarch -R`, which turns off ASLR for a
+/// process and its children.
+const ADDR_NO_RANDOMIZE: u32 = 0x0040000;
+
+/// The ELF type of shared objects, which position-independent executables are.
+const ET_DYN: u16 = 3;
+
+/// The section type of the dynamic symbol table.
+const SHT_DYNSYM: u32 = 11;
+
+/// The longest ELF header, which is that of 64-bit executables.
+const HEADER_LEN: usize = 64;
+
+/// The largest string table that's read, so a broken header can't make this
+/// read huge amounts of memory.
+const MAX_STRINGS_LEN: u64 = 16 << 20;
+
+/// The symbols that code built with stack canaries imports from libc.
+const CANARY_SYMBOLS: [&[u8]; 2] = [b"__stack_chk_fail", b"__stack_chk_guard"];
+
+/// The word size and byte order of an ELF file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ElfClass {
+    is_64: bool,
+    is_le: bool,
+}
+
+impl ElfClass {
+    fn uint(self, bytes: &[u8], offset: usize, len: usize) -> Option<u64> {
+        let bytes = bytes.get(offset..offset + len)?;
+        let value = |acc: u64, byte: &u8| acc << 8 | u64::from(*byte);
+
+        Some(if self.is_le {
+            bytes.iter().rev().fold(0, value)
+        } else {
+            bytes.iter().fold(0, value)
+        })
+    }
+
+    fn u16(self, bytes: &[u8], offset: usize) -> Option<u16> {
+        self.uint(bytes, offset, 2).map(|value| value as u16)
+    }
+
+    fn u32(self, bytes: &[u8], offset: usize) -> Option<u32> {
+        self.uint(bytes, offset, 4).map(|value| value as u32)
+    }
+
+    /// Reads an address or offset, which is as wide as the class's words.
+    fn word(self, bytes: &[u8], offset: usize) -> Option<u64> {
+        self.uint(bytes, offset, if self.is_64 { 8 } else { 4 })
+    }
+}
+
+/// The parts of the ELF header that are needed.
+#[derive(Debug, PartialEq, Eq)]
+struct ElfHeader {
+    class: ElfClass,
+    kind: u16,
+    section_offset: u64,
+    section_len: u16,
+    section_count: u16,
+}
+
+fn parse_header(bytes: &[u8]) -> Option<ElfHeader> {
+    if bytes.get(..4)? != b"\x7fELF" {
+        return None;
+    }
+
+    let class = ElfClass {
+        is_64: match bytes.get(4)? {
+            1 => false,
+            2 => true,
+            _ => return None,
+        },
+        is_le: match bytes.get(5)? {
+            1 => true,
+            2 => false,
+            _ => return None,
+        },
+    };
+    let (offset, len, count) = if class.is_64 {
+        (40, 58, 60)
+    } else {
+        (32, 46, 48)
+    };
+
+    Some(ElfHeader {
+        class,
+        kind: class.u16(bytes, 16)?,
+        section_offset: class.word(bytes, offset)?,
+        section_len: class.u16(bytes, len)?,
+        section_count: class.u16(bytes, count)?,
+    })
+}
+
+/// The parts of a section header that are needed.
+struct Section {
+    kind: u32,
+    offset: u64,
+    size: u64,
+    link: u32,
+}
+
+fn parse_section(class: ElfClass, bytes: &[u8]) -> Option<Section> {
+    let (offset, size, link) = if class.is_64 {
+        (24, 32, 40)
+    } else {
+        (16, 20, 24)
+    };
+
+    Some(Section {
+        kind: class.u32(bytes, 4)?,
+        offset: class.word(bytes, offset)?,
+        size: class.word(bytes, size)?,
+        link: class.u32(bytes, link)?,
+    })
+}
+
+fn read_exact(file: &File, offset: u64, len: u64) -> Option<Vec<u8>> {
+    let mut buffer = vec![0; usize::try_from(len).ok()?];
+    file.read_exact_at(&mut buffer, offset).ok()?;
+
+    Some(buffer)
+}
+
+/// Whether any of the names in a string table are those of [`CANARY_SYMBOLS`].
+fn imports_canary(strings: &[u8]) -> bool {
+    strings
+        .split(|&byte| byte == 0)
+        .any(|name| CANARY_SYMBOLS.contains(&name))
+}
+
+/// Whether the executable imports what stack canaries need, or [`None`] if it
+/// has no dynamic symbols to tell by, like statically linked executables.
+fn has_stack_canary(file: &File, header: &ElfHeader) -> Option<bool> {
+    if header.section_count == 0 || header.section_len == 0 {
+        return None;
+    }
+
+    let sections = read_exact(
+        file,
+        header.section_offset,
+        u64::from(header.section_len) * u64::from(header.section_count),
+    )?;
+    let sections = sections
+        .chunks_exact(header.section_len.into())
+        .filter_map(|bytes| parse_section(header.class, bytes))
+        .collect::<Vec<_>>();
+
+    let symbols = sections.iter().find(|section| section.kind == SHT_DYNSYM)?;
+    let strings = sections
+        .get(symbols.link as usize)
+        .filter(|section| section.size <= MAX_STRINGS_LEN)?;
+
+    Some(imports_canary(&read_exact(
+        file,
+        strings.offset,
+        strings.size,
+    )?))
+}
+
+/// Whether the stack in a `maps` file is executable, or [`None`] if there's no
+/// stack, as for kernel threads.
+fn is_stack_executable(maps: &str) -> Option<bool> {
+    maps.lines()
+        .find(|line| line.ends_with("[stack]"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .map(|permissions| permissions.contains('x'))
+}
+
+/// Whether ASLR is on for the process, which needs it to be on for the whole
+/// system in `randomize_va_space` and not turned off for the process.
+fn is_aslr_enabled(pid: Pid) -> bool {
+    let system = fs::read_to_string("/proc/sys/kernel/randomize_va_space")
+        .ok()
+        .and_then(|level| level.trim().parse::<u8>().ok())
+        .is_some_and(|level| level > 0);
+    let personality = fs::read_to_string(format!("/proc/{pid}/personality"))
+        .ok()
+        .and_then(|personality| u32::from_str_radix(personality.trim(), 16).ok())
+        .unwrap_or_default();
+
+    system && personality & ADDR_NO_RANDOMIZE == 0
+}
+
+/// Reads the memory safety features of a process, or [`None`] if its
+/// executable or memory map can't be read.
+pub(crate) fn read_security_flags(pid: Pid) -> Option<SecurityFlags> {
+    let maps = fs::read_to_string(format!("/proc/{pid}/maps")).ok()?;
+    let file = File::open(format!("/proc/{pid}/exe")).ok()?;
+
+    let mut header = [0; HEADER_LEN];
+    let len = file.read_at(&mut header, 0).ok()?;
+    let header = parse_header(&header[..len])?;
+
+    Some(SecurityFlags {
+        aslr: is_aslr_enabled(pid),
+        stack_canary: has_stack_canary(&file, &header),
+        nx: !is_stack_executable(&maps)?,
+        pie: header.kind == ET_DYN,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn headers() {
+        let mut bytes = vec![0; HEADER_LEN];
+        bytes[..6].copy_from_slice(b"\x7fELF\x02\x01");
+        bytes[16] = 3;
+        bytes[40] = 0x40;
+        bytes[41] = 0x12;
+        bytes[58] = 64;
+        bytes[60] = 30;
+
+        assert_eq!(
+            parse_header(&bytes),
+            Some(ElfHeader {
+                class: ElfClass {
+                    is_64: true,
+                    is_le: true
+                },
+                kind: ET_DYN,
+                section_offset: 0x1240,
+                section_len: 64,
+                section_count: 30,
+            })
+        );
+
+        let mut bytes = vec![0; 52];
+        bytes[..6].copy_from_slice(b"\x7fELF\x01\x02");
+        bytes[17] = 2;
+        bytes[34] = 0x12;
+        bytes[35] = 0x40;
+        bytes[47] = 40;
+        bytes[49] = 25;
+
+        assert_eq!(
+            parse_header(&bytes),
+            Some(ElfHeader {
+                class: ElfClass {
+                    is_64: false,
+                    is_le: false
+                },
+                kind: 2,
+                section_offset: 0x1240,
+                section_len: 40,
+                section_count: 25,
+            })
+        );
+
+        assert_eq!(parse_header(b"#!/bin/sh\n"), None);
+        assert_eq!(parse_header(b"\x7fELF"), None);
+    }
+
+    #[test]
+    fn canaries() {
+        assert!(imports_canary(b"\0libc.so.6\0puts\0__stack_chk_fail\0"));
+        assert!(!imports_canary(
+            b"\0libc.so.6\0puts\0__stack_chk_fail_local\0"
+        ));
+        assert!(!imports_canary(b""));
+    }
+
+    #[test]
+    fn stacks() {
+        let maps = "5566d1a00000-5566d1a28000 r--p 00000000 08:01 1234 /usr/bin/bash
+7ffc8a9f0000-7ffc8aa11000 rw-p 00000000 00:00 0                          [stack]
+7ffc8aaf3000-7ffc8aaf5000 r-xp 00000000 00:00 0                          [vdso]
+";
+        assert_eq!(is_stack_executable(maps), Some(false));
+        assert_eq!(
+            is_stack_executable(&maps.replace("rw-p 00000000 00:00 0 ", "rwxp 00000000 00:00 0 ")),
+            Some(true)
+        );
+        assert_eq!(is_stack_executable(""), None);
+    }
+
+    #[test]
+    fn own_flags() {
+        let flags = read_security_flags(std::process::id() as Pid).unwrap();
+        assert!(flags.nx);
+    }
+}
@@ -0,0 +1,154 @@
+//! Reads the memory regions of a process from `/proc/<PID>/smaps`, and its
+//! proportional set size from `/proc/<PID>/smaps_rollup`.
+
+use std::fs;
+
+use crate::data_collection::processes::{Pid, SmapRegion};
+
+/// Reads the memory regions of a process. `smaps_rollup` is cheaper to read,
+/// but it sums everything together, so it can't be used to tell which files
+/// the memory comes from.
+pub(crate) fn read_smaps(pid: Pid) -> Vec<SmapRegion> {
+    match fs::read_to_string(format!("/proc/{pid}/smaps")) {
+        Ok(smaps) => parse_smaps(&smaps),
+        Err(_) => vec![],
+    }
+}
+
+/// Reads the proportional set size of a process in bytes, which splits each
+/// shared page evenly between the processes that map it. This is [`None`] if
+/// the process can't be read, which is usually the case for other users'
+/// processes unless running as root.
+pub(crate) fn read_pss(pid: Pid) -> Option<u64> {
+    fs::read_to_string(format!("/proc/{pid}/smaps_rollup"))
+        .ok()
+        .and_then(|rollup| parse_rollup_pss(&rollup))
+}
+
+/// The proportional set size of every process that can be read, in bytes.
+/// This is used when PSS isn't read as part of the process list.
+pub(crate) fn total_pss() -> u64 {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<Pid>().ok())
+        .filter_map(read_pss)
+        .sum()
+}
+
+/// Gets the `Pss` line out of an `smaps_rollup` file.
+fn parse_rollup_pss(rollup: &str) -> Option<u64> {
+    rollup.lines().find_map(|line| {
+        let value = line.strip_prefix("Pss:")?.split_whitespace().next()?;
+        value.parse::<u64>().ok().map(|kb| kb * 1024)
+    })
+}
+
+/// Parses the contents of an `smaps` file, where each region starts with a
+/// header line like the ones in `maps`, followed by `Key: value kB` lines.
+fn parse_smaps(smaps: &str) -> Vec<SmapRegion> {
+    let mut regions: Vec<SmapRegion> = vec![];
+
+    for line in smaps.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(first) = fields.next() else {
+            continue;
+        };
+
+        if let Some(key) = first.strip_suffix(':') {
+            let Some(region) = regions.last_mut() else {
+                continue;
+            };
+            // Values are always in kB, other than the flags at the end.
+            let Some(value) = fields.next().and_then(|value| value.parse::<u64>().ok()) else {
+                continue;
+            };
+            let value = value * 1024;
+
+            match key {
+                "Rss" => region.rss = value,
+                "Pss" => region.pss = value,
+                "Shared_Clean" => region.shared_clean = value,
+                "Shared_Dirty" => region.shared_dirty = value,
+                "Private_Clean" => region.private_clean = value,
+                "Private_Dirty" => region.private_dirty = value,
+                _ => {}
+            }
+        } else if first.contains('-') {
+            // The permissions, offset, device, and inode come before the path,
+            // which is empty for anonymous regions.
+            let path = fields.skip(4).collect::<Vec<_>>().join(" ");
+            regions.push(SmapRegion {
+                path,
+                ..Default::default()
+            });
+        }
+    }
+
+    regions
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn smaps() {
+        let smaps = "\
+5603b6a00000-5603b6a2c000 r--p 00000000 08:02 1704487                    /usr/bin/bash
+Size:                176 kB
+KernelPageSize:        4 kB
+Rss:                 176 kB
+Pss:                  58 kB
+Shared_Clean:        176 kB
+Shared_Dirty:          0 kB
+Private_Clean:         0 kB
+Private_Dirty:         0 kB
+VmFlags: rd mr mw me dw sd
+5603b7c4e000-5603b7e0a000 rw-p 00000000 00:00 0                          [heap]
+Rss:                1776 kB
+Pss:                1776 kB
+Private_Dirty:      1776 kB
+7f2a1c000000-7f2a1c021000 rw-p 00000000 00:00 0 
+Rss:                   8 kB
+7f2a1d400000-7f2a1d401000 r--s 00000000 00:01 2050                       /memfd:shm file (deleted)
+Rss:                   4 kB
+";
+
+        let regions = parse_smaps(smaps);
+        assert_eq!(regions.len(), 4);
+        assert_eq!(
+            regions[0],
+            SmapRegion {
+                path: "/usr/bin/bash".to_string(),
+                rss: 176 * 1024,
+                pss: 58 * 1024,
+                shared_clean: 176 * 1024,
+                ..Default::default()
+            }
+        );
+        assert_eq!(regions[1].path, "[heap]");
+        assert_eq!(regions[1].private_dirty, 1776 * 1024);
+        assert_eq!(regions[2].path, "");
+        assert_eq!(regions[2].rss, 8 * 1024);
+        assert_eq!(regions[3].path, "/memfd:shm file (deleted)");
+    }
+
+    #[test]
+    fn rollup_pss() {
+        let rollup = "\
+55d1c9a9c000-7ffd6b3f2000 ---p 00000000 00:00 0                          [rollup]
+Rss:                5120 kB
+Pss:                2345 kB
+Pss_Anon:           1024 kB
+Pss_File:           1321 kB
+Shared_Clean:       3072 kB
+";
+
+        assert_eq!(parse_rollup_pss(rollup), Some(2345 * 1024));
+        assert_eq!(parse_rollup_pss("Rss: 5120 kB\n"), None);
+    }
+}
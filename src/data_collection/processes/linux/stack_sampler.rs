@@ -0,0 +1,527 @@
+//! Samples the stacks of a process for flame graphs.
+//!
+//! There's no symbolization, so frames are named after the kernel function or
+//! the user module that they're in:
+//!
+//! - Kernel frames are read from `/proc/<PID>/stack`, which needs root, or
+//!   `/proc/<PID>/wchan` otherwise, which only gives the innermost function.
+//! - User frames come from briefly stopping each thread with ptrace, which
+//!   needs `CAP_SYS_PTRACE` for processes that aren't our children, and
+//!   walking the frame pointers from its registers. Each return address is
+//!   looked up in `/proc/<PID>/maps`. Code built without frame pointers gives
+//!   short stacks.
+//!
+//! Every thread of the process is sampled. The threads are seized once, for
+//! the whole burst, and only interrupted for as long as it takes to read
+//! their stacks, rather than being attached to and detached from each time.
+
+use std::{
+    collections::HashMap,
+    fs, io, ptr, thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    app::flamegraph::{FoldedStacks, Frame, FrameKind},
+    data_collection::processes::Pid,
+};
+
+/// How many user frames are followed at most.
+const MAX_USER_FRAMES: usize = 64;
+
+/// Samples the stacks of every thread of a process a number of times, spread
+/// evenly over the given duration. Each thread's stack counts as a sample.
+pub fn sample_stacks(pid: Pid, samples: usize, duration: Duration) -> Result<FoldedStacks, String> {
+    let maps = fs::read_to_string(format!("/proc/{pid}/maps"))
+        .map_err(|err| format!("Could not read the memory map of process {pid}: {err}."))?;
+    let maps = parse_maps(&maps);
+
+    let mut tracees = HashMap::new();
+    let mut stacks = FoldedStacks::default();
+    let interval = duration / samples.max(1) as u32;
+    let start = Instant::now();
+
+    for i in 0..samples {
+        if let Some(wait) = (start + interval * i as u32).checked_duration_since(Instant::now()) {
+            thread::sleep(wait);
+        }
+
+        // Threads can start at any time, so look for new ones every time.
+        if let Err(err) = seize_threads(pid, &mut tracees) {
+            // Keep what was sampled before the process exited.
+            if err.raw_os_error() == Some(libc::ESRCH) && stacks.total() > 0 {
+                break;
+            }
+            return Err(describe_error(pid, &err));
+        }
+
+        let mut exited = Vec::new();
+        for (&tid, tracee) in &tracees {
+            let kernel = kernel_frames(pid, tid);
+            let user = match user_frames(tracee, &maps) {
+                Ok(user) => user,
+                Err(err) if err.raw_os_error() == Some(libc::ESRCH) => {
+                    exited.push(tid);
+                    continue;
+                }
+                Err(err) => return Err(describe_error(pid, &err)),
+            };
+
+            stacks.add(
+                user.into_iter()
+                    .rev()
+                    .chain(kernel.into_iter().rev())
+                    .collect(),
+            );
+        }
+        for tid in exited {
+            if let Some(tracee) = tracees.remove(&tid) {
+                tracee.forget();
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// Seizes each thread of the process that isn't already, without stopping it.
+fn seize_threads(pid: Pid, tracees: &mut HashMap<Pid, Tracee>) -> io::Result<()> {
+    let tasks = fs::read_dir(format!("/proc/{pid}/task"))
+        .map_err(|_| io::Error::from_raw_os_error(libc::ESRCH))?;
+
+    for tid in tasks
+        .flatten()
+        .filter_map(|task| task.file_name().to_str()?.parse().ok())
+    {
+        if tracees.contains_key(&tid) {
+            continue;
+        }
+
+        match Tracee::seize(tid) {
+            Ok(tracee) => {
+                tracees.insert(tid, tracee);
+            }
+            // The thread exited after being listed.
+            Err(err) if err.raw_os_error() == Some(libc::ESRCH) => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    if tracees.is_empty() {
+        Err(io::Error::from_raw_os_error(libc::ESRCH))
+    } else {
+        Ok(())
+    }
+}
+
+/// What a failure to sample means to the user.
+fn describe_error(pid: Pid, err: &io::Error) -> String {
+    match err.raw_os_error() {
+        Some(libc::EPERM) => format!(
+            "Not allowed to trace process {pid}. This needs CAP_SYS_PTRACE (e.g. running as root), \
+             or kernel.yama.ptrace_scope set to 0 for your own processes."
+        ),
+        Some(libc::ESRCH) => format!("Process {pid} has exited."),
+        _ => format!("Could not sample the stack of process {pid}: {err}."),
+    }
+}
+
+/// The kernel frames of a thread, innermost first. These are read before
+/// stopping the thread, as once stopped it's always in the ptrace code.
+fn kernel_frames(pid: Pid, tid: Pid) -> Vec<Frame> {
+    let task = format!("/proc/{pid}/task/{tid}");
+    if let Ok(stack) = fs::read_to_string(format!("{task}/stack")) {
+        return parse_kernel_stack(&stack);
+    }
+
+    // This is "0" if the thread is running rather than waiting.
+    match fs::read_to_string(format!("{task}/wchan")) {
+        Ok(wchan) if !wchan.is_empty() && wchan != "0" => {
+            vec![Frame::new(wchan.trim(), FrameKind::Kernel)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Parses a `/proc/<PID>/stack` file, where each line looks like
+/// `[<0>] do_sys_poll+0x2b3/0x590`.
+fn parse_kernel_stack(stack: &str) -> Vec<Frame> {
+    stack
+        .lines()
+        .filter_map(|line| {
+            let symbol = line.split_once("] ").map_or(line, |(_, symbol)| symbol);
+            let name = symbol.split('+').next()?.trim();
+            (!name.is_empty()).then(|| Frame::new(name, FrameKind::Kernel))
+        })
+        .collect()
+}
+
+/// An executable mapping of a process.
+#[derive(Debug, PartialEq)]
+struct Mapping {
+    start: u64,
+    end: u64,
+    frame: Frame,
+}
+
+/// Parses the executable mappings from a `/proc/<PID>/maps` file. Anonymous
+/// executable memory is assumed to be JIT-compiled code.
+fn parse_maps(maps: &str) -> Vec<Mapping> {
+    maps.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (start, end) = fields.next()?.split_once('-')?;
+            let perms = fields.next()?;
+            if !perms.contains('x') {
+                return None;
+            }
+
+            // Skip the offset, device, and inode.
+            let path = fields.nth(3).unwrap_or_default();
+            let frame = if path.is_empty() {
+                Frame::new("[jit]", FrameKind::Jit)
+            } else {
+                let name = path.rsplit('/').next().unwrap_or(path);
+                Frame::new(name, FrameKind::User)
+            };
+
+            Some(Mapping {
+                start: u64::from_str_radix(start, 16).ok()?,
+                end: u64::from_str_radix(end, 16).ok()?,
+                frame,
+            })
+        })
+        .collect()
+}
+
+/// Turns the addresses of a user stack, innermost first, into frames named
+/// after their modules. Calls within the same module are merged, and addresses
+/// outside of any executable mapping end the stack, as the walk has likely
+/// gone wrong there.
+fn resolve(addresses: &[u64], maps: &[Mapping]) -> Vec<Frame> {
+    let mut frames: Vec<Frame> = Vec::new();
+    for &address in addresses {
+        let Some(mapping) = maps
+            .iter()
+            .find(|mapping| (mapping.start..mapping.end).contains(&address))
+        else {
+            break;
+        };
+
+        if frames.last() != Some(&mapping.frame) {
+            frames.push(mapping.frame.clone());
+        }
+    }
+
+    frames
+}
+
+/// Interrupts a thread, reads its user stack, and lets it continue.
+fn user_frames(tracee: &Tracee, maps: &[Mapping]) -> io::Result<Vec<Frame>> {
+    let stop = tracee.interrupt()?;
+    let registers = tracee.registers();
+    let addresses = registers.map(|(pc, fp)| {
+        let mut addresses = vec![pc];
+        let mut fp = fp;
+        while addresses.len() < MAX_USER_FRAMES && fp != 0 && fp % 8 == 0 {
+            // Each frame starts with the caller's frame pointer, then the
+            // return address.
+            let Ok([next_fp, return_address]) = tracee.read_words(fp) else {
+                break;
+            };
+            if return_address == 0 {
+                break;
+            }
+            addresses.push(return_address);
+
+            // The stack grows down, so callers' frames are always higher up.
+            if next_fp <= fp {
+                break;
+            }
+            fp = next_fp;
+        }
+        addresses
+    });
+    tracee.resume(stop);
+
+    Ok(resolve(&addresses?, maps))
+}
+
+/// Why a thread interrupted with ptrace is stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stop {
+    Interrupted,
+    /// The whole process was already stopped, e.g. by `SIGSTOP`, and has to
+    /// stay that way.
+    GroupStop,
+}
+
+/// A thread seized with ptrace, which is detached from when dropped. It keeps
+/// running while seized, except while interrupted.
+struct Tracee {
+    tid: Pid,
+}
+
+impl Tracee {
+    fn seize(tid: Pid) -> io::Result<Self> {
+        // SAFETY: Seizing doesn't touch our memory.
+        if unsafe {
+            libc::ptrace(
+                libc::PTRACE_SEIZE,
+                tid,
+                ptr::null_mut::<libc::c_void>(),
+                ptr::null_mut::<libc::c_void>(),
+            )
+        } < 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { tid })
+    }
+
+    /// Stops the thread and waits for it to stop. Signals it gets in the
+    /// meantime, which were held back while it was seized, are passed on.
+    fn interrupt(&self) -> io::Result<Stop> {
+        // SAFETY: Interrupting doesn't touch our memory.
+        if unsafe {
+            libc::ptrace(
+                libc::PTRACE_INTERRUPT,
+                self.tid,
+                ptr::null_mut::<libc::c_void>(),
+                ptr::null_mut::<libc::c_void>(),
+            )
+        } < 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+
+        loop {
+            let mut status = 0;
+            // SAFETY: The status is a valid pointer.
+            if unsafe { libc::waitpid(self.tid, &mut status, libc::__WALL) } < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            if !libc::WIFSTOPPED(status) {
+                return Err(io::Error::from_raw_os_error(libc::ESRCH));
+            }
+
+            let signal = libc::WSTOPSIG(status);
+            if status >> 16 == libc::PTRACE_EVENT_STOP {
+                return Ok(if signal == libc::SIGTRAP {
+                    Stop::Interrupted
+                } else {
+                    Stop::GroupStop
+                });
+            }
+
+            // SAFETY: Continuing doesn't touch our memory.
+            unsafe {
+                libc::ptrace(
+                    libc::PTRACE_CONT,
+                    self.tid,
+                    ptr::null_mut::<libc::c_void>(),
+                    signal as usize as *mut libc::c_void,
+                )
+            };
+        }
+    }
+
+    /// Lets an interrupted thread continue, or go back to being stopped along
+    /// with the rest of the process.
+    fn resume(&self, stop: Stop) {
+        let request = match stop {
+            Stop::Interrupted => libc::PTRACE_CONT,
+            Stop::GroupStop => libc::PTRACE_LISTEN,
+        };
+        // SAFETY: Continuing doesn't touch our memory.
+        unsafe {
+            libc::ptrace(
+                request,
+                self.tid,
+                ptr::null_mut::<libc::c_void>(),
+                ptr::null_mut::<libc::c_void>(),
+            )
+        };
+    }
+
+    /// Stops tracking a thread that has exited, which can't be detached from.
+    fn forget(self) {
+        std::mem::forget(self);
+    }
+
+    /// Reads the program counter and frame pointer.
+    #[cfg(target_arch = "x86_64")]
+    fn registers(&self) -> io::Result<(u64, u64)> {
+        // SAFETY: The registers are plain integers, so all zeros is valid.
+        let mut regs: libc::user_regs_struct = unsafe { std::mem::zeroed() };
+        // SAFETY: The kernel writes a `user_regs_struct` to the given pointer.
+        let result = unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETREGS,
+                self.tid,
+                ptr::null_mut::<libc::c_void>(),
+                (&mut regs as *mut libc::user_regs_struct).cast::<libc::c_void>(),
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok((regs.rip, regs.rbp))
+    }
+
+    /// Reads the program counter and frame pointer. There's no
+    /// `PTRACE_GETREGS` on ARM64, so the register set is read instead.
+    #[cfg(target_arch = "aarch64")]
+    fn registers(&self) -> io::Result<(u64, u64)> {
+        /// The register set with the general purpose registers.
+        const NT_PRSTATUS: usize = 1;
+
+        // SAFETY: The registers are plain integers, so all zeros is valid.
+        let mut regs: libc::user_regs_struct = unsafe { std::mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: (&mut regs as *mut libc::user_regs_struct).cast::<libc::c_void>(),
+            iov_len: std::mem::size_of::<libc::user_regs_struct>(),
+        };
+        // SAFETY: The kernel writes at most `iov_len` bytes to `iov_base`.
+        let result = unsafe {
+            libc::ptrace(
+                libc::PTRACE_GETREGSET,
+                self.tid,
+                NT_PRSTATUS as *mut libc::c_void,
+                (&mut iov as *mut libc::iovec).cast::<libc::c_void>(),
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok((regs.pc, regs.regs[29]))
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    fn registers(&self) -> io::Result<(u64, u64)> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "reading registers isn't supported on this architecture",
+        ))
+    }
+
+    /// Reads two words from the process's memory.
+    fn read_words(&self, address: u64) -> io::Result<[u64; 2]> {
+        let mut words = [0_u64; 2];
+        let local = libc::iovec {
+            iov_base: words.as_mut_ptr().cast(),
+            iov_len: std::mem::size_of_val(&words),
+        };
+        let remote = libc::iovec {
+            iov_base: address as usize as *mut libc::c_void,
+            iov_len: std::mem::size_of_val(&words),
+        };
+
+        // SAFETY: Only our local buffer is written to, and at most its length.
+        let read = unsafe { libc::process_vm_readv(self.tid, &local, 1, &remote, 1, 0) };
+        if read < 0 {
+            Err(io::Error::last_os_error())
+        } else if read as usize != local.iov_len {
+            Err(io::ErrorKind::UnexpectedEof.into())
+        } else {
+            Ok(words)
+        }
+    }
+}
+
+impl Drop for Tracee {
+    fn drop(&mut self) {
+        // Only stopped threads can be detached from.
+        if self.interrupt().is_err() {
+            return;
+        }
+
+        // SAFETY: Detaching doesn't touch our memory.
+        unsafe {
+            libc::ptrace(
+                libc::PTRACE_DETACH,
+                self.tid,
+                ptr::null_mut::<libc::c_void>(),
+                ptr::null_mut::<libc::c_void>(),
+            )
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn kernel_stack() {
+        let stack = "[<0>] do_sys_poll+0x2b3/0x590\n[<0>] __x64_sys_poll+0xba/0x150\n";
+        assert_eq!(
+            parse_kernel_stack(stack),
+            vec![
+                Frame::new("do_sys_poll", FrameKind::Kernel),
+                Frame::new("__x64_sys_poll", FrameKind::Kernel),
+            ]
+        );
+    }
+
+    #[test]
+    fn maps() {
+        let maps = "\
+55d0c0a00000-55d0c0a10000 r--p 00000000 08:01 1234    /usr/bin/btm
+55d0c0a10000-55d0c0b00000 r-xp 00010000 08:01 1234    /usr/bin/btm
+7f0000000000-7f0000100000 r-xp 00000000 08:01 5678    /usr/lib/libc.so.6
+7f1000000000-7f1000010000 rwxp 00000000 00:00 0
+";
+        let maps = parse_maps(maps);
+        assert_eq!(maps.len(), 3);
+        assert_eq!(maps[0].frame, Frame::new("btm", FrameKind::User));
+        assert_eq!(maps[2].frame, Frame::new("[jit]", FrameKind::Jit));
+
+        let addresses = [
+            0x7f0000000100,
+            0x7f0000000200,
+            0x7f1000000010,
+            0x55d0c0a10100,
+            0x55d0c0a10200,
+            // This isn't executable, so the stack ends here.
+            0x55d0c0a00100,
+            0x7f0000000100,
+        ];
+        assert_eq!(
+            resolve(&addresses, &maps),
+            vec![
+                Frame::new("libc.so.6", FrameKind::User),
+                Frame::new("[jit]", FrameKind::Jit),
+                Frame::new("btm", FrameKind::User),
+            ]
+        );
+    }
+
+    #[test]
+    fn sample_child() {
+        // Children can be traced without extra privileges, unless ptrace is
+        // disabled entirely.
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let pid = child.id() as Pid;
+        let result = sample_stacks(pid, 5, Duration::from_millis(50));
+
+        // The child is only seized for the burst, and is left running.
+        let status = fs::read_to_string(format!("/proc/{pid}/status")).unwrap();
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(status.contains("\nTracerPid:\t0\n"), "{status}");
+        assert!(!status.contains("\nState:\tt"), "{status}");
+
+        match result {
+            Ok(stacks) => assert!(stacks.total() > 0),
+            Err(err) => assert!(err.starts_with("Not allowed"), "{err}"),
+        }
+    }
+}
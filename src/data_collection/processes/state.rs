@@ -0,0 +1,166 @@
+//! The states a process can be in.
+
+use std::fmt::{self, Display};
+
+/// The state of a process, as given by its single character code in
+/// `/proc/<PID>/stat` on Linux. Other platforms are mapped onto the closest
+/// Linux state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessState {
+    /// `R`, running or runnable.
+    Running,
+    /// `S`, in an interruptible sleep.
+    Sleeping,
+    /// `D`, in an uninterruptible sleep, usually waiting on IO.
+    DiskSleep,
+    /// `T`, stopped by a signal.
+    Stopped,
+    /// `t`, stopped by a debugger.
+    TracingStop,
+    /// `Z`, exited but not yet reaped by its parent.
+    Zombie,
+    /// `X` (or `x` before Linux 3.13), exiting.
+    Dead,
+    /// `K`, woken up to be killed. Only between Linux 2.6.33 and 3.13.
+    WakeKill,
+    /// `W`, waking up. Only between Linux 2.6.33 and 3.13.
+    Waking,
+    /// `P`, a parked kernel thread. Only between Linux 3.9 and 3.13.
+    Parked,
+    /// `I`, an idle kernel thread. Since Linux 4.14.
+    Idle,
+    Unknown(char),
+}
+
+impl From<char> for ProcessState {
+    fn from(code: char) -> Self {
+        match code {
+            'R' => ProcessState::Running,
+            'S' => ProcessState::Sleeping,
+            'D' => ProcessState::DiskSleep,
+            'T' => ProcessState::Stopped,
+            't' => ProcessState::TracingStop,
+            'Z' => ProcessState::Zombie,
+            'X' | 'x' => ProcessState::Dead,
+            'K' => ProcessState::WakeKill,
+            'W' => ProcessState::Waking,
+            'P' => ProcessState::Parked,
+            'I' => ProcessState::Idle,
+            code => ProcessState::Unknown(code),
+        }
+    }
+}
+
+impl ProcessState {
+    /// The single character code of the state.
+    pub fn code(&self) -> char {
+        match self {
+            ProcessState::Running => 'R',
+            ProcessState::Sleeping => 'S',
+            ProcessState::DiskSleep => 'D',
+            ProcessState::Stopped => 'T',
+            ProcessState::TracingStop => 't',
+            ProcessState::Zombie => 'Z',
+            ProcessState::Dead => 'X',
+            ProcessState::WakeKill => 'K',
+            ProcessState::Waking => 'W',
+            ProcessState::Parked => 'P',
+            ProcessState::Idle => 'I',
+            ProcessState::Unknown(code) => *code,
+        }
+    }
+
+    /// Whether the process is in a state worth pointing out, as it's stuck or
+    /// not running when it normally would be.
+    pub fn is_unusual(&self) -> bool {
+        matches!(
+            self,
+            ProcessState::DiskSleep
+                | ProcessState::Stopped
+                | ProcessState::TracingStop
+                | ProcessState::Zombie
+                | ProcessState::Dead
+        )
+    }
+}
+
+impl Display for ProcessState {
+    /// Uses the same names as sysinfo, so searching by state is the same on
+    /// every platform.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProcessState::Running => "Runnable",
+            ProcessState::Sleeping => "Sleeping",
+            ProcessState::DiskSleep => "UninterruptibleDiskSleep",
+            ProcessState::Stopped => "Stopped",
+            ProcessState::TracingStop => "Tracing",
+            ProcessState::Zombie => "Zombie",
+            ProcessState::Dead => "Dead",
+            ProcessState::WakeKill => "Wakekill",
+            ProcessState::Waking => "Waking",
+            ProcessState::Parked => "Parked",
+            ProcessState::Idle => "Idle",
+            ProcessState::Unknown(_) => "Unknown",
+        })
+    }
+}
+
+/// Summarizes how many processes are in each unusual state, e.g. `3D 2Z`, or
+/// returns [`None`] if there are none. Stopped and traced processes are
+/// counted together.
+pub fn unusual_state_summary(states: impl IntoIterator<Item = ProcessState>) -> Option<String> {
+    const CODES: [char; 4] = ['D', 'T', 'Z', 'X'];
+
+    let mut counts = [0; CODES.len()];
+    for state in states.into_iter().filter(ProcessState::is_unusual) {
+        let code = match state {
+            ProcessState::TracingStop => 'T',
+            state => state.code(),
+        };
+        if let Some(index) = CODES.iter().position(|c| *c == code) {
+            counts[index] += 1;
+        }
+    }
+
+    let summary = CODES
+        .iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .map(|(code, count)| format!("{count}{code}"))
+        .collect::<Vec<_>>();
+
+    (!summary.is_empty()).then(|| summary.join(" "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn codes() {
+        for code in ['R', 'S', 'D', 'T', 't', 'Z', 'X', 'K', 'W', 'P', 'I', '?'] {
+            assert_eq!(ProcessState::from(code).code(), code);
+        }
+        assert_eq!(ProcessState::from('x'), ProcessState::Dead);
+        assert_eq!(ProcessState::from('?'), ProcessState::Unknown('?'));
+        assert_eq!(
+            ProcessState::from('D').to_string(),
+            "UninterruptibleDiskSleep"
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let states = |codes: &str| codes.chars().map(ProcessState::from).collect::<Vec<_>>();
+
+        assert_eq!(unusual_state_summary(states("RSSI")), None);
+        assert_eq!(
+            unusual_state_summary(states("ZDRSDZDt")),
+            Some("3D 1T 2Z".to_string())
+        );
+        assert_eq!(
+            unusual_state_summary(states("TtX")),
+            Some("2T 1X".to_string())
+        );
+    }
+}
@@ -76,19 +76,18 @@ pub(crate) trait UnixProcessExt {
                 },
                 mem_usage_bytes: process_val.memory(),
                 cpu_usage_percent: process_cpu_usage,
+                // Both are filled in once every process is collected.
+                cpu_usage_percent_per_core: 0.0,
+                cpu_usage_percent_of_total: 0.0,
                 read_bytes_per_sec: disk_usage.read_bytes,
                 write_bytes_per_sec: disk_usage.written_bytes,
                 total_read_bytes: disk_usage.total_read_bytes,
                 total_write_bytes: disk_usage.total_written_bytes,
                 process_state,
+                num_threads: None,
                 uid,
                 user: uid
-                    .and_then(|uid| {
-                        user_table
-                            .get_uid_to_username_mapping(uid)
-                            .map(Into::into)
-                            .ok()
-                    })
+                    .map(|uid| user_table.get_username_or_uid(uid).into())
                     .unwrap_or_else(|| "N/A".into()),
                 time: if process_val.start_time() == 0 {
                     // Workaround for sysinfo occasionally returning a start time equal to UNIX
@@ -100,6 +99,16 @@ pub(crate) trait UnixProcessExt {
                 } else {
                     Duration::from_secs(process_val.run_time())
                 },
+                minflt_per_sec: 0.0,
+                majflt_per_sec: 0.0,
+                wchan: None,
+                wchan_time: Duration::ZERO,
+                namespaces: Default::default(),
+                cgroup_cpu: None,
+                container: None,
+                pss_bytes: None,
+                tcp_connections: None,
+                inotify_watches: None,
                 #[cfg(feature = "gpu")]
                 gpu_mem: 0,
                 #[cfg(feature = "gpu")]
@@ -149,8 +158,15 @@ fn convert_process_status_to_char(status: ProcessStatus) -> char {
     match status {
         ProcessStatus::Run => 'R',
         ProcessStatus::Sleep => 'S',
-        ProcessStatus::Idle => 'D',
+        ProcessStatus::Idle => 'I',
+        ProcessStatus::UninterruptibleDiskSleep => 'D',
+        ProcessStatus::Stop => 'T',
+        ProcessStatus::Tracing => 't',
         ProcessStatus::Zombie => 'Z',
+        ProcessStatus::Dead => 'X',
+        ProcessStatus::Wakekill => 'K',
+        ProcessStatus::Waking => 'W',
+        ProcessStatus::Parked => 'P',
         _ => '?',
     }
 }
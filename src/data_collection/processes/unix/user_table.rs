@@ -30,4 +30,34 @@ impl UserTable {
             }
         }
     }
+
+    /// Returns the name of the user with the given UID, or the UID itself if it
+    /// has no passwd entry. The UID is remembered either way, so it isn't looked
+    /// up again on every update.
+    pub fn get_username_or_uid(&mut self, uid: libc::uid_t) -> String {
+        self.get_uid_to_username_mapping(uid).unwrap_or_else(|_| {
+            let username = uid.to_string();
+            self.uid_user_mapping.insert(uid, username.clone());
+
+            username
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unknown_uid() {
+        let mut user_table = UserTable::default();
+
+        // No system should have a user with a UID this large.
+        let uid = 3_999_999_999;
+        assert_eq!(user_table.get_username_or_uid(uid), uid.to_string());
+        assert_eq!(
+            user_table.uid_user_mapping.get(&uid),
+            Some(&uid.to_string())
+        );
+    }
 }
@@ -0,0 +1,160 @@
+//! Counting processes by user and by state, from the processes that were
+//! already collected.
+
+use cfg_if::cfg_if;
+use hashbrown::HashMap;
+
+use super::{ProcessHarvest, ProcessState};
+
+/// How many processes and threads a user has.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserProcCount {
+    /// The user ID. This is always 0 on Windows, where processes are only
+    /// told apart by their username.
+    pub uid: u32,
+    pub username: String,
+    pub proc_count: u32,
+    pub thread_count: u32,
+}
+
+/// How many processes and threads there are in total, and how many processes
+/// are in each state, like the counts in `/proc/loadavg`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessCounts {
+    pub processes: u32,
+    pub threads: u32,
+    pub running: u32,
+    /// Processes in an interruptible or uninterruptible sleep, or idle.
+    pub sleeping: u32,
+    /// Processes stopped by a signal or a debugger.
+    pub stopped: u32,
+    pub zombies: u32,
+}
+
+/// The number of threads in a process. Where this isn't collected, each
+/// process is counted as having one.
+fn num_threads(process: &ProcessHarvest) -> u32 {
+    process.num_threads.unwrap_or(1)
+}
+
+fn uid(process: &ProcessHarvest) -> u32 {
+    cfg_if! {
+        if #[cfg(target_family = "unix")] {
+            process.uid.unwrap_or(u32::MAX)
+        } else {
+            let _ = process;
+            0
+        }
+    }
+}
+
+/// Counts the processes and threads of each user, with the users with the
+/// most processes first.
+pub fn aggregate_by_user<'a>(
+    processes: impl IntoIterator<Item = &'a ProcessHarvest>,
+) -> Vec<UserProcCount> {
+    let mut counts: HashMap<(u32, &str), (u32, u32)> = HashMap::new();
+    for process in processes {
+        let (proc_count, thread_count) = counts
+            .entry((uid(process), process.user.as_ref()))
+            .or_default();
+        *proc_count += 1;
+        *thread_count += num_threads(process);
+    }
+
+    let mut counts = counts
+        .into_iter()
+        .map(
+            |((uid, username), (proc_count, thread_count))| UserProcCount {
+                uid,
+                username: username.to_string(),
+                proc_count,
+                thread_count,
+            },
+        )
+        .collect::<Vec<_>>();
+    counts.sort_by(|a, b| {
+        b.proc_count
+            .cmp(&a.proc_count)
+            .then_with(|| a.username.cmp(&b.username))
+    });
+
+    counts
+}
+
+/// Counts all the processes and threads, and the processes in each state.
+pub fn count_processes<'a>(
+    processes: impl IntoIterator<Item = &'a ProcessHarvest>,
+) -> ProcessCounts {
+    let mut counts = ProcessCounts::default();
+    for process in processes {
+        counts.processes += 1;
+        counts.threads += num_threads(process);
+
+        match ProcessState::from(process.process_state.1) {
+            ProcessState::Running => counts.running += 1,
+            ProcessState::Sleeping | ProcessState::DiskSleep | ProcessState::Idle => {
+                counts.sleeping += 1
+            }
+            ProcessState::Stopped | ProcessState::TracingStop => counts.stopped += 1,
+            ProcessState::Zombie => counts.zombies += 1,
+            _ => {}
+        }
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    fn process(user: &'static str, state: char, num_threads: Option<u32>) -> ProcessHarvest {
+        ProcessHarvest {
+            process_state: (ProcessState::from(state).to_string(), state),
+            num_threads,
+            user: Cow::Borrowed(user),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn by_user() {
+        let processes = [
+            process("alice", 'S', Some(4)),
+            process("bob", 'R', Some(1)),
+            process("alice", 'R', Some(2)),
+            process("carol", 'Z', None),
+            process("bob", 'S', Some(3)),
+            process("alice", 'T', Some(1)),
+        ];
+
+        let counts = aggregate_by_user(&processes)
+            .into_iter()
+            .map(|count| (count.username, count.proc_count, count.thread_count))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            vec![
+                ("alice".to_string(), 3, 7),
+                ("bob".to_string(), 2, 4),
+                ("carol".to_string(), 1, 1),
+            ]
+        );
+
+        assert_eq!(
+            count_processes(&processes),
+            ProcessCounts {
+                processes: 6,
+                threads: 12,
+                running: 2,
+                sleeping: 2,
+                stopped: 1,
+                zombies: 1,
+            }
+        );
+        assert!(aggregate_by_user(&[]).is_empty());
+    }
+}
@@ -3,6 +3,8 @@
 use std::time::Duration;
 
 use super::ProcessHarvest;
+#[cfg(feature = "gpu")]
+use crate::data_collection::gpu::{process_gpu_mem, process_gpu_util};
 use crate::data_collection::{error::CollectionResult, DataCollector};
 
 // TODO: There's a lot of shared code with this and the unix impl.
@@ -68,13 +70,9 @@ pub fn sysinfo_process_data(
             let mut gpu_util = 0;
             let mut gpu_mem_percent = 0.0;
             if let Some(gpus) = &collector.gpu_pids {
-                gpus.iter().for_each(|gpu| {
-                    // add mem/util for all gpus to pid
-                    if let Some((mem, util)) = gpu.get(&process_val.pid().as_u32()) {
-                        gpu_mem += mem;
-                        gpu_util += util;
-                    }
-                });
+                let pid = process_val.pid().as_u32();
+                gpu_mem = process_gpu_mem(gpus, pid).unwrap_or_default();
+                gpu_util = process_gpu_util(gpus, pid);
             }
             if let Some(gpu_total_mem) = &collector.gpus_total_mem {
                 gpu_mem_percent = (gpu_mem as f64 / *gpu_total_mem as f64 * 100.0) as f32;
@@ -93,11 +91,15 @@ pub fn sysinfo_process_data(
             } as f32,
             mem_usage_bytes: process_val.memory(),
             cpu_usage_percent: process_cpu_usage,
+            // Both are filled in once every process is collected.
+            cpu_usage_percent_per_core: 0.0,
+            cpu_usage_percent_of_total: 0.0,
             read_bytes_per_sec: disk_usage.read_bytes,
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
             process_state,
+            num_threads: None,
             user: process_val
                 .user_id()
                 .and_then(|uid| users.get_user_by_id(uid))
@@ -112,6 +114,16 @@ pub fn sysinfo_process_data(
             } else {
                 Duration::from_secs(process_val.run_time())
             },
+            minflt_per_sec: 0.0,
+            majflt_per_sec: 0.0,
+            wchan: None,
+            wchan_time: Duration::ZERO,
+            namespaces: Default::default(),
+            cgroup_cpu: None,
+            container: None,
+            pss_bytes: None,
+            tcp_connections: None,
+            inotify_watches: None,
             #[cfg(feature = "gpu")]
             gpu_mem,
             #[cfg(feature = "gpu")]
@@ -0,0 +1,245 @@
+//! Made-up data for developing and testing the interface, such as to see how
+//! high usage, alerts, and themes look without loading the system.
+
+use std::{
+    borrow::Cow,
+    f64::consts::TAU,
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{
+    cpu::{CpuData, CpuDataType},
+    memory::MemHarvest,
+    network::NetworkHarvest,
+    processes::{Pid, ProcessHarvest},
+    Data,
+};
+
+/// How much memory the made-up system has.
+const SYNTHETIC_TOTAL_MEMORY: u64 = 16 * 1024 * 1024 * 1024;
+
+/// What to make up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntheticSettings {
+    /// The CPU usage of each core, in percent.
+    pub cpu_percent: f64,
+    /// The memory usage, in percent.
+    pub mem_percent: f64,
+    /// How much is received and sent over the network each second, in bits.
+    pub net_bits_per_sec: u64,
+    /// If set, usage rises from 0 to the values above and back over this
+    /// long, rather than staying put.
+    pub period: Option<Duration>,
+    /// How many processes there are, which share the CPU and memory usage
+    /// between them.
+    pub num_processes: usize,
+}
+
+impl SyntheticSettings {
+    pub const DEFAULT_CPU_PERCENT: f64 = 50.0;
+    pub const DEFAULT_MEM_PERCENT: f64 = 50.0;
+    pub const DEFAULT_NET_BITS_PER_SEC: u64 = 10_000_000;
+    pub const DEFAULT_NUM_PROCESSES: usize = 20;
+}
+
+/// Makes up data in place of a [`DataCollector`](super::DataCollector), with
+/// the same way of updating it.
+#[derive(Debug)]
+pub struct SyntheticCollector {
+    pub data: Data,
+    settings: SyntheticSettings,
+    show_average_cpu: bool,
+    num_cpus: usize,
+    start: Instant,
+    last_collection_time: Option<Instant>,
+    total_rx: u64,
+    total_tx: u64,
+}
+
+impl SyntheticCollector {
+    pub fn new(settings: SyntheticSettings) -> Self {
+        Self {
+            data: Data::default(),
+            settings,
+            show_average_cpu: false,
+            num_cpus: thread::available_parallelism().map_or(4, |num| num.get()),
+            start: Instant::now(),
+            last_collection_time: None,
+            total_rx: 0,
+            total_tx: 0,
+        }
+    }
+
+    pub fn set_show_average_cpu(&mut self, show_average_cpu: bool) {
+        self.show_average_cpu = show_average_cpu;
+    }
+
+    /// How far through its cycle a value is at `now`, from 0 to 1, with each
+    /// value offset by an `offset` fraction of the cycle. Without a period,
+    /// values stay at 1.
+    fn level(&self, now: Instant, offset: f64) -> f64 {
+        match self.settings.period {
+            Some(period) if !period.is_zero() => {
+                let phase = now.duration_since(self.start).as_secs_f64() / period.as_secs_f64();
+                (1.0 - (TAU * (phase + offset)).cos()) / 2.0
+            }
+            _ => 1.0,
+        }
+    }
+
+    pub fn update_data(&mut self) {
+        let now = Instant::now();
+        self.data.collection_time = now;
+
+        let cores = (0..self.num_cpus)
+            .map(|core| {
+                let offset = core as f64 / self.num_cpus as f64;
+                self.settings.cpu_percent * self.level(now, offset)
+            })
+            .collect::<Vec<_>>();
+        let average_cpu = cores.iter().sum::<f64>() / cores.len() as f64;
+
+        let mut cpu = cores
+            .iter()
+            .enumerate()
+            .map(|(core, &cpu_usage)| CpuData {
+                data_type: CpuDataType::Cpu(core),
+                cpu_usage,
+            })
+            .collect::<Vec<_>>();
+        if self.show_average_cpu {
+            cpu.insert(
+                0,
+                CpuData {
+                    data_type: CpuDataType::Avg,
+                    cpu_usage: average_cpu,
+                },
+            );
+        }
+        self.data.cpu = Some(cpu);
+
+        let load = (average_cpu / 100.0 * self.num_cpus as f64) as f32;
+        self.data.load_avg = Some([load; 3]);
+
+        let mem_percent = self.settings.mem_percent * self.level(now, 0.0);
+        let used_bytes = (SYNTHETIC_TOTAL_MEMORY as f64 * mem_percent / 100.0) as u64;
+        self.data.memory = Some(MemHarvest {
+            used_bytes,
+            total_bytes: SYNTHETIC_TOTAL_MEMORY,
+            use_percent: Some(mem_percent),
+        });
+
+        // There's no swap, but memory is only shown along with it.
+        self.data.swap = Some(MemHarvest {
+            used_bytes: 0,
+            total_bytes: 0,
+            use_percent: None,
+        });
+
+        let net_bits_per_sec = self.settings.net_bits_per_sec as f64;
+        let rx = (net_bits_per_sec * self.level(now, 0.25)) as u64;
+        let tx = (net_bits_per_sec * self.level(now, 0.75)) as u64;
+        if let Some(last) = self.last_collection_time {
+            let elapsed = now.duration_since(last).as_secs_f64();
+            self.total_rx += (rx as f64 * elapsed) as u64;
+            self.total_tx += (tx as f64 * elapsed) as u64;
+        }
+        self.data.network = Some(NetworkHarvest {
+            rx,
+            tx,
+            total_rx: self.total_rx,
+            total_tx: self.total_tx,
+            ..Default::default()
+        });
+
+        let mut processes = self.processes(average_cpu, mem_percent, used_bytes);
+        for process in &mut processes {
+            process.set_cpu_usage_modes(false, self.num_cpus);
+        }
+        self.data.num_cpus = Some(self.num_cpus);
+        self.data.list_of_processes = Some(processes);
+        self.last_collection_time = Some(now);
+    }
+
+    /// Makes up processes that share the CPU and memory usage between them,
+    /// with the first getting the most, half as much for the second, a third
+    /// for the third, and so on.
+    fn processes(
+        &self, cpu_percent: f64, mem_percent: f64, used_bytes: u64,
+    ) -> Vec<ProcessHarvest> {
+        let num_processes = self.settings.num_processes;
+        let total_weight = (1..=num_processes).map(|n| 1.0 / n as f64).sum::<f64>();
+        let uptime = self.start.elapsed();
+
+        (1..=num_processes)
+            .map(|n| {
+                let share = 1.0 / n as f64 / total_weight;
+                let name = format!("synthetic-{n}");
+
+                ProcessHarvest {
+                    pid: n as Pid,
+                    parent_pid: None,
+                    cpu_usage_percent: (cpu_percent * share) as f32,
+                    mem_usage_percent: (mem_percent * share) as f32,
+                    mem_usage_bytes: (used_bytes as f64 * share) as u64,
+                    command: format!("{name} --simulate"),
+                    name,
+                    process_state: ("Running".to_string(), 'R'),
+                    user: Cow::Borrowed("synthetic"),
+                    time: uptime,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn settings(period: Option<Duration>) -> SyntheticSettings {
+        SyntheticSettings {
+            cpu_percent: 80.0,
+            mem_percent: 40.0,
+            net_bits_per_sec: 1000,
+            period,
+            num_processes: 3,
+        }
+    }
+
+    #[test]
+    fn steady() {
+        let mut collector = SyntheticCollector::new(settings(None));
+        collector.set_show_average_cpu(true);
+        collector.update_data();
+
+        let cpu = collector.data.cpu.as_ref().unwrap();
+        assert_eq!(cpu.len(), collector.num_cpus + 1);
+        assert!(matches!(cpu[0].data_type, CpuDataType::Avg));
+        assert!(cpu.iter().all(|cpu| cpu.cpu_usage == 80.0));
+
+        let memory = collector.data.memory.as_ref().unwrap();
+        assert_eq!(memory.used_bytes, SYNTHETIC_TOTAL_MEMORY * 2 / 5);
+
+        let processes = collector.data.list_of_processes.as_ref().unwrap();
+        assert_eq!(processes.len(), 3);
+        assert!(processes[0].cpu_usage_percent > processes[1].cpu_usage_percent);
+        let total_cpu = processes
+            .iter()
+            .map(|process| process.cpu_usage_percent)
+            .sum::<f32>();
+        assert!((total_cpu - 80.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn oscillating() {
+        let collector = SyntheticCollector::new(settings(Some(Duration::from_secs(10))));
+        let start = collector.start;
+
+        assert!(collector.level(start, 0.0).abs() < 1e-9);
+        assert!((collector.level(start + Duration::from_secs(5), 0.0) - 1.0).abs() < 1e-9);
+        assert!((collector.level(start, 0.5) - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,161 @@
+//! A summary of the system bottom is running on, like `uname -a` with some
+//! extra details. This is only collected once, as none of it changes while
+//! running.
+
+use cfg_if::cfg_if;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+
+use super::processes::{read_rlimits, RlimitEntry};
+
+/// The details shown in the system summary.
+#[derive(Debug, Clone, Default)]
+pub struct SystemSummary {
+    /// What `uname -a` would print.
+    pub uname: String,
+
+    /// The CPU architecture, like `x86_64`.
+    pub arch: String,
+
+    /// How many physical CPU packages there are, if known.
+    pub sockets: Option<usize>,
+
+    /// How many physical cores there are, if known.
+    pub cores: Option<usize>,
+
+    /// How many logical CPUs there are.
+    pub threads: usize,
+
+    /// The total amount of RAM, in bytes.
+    pub total_memory: u64,
+
+    /// The command line the running kernel was booted with, if known.
+    pub kernel_cmdline: Option<String>,
+
+    /// The resource limits of bottom itself, which are usually the same as
+    /// the ones `ulimit -a` shows in the shell that started it.
+    pub limits: Vec<RlimitEntry>,
+}
+
+/// Collects the system summary.
+pub fn full_uname() -> SystemSummary {
+    let sys = System::new_with_specifics(
+        RefreshKind::new()
+            .with_cpu(CpuRefreshKind::new())
+            .with_memory(MemoryRefreshKind::new().with_ram()),
+    );
+
+    SystemSummary {
+        uname: uname(),
+        arch: System::cpu_arch().unwrap_or_else(|| std::env::consts::ARCH.to_string()),
+        sockets: sockets(),
+        cores: sys.physical_core_count(),
+        threads: sys.cpus().len(),
+        total_memory: sys.total_memory(),
+        kernel_cmdline: kernel_cmdline(),
+        limits: read_rlimits(std::process::id() as _),
+    }
+}
+
+#[cfg(target_family = "unix")]
+fn uname() -> String {
+    use std::ffi::CStr;
+
+    // SAFETY: `utsname` is plain data, so all zeroes is valid, and `uname`
+    // only writes to the struct it's given.
+    let mut info: libc::utsname = unsafe { std::mem::zeroed() };
+    if unsafe { libc::uname(&mut info) } != 0 {
+        return fallback_uname();
+    }
+
+    [
+        &info.sysname[..],
+        &info.nodename[..],
+        &info.release[..],
+        &info.version[..],
+        &info.machine[..],
+    ]
+    .iter()
+    .map(|field| {
+        // SAFETY: `uname` null-terminates each field.
+        unsafe { CStr::from_ptr(field.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    })
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+#[cfg(not(target_family = "unix"))]
+fn uname() -> String {
+    fallback_uname()
+}
+
+/// Builds something like `uname -a` from what sysinfo knows.
+fn fallback_uname() -> String {
+    [
+        System::name(),
+        System::host_name(),
+        System::kernel_version(),
+        System::long_os_version(),
+        System::cpu_arch(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn sockets() -> Option<usize> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            std::fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|cpuinfo| count_sockets(&cpuinfo))
+        } else {
+            None
+        }
+    }
+}
+
+/// Counts the distinct physical IDs in `/proc/cpuinfo`. Some architectures,
+/// like most ARM systems, don't list them.
+#[cfg(any(target_os = "linux", test))]
+fn count_sockets(cpuinfo: &str) -> Option<usize> {
+    let mut ids = cpuinfo
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == "physical id").then(|| value.trim())
+        })
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+    ids.dedup();
+
+    (!ids.is_empty()).then_some(ids.len())
+}
+
+fn kernel_cmdline() -> Option<String> {
+    cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            std::fs::read_to_string("/proc/cmdline")
+                .ok()
+                .map(|cmdline| cmdline.trim().to_string())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn physical_sockets() {
+        let cpuinfo = "processor\t: 0\nphysical id\t: 0\ncore id\t: 0\n\n\
+                       processor\t: 1\nphysical id\t: 1\ncore id\t: 0\n\n\
+                       processor\t: 2\nphysical id\t: 0\ncore id\t: 1\n";
+        assert_eq!(count_sockets(cpuinfo), Some(2));
+        assert_eq!(count_sockets("processor\t: 0\nBogoMIPS\t: 48.00\n"), None);
+    }
+}
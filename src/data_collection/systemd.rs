@@ -0,0 +1,221 @@
+//! Data collection for systemd user units that are started at login.
+//!
+//! The units are found by scanning the user unit directories for unit files
+//! installed into `default.target`, and their state is then read with a single
+//! `systemctl --user show`. This works for disabled units too, which
+//! `systemctl` on its own would only list once they've been enabled.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+
+use super::processes::Pid;
+
+/// The target that units started at login are installed into.
+const DEFAULT_TARGET: &str = "default.target";
+
+/// The properties read for each unit.
+const PROPERTIES: &str = "Id,Description,ActiveState,MainPID,UnitFileState";
+
+/// A systemd user unit that is started at login when enabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserUnit {
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+    pub active: String,
+    pub main_pid: Option<Pid>,
+}
+
+/// The directories user unit files are read from, from highest to lowest
+/// priority.
+fn unit_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::with_capacity(4);
+    if let Some(config) = dirs::config_dir() {
+        dirs.push(config.join("systemd/user"));
+    }
+    if let Some(data) = dirs::data_dir() {
+        dirs.push(data.join("systemd/user"));
+    }
+    dirs.push(PathBuf::from("/etc/systemd/user"));
+    dirs.push(PathBuf::from("/usr/lib/systemd/user"));
+
+    dirs
+}
+
+/// Whether a unit file's `[Install]` section has it wanted by
+/// [`DEFAULT_TARGET`].
+fn is_wanted_by_default(contents: &str) -> bool {
+    let mut in_install = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_install = line == "[Install]";
+        } else if in_install {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "WantedBy"
+                    && value
+                        .split_whitespace()
+                        .any(|target| target == DEFAULT_TARGET)
+                {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// The names of the unit files in `dirs` wanted by [`DEFAULT_TARGET`]. A unit
+/// file hides any others with the same name in later directories, as it does
+/// for systemd.
+fn wanted_by_default(dirs: &[impl AsRef<Path>]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut names = Vec::new();
+
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+
+        let mut entries = entries
+            .flatten()
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .into_string()
+                    .ok()
+                    .map(|n| (n, entry.path()))
+            })
+            .filter(|(name, _)| name.ends_with(".service"))
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        for (name, path) in entries {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            if fs::read_to_string(path).is_ok_and(|contents| is_wanted_by_default(&contents)) {
+                names.push(name);
+            }
+        }
+    }
+
+    names
+}
+
+/// Parses the output of `systemctl show`, which has a block of `key=value`
+/// lines for each unit separated by blank lines. Blocks without an ID, like
+/// the manager's own properties, are skipped.
+fn parse_show(output: &str) -> Vec<UserUnit> {
+    output
+        .split("\n\n")
+        .filter_map(|block| {
+            let mut unit = UserUnit::default();
+            for (key, value) in block.lines().filter_map(|line| line.split_once('=')) {
+                match key {
+                    "Id" => unit.name = value.to_string(),
+                    "Description" => unit.description = value.to_string(),
+                    "ActiveState" => unit.active = value.to_string(),
+                    "MainPID" => unit.main_pid = value.parse().ok().filter(|pid| *pid != 0),
+                    "UnitFileState" => unit.enabled = value.starts_with("enabled"),
+                    _ => {}
+                }
+            }
+
+            (!unit.name.is_empty()).then_some(unit)
+        })
+        .collect()
+}
+
+fn systemctl() -> Command {
+    let mut command = Command::new("systemctl");
+    command.arg("--user");
+    command
+}
+
+/// Whether there is a systemd user instance to talk to.
+pub fn is_available() -> bool {
+    systemctl()
+        .args(["show", "--property=Version"])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// The user units started at login, or [`None`] if systemd couldn't be
+/// reached.
+pub fn user_units() -> Option<Vec<UserUnit>> {
+    let names = wanted_by_default(&unit_dirs());
+    let output = systemctl()
+        .args(["show", "--property", PROPERTIES, "--"])
+        .args(&names)
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| parse_show(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Enables or disables a user unit, returning what `systemctl` printed if it
+/// failed.
+pub fn set_unit_enabled(unit: &str, enabled: bool) -> Result<(), String> {
+    let action = if enabled { "enable" } else { "disable" };
+    let output = systemctl()
+        .args([action, "--", unit])
+        .output()
+        .map_err(|err| err.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn install_section() {
+        let unit = "[Unit]\nDescription=Sync\n\n[Service]\nExecStart=/usr/bin/sync\n\n[Install]\nWantedBy=graphical-session.target default.target\n";
+        assert!(is_wanted_by_default(unit));
+
+        let unit = "[Unit]\nWantedBy=default.target\n\n[Install]\nWantedBy=timers.target\n";
+        assert!(!is_wanted_by_default(unit));
+        assert!(!is_wanted_by_default("[Service]\nExecStart=/bin/true\n"));
+    }
+
+    #[test]
+    fn show_output() {
+        let output = "Version=255\n\nId=syncthing.service\nDescription=Syncthing - Open Source Continuous File Synchronization\nMainPID=1234\nActiveState=active\nUnitFileState=enabled\n\nMainPID=0\nId=foo.service\nDescription=Foo=bar\nActiveState=inactive\nUnitFileState=disabled\n";
+
+        assert_eq!(
+            parse_show(output),
+            vec![
+                UserUnit {
+                    name: "syncthing.service".to_string(),
+                    description: "Syncthing - Open Source Continuous File Synchronization"
+                        .to_string(),
+                    enabled: true,
+                    active: "active".to_string(),
+                    main_pid: Some(1234),
+                },
+                UserUnit {
+                    name: "foo.service".to_string(),
+                    description: "Foo=bar".to_string(),
+                    enabled: false,
+                    active: "inactive".to_string(),
+                    main_pid: None,
+                },
+            ]
+        );
+    }
+}
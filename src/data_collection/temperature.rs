@@ -13,15 +13,21 @@ cfg_if::cfg_if! {
     }
 }
 
-use std::str::FromStr;
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
 
-#[derive(Default, Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct TempHarvest {
     pub name: String,
     pub temperature: Option<f32>,
 }
 
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TemperatureType {
     #[default]
     Celsius,
@@ -44,7 +50,37 @@ impl FromStr for TemperatureType {
     }
 }
 
+impl Display for TemperatureType {
+    /// Writes the unit label, e.g. `°C`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TemperatureType::Celsius => "°C",
+            TemperatureType::Kelvin => "K",
+            TemperatureType::Fahrenheit => "°F",
+        })
+    }
+}
+
 impl TemperatureType {
+    /// The unit after this one when cycling through them, going from Celsius
+    /// to Fahrenheit to Kelvin.
+    pub fn next(&self) -> Self {
+        match self {
+            TemperatureType::Celsius => TemperatureType::Fahrenheit,
+            TemperatureType::Fahrenheit => TemperatureType::Kelvin,
+            TemperatureType::Kelvin => TemperatureType::Celsius,
+        }
+    }
+
+    /// Given a temperature in this unit, converts it back to Celsius.
+    pub fn to_celsius(self, temp: f32) -> f32 {
+        match self {
+            TemperatureType::Celsius => temp,
+            TemperatureType::Kelvin => temp - 273.15,
+            TemperatureType::Fahrenheit => (temp - 32.0) * 5.0 / 9.0,
+        }
+    }
+
     /// Given a temperature in Celsius, covert it if necessary for a different
     /// unit.
     pub fn convert_temp_unit(&self, temp_celsius: f32) -> f32 {
@@ -81,5 +117,24 @@ mod test {
         assert_eq!(TemperatureType::Kelvin.convert_temp_unit(TEMP), 373.15);
 
         assert_eq!(TemperatureType::Fahrenheit.convert_temp_unit(TEMP), 212.0);
+
+        assert!((TemperatureType::Kelvin.to_celsius(373.15) - TEMP).abs() < 0.01);
+        assert_eq!(TemperatureType::Fahrenheit.to_celsius(212.0), TEMP);
+    }
+
+    #[test]
+    fn temp_cycle() {
+        let mut temp_type = TemperatureType::Celsius;
+        let mut labels = vec![];
+        for _ in 0..3 {
+            temp_type = temp_type.next();
+            labels.push(temp_type.to_string());
+        }
+
+        assert_eq!(labels, ["°F", "K", "°C"]);
+        assert_eq!(
+            serde_json::to_string(&TemperatureType::Fahrenheit).unwrap(),
+            r#""fahrenheit""#
+        );
     }
 }
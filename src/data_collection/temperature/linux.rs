@@ -1,5 +1,7 @@
 //! Gets temperature sensor data for Linux platforms.
 
+mod cores;
+
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -8,6 +10,8 @@ use std::{
 use anyhow::Result;
 use hashbrown::{HashMap, HashSet};
 
+use cores::{map_cores, read_topology, CpuSensorGroup, CpuSensorKind};
+
 use super::{TempHarvest, TemperatureType};
 use crate::app::filter::Filter;
 
@@ -18,6 +22,16 @@ const EMPTY_NAME: &str = "Unknown";
 struct HwmonResults {
     temperatures: Vec<TempHarvest>,
     num_hwmon: usize,
+    /// The sensors of CPU temperature drivers, to map to the cores.
+    cpu_groups: Vec<CpuSensorGroup>,
+}
+
+/// The temperature sensors, along with which of them measure each core.
+pub struct TempData {
+    pub sensors: Vec<TempHarvest>,
+    /// Maps each logical CPU with a sensor of its own to the index of that
+    /// sensor.
+    pub core_sensors: HashMap<usize, usize>,
 }
 
 /// Parses and reads temperatures that were in millidegree Celsius, and if
@@ -226,6 +240,7 @@ fn is_device_awake(path: &Path) -> bool {
 fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> HwmonResults {
     let mut temperatures: Vec<TempHarvest> = vec![];
     let mut seen_names: HashMap<String, u32> = HashMap::new();
+    let mut cpu_groups = vec![];
 
     let (dirs, num_hwmon) = get_hwmon_candidates();
 
@@ -254,6 +269,16 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
             continue;
         }
 
+        let mut cpu_group = sensor_name
+            .as_deref()
+            .and_then(CpuSensorKind::from_hwmon_name)
+            .map(|kind| CpuSensorGroup {
+                kind,
+                device: fs::canonicalize(file_path.join("device"))
+                    .unwrap_or_else(|_| file_path.clone()),
+                sensors: vec![],
+            });
+
         if let Ok(dir_entries) = file_path.read_dir() {
             // Enumerate the devices temperature sensors
             for file in dir_entries.flatten() {
@@ -323,12 +348,17 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
                     }
                 };
 
+                let cpu_label = sensor_label.clone().filter(|_| cpu_group.is_some());
                 let name = finalize_name(hwmon_name, sensor_label, &sensor_name, &mut seen_names);
 
                 // TODO: It's possible we may want to move the filter check further up to avoid
                 // probing hwmon if not needed?
                 if Filter::optional_should_keep(filter, &name) {
                     if let Ok(temp_celsius) = parse_temp(&temp_path) {
+                        if let (Some(group), Some(label)) = (&mut cpu_group, cpu_label) {
+                            group.sensors.push((label, temperatures.len()));
+                        }
+
                         temperatures.push(TempHarvest {
                             name,
                             temperature: Some(temp_type.convert_temp_unit(temp_celsius)),
@@ -337,11 +367,14 @@ fn hwmon_temperatures(temp_type: &TemperatureType, filter: &Option<Filter>) -> H
                 }
             }
         }
+
+        cpu_groups.extend(cpu_group);
     }
 
     HwmonResults {
         temperatures,
         num_hwmon,
+        cpu_groups,
     }
 }
 
@@ -393,17 +426,26 @@ fn add_thermal_zone_temperatures(
     }
 }
 
-/// Gets temperature sensors and data.
+/// Gets temperature sensors and data, and which of them measure each core.
 pub fn get_temperature_data(
     temp_type: &TemperatureType, filter: &Option<Filter>,
-) -> Result<Option<Vec<TempHarvest>>> {
+) -> Result<Option<TempData>> {
     let mut results = hwmon_temperatures(temp_type, filter);
 
     if results.num_hwmon == 0 {
         add_thermal_zone_temperatures(&mut results.temperatures, temp_type, filter);
     }
 
-    Ok(Some(results.temperatures))
+    let core_sensors = if results.cpu_groups.is_empty() {
+        HashMap::new()
+    } else {
+        map_cores(&read_topology(), &results.cpu_groups)
+    };
+
+    Ok(Some(TempData {
+        sensors: results.temperatures,
+        core_sensors,
+    }))
 }
 
 #[cfg(test)]
@@ -0,0 +1,339 @@
+//! Maps CPU temperature sensors to the logical CPUs they measure, using the
+//! topology in `/sys/devices/system/cpu`.
+//!
+//! Intel's `coretemp` has a `Core N` sensor for each physical core, matching
+//! the core's `core_id`, along with a `Package id P` sensor. AMD's `k10temp`
+//! and `zenpower` instead have a `TccdN` sensor for each CCD (core complex
+//! die), and the CCD of a core is found from its L3 cache, which is shared by
+//! each CCX (core complex). Zen 2 has two CCXs per CCD, while later ones have
+//! one.
+
+use std::{fs, path::PathBuf};
+
+use hashbrown::HashMap;
+
+/// Where a logical CPU is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct CpuTopology {
+    pub cpu: usize,
+    pub package: u32,
+    pub core: u32,
+    /// The ID of the L3 cache, which lines up with the CCXs on AMD.
+    pub l3: Option<u32>,
+}
+
+/// Which driver a group of CPU temperature sensors is from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CpuSensorKind {
+    Coretemp,
+    Amd,
+}
+
+impl CpuSensorKind {
+    /// Gets the kind of sensors from the `name` of an hwmon device, if it
+    /// measures CPUs.
+    pub fn from_hwmon_name(name: &str) -> Option<Self> {
+        match name {
+            "coretemp" => Some(CpuSensorKind::Coretemp),
+            "k10temp" | "zenpower" => Some(CpuSensorKind::Amd),
+            _ => None,
+        }
+    }
+}
+
+/// The CPU temperature sensors of one hwmon device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CpuSensorGroup {
+    pub kind: CpuSensorKind,
+    /// The device the sensors are on, which orders the groups of the same
+    /// kind by package.
+    pub device: PathBuf,
+    /// The label of each sensor, with its index in the list of sensors.
+    pub sensors: Vec<(String, usize)>,
+}
+
+impl CpuSensorGroup {
+    fn find(&self, label: &str) -> Option<usize> {
+        self.sensors
+            .iter()
+            .find_map(|(sensor, index)| (sensor == label).then_some(*index))
+    }
+
+    /// The package of a `coretemp` group, from its `Package id P` sensor.
+    fn package(&self) -> Option<u32> {
+        self.sensors
+            .iter()
+            .find_map(|(label, _)| label.strip_prefix("Package id ")?.parse().ok())
+    }
+
+    /// The `TccdN` sensors of an AMD group, ordered by CCD.
+    fn ccds(&self) -> Vec<usize> {
+        let mut ccds = self
+            .sensors
+            .iter()
+            .filter_map(|(label, index)| {
+                let ccd: u32 = label.strip_prefix("Tccd")?.parse().ok()?;
+                Some((ccd, *index))
+            })
+            .collect::<Vec<_>>();
+        ccds.sort_unstable();
+
+        ccds.into_iter().map(|(_, index)| index).collect()
+    }
+}
+
+fn read_id(path: PathBuf) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Reads where each online logical CPU is.
+pub(super) fn read_topology() -> Vec<CpuTopology> {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") else {
+        return vec![];
+    };
+
+    let mut topology = entries
+        .flatten()
+        .filter_map(|entry| {
+            let cpu = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("cpu")?
+                .parse()
+                .ok()?;
+            let path = entry.path();
+
+            Some(CpuTopology {
+                cpu,
+                package: read_id(path.join("topology/physical_package_id"))?,
+                core: read_id(path.join("topology/core_id"))?,
+                l3: read_id(path.join("cache/index3/id")),
+            })
+        })
+        .collect::<Vec<_>>();
+    topology.sort_unstable_by_key(|cpu| cpu.cpu);
+
+    topology
+}
+
+/// Gets the packages of the CPUs, in order.
+fn packages(topology: &[CpuTopology]) -> Vec<u32> {
+    let mut packages = topology.iter().map(|cpu| cpu.package).collect::<Vec<_>>();
+    packages.sort_unstable();
+    packages.dedup();
+
+    packages
+}
+
+/// Maps the CPUs of a package to the `TccdN` sensors of its AMD group.
+fn map_amd_package(
+    topology: &[CpuTopology], package: u32, ccds: &[usize], map: &mut HashMap<usize, usize>,
+) {
+    let cpus = topology.iter().filter(|cpu| cpu.package == package);
+
+    let mut ccxs = cpus.clone().filter_map(|cpu| cpu.l3).collect::<Vec<_>>();
+    ccxs.sort_unstable();
+    ccxs.dedup();
+
+    let ccxs_per_ccd = match (ccxs.len(), ccds.len()) {
+        (_, 0) => return,
+        (ccxs, ccds) if ccxs == ccds => 1,
+        (ccxs, ccds) if ccxs == ccds * 2 => 2,
+        _ => return,
+    };
+
+    for cpu in cpus {
+        let ccd = cpu
+            .l3
+            .and_then(|l3| ccxs.iter().position(|&ccx| ccx == l3))
+            .map(|ccx| ccx / ccxs_per_ccd);
+        if let Some(&sensor) = ccd.and_then(|ccd| ccds.get(ccd)) {
+            map.insert(cpu.cpu, sensor);
+        }
+    }
+}
+
+/// Maps each logical CPU that has a temperature sensor of its own to the
+/// index of that sensor. CPUs whose sensor can't be worked out are left out.
+pub(super) fn map_cores(
+    topology: &[CpuTopology], groups: &[CpuSensorGroup],
+) -> HashMap<usize, usize> {
+    let mut map = HashMap::new();
+    let packages = packages(topology);
+
+    let mut coretemp = vec![];
+    let mut amd = vec![];
+    for group in groups {
+        match group.kind {
+            CpuSensorKind::Coretemp => coretemp.push(group),
+            CpuSensorKind::Amd => amd.push(group),
+        }
+    }
+
+    // Without a package sensor, a coretemp group can only be matched up if
+    // there's just the one package.
+    for group in &coretemp {
+        let package = group
+            .package()
+            .or_else(|| (packages.len() == 1 && coretemp.len() == 1).then(|| packages[0]));
+        let Some(package) = package else {
+            continue;
+        };
+
+        for cpu in topology.iter().filter(|cpu| cpu.package == package) {
+            if let Some(sensor) = group.find(&format!("Core {}", cpu.core)) {
+                map.insert(cpu.cpu, sensor);
+            }
+        }
+    }
+
+    // There's an AMD group per package, which are ordered like the packages.
+    if amd.len() == packages.len() {
+        amd.sort_by(|a, b| a.device.cmp(&b.device));
+        for (group, &package) in amd.iter().zip(&packages) {
+            map_amd_package(topology, package, &group.ccds(), &mut map);
+        }
+    }
+
+    map
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn group(kind: CpuSensorKind, device: &str, labels: &[&str], first: usize) -> CpuSensorGroup {
+        CpuSensorGroup {
+            kind,
+            device: PathBuf::from(device),
+            sensors: labels
+                .iter()
+                .enumerate()
+                .map(|(itx, label)| (label.to_string(), first + itx))
+                .collect(),
+        }
+    }
+
+    fn cpu(cpu: usize, package: u32, core: u32, l3: u32) -> CpuTopology {
+        CpuTopology {
+            cpu,
+            package,
+            core,
+            l3: Some(l3),
+        }
+    }
+
+    #[test]
+    fn intel() {
+        // Two packages with two cores and hyperthreading each, where the cores
+        // of the second package have their own IDs.
+        let topology = [
+            cpu(0, 0, 0, 0),
+            cpu(1, 0, 4, 0),
+            cpu(2, 1, 0, 1),
+            cpu(3, 1, 8, 1),
+            cpu(4, 0, 0, 0),
+            cpu(5, 0, 4, 0),
+            cpu(6, 1, 0, 1),
+            cpu(7, 1, 8, 1),
+        ];
+        let groups = [
+            group(
+                CpuSensorKind::Coretemp,
+                "/coretemp.1",
+                &["Package id 1", "Core 0", "Core 8"],
+                0,
+            ),
+            group(
+                CpuSensorKind::Coretemp,
+                "/coretemp.0",
+                &["Package id 0", "Core 0", "Core 4"],
+                3,
+            ),
+        ];
+
+        let map = map_cores(&topology, &groups);
+        assert_eq!(
+            map,
+            HashMap::from([
+                (0, 4),
+                (1, 5),
+                (2, 1),
+                (3, 2),
+                (4, 4),
+                (5, 5),
+                (6, 1),
+                (7, 2)
+            ])
+        );
+    }
+
+    #[test]
+    fn intel_missing_cores() {
+        let topology = [cpu(0, 0, 0, 0), cpu(1, 0, 1, 0)];
+        let groups = [group(
+            CpuSensorKind::Coretemp,
+            "/coretemp.0",
+            &["Core 1"],
+            0,
+        )];
+
+        assert_eq!(map_cores(&topology, &groups), HashMap::from([(1, 0)]));
+    }
+
+    #[test]
+    fn amd() {
+        // Zen 3, with a CCX per CCD.
+        let topology = [
+            cpu(0, 0, 0, 0),
+            cpu(1, 0, 1, 0),
+            cpu(2, 0, 8, 8),
+            cpu(3, 0, 9, 8),
+        ];
+        let groups = [group(
+            CpuSensorKind::Amd,
+            "/pci",
+            &["Tctl", "Tccd2", "Tccd1"],
+            0,
+        )];
+        assert_eq!(
+            map_cores(&topology, &groups),
+            HashMap::from([(0, 2), (1, 2), (2, 1), (3, 1)])
+        );
+
+        // Zen 2, with two CCXs per CCD.
+        let topology = [
+            cpu(0, 0, 0, 0),
+            cpu(1, 0, 4, 4),
+            cpu(2, 0, 8, 8),
+            cpu(3, 0, 12, 12),
+        ];
+        let groups = [group(
+            CpuSensorKind::Amd,
+            "/pci",
+            &["Tctl", "Tccd1", "Tccd2"],
+            0,
+        )];
+        assert_eq!(
+            map_cores(&topology, &groups),
+            HashMap::from([(0, 1), (1, 1), (2, 2), (3, 2)])
+        );
+
+        // Without per-CCD sensors, only the whole package is measured.
+        let groups = [group(CpuSensorKind::Amd, "/pci", &["Tctl"], 0)];
+        assert!(map_cores(&topology, &groups).is_empty());
+    }
+
+    #[test]
+    fn hwmon_names() {
+        assert_eq!(
+            CpuSensorKind::from_hwmon_name("coretemp"),
+            Some(CpuSensorKind::Coretemp)
+        );
+        assert_eq!(
+            CpuSensorKind::from_hwmon_name("k10temp"),
+            Some(CpuSensorKind::Amd)
+        );
+        assert_eq!(CpuSensorKind::from_hwmon_name("nvme"), None);
+    }
+}
@@ -3,17 +3,34 @@
 
 // TODO: Split this up!
 
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::VecDeque, time::Instant};
+
+use tui::{
+    style::Style,
+    text::{Line, Span},
+};
 
 use crate::{
-    app::{data_farmer::DataCollection, AxisScaling},
+    app::{
+        data_farmer::{DataCollection, Value, PROCESS_HISTORY_LEN},
+        diff::DiffChange,
+        AppConfigFields, AxisScaling,
+    },
     canvas::components::time_chart::Point,
-    data_collection::{cpu::CpuDataType, memory::MemHarvest, temperature::TemperatureType},
-    utils::{data_prefixes::*, data_units::DataUnit},
-    widgets::{DiskWidgetData, TempWidgetData},
+    data_collection::{
+        cpu::CpuDataType,
+        disks::DiskHarvest,
+        memory::{CgroupMemoryLimits, MemHarvest, SwapIOData},
+        network::{NetworkHarvest, SockstatSummary},
+        processes::{aggregate_by_user, count_processes, ProcessCounts, UserProcCount},
+        temperature::{TempHarvest, TemperatureType},
+    },
+    options::config::style::ColourPalette,
+    utils::{data_prefixes::*, data_units::DataUnit, sparkline},
+    widgets::{DiskWidgetData, StartupWidgetData, TempWidgetData},
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub enum BatteryDuration {
     ToEmpty(i64),
     ToFull(i64),
@@ -23,7 +40,7 @@ pub enum BatteryDuration {
     Unknown,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, PartialEq)]
 pub struct ConvertedBatteryData {
     pub charge_percentage: f64,
     pub watt_consumption: String,
@@ -40,6 +57,7 @@ pub struct ConvertedNetworkData {
     pub tx_display: String,
     pub total_rx_display: Option<String>,
     pub total_tx_display: Option<String>,
+    pub ipv6: Option<ConvertedIpv6Data>,
     // TODO: [NETWORKING] add min/max/mean of each
     // min_rx : f64,
     // max_rx : f64,
@@ -49,6 +67,74 @@ pub struct ConvertedNetworkData {
     // mean_tx: f64,
 }
 
+/// The traffic of a single network interface, as graphed.
+#[derive(Clone, Default, Debug)]
+pub struct NetworkInterfaceGraphData {
+    pub name: String,
+    pub rx_points: Vec<Point>,
+    pub tx_points: Vec<Point>,
+    /// The largest point of either, or of any interface if they share an axis.
+    pub y_max: f64,
+    pub rx_display: String,
+    pub tx_display: String,
+}
+
+/// The IPv6 part of network traffic, as displayed.
+#[derive(Clone, Default, Debug)]
+pub struct ConvertedIpv6Data {
+    pub rx_display: String,
+    pub tx_display: String,
+    pub total_rx_display: String,
+    pub total_tx_display: String,
+    pub address: Option<String>,
+}
+
+/// A swap device, as displayed.
+#[derive(Clone, Default, Debug)]
+pub struct ConvertedSwapDevice {
+    pub path: String,
+    pub kind: String,
+    pub total: String,
+    pub used: String,
+    pub free: String,
+    pub priority: String,
+    pub compression_ratio: Option<String>,
+}
+
+/// How many sockets of each protocol are in use over IPv4 or IPv6, as
+/// displayed. Orphaned and `TIME_WAIT` sockets, and socket memory, are only
+/// counted for both together, so they're only on the IPv4 row.
+#[derive(Clone, Default, Debug)]
+pub struct ConvertedSockstatRow {
+    pub family: &'static str,
+    pub tcp: String,
+    pub orphaned: String,
+    pub time_wait: String,
+    pub udp: String,
+    pub raw: String,
+    pub mem: String,
+    pub is_orphaned_high: bool,
+    pub is_time_wait_high: bool,
+}
+
+/// The socket stats panel, as displayed.
+#[derive(Clone, Default, Debug)]
+pub struct ConvertedSockstat {
+    pub sockets_used: String,
+    pub rows: Vec<ConvertedSockstatRow>,
+}
+
+/// A zone's free memory and how fragmented it is, as displayed.
+#[derive(Clone, Default, Debug)]
+pub struct ConvertedBuddyZone {
+    pub node: String,
+    pub zone: String,
+    pub free: String,
+    pub large_free: String,
+    pub index: String,
+    pub is_fragmented: bool,
+}
+
 #[derive(Clone, Debug)]
 pub enum CpuWidgetData {
     All,
@@ -68,6 +154,11 @@ pub struct ConvertedData {
     pub total_tx_display: String,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
+    /// The traffic of each interface, if shown per interface.
+    pub network_interfaces: Vec<NetworkInterfaceGraphData>,
+    pub ipv6: Option<ConvertedIpv6Data>,
+    /// How much of the kernel's socket pools is in use, if known.
+    pub sockstat: Option<ConvertedSockstat>,
 
     pub mem_labels: Option<(String, String)>,
     #[cfg(not(target_os = "windows"))]
@@ -79,6 +170,16 @@ pub struct ConvertedData {
     #[cfg(not(target_os = "windows"))]
     pub cache_data: Vec<Point>,
     pub swap_data: Vec<Point>,
+    pub swap_devices: Vec<ConvertedSwapDevice>,
+    /// How fragmented the free memory of each zone is.
+    pub buddy_zones: Vec<ConvertedBuddyZone>,
+    /// How quickly pages are being swapped in and out, if known.
+    pub swap_io_label: Option<String>,
+    /// The total proportional set size of every process, if it's computed.
+    pub pss_label: Option<String>,
+    /// The memory limits of the cgroup bottom runs in, if RAM is shown
+    /// against them.
+    pub cgroup_memory_label: Option<String>,
 
     #[cfg(feature = "zfs")]
     pub arc_labels: Option<(String, String)>,
@@ -92,12 +193,62 @@ pub struct ConvertedData {
     pub cpu_data: Vec<CpuWidgetData>,
     pub battery_data: Vec<ConvertedBatteryData>,
     pub disk_data: Vec<DiskWidgetData>,
+    pub startup_data: Vec<StartupWidgetData>,
     pub temp_data: Vec<TempWidgetData>,
+    pub user_proc_data: Vec<UserProcCount>,
+    pub process_counts: ProcessCounts,
 }
 
 impl ConvertedData {
+    /// Converts network data using the units and legend style in the config.
+    pub fn convert_network_data(
+        &mut self, data: &DataCollection, config: &AppConfigFields,
+        baseline: Option<&NetworkHarvest>,
+    ) {
+        let network_data = convert_network_points(
+            data,
+            config.use_basic_mode || config.use_old_network_legend,
+            &config.network_scale_type,
+            &config.network_unit_type,
+            config.network_use_binary_prefix,
+            baseline,
+            config.net_smooth_n,
+        );
+        self.network_data_rx = network_data.rx;
+        self.network_data_tx = network_data.tx;
+        self.rx_display = network_data.rx_display;
+        self.tx_display = network_data.tx_display;
+        self.ipv6 = network_data.ipv6.filter(|_| config.show_ipv6);
+        if let Some(total_rx_display) = network_data.total_rx_display {
+            self.total_rx_display = total_rx_display;
+        }
+        if let Some(total_tx_display) = network_data.total_tx_display {
+            self.total_tx_display = total_tx_display;
+        }
+
+        self.sockstat = data
+            .sockstat
+            .as_ref()
+            .map(|sockstat| convert_sockstat(sockstat, config.show_ipv6));
+
+        self.network_interfaces = if config.network_per_interface {
+            get_interface_network_points(
+                data,
+                &config.network_scale_type,
+                &config.network_unit_type,
+                config.network_use_binary_prefix,
+                config.net_smooth_n,
+                config.network_shared_axis,
+            )
+        } else {
+            Vec::new()
+        };
+    }
+
     // TODO: Can probably heavily reduce this step to avoid clones.
-    pub fn convert_disk_data(&mut self, data: &DataCollection) {
+    /// Converts disk data. If a baseline is given, the used space of each disk
+    /// is compared to that of the disk with the same mount point in it.
+    pub fn convert_disk_data(&mut self, data: &DataCollection, baseline: Option<&[DiskHarvest]>) {
         self.disk_data.clear();
 
         data.disk_harvest
@@ -119,27 +270,83 @@ impl ConvertedData {
                     summed_total_bytes,
                     io_read: Cow::Owned(io_read.to_string()),
                     io_write: Cow::Owned(io_write.to_string()),
+                    endurance: data.disk_endurance.get(&disk.name).copied(),
+                    baseline_used_bytes: baseline
+                        .and_then(|baseline| {
+                            baseline
+                                .iter()
+                                .find(|old| old.mount_point == disk.mount_point)
+                        })
+                        .and_then(|old| old.used_space),
                 });
             });
 
         self.disk_data.shrink_to_fit();
     }
 
-    pub fn convert_temp_data(&mut self, data: &DataCollection, temperature_type: TemperatureType) {
+    /// Converts the user units, matching each to the process of its main PID.
+    #[cfg(feature = "systemd")]
+    pub fn convert_startup_data(&mut self, data: &DataCollection) {
+        let processes = &data.process_data.process_harvest;
+
+        self.startup_data = data
+            .user_units
+            .iter()
+            .map(|unit| {
+                let process = unit.main_pid.and_then(|pid| processes.get(&pid));
+
+                StartupWidgetData {
+                    unit: Cow::Owned(unit.name.clone()),
+                    description: Cow::Owned(unit.description.clone()),
+                    enabled: unit.enabled,
+                    active: Cow::Owned(unit.active.clone()),
+                    pid: unit.main_pid,
+                    cpu_usage_percent: process.map(|process| process.cpu_usage_percent),
+                    mem_usage_bytes: process.map(|process| process.mem_usage_bytes),
+                }
+            })
+            .collect();
+    }
+
+    /// Counts the processes of each user, and of the system as a whole.
+    pub fn convert_user_proc_data(&mut self, data: &DataCollection) {
+        let processes = data.process_data.process_harvest.values();
+
+        self.user_proc_data = aggregate_by_user(processes.clone());
+        self.process_counts = count_processes(processes);
+    }
+
+    /// Converts temperature data. If a baseline is given, each sensor is
+    /// compared to the sensor with the same name in it.
+    pub fn convert_temp_data(
+        &mut self, data: &DataCollection, temperature_type: TemperatureType,
+        baseline: Option<&[TempHarvest]>,
+    ) {
         self.temp_data.clear();
 
         data.temp_harvest.iter().for_each(|temp_harvest| {
+            let history = data.temp_history.get(&temp_harvest.name);
+            let baseline_value = baseline
+                .and_then(|baseline| baseline.iter().find(|old| old.name == temp_harvest.name))
+                .and_then(|old| old.temperature)
+                .map(|temp| temp.ceil() as u64);
+
             self.temp_data.push(TempWidgetData {
                 sensor: Cow::Owned(temp_harvest.name.to_string()),
                 temperature_value: temp_harvest.temperature.map(|temp| temp.ceil() as u64),
                 temperature_type,
+                sparkline: history.map(convert_temp_sparkline).unwrap_or_default(),
+                history: history
+                    .map(|history| convert_temp_points(history, data.current_instant))
+                    .unwrap_or_default(),
+                baseline_value,
             });
         });
 
         self.temp_data.shrink_to_fit();
     }
 
-    pub fn convert_cpu_data(&mut self, current_data: &DataCollection) {
+    pub fn convert_cpu_data(&mut self, current_data: &DataCollection, smooth_n: usize) {
         let current_time = current_data.current_instant;
 
         // (Re-)initialize the vector if the lengths don't match...
@@ -202,11 +409,71 @@ impl ConvertedData {
                         }
                     }
 
+                    smooth_points(data, smooth_n);
                     data.shrink_to_fit();
                 }
             }
         }
     }
+
+    /// Smooths all of the memory graph's data over the given number of points.
+    pub fn smooth_mem_data(&mut self, smooth_n: usize) {
+        smooth_points(&mut self.mem_data, smooth_n);
+        #[cfg(not(target_os = "windows"))]
+        smooth_points(&mut self.cache_data, smooth_n);
+        smooth_points(&mut self.swap_data, smooth_n);
+        #[cfg(feature = "zfs")]
+        smooth_points(&mut self.arc_data, smooth_n);
+        #[cfg(feature = "gpu")]
+        for gpu in self.gpu_data.iter_mut().flatten() {
+            smooth_points(&mut gpu.points, smooth_n);
+        }
+    }
+}
+
+/// Replaces each point's value with the average of it and the up to `n - 1`
+/// points before it. An `n` of 1 leaves the points unchanged.
+pub fn smooth_points(points: &mut [Point], n: usize) {
+    if n <= 1 {
+        return;
+    }
+
+    let values = points.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+    let mut sum = 0.0;
+    for (index, point) in points.iter_mut().enumerate() {
+        sum += values[index];
+        if index >= n {
+            sum -= values[index - n];
+        }
+        point.1 = sum / (index + 1).min(n) as f64;
+    }
+}
+
+/// Converts points into how fast their value changes per second, scaled by
+/// `scale`, e.g. to turn a percentage into bytes. Each delta is placed at the
+/// later of the two points it's between, so there's one point fewer.
+pub fn delta_points(points: &[Point], scale: f64) -> Vec<Point> {
+    points
+        .windows(2)
+        .filter_map(|pair| {
+            let [(prev_time, prev_value), (time, value)] = pair else {
+                return None;
+            };
+            let seconds = (time - prev_time) / 1000.0;
+
+            (seconds > 0.0).then(|| (*time, (value - prev_value) * scale / seconds))
+        })
+        .collect()
+}
+
+/// Splits points into their positive and negative parts, so each can be drawn
+/// in its own colour. The other part is kept at zero rather than left out, so
+/// neither line jumps across the gaps.
+pub fn split_by_sign(points: &[Point]) -> (Vec<Point>, Vec<Point>) {
+    points
+        .iter()
+        .map(|(time, value)| ((*time, value.max(0.0)), (*time, value.min(0.0))))
+        .unzip()
 }
 
 pub fn convert_mem_data_points(data: &DataCollection) -> Vec<Point> {
@@ -227,6 +494,24 @@ pub fn convert_mem_data_points(data: &DataCollection) -> Vec<Point> {
     result
 }
 
+/// Converts the 1, 5, and 15 minute load averages into points for a graph.
+pub fn convert_load_avg_points(data: &DataCollection) -> [Vec<Point>; 3] {
+    let mut result: [Vec<Point>; 3] = Default::default();
+    let current_time = data.current_instant;
+
+    for (time, data) in &data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+        for (points, load) in result.iter_mut().zip(data.load_avg_data) {
+            points.push((-time_from_start, load.into()));
+        }
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn convert_cache_data_points(data: &DataCollection) -> Vec<Point> {
     let mut result: Vec<Point> = Vec::new();
@@ -264,6 +549,104 @@ pub fn convert_swap_data_points(data: &DataCollection) -> Vec<Point> {
     result
 }
 
+/// Converts each swap device into a row for the memory widget's swap table.
+pub fn convert_swap_device_rows(data: &DataCollection) -> Vec<ConvertedSwapDevice> {
+    fn bytes_string(bytes: u64) -> String {
+        let (value, unit) = get_binary_bytes(bytes);
+        format!("{value:.1}{unit}")
+    }
+
+    data.swap_devices
+        .iter()
+        .map(|device| ConvertedSwapDevice {
+            path: device.path.clone(),
+            kind: device.kind.to_string(),
+            total: bytes_string(device.total_bytes),
+            used: bytes_string(device.used_bytes),
+            free: bytes_string(device.total_bytes.saturating_sub(device.used_bytes)),
+            priority: device.priority.to_string(),
+            compression_ratio: device.compression_ratio.map(|ratio| format!("{ratio:.2}x")),
+        })
+        .collect()
+}
+
+pub fn convert_sockstat(sockstat: &SockstatSummary, show_ipv6: bool) -> ConvertedSockstat {
+    let ipv4 = ConvertedSockstatRow {
+        family: "IPv4",
+        tcp: sockstat.ipv4.tcp.to_string(),
+        orphaned: sockstat.tcp_orphaned.to_string(),
+        time_wait: sockstat.tcp_time_wait.to_string(),
+        udp: sockstat.ipv4.udp.to_string(),
+        raw: sockstat.ipv4.raw.to_string(),
+        mem: format!("{:.1}MiB", sockstat.mem_bytes as f64 / MEBI_LIMIT_F64),
+        is_orphaned_high: sockstat.is_orphaned_high(),
+        is_time_wait_high: sockstat.is_time_wait_high(),
+    };
+    let ipv6 = sockstat
+        .ipv6
+        .filter(|_| show_ipv6)
+        .map(|ipv6| ConvertedSockstatRow {
+            family: "IPv6",
+            tcp: ipv6.tcp.to_string(),
+            orphaned: "-".to_string(),
+            time_wait: "-".to_string(),
+            udp: ipv6.udp.to_string(),
+            raw: ipv6.raw.to_string(),
+            mem: "-".to_string(),
+            is_orphaned_high: false,
+            is_time_wait_high: false,
+        });
+
+    ConvertedSockstat {
+        sockets_used: sockstat.sockets_used.to_string(),
+        rows: std::iter::once(ipv4).chain(ipv6).collect(),
+    }
+}
+
+pub fn convert_buddy_zone_rows(data: &DataCollection) -> Vec<ConvertedBuddyZone> {
+    data.buddy_zones
+        .iter()
+        .map(|zone| {
+            let (value, unit) = get_binary_bytes(zone.free_bytes());
+
+            ConvertedBuddyZone {
+                node: zone.node.to_string(),
+                zone: zone.zone.clone(),
+                free: format!("{value:.1}{unit}"),
+                large_free: zone
+                    .large_free_ratio()
+                    .map_or_else(|| "-".to_string(), |ratio| format!("{:.0}%", ratio * 100.0)),
+                index: zone
+                    .fragmentation_index()
+                    .map_or_else(|| "-".to_string(), |index| format!("{index:.3}")),
+                is_fragmented: zone.is_fragmented(),
+            }
+        })
+        .collect()
+}
+
+/// Converts how quickly pages are being swapped in and out into a label like
+/// `Swap I/O: ↓1.2MiB/s ↑0.0B/s`.
+pub fn convert_swap_io_label(swap_io: &SwapIOData) -> String {
+    fn rate_string(bytes_per_sec: u64) -> String {
+        let (value, unit) = get_binary_bytes(bytes_per_sec);
+        format!("{value:.1}{unit}/s")
+    }
+
+    let (swap_in, swap_out) = swap_io.bytes_per_sec();
+    format!(
+        "Swap I/O: ↓{} ↑{}",
+        rate_string(swap_in),
+        rate_string(swap_out)
+    )
+}
+
+/// Converts the total proportional set size into a label like `PSS: 4.2GiB`.
+pub fn convert_pss_label(total_pss: u64) -> String {
+    let (value, unit) = get_binary_bytes(total_pss);
+    format!("PSS: {value:.1}{unit}")
+}
+
 /// Returns the most appropriate binary prefix unit type (e.g. kibibyte) and
 /// denominator for the given amount of bytes.
 ///
@@ -301,8 +684,18 @@ pub fn convert_mem_label(harvest: &MemHarvest) -> Option<(String, String)> {
     }
 }
 
+/// The change in used memory compared to a baseline, in diff mode, like
+/// ` Δ+1.2GiB`.
+pub fn convert_mem_delta_label(harvest: &MemHarvest, baseline: &MemHarvest) -> String {
+    delta_string(harvest.used_bytes, baseline.used_bytes, |bytes| {
+        let (unit, denominator) = get_binary_unit_and_denominator(bytes);
+        format!("{:.1}{unit}", bytes as f64 / denominator)
+    })
+}
+
 pub fn get_network_points(
     data: &DataCollection, scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
+    smooth_n: usize,
 ) -> (Vec<Point>, Vec<Point>) {
     let mut rx: Vec<Point> = Vec::new();
     let mut tx: Vec<Point> = Vec::new();
@@ -312,46 +705,229 @@ pub fn get_network_points(
     for (time, data) in &data.timed_data_vec {
         let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
 
-        let (rx_data, tx_data) = match scale_type {
+        rx.push((-time_from_start, data.rx_data));
+        tx.push((-time_from_start, data.tx_data));
+        if *time == current_time {
+            break;
+        }
+    }
+
+    // Smooth before scaling, so log scales show the log of the average.
+    smooth_points(&mut rx, smooth_n);
+    smooth_points(&mut tx, smooth_n);
+    scale_network_points(&mut rx, scale_type, unit_type, use_binary_prefix);
+    scale_network_points(&mut tx, scale_type, unit_type, use_binary_prefix);
+
+    (rx, tx)
+}
+
+/// Scales network rates, which are in bits, to the unit and scale they're
+/// graphed with.
+fn scale_network_points(
+    points: &mut [Point], scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
+) {
+    for (_, value) in points {
+        *value = match scale_type {
             AxisScaling::Log => {
                 if use_binary_prefix {
                     match unit_type {
-                        DataUnit::Byte => {
-                            // As dividing by 8 is equal to subtracting 4 in base 2!
-                            ((data.rx_data).log2() - 4.0, (data.tx_data).log2() - 4.0)
-                        }
-                        DataUnit::Bit => ((data.rx_data).log2(), (data.tx_data).log2()),
+                        // As dividing by 8 is equal to subtracting 4 in base 2!
+                        DataUnit::Byte => value.log2() - 4.0,
+                        DataUnit::Bit => value.log2(),
                     }
                 } else {
                     match unit_type {
-                        DataUnit::Byte => {
-                            ((data.rx_data / 8.0).log10(), (data.tx_data / 8.0).log10())
-                        }
-                        DataUnit::Bit => ((data.rx_data).log10(), (data.tx_data).log10()),
+                        DataUnit::Byte => (*value / 8.0).log10(),
+                        DataUnit::Bit => value.log10(),
                     }
                 }
             }
             AxisScaling::Linear => match unit_type {
-                DataUnit::Byte => (data.rx_data / 8.0, data.tx_data / 8.0),
-                DataUnit::Bit => (data.rx_data, data.tx_data),
+                DataUnit::Byte => *value / 8.0,
+                DataUnit::Bit => *value,
             },
         };
+    }
+}
 
-        rx.push((-time_from_start, rx_data));
-        tx.push((-time_from_start, tx_data));
-        if *time == current_time {
-            break;
+/// Undoes [`scale_network_points`] for a single value, giving the rate in bits
+/// per second.
+pub fn unscale_network_value(
+    value: f64, scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
+) -> f64 {
+    match scale_type {
+        AxisScaling::Log => {
+            if use_binary_prefix {
+                match unit_type {
+                    DataUnit::Byte => (value + 4.0).exp2(),
+                    DataUnit::Bit => value.exp2(),
+                }
+            } else {
+                match unit_type {
+                    DataUnit::Byte => 10f64.powf(value) * 8.0,
+                    DataUnit::Bit => 10f64.powf(value),
+                }
+            }
         }
+        AxisScaling::Linear => match unit_type {
+            DataUnit::Byte => value * 8.0,
+            DataUnit::Bit => value,
+        },
     }
+}
 
-    (rx, tx)
+/// Gets the RX and TX points of each network interface, each scaled to its own
+/// max unless `shared_axis` is set, in which case they share the max of all of
+/// them. Points are scaled like [`get_network_points`].
+pub fn get_interface_network_points(
+    data: &DataCollection, scale_type: &AxisScaling, unit_type: &DataUnit, use_binary_prefix: bool,
+    smooth_n: usize, shared_axis: bool,
+) -> Vec<NetworkInterfaceGraphData> {
+    let current_time = data.current_instant;
+
+    let mut interfaces = data
+        .network_harvest
+        .interfaces
+        .iter()
+        .map(|interface| {
+            let mut rx_points: Vec<Point> = Vec::new();
+            let mut tx_points: Vec<Point> = Vec::new();
+
+            for (time, data) in &data.timed_data_vec {
+                let time_from_start: f64 =
+                    (current_time.duration_since(*time).as_millis() as f64).floor();
+
+                // Interfaces that came up later have no points before then.
+                if let Some((_, rx, tx)) = data
+                    .net_interface_data
+                    .iter()
+                    .find(|(name, _, _)| *name == interface.name)
+                {
+                    rx_points.push((-time_from_start, *rx));
+                    tx_points.push((-time_from_start, *tx));
+                }
+                if *time == current_time {
+                    break;
+                }
+            }
+
+            smooth_points(&mut rx_points, smooth_n);
+            smooth_points(&mut tx_points, smooth_n);
+            scale_network_points(&mut rx_points, scale_type, unit_type, use_binary_prefix);
+            scale_network_points(&mut tx_points, scale_type, unit_type, use_binary_prefix);
+
+            let y_max = rx_points
+                .iter()
+                .chain(&tx_points)
+                .map(|(_, value)| *value)
+                .fold(0.0, f64::max);
+
+            NetworkInterfaceGraphData {
+                name: interface.name.clone(),
+                rx_points,
+                tx_points,
+                y_max,
+                rx_display: format_bandwidth(interface.rx, unit_type, use_binary_prefix),
+                tx_display: format_bandwidth(interface.tx, unit_type, use_binary_prefix),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if shared_axis {
+        let y_max = interfaces
+            .iter()
+            .map(|interface| interface.y_max)
+            .fold(0.0, f64::max);
+        for interface in &mut interfaces {
+            interface.y_max = y_max;
+        }
+    }
+
+    interfaces
 }
 
+/// Separates a baseline value from the current one in diff mode.
+pub const DIFF_ARROW: &str = " → ";
+
+/// Shows a value next to its baseline as `old → new`, with the arrow styled by
+/// whether the value improved or regressed.
+pub fn convert_diff_line(
+    baseline: String, current: String, change: DiffChange, palette: &ColourPalette,
+) -> Line<'static> {
+    let arrow_style = match change {
+        DiffChange::Unchanged => Style::default(),
+        DiffChange::Improved => palette.improved_text_style,
+        DiffChange::Regressed => palette.regressed_text_style,
+    };
+
+    Line::from(vec![
+        Span::raw(baseline),
+        Span::styled(DIFF_ARROW, arrow_style),
+        Span::raw(current),
+    ])
+}
+
+/// Returns the difference between a current and baseline value as ` Δ+x` or
+/// ` Δ-x`, in diff mode, where `x` is formatted by `format`.
+pub fn delta_string(current: u64, baseline: u64, format: impl FnOnce(u64) -> String) -> String {
+    let (sign, difference) = if current >= baseline {
+        ('+', current - baseline)
+    } else {
+        ('-', baseline - current)
+    };
+
+    format!(" Δ{sign}{}", format(difference))
+}
+
+/// Formats a network rate in bits per second as bits or bytes per second,
+/// scaled to the largest SI (Kb/s, MB/s) or binary (Kib/s, MiB/s) prefix that
+/// keeps the value above one.
+pub fn format_bandwidth(
+    bits_per_sec: u64, unit_type: &DataUnit, use_binary_prefix: bool,
+) -> String {
+    let (unit, rate) = match unit_type {
+        DataUnit::Byte => ("B/s", bits_per_sec / 8),
+        DataUnit::Bit => ("b/s", bits_per_sec),
+    };
+
+    let (value, unit) = if use_binary_prefix {
+        get_binary_prefix(rate, unit)
+    } else {
+        get_decimal_prefix(rate, unit)
+    };
+
+    format!("{value:.1}{unit}")
+}
+
+/// Returns a total amount of network traffic in bits as a string in bytes.
+fn total_string(total: u64, use_binary_prefix: bool) -> String {
+    let (value, unit) = if use_binary_prefix {
+        get_binary_bytes(total / 8)
+    } else {
+        get_decimal_bytes(total / 8)
+    };
+
+    format!("{value:.1}{unit}")
+}
+
+/// Converts network data. If a baseline is given, the difference in rates
+/// compared to it is also displayed.
 pub fn convert_network_points(
     data: &DataCollection, need_four_points: bool, scale_type: &AxisScaling, unit_type: &DataUnit,
-    use_binary_prefix: bool,
+    use_binary_prefix: bool, baseline: Option<&NetworkHarvest>, smooth_n: usize,
 ) -> ConvertedNetworkData {
-    let (rx, tx) = get_network_points(data, scale_type, unit_type, use_binary_prefix);
+    let (rx, tx) = get_network_points(data, scale_type, unit_type, use_binary_prefix, smooth_n);
+    let ipv6 = data
+        .network_harvest
+        .ipv6
+        .as_ref()
+        .map(|ipv6| ConvertedIpv6Data {
+            rx_display: format_bandwidth(ipv6.rx, unit_type, use_binary_prefix),
+            tx_display: format_bandwidth(ipv6.tx, unit_type, use_binary_prefix),
+            total_rx_display: total_string(ipv6.total_rx, use_binary_prefix),
+            total_tx_display: total_string(ipv6.total_tx, use_binary_prefix),
+            address: ipv6.address.clone(),
+        });
 
     let unit = match unit_type {
         DataUnit::Byte => "B/s",
@@ -401,13 +977,31 @@ pub fn convert_network_points(
             )
         };
 
+    let (rx_delta, tx_delta) = match baseline {
+        Some(baseline) => (
+            delta_string(data.network_harvest.rx, baseline.rx, |rate| {
+                format_bandwidth(rate, unit_type, use_binary_prefix)
+            }),
+            delta_string(data.network_harvest.tx, baseline.tx, |rate| {
+                format_bandwidth(rate, unit_type, use_binary_prefix)
+            }),
+        ),
+        None => (String::default(), String::default()),
+    };
+
     if need_four_points {
-        let rx_display = format!("{:.1}{}", rx_converted_result.0, rx_converted_result.1);
+        let rx_display = format!(
+            "{}{rx_delta}",
+            format_bandwidth(data.network_harvest.rx, unit_type, use_binary_prefix)
+        );
         let total_rx_display = Some(format!(
             "{:.1}{}",
             total_rx_converted_result.0, total_rx_converted_result.1
         ));
-        let tx_display = format!("{:.1}{}", tx_converted_result.0, tx_converted_result.1);
+        let tx_display = format!(
+            "{}{tx_delta}",
+            format_bandwidth(data.network_harvest.tx, unit_type, use_binary_prefix)
+        );
         let total_tx_display = Some(format!(
             "{:.1}{}",
             total_tx_converted_result.0, total_tx_converted_result.1
@@ -419,10 +1013,11 @@ pub fn convert_network_points(
             tx_display,
             total_rx_display,
             total_tx_display,
+            ipv6,
         }
     } else {
         let rx_display = format!(
-            "RX: {:<10}  All: {}",
+            "RX: {:<10}  All: {}{rx_delta}",
             if use_binary_prefix {
                 format!("{:.1}{:3}", rx_converted_result.0, rx_converted_result.1)
             } else {
@@ -441,7 +1036,7 @@ pub fn convert_network_points(
             }
         );
         let tx_display = format!(
-            "TX: {:<10}  All: {}",
+            "TX: {:<10}  All: {}{tx_delta}",
             if use_binary_prefix {
                 format!("{:.1}{:3}", tx_converted_result.0, tx_converted_result.1)
             } else {
@@ -467,6 +1062,7 @@ pub fn convert_network_points(
             tx_display,
             total_rx_display: None,
             total_tx_display: None,
+            ipv6,
         }
     }
 }
@@ -509,6 +1105,63 @@ pub fn dec_bytes_string(value: u64) -> String {
 }
 
 #[cfg(feature = "battery")]
+/// Sums the given per-process histories, aligned by their newest sample, and
+/// renders them as a sparkline.
+fn history_sparkline<'a, T: Copy + 'a>(
+    histories: impl IntoIterator<Item = &'a VecDeque<T>>, to_value: impl Fn(T) -> f64,
+) -> String {
+    let mut summed = [0.0; PROCESS_HISTORY_LEN];
+    let mut len = 0;
+
+    for history in histories {
+        len = len.max(history.len());
+        for (sum, value) in summed.iter_mut().rev().zip(history.iter().rev()) {
+            *sum += to_value(*value);
+        }
+    }
+
+    sparkline::render(&summed[PROCESS_HISTORY_LEN - len.min(PROCESS_HISTORY_LEN)..])
+}
+
+/// Renders the CPU usage history of one or more processes as a sparkline.
+pub fn convert_cpu_sparkline<'a>(histories: impl IntoIterator<Item = &'a VecDeque<f64>>) -> String {
+    history_sparkline(histories, |usage| usage)
+}
+
+/// Renders the readings of a temperature sensor as a sparkline.
+pub fn convert_temp_sparkline(history: &VecDeque<(Instant, Value)>) -> String {
+    sparkline::render(
+        &history
+            .iter()
+            .map(|(_, temperature)| *temperature)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Renders the recent readings of the 1 minute load average as a sparkline.
+pub fn convert_load_avg_sparkline(history: &VecDeque<f64>) -> String {
+    sparkline::render(&history.iter().copied().collect::<Vec<_>>())
+}
+
+/// Converts the readings of a temperature sensor into points for a graph,
+/// relative to the current time.
+pub fn convert_temp_points(
+    history: &VecDeque<(Instant, Value)>, current_time: Instant,
+) -> Vec<Point> {
+    history
+        .iter()
+        .map(|(time, temperature)| {
+            let time_from_start = current_time.saturating_duration_since(*time).as_millis() as f64;
+            (-time_from_start, *temperature)
+        })
+        .collect()
+}
+
+/// Renders the memory usage history of one or more processes as a sparkline.
+pub fn convert_mem_sparkline<'a>(histories: impl IntoIterator<Item = &'a VecDeque<u64>>) -> String {
+    history_sparkline(histories, |bytes| bytes as f64)
+}
+
 pub fn convert_battery_harvest(current_data: &DataCollection) -> Vec<ConvertedBatteryData> {
     current_data
         .battery_harvest
@@ -564,6 +1217,8 @@ pub struct ConvertedGpuData {
     pub name: String,
     pub mem_total: String,
     pub mem_percent: String,
+    /// How busy the GPU is and how much power it's drawing, if reported.
+    pub usage: Option<String>,
     pub points: Vec<Point>,
 }
 
@@ -596,7 +1251,8 @@ pub fn convert_gpu_data(current_data: &DataCollection) -> Option<Vec<ConvertedGp
         .gpu_harvest
         .iter()
         .zip(point_vec)
-        .map(|(gpu, points)| {
+        .zip(&current_data.gpu_usage)
+        .map(|((gpu, points), usage)| {
             let short_name = {
                 let last_words = gpu.0.split_whitespace().rev().take(2).collect::<Vec<_>>();
                 let short_name = format!("{} {}", last_words[1], last_words[0]);
@@ -607,6 +1263,7 @@ pub fn convert_gpu_data(current_data: &DataCollection) -> Option<Vec<ConvertedGp
                 name: short_name,
                 points,
                 mem_percent: format!("{:3.0}%", gpu.1.use_percent.unwrap_or(0.0)),
+                usage: usage.summary(),
                 mem_total: {
                     let (unit, denominator) = get_binary_unit_and_denominator(gpu.1.total_bytes);
 
@@ -627,10 +1284,144 @@ pub fn convert_gpu_data(current_data: &DataCollection) -> Option<Vec<ConvertedGp
     }
 }
 
+/// Converts the memory limits of a cgroup into a label like `Limit: 2.0GiB
+/// (high 1.5GiB) of 15.6GiB RAM`.
+pub fn convert_cgroup_memory_label(limits: &CgroupMemoryLimits) -> String {
+    fn bytes_string(bytes: u64) -> String {
+        let (value, unit) = get_binary_bytes(bytes);
+        format!("{value:.1}{unit}")
+    }
+
+    let high = limits
+        .high_bytes
+        .map(|high_bytes| format!(" (high {})", bytes_string(high_bytes)))
+        .unwrap_or_default();
+    format!(
+        "Limit: {}{high} of {} RAM",
+        bytes_string(limits.max_bytes),
+        bytes_string(limits.physical_total_bytes)
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn swap_io_label() {
+        let swap_io = SwapIOData {
+            swap_in_pages_per_sec: 300,
+            swap_out_pages_per_sec: 0,
+            page_size: 4096,
+        };
+
+        assert_eq!(
+            convert_swap_io_label(&swap_io),
+            "Swap I/O: ↓1.2MiB/s ↑0.0B/s"
+        );
+    }
+
+    #[test]
+    fn network_values_unscale() {
+        for scale_type in [AxisScaling::Linear, AxisScaling::Log] {
+            for unit_type in [DataUnit::Byte, DataUnit::Bit] {
+                for use_binary_prefix in [false, true] {
+                    let mut points = [(0.0, 80_000.0)];
+                    scale_network_points(&mut points, &scale_type, &unit_type, use_binary_prefix);
+                    let value = unscale_network_value(
+                        points[0].1,
+                        &scale_type,
+                        &unit_type,
+                        use_binary_prefix,
+                    );
+                    assert!((value - 80_000.0).abs() < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pss_label() {
+        assert_eq!(convert_pss_label(4_509_715_660), "PSS: 4.2GiB");
+    }
+
+    #[test]
+    fn diff_deltas() {
+        let baseline = MemHarvest {
+            used_bytes: 4 * GIBI_LIMIT,
+            total_bytes: 16 * GIBI_LIMIT,
+            use_percent: Some(25.0),
+        };
+        let current = MemHarvest {
+            used_bytes: 5 * GIBI_LIMIT + GIBI_LIMIT / 2,
+            ..baseline
+        };
+        assert_eq!(convert_mem_delta_label(&current, &baseline), " Δ+1.5GiB");
+        assert_eq!(convert_mem_delta_label(&baseline, &current), " Δ-1.5GiB");
+
+        let temp = TempWidgetData {
+            sensor: "cpu".into(),
+            temperature_value: Some(72),
+            temperature_type: TemperatureType::Celsius,
+            sparkline: String::new(),
+            history: vec![],
+            baseline_value: Some(60),
+        };
+        assert_eq!(temp.temperature(), "72°C Δ+12°C");
+    }
+
+    #[test]
+    fn cgroup_memory_label() {
+        let mut limits = CgroupMemoryLimits {
+            max_bytes: 2 << 30,
+            high_bytes: Some(3 << 29),
+            physical_total_bytes: 16 << 30,
+        };
+        assert_eq!(
+            convert_cgroup_memory_label(&limits),
+            "Limit: 2.0GiB (high 1.5GiB) of 16.0GiB RAM"
+        );
+
+        limits.high_bytes = None;
+        assert_eq!(
+            convert_cgroup_memory_label(&limits),
+            "Limit: 2.0GiB of 16.0GiB RAM"
+        );
+    }
+
+    #[test]
+    fn sockstat_rows() {
+        use crate::data_collection::network::SocketCounts;
+
+        let sockstat = SockstatSummary {
+            sockets_used: 120,
+            ipv4: SocketCounts {
+                tcp: 40,
+                udp: 6,
+                raw: 1,
+            },
+            ipv6: Some(SocketCounts {
+                tcp: 3,
+                udp: 2,
+                raw: 0,
+            }),
+            tcp_orphaned: 20_000,
+            tcp_time_wait: 12,
+            mem_bytes: 3 << 19,
+        };
+
+        let converted = convert_sockstat(&sockstat, true);
+        assert_eq!(converted.sockets_used, "120");
+        assert_eq!(converted.rows.len(), 2);
+        assert_eq!(converted.rows[0].mem, "1.5MiB");
+        assert!(converted.rows[0].is_orphaned_high);
+        assert!(!converted.rows[0].is_time_wait_high);
+        assert_eq!(converted.rows[1].tcp, "3");
+        assert_eq!(converted.rows[1].orphaned, "-");
+
+        assert_eq!(convert_sockstat(&sockstat, false).rows.len(), 1);
+    }
+
     #[test]
     fn test_binary_byte_string() {
         assert_eq!(binary_byte_string(0), "0B".to_string());
@@ -656,6 +1447,194 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_format_bandwidth() {
+        assert_eq!(format_bandwidth(0, &DataUnit::Bit, false), "0.0b/s");
+        assert_eq!(
+            format_bandwidth(8_000_000, &DataUnit::Bit, false),
+            "8.0Mb/s"
+        );
+        assert_eq!(
+            format_bandwidth(8_000_000, &DataUnit::Byte, false),
+            "1.0MB/s"
+        );
+        assert_eq!(format_bandwidth(8 * 1024, &DataUnit::Bit, true), "8.0Kib/s");
+        assert_eq!(
+            format_bandwidth(8 * 1024 * 1024, &DataUnit::Byte, true),
+            "1.0MiB/s"
+        );
+    }
+
+    #[test]
+    fn test_smooth_points() {
+        let original = vec![(0.0, 3.0), (1.0, 6.0), (2.0, 0.0), (3.0, 9.0)];
+
+        let mut points = original.clone();
+        smooth_points(&mut points, 1);
+        assert_eq!(points, original);
+
+        smooth_points(&mut points, 3);
+        assert_eq!(points, vec![(0.0, 3.0), (1.0, 4.5), (2.0, 3.0), (3.0, 5.0)]);
+
+        let mut points = original.clone();
+        smooth_points(&mut points, 10);
+        assert_eq!(points, vec![(0.0, 3.0), (1.0, 4.5), (2.0, 3.0), (3.0, 4.5)]);
+    }
+
+    #[test]
+    fn test_delta_points() {
+        let points = vec![
+            (-2000.0, 10.0),
+            (-1000.0, 12.0),
+            (-500.0, 11.0),
+            (-500.0, 0.0),
+        ];
+        assert_eq!(
+            delta_points(&points, 1.0),
+            vec![(-1000.0, 2.0), (-500.0, -2.0)]
+        );
+        assert_eq!(delta_points(&points[..2], 100.0), vec![(-1000.0, 200.0)]);
+        assert!(delta_points(&points[..1], 1.0).is_empty());
+
+        let (positive, negative) = split_by_sign(&[(0.0, 2.0), (1.0, -3.0)]);
+        assert_eq!(positive, vec![(0.0, 2.0), (1.0, 0.0)]);
+        assert_eq!(negative, vec![(0.0, 0.0), (1.0, -3.0)]);
+    }
+
+    #[test]
+    fn test_interface_network_points() {
+        use std::time::{Duration, Instant};
+
+        use crate::{app::data_farmer::TimedData, data_collection::network::InterfaceHarvest};
+
+        let interface = |name: &str, rx: u64, tx: u64| InterfaceHarvest {
+            name: name.to_string(),
+            rx,
+            tx,
+        };
+        let entry = |interfaces: Vec<(&str, f64, f64)>| TimedData {
+            net_interface_data: interfaces
+                .into_iter()
+                .map(|(name, rx, tx)| (name.to_string(), rx, tx))
+                .collect(),
+            ..Default::default()
+        };
+
+        let now = Instant::now();
+        let mut data = DataCollection {
+            current_instant: now,
+            ..Default::default()
+        };
+        data.timed_data_vec = vec![
+            (
+                now - Duration::from_secs(1),
+                entry(vec![("eth0", 1_000_000_000.0, 8.0)]),
+            ),
+            (
+                now,
+                entry(vec![("eth0", 500.0, 0.0), ("wlan0", 100.0, 50.0)]),
+            ),
+        ];
+        data.network_harvest.interfaces =
+            vec![interface("eth0", 500, 0), interface("wlan0", 100, 50)];
+
+        let get = |shared_axis| {
+            get_interface_network_points(
+                &data,
+                &AxisScaling::Linear,
+                &DataUnit::Bit,
+                false,
+                0,
+                shared_axis,
+            )
+        };
+
+        let interfaces = get(false);
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(
+            interfaces[0].rx_points,
+            vec![(-1000.0, 1_000_000_000.0), (0.0, 500.0)]
+        );
+        assert_eq!(interfaces[0].y_max, 1_000_000_000.0);
+        // The quiet interface only has points since it came up, scaled to its
+        // own traffic.
+        assert_eq!(interfaces[1].name, "wlan0");
+        assert_eq!(interfaces[1].tx_points, vec![(0.0, 50.0)]);
+        assert_eq!(interfaces[1].y_max, 100.0);
+        assert_eq!(interfaces[1].rx_display, "100.0b/s");
+
+        let interfaces = get(true);
+        assert!(interfaces
+            .iter()
+            .all(|interface| interface.y_max == 1_000_000_000.0));
+    }
+
+    #[test]
+    fn test_history_sparklines() {
+        let a = VecDeque::from([1.0, 2.0, 3.0]);
+        let b = VecDeque::from([3.0, 3.0]);
+        assert_eq!(convert_cpu_sparkline([&a]), "▁▅█");
+        assert_eq!(convert_cpu_sparkline([&a, &b]), "▁▇█");
+        assert_eq!(convert_cpu_sparkline([]), "");
+
+        let mem = VecDeque::from([10, 20]);
+        assert_eq!(convert_mem_sparkline([&mem]), "▁█");
+    }
+
+    #[test]
+    fn test_load_avg_history() {
+        use std::time::Duration;
+
+        use crate::app::data_farmer::TimedData;
+
+        let mut data = DataCollection::default();
+        let now = data.current_instant;
+        data.timed_data_vec = vec![
+            (
+                now - Duration::from_secs(1),
+                TimedData {
+                    load_avg_data: [1.0, 2.0, 3.0],
+                    ..Default::default()
+                },
+            ),
+            (
+                now,
+                TimedData {
+                    load_avg_data: [4.0, 5.0, 6.0],
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let [one, five, fifteen] = convert_load_avg_points(&data);
+        assert_eq!(one, vec![(-1000.0, 1.0), (0.0, 4.0)]);
+        assert_eq!(five, vec![(-1000.0, 2.0), (0.0, 5.0)]);
+        assert_eq!(fifteen[1], (0.0, 6.0));
+
+        assert_eq!(
+            convert_load_avg_sparkline(&VecDeque::from([0.5, 1.5, 1.0])),
+            "▁█▅"
+        );
+    }
+
+    #[test]
+    fn test_temp_history() {
+        use std::time::Duration;
+
+        let now = Instant::now();
+        let history = VecDeque::from([
+            (now - Duration::from_secs(2), 40.0),
+            (now - Duration::from_secs(1), 80.0),
+            (now, 60.0),
+        ]);
+
+        assert_eq!(convert_temp_sparkline(&history), "▁█▅");
+        assert_eq!(
+            convert_temp_points(&history, now),
+            vec![(-2000.0, 40.0), (-1000.0, 80.0), (0.0, 60.0)]
+        );
+    }
+
     #[test]
     fn test_dec_bytes_per_second_string() {
         assert_eq!(dec_bytes_per_second_string(0), "0B/s".to_string());
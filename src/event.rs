@@ -5,8 +5,13 @@ use std::sync::mpsc::Sender;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
 use crate::{
-    app::{layout_manager::WidgetDirection, App},
-    data_collection::Data,
+    app::{
+        event_log::LogLevel,
+        layout_manager::{BottomWidgetType, WidgetDirection},
+        visible_columns::VisibleColumns,
+        App,
+    },
+    data_collection::{temperature::TemperatureType, Data},
 };
 
 /// Events sent to the main thread.
@@ -17,6 +22,30 @@ pub enum BottomEvent {
     MouseInput(MouseEvent),
     PasteEvent(String),
     Update(Box<Data>),
+    ReplayPosition(u64),
+    /// New data from the remote host at the given index.
+    RemoteUpdate(usize, Box<Data>),
+    /// The remote host at the given index disconnected.
+    RemoteDisconnected(usize),
+    /// A message to add to the event log.
+    Log(LogLevel, String),
+    /// What the `--exec` command printed the last time it ran.
+    ExecOutput(Box<crate::app::exec::ExecOutput>),
+    /// How far the `--disk_bench` benchmark has gotten, or its result.
+    DiskBench(Box<crate::data_collection::disk_bench::BenchmarkResult>),
+    /// The version of a newer release, found by the daily update check.
+    #[cfg(feature = "update-check")]
+    UpdateAvailable(String),
+    /// What's changed in the file followed with `--watch_file` at the given
+    /// index.
+    LogTail(usize, crate::data_collection::log_tail::LogTailUpdate),
+    /// The stacks sampled from a process for its flame graph, or why they
+    /// couldn't be.
+    #[cfg(feature = "flame")]
+    FlameGraph(
+        crate::data_collection::processes::Pid,
+        Result<crate::app::flamegraph::FoldedStacks, String>,
+    ),
     Clean,
     Terminate,
 }
@@ -25,6 +54,16 @@ pub enum BottomEvent {
 #[derive(Debug)]
 pub enum CollectionThreadEvent {
     Reset,
+    /// Pauses a replay.
+    Pause,
+    /// Resumes a paused replay.
+    Resume,
+    /// Collects new data, when only updating on keypresses.
+    Refresh,
+    /// Collects temperatures in a different unit.
+    SetTemperatureType(TemperatureType),
+    /// Collects the data for a different set of process columns.
+    SetVisibleColumns(VisibleColumns),
 }
 
 /// Handle a [`MouseEvent`].
@@ -40,7 +79,9 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
                         // Trigger left click widget activity
                         app.on_left_mouse_up(x, y);
                     }
-                    crossterm::event::MouseButton::Right => {}
+                    crossterm::event::MouseButton::Right => {
+                        app.on_right_mouse_up(x, y);
+                    }
                     _ => {}
                 }
             }
@@ -51,13 +92,20 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
 
 /// Handle a [`KeyEvent`].
 pub fn handle_key_event_or_break(
-    event: KeyEvent, app: &mut App, reset_sender: &Sender<CollectionThreadEvent>,
+    event: KeyEvent, app: &mut App, collection_sender: &Sender<CollectionThreadEvent>,
 ) -> bool {
     // c_debug!("KeyEvent: {event:?}");
 
+    let was_frozen = app.frozen_state.is_frozen();
+    let old_temperature_type = app.app_config_fields.temperature_type;
+    let old_visible_columns = app.visible_columns();
+
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
+        if event.code == KeyCode::Char('q')
+            && !app.is_in_search_widget()
+            && !app.meminfo.is_filtering
+        {
             return true;
         }
         match event.code {
@@ -92,6 +140,7 @@ pub fn handle_key_event_or_break(
                 KeyCode::Char('r') | KeyCode::Char('R') => app.toggle_search_regex(),
                 KeyCode::Char('h') => app.on_left_key(),
                 KeyCode::Char('l') => app.on_right_key(),
+                KeyCode::Char('m') => app.toggle_meminfo(),
                 _ => {}
             }
         } else if let KeyModifiers::CONTROL = event.modifiers {
@@ -101,12 +150,19 @@ pub fn handle_key_event_or_break(
 
             match event.code {
                 KeyCode::Char('f') => app.on_slash(),
+                KeyCode::Char('s') => app.save_column_preset(),
+                KeyCode::Char('m') => app.toggle_meminfo(),
+                KeyCode::Left | KeyCode::Right
+                    if app.current_widget.widget_type == BottomWidgetType::ProcSort =>
+                {
+                    app.move_sort_column(event.code == KeyCode::Right);
+                }
                 KeyCode::Left => app.move_widget_selection(&WidgetDirection::Left),
                 KeyCode::Right => app.move_widget_selection(&WidgetDirection::Right),
                 KeyCode::Up => app.move_widget_selection(&WidgetDirection::Up),
                 KeyCode::Down => app.move_widget_selection(&WidgetDirection::Down),
                 KeyCode::Char('r') => {
-                    if reset_sender.send(CollectionThreadEvent::Reset).is_ok() {
+                    if collection_sender.send(CollectionThreadEvent::Reset).is_ok() {
                         app.reset();
                     }
                 }
@@ -126,6 +182,10 @@ pub fn handle_key_event_or_break(
                 // KeyCode::Backspace => app.skip_word_backspace(),
                 _ => {}
             }
+        } else if event.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT {
+            if let KeyCode::Char('c') | KeyCode::Char('C') = event.code {
+                app.copy_selected_value(true);
+            }
         } else if let KeyModifiers::SHIFT = event.modifiers {
             match event.code {
                 KeyCode::Left => app.move_widget_selection(&WidgetDirection::Left),
@@ -138,5 +198,29 @@ pub fn handle_key_event_or_break(
         }
     }
 
+    let temperature_type = app.app_config_fields.temperature_type;
+    if temperature_type != old_temperature_type {
+        let _ = collection_sender.send(CollectionThreadEvent::SetTemperatureType(temperature_type));
+    }
+
+    let visible_columns = app.visible_columns();
+    if visible_columns != old_visible_columns {
+        let _ = collection_sender.send(CollectionThreadEvent::SetVisibleColumns(visible_columns));
+    }
+
+    if app.app_config_fields.update_on_key {
+        let _ = collection_sender.send(CollectionThreadEvent::Refresh);
+    }
+
+    // When replaying, freezing also pauses the playback itself.
+    let is_frozen = app.frozen_state.is_frozen();
+    if app.replay.is_some() && is_frozen != was_frozen {
+        let _ = collection_sender.send(if is_frozen {
+            CollectionThreadEvent::Pause
+        } else {
+            CollectionThreadEvent::Resume
+        });
+    }
+
     false
 }
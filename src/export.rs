@@ -0,0 +1,198 @@
+//! Exporting and importing collected data as JSON snapshots.
+
+pub(crate) mod hooks;
+pub(crate) mod influxdb;
+#[cfg(unix)]
+pub(crate) mod ipc;
+pub(crate) mod record;
+pub(crate) mod tls;
+pub(crate) mod ws_client;
+pub(crate) mod ws_server;
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::Context;
+use influxdb::InfluxDbExporter;
+use record::Recorder;
+use ws_server::WsServer;
+
+use crate::{
+    app::{
+        layout_manager::UsedWidgets, visible_columns::VisibleColumns, AppConfigFields, DataFilters,
+    },
+    data_collection::{Data, DataCollector},
+};
+
+/// Everywhere collected data is sent to besides the interface.
+#[derive(Default)]
+pub(crate) struct Exporters {
+    pub(crate) recorder: Option<Recorder>,
+    pub(crate) influxdb: Option<InfluxDbExporter>,
+    pub(crate) ws_server: Option<WsServer>,
+    #[cfg(unix)]
+    pub(crate) ipc_server: Option<ipc::IpcServer>,
+}
+
+impl Exporters {
+    /// Sends a data update to each exporter.
+    pub(crate) fn export(&mut self, data: &Data) {
+        if let Some(recorder) = &mut self.recorder {
+            // If writing fails there's nowhere to report it, so just stop recording.
+            if recorder.record(data).is_err() {
+                self.recorder = None;
+            }
+        }
+
+        if let Some(influxdb) = &mut self.influxdb {
+            influxdb.export(data);
+        }
+
+        if let Some(ws_server) = &mut self.ws_server {
+            ws_server.export(data);
+        }
+
+        #[cfg(unix)]
+        if let Some(ipc_server) = &mut self.ipc_server {
+            ipc_server.export(data);
+        }
+    }
+
+    /// Waits for each exporter to send what it has queued and stop, once
+    /// cancelled.
+    pub(crate) fn finish(self) {
+        if let Some(influxdb) = self.influxdb {
+            influxdb.finish();
+        }
+
+        if let Some(ws_server) = self.ws_server {
+            ws_server.finish();
+        }
+
+        #[cfg(unix)]
+        if let Some(ipc_server) = self.ipc_server {
+            ipc_server.finish();
+        }
+    }
+}
+
+/// Every widget's data, for when there's no interface to decide what's needed.
+pub(crate) fn all_widgets(app_config_fields: &AppConfigFields) -> UsedWidgets {
+    UsedWidgets {
+        use_cpu: true,
+        use_mem: true,
+        use_cache: app_config_fields.enable_cache_memory,
+        use_gpu: app_config_fields.enable_gpu,
+        use_net: true,
+        use_proc: true,
+        use_disk: true,
+        use_smart: true,
+        use_temp: true,
+        use_battery: true,
+        use_startup: true,
+        use_user_procs: true,
+    }
+}
+
+/// Collects a single snapshot of data, harvesting everything regardless of
+/// which widgets are in use.
+pub(crate) fn collect_snapshot(app_config_fields: &AppConfigFields, filters: DataFilters) -> Data {
+    collect_once(app_config_fields, filters, all_widgets(app_config_fields))
+}
+
+/// Collects a single sample of only the given widgets' data, for commands that
+/// print something and exit without starting the interface.
+pub(crate) fn collect_once(
+    app_config_fields: &AppConfigFields, filters: DataFilters, used_widgets: UsedWidgets,
+) -> Data {
+    let mut data_state = snapshot_collector(app_config_fields, filters);
+    data_state.set_data_collection(used_widgets);
+
+    // Initialization does a first pass and throws it away; the next update gives
+    // us values that rely on a previous sample (e.g. rates).
+    data_state.init(app_config_fields.collection_flags);
+    data_state.update_data();
+
+    data_state.data
+}
+
+/// A collector that harvests everything, for when there's no interface.
+pub(crate) fn snapshot_collector(
+    app_config_fields: &AppConfigFields, filters: DataFilters,
+) -> DataCollector {
+    let mut data_state = DataCollector::new(filters);
+
+    data_state.set_data_collection(all_widgets(app_config_fields));
+    data_state.set_temperature_type(app_config_fields.temperature_type);
+    data_state.set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
+    data_state.set_unnormalized_cpu(app_config_fields.unnormalized_cpu);
+    data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+    data_state.set_visible_columns(VisibleColumns::all());
+    data_state.set_collect_ipv6(true);
+    data_state.set_compute_pss(app_config_fields.compute_pss);
+    data_state.set_ignore_cgroup_limits(app_config_fields.ignore_cgroup_limits);
+    data_state.set_synthetic(app_config_fields.synthetic.clone());
+
+    data_state
+}
+
+/// Writes a snapshot to the given path as JSON.
+pub(crate) fn write_snapshot(path: &Path, data: &Data) -> anyhow::Result<()> {
+    let file = File::create(path)
+        .with_context(|| format!("Could not create the export file '{}'.", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    serde_json::to_writer(&mut writer, data)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Reads a snapshot previously written by [`write_snapshot`].
+pub(crate) fn read_snapshot(path: &Path) -> anyhow::Result<Data> {
+    let file = File::open(path)
+        .with_context(|| format!("Could not open the snapshot file '{}'.", path.display()))?;
+
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("'{}' is not a valid snapshot file.", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::{memory::MemHarvest, processes::ProcessHarvest};
+
+    #[test]
+    fn snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.json");
+
+        let data = Data {
+            memory: Some(MemHarvest {
+                used_bytes: 100,
+                total_bytes: 200,
+                use_percent: Some(50.0),
+            }),
+            list_of_processes: Some(vec![ProcessHarvest {
+                pid: 1,
+                name: "init".into(),
+                cpu_usage_percent: 1.5,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        write_snapshot(&path, &data).unwrap();
+        let read = read_snapshot(&path).unwrap();
+
+        assert_eq!(read.memory.unwrap().used_bytes, 100);
+
+        let processes = read.list_of_processes.unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].name, "init");
+        assert_eq!(processes[0].cpu_usage_percent, 1.5);
+    }
+}
@@ -0,0 +1,351 @@
+//! Running user-defined commands on data updates and alerts.
+//!
+//! Each hook is a shell command that's sent the current [`Data`] as JSON on
+//! stdin. Hooks run in their own threads so a slow one never holds up the
+//! interface, and any output they print is added to the event log.
+
+use std::{
+    io::{Read, Write},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{app::event_log::LogLevel, data_collection::Data, event::BottomEvent};
+
+/// The default time a hook can run for before it's killed.
+pub const DEFAULT_HOOK_TIMEOUT_MS: u64 = 500;
+
+/// How often to check whether a hook has exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// The environment variable that holds the alert message for `on_alert` hooks.
+const ALERT_ENV_VAR: &str = "BOTTOM_ALERT";
+
+/// Which commands to run, and for how long.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookSettings {
+    pub on_update: Vec<String>,
+    pub on_alert: Vec<String>,
+    pub timeout: Duration,
+}
+
+/// A single command to run.
+struct Hook {
+    command: Arc<str>,
+    /// Whether the last run of this hook hasn't finished yet, in which case
+    /// it's skipped rather than piling up more runs.
+    is_running: Arc<AtomicBool>,
+}
+
+impl Hook {
+    fn new(command: String) -> Self {
+        Self {
+            command: command.into(),
+            is_running: Arc::default(),
+        }
+    }
+}
+
+/// Runs hooks in the background and reports their output to the event log.
+pub(crate) struct Hooks {
+    on_update: Vec<Hook>,
+    on_alert: Vec<Hook>,
+    timeout: Duration,
+    sender: Sender<BottomEvent>,
+}
+
+impl Hooks {
+    pub(crate) fn new(settings: HookSettings, sender: Sender<BottomEvent>) -> Self {
+        Self {
+            on_update: settings.on_update.into_iter().map(Hook::new).collect(),
+            on_alert: settings.on_alert.into_iter().map(Hook::new).collect(),
+            timeout: settings.timeout,
+            sender,
+        }
+    }
+
+    /// Runs the `on_update` hooks with the given data, as well as the
+    /// `on_alert` hooks if an alert was raised. All hooks run in parallel.
+    pub(crate) fn run(&self, data: &Data, alert: Option<&str>) {
+        if self.on_update.is_empty() && (alert.is_none() || self.on_alert.is_empty()) {
+            return;
+        }
+
+        let Ok(input) = serde_json::to_vec(data) else {
+            return;
+        };
+        let input: Arc<[u8]> = input.into();
+
+        for hook in &self.on_update {
+            self.spawn(hook, &input, None);
+        }
+
+        if let Some(alert) = alert {
+            for hook in &self.on_alert {
+                self.spawn(hook, &input, Some(alert));
+            }
+        }
+    }
+
+    fn spawn(&self, hook: &Hook, input: &Arc<[u8]>, alert: Option<&str>) {
+        if hook.is_running.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let command = hook.command.clone();
+        let is_running = hook.is_running.clone();
+        let input = input.clone();
+        let alert = alert.map(str::to_string);
+        let timeout = self.timeout;
+        let sender = self.sender.clone();
+
+        thread::spawn(move || {
+            let start = Instant::now();
+            let result = run_command(&command, &input, alert.as_deref(), timeout);
+            let elapsed = start.elapsed().as_millis();
+            is_running.store(false, Ordering::Release);
+
+            crate::debug!("Hook '{command}' took {elapsed}ms");
+
+            let entry = match result {
+                Ok(HookOutput { status, output }) if status.success() => {
+                    (!output.is_empty()).then(|| {
+                        (
+                            LogLevel::Info,
+                            format!("Hook '{command}' ({elapsed}ms): {output}"),
+                        )
+                    })
+                }
+                Ok(HookOutput { status, output }) => Some((
+                    LogLevel::Warn,
+                    format!("Hook '{command}' failed with {status} ({elapsed}ms): {output}"),
+                )),
+                Err(CommandError::Timeout) => Some((
+                    LogLevel::Warn,
+                    format!(
+                        "Hook '{command}' was killed after taking longer than {}ms",
+                        timeout.as_millis()
+                    ),
+                )),
+                Err(CommandError::Io(err)) => Some((
+                    LogLevel::Warn,
+                    format!("Hook '{command}' could not be run: {err}"),
+                )),
+            };
+
+            if let Some((level, message)) = entry {
+                let _ = sender.send(BottomEvent::Log(level, message));
+            }
+        });
+    }
+}
+
+/// What a hook printed, and how it exited.
+#[derive(Debug)]
+struct HookOutput {
+    status: ExitStatus,
+    /// Everything printed to stdout and then stderr, with lines joined by `; `.
+    output: String,
+}
+
+/// What a shell command printed, and how it exited.
+#[derive(Debug)]
+pub(crate) struct CommandOutput {
+    pub(crate) status: ExitStatus,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+}
+
+#[derive(Debug)]
+pub(crate) enum CommandError {
+    Timeout,
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(err: std::io::Error) -> Self {
+        CommandError::Io(err)
+    }
+}
+
+/// Creates a shell command for the current platform.
+pub(crate) fn shell_command(command: &str) -> Command {
+    #[cfg(target_family = "windows")]
+    {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    }
+
+    #[cfg(not(target_family = "windows"))]
+    {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+/// Reads everything from a pipe in the background, so a chatty command can't
+/// block on a full pipe.
+fn read_in_background(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+/// Waits for the child to exit, killing it if it takes too long.
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<ExitStatus, CommandError> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+
+        if start.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(CommandError::Timeout);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Runs a shell command, killing it if it takes longer than `timeout`. If
+/// there's any input, it's written to the command's stdin.
+pub(crate) fn run_shell_command(
+    mut shell: Command, input: Option<&Arc<[u8]>>, timeout: Duration,
+) -> Result<CommandOutput, CommandError> {
+    shell
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = shell.spawn()?;
+
+    // Write in the background too, as the command may not read all of its input.
+    let writer = child.stdin.take().zip(input).map(|(mut stdin, input)| {
+        let input = input.clone();
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        })
+    });
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let status = wait_with_timeout(&mut child, timeout)?;
+
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+
+    Ok(CommandOutput {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Runs a command with the given input on stdin, and returns what it printed.
+fn run_command(
+    command: &str, input: &Arc<[u8]>, alert: Option<&str>, timeout: Duration,
+) -> Result<HookOutput, CommandError> {
+    let mut shell = shell_command(command);
+    if let Some(alert) = alert {
+        shell.env(ALERT_ENV_VAR, alert);
+    }
+
+    let CommandOutput {
+        status,
+        stdout,
+        stderr,
+    } = run_shell_command(shell, Some(input), timeout)?;
+
+    let output = stdout
+        .lines()
+        .chain(stderr.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    Ok(HookOutput { status, output })
+}
+
+#[cfg(all(test, target_family = "unix"))]
+mod test {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn input(value: &str) -> Arc<[u8]> {
+        value.as_bytes().into()
+    }
+
+    #[test]
+    fn reads_stdin_and_output() {
+        let result = run_command("cat; echo done >&2", &input("a\nb\n"), None, TIMEOUT).unwrap();
+        assert!(result.status.success());
+        assert_eq!(result.output, "a; b; done");
+
+        let result = run_command("exit 3", &input(""), None, TIMEOUT).unwrap();
+        assert_eq!(result.status.code(), Some(3));
+    }
+
+    #[test]
+    fn alert_is_passed_through_env() {
+        let command = format!("echo ${ALERT_ENV_VAR}");
+        let result = run_command(&command, &input(""), Some("CPU exceeded 90%"), TIMEOUT).unwrap();
+        assert_eq!(result.output, "CPU exceeded 90%");
+    }
+
+    #[test]
+    fn slow_hooks_are_killed() {
+        let result = run_command("sleep 5", &input(""), None, Duration::from_millis(50));
+        assert!(matches!(result, Err(CommandError::Timeout)));
+    }
+
+    #[test]
+    fn hooks_log_output() {
+        let (sender, receiver) = mpsc::channel();
+        let hooks = Hooks::new(
+            HookSettings {
+                on_update: vec!["echo update".to_string()],
+                on_alert: vec!["echo $BOTTOM_ALERT".to_string()],
+                timeout: TIMEOUT,
+            },
+            sender,
+        );
+
+        hooks.run(&Data::default(), Some("Disk '/' exceeded 90%"));
+
+        let mut messages = (0..2)
+            .map(|_| match receiver.recv_timeout(TIMEOUT) {
+                Ok(BottomEvent::Log(LogLevel::Info, message)) => message,
+                other => panic!("unexpected event: {other:?}"),
+            })
+            .collect::<Vec<_>>();
+        messages.sort();
+
+        assert!(messages[0].starts_with("Hook 'echo $BOTTOM_ALERT' ("));
+        assert!(messages[0].ends_with("): Disk '/' exceeded 90%"));
+        assert!(messages[1].starts_with("Hook 'echo update' ("));
+        assert!(messages[1].ends_with("): update"));
+    }
+}
@@ -0,0 +1,706 @@
+//! Exporting collected data to InfluxDB using its [line protocol].
+//!
+//! Each [`Data`] update is converted into a set of points, which are sent to
+//! InfluxDB's v2 write API from a background thread so that a slow or
+//! unreachable server never holds up data collection.
+//!
+//! [line protocol]: https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/
+
+use std::{
+    fmt::Write as _,
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context};
+use rustls::ClientConfig;
+
+use crate::{
+    data_collection::{cpu::CpuDataType, Data},
+    export::tls,
+    utils::cancellation_token::CancellationToken,
+};
+
+/// The default maximum number of points sent per request.
+pub const DEFAULT_INFLUXDB_BATCH_SIZE: usize = 5000;
+
+/// How many times sending a batch is attempted before it's dropped.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// How long to wait before the first retry; this doubles after each attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How many updates can be waiting to be sent before new ones are dropped.
+const MAX_QUEUED_UPDATES: usize = 60;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where and how to send data to InfluxDB.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InfluxDbSettings {
+    pub url: String,
+    pub token: Option<String>,
+    pub org: String,
+    pub bucket: String,
+    pub batch_size: usize,
+}
+
+/// The parsed parts of an `http://` or `https://` URL to write to.
+#[derive(Debug, PartialEq, Eq)]
+struct WriteEndpoint {
+    /// The host and port, e.g. `localhost:8086` or `[::1]:8086`.
+    address: String,
+    /// The host without brackets or a port, which TLS checks the server's
+    /// certificate against.
+    host: String,
+    is_https: bool,
+    /// The path and query of the write API.
+    path: String,
+}
+
+impl WriteEndpoint {
+    fn new(settings: &InfluxDbSettings) -> anyhow::Result<Self> {
+        let url = &settings.url;
+        let (rest, is_https, default_port) = if let Some(rest) = url.strip_prefix("http://") {
+            (rest, false, 80)
+        } else if let Some(rest) = url.strip_prefix("https://") {
+            (rest, true, 443)
+        } else {
+            bail!("The InfluxDB URL '{url}' is not supported, it must start with http:// or https://.");
+        };
+
+        let (authority, base_path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+
+        // IPv6 addresses are in brackets, so their colons aren't read as the
+        // start of a port.
+        let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+            let Some((host, after)) = bracketed.split_once(']') else {
+                bail!("The InfluxDB URL '{url}' is missing a closing ']'.");
+            };
+            match after {
+                "" => (host, None),
+                after => match after.strip_prefix(':') {
+                    Some(port) => (host, Some(port)),
+                    None => bail!("The InfluxDB URL '{url}' has an invalid host."),
+                },
+            }
+        } else {
+            match authority.split_once(':') {
+                Some((_, port)) if port.contains(':') => {
+                    bail!("The IPv6 address in the InfluxDB URL '{url}' must be in brackets.")
+                }
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            }
+        };
+        if host.is_empty() {
+            bail!("The InfluxDB URL '{url}' is missing a host.");
+        }
+        let port = match port {
+            Some(port) => port
+                .parse::<u16>()
+                .with_context(|| format!("The InfluxDB URL '{url}' has an invalid port."))?,
+            None => default_port,
+        };
+
+        let address = if host.contains(':') {
+            format!("[{host}]:{port}")
+        } else {
+            format!("{host}:{port}")
+        };
+
+        let path = format!(
+            "{}/api/v2/write?org={}&bucket={}&precision=ns",
+            base_path.trim_end_matches('/'),
+            percent_encode(&settings.org),
+            percent_encode(&settings.bucket),
+        );
+
+        Ok(Self {
+            address,
+            host: host.to_string(),
+            is_https,
+            path,
+        })
+    }
+}
+
+/// Percent-encodes a query parameter value.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// Escapes a measurement name, tag key, or tag value.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Builds a single line protocol point. Fields are given pre-formatted, e.g.
+/// `used=10i`.
+fn point(measurement: &str, tags: &[(&str, &str)], fields: &[String], timestamp: u128) -> String {
+    let mut line = escape(measurement);
+    for (key, value) in tags {
+        let _ = write!(line, ",{}={}", escape(key), escape(value));
+    }
+    let _ = write!(line, " {} {timestamp}", fields.join(","));
+    line
+}
+
+fn float(key: &str, value: f64) -> String {
+    format!("{key}={value}")
+}
+
+fn int(key: &str, value: u64) -> String {
+    format!("{key}={value}i")
+}
+
+/// Converts a [`Data`] update into line protocol points, tagged with the host.
+pub(crate) fn to_line_protocol(data: &Data, host: &str, timestamp: u128) -> Vec<String> {
+    let host_tag = ("host", host);
+    let mut lines = Vec::new();
+
+    if let Some(cpu) = &data.cpu {
+        for cpu in cpu {
+            let core = match cpu.data_type {
+                CpuDataType::Avg => "avg".to_string(),
+                CpuDataType::Cpu(index) => index.to_string(),
+            };
+            lines.push(point(
+                "cpu",
+                &[host_tag, ("cpu", &core)],
+                &[float("usage_percent", cpu.cpu_usage)],
+                timestamp,
+            ));
+        }
+    }
+
+    if let Some(load_avg) = &data.load_avg {
+        lines.push(point(
+            "load_avg",
+            &[host_tag],
+            &[
+                float("load1", load_avg[0].into()),
+                float("load5", load_avg[1].into()),
+                float("load15", load_avg[2].into()),
+            ],
+            timestamp,
+        ));
+    }
+
+    let memory = [
+        ("ram", &data.memory),
+        #[cfg(not(target_os = "windows"))]
+        ("cache", &data.cache),
+        ("swap", &data.swap),
+        #[cfg(feature = "zfs")]
+        ("arc", &data.arc),
+    ];
+    for (kind, harvest) in memory {
+        if let Some(harvest) = harvest {
+            lines.push(point(
+                "memory",
+                &[host_tag, ("kind", kind)],
+                &[
+                    int("used_bytes", harvest.used_bytes),
+                    int("total_bytes", harvest.total_bytes),
+                ],
+                timestamp,
+            ));
+        }
+    }
+
+    #[cfg(feature = "gpu")]
+    if let Some(gpus) = &data.gpu {
+        for (name, harvest) in gpus {
+            lines.push(point(
+                "gpu_memory",
+                &[host_tag, ("gpu", name)],
+                &[
+                    int("used_bytes", harvest.used_bytes),
+                    int("total_bytes", harvest.total_bytes),
+                ],
+                timestamp,
+            ));
+        }
+    }
+
+    #[cfg(feature = "gpu")]
+    if let (Some(gpus), Some(usage)) = (&data.gpu, &data.gpu_usage) {
+        for ((name, _), usage) in gpus.iter().zip(usage) {
+            let fields = [
+                usage
+                    .utilization
+                    .map(|utilization| int("utilization_percent", utilization.into())),
+                usage.power_watts.map(|power| float("power_watts", power)),
+            ];
+            let fields = fields.into_iter().flatten().collect::<Vec<_>>();
+            if !fields.is_empty() {
+                lines.push(point(
+                    "gpu_usage",
+                    &[host_tag, ("gpu", name)],
+                    &fields,
+                    timestamp,
+                ));
+            }
+        }
+    }
+
+    // Network usage is only collected as a total across all interfaces.
+    if let Some(network) = &data.network {
+        lines.push(point(
+            "network",
+            &[host_tag],
+            &[
+                int("rx_bits_per_sec", network.rx),
+                int("tx_bits_per_sec", network.tx),
+                int("total_rx_bits", network.total_rx),
+                int("total_tx_bits", network.total_tx),
+            ],
+            timestamp,
+        ));
+    }
+
+    if let Some(disks) = &data.disks {
+        for disk in disks {
+            let fields = [
+                ("used_bytes", disk.used_space),
+                ("free_bytes", disk.free_space),
+                ("total_bytes", disk.total_space),
+            ]
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| int(key, value)))
+            .collect::<Vec<_>>();
+
+            if !fields.is_empty() {
+                lines.push(point(
+                    "disk",
+                    &[host_tag, ("disk", &disk.name), ("mount", &disk.mount_point)],
+                    &fields,
+                    timestamp,
+                ));
+            }
+        }
+    }
+
+    if let Some(sensors) = &data.temperature_sensors {
+        for sensor in sensors {
+            if let Some(temperature) = sensor.temperature {
+                lines.push(point(
+                    "temperature",
+                    &[host_tag, ("sensor", &sensor.name)],
+                    &[float("value", temperature.into())],
+                    timestamp,
+                ));
+            }
+        }
+    }
+
+    if let Some(processes) = &data.list_of_processes {
+        lines.push(point(
+            "processes",
+            &[host_tag],
+            &[int("count", processes.len() as u64)],
+            timestamp,
+        ));
+    }
+
+    #[cfg(feature = "battery")]
+    if let Some(batteries) = &data.list_of_batteries {
+        for (index, battery) in batteries.iter().enumerate() {
+            lines.push(point(
+                "battery",
+                &[host_tag, ("battery", &index.to_string())],
+                &[
+                    float("charge_percent", battery.charge_percent),
+                    float("health_percent", battery.health_percent),
+                    float("power_watts", battery.power_consumption_rate_watts),
+                ],
+                timestamp,
+            ));
+        }
+    }
+
+    lines
+}
+
+/// Sends a batch of points in a single request, over TLS if `tls_config` is
+/// set.
+fn send_batch(
+    endpoint: &WriteEndpoint, tls_config: Option<&Arc<ClientConfig>>, token: Option<&str>,
+    lines: &[String],
+) -> anyhow::Result<()> {
+    let body = lines.join("\n");
+
+    let address = endpoint
+        .address
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| format!("Could not resolve '{}'.", endpoint.address))?;
+    let stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let mut stream = tls::connect(stream, &endpoint.host, tls_config)?;
+
+    let mut request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n",
+        endpoint.path,
+        endpoint.address,
+        body.len()
+    );
+    if let Some(token) = token {
+        let _ = write!(request, "Authorization: Token {token}\r\n");
+    }
+    request.push_str("\r\n");
+
+    stream.write_all(request.as_bytes())?;
+    stream.write_all(body.as_bytes())?;
+    stream.flush()?;
+
+    // Servers don't always end TLS cleanly after the response, which is fine
+    // as the response has been read by then.
+    let mut response = Vec::new();
+    match stream.read_to_end(&mut response) {
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof && !response.is_empty() => {}
+        Err(err) => return Err(tls::readable_error(err)),
+    }
+    let response = String::from_utf8_lossy(&response);
+
+    let status = response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .context("InfluxDB sent an invalid response.")?;
+
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        let reason = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.trim())
+            .unwrap_or_default();
+        bail!("InfluxDB responded with status {status}: {reason}")
+    }
+}
+
+/// Sends a batch, retrying with exponential backoff if it fails. Returns
+/// `false` if cancelled while waiting to retry.
+fn send_with_retries(
+    endpoint: &WriteEndpoint, tls_config: Option<&Arc<ClientConfig>>, token: Option<&str>,
+    lines: &[String], cancellation_token: &CancellationToken,
+) -> bool {
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        match send_batch(endpoint, tls_config, token, lines) {
+            Ok(()) => return true,
+            Err(_err) if attempt < MAX_ATTEMPTS => {
+                crate::warn!(
+                    "Failed to send data to InfluxDB (attempt {attempt} of {MAX_ATTEMPTS}), retrying in {backoff:?}: {_err}"
+                );
+                if cancellation_token.sleep_with_cancellation(backoff) {
+                    return false;
+                }
+                backoff *= 2;
+            }
+            Err(_err) => {
+                crate::error!(
+                    "Failed to send data to InfluxDB, dropping {} points: {_err}",
+                    lines.len()
+                );
+            }
+        }
+    }
+
+    true
+}
+
+/// Sends data to InfluxDB in the background.
+pub(crate) struct InfluxDbExporter {
+    sender: SyncSender<Vec<String>>,
+    host: String,
+    thread: JoinHandle<()>,
+}
+
+impl InfluxDbExporter {
+    /// Creates a new [`InfluxDbExporter`], starting its background thread.
+    pub(crate) fn new(
+        settings: InfluxDbSettings, cancellation_token: Arc<CancellationToken>,
+    ) -> anyhow::Result<Self> {
+        let endpoint = WriteEndpoint::new(&settings)?;
+        let tls_config = if endpoint.is_https {
+            Some(tls::system_client_config()?)
+        } else {
+            None
+        };
+        let (sender, receiver) = mpsc::sync_channel(MAX_QUEUED_UPDATES);
+        let host = sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string());
+
+        let thread = thread::spawn(move || {
+            run_export_thread(endpoint, tls_config, settings, receiver, cancellation_token)
+        });
+
+        Ok(Self {
+            sender,
+            host,
+            thread,
+        })
+    }
+
+    /// Waits for any queued updates to be sent. Retries stop once cancelled.
+    pub(crate) fn finish(self) {
+        drop(self.sender);
+        let _ = self.thread.join();
+    }
+
+    /// Queues a data update to be sent. This never blocks; if too many updates
+    /// are already waiting to be sent, the update is dropped.
+    pub(crate) fn export(&mut self, data: &Data) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let lines = to_line_protocol(data, &self.host, timestamp);
+
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(lines) {
+            crate::warn!("InfluxDB export is falling behind, dropping an update.");
+        }
+    }
+}
+
+fn run_export_thread(
+    endpoint: WriteEndpoint, tls_config: Option<Arc<ClientConfig>>, settings: InfluxDbSettings,
+    receiver: Receiver<Vec<String>>, cancellation_token: Arc<CancellationToken>,
+) {
+    let batch_size = settings.batch_size.max(1);
+    let token = settings.token.as_deref();
+
+    while let Ok(mut lines) = receiver.recv() {
+        // Catch up on anything that queued up while we were sending.
+        while let Ok(more) = receiver.try_recv() {
+            lines.extend(more);
+        }
+
+        for batch in lines.chunks(batch_size) {
+            if !send_with_retries(
+                &endpoint,
+                tls_config.as_ref(),
+                token,
+                batch,
+                &cancellation_token,
+            ) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::TcpListener;
+
+    use super::*;
+    use crate::data_collection::{cpu::CpuData, disks::DiskHarvest, memory::MemHarvest};
+
+    fn settings(url: &str) -> InfluxDbSettings {
+        InfluxDbSettings {
+            url: url.to_string(),
+            token: Some("secret".to_string()),
+            org: "my org".to_string(),
+            bucket: "metrics".to_string(),
+            batch_size: DEFAULT_INFLUXDB_BATCH_SIZE,
+        }
+    }
+
+    #[test]
+    fn endpoint_parsing() {
+        assert_eq!(
+            WriteEndpoint::new(&settings("http://localhost:8086")).unwrap(),
+            WriteEndpoint {
+                address: "localhost:8086".to_string(),
+                host: "localhost".to_string(),
+                is_https: false,
+                path: "/api/v2/write?org=my%20org&bucket=metrics&precision=ns".to_string(),
+            }
+        );
+        assert_eq!(
+            WriteEndpoint::new(&settings("http://influx/prefix/"))
+                .unwrap()
+                .address,
+            "influx:80"
+        );
+        assert!(WriteEndpoint::new(&settings("ftp://localhost:8086")).is_err());
+        assert!(WriteEndpoint::new(&settings("http://")).is_err());
+        assert!(WriteEndpoint::new(&settings("http://localhost:port")).is_err());
+
+        let https = WriteEndpoint::new(&settings("https://influx.example.com")).unwrap();
+        assert_eq!(
+            (https.address.as_str(), https.host.as_str(), https.is_https),
+            ("influx.example.com:443", "influx.example.com", true)
+        );
+
+        // IPv6 addresses keep their brackets in the address, but not the host.
+        let ipv6 = WriteEndpoint::new(&settings("http://[::1]")).unwrap();
+        assert_eq!(
+            (ipv6.address.as_str(), ipv6.host.as_str()),
+            ("[::1]:80", "::1")
+        );
+        assert_eq!(
+            WriteEndpoint::new(&settings("https://[fe80::1]:8086/influx"))
+                .unwrap()
+                .address,
+            "[fe80::1]:8086"
+        );
+        assert!(WriteEndpoint::new(&settings("http://[::1")).is_err());
+        assert!(WriteEndpoint::new(&settings("http://::1:8086")).is_err());
+    }
+
+    #[test]
+    fn line_protocol() {
+        let data = Data {
+            cpu: Some(vec![
+                CpuData {
+                    data_type: CpuDataType::Avg,
+                    cpu_usage: 12.5,
+                },
+                CpuData {
+                    data_type: CpuDataType::Cpu(0),
+                    cpu_usage: 50.0,
+                },
+            ]),
+            memory: Some(MemHarvest {
+                used_bytes: 100,
+                total_bytes: 200,
+                use_percent: Some(50.0),
+            }),
+            disks: Some(vec![DiskHarvest {
+                name: "/dev/sda 1".into(),
+                mount_point: "/".into(),
+                #[cfg(target_os = "windows")]
+                volume_name: None,
+                free_space: Some(10),
+                used_space: Some(90),
+                total_space: None,
+            }]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_line_protocol(&data, "my,host", 123),
+            vec![
+                "cpu,host=my\\,host,cpu=avg usage_percent=12.5 123",
+                "cpu,host=my\\,host,cpu=0 usage_percent=50 123",
+                "memory,host=my\\,host,kind=ram used_bytes=100i,total_bytes=200i 123",
+                "disk,host=my\\,host,disk=/dev/sda\\ 1,mount=/ used_bytes=90i,free_bytes=10i 123",
+            ]
+        );
+    }
+
+    #[test]
+    fn sends_batch() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !String::from_utf8_lossy(&request).contains("b 2") {
+                let read = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        let endpoint = WriteEndpoint::new(&settings(&url)).unwrap();
+        send_batch(
+            &endpoint,
+            None,
+            Some("secret"),
+            &["a 1".into(), "b 2".into()],
+        )
+        .unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with(
+            "POST /api/v2/write?org=my%20org&bucket=metrics&precision=ns HTTP/1.1\r\n"
+        ));
+        assert!(request.contains("Authorization: Token secret\r\n"));
+        assert!(request.ends_with("\r\n\r\na 1\nb 2"));
+    }
+
+    #[test]
+    fn sends_batch_over_tls() {
+        let fixture = |name: &str| {
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/tls")
+                .join(name)
+        };
+        let server_tls =
+            tls::server_config(&fixture("server.pem"), &fixture("server.key"), None).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut stream = tls::accept(stream, Some(&server_tls)).unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !String::from_utf8_lossy(&request).contains("a 1") {
+                let read = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .unwrap();
+            stream.flush().unwrap();
+            String::from_utf8(request).unwrap()
+        });
+
+        // The fixture's certificate is only valid for localhost.
+        let endpoint = WriteEndpoint {
+            address: address.to_string(),
+            host: "localhost".to_string(),
+            is_https: true,
+            path: "/api/v2/write".to_string(),
+        };
+        let client_tls = tls::client_config(Some(&fixture("ca.pem")), None).unwrap();
+        send_batch(
+            &endpoint,
+            Some(&client_tls),
+            Some("secret"),
+            &["a 1".into()],
+        )
+        .unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.contains("Authorization: Token secret\r\n"));
+    }
+}
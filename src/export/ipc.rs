@@ -0,0 +1,287 @@
+//! A UNIX socket that external tools can query the latest collected data from,
+//! without having to collect it themselves.
+//!
+//! Clients send one request per line and get back a single line of JSON for
+//! each. The supported requests are `GET cpu`, `GET mem`, `GET processes`, and
+//! `GET all`, which return the matching parts of the collected data. Errors are
+//! returned as `{"error": "..."}`.
+
+use std::{
+    fs::{self, Permissions},
+    io::{BufRead, BufReader, ErrorKind, Write},
+    os::unix::{
+        fs::{FileTypeExt, PermissionsExt},
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
+use serde_json::{json, Value};
+
+use crate::{data_collection::Data, utils::cancellation_token::CancellationToken};
+
+/// The permissions of the socket if none are set, which only allow the
+/// current user to connect.
+pub(crate) const DEFAULT_IPC_SOCKET_MODE: u32 = 0o600;
+
+/// The parts of the collected data that can be requested.
+pub(crate) const IPC_SECTIONS: [&str; 4] = ["cpu", "mem", "processes", "all"];
+
+/// Clients that don't send anything for this long are disconnected.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The latest collected data, shared with the threads answering clients.
+type SharedData = Arc<Mutex<Option<Data>>>;
+
+/// Removes the socket when dropped, so it doesn't linger after bottom exits.
+pub(crate) struct IpcSocketGuard {
+    path: PathBuf,
+}
+
+impl Drop for IpcSocketGuard {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Answers requests for the latest collected data over a UNIX socket.
+pub(crate) struct IpcServer {
+    data: SharedData,
+    thread: JoinHandle<()>,
+}
+
+impl IpcServer {
+    /// Starts a new [`IpcServer`] listening on a socket at the given path with
+    /// the given permissions. A socket left behind by a previous run is
+    /// replaced, but one still in use is not.
+    ///
+    /// The socket is removed once the returned guard is dropped.
+    pub(crate) fn new(
+        path: &Path, mode: u32, cancellation_token: Arc<CancellationToken>,
+    ) -> anyhow::Result<(Self, IpcSocketGuard)> {
+        remove_stale_socket(path)?;
+
+        let listener = UnixListener::bind(path)
+            .with_context(|| format!("Could not create the IPC socket at {}.", path.display()))?;
+        let guard = IpcSocketGuard { path: path.into() };
+
+        fs::set_permissions(path, Permissions::from_mode(mode)).with_context(|| {
+            format!(
+                "Could not set the permissions of the IPC socket at {}.",
+                path.display()
+            )
+        })?;
+        listener.set_nonblocking(true)?;
+
+        let data = SharedData::default();
+        let thread = {
+            let data = data.clone();
+            thread::spawn(move || accept_clients(listener, data, cancellation_token))
+        };
+
+        Ok((Self { data, thread }, guard))
+    }
+
+    /// Waits for the server to stop accepting clients once cancelled.
+    pub(crate) fn finish(self) {
+        let _ = self.thread.join();
+    }
+
+    /// Replaces the data that clients are sent with a new update.
+    pub(crate) fn export(&mut self, data: &Data) {
+        if let Ok(mut latest) = self.data.lock() {
+            *latest = Some(data.clone());
+        }
+    }
+}
+
+/// Removes a socket at the given path if nothing is listening on it anymore.
+fn remove_stale_socket(path: &Path) -> anyhow::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            if UnixStream::connect(path).is_ok() {
+                bail!(
+                    "The IPC socket at {} is already in use by another process.",
+                    path.display()
+                );
+            }
+            fs::remove_file(path).with_context(|| {
+                format!("Could not remove the old IPC socket at {}.", path.display())
+            })
+        }
+        Ok(_) => bail!(
+            "Could not create the IPC socket at {} as something else is already there.",
+            path.display()
+        ),
+        Err(_) => Ok(()),
+    }
+}
+
+fn accept_clients(
+    listener: UnixListener, data: SharedData, cancellation_token: Arc<CancellationToken>,
+) {
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let data = data.clone();
+                thread::spawn(move || {
+                    if let Err(_err) = answer_client(stream, &data) {
+                        crate::debug!("IPC client disconnected: {_err}");
+                    }
+                });
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                if cancellation_token.sleep_with_cancellation(Duration::from_millis(100)) {
+                    break;
+                }
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Answers each request from a client until it disconnects.
+fn answer_client(stream: UnixStream, data: &SharedData) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+
+    let mut writer = stream.try_clone()?;
+    for request in BufReader::new(stream).lines() {
+        let response = match data.lock() {
+            Ok(data) => respond(&request?, data.as_ref()),
+            Err(_) => break,
+        };
+        writeln!(writer, "{response}")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the JSON response to a single request.
+fn respond(request: &str, data: Option<&Data>) -> Value {
+    let section = match request.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [method, section] if method.eq_ignore_ascii_case("GET") => section.to_ascii_lowercase(),
+        _ => {
+            let err = format!(
+                "'{}' is not a valid request, expected e.g. 'GET cpu'.",
+                request.trim()
+            );
+            return json!({ "error": err });
+        }
+    };
+
+    let keys: &[&str] = match section.as_str() {
+        "cpu" => &["cpu", "cpu_times", "load_avg", "sched_tasks"],
+        "mem" => &["memory", "cache", "swap", "arc", "gpu"],
+        "processes" => &["list_of_processes"],
+        "all" => &[],
+        _ => {
+            let err = format!(
+                "'{section}' is not a valid section, expected one of: {}.",
+                IPC_SECTIONS.join(", ")
+            );
+            return json!({ "error": err });
+        }
+    };
+
+    let Some(data) = data else {
+        return json!({ "error": "No data has been collected yet." });
+    };
+
+    match serde_json::to_value(data) {
+        Ok(mut value) => {
+            if let (Value::Object(fields), false) = (&mut value, keys.is_empty()) {
+                fields.retain(|key, _| keys.contains(&key.as_str()));
+            }
+            value
+        }
+        Err(err) => json!({ "error": err.to_string() }),
+    }
+}
+
+/// Requests a section of the latest data from bottom running with an IPC
+/// socket at the given path, and prints it.
+pub(crate) fn query(path: &Path, section: &str) -> anyhow::Result<()> {
+    let mut stream = UnixStream::connect(path)
+        .with_context(|| format!("Could not connect to the IPC socket at {}.", path.display()))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    writeln!(stream, "GET {section}")?;
+
+    let mut response = String::new();
+    BufReader::new(&stream).read_line(&mut response)?;
+
+    match serde_json::from_str::<Value>(&response)?.get("error") {
+        Some(Value::String(err)) => bail!("{err}"),
+        _ => {
+            print!("{response}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn request(stream: &mut UnixStream, request: &str) -> Value {
+        writeln!(stream, "{request}").unwrap();
+
+        let mut response = String::new();
+        BufReader::new(&*stream).read_line(&mut response).unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[test]
+    fn sections() {
+        let data = Data::default();
+
+        let cpu = respond("GET cpu", Some(&data));
+        let mut keys = cpu.as_object().unwrap().keys().collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!(keys, ["cpu", "cpu_times", "load_avg", "sched_tasks"]);
+
+        let all = respond("get ALL", Some(&data));
+        assert!(all.get("list_of_processes").is_some());
+        assert!(all.get("network").is_some());
+
+        assert!(respond("GET cpu", None).get("error").is_some());
+        assert!(respond("GET disks", Some(&data)).get("error").is_some());
+        assert!(respond("PUT cpu", Some(&data)).get("error").is_some());
+        assert!(respond("", Some(&data)).get("error").is_some());
+    }
+
+    #[test]
+    fn socket_lifecycle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.sock");
+
+        // A socket left behind by a previous run is replaced.
+        drop(UnixListener::bind(&path).unwrap());
+
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let (mut server, guard) = IpcServer::new(&path, 0o600, cancellation_token.clone()).unwrap();
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+
+        // One still in use isn't.
+        assert!(IpcServer::new(&path, 0o600, cancellation_token.clone()).is_err());
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+        assert!(request(&mut stream, "GET mem").get("error").is_some());
+
+        server.export(&Data::default());
+        let response = request(&mut stream, "GET processes");
+        assert_eq!(response, json!({ "list_of_processes": null }));
+
+        cancellation_token.cancel();
+        drop(guard);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,341 @@
+//! Recording collected data to a file, and replaying it back later.
+//!
+//! Recordings are stored as newline-delimited JSON, where each line is a
+//! [`Frame`] containing a [`Data`] snapshot and when it was taken, both
+//! relative to the start of the recording and as a wall-clock time.
+
+use std::{
+    collections::VecDeque,
+    fs::{self, File},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data_collection::Data,
+    event::{BottomEvent, CollectionThreadEvent},
+    utils::cancellation_token::CancellationToken,
+};
+
+/// The default maximum size of a recording, in megabytes.
+pub const DEFAULT_RECORD_MAX_SIZE_MB: u64 = 50;
+
+/// A single recorded snapshot.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Frame {
+    /// Milliseconds since the start of the recording.
+    pub elapsed_ms: u64,
+    /// Milliseconds since the Unix epoch. Older recordings don't have this.
+    #[serde(default)]
+    pub time_ms: Option<u64>,
+    pub data: Data,
+}
+
+/// A borrowed [`Frame`], to avoid cloning data just to serialize it.
+#[derive(Serialize)]
+struct FrameRef<'a> {
+    elapsed_ms: u64,
+    time_ms: Option<u64>,
+    data: &'a Data,
+}
+
+/// Writes [`Data`] snapshots to a recording file.
+///
+/// The recording is bounded by a maximum size. Once it's exceeded, the oldest
+/// frames are dropped and the file is rewritten, acting as a ring buffer of
+/// snapshots. To avoid rewriting the file on every frame after that point, a
+/// tenth of the maximum size is freed each time.
+pub(crate) struct Recorder {
+    path: PathBuf,
+    file: File,
+    max_size: u64,
+    frames: VecDeque<String>,
+    size: u64,
+    start: Instant,
+}
+
+impl Recorder {
+    /// Creates a new [`Recorder`] writing to `path`, truncating any existing file.
+    pub(crate) fn new(path: &Path, max_size: u64) -> anyhow::Result<Self> {
+        let file = File::create(path).with_context(|| {
+            format!("Could not create the recording file '{}'.", path.display())
+        })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            max_size,
+            frames: VecDeque::new(),
+            size: 0,
+            start: Instant::now(),
+        })
+    }
+
+    /// Records a new snapshot.
+    pub(crate) fn record(&mut self, data: &Data) -> anyhow::Result<()> {
+        let mut line = serde_json::to_string(&FrameRef {
+            elapsed_ms: self.start.elapsed().as_millis() as u64,
+            time_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|time| time.as_millis() as u64),
+            data,
+        })?;
+        line.push('\n');
+
+        self.size += line.len() as u64;
+
+        if self.size > self.max_size {
+            self.frames.push_back(line);
+
+            let target_size = self.max_size / 10 * 9;
+            while self.size > target_size && self.frames.len() > 1 {
+                if let Some(frame) = self.frames.pop_front() {
+                    self.size -= frame.len() as u64;
+                }
+            }
+
+            // Write the kept frames to a temporary file and rename it over the
+            // recording, so it's never left half-written if bottom is killed here.
+            let temp_path = temp_path(&self.path);
+            let mut file = File::create(&temp_path).with_context(|| {
+                format!(
+                    "Could not create the recording file '{}'.",
+                    temp_path.display()
+                )
+            })?;
+            for frame in &self.frames {
+                file.write_all(frame.as_bytes())?;
+            }
+            fs::rename(&temp_path, &self.path)?;
+            self.file = file;
+        } else {
+            self.file.write_all(line.as_bytes())?;
+            self.frames.push_back(line);
+        }
+
+        Ok(())
+    }
+}
+
+/// The path a recording is rewritten to before it replaces the original.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Reads all frames from a recording file.
+pub(crate) fn read_recording(path: &Path) -> anyhow::Result<Vec<Frame>> {
+    let file = File::open(path)
+        .with_context(|| format!("Could not open the recording file '{}'.", path.display()))?;
+
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(index, line)| {
+            let line = line?;
+            serde_json::from_str(&line).with_context(|| {
+                format!(
+                    "Line {} of '{}' is not a valid recording frame.",
+                    index + 1,
+                    path.display()
+                )
+            })
+        })
+        .collect()
+}
+
+/// The state of a replay, used to draw the timeline.
+#[derive(Debug, Default, Clone)]
+pub struct ReplayState {
+    /// How far into the recording the replay currently is, in milliseconds.
+    pub position_ms: u64,
+    /// The length of the recording, in milliseconds.
+    pub length_ms: u64,
+    /// The playback speed multiplier.
+    pub speed: f64,
+    /// The wall-clock time the recording started at, if it was recorded.
+    pub start_time: Option<SystemTime>,
+}
+
+impl ReplayState {
+    /// Creates a new [`ReplayState`] for the given frames.
+    pub(crate) fn new(frames: &[Frame], speed: f64) -> Self {
+        let length_ms = match (frames.first(), frames.last()) {
+            (Some(first), Some(last)) => last.elapsed_ms.saturating_sub(first.elapsed_ms),
+            _ => 0,
+        };
+
+        Self {
+            position_ms: 0,
+            length_ms,
+            speed,
+            start_time: frames
+                .first()
+                .and_then(|frame| frame.time_ms)
+                .map(|time_ms| UNIX_EPOCH + Duration::from_millis(time_ms)),
+        }
+    }
+
+    /// The wall-clock time of the frame currently being replayed, if the
+    /// recording has times.
+    pub fn current_time(&self) -> Option<SystemTime> {
+        self.start_time
+            .map(|start| start + Duration::from_millis(self.position_ms))
+    }
+}
+
+/// Create a thread that plays back recorded frames in place of the collection
+/// thread. Pausing is handled by [`CollectionThreadEvent::Pause`] and
+/// [`CollectionThreadEvent::Resume`].
+pub(crate) fn create_replay_thread(
+    sender: Sender<BottomEvent>, control_receiver: Receiver<CollectionThreadEvent>,
+    cancellation_token: Arc<CancellationToken>, frames: Vec<Frame>, speed: f64,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let start_ms = frames.first().map(|frame| frame.elapsed_ms).unwrap_or(0);
+        let mut previous_ms = start_ms;
+        let mut paused = false;
+
+        for mut frame in frames {
+            let wait = Duration::from_millis(frame.elapsed_ms.saturating_sub(previous_ms));
+            if cancellation_token.sleep_with_cancellation(wait.div_f64(speed)) {
+                return;
+            }
+            previous_ms = frame.elapsed_ms;
+
+            loop {
+                while let Ok(message) = control_receiver.try_recv() {
+                    match message {
+                        CollectionThreadEvent::Reset
+                        | CollectionThreadEvent::Refresh
+                        | CollectionThreadEvent::SetTemperatureType(_)
+                        | CollectionThreadEvent::SetVisibleColumns(_) => {}
+                        CollectionThreadEvent::Pause => paused = true,
+                        CollectionThreadEvent::Resume => paused = false,
+                    }
+                }
+
+                if !paused {
+                    break;
+                }
+
+                if cancellation_token.sleep_with_cancellation(Duration::from_millis(100)) {
+                    return;
+                }
+            }
+
+            // Frames of a hand-edited or concatenated recording may go back in time.
+            let position = BottomEvent::ReplayPosition(frame.elapsed_ms.saturating_sub(start_ms));
+            if sender.send(position).is_err() {
+                return;
+            }
+
+            // The collection time isn't recorded, so treat the frame as if it was just
+            // collected.
+            frame.data.collection_time = Instant::now();
+            if sender
+                .send(BottomEvent::Update(Box::new(frame.data)))
+                .is_err()
+            {
+                return;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::memory::MemHarvest;
+
+    fn data(used_bytes: u64) -> Data {
+        Data {
+            memory: Some(MemHarvest {
+                used_bytes,
+                total_bytes: 1000,
+                use_percent: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn record_and_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.btm");
+
+        let mut recorder = Recorder::new(&path, 1024 * 1024).unwrap();
+        recorder.record(&data(1)).unwrap();
+        recorder.record(&data(2)).unwrap();
+
+        let frames = read_recording(&path).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].data.memory.as_ref().unwrap().used_bytes, 1);
+        assert_eq!(frames[1].data.memory.as_ref().unwrap().used_bytes, 2);
+        assert!(frames[0].elapsed_ms <= frames[1].elapsed_ms);
+
+        let mut replay = ReplayState::new(&frames, 1.0);
+        let start = replay.start_time.unwrap();
+        replay.position_ms = 1500;
+        assert_eq!(
+            replay.current_time(),
+            Some(start + Duration::from_millis(1500))
+        );
+    }
+
+    #[test]
+    fn old_recordings_have_no_time() {
+        let frame: Frame =
+            serde_json::from_value(serde_json::json!({ "elapsed_ms": 0, "data": data(1) }))
+                .unwrap();
+        assert_eq!(frame.time_ms, None);
+        assert_eq!(ReplayState::new(&[frame], 1.0).current_time(), None);
+    }
+
+    #[test]
+    fn recording_wraps() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.btm");
+
+        let frame_size = serde_json::to_string(&FrameRef {
+            elapsed_ms: 0,
+            time_ms: Some(1_700_000_000_000),
+            data: &data(0),
+        })
+        .unwrap()
+        .len() as u64;
+
+        let mut recorder = Recorder::new(&path, frame_size * 10).unwrap();
+        for i in 0..25 {
+            recorder.record(&data(i)).unwrap();
+        }
+
+        let frames = read_recording(&path).unwrap();
+        assert!(frames.len() < 25);
+        assert!(!temp_path(&path).exists());
+        assert!(std::fs::metadata(&path).unwrap().len() <= frame_size * 10 + 10);
+        assert_eq!(
+            frames
+                .last()
+                .unwrap()
+                .data
+                .memory
+                .as_ref()
+                .unwrap()
+                .used_bytes,
+            24
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! TLS for the WebSocket server and client, so remote hosts can be monitored
+//! without sending data in plaintext.
+//!
+//! Certificates and keys are read from PEM files. Without any TLS options,
+//! connections stay in plaintext as before.
+
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::Path,
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Context};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{self, CryptoProvider},
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    server::WebPkiClientVerifier,
+    AlertDescription, CertificateError, ClientConfig, ClientConnection, DigitallySignedStruct,
+    RootCertStore, ServerConfig, ServerConnection, SignatureScheme, StreamOwned,
+};
+
+/// A connection that may or may not be encrypted.
+pub(crate) trait Transport: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Transport for T {}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(crypto::ring::default_provider())
+}
+
+/// Creates the server's TLS config from its certificate and private key. If
+/// a CA is given, clients must present a certificate signed by it.
+pub(crate) fn server_config(
+    cert: &Path, key: &Path, client_ca: Option<&Path>,
+) -> anyhow::Result<Arc<ServerConfig>> {
+    let builder =
+        ServerConfig::builder_with_provider(provider()).with_safe_default_protocol_versions()?;
+    let builder = match client_ca {
+        Some(client_ca) => {
+            let verifier = WebPkiClientVerifier::builder_with_provider(
+                Arc::new(read_roots(client_ca)?),
+                provider(),
+            )
+            .build()?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let config = builder
+        .with_single_cert(read_certs(cert)?, read_key(key)?)
+        .with_context(|| {
+            format!(
+                "Could not use the certificate '{}' with the key '{}'.",
+                cert.display(),
+                key.display()
+            )
+        })?;
+
+    Ok(Arc::new(config))
+}
+
+/// Creates the client's TLS config. The server's certificate is checked
+/// against the given CA, or not at all if there isn't one. A certificate and
+/// key can be given for servers that require clients to have one.
+pub(crate) fn client_config(
+    ca: Option<&Path>, client_auth: Option<(&Path, &Path)>,
+) -> anyhow::Result<Arc<ClientConfig>> {
+    let builder =
+        ClientConfig::builder_with_provider(provider()).with_safe_default_protocol_versions()?;
+    let builder = match ca {
+        Some(ca) => builder.with_root_certificates(read_roots(ca)?),
+        None => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification(provider()))),
+    };
+
+    let config = match client_auth {
+        Some((cert, key)) => builder
+            .with_client_auth_cert(read_certs(cert)?, read_key(key)?)
+            .with_context(|| {
+                format!(
+                    "Could not use the certificate '{}' with the key '{}'.",
+                    cert.display(),
+                    key.display()
+                )
+            })?,
+        None => builder.with_no_client_auth(),
+    };
+
+    Ok(Arc::new(config))
+}
+
+/// Where the system's CA certificates are usually bundled, on Linux
+/// distributions, macOS and the BSDs.
+const SYSTEM_CA_BUNDLES: [&str; 5] = [
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/ssl/cert.pem",
+    "/usr/local/share/certs/ca-root-nss.crt",
+];
+
+/// Creates a client TLS config that checks servers against the system's CA
+/// certificates, from `SSL_CERT_FILE` if it's set, or the first bundle found
+/// in the usual places.
+pub(crate) fn system_client_config() -> anyhow::Result<Arc<ClientConfig>> {
+    let bundle = std::env::var_os("SSL_CERT_FILE")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            SYSTEM_CA_BUNDLES
+                .iter()
+                .map(Path::new)
+                .find(|path| path.is_file())
+                .map(Path::to_path_buf)
+        })
+        .context("No CA certificates were found, set SSL_CERT_FILE to a bundle of them.")?;
+
+    // Bundles can have certificates that rustls can't use, which are skipped.
+    let mut roots = RootCertStore::empty();
+    let (added, _) = roots.add_parsable_certificates(read_certs(&bundle)?);
+    if added == 0 {
+        anyhow::bail!(
+            "No usable CA certificates were found in '{}'.",
+            bundle.display()
+        );
+    }
+
+    let config = ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    Ok(Arc::new(config))
+}
+
+fn read_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("Could not read the certificates in '{}'.", path.display()))?;
+
+    if certs.is_empty() {
+        anyhow::bail!("No certificates were found in '{}'.", path.display());
+    }
+
+    Ok(certs)
+}
+
+fn read_key(path: &Path) -> anyhow::Result<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_file(path)
+        .with_context(|| format!("Could not read the private key in '{}'.", path.display()))
+}
+
+fn read_roots(path: &Path) -> anyhow::Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for cert in read_certs(path)? {
+        roots.add(cert).with_context(|| {
+            format!("Could not use the CA certificate in '{}'.", path.display())
+        })?;
+    }
+
+    Ok(roots)
+}
+
+/// Does the server side of the TLS handshake on a newly accepted connection,
+/// if TLS is enabled.
+pub(super) fn accept(
+    mut stream: TcpStream, config: Option<&Arc<ServerConfig>>,
+) -> anyhow::Result<Box<dyn Transport>> {
+    let Some(config) = config else {
+        return Ok(Box::new(stream));
+    };
+
+    let mut connection = ServerConnection::new(config.clone())?;
+    while connection.is_handshaking() {
+        connection
+            .complete_io(&mut stream)
+            .map_err(readable_error)?;
+    }
+
+    Ok(Box::new(StreamOwned::new(connection, stream)))
+}
+
+/// Does the client side of the TLS handshake with the server at the given
+/// host, if TLS is enabled.
+pub(crate) fn connect(
+    mut stream: TcpStream, host: &str, config: Option<&Arc<ClientConfig>>,
+) -> anyhow::Result<Box<dyn Transport>> {
+    let Some(config) = config else {
+        return Ok(Box::new(stream));
+    };
+
+    let name = ServerName::try_from(host.to_string())
+        .map_err(|_| anyhow!("'{host}' isn't a valid host name for TLS"))?;
+    let mut connection = ClientConnection::new(config.clone(), name)?;
+    while connection.is_handshaking() {
+        connection
+            .complete_io(&mut stream)
+            .map_err(readable_error)?;
+    }
+
+    Ok(Box::new(StreamOwned::new(connection, stream)))
+}
+
+/// Turns an error from a connection that might be using TLS into a readable
+/// message. Some TLS errors, like a rejected client certificate, only show up
+/// once data is read after the handshake.
+pub(crate) fn readable_error(err: io::Error) -> anyhow::Error {
+    match err
+        .get_ref()
+        .and_then(|err| err.downcast_ref::<rustls::Error>())
+    {
+        Some(err) => anyhow!("{}", describe(err)),
+        None if err.kind() == io::ErrorKind::UnexpectedEof => {
+            anyhow!("the connection was closed during the TLS handshake")
+        }
+        None => err.into(),
+    }
+}
+
+/// Describes a TLS error in words, rather than with rustls' own names.
+fn describe(err: &rustls::Error) -> String {
+    match err {
+        rustls::Error::InvalidCertificate(err) => match err {
+            CertificateError::Expired | CertificateError::ExpiredContext { .. } => {
+                "the certificate has expired".into()
+            }
+            CertificateError::NotValidYet | CertificateError::NotValidYetContext { .. } => {
+                "the certificate isn't valid yet".into()
+            }
+            CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. } => {
+                "the certificate isn't valid for this host name".into()
+            }
+            CertificateError::UnknownIssuer => {
+                "the certificate isn't signed by the given certificate authority".into()
+            }
+            CertificateError::BadSignature => "the certificate's signature is invalid".into(),
+            CertificateError::Revoked => "the certificate has been revoked".into(),
+            CertificateError::BadEncoding => "the certificate couldn't be parsed".into(),
+            err => format!("the certificate is invalid ({err:?})"),
+        },
+        rustls::Error::NoCertificatesPresented => "no client certificate was sent".into(),
+        rustls::Error::AlertReceived(alert) => match alert {
+            AlertDescription::CertificateExpired => {
+                "the other side rejected the certificate as expired".into()
+            }
+            AlertDescription::CertificateRequired => {
+                "the other side requires a client certificate".into()
+            }
+            AlertDescription::BadCertificate
+            | AlertDescription::UnsupportedCertificate
+            | AlertDescription::CertificateRevoked
+            | AlertDescription::CertificateUnknown
+            | AlertDescription::UnknownCA => "the other side rejected the certificate".into(),
+            alert => format!("the other side ended the TLS handshake ({alert:?})"),
+        },
+        rustls::Error::InvalidMessage(_) => "the other side doesn't seem to be using TLS".into(),
+        rustls::Error::PeerIncompatible(_) => {
+            "the other side doesn't support any of the same TLS versions or ciphers".into()
+        }
+        err => err.to_string(),
+    }
+}
+
+/// Accepts any server certificate, for `--hosts_skip_verify`. Signatures are
+/// still checked, so the connection is encrypted, but the server could be
+/// anyone.
+#[derive(Debug)]
+struct NoVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self, _end_entity: &CertificateDer<'_>, _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>, _ocsp_response: &[u8], _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self, message: &[u8], cert: &CertificateDer<'_>, dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn readable_errors() {
+        assert_eq!(
+            describe(&rustls::Error::InvalidCertificate(
+                CertificateError::Expired
+            )),
+            "the certificate has expired"
+        );
+        assert_eq!(
+            describe(&rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName
+            )),
+            "the certificate isn't valid for this host name"
+        );
+        assert_eq!(
+            describe(&rustls::Error::AlertReceived(
+                AlertDescription::CertificateRequired
+            )),
+            "the other side requires a client certificate"
+        );
+    }
+
+    #[test]
+    fn missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.pem");
+        let empty = dir.path().join("empty.pem");
+        std::fs::write(&empty, "").unwrap();
+
+        let err = server_config(&missing, &missing, None).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Could not read the certificates"));
+
+        let err = client_config(Some(&empty), None).unwrap_err();
+        assert!(err.to_string().starts_with("No certificates were found"));
+    }
+}
@@ -0,0 +1,465 @@
+//! A WebSocket client that receives data from another bottom instance's
+//! [`WsServer`](super::ws_server::WsServer), for monitoring remote hosts.
+//!
+//! Like the server, only what's needed is implemented: the opening handshake
+//! and reading frames. Nothing is ever sent to the server after connecting.
+//!
+//! If a refresh rate was given for the host, it's asked for in the handshake,
+//! so the server skips updates in between instead of sending all of them.
+//!
+//! Connection errors, including TLS ones, are noted in the event log. Only
+//! the first of a run of identical errors is noted, so a host that stays
+//! unreachable doesn't fill up the log while reconnecting.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{mpsc::Sender, Arc},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
+use rustls::ClientConfig;
+
+use super::{
+    tls::{self, Transport},
+    ws_server::accept_key,
+};
+use crate::{
+    app::{event_log::LogLevel, multi_host::host_name},
+    data_collection::Data,
+    event::BottomEvent,
+    utils::{
+        cancellation_token::CancellationToken,
+        general::{base64, random_u64},
+    },
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to go without any data before assuming the host is gone.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait before reconnecting to a host.
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// The largest message that will be accepted, to avoid running out of memory
+/// on a misbehaving server.
+const MAX_MESSAGE_LEN: u64 = 64 * 1024 * 1024;
+
+/// Starts a thread that connects to the host at the given address and sends
+/// its data to the main thread, reconnecting whenever the connection is lost.
+/// If a rate in milliseconds is given, the host is asked to send updates that
+/// often. If a TLS config is given, the connection uses TLS.
+pub(crate) fn create_remote_thread(
+    index: usize, address: String, rate: Option<u64>, token: Option<String>,
+    tls: Option<Arc<ClientConfig>>, sender: Sender<BottomEvent>,
+    cancellation_token: Arc<CancellationToken>,
+) -> JoinHandle<()> {
+    let mut last_error = None;
+    thread::spawn(move || loop {
+        let result =
+            connect(&address, rate, token.as_deref(), tls.as_ref()).and_then(|mut reader| loop {
+                let Some(message) = read_message(&mut reader)? else {
+                    bail!("the connection was closed");
+                };
+
+                let data: Data = serde_json::from_slice(&message)?;
+                last_error = None;
+                if sender
+                    .send(BottomEvent::RemoteUpdate(index, Box::new(data)))
+                    .is_err()
+                {
+                    return Ok(());
+                }
+            });
+
+        match result {
+            // The main thread is gone, so there's nothing left to do.
+            Ok(()) => break,
+            Err(err) => {
+                let err = format!("{err:#}");
+                crate::debug!("Lost connection to remote host {address}: {err}");
+
+                let is_new_error = last_error.as_ref() != Some(&err);
+                if is_new_error {
+                    let _ = sender.send(BottomEvent::Log(
+                        LogLevel::Warn,
+                        format!("Could not connect to {address}: {err}"),
+                    ));
+                    last_error = Some(err);
+                }
+                if sender.send(BottomEvent::RemoteDisconnected(index)).is_err() {
+                    break;
+                }
+            }
+        }
+
+        if cancellation_token.sleep_with_cancellation(RECONNECT_DELAY) {
+            break;
+        }
+    })
+}
+
+/// Connects to a WebSocket server and does the opening handshake, asking for
+/// updates at the given rate if there is one.
+fn connect(
+    address: &str, rate: Option<u64>, token: Option<&str>, tls: Option<&Arc<ClientConfig>>,
+) -> anyhow::Result<BufReader<Box<dyn Transport>>> {
+    let socket_address = address
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| format!("Could not resolve {address}."))?;
+    let stream = TcpStream::connect_timeout(&socket_address, CONNECT_TIMEOUT)?;
+    // Give slow rates a chance to send something before giving up.
+    let read_timeout = match rate {
+        Some(rate) => READ_TIMEOUT.max(Duration::from_millis(rate) * 2),
+        None => READ_TIMEOUT,
+    };
+    stream.set_read_timeout(Some(read_timeout))?;
+    let mut stream = tls::connect(stream, host_name(address), tls)?;
+
+    // The key doesn't need to be cryptographically secure.
+    let key = {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&random_u64().to_be_bytes());
+        bytes[8..].copy_from_slice(&random_u64().to_be_bytes());
+        base64(&bytes)
+    };
+    let target = match rate {
+        Some(rate) => format!("/?rate={rate}"),
+        None => "/".to_string(),
+    };
+    let authorization = match token {
+        Some(token) => format!("Authorization: Bearer {token}\r\n"),
+        None => String::new(),
+    };
+    let request = format!(
+        "GET {target} HTTP/1.1\r\nHost: {address}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {key}\r\nSec-WebSocket-Version: 13\r\n{authorization}\r\n"
+    );
+    if let Err(err) = stream
+        .write_all(request.as_bytes())
+        .and_then(|()| stream.flush())
+    {
+        // A server that rejects our certificate only does so after the
+        // handshake, and may have closed the connection before the request is
+        // sent. The reason is in the alert it sent first.
+        return Err(match stream.read(&mut [0; 1]) {
+            Err(read_err) if read_err.get_ref().is_some() => tls::readable_error(read_err),
+            _ => tls::readable_error(err),
+        });
+    }
+
+    let mut reader = BufReader::new(stream);
+    let mut status_line = String::new();
+    reader
+        .read_line(&mut status_line)
+        .map_err(tls::readable_error)?;
+
+    let mut accept = None;
+    let mut _negotiated_rate = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("sec-websocket-accept") {
+                accept = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("x-bottom-rate") {
+                _negotiated_rate = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    match status_line.split_whitespace().nth(1) {
+        Some("101") => {}
+        // A server using TLS closes plaintext connections straight away.
+        None if tls.is_none() => bail!("the connection was closed; the host may be using TLS"),
+        Some("401") => bail!("the token was rejected"),
+        _ => bail!("unexpected response: {}", status_line.trim()),
+    }
+    if accept.as_deref() != Some(accept_key(&key).as_str()) {
+        bail!("invalid handshake response");
+    }
+    if let Some(_rate) = rate {
+        // Older servers don't know about rates, and send every update.
+        crate::debug!(
+            "Asked {address} for updates every {_rate}ms, got {}.",
+            _negotiated_rate.as_deref().unwrap_or("every update")
+        );
+    }
+
+    Ok(reader)
+}
+
+/// Reads the next complete message. Returns [`None`] if the server closed the
+/// connection.
+fn read_message(reader: &mut impl Read) -> anyhow::Result<Option<Vec<u8>>> {
+    let mut message = Vec::new();
+
+    loop {
+        let mut header = [0; 2];
+        reader.read_exact(&mut header)?;
+
+        let is_final = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0F;
+        let is_masked = header[1] & 0x80 != 0;
+        let len = match header[1] & 0x7F {
+            126 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u64::from(u16::from_be_bytes(len))
+            }
+            127 => {
+                let mut len = [0; 8];
+                reader.read_exact(&mut len)?;
+                u64::from_be_bytes(len)
+            }
+            len => u64::from(len),
+        };
+
+        if message.len() as u64 + len > MAX_MESSAGE_LEN {
+            bail!("message is too large");
+        }
+
+        let mut mask = [0; 4];
+        if is_masked {
+            reader.read_exact(&mut mask)?;
+        }
+
+        let mut payload = vec![0; len as usize];
+        reader.read_exact(&mut payload)?;
+        if is_masked {
+            for (i, byte) in payload.iter_mut().enumerate() {
+                *byte ^= mask[i % 4];
+            }
+        }
+
+        match opcode {
+            0x8 => return Ok(None),
+            // Pings and pongs; the server never sends pings, so these are ignored.
+            0x9 | 0xA => {}
+            _ => {
+                message.extend_from_slice(&payload);
+                if is_final {
+                    return Ok(Some(message));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        net::{Ipv4Addr, SocketAddr, TcpListener, UdpSocket},
+        sync::mpsc,
+    };
+
+    use super::*;
+    use crate::export::ws_server::WsServer;
+
+    #[test]
+    fn fragmented_and_masked_messages() {
+        let mut frames = Vec::new();
+        // A ping in between, which is skipped.
+        frames.extend_from_slice(&[0x89, 0x00]);
+        // An unmasked, non-final text frame.
+        frames.extend_from_slice(&[0x01, 0x03]);
+        frames.extend_from_slice(b"abc");
+        // A masked, final continuation frame.
+        let mask = [1, 2, 3, 4];
+        frames.extend_from_slice(&[0x80, 0x82]);
+        frames.extend_from_slice(&mask);
+        frames.extend(b"de".iter().zip(mask).map(|(byte, mask)| byte ^ mask));
+        // A close frame.
+        frames.extend_from_slice(&[0x88, 0x00]);
+
+        let mut reader = frames.as_slice();
+        assert_eq!(read_message(&mut reader).unwrap(), Some(b"abcde".to_vec()));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+        assert!(read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn oversized_messages() {
+        let mut frames = vec![0x81, 127];
+        frames.extend_from_slice(&(MAX_MESSAGE_LEN + 1).to_be_bytes());
+
+        assert!(read_message(&mut frames.as_slice()).is_err());
+    }
+
+    #[test]
+    fn receives_from_server() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let mut server = WsServer::new(
+            (Ipv4Addr::LOCALHOST, port).into(),
+            Some("secret".into()),
+            1000,
+            None,
+            cancellation_token.clone(),
+        )
+        .unwrap();
+
+        assert!(connect(&format!("127.0.0.1:{port}"), None, Some("wrong"), None).is_err());
+
+        let (sender, receiver) = mpsc::channel();
+        let _thread = create_remote_thread(
+            3,
+            format!("127.0.0.1:{port}"),
+            Some(2000),
+            Some("secret".into()),
+            None,
+            sender,
+            cancellation_token.clone(),
+        );
+
+        // Keep sending updates until the client has connected and received one.
+        let received = (0..50).find_map(|_| {
+            server.export(&Data::default());
+            receiver.recv_timeout(Duration::from_millis(100)).ok()
+        });
+        assert!(matches!(received, Some(BottomEvent::RemoteUpdate(3, _))));
+
+        cancellation_token.cancel();
+    }
+
+    #[test]
+    fn receives_over_tls() {
+        // These were made with openssl, and are valid until 2126.
+        let fixture = |name: &str| {
+            std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/tls")
+                .join(name)
+        };
+
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let address = format!("localhost:{port}");
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let server_tls = tls::server_config(
+            &fixture("server.pem"),
+            &fixture("server.key"),
+            Some(&fixture("ca.pem")),
+        )
+        .unwrap();
+        let mut server = WsServer::new(
+            (Ipv4Addr::LOCALHOST, port).into(),
+            None,
+            1000,
+            Some(server_tls),
+            cancellation_token.clone(),
+        )
+        .unwrap();
+
+        let err = connect(&address, None, None, None).err().unwrap();
+        assert!(err.to_string().contains("may be using TLS"));
+
+        let without_cert = tls::client_config(Some(&fixture("ca.pem")), None).unwrap();
+        let err = connect(&address, None, None, Some(&without_cert))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "the other side requires a client certificate"
+        );
+
+        let client_tls = tls::client_config(
+            Some(&fixture("ca.pem")),
+            Some((&fixture("client.pem"), &fixture("client.key"))),
+        )
+        .unwrap();
+        let (sender, receiver) = mpsc::channel();
+        let _thread = create_remote_thread(
+            0,
+            address,
+            None,
+            None,
+            Some(client_tls),
+            sender,
+            cancellation_token.clone(),
+        );
+
+        let received = (0..50).find_map(|_| {
+            server.export(&Data::default());
+            receiver.recv_timeout(Duration::from_millis(100)).ok()
+        });
+        assert!(matches!(received, Some(BottomEvent::RemoteUpdate(0, _))));
+
+        cancellation_token.cancel();
+    }
+
+    #[test]
+    fn tls_on_other_interfaces() {
+        // Connecting a UDP socket doesn't send anything, but picks the address
+        // of the interface it would be sent from.
+        let interface = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+            .and_then(|socket| {
+                socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9))?;
+                socket.local_addr()
+            })
+            .map(|address| address.ip())
+            .ok()
+            .filter(|ip| !ip.is_loopback() && !ip.is_unspecified());
+        let Some(interface) = interface else {
+            // There's no network to test with.
+            return;
+        };
+
+        let fixture = |name: &str| {
+            std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/tls")
+                .join(name)
+        };
+        let server_tls =
+            || tls::server_config(&fixture("server.pem"), &fixture("server.key"), None);
+        // The fixture's certificate is only valid for localhost.
+        let client_tls = tls::client_config(None, None).unwrap();
+
+        let free_port = || {
+            TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0))
+                .unwrap()
+                .local_addr()
+                .unwrap()
+                .port()
+        };
+        let cancellation_token = Arc::new(CancellationToken::default());
+
+        // By default, the server can't be reached from other interfaces.
+        let port = free_port();
+        let _local = WsServer::new(
+            (Ipv4Addr::LOCALHOST, port).into(),
+            None,
+            1000,
+            Some(server_tls().unwrap()),
+            cancellation_token.clone(),
+        )
+        .unwrap();
+        let address = SocketAddr::new(interface, port).to_string();
+        assert!(connect(&address, None, None, Some(&client_tls)).is_err());
+
+        let port = free_port();
+        let _all = WsServer::new(
+            (Ipv4Addr::UNSPECIFIED, port).into(),
+            None,
+            1000,
+            Some(server_tls().unwrap()),
+            cancellation_token.clone(),
+        )
+        .unwrap();
+        let address = SocketAddr::new(interface, port).to_string();
+        assert!(connect(&address, None, None, Some(&client_tls)).is_ok());
+
+        cancellation_token.cancel();
+    }
+}
@@ -0,0 +1,723 @@
+//! A WebSocket server that streams collected data to clients as JSON, e.g. for
+//! browser-based dashboards.
+//!
+//! Only what's needed for this is implemented: the opening handshake and
+//! unmasked text frames from the server. Anything sent by clients is ignored.
+//!
+//! Clients can ask for updates less often than they're collected by adding
+//! `rate=<ms>` to the query in the request. The rate that will be used, which
+//! is rounded to a multiple of the server's own, is sent back in the
+//! `X-Bottom-Rate` header of the response.
+//!
+//! Connections can be encrypted with TLS by giving the server a certificate;
+//! see [`tls`](super::tls).
+//!
+//! Each client has its own thread and queue of updates, so one that's slow to
+//! read only falls behind itself. How many clients can be connected, or be in
+//! the middle of the handshake, is limited, as is the size of the request.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use rustls::ServerConfig;
+
+use super::tls::{self, Transport};
+use crate::{
+    data_collection::Data,
+    utils::{cancellation_token::CancellationToken, general::base64},
+};
+
+/// The GUID used to compute `Sec-WebSocket-Accept`, from RFC 6455.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// How many updates can be waiting to be broadcast, or to be sent to a
+/// single client, before new ones are dropped.
+const MAX_QUEUED_UPDATES: usize = 4;
+
+/// How many clients can be connected at once.
+const MAX_CLIENTS: usize = 64;
+
+/// How many connections can be doing the handshake at once. Others are turned
+/// away until one finishes.
+const MAX_PENDING_HANDSHAKES: usize = 16;
+
+/// The longest line allowed in a handshake request, in bytes.
+const MAX_HEADER_LINE_LENGTH: u64 = 8192;
+
+/// The most lines allowed in a handshake request, including the request line.
+const MAX_HEADER_LINES: usize = 64;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A connected client, whose updates are written by its own thread.
+struct Client {
+    sender: SyncSender<Arc<[u8]>>,
+    /// Only used for logging.
+    #[cfg_attr(not(feature = "logging"), allow(dead_code))]
+    address: SocketAddr,
+    /// How often to send updates to the client, if it asked for a rate.
+    interval: Option<Duration>,
+    last_sent: Option<Instant>,
+}
+
+impl Client {
+    /// Whether the client is due another update. Updates don't arrive at
+    /// exactly the server's update rate, so up to half of it is allowed as
+    /// slack.
+    fn is_due(&self, update_rate: Duration) -> bool {
+        match (self.interval, self.last_sent) {
+            (Some(interval), Some(last_sent)) => last_sent.elapsed() + update_rate / 2 >= interval,
+            _ => true,
+        }
+    }
+}
+
+type Clients = Arc<Mutex<Vec<Client>>>;
+
+/// Broadcasts data updates to all connected WebSocket clients.
+pub(crate) struct WsServer {
+    sender: SyncSender<String>,
+    threads: [JoinHandle<()>; 2],
+}
+
+impl WsServer {
+    /// Starts a new [`WsServer`] listening at the given address. If a token is
+    /// given, clients must provide it to connect. The update rate is how often,
+    /// in milliseconds, data is collected and exported. If a TLS config is
+    /// given, all connections must use TLS.
+    pub(crate) fn new(
+        address: SocketAddr, token: Option<String>, update_rate: u64,
+        tls: Option<Arc<ServerConfig>>, cancellation_token: Arc<CancellationToken>,
+    ) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(address)
+            .with_context(|| format!("Could not start the WebSocket server on {address}."))?;
+        listener.set_nonblocking(true)?;
+
+        let clients = Clients::default();
+        let (sender, receiver) = mpsc::sync_channel(MAX_QUEUED_UPDATES);
+
+        let accept_thread = {
+            let clients = clients.clone();
+            thread::spawn(move || {
+                accept_clients(
+                    listener,
+                    token,
+                    update_rate,
+                    tls,
+                    clients,
+                    cancellation_token,
+                )
+            })
+        };
+        let update_rate = Duration::from_millis(update_rate);
+        let broadcast_thread = thread::spawn(move || broadcast(receiver, clients, update_rate));
+
+        Ok(Self {
+            sender,
+            threads: [accept_thread, broadcast_thread],
+        })
+    }
+
+    /// Waits for the server to stop once cancelled, so the port is free again.
+    pub(crate) fn finish(self) {
+        drop(self.sender);
+        for thread in self.threads {
+            let _ = thread.join();
+        }
+    }
+
+    /// Queues a data update to be sent to all clients. This never blocks.
+    pub(crate) fn export(&mut self, data: &Data) {
+        let Ok(json) = serde_json::to_string(data) else {
+            return;
+        };
+
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(json) {
+            crate::debug!("WebSocket clients are falling behind, dropping an update.");
+        }
+    }
+}
+
+/// Counts a connection as doing the handshake until dropped.
+struct PendingHandshake(Arc<AtomicUsize>);
+
+impl PendingHandshake {
+    /// Counts a new connection, unless too many are already doing the
+    /// handshake.
+    fn start(pending: &Arc<AtomicUsize>) -> Option<Self> {
+        pending
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_PENDING_HANDSHAKES).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(pending.clone()))
+    }
+}
+
+impl Drop for PendingHandshake {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn accept_clients(
+    listener: TcpListener, token: Option<String>, update_rate: u64, tls: Option<Arc<ServerConfig>>,
+    clients: Clients, cancellation_token: Arc<CancellationToken>,
+) {
+    let token = token.map(Arc::<str>::from);
+    let pending = Arc::new(AtomicUsize::new(0));
+
+    loop {
+        match listener.accept() {
+            Ok((mut stream, address)) => {
+                let is_full = clients
+                    .lock()
+                    .map_or(true, |clients| clients.len() >= MAX_CLIENTS);
+                let pending_handshake = PendingHandshake::start(&pending).filter(|_| !is_full);
+                let Some(pending_handshake) = pending_handshake else {
+                    crate::debug!("WebSocket client {address} turned away, too many connections.");
+                    let _ = stream.write_all(
+                        b"HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\n\r\n",
+                    );
+                    continue;
+                };
+
+                // Do the handshake separately so a slow client can't hold up others.
+                let clients = clients.clone();
+                let token = token.clone();
+                let tls = tls.clone();
+                thread::spawn(move || {
+                    let connected =
+                        connect_client(stream, tls.as_ref(), token.as_deref(), update_rate);
+                    drop(pending_handshake);
+
+                    match connected {
+                        Ok((stream, interval)) => {
+                            crate::debug!("WebSocket client {address} connected.");
+                            let (sender, receiver) = mpsc::sync_channel(MAX_QUEUED_UPDATES);
+                            if let Ok(mut clients) = clients.lock() {
+                                clients.push(Client {
+                                    sender,
+                                    address,
+                                    interval,
+                                    last_sent: None,
+                                });
+                            }
+                            drop(clients);
+
+                            send_updates(stream, receiver);
+                            crate::debug!("WebSocket client {address} disconnected.");
+                        }
+                        Err(_err) => {
+                            crate::debug!("WebSocket client {address} failed to connect: {_err}");
+                        }
+                    }
+                });
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                if cancellation_token.sleep_with_cancellation(Duration::from_millis(100)) {
+                    break;
+                }
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Writes each queued frame to a client, until it disconnects or the server
+/// stops.
+fn send_updates(mut stream: Box<dyn Transport>, receiver: Receiver<Arc<[u8]>>) {
+    while let Ok(frame) = receiver.recv() {
+        if stream
+            .write_all(&frame)
+            .and_then(|_| stream.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+fn broadcast(receiver: Receiver<String>, clients: Clients, update_rate: Duration) {
+    while let Ok(json) = receiver.recv() {
+        let frame = Arc::from(text_frame(&json));
+
+        let Ok(mut clients) = clients.lock() else {
+            break;
+        };
+        queue_frame(&mut clients, &frame, update_rate);
+    }
+
+    // Stops the clients' threads once they've sent what's queued.
+    if let Ok(mut clients) = clients.lock() {
+        clients.clear();
+    }
+}
+
+/// Queues a frame for each client that's due an update, without waiting on
+/// any of them. A client that's still busy with earlier updates misses this
+/// one, and one that's disconnected is removed.
+fn queue_frame(clients: &mut Vec<Client>, frame: &Arc<[u8]>, update_rate: Duration) {
+    clients.retain_mut(|client| {
+        if !client.is_due(update_rate) {
+            return true;
+        }
+
+        client.last_sent = Some(Instant::now());
+        match client.sender.try_send(frame.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                crate::debug!(
+                    "WebSocket client {} is falling behind, dropping an update.",
+                    client.address
+                );
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    });
+}
+
+/// Sets up a newly accepted connection, doing the TLS handshake if needed and
+/// then the WebSocket one.
+fn connect_client(
+    stream: TcpStream, tls: Option<&Arc<ServerConfig>>, token: Option<&str>, update_rate: u64,
+) -> anyhow::Result<(Box<dyn Transport>, Option<Duration>)> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut stream = tls::accept(stream, tls)?;
+    let interval = handshake(&mut stream, token, update_rate)?;
+
+    Ok((stream, interval))
+}
+
+/// Handles the client's opening handshake, checking the token if one is set.
+/// Returns how often to send updates to the client, if it asked for a rate.
+fn handshake(
+    stream: &mut dyn Transport, token: Option<&str>, update_rate: u64,
+) -> anyhow::Result<Option<Duration>> {
+    let mut reader = BufReader::new(&mut *stream);
+    let mut request_line = String::new();
+    read_header_line(&mut reader, &mut request_line)?;
+
+    let mut key = None;
+    let mut authorization = None;
+    for lines in 1.. {
+        if lines >= MAX_HEADER_LINES {
+            anyhow::bail!("the request has too many headers");
+        }
+
+        let mut line = String::new();
+        if read_header_line(&mut reader, &mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim().to_string();
+            if name.eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value);
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value);
+            }
+        }
+    }
+
+    drop(reader);
+    let Some(key) = key else {
+        stream.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")?;
+        stream.flush()?;
+        anyhow::bail!("not a WebSocket request");
+    };
+
+    let query_param = |name: &str| {
+        request_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|target| target.split_once('?'))
+            .and_then(|(_, query)| {
+                query
+                    .split('&')
+                    .find_map(|param| param.strip_prefix(name)?.strip_prefix('='))
+            })
+    };
+
+    if let Some(token) = token {
+        // Browsers can't set headers on WebSocket requests, so also accept the
+        // token as a query parameter.
+        let query_token = query_param("token");
+        let header_token = authorization
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        let matches = |given: Option<&str>| given.is_some_and(|given| tokens_match(given, token));
+        if !matches(header_token) && !matches(query_token) {
+            stream.write_all(b"HTTP/1.1 401 Unauthorized\r\nConnection: close\r\n\r\n")?;
+            stream.flush()?;
+            anyhow::bail!("invalid token");
+        }
+    }
+
+    let rate = query_param("rate")
+        .and_then(|rate| rate.parse().ok())
+        .map(|rate| negotiate_rate(rate, update_rate));
+    let rate_header = match rate {
+        Some(rate) => format!("X-Bottom-Rate: {rate}\r\n"),
+        None => String::new(),
+    };
+
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n{rate_header}\r\n",
+        accept_key(&key)
+    );
+    stream.write_all(response.as_bytes())?;
+    stream.flush()?;
+
+    Ok(rate.map(Duration::from_millis))
+}
+
+/// Reads a line of the handshake request into `line`, returning how many
+/// bytes were read. Lines can't be longer than [`MAX_HEADER_LINE_LENGTH`], so
+/// a client that never ends one can't use up memory.
+fn read_header_line(reader: &mut impl BufRead, line: &mut String) -> anyhow::Result<usize> {
+    let read = reader.take(MAX_HEADER_LINE_LENGTH).read_line(line)?;
+    if read as u64 == MAX_HEADER_LINE_LENGTH && !line.ends_with('\n') {
+        anyhow::bail!("a line of the request is too long");
+    }
+
+    Ok(read)
+}
+
+/// Compares a token given by a client against the expected one in constant
+/// time, so how long a comparison takes doesn't reveal how much of a guess was
+/// right. Only the length can be told apart.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Picks the rate to send updates to a client at, given the one it asked for.
+/// Updates can only be skipped, so this is the nearest multiple of the
+/// server's own rate, and never faster than it.
+fn negotiate_rate(requested: u64, update_rate: u64) -> u64 {
+    if update_rate == 0 {
+        return requested;
+    }
+
+    let multiple = (requested + update_rate / 2) / update_rate;
+    multiple.max(1) * update_rate
+}
+
+/// Computes the `Sec-WebSocket-Accept` value for a `Sec-WebSocket-Key`.
+pub(super) fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{WEBSOCKET_GUID}").as_bytes()))
+}
+
+/// Builds an unmasked text frame containing the whole message.
+fn text_frame(text: &str) -> Vec<u8> {
+    let payload = text.as_bytes();
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+
+    // FIN bit and the text opcode.
+    frame.push(0x81);
+    match payload.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+
+    frame
+}
+
+/// A SHA-1 digest, which is only used for the handshake.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks_exact(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (h, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0; 20];
+    for (bytes, value) in digest.chunks_exact_mut(4).zip(h) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Read, net::Ipv4Addr};
+
+    use super::*;
+
+    #[test]
+    fn handshake_accept_key() {
+        // The example from RFC 6455.
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn frame_lengths() {
+        assert_eq!(text_frame("hi"), vec![0x81, 2, b'h', b'i']);
+
+        let medium = "a".repeat(200);
+        assert_eq!(&text_frame(&medium)[..4], &[0x81, 126, 0, 200]);
+
+        let large = "a".repeat(70_000);
+        assert_eq!(
+            &text_frame(&large)[..10],
+            &[0x81, 127, 0, 0, 0, 0, 0, 1, 0x11, 0x70]
+        );
+    }
+
+    #[test]
+    fn rate_negotiation() {
+        assert_eq!(negotiate_rate(2000, 1000), 2000);
+        assert_eq!(negotiate_rate(2400, 1000), 2000);
+        assert_eq!(negotiate_rate(2500, 1000), 3000);
+        assert_eq!(negotiate_rate(500, 1000), 1000);
+        assert_eq!(negotiate_rate(0, 1000), 1000);
+    }
+
+    #[test]
+    fn token_comparison() {
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secreT", "secret"));
+        assert!(!tokens_match("secre", "secret"));
+        assert!(!tokens_match("secrets", "secret"));
+        assert!(!tokens_match("", "secret"));
+    }
+
+    #[test]
+    fn request_limits() {
+        let handshake_with = |request: String| {
+            let mut stream = std::io::Cursor::new(request.into_bytes());
+            handshake(&mut stream, None, 1000)
+        };
+        let request = |headers: &str| {
+            format!(
+                "GET / HTTP/1.1\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n{headers}\r\n"
+            )
+        };
+
+        assert!(handshake_with(request("")).is_ok());
+
+        // A line that never ends is cut off instead of being read forever.
+        let long = format!(
+            "X-Long: {}\r\n",
+            "a".repeat(MAX_HEADER_LINE_LENGTH as usize)
+        );
+        assert!(handshake_with(request(&long)).is_err());
+        assert!(handshake_with("a".repeat(MAX_HEADER_LINE_LENGTH as usize * 2)).is_err());
+
+        let many = "X-Header: a\r\n".repeat(MAX_HEADER_LINES);
+        assert!(handshake_with(request(&many)).is_err());
+        let enough = "X-Header: a\r\n".repeat(MAX_HEADER_LINES - 3);
+        assert!(handshake_with(request(&enough)).is_ok());
+    }
+
+    #[test]
+    fn slow_clients() {
+        let client = |capacity| {
+            let (sender, receiver) = mpsc::sync_channel(capacity);
+            let client = Client {
+                sender,
+                address: (Ipv4Addr::LOCALHOST, 0).into(),
+                interval: None,
+                last_sent: None,
+            };
+            (client, receiver)
+        };
+        let (stalled, _stalled_receiver) = client(1);
+        let (reading, reading_receiver) = client(1);
+        let (gone, gone_receiver) = client(1);
+        drop(gone_receiver);
+
+        let mut clients = vec![stalled, reading, gone];
+        let frame: Arc<[u8]> = Arc::from(text_frame("a"));
+        queue_frame(&mut clients, &frame, Duration::ZERO);
+        assert_eq!(clients.len(), 2);
+        assert_eq!(reading_receiver.try_recv().unwrap(), frame);
+
+        // A client that isn't reading misses updates, but doesn't hold up the
+        // others.
+        queue_frame(&mut clients, &frame, Duration::ZERO);
+        assert_eq!(clients.len(), 2);
+        assert_eq!(reading_receiver.try_recv().unwrap(), frame);
+    }
+
+    #[test]
+    fn pending_handshakes() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let _server = WsServer::new(
+            (Ipv4Addr::LOCALHOST, port).into(),
+            None,
+            1000,
+            None,
+            cancellation_token.clone(),
+        )
+        .unwrap();
+
+        // Connections that never finish the handshake take up every slot.
+        let _idle = (0..MAX_PENDING_HANDSHAKES)
+            .map(|_| TcpStream::connect(("127.0.0.1", port)).unwrap())
+            .collect::<Vec<_>>();
+        thread::sleep(Duration::from_millis(300));
+
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503"));
+
+        cancellation_token.cancel();
+    }
+
+    /// Connects to the server and returns the response to the handshake.
+    fn connect(port: u16, target: &str, extra_headers: &str) -> (TcpStream, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+        write!(
+            stream,
+            "GET {target} HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n{extra_headers}\r\n"
+        )
+        .unwrap();
+
+        let mut response = Vec::new();
+        let mut byte = [0];
+        while !response.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).unwrap();
+            response.push(byte[0]);
+        }
+        (stream, String::from_utf8(response).unwrap())
+    }
+
+    #[test]
+    fn token_required() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let mut server = WsServer::new(
+            (Ipv4Addr::LOCALHOST, port).into(),
+            Some("secret".into()),
+            1000,
+            None,
+            cancellation_token.clone(),
+        )
+        .unwrap();
+
+        let (_, response) = connect(port, "/", "");
+        assert!(response.starts_with("HTTP/1.1 401"));
+
+        let (mut stream, response) = connect(port, "/", "Authorization: Bearer secret\r\n");
+        assert!(response.starts_with("HTTP/1.1 101"));
+        assert!(response.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+
+        // Wait for the client to be registered before sending anything.
+        thread::sleep(Duration::from_millis(200));
+        server.export(&Data::default());
+
+        let mut header = [0; 2];
+        stream.read_exact(&mut header).unwrap();
+        assert_eq!(header[0], 0x81);
+
+        cancellation_token.cancel();
+    }
+
+    #[test]
+    fn requested_rate() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let cancellation_token = Arc::new(CancellationToken::default());
+        let _server = WsServer::new(
+            (Ipv4Addr::LOCALHOST, port).into(),
+            None,
+            1000,
+            None,
+            cancellation_token.clone(),
+        )
+        .unwrap();
+
+        let (_, response) = connect(port, "/?rate=1800", "");
+        assert!(response.starts_with("HTTP/1.1 101"));
+        assert!(response.contains("X-Bottom-Rate: 2000\r\n"));
+
+        let (_, response) = connect(port, "/", "");
+        assert!(!response.contains("X-Bottom-Rate"));
+
+        cancellation_token.cancel();
+    }
+}
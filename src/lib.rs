@@ -8,51 +8,95 @@
 //! *usage* of bottom, refer to [here](https://clementtsang.github.io/bottom/stable/).
 
 pub(crate) mod app;
+pub(crate) mod bench;
+pub(crate) mod check;
 mod utils {
     pub(crate) mod cancellation_token;
     pub(crate) mod data_prefixes;
     pub(crate) mod data_units;
     pub(crate) mod general;
+    #[cfg(feature = "logging")]
+    pub(crate) mod json_logger;
     pub(crate) mod logging;
+    pub(crate) mod sparkline;
+    pub(crate) mod stats;
     pub(crate) mod strings;
+    pub(crate) mod terminal;
 }
 pub(crate) mod canvas;
 pub(crate) mod constants;
+#[cfg(unix)]
+pub(crate) mod daemon;
 pub(crate) mod data_collection;
 pub(crate) mod data_conversion;
 pub(crate) mod event;
+pub(crate) mod export;
 pub mod options;
+pub(crate) mod output;
+pub(crate) mod top_summary;
+#[cfg(feature = "update-check")]
+pub(crate) mod update_check;
 pub mod widgets;
 
 use std::{
     boxed::Box,
     io::{stderr, stdout, Write},
     panic::{self, PanicHookInfo},
+    path::PathBuf,
     sync::{
-        mpsc::{self, Receiver, Sender},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
         Arc,
     },
     thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
-use app::{layout_manager::UsedWidgets, App, AppConfigFields, DataFilters};
+use app::{
+    clipboard::Flash,
+    event_log::LogLevel,
+    exec::ExecRunner,
+    layout_manager::{BottomWidgetType, UsedWidgets},
+    multi_host::MultiHostState,
+    visible_columns::VisibleColumns,
+    App, AppConfigFields, DataFilters,
+};
 use crossterm::{
+    cursor::Show,
     event::{
         poll, read, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
         EnableMouseCapture, Event, KeyEventKind, MouseEventKind,
     },
     execute,
-    style::Print,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
+use data_collection::log_tail::{LogTail, LOG_TAIL_POLL_INTERVAL};
 use data_conversion::*;
 use event::{handle_key_event_or_break, handle_mouse_event, BottomEvent, CollectionThreadEvent};
-use options::{args, get_or_create_config, init_app};
-use tui::{backend::CrosstermBackend, Terminal};
-use utils::cancellation_token::CancellationToken;
+#[cfg(unix)]
+use export::ipc::{self, IpcServer};
+use export::{
+    hooks::Hooks,
+    influxdb::InfluxDbExporter,
+    record::{self, read_recording, Recorder, ReplayState},
+    ws_client,
+    ws_server::WsServer,
+    Exporters,
+};
+#[cfg(unix)]
+use options::get_ipc_socket_mode;
+use options::{
+    args, get_check_settings, get_exec_timeout, get_hook_settings, get_hosts, get_hosts_tls_config,
+    get_influxdb_settings, get_or_create_config, get_replay_speed, get_ws_tls_config, init_app,
+};
+use tui::{backend::CrosstermBackend, layout::Rect, Terminal, TerminalOptions, Viewport};
 #[allow(unused_imports, reason = "this is needed if logging is enabled")]
 use utils::logging::*;
+use utils::{
+    cancellation_token::CancellationToken, general::jittered_interval, terminal::get_terminal_size,
+};
 
 // Used for heap allocation debugging purposes.
 // #[global_allocator]
@@ -101,11 +145,18 @@ fn check_if_terminal() {
     }
 }
 
-/// A panic hook to properly restore the terminal in the case of a panic.
-/// Originally based on [spotify-tui's implementation](https://github.com/Rigellute/spotify-tui/blob/master/src/main.rs).
-fn panic_hook(panic_info: &PanicHookInfo<'_>) {
-    let mut stdout = stdout();
+/// Whether panics should include a backtrace, which they do with
+/// `--backtrace`, in debug builds, or if `RUST_BACKTRACE` asks for one.
+fn should_show_backtrace(backtrace_flag: bool) -> bool {
+    backtrace_flag
+        || cfg!(debug_assertions)
+        || std::env::var_os("RUST_BACKTRACE").is_some_and(|value| value != "0")
+}
 
+/// A panic hook to properly restore the terminal in the case of a panic, and
+/// then print what happened to stderr.
+/// Originally based on [spotify-tui's implementation](https://github.com/Rigellute/spotify-tui/blob/master/src/main.rs).
+fn panic_hook(panic_info: &PanicHookInfo<'_>, show_backtrace: bool) {
     let msg = match panic_info.payload().downcast_ref::<&'static str>() {
         Some(s) => *s,
         None => match panic_info.payload().downcast_ref::<String>() {
@@ -114,34 +165,68 @@ fn panic_hook(panic_info: &PanicHookInfo<'_>) {
         },
     };
 
-    let backtrace = format!("{:?}", backtrace::Backtrace::new());
+    let backtrace = show_backtrace.then(backtrace::Backtrace::new);
 
-    let _ = disable_raw_mode();
-    let _ = execute!(
-        stdout,
-        DisableBracketedPaste,
-        DisableMouseCapture,
-        LeaveAlternateScreen
-    );
-
-    // Print stack trace. Must be done after!
-    if let Some(panic_info) = panic_info.location() {
+    // The terminal is only set up while the interface is running, in which
+    // case it's in raw mode.
+    let is_interface_running = is_raw_mode_enabled().unwrap_or(false);
+    if is_interface_running {
+        let _ = disable_raw_mode();
         let _ = execute!(
-            stdout,
-            Print(format!(
-                "thread '<unnamed>' panicked at '{msg}', {panic_info}\n\r{backtrace}",
-            )),
+            stdout(),
+            DisableBracketedPaste,
+            DisableMouseCapture,
+            LeaveAlternateScreen,
+            Show
         );
     }
 
+    // Print the panic and stack trace. Must be done after, or leaving the
+    // alternate screen would hide it!
+    let mut stderr = stderr().lock();
+    let thread = thread::current();
+    let location = panic_info
+        .location()
+        .map(|location| format!(" at {location}"))
+        .unwrap_or_default();
+    let _ = writeln!(
+        stderr,
+        "thread '{}' panicked{location}:\n{msg}",
+        thread.name().unwrap_or("<unnamed>")
+    );
+    let _ = match backtrace {
+        Some(backtrace) => writeln!(stderr, "\n{backtrace:?}"),
+        None => writeln!(stderr, "\nRun with --backtrace to include a backtrace."),
+    };
+
     // TODO: Might be cleaner in the future to use a cancellation token, but that causes some fun issues with
     // lifetimes; for now if it panics then shut down the main program entirely ASAP.
-    std::process::exit(1);
+    if is_interface_running {
+        std::process::exit(1);
+    }
+}
+
+/// Create a thread that tells the main thread as soon as the terminal is
+/// resized, or [`None`] if the signal handler couldn't be set.
+#[cfg(unix)]
+fn create_resize_thread(sender: Sender<BottomEvent>) -> Option<JoinHandle<()>> {
+    use signal_hook::{consts::SIGWINCH, iterator::Signals};
+
+    let mut signals = Signals::new([SIGWINCH]).ok()?;
+    Some(thread::spawn(move || {
+        for _ in signals.forever() {
+            if sender.send(BottomEvent::Resize).is_err() {
+                break;
+            }
+        }
+    }))
 }
 
 /// Create a thread to poll for user inputs and forward them to the main thread.
+/// Resizes are only forwarded if `forward_resizes` is set, for when they aren't
+/// already being sent from a signal handler.
 fn create_input_thread(
-    sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>,
+    sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>, forward_resizes: bool,
 ) -> JoinHandle<()> {
     thread::spawn(move || {
         let mut mouse_timer = Instant::now();
@@ -159,13 +244,7 @@ fn create_input_thread(
                     if let Ok(event) = read() {
                         match event {
                             Event::Resize(_, _) => {
-                                // TODO: Might want to debounce this in the future, or take into
-                                // account the actual resize values.
-                                // Maybe we want to keep the current implementation in case the
-                                // resize event might not fire...
-                                // not sure.
-
-                                if sender.send(BottomEvent::Resize).is_err() {
+                                if forward_resizes && sender.send(BottomEvent::Resize).is_err() {
                                     break;
                                 }
                             }
@@ -210,16 +289,50 @@ fn create_input_thread(
 }
 
 /// Create a thread to handle data collection.
+/// Create a thread to follow the files given with `--watch_file`, sending
+/// what's changed in each to the main thread.
+fn create_log_tail_thread(
+    paths: Vec<PathBuf>, sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut log_tails = paths.into_iter().map(LogTail::new).collect::<Vec<_>>();
+
+        loop {
+            for (index, log_tail) in log_tails.iter_mut().enumerate() {
+                if let Some(update) = log_tail.poll() {
+                    if sender.send(BottomEvent::LogTail(index, update)).is_err() {
+                        return;
+                    }
+                }
+            }
+
+            if cancellation_token.sleep_with_cancellation(LOG_TAIL_POLL_INTERVAL) {
+                break;
+            }
+        }
+    })
+}
+
 fn create_collection_thread(
     sender: Sender<BottomEvent>, control_receiver: Receiver<CollectionThreadEvent>,
     cancellation_token: Arc<CancellationToken>, app_config_fields: &AppConfigFields,
-    filters: DataFilters, used_widget_set: UsedWidgets,
+    filters: DataFilters, used_widget_set: UsedWidgets, visible_columns: VisibleColumns,
+    mut exporters: Exporters,
 ) -> JoinHandle<()> {
     let temp_type = app_config_fields.temperature_type;
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
     let unnormalized_cpu = app_config_fields.unnormalized_cpu;
     let show_average_cpu = app_config_fields.show_average_cpu;
+    let collect_ipv6 = app_config_fields.show_ipv6;
+    let compute_pss = app_config_fields.compute_pss;
+    let ignore_cgroup_limits = app_config_fields.ignore_cgroup_limits;
+    let collect_affinity = app_config_fields.use_basic_mode;
+    let synthetic = app_config_fields.synthetic.clone();
     let update_time = app_config_fields.update_rate;
+    let jitter_pct = app_config_fields.jitter_pct;
+    let subsystem_retry_count = app_config_fields.subsystem_retry_count;
+    let collection_flags = app_config_fields.collection_flags;
+    let update_on_key = app_config_fields.update_on_key;
 
     thread::spawn(move || {
         let mut data_state = data_collection::DataCollector::new(filters);
@@ -229,8 +342,15 @@ fn create_collection_thread(
         data_state.set_use_current_cpu_total(use_current_cpu_total);
         data_state.set_unnormalized_cpu(unnormalized_cpu);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_visible_columns(visible_columns);
+        data_state.set_collect_ipv6(collect_ipv6);
+        data_state.set_compute_pss(compute_pss);
+        data_state.set_ignore_cgroup_limits(ignore_cgroup_limits);
+        data_state.set_collect_affinity(collect_affinity);
+        data_state.set_synthetic(synthetic);
+        data_state.set_subsystem_retry_count(subsystem_retry_count);
 
-        data_state.init();
+        data_state.init(collection_flags);
 
         loop {
             // Check once at the very top... don't block though.
@@ -246,6 +366,15 @@ fn create_collection_thread(
                     CollectionThreadEvent::Reset => {
                         data_state.data.cleanup();
                     }
+                    CollectionThreadEvent::SetTemperatureType(temperature_type) => {
+                        data_state.set_temperature_type(temperature_type);
+                    }
+                    CollectionThreadEvent::SetVisibleColumns(visible_columns) => {
+                        data_state.set_visible_columns(visible_columns);
+                    }
+                    CollectionThreadEvent::Pause
+                    | CollectionThreadEvent::Resume
+                    | CollectionThreadEvent::Refresh => {}
                 }
             }
 
@@ -258,20 +387,58 @@ fn create_collection_thread(
                 }
             }
 
+            exporters.export(&data_state.data);
+            #[cfg(feature = "logging")]
+            utils::json_logger::log_metrics(&data_state.data);
+
             let event = BottomEvent::Update(Box::from(data_state.data));
             data_state.data = data_collection::Data::default();
             if sender.send(event).is_err() {
                 break;
             }
 
-            // Sleep while allowing for interruptions...
-            if cancellation_token.sleep_with_cancellation(Duration::from_millis(update_time)) {
+            // Sleep while allowing for interruptions, or wait for a keypress instead...
+            let is_terminated = if update_on_key {
+                wait_for_refresh(&control_receiver, &mut data_state, &cancellation_token)
+            } else {
+                cancellation_token
+                    .sleep_with_cancellation(jittered_interval(update_time, jitter_pct))
+            };
+            if is_terminated {
                 break;
             }
         }
+
+        exporters.finish();
     })
 }
 
+/// Blocks until a [`CollectionThreadEvent::Refresh`] is received, handling any
+/// other events in the meantime. Returns whether the thread should stop instead.
+fn wait_for_refresh(
+    control_receiver: &Receiver<CollectionThreadEvent>,
+    data_state: &mut data_collection::DataCollector, cancellation_token: &CancellationToken,
+) -> bool {
+    loop {
+        match control_receiver.recv_timeout(Duration::from_millis(100)) {
+            Ok(CollectionThreadEvent::Refresh) => return false,
+            Ok(CollectionThreadEvent::Reset) => data_state.data.cleanup(),
+            Ok(CollectionThreadEvent::SetTemperatureType(temperature_type)) => {
+                data_state.set_temperature_type(temperature_type);
+            }
+            Ok(CollectionThreadEvent::SetVisibleColumns(visible_columns)) => {
+                data_state.set_visible_columns(visible_columns);
+            }
+            Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return true,
+        }
+
+        if let Some(true) = cancellation_token.try_check() {
+            return true;
+        }
+    }
+}
+
 /// Main code to call.
 #[inline]
 pub fn start_bottom() -> anyhow::Result<()> {
@@ -279,24 +446,173 @@ pub fn start_bottom() -> anyhow::Result<()> {
 
     let args = args::get_args();
 
+    // Set the panic hook first, so the terminal is restored however early a
+    // panic happens.
+    let show_backtrace = should_show_backtrace(args.general.backtrace);
+    panic::set_hook(Box::new(move |panic_info| {
+        panic_hook(panic_info, show_backtrace)
+    }));
+
     #[cfg(feature = "logging")]
     {
+        let json_log = options::get_json_log_settings(&args)?;
         if let Err(err) = init_logger(
             log::LevelFilter::Debug,
             Some(std::ffi::OsStr::new("debug.log")),
+            json_log,
         ) {
             println!("Issue initializing logger: {err}");
         }
     }
 
+    // Querying another instance doesn't need anything else to be set up.
+    #[cfg(unix)]
+    if let (Some(section), Some(path)) = (&args.general.query, &args.general.ipc_socket) {
+        return ipc::query(path, section);
+    }
+
+    #[cfg(not(unix))]
+    if args.general.ipc_socket.is_some() {
+        anyhow::bail!("IPC sockets are only supported on Unix-like systems.");
+    }
+
+    #[cfg(unix)]
+    if args.general.daemon {
+        return daemon::run(args);
+    }
+
+    #[cfg(not(unix))]
+    if args.general.daemon {
+        anyhow::bail!("Daemon mode is only supported on Unix-like systems.");
+    }
+
+    if args.general.generate_config {
+        if args.general.system {
+            return options::write_system_config();
+        }
+
+        return options::print_generated_config(args.general.format.as_deref());
+    }
+
+    #[cfg(feature = "update-check")]
+    if args.general.version_check {
+        return update_check::run();
+    }
+
     // Read from config file.
-    let config = get_or_create_config(args.general.config_location.as_deref())?;
+    let config = get_or_create_config(
+        args.general.config_location.as_deref(),
+        !args.general.no_system_config,
+    )?;
+
+    let export_path = args.general.export.clone();
+    let export_on_exit = args.general.export_on_exit.clone();
+    let export_once_on_start = args.general.export_once_on_start.clone();
+    let is_bench = args.general.bench;
+    let is_top_summary = args.general.top_summary;
+    let raw_filter = args.general.raw.then(|| {
+        args.general
+            .raw_filter
+            .as_deref()
+            .map(output::raw::RawFilter::new)
+    });
+    let check_settings = get_check_settings(&args)?;
+    let terminal_size_override = options::get_terminal_size_override(&args)?;
+    // --once is implied by --top_summary and --raw, which print instead.
+    let once_settings = if args.general.once && !is_top_summary && raw_filter.is_none() {
+        Some(options::get_once_settings(&args, &config)?)
+    } else {
+        None
+    };
+
+    // Set up recording or replaying before we take over the terminal, so errors can be shown.
+    let recorder = match &args.general.record {
+        Some(path) => Some(Recorder::new(path, options::get_record_max_size(&args)?)?),
+        None => None,
+    };
+    let influxdb_settings = get_influxdb_settings(&args, &config)?;
+    let hook_settings = get_hook_settings(&args, &config)?;
+    let exec_timeout = get_exec_timeout(&args)?;
+    let ws_settings = match options::get_ws_address(&args) {
+        Some(address) => Some((
+            address,
+            args.general.ws_token.clone(),
+            get_ws_tls_config(&args)?,
+        )),
+        None => None,
+    };
+    #[cfg(unix)]
+    let ipc_settings = match &args.general.ipc_socket {
+        Some(path) => Some((path.clone(), get_ipc_socket_mode(&args)?)),
+        None => None,
+    };
+    let hosts = get_hosts(&args)?;
+    let hosts_token = args.general.hosts_token.clone();
+    let hosts_tls = get_hosts_tls_config(&args)?;
+    let replay = match &args.general.replay {
+        Some(path) => Some((read_recording(path)?, get_replay_speed(&args)?)),
+        None => None,
+    };
 
     // Create the "app" and initialize a bunch of stuff.
     let (mut app, widget_layout, styling) = init_app(args, config)?;
 
+    if is_bench {
+        return bench::run(&app.app_config_fields, app.filters.clone());
+    }
+
+    if is_top_summary {
+        return top_summary::run(&app.app_config_fields, app.filters.clone());
+    }
+
+    if let Some(raw_filter) = raw_filter {
+        return output::raw::run(&app.app_config_fields, app.filters.clone(), raw_filter);
+    }
+
+    if let Some(settings) = once_settings {
+        return output::table::run(&app.app_config_fields, app.filters.clone(), &settings);
+    }
+
+    if let Some(settings) = check_settings {
+        let status = check::run(settings, &app.app_config_fields, app.filters.clone());
+        std::process::exit(status.exit_code());
+    }
+
+    // If we're just exporting, collect once and skip the interface entirely.
+    if let Some(path) = export_path {
+        let data = export::collect_snapshot(&app.app_config_fields, app.filters.clone());
+        return export::write_snapshot(&path, &data);
+    }
+
+    // The startup snapshot is taken before the interface starts. If it can't be written, the
+    // interface still starts, but the error is returned on exit.
+    let start_export_error = export_once_on_start.and_then(|path| {
+        let data = export::collect_snapshot(&app.app_config_fields, app.filters.clone());
+        let err = export::write_snapshot(&path, &data).err()?;
+        app.event_log.push(
+            LogLevel::Crit,
+            format!("Couldn't write the startup snapshot: {err:#}"),
+        );
+        Some(err)
+    });
+
+    if let Some((frames, speed)) = &replay {
+        app.replay = Some(ReplayState::new(frames, *speed));
+    }
+
+    if let Some(hosts) = &hosts {
+        app.multi_host = Some(MultiHostState::new(
+            hosts.iter().map(|(address, _)| address.clone()).collect(),
+        ));
+    }
+
     // Create painter and set colours.
-    let mut painter = canvas::Painter::init(widget_layout, styling)?;
+    let mut painter = canvas::Painter::init(
+        widget_layout,
+        styling,
+        app.app_config_fields.canvas_style.clone(),
+        app.app_config_fields.quiet_mode,
+    )?;
 
     // Check if the current environment is in a terminal.
     check_if_terminal();
@@ -304,20 +620,106 @@ pub fn start_bottom() -> anyhow::Result<()> {
     let cancellation_token = Arc::new(CancellationToken::default());
     let (sender, receiver) = mpsc::channel();
 
+    // The socket is removed when this guard is dropped on exit.
+    #[cfg(unix)]
+    let (ipc_server, _ipc_socket_guard) = match ipc_settings {
+        Some((path, mode)) => {
+            let (server, guard) = IpcServer::new(&path, mode, cancellation_token.clone())?;
+            (Some(server), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    let exporters = Exporters {
+        recorder,
+        influxdb: influxdb_settings
+            .map(|settings| InfluxDbExporter::new(settings, cancellation_token.clone()))
+            .transpose()?,
+        ws_server: ws_settings
+            .map(|(address, token, tls)| {
+                WsServer::new(
+                    address,
+                    token,
+                    app.app_config_fields.update_rate,
+                    tls,
+                    cancellation_token.clone(),
+                )
+            })
+            .transpose()?,
+        #[cfg(unix)]
+        ipc_server,
+    };
+
+    // Hooks only run on locally collected data, not replays or remote hosts.
+    let hooks = match (&hosts, &replay) {
+        (None, None) => hook_settings.map(|settings| Hooks::new(settings, sender.clone())),
+        _ => None,
+    };
+    let exec = app
+        .run_command
+        .as_deref()
+        .map(|command| ExecRunner::new(command, exec_timeout, sender.clone()));
+
+    if let Some(disk_bench) = &mut app.disk_bench {
+        disk_bench.start(sender.clone());
+    }
+
+    #[cfg(feature = "update-check")]
+    if app.app_config_fields.check_for_updates {
+        if let Err(err) = update_check::spawn_daily_check(sender.clone()) {
+            app.event_log.push(
+                LogLevel::Warn,
+                format!("Couldn't save the time of the update check: {err}"),
+            );
+        }
+    }
+
     // Set up the event loop thread; we set this up early to speed up
     // first-time-to-data.
     let (collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
-    let _collection_thread = create_collection_thread(
-        sender.clone(),
-        collection_thread_ctrl_receiver,
-        cancellation_token.clone(),
-        &app.app_config_fields,
-        app.filters.clone(),
-        app.used_widgets,
-    );
+    let _collection_threads = match (hosts, replay) {
+        // When monitoring remote hosts, nothing is collected locally.
+        (Some(hosts), _) => hosts
+            .into_iter()
+            .enumerate()
+            .map(|(index, (address, rate))| {
+                ws_client::create_remote_thread(
+                    index,
+                    address,
+                    rate,
+                    hosts_token.clone(),
+                    hosts_tls.clone(),
+                    sender.clone(),
+                    cancellation_token.clone(),
+                )
+            })
+            .collect(),
+        (None, Some((frames, speed))) => vec![record::create_replay_thread(
+            sender.clone(),
+            collection_thread_ctrl_receiver,
+            cancellation_token.clone(),
+            frames,
+            speed,
+        )],
+        (None, None) => vec![create_collection_thread(
+            sender.clone(),
+            collection_thread_ctrl_receiver,
+            cancellation_token.clone(),
+            &app.app_config_fields,
+            app.filters.clone(),
+            app.used_widgets,
+            app.visible_columns(),
+            exporters,
+        )],
+    };
 
-    // Set up the input handling loop thread.
-    let _input_thread = create_input_thread(sender.clone(), cancellation_token.clone());
+    // Set up the resize signal and input handling loop threads.
+    #[cfg(unix)]
+    let forward_resizes = create_resize_thread(sender.clone()).is_none();
+    #[cfg(not(unix))]
+    let forward_resizes = true;
+    let _input_thread =
+        create_input_thread(sender.clone(), cancellation_token.clone(), forward_resizes);
 
     // Set up the cleaning loop thread.
     let _cleaning_thread = {
@@ -335,6 +737,12 @@ pub fn start_bottom() -> anyhow::Result<()> {
         })
     };
 
+    // Set up the thread following the files given with --watch_file.
+    let _log_tail_thread = (!app.log_tails.is_empty()).then(|| {
+        let paths = app.log_tails.iter().map(|log_tail| log_tail.path.clone());
+        create_log_tail_thread(paths.collect(), sender.clone(), cancellation_token.clone())
+    });
+
     // Set up tui and crossterm
     let mut stdout_val = stdout();
     execute!(
@@ -345,7 +753,16 @@ pub fn start_bottom() -> anyhow::Result<()> {
     )?;
     enable_raw_mode()?;
 
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout_val))?;
+    // The size is kept fixed between resizes, as what the terminal itself
+    // reports isn't always the right size in a multiplexer.
+    let terminal_size = || terminal_size_override.unwrap_or_else(get_terminal_size);
+    let (width, height) = terminal_size();
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(stdout_val),
+        TerminalOptions {
+            viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+        },
+    )?;
     terminal.clear()?;
     terminal.hide_cursor()?;
 
@@ -361,8 +778,9 @@ pub fn start_bottom() -> anyhow::Result<()> {
         FileDescriptor::redirect_stdio(&path, StdioDescriptor::Stderr)?
     };
 
-    // Set panic hook
-    panic::set_hook(Box::new(panic_hook));
+    // Flame graphs are sampled in the background, so they need their own sender.
+    #[cfg(feature = "flame")]
+    let flame_graph_sender = sender.clone();
 
     // Set termination hook
     ctrlc::set_handler(move || {
@@ -371,38 +789,99 @@ pub fn start_bottom() -> anyhow::Result<()> {
 
     let mut first_run = true;
 
+    app.on_resize(width, height);
+
     // Draw once first to initialize the canvas, so it doesn't feel like it's
     // frozen.
     try_drawing(&mut terminal, &mut app, &mut painter)?;
 
     loop {
-        if let Ok(recv) = receiver.recv() {
+        if app.on_scroll_tick() {
+            app.dirty_widgets.mark(&app.current_widget.widget_type);
+            app.update_data();
+            try_drawing(&mut terminal, &mut app, &mut painter)?;
+        }
+
+        if app.flash.take_if(|flash| !flash.is_showing()).is_some() {
+            try_drawing(&mut terminal, &mut app, &mut painter)?;
+        }
+
+        // While a table is scrolling with momentum, a message is shown, or the
+        // heartbeat is shown, wake up in time for the next tick, to hide the
+        // message, or to show that the update is late.
+        let recv = match app
+            .scroll_momentum
+            .as_ref()
+            .and_then(|momentum| momentum.until_next_tick(Instant::now()))
+            .into_iter()
+            .chain(app.flash.as_ref().map(Flash::until_hidden))
+            .chain(app.until_heartbeat_change())
+            .min()
+        {
+            Some(timeout) => receiver.recv_timeout(timeout).ok(),
+            None => receiver.recv().ok(),
+        };
+
+        if let Some(recv) = recv {
             match recv {
                 BottomEvent::Terminate => {
                     break;
                 }
                 BottomEvent::Resize => {
+                    // Clear everything for the new size now, rather than waiting for the next
+                    // draw to notice.
+                    let (width, height) = terminal_size();
+                    terminal.resize(Rect::new(0, 0, width, height))?;
+                    app.on_resize(width, height);
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::KeyInput(event) => {
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
+                    #[cfg(feature = "flame")]
+                    if let Some(flame_graph) = &mut app.flame_graph {
+                        if flame_graph.is_pending {
+                            flame_graph.is_pending = false;
+                            app::flamegraph::start_capture(
+                                flame_graph.pid,
+                                flame_graph_sender.clone(),
+                            );
+                        }
+                    }
+                    app.dirty_widgets.mark(&app.current_widget.widget_type);
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::MouseInput(event) => {
                     handle_mouse_event(event, &mut app);
+                    app.dirty_widgets.mark(&app.current_widget.widget_type);
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::PasteEvent(paste) => {
                     app.handle_paste(paste);
+                    app.dirty_widgets.mark(&app.current_widget.widget_type);
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::Update(data) => {
+                    app.on_update_received();
+                    let alert = app
+                        .event_log
+                        .check_data(&data, app.app_config_fields.temperature_type);
+                    if let Some(hooks) = &hooks {
+                        hooks.run(&data, alert.as_deref());
+                    }
+                    if let Some(exec) = exec.as_ref().filter(|_| !app.frozen_state.is_frozen()) {
+                        exec.run();
+                    }
                     app.data_collection.eat_data(data);
+                    app.data_collection
+                        .prune_stale_processes(Duration::from_millis(
+                            app.app_config_fields.process_stale_threshold_ms,
+                        ));
+                    app.check_freeze_on_alert(alert);
 
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
@@ -412,34 +891,63 @@ pub fn start_bottom() -> anyhow::Result<()> {
                     }
 
                     if !app.frozen_state.is_frozen() {
+                        // The meminfo overlay isn't part of the collected data, so
+                        // it's read again here instead.
+                        app.meminfo.refresh();
+
                         // Convert all data into data for the displayed widgets.
 
                         if app.used_widgets.use_net {
-                            let network_data = convert_network_points(
+                            app.converted_data.convert_network_data(
                                 &app.data_collection,
-                                app.app_config_fields.use_basic_mode
-                                    || app.app_config_fields.use_old_network_legend,
-                                &app.app_config_fields.network_scale_type,
-                                &app.app_config_fields.network_unit_type,
-                                app.app_config_fields.network_use_binary_prefix,
+                                &app.app_config_fields,
+                                app.baseline.as_ref().and_then(|b| b.data.network.as_ref()),
                             );
-                            app.converted_data.network_data_rx = network_data.rx;
-                            app.converted_data.network_data_tx = network_data.tx;
-                            app.converted_data.rx_display = network_data.rx_display;
-                            app.converted_data.tx_display = network_data.tx_display;
-                            if let Some(total_rx_display) = network_data.total_rx_display {
-                                app.converted_data.total_rx_display = total_rx_display;
+                            app.dirty_widgets.mark(&BottomWidgetType::Net);
+                        }
+
+                        // Tables that haven't changed are left alone, so they aren't redrawn.
+                        if app.used_widgets.use_disk {
+                            let previous = std::mem::take(&mut app.converted_data.disk_data);
+                            app.converted_data.convert_disk_data(
+                                &app.data_collection,
+                                app.baseline.as_ref().and_then(|b| b.data.disks.as_deref()),
+                            );
+
+                            if app.converted_data.disk_data != previous {
+                                for disk in app.states.disk_state.widget_states.values_mut() {
+                                    disk.force_data_update();
+                                }
                             }
-                            if let Some(total_tx_display) = network_data.total_tx_display {
-                                app.converted_data.total_tx_display = total_tx_display;
+                        }
+
+                        #[cfg(feature = "systemd")]
+                        if app.used_widgets.use_startup {
+                            let previous = std::mem::take(&mut app.converted_data.startup_data);
+                            app.converted_data
+                                .convert_startup_data(&app.data_collection);
+
+                            if app.converted_data.startup_data != previous {
+                                for startup in app.states.startup_state.widget_states.values_mut() {
+                                    startup.force_data_update();
+                                }
                             }
                         }
 
-                        if app.used_widgets.use_disk {
-                            app.converted_data.convert_disk_data(&app.data_collection);
+                        if app.used_widgets.use_user_procs {
+                            let previous = std::mem::take(&mut app.converted_data.user_proc_data);
+                            let previous_counts = app.converted_data.process_counts;
+                            app.converted_data
+                                .convert_user_proc_data(&app.data_collection);
 
-                            for disk in app.states.disk_state.widget_states.values_mut() {
-                                disk.force_data_update();
+                            if app.converted_data.user_proc_data != previous
+                                || app.converted_data.process_counts != previous_counts
+                            {
+                                for user_procs in
+                                    app.states.user_proc_state.widget_states.values_mut()
+                                {
+                                    user_procs.force_data_update();
+                                }
                             }
                         }
 
@@ -447,6 +955,9 @@ pub fn start_bottom() -> anyhow::Result<()> {
                             app.converted_data.convert_temp_data(
                                 &app.data_collection,
                                 app.app_config_fields.temperature_type,
+                                app.baseline
+                                    .as_ref()
+                                    .and_then(|b| b.data.temperature_sensors.as_deref()),
                             );
 
                             for temp in app.states.temp_state.widget_states.values_mut() {
@@ -458,8 +969,10 @@ pub fn start_bottom() -> anyhow::Result<()> {
                             app.converted_data.mem_data =
                                 convert_mem_data_points(&app.data_collection);
 
+                            // Cache usage may also be collected for the header, so check
+                            // whether it should be shown in the memory widget too.
                             #[cfg(not(target_os = "windows"))]
-                            {
+                            if app.app_config_fields.enable_cache_memory {
                                 app.converted_data.cache_data =
                                     convert_cache_data_points(&app.data_collection);
                             }
@@ -479,14 +992,53 @@ pub fn start_bottom() -> anyhow::Result<()> {
                                     convert_gpu_data(&app.data_collection);
                             }
 
+                            app.converted_data
+                                .smooth_mem_data(app.app_config_fields.mem_smooth_n);
+
                             app.converted_data.mem_labels =
                                 convert_mem_label(&app.data_collection.memory_harvest);
 
                             app.converted_data.swap_labels =
                                 convert_mem_label(&app.data_collection.swap_harvest);
+                            if let Some(baseline) = &app.baseline {
+                                let collection = &app.data_collection;
+                                let converted = &mut app.converted_data;
+                                for (labels, harvest, baseline) in [
+                                    (
+                                        &mut converted.mem_labels,
+                                        &collection.memory_harvest,
+                                        &baseline.data.memory,
+                                    ),
+                                    (
+                                        &mut converted.swap_labels,
+                                        &collection.swap_harvest,
+                                        &baseline.data.swap,
+                                    ),
+                                ] {
+                                    if let (Some((_, frac)), Some(baseline)) = (labels, baseline) {
+                                        frac.push_str(&convert_mem_delta_label(harvest, baseline));
+                                    }
+                                }
+                            }
+                            app.converted_data.swap_devices =
+                                convert_swap_device_rows(&app.data_collection);
+                            app.converted_data.buddy_zones =
+                                convert_buddy_zone_rows(&app.data_collection);
+                            app.converted_data.swap_io_label = app
+                                .data_collection
+                                .swap_io
+                                .as_ref()
+                                .map(convert_swap_io_label);
+                            app.converted_data.pss_label =
+                                app.data_collection.total_pss.map(convert_pss_label);
+                            app.converted_data.cgroup_memory_label = app
+                                .data_collection
+                                .cgroup_memory
+                                .as_ref()
+                                .map(convert_cgroup_memory_label);
 
                             #[cfg(not(target_os = "windows"))]
-                            {
+                            if app.app_config_fields.enable_cache_memory {
                                 app.converted_data.cache_labels =
                                     convert_mem_label(&app.data_collection.cache_harvest);
                             }
@@ -496,24 +1048,40 @@ pub fn start_bottom() -> anyhow::Result<()> {
                                 app.converted_data.arc_labels =
                                     convert_mem_label(&app.data_collection.arc_harvest);
                             }
+
+                            app.dirty_widgets.mark(&BottomWidgetType::Mem);
                         }
 
                         if app.used_widgets.use_cpu {
-                            app.converted_data.convert_cpu_data(&app.data_collection);
+                            app.converted_data.convert_cpu_data(
+                                &app.data_collection,
+                                app.app_config_fields.cpu_smooth_n,
+                            );
                             app.converted_data.load_avg_data = app.data_collection.load_avg_harvest;
+                            app.dirty_widgets.mark(&BottomWidgetType::Cpu);
                         }
 
                         if app.used_widgets.use_proc {
                             for proc in app.states.proc_state.widget_states.values_mut() {
                                 proc.force_data_update();
+                                proc.update_thread_panel();
+                            }
+                        }
+
+                        if app.used_widgets.use_net {
+                            for net in app.states.net_state.widget_states.values_mut() {
+                                net.nic_stats_panel.refresh();
                             }
                         }
 
                         #[cfg(feature = "battery")]
                         {
                             if app.used_widgets.use_battery {
-                                app.converted_data.battery_data =
-                                    convert_battery_harvest(&app.data_collection);
+                                let battery_data = convert_battery_harvest(&app.data_collection);
+                                if battery_data != app.converted_data.battery_data {
+                                    app.converted_data.battery_data = battery_data;
+                                    app.dirty_widgets.mark(&BottomWidgetType::Battery);
+                                }
                             }
                         }
 
@@ -521,11 +1089,86 @@ pub fn start_bottom() -> anyhow::Result<()> {
                         try_drawing(&mut terminal, &mut app, &mut painter)?;
                     }
                 }
+                BottomEvent::ReplayPosition(position_ms) => {
+                    if let Some(replay) = &mut app.replay {
+                        replay.position_ms = position_ms;
+                    }
+                }
+                BottomEvent::RemoteUpdate(index, data) => {
+                    if let Some(multi_host) = &mut app.multi_host {
+                        multi_host.update(index, data);
+                    }
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                }
+                BottomEvent::RemoteDisconnected(index) => {
+                    if let Some(multi_host) = &mut app.multi_host {
+                        multi_host.disconnect(index);
+                    }
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                }
+                BottomEvent::Log(level, message) => {
+                    app.event_log.push(level, message);
+                    if app.event_log.is_showing {
+                        try_drawing(&mut terminal, &mut app, &mut painter)?;
+                    }
+                }
+                #[cfg(feature = "flame")]
+                BottomEvent::FlameGraph(pid, result) => {
+                    // The overlay may have been closed or opened for another process since.
+                    if let Some(flame_graph) = &mut app.flame_graph {
+                        if flame_graph.pid == pid {
+                            flame_graph.status = match result {
+                                Ok(stacks) => app::flamegraph::FlameGraphStatus::Done(stacks),
+                                Err(err) => app::flamegraph::FlameGraphStatus::Failed(err),
+                            };
+                            try_drawing(&mut terminal, &mut app, &mut painter)?;
+                        }
+                    }
+                }
+                BottomEvent::ExecOutput(output) => {
+                    app.exec_output = Some(*output);
+                    app.dirty_widgets.mark(&BottomWidgetType::Exec);
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                }
+                BottomEvent::DiskBench(result) => {
+                    if let Some(disk_bench) = &mut app.disk_bench {
+                        disk_bench.update(*result);
+                    }
+                    app.dirty_widgets.mark(&BottomWidgetType::Disk);
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                }
+                #[cfg(feature = "update-check")]
+                BottomEvent::UpdateAvailable(version) => {
+                    let message = format!("New version available: {version}");
+                    app.event_log.push(LogLevel::Info, message.clone());
+                    app.flash = Some(Flash::lasting(message, update_check::NOTICE_DURATION));
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                }
+                BottomEvent::LogTail(index, update) => {
+                    if let Some(log_tail) = app.log_tails.get_mut(index) {
+                        let num_new_lines = log_tail.update(update);
+                        let num_lines = log_tail.lines.len();
+                        for log_tail_widget_state in app
+                            .states
+                            .log_tail_state
+                            .widget_states
+                            .values_mut()
+                            .filter(|state| state.selected_tab == index)
+                        {
+                            log_tail_widget_state.on_new_lines(num_new_lines, num_lines);
+                        }
+                        app.dirty_widgets.mark(&BottomWidgetType::LogTail);
+                        try_drawing(&mut terminal, &mut app, &mut painter)?;
+                    }
+                }
                 BottomEvent::Clean => {
                     app.data_collection
                         .clean_data(app.app_config_fields.retention_ms);
                 }
             }
+        } else if app.is_showing_heartbeat() && app.late_update_age().is_some() {
+            // Keep counting how long ago the last update was.
+            try_drawing(&mut terminal, &mut app, &mut painter)?;
         }
     }
 
@@ -534,5 +1177,13 @@ pub fn start_bottom() -> anyhow::Result<()> {
     cancellation_token.cancel();
     cleanup_terminal(&mut terminal)?;
 
-    Ok(())
+    if let Some(path) = export_on_exit {
+        let data = export::collect_snapshot(&app.app_config_fields, app.filters.clone());
+        export::write_snapshot(&path, &data)?;
+    }
+
+    match start_export_error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
 }
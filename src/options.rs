@@ -10,31 +10,65 @@ use std::{
     convert::TryInto,
     fs,
     io::Write,
+    net::{Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
     str::FromStr,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result};
-use config::style::ColourPalette;
+use config::style::{CanvasStyle, ColourPalette};
 pub use config::Config;
 pub(crate) use error::{OptionError, OptionResult};
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexSet;
 use regex::Regex;
+use rustls::{ClientConfig, ServerConfig};
 #[cfg(feature = "battery")]
 use starship_battery::Manager;
 
 use self::{
-    args::BottomArgs,
-    config::{layout::Row, IgnoreList, StringOrNum},
+    args::{BottomArgs, SimulateArgs},
+    config::{
+        layout::{FinalWidget, Row, RowChildren},
+        IgnoreList, StringOrNum,
+    },
 };
+#[cfg(feature = "logging")]
+use crate::utils::json_logger::{JsonLogSettings, DEFAULT_LOG_KEEP, DEFAULT_LOG_MAX_SIZE_MB};
 use crate::{
-    app::{filter::Filter, layout_manager::*, *},
-    canvas::components::time_chart::LegendPosition,
+    app::{
+        diff::{DiffBaseline, DEFAULT_DIFF_THRESHOLD},
+        disk_bench::DiskBenchState,
+        exec::DEFAULT_EXEC_TIMEOUT_MS,
+        filter::Filter,
+        frozen_state::FreezeOnAlertMode,
+        layout_manager::*,
+        saved_state::SavedState,
+        scroll_momentum::DEFAULT_SCROLL_FRICTION,
+        system_summary::SystemSummaryPanel,
+        *,
+    },
+    canvas::components::{data_table::SortOrder, time_chart::LegendPosition},
+    check::{CheckFormat, CheckSettings, Thresholds, DEFAULT_CHECK_TIMEOUT},
     constants::*,
-    data_collection::temperature::TemperatureType,
-    utils::data_units::DataUnit,
+    data_collection::{
+        log_tail::{LogTailBuffer, DEFAULT_LOG_TAIL_LINES},
+        synthetic::SyntheticSettings,
+        system_info::full_uname,
+        temperature::TemperatureType,
+        CollectionFlags, DEFAULT_SUBSYSTEM_RETRY_COUNT,
+    },
+    export::{
+        hooks::{HookSettings, DEFAULT_HOOK_TIMEOUT_MS},
+        influxdb::{InfluxDbSettings, DEFAULT_INFLUXDB_BATCH_SIZE},
+        read_snapshot,
+        record::DEFAULT_RECORD_MAX_SIZE_MB,
+        tls,
+    },
+    output::table::{OnceFormat, TableSettings, DEFAULT_COLUMNS},
+    utils::{data_units::DataUnit, terminal},
     widgets::*,
 };
 
@@ -63,6 +97,9 @@ macro_rules! is_flag_enabled {
 /// The default config file sub-path.
 const DEFAULT_CONFIG_FILE_LOCATION: &str = "bottom/bottom.toml";
 
+/// The system-wide config file, which the user's config is layered over.
+pub(crate) const SYSTEM_CONFIG_PATH: &str = "/etc/bottom/bottom.toml";
+
 /// Returns the config path to use. If `override_config_path` is specified, then
 /// we will use that. If not, then return the "default" config path, which is:
 ///
@@ -123,7 +160,7 @@ fn get_config_path(override_config_path: Option<&Path>) -> Option<PathBuf> {
     config_path
 }
 
-fn create_config_at_path(path: &Path) -> anyhow::Result<Config> {
+fn create_config_at_path(path: &Path) -> anyhow::Result<()> {
     if let Some(parent_path) = path.parent() {
         fs::create_dir_all(parent_path)?;
     }
@@ -131,50 +168,171 @@ fn create_config_at_path(path: &Path) -> anyhow::Result<Config> {
     let mut file = fs::File::create(path)?;
     file.write_all(CONFIG_TEXT.as_bytes())?;
 
-    Ok(Config::default())
+    Ok(())
+}
+
+/// Reads the system-wide config file at `path`, if it exists and can be read by
+/// everyone. One that is only readable by some users is skipped rather than
+/// applying to just them.
+#[cfg(target_family = "unix")]
+fn read_system_config(path: &Path) -> anyhow::Result<Option<String>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(anyhow::Error::new(err)
+                .context(format!("bottom could not read '{}'.", path.display())))
+        }
+    };
+
+    if !metadata.is_file() || metadata.permissions().mode() & 0o004 == 0 {
+        return Ok(None);
+    }
+
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("bottom could not read '{}'.", path.display()))?;
+
+    Ok(Some(text))
+}
+
+#[cfg(not(target_family = "unix"))]
+fn read_system_config(_path: &Path) -> anyhow::Result<Option<String>> {
+    Ok(None)
+}
+
+/// The header of a config file written by `--generate_config`, which replaces
+/// the one of [`CONFIG_TEXT`].
+const GENERATED_CONFIG_HEADER: &str = r#"# This config file for bottom was generated with `btm --generate_config`, and
+# has every setting set to its default. Settings commented out with a space
+# after the # are examples, rather than defaults.
+"#;
+
+/// The default config file with every setting that has a default uncommented,
+/// leaving only the examples commented out.
+pub(crate) fn generated_config() -> String {
+    let body = CONFIG_TEXT
+        .split_once("\n\n")
+        .map_or(CONFIG_TEXT, |(_header, body)| body);
+
+    let config = Regex::new(r"(?m)^#([a-zA-Z\[])")
+        .unwrap()
+        .replace_all(body, "$1");
+
+    // The layout is indented to show how it's nested.
+    let config = Regex::new(r"(?m)^#(\s\s+)([a-zA-Z\[])")
+        .unwrap()
+        .replace_all(&config, "$1$2");
+
+    format!("{GENERATED_CONFIG_HEADER}\n{config}")
+}
+
+/// Prints a config file with every setting set to its default for
+/// `--generate_config`, or the JSON schema of the config file if the format is
+/// "json".
+pub fn print_generated_config(format: Option<&str>) -> anyhow::Result<()> {
+    match format {
+        Some("json") => {
+            #[cfg(feature = "generate_schema")]
+            println!("{}", config::schema::generate("nightly")?);
+
+            #[cfg(not(feature = "generate_schema"))]
+            anyhow::bail!(
+                "This build of bottom doesn't include the config schema, which can instead be found at \
+                https://github.com/ClementTsang/bottom/blob/main/schema/nightly/bottom.json."
+            );
+        }
+        Some("table") => anyhow::bail!("--format table can only be used with --once."),
+        _ => print!("{}", generated_config()),
+    }
+
+    Ok(())
+}
+
+/// Writes a config file with every setting set to its default to
+/// [`SYSTEM_CONFIG_PATH`] for `--generate_config --system`. This must be run as
+/// root, and won't replace an existing file.
+#[cfg(target_family = "unix")]
+pub fn write_system_config() -> anyhow::Result<()> {
+    // SAFETY: geteuid has no preconditions and can't fail.
+    if unsafe { libc::geteuid() } != 0 {
+        anyhow::bail!(
+            "--generate_config --system must be run as root to write to {SYSTEM_CONFIG_PATH}."
+        );
+    }
+
+    let path = Path::new(SYSTEM_CONFIG_PATH);
+    if path.try_exists()? {
+        anyhow::bail!("{SYSTEM_CONFIG_PATH} already exists, so it wasn't replaced.");
+    }
+
+    if let Some(parent_path) = path.parent() {
+        fs::create_dir_all(parent_path)?;
+    }
+    fs::write(path, generated_config())
+        .with_context(|| format!("bottom could not write to '{SYSTEM_CONFIG_PATH}'."))?;
+
+    println!("Wrote a default config to {SYSTEM_CONFIG_PATH}.");
+
+    Ok(())
+}
+
+#[cfg(not(target_family = "unix"))]
+pub fn write_system_config() -> anyhow::Result<()> {
+    anyhow::bail!("A system-wide config file is only supported on Unix-like systems.");
 }
 
 /// Get the config at `config_path`. If there is no config file at the specified
 /// path, it will try to create a new file with the default settings, and return
 /// the default config.
 ///
+/// If `use_system_config` is set, the config is layered over the system-wide one
+/// at [`SYSTEM_CONFIG_PATH`], if there is one.
+///
 /// We're going to use the following behaviour on when we'll return an error rather
 /// than just "silently" continuing on:
 /// - If the user passed in a path explicitly, then we will be loud and error out.
 /// - If the user does NOT pass in a path explicitly, then just show a warning,
 ///   but continue. This is in case they do not want to write a default config file at
 ///   the XDG locations, for example.
-pub fn get_or_create_config(config_path: Option<&Path>) -> anyhow::Result<Config> {
+pub fn get_or_create_config(
+    config_path: Option<&Path>, use_system_config: bool,
+) -> anyhow::Result<Config> {
+    let system_config = if use_system_config {
+        read_system_config(Path::new(SYSTEM_CONFIG_PATH))?
+    } else {
+        None
+    };
+
     let adjusted_config_path = get_config_path(config_path);
 
-    match &adjusted_config_path {
+    let user_config = match &adjusted_config_path {
         Some(path) => {
             if let Ok(config_string) = fs::read_to_string(path) {
-                Ok(toml_edit::de::from_str(&config_string)?)
+                config_string
             } else {
-                match create_config_at_path(path) {
-                    Ok(cfg) => Ok(cfg),
-                    Err(err) => {
-                        if config_path.is_some() {
-                            Err(err.context(format!(
-                                "bottom could not create a new config file at '{}'.",
-                                path.display()
-                            )))
-                        } else {
-                            indoc::eprintdoc!(
-                                "Note: bottom couldn't create a default config file at '{}', and the \
-                                application has fallen back to the default configuration.
-                                    
-                                Caused by:
-                                    {err}
-                                ",
-                                path.display()
-                            );
-
-                            Ok(Config::default())
-                        }
+                // A freshly created config has nothing set, so it's treated as empty.
+                if let Err(err) = create_config_at_path(path) {
+                    if config_path.is_some() {
+                        return Err(err.context(format!(
+                            "bottom could not create a new config file at '{}'.",
+                            path.display()
+                        )));
+                    } else {
+                        indoc::eprintdoc!(
+                            "Note: bottom couldn't create a default config file at '{}', and the \
+                            application has fallen back to the default configuration.
+                                
+                            Caused by:
+                                {err}
+                            ",
+                            path.display()
+                        );
                     }
                 }
+
+                String::new()
             }
         }
         None => {
@@ -190,9 +348,11 @@ pub fn get_or_create_config(config_path: Option<&Path>) -> anyhow::Result<Config
                 This could be for a variety of reasons, such as issues with file permissions."
             );
 
-            Ok(Config::default())
+            String::new()
         }
-    }
+    };
+
+    config::load_merged(system_config.as_deref(), &user_config)
 }
 
 /// Initialize the app.
@@ -207,17 +367,20 @@ pub(crate) fn init_app(
     let config = &config;
 
     let styling = ColourPalette::new(args, config)?;
+    let canvas_style = CanvasStyle::new(args, config)?;
 
-    let (widget_layout, default_widget_id, default_widget_type_option) =
+    let (widget_layout, default_widget_id, default_widget_type_option, alternate_layout) =
         get_widget_layout(args, config)
             .context("Found an issue while trying to build the widget layout.")?;
 
+    let update_rate = get_update_rate(args, config)?;
     let retention_ms = get_retention(args, config)?;
     let autohide_time = is_flag_enabled!(autohide_time, args.general, config);
     let default_time_value = get_default_time_value(args, config, retention_ms)?;
 
     let use_basic_mode = is_flag_enabled!(basic, args.general, config);
-    let expanded = is_flag_enabled!(expanded, args.general, config);
+    // Watching a single process always starts with the process widget expanded.
+    let expanded = args.process.pid.is_some() || is_flag_enabled!(expanded, args.general, config);
 
     // For processes
     let is_grouped = is_flag_enabled!(group_processes, args.process, config);
@@ -229,9 +392,15 @@ pub(crate) fn init_app(
     let is_advanced_kill = !(is_flag_enabled!(disable_advanced_kill, args.process, config));
     let process_memory_as_value = is_flag_enabled!(process_memory_as_value, args.process, config);
 
+    // Quiet mode leaves no room for the header.
+    let quiet_mode = is_flag_enabled!(quiet, args.general, config);
+    let show_header = is_flag_enabled!(show_header, args.general, config) && !quiet_mode;
+
     // For CPU
     let default_cpu_selection = get_default_cpu_selection(args, config);
 
+    let baseline = get_diff_baseline(args, config)?;
+
     let mut widget_map = HashMap::new();
     let mut cpu_state_map: HashMap<u64, CpuWidgetState> = HashMap::new();
     let mut mem_state_map: HashMap<u64, MemWidgetState> = HashMap::new();
@@ -240,6 +409,10 @@ pub(crate) fn init_app(
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::new();
     let mut disk_state_map: HashMap<u64, DiskTableWidget> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
+    let mut startup_state_map: HashMap<u64, StartupWidgetState> = HashMap::new();
+    let mut exec_state_map: HashMap<u64, ExecWidgetState> = HashMap::new();
+    let mut user_proc_state_map: HashMap<u64, UserProcWidgetState> = HashMap::new();
+    let mut log_tail_state_map: HashMap<u64, LogTailWidgetState> = HashMap::new();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -257,26 +430,22 @@ pub(crate) fn init_app(
     let network_use_binary_prefix =
         is_flag_enabled!(network_use_binary_prefix, args.network, config);
 
-    let proc_columns: Option<IndexSet<ProcWidgetColumn>> = {
-        config.processes.as_ref().and_then(|cfg| {
-            if cfg.columns.is_empty() {
-                None
-            } else {
-                // TODO: Should we be using an indexmap? Or maybe allow dupes.
-                Some(IndexSet::from_iter(
-                    cfg.columns.iter().map(ProcWidgetColumn::from),
-                ))
-            }
-        })
-    };
+    let proc_columns = get_proc_columns(args, config)?;
 
     let network_legend_position = get_network_legend_position(args, config)?;
     let memory_legend_position = get_memory_legend_position(args, config)?;
 
+    let saved_state = SavedState::load();
+
     // TODO: Can probably just reuse the options struct.
     let app_config_fields = AppConfigFields {
-        update_rate: get_update_rate(args, config)?,
-        temperature_type: get_temperature(args, config)
+        update_rate,
+        update_on_key: is_flag_enabled!(update_on_key, args.general, config),
+        jitter_pct: get_jitter_pct(args, config)?,
+        subsystem_retry_count: get_subsystem_retry_count(config)?,
+        collection_flags: get_collection_flags(args, config),
+        synthetic: get_synthetic_settings(args)?,
+        temperature_type: get_temperature(args, config, &saved_state)
             .context("Update 'temperature_type' in your config file.")?,
         show_average_cpu: get_show_average_cpu(args, config),
         use_dot: is_flag_enabled!(dot_marker, args.general, config),
@@ -288,9 +457,11 @@ pub(crate) fn init_app(
         time_interval: get_time_interval(args, config, retention_ms)?,
         hide_time: is_flag_enabled!(hide_time, args.general, config),
         autohide_time,
+        absolute_timestamps: get_absolute_timestamps(args, config)?,
         use_old_network_legend: is_flag_enabled!(use_old_network_legend, args.network, config),
         table_gap: u16::from(!(is_flag_enabled!(hide_table_gap, args.general, config))),
         disable_click: is_flag_enabled!(disable_click, args.general, config),
+        no_clipboard: is_flag_enabled!(no_clipboard, args.general, config),
         enable_gpu: get_enable_gpu(args, config),
         enable_cache_memory: get_enable_cache_memory(args, config),
         show_table_scroll_position: is_flag_enabled!(
@@ -298,6 +469,11 @@ pub(crate) fn init_app(
             args.general,
             config
         ),
+        show_header,
+        show_heartbeat: is_flag_enabled!(show_heartbeat, args.general, config),
+        quiet_mode,
+        canvas_style,
+        scroll_friction: get_scroll_friction(args, config)?,
         is_advanced_kill,
         memory_legend_position,
         network_legend_position,
@@ -305,24 +481,121 @@ pub(crate) fn init_app(
         network_unit_type,
         network_use_binary_prefix,
         retention_ms,
+        process_stale_threshold_ms: config
+            .processes
+            .as_ref()
+            .and_then(|cfg| cfg.stale_threshold_ms)
+            .unwrap_or(update_rate * STALE_PROCESS_UPDATE_PERIODS),
         dedicated_average_row: get_dedicated_avg_row(config),
+        show_ipv6: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.show_ipv6)
+            .unwrap_or(true),
+        network_per_interface: false,
+        network_shared_axis: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.network_shared_axis)
+            .unwrap_or(false),
+        freeze_on_alert: get_freeze_on_alert(args, config)?,
+        cpu_smooth_n: get_smooth_n(
+            args.cpu.cpu_avg_n,
+            config.flags.as_ref().and_then(|flags| flags.cpu_avg_n),
+            "cpu_avg_n",
+        )?,
+        mem_smooth_n: get_smooth_n(
+            args.memory.mem_avg_n,
+            config.flags.as_ref().and_then(|flags| flags.mem_avg_n),
+            "mem_avg_n",
+        )?,
+        swap_out_warning: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.swap_out_warning)
+            .unwrap_or(0),
+        compute_pss: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.compute_pss)
+            .unwrap_or(false),
+        ignore_cgroup_limits: is_flag_enabled!(ignore_cgroup_limits, args.memory, config),
+        show_graph_stats: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.show_graph_stats)
+            .unwrap_or(false),
+        check_for_updates: config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.check_for_updates)
+            .unwrap_or(false),
+        net_smooth_n: get_smooth_n(
+            args.network.net_avg_n,
+            config.flags.as_ref().and_then(|flags| flags.net_avg_n),
+            "net_avg_n",
+        )?,
     };
 
+    let (sort_by, sort_order) = get_process_sort(args, config, &proc_columns)?;
+    let thresholds = get_process_thresholds(args, config)?;
     let table_config = ProcTableConfig {
         is_case_sensitive,
         is_match_whole_word,
         is_use_regex,
         show_memory_as_values: process_memory_as_value,
         is_command: is_default_command,
+        show_diff: baseline.is_some(),
+        diff_threshold: baseline
+            .as_ref()
+            .map(|baseline| baseline.threshold)
+            .unwrap_or(DEFAULT_DIFF_THRESHOLD),
+        diff_label: baseline.as_ref().map(DiffBaseline::title_label),
+        major_fault_threshold: config
+            .processes
+            .as_ref()
+            .and_then(|cfg| cfg.major_fault_threshold)
+            .unwrap_or(DEFAULT_MAJOR_FAULT_THRESHOLD),
+        tcp_connection_threshold: config
+            .processes
+            .as_ref()
+            .and_then(|cfg| cfg.tcp_connection_threshold)
+            .unwrap_or(DEFAULT_TCP_CONNECTION_THRESHOLD),
+        change_thresholds: get_change_thresholds(args, config)?,
+        change_sort_ticks: get_change_sort_ticks(config)?,
+        highlight_top: config
+            .processes
+            .as_ref()
+            .map(|cfg| cfg.highlight_top.clone())
+            .unwrap_or_default(),
+        pinned_columns: config
+            .processes
+            .as_ref()
+            .map(|cfg| cfg.pinned_columns.clone())
+            .unwrap_or_default(),
+        column_order: saved_state.process_columns.clone(),
+        sort_by,
+        sort_order,
+        default_filter: get_default_filter(args, config),
+        watched_process: args.process.pid.clone(),
+        thresholds,
     };
 
-    for row in &widget_layout.rows {
+    // Widgets in the alternate layout get states too, so switching layouts
+    // keeps them around, but they aren't selected at first.
+    let rows = widget_layout.rows.iter().map(|row| (row, true)).chain(
+        alternate_layout
+            .iter()
+            .flat_map(|alternate| &alternate.layout.rows)
+            .map(|row| (row, false)),
+    );
+    for (row, is_initial_layout) in rows {
         for col in &row.children {
             for col_row in &col.children {
                 for widget in &col_row.children {
                     widget_map.insert(widget.widget_id, widget.clone());
                     if let Some(default_widget_type) = &default_widget_type_option {
-                        if !is_custom_layout || use_basic_mode {
+                        if is_initial_layout && (!is_custom_layout || use_basic_mode) {
                             match widget.widget_type {
                                 BasicCpu => {
                                     if let Cpu = *default_widget_type {
@@ -370,13 +643,22 @@ pub(crate) fn init_app(
                         Mem => {
                             mem_state_map.insert(
                                 widget.widget_id,
-                                MemWidgetState::init(default_time_value, autohide_timer),
+                                MemWidgetState::init(
+                                    default_time_value,
+                                    autohide_timer,
+                                    app_config_fields.show_graph_stats,
+                                ),
                             );
                         }
                         Net => {
                             net_state_map.insert(
                                 widget.widget_id,
-                                NetWidgetState::init(default_time_value, autohide_timer),
+                                NetWidgetState::init(
+                                    &app_config_fields,
+                                    default_time_value,
+                                    autohide_timer,
+                                    &styling,
+                                ),
                             );
                         }
                         Proc => {
@@ -395,7 +677,7 @@ pub(crate) fn init_app(
                                 ProcWidgetState::new(
                                     &app_config_fields,
                                     mode,
-                                    table_config,
+                                    table_config.clone(),
                                     &styling,
                                     &proc_columns,
                                 ),
@@ -421,6 +703,25 @@ pub(crate) fn init_app(
                             battery_state_map
                                 .insert(widget.widget_id, BatteryWidgetState::default());
                         }
+                        Startup => {
+                            startup_state_map.insert(
+                                widget.widget_id,
+                                StartupWidgetState::new(&app_config_fields, &styling),
+                            );
+                        }
+                        Exec => {
+                            exec_state_map.insert(widget.widget_id, ExecWidgetState::default());
+                        }
+                        UserProcs => {
+                            user_proc_state_map.insert(
+                                widget.widget_id,
+                                UserProcWidgetState::new(&app_config_fields, &styling),
+                            );
+                        }
+                        LogTail => {
+                            log_tail_state_map
+                                .insert(widget.widget_id, LogTailWidgetState::default());
+                        }
                         _ => {}
                     }
                 }
@@ -451,18 +752,37 @@ pub(crate) fn init_app(
         None
     };
 
-    let use_mem = used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some();
-    let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
+    // The header needs CPU, memory, and process data, even if no widget shows them.
+    let use_mem = show_header
+        || used_widget_set.get(&Mem).is_some()
+        || used_widget_set.get(&BasicMem).is_some();
+    let mut used_widgets = UsedWidgets {
+        use_cpu: show_header
+            || used_widget_set.get(&Cpu).is_some()
+            || used_widget_set.get(&BasicCpu).is_some(),
         use_mem,
-        use_cache: use_mem && get_enable_cache_memory(args, config),
+        use_cache: show_header || (use_mem && get_enable_cache_memory(args, config)),
         use_gpu: get_enable_gpu(args, config),
         use_net: used_widget_set.get(&Net).is_some() || used_widget_set.get(&BasicNet).is_some(),
-        use_proc: used_widget_set.get(&Proc).is_some(),
+        // The startup widget shows the usage of each unit's main process, and
+        // the user processes widget counts processes.
+        use_proc: show_header
+            || used_widget_set.get(&Proc).is_some()
+            || used_widget_set.get(&Startup).is_some()
+            || used_widget_set.get(&UserProcs).is_some(),
         use_disk: used_widget_set.get(&Disk).is_some(),
+        use_smart: used_widget_set.get(&Disk).is_some()
+            && config.disk.as_ref().is_some_and(|cfg| {
+                cfg.columns
+                    .iter()
+                    .any(|column| matches!(column, DiskColumn::Wear))
+            }),
         use_temp: used_widget_set.get(&Temp).is_some(),
         use_battery: used_widget_set.get(&Battery).is_some(),
+        use_startup: used_widget_set.get(&Startup).is_some(),
+        use_user_procs: used_widget_set.get(&UserProcs).is_some(),
     };
+    app_config_fields.collection_flags.mask(&mut used_widgets);
 
     let (disk_name_filter, disk_mount_filter) = {
         match &config.disk {
@@ -496,6 +816,10 @@ pub(crate) fn init_app(
         temp_state: TempState::init(temp_state_map),
         disk_state: DiskState::init(disk_state_map),
         battery_state: BatteryState::init(battery_state_map),
+        startup_state: StartupState::init(startup_state_map),
+        exec_state: ExecState::init(exec_state_map),
+        user_proc_state: UserProcState::init(user_proc_state_map),
+        log_tail_state: LogTailState::init(log_tail_state_map),
         basic_table_widget_state,
     };
 
@@ -508,84 +832,324 @@ pub(crate) fn init_app(
     };
     let is_expanded = expanded && !use_basic_mode;
 
-    Ok((
-        App::new(
-            app_config_fields,
-            states,
-            widget_map,
-            current_widget,
-            used_widgets,
-            filters,
-            is_expanded,
-        ),
-        widget_layout,
-        styling,
-    ))
+    let mut app = App::new(
+        app_config_fields,
+        states,
+        widget_map,
+        current_widget,
+        used_widgets,
+        filters,
+        is_expanded,
+        baseline,
+    );
+    app.alternate_layout = alternate_layout;
+    app.set_process_thresholds(thresholds);
+    app.set_process_top_n(get_process_top_n(args)?);
+    app.system_summary = SystemSummaryPanel::new(&full_uname());
+    app.column_presets = get_column_presets(config, saved_state.column_presets);
+    app.run_command = args.general.exec.clone();
+    app.disk_bench = args.general.disk_bench.clone().map(DiskBenchState::new);
+    app.log_tails = get_log_tails(args)?;
+
+    Ok((app, widget_layout, styling))
+}
+
+/// The process column presets, which are the ones in the config file followed
+/// by the ones saved at runtime.
+fn get_column_presets(config: &Config, saved: Vec<ColumnPreset>) -> Vec<ColumnPreset> {
+    let presets = config
+        .presets
+        .iter()
+        .flatten()
+        .map(|(name, columns)| ColumnPreset::new(name.clone(), columns.clone()))
+        .collect();
+
+    column_preset::merge_presets(presets, saved)
 }
 
 fn get_widget_layout(
     args: &BottomArgs, config: &Config,
-) -> OptionResult<(BottomLayout, u64, Option<BottomWidgetType>)> {
+) -> OptionResult<(
+    BottomLayout,
+    u64,
+    Option<BottomWidgetType>,
+    Option<AlternateLayout>,
+)> {
     let cpu_left_legend = is_flag_enabled!(cpu_left_legend, args.cpu, config);
 
     let (default_widget_type, mut default_widget_count) =
         get_default_widget_and_count(args, config)?;
     let mut default_widget_id = 1;
 
-    let bottom_layout = if is_flag_enabled!(basic, args.general, config) {
+    if is_flag_enabled!(basic, args.general, config) {
         default_widget_id = DEFAULT_WIDGET_ID;
 
-        BottomLayout::init_basic_default(get_use_battery(args, config))
-    } else {
-        let ref_row: Vec<Row>; // Required to handle reference
-        let rows = match &config.row {
-            Some(r) => r,
-            None => {
-                // This cannot (like it really shouldn't) fail!
-                ref_row = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
-                    DEFAULT_BATTERY_LAYOUT
-                } else {
-                    DEFAULT_LAYOUT
-                })?
-                .row
-                .unwrap();
-                &ref_row
+        return Ok((
+            BottomLayout::init_basic_default(get_use_battery(args, config)),
+            default_widget_id,
+            default_widget_type,
+            None,
+        ));
+    }
+
+    let ref_row: Vec<Row>; // Required to handle reference
+    let rows = match &config.row {
+        Some(r) => r,
+        None => {
+            // This cannot (like it really shouldn't) fail!
+            ref_row = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
+                DEFAULT_BATTERY_LAYOUT
+            } else {
+                DEFAULT_LAYOUT
+            })?
+            .row
+            .unwrap();
+            &ref_row
+        }
+    };
+
+    // Make room for the output of --exec, for --nproc, and for the files
+    // followed with --watch_file if the layout doesn't have anywhere to show
+    // them.
+    let is_exec = args.general.exec.is_some();
+    let is_nproc = args.general.nproc;
+    let is_watch_file = !args.general.watch_file.is_empty();
+    let collection_flags = get_collection_flags(args, config);
+    let rows = &without_uncollected_widgets(
+        with_extra_rows(rows, is_exec, is_nproc, is_watch_file),
+        collection_flags,
+    );
+
+    let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
+    let bottom_layout = build_layout(
+        rows,
+        &mut iter_id,
+        &mut default_widget_id,
+        &default_widget_type,
+        &mut default_widget_count,
+        cpu_left_legend,
+        is_exec,
+        is_watch_file,
+    )?;
+
+    if is_flag_enabled!(minimal, args.general, config) {
+        // Start with the minimal layout, keeping the full one to switch to. IDs
+        // carry on from the full layout so both can share the same widget map.
+        let (_, mut minimal_widget_count) = get_default_widget_and_count(args, config)?;
+        let mut minimal_widget_id = iter_id + 1;
+        let minimal_rows = toml_edit::de::from_str::<Config>(MINIMAL_LAYOUT)?
+            .row
+            .unwrap();
+        let minimal_rows = without_uncollected_widgets(
+            with_extra_rows(&minimal_rows, is_exec, is_nproc, is_watch_file),
+            collection_flags,
+        );
+        let minimal_layout = build_layout(
+            &minimal_rows,
+            &mut iter_id,
+            &mut minimal_widget_id,
+            &default_widget_type,
+            &mut minimal_widget_count,
+            cpu_left_legend,
+            is_exec,
+            is_watch_file,
+        )?;
+
+        return Ok((
+            minimal_layout,
+            minimal_widget_id,
+            default_widget_type,
+            Some(AlternateLayout {
+                layout: bottom_layout,
+                widget_id: default_widget_id,
+            }),
+        ));
+    }
+
+    Ok((bottom_layout, default_widget_id, default_widget_type, None))
+}
+
+/// Builds a layout from config rows, with widget IDs starting after `iter_id`.
+fn build_layout(
+    rows: &[Row], iter_id: &mut u64, default_widget_id: &mut u64,
+    default_widget_type: &Option<BottomWidgetType>, default_widget_count: &mut u64,
+    cpu_left_legend: bool, is_exec: bool, is_watch_file: bool,
+) -> OptionResult<BottomLayout> {
+    let first_id = *iter_id;
+    let mut total_height_ratio = 0;
+
+    let mut bottom_layout = BottomLayout {
+        rows: rows
+            .iter()
+            .map(|row| {
+                row.convert_row_to_bottom_row(
+                    iter_id,
+                    &mut total_height_ratio,
+                    default_widget_id,
+                    default_widget_type,
+                    default_widget_count,
+                    cpu_left_legend,
+                )
+                .map_err(|err| OptionError::config(err.to_string()))
+            })
+            .collect::<OptionResult<Vec<_>>>()?,
+        total_row_height_ratio: total_height_ratio,
+    };
+
+    // The startup widget needs a systemd user instance, so hide it if there
+    // isn't one.
+    {
+        let mut startup_widgets = bottom_layout
+            .widgets_mut()
+            .filter(|widget| widget.widget_type == BottomWidgetType::Startup)
+            .peekable();
+        if startup_widgets.peek().is_some() && !is_systemd_available() {
+            for widget in startup_widgets {
+                widget.widget_type = BottomWidgetType::Empty;
             }
+        }
+    }
+
+    // Exec widgets have nothing to show without a command, nor log tail
+    // widgets without files.
+    for widget in bottom_layout.widgets_mut() {
+        let is_unused = match widget.widget_type {
+            BottomWidgetType::Exec => !is_exec,
+            BottomWidgetType::LogTail => !is_watch_file,
+            _ => false,
         };
+        if is_unused {
+            widget.widget_type = BottomWidgetType::Empty;
+        }
+    }
 
-        let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
-        let mut total_height_ratio = 0;
+    // Confirm that we have at least ONE widget left - if not, error out!
+    if *iter_id > first_id {
+        bottom_layout.get_movement_mappings();
+        Ok(bottom_layout)
+    } else {
+        Err(OptionError::config(
+            "have at least one widget under the '[[row]]' section.",
+        ))
+    }
+}
 
-        let mut ret_bottom_layout = BottomLayout {
-            rows: rows
-                .iter()
-                .map(|row| {
-                    row.convert_row_to_bottom_row(
-                        &mut iter_id,
-                        &mut total_height_ratio,
-                        &mut default_widget_id,
-                        &default_widget_type,
-                        &mut default_widget_count,
-                        cpu_left_legend,
-                    )
-                    .map_err(|err| OptionError::config(err.to_string()))
-                })
-                .collect::<OptionResult<Vec<_>>>()?,
-            total_row_height_ratio: total_height_ratio,
-        };
+/// The rows with an exec row, a user processes row, and a log tail row added
+/// at the bottom for --exec, --nproc, and --watch_file, unless the rows
+/// already have a widget of that type.
+fn with_extra_rows(rows: &[Row], is_exec: bool, is_nproc: bool, is_watch_file: bool) -> Vec<Row> {
+    let mut rows = rows.to_vec();
+    if is_exec {
+        rows = with_widget_row(rows, BottomWidgetType::Exec, "exec");
+    }
+    if is_nproc {
+        rows = with_widget_row(rows, BottomWidgetType::UserProcs, "user_procs");
+    }
+    if is_watch_file {
+        rows = with_widget_row(rows, BottomWidgetType::LogTail, "log_tail");
+    }
 
-        // Confirm that we have at least ONE widget left - if not, error out!
-        if iter_id > 0 {
-            ret_bottom_layout.get_movement_mappings();
-            ret_bottom_layout
-        } else {
-            return Err(OptionError::config(
-                "have at least one widget under the '[[row]]' section.",
-            ));
-        }
+    rows
+}
+
+/// The rows with a row of a `widget_type` widget added at the bottom if none
+/// of the rows have one. The row takes up a fifth of the height.
+fn with_widget_row(
+    mut rows: Vec<Row>, widget_type: BottomWidgetType, widget_name: &str,
+) -> Vec<Row> {
+    let has_widget = rows
+        .iter()
+        .flat_map(|row| row.child.iter().flatten())
+        .flat_map(|child| match child {
+            RowChildren::Widget(widget) => std::slice::from_ref(widget),
+            RowChildren::Col { child, .. } => child.as_slice(),
+        })
+        .any(|widget| {
+            widget.widget_type.parse::<BottomWidgetType>().ok() == Some(widget_type.clone())
+        });
+
+    if !has_widget {
+        let total_ratio: u32 = rows.iter().map(|row| row.ratio.unwrap_or(1)).sum();
+        rows.push(Row {
+            ratio: Some((total_ratio / 4).max(1)),
+            child: Some(vec![RowChildren::Widget(FinalWidget {
+                ratio: None,
+                widget_type: widget_name.to_string(),
+                default: None,
+            })]),
+        });
+    }
+
+    rows
+}
+
+/// The rows without the widgets whose data isn't collected, dropping any rows
+/// and columns left empty. Widgets of unknown types are kept so that building
+/// the layout reports them.
+fn without_uncollected_widgets(rows: Vec<Row>, collection_flags: CollectionFlags) -> Vec<Row> {
+    let is_collected = |widget: &FinalWidget| {
+        widget
+            .widget_type
+            .parse::<BottomWidgetType>()
+            .map_or(true, |widget_type| collection_flags.collects(&widget_type))
     };
 
-    Ok((bottom_layout, default_widget_id, default_widget_type))
+    rows.into_iter()
+        .filter_map(|row| {
+            let children = row
+                .child?
+                .into_iter()
+                .filter_map(|child| match child {
+                    RowChildren::Widget(widget) => {
+                        is_collected(&widget).then_some(RowChildren::Widget(widget))
+                    }
+                    RowChildren::Col { ratio, child } => {
+                        let child = child.into_iter().filter(is_collected).collect::<Vec<_>>();
+                        (!child.is_empty()).then_some(RowChildren::Col { ratio, child })
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            (!children.is_empty()).then_some(Row {
+                ratio: row.ratio,
+                child: Some(children),
+            })
+        })
+        .collect()
+}
+
+/// The subsystems to collect, which are all of them unless turned off with
+/// --no_proc, --no_net, --no_disk, or --no_temp.
+fn get_collection_flags(args: &BottomArgs, config: &Config) -> CollectionFlags {
+    let mut collection_flags = CollectionFlags::all();
+    collection_flags.set(
+        CollectionFlags::PROCESSES,
+        !is_flag_enabled!(no_proc, args.general, config),
+    );
+    collection_flags.set(
+        CollectionFlags::NETWORK,
+        !is_flag_enabled!(no_net, args.general, config),
+    );
+    collection_flags.set(
+        CollectionFlags::DISKS,
+        !is_flag_enabled!(no_disk, args.general, config),
+    );
+    collection_flags.set(
+        CollectionFlags::TEMPERATURE,
+        !is_flag_enabled!(no_temp, args.general, config),
+    );
+
+    collection_flags
+}
+
+#[cfg(feature = "systemd")]
+fn is_systemd_available() -> bool {
+    crate::data_collection::systemd::is_available()
+}
+
+#[cfg(not(feature = "systemd"))]
+fn is_systemd_available() -> bool {
+    false
 }
 
 #[inline]
@@ -684,13 +1248,123 @@ fn get_update_rate(args: &BottomArgs, config: &Config) -> OptionResult<u64> {
     )
 }
 
-fn get_temperature(args: &BottomArgs, config: &Config) -> OptionResult<TemperatureType> {
+/// Parses a network rate like `100mb` or `1.5gbit` into bits per second. The
+/// units are decimal, and a plain number is in bits.
+fn parse_bit_rate(rate: &str) -> Result<u64, ()> {
+    let rate = rate.trim().to_ascii_lowercase();
+    let rate = rate.strip_suffix("/s").unwrap_or(&rate);
+    let rate = rate
+        .strip_suffix("bit")
+        .or_else(|| rate.strip_suffix('b'))
+        .unwrap_or(rate);
+    let (number, multiplier) = match rate.char_indices().last() {
+        Some((index, 'k')) => (&rate[..index], 1e3),
+        Some((index, 'm')) => (&rate[..index], 1e6),
+        Some((index, 'g')) => (&rate[..index], 1e9),
+        _ => (rate, 1.0),
+    };
+
+    match number.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok((value * multiplier) as u64),
+        _ => Err(()),
+    }
+}
+
+/// Gets what data to make up, if any of the simulation options are given.
+fn get_synthetic_settings(args: &BottomArgs) -> OptionResult<Option<SyntheticSettings>> {
+    let SimulateArgs {
+        simulate_cpu,
+        simulate_mem,
+        simulate_net,
+        simulate_period,
+        simulate_procs,
+    } = &args.simulate;
+
+    if simulate_cpu.is_none()
+        && simulate_mem.is_none()
+        && simulate_net.is_none()
+        && simulate_period.is_none()
+        && simulate_procs.is_none()
+    {
+        return Ok(None);
+    }
+
+    let percent = |value: Option<f64>, default: f64, flag: &str| match value {
+        Some(value) if (0.0..=100.0).contains(&value) => Ok(value),
+        Some(_) => Err(OptionError::invalid_arg_value(flag)),
+        None => Ok(default),
+    };
+
+    let net_bits_per_sec = match simulate_net {
+        Some(rate) => parse_arg_value!(parse_bit_rate(rate), "simulate_net")?,
+        None => SyntheticSettings::DEFAULT_NET_BITS_PER_SEC,
+    };
+
+    let period = match simulate_period {
+        Some(period) => match try_parse_ms(period) {
+            Ok(ms) if ms > 0 => Some(Duration::from_millis(ms)),
+            _ => return Err(OptionError::invalid_arg_value("simulate_period")),
+        },
+        None => None,
+    };
+
+    Ok(Some(SyntheticSettings {
+        cpu_percent: percent(
+            *simulate_cpu,
+            SyntheticSettings::DEFAULT_CPU_PERCENT,
+            "simulate_cpu",
+        )?,
+        mem_percent: percent(
+            *simulate_mem,
+            SyntheticSettings::DEFAULT_MEM_PERCENT,
+            "simulate_mem",
+        )?,
+        net_bits_per_sec,
+        period,
+        num_processes: simulate_procs.unwrap_or(SyntheticSettings::DEFAULT_NUM_PROCESSES),
+    }))
+}
+
+/// Gets how many percent of the refresh rate each update is randomly moved by.
+/// Arguments take precedence over the config file.
+fn get_jitter_pct(args: &BottomArgs, config: &Config) -> OptionResult<u64> {
+    match (
+        args.general.interval_jitter,
+        config.timing.as_ref().and_then(|cfg| cfg.jitter_pct),
+    ) {
+        (Some(pct), _) if pct > 100 => Err(OptionError::invalid_arg_value("interval_jitter")),
+        (None, Some(pct)) if pct > 100 => Err(OptionError::invalid_config_value("jitter_pct")),
+        (Some(pct), _) | (None, Some(pct)) => Ok(pct),
+        (None, None) => Ok(0),
+    }
+}
+
+/// Gets how many times in a row a subsystem can fail before it's turned off.
+fn get_subsystem_retry_count(config: &Config) -> OptionResult<u32> {
+    match config
+        .timing
+        .as_ref()
+        .and_then(|cfg| cfg.subsystem_retry_count)
+    {
+        Some(0) => Err(OptionError::invalid_config_value("subsystem_retry_count")),
+        Some(count) => Ok(count),
+        None => Ok(DEFAULT_SUBSYSTEM_RETRY_COUNT),
+    }
+}
+
+/// Gets the temperature unit. A unit picked at runtime is remembered, and takes
+/// priority over the config file but not the command-line flags.
+fn get_temperature(
+    args: &BottomArgs, config: &Config, saved_state: &SavedState,
+) -> OptionResult<TemperatureType> {
     if args.temperature.fahrenheit {
         return Ok(TemperatureType::Fahrenheit);
     } else if args.temperature.kelvin {
         return Ok(TemperatureType::Kelvin);
     } else if args.temperature.celsius {
         return Ok(TemperatureType::Celsius);
+    } else if let Some(temp_type) = saved_state.temperature_type {
+        return Ok(temp_type);
     } else if let Some(flags) = &config.flags {
         if let Some(temp_type) = &flags.temperature_type {
             return parse_config_value!(TemperatureType::from_str(temp_type), "temperature_type");
@@ -733,6 +1407,254 @@ fn get_dedicated_avg_row(config: &Config) -> bool {
     conf
 }
 
+/// Gets how many points a graph is smoothed over. This defaults to 1, which
+/// means no smoothing.
+fn get_smooth_n(arg: Option<usize>, config: Option<usize>, setting: &str) -> OptionResult<usize> {
+    match (arg, config) {
+        (Some(0), _) => Err(OptionError::arg(format!(
+            "'--{setting}' must be at least 1"
+        ))),
+        (Some(n), _) => Ok(n),
+        (None, Some(0)) => Err(OptionError::config(format!(
+            "'{setting}' must be at least 1"
+        ))),
+        (None, Some(n)) => Ok(n),
+        (None, None) => Ok(1),
+    }
+}
+
+/// Gets whether graphs label their time axis with wall-clock times, from
+/// either `--absolute_timestamps` or `graph_timestamps` in the config file.
+fn get_absolute_timestamps(args: &BottomArgs, config: &Config) -> OptionResult<bool> {
+    if args.general.absolute_timestamps {
+        return Ok(true);
+    }
+
+    match config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.graph_timestamps.as_deref())
+    {
+        Some(value) => match value.to_ascii_lowercase().trim() {
+            "absolute" => Ok(true),
+            "relative" => Ok(false),
+            _ => Err(OptionError::invalid_config_value("graph_timestamps")),
+        },
+        None => Ok(false),
+    }
+}
+
+/// Gets whether to freeze on alerts, and in which mode.
+fn get_freeze_on_alert(
+    args: &BottomArgs, config: &Config,
+) -> OptionResult<Option<FreezeOnAlertMode>> {
+    if !is_flag_enabled!(freeze_on_alert, args.general, config) {
+        return Ok(None);
+    }
+
+    match config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.freeze_on_alert_mode.as_deref())
+    {
+        Some(mode) => Ok(Some(parse_config_value!(
+            mode.parse(),
+            "freeze_on_alert_mode"
+        )?)),
+        None => Ok(Some(FreezeOnAlertMode::default())),
+    }
+}
+
+/// Returns the process filter to start with, if set. An empty filter is the
+/// same as none. Watching a single process takes priority. Filtering by user
+/// applies on top of either.
+fn get_default_filter(args: &BottomArgs, config: &Config) -> Option<String> {
+    let filter = if let Some(process) = &args.process.pid {
+        Some(watched_process_filter(process))
+    } else {
+        args.process
+            .filter_processes
+            .as_ref()
+            .or_else(|| {
+                config
+                    .processes
+                    .as_ref()
+                    .and_then(|cfg| cfg.default_filter.as_ref())
+            })
+            .filter(|filter| !filter.is_empty())
+            .cloned()
+    };
+
+    match (args.process.filter_user.as_deref(), filter) {
+        (Some(user), Some(filter)) => Some(format!("{} and ({filter})", user_filter(user))),
+        (Some(user), None) => Some(user_filter(user)),
+        (None, filter) => filter,
+    }
+}
+
+/// Returns how many processes are shown at most, if limited.
+fn get_process_top_n(args: &BottomArgs) -> OptionResult<Option<usize>> {
+    match args.process.top {
+        Some(0) => Err(OptionError::invalid_arg_value("top")),
+        top => Ok(top),
+    }
+}
+
+/// Returns the least usage processes need to be shown at first. Arguments
+/// take precedence over the config file.
+fn get_process_thresholds(args: &BottomArgs, config: &Config) -> OptionResult<ProcThresholds> {
+    let processes = config.processes.as_ref();
+    let threshold = |arg: Option<f64>, config: Option<f64>, name: &str| match (arg, config) {
+        (Some(threshold), _) if threshold >= 0.0 => Ok(Some(threshold)),
+        (Some(_), _) => Err(OptionError::invalid_arg_value(name)),
+        (None, Some(threshold)) if threshold >= 0.0 => Ok(Some(threshold)),
+        (None, Some(_)) => Err(OptionError::invalid_config_value(name)),
+        (None, None) => Ok(None),
+    };
+
+    Ok(ProcThresholds::new(
+        threshold(
+            args.process.threshold_cpu,
+            processes.and_then(|cfg| cfg.threshold_cpu),
+            "threshold_cpu",
+        )?,
+        threshold(
+            args.process.threshold_mem,
+            processes.and_then(|cfg| cfg.threshold_mem),
+            "threshold_mem",
+        )?,
+    ))
+}
+
+/// Returns the columns of the process widget, or [`None`] for the default ones.
+/// `--columns` overrides the config file.
+fn get_proc_columns(
+    args: &BottomArgs, config: &Config,
+) -> OptionResult<Option<IndexSet<ProcWidgetColumn>>> {
+    if let Some(columns) = &args.process.columns {
+        return column_preset::parse_column_list(columns).map_err(OptionError::arg);
+    }
+
+    Ok(config.processes.as_ref().and_then(|cfg| {
+        if cfg.columns.is_empty() {
+            None
+        } else {
+            // TODO: Should we be using an indexmap? Or maybe allow dupes.
+            Some(column_preset::widget_columns(&cfg.columns))
+        }
+    }))
+}
+
+/// Returns how `--once` prints processes, which is a table if not set.
+fn get_once_format(args: &BottomArgs) -> OptionResult<OnceFormat> {
+    match args.general.format.as_deref() {
+        None | Some("table") => Ok(OnceFormat::Table),
+        Some("json") => Ok(OnceFormat::Json),
+        Some(format) => Err(OptionError::arg(format!(
+            "--once can print a table or JSON, not '{format}'."
+        ))),
+    }
+}
+
+/// Returns the columns of the table printed by `--once`. Like the process
+/// widget's, `--columns` overrides the config file.
+fn get_once_columns(args: &BottomArgs, config: &Config) -> OptionResult<Vec<ProcColumn>> {
+    use itertools::Itertools;
+
+    let columns = match &args.process.columns {
+        Some(columns) => column_preset::parse_proc_columns(columns).map_err(OptionError::arg)?,
+        None => config
+            .processes
+            .as_ref()
+            .map(|cfg| cfg.columns.clone())
+            .filter(|columns| !columns.is_empty()),
+    };
+
+    Ok(columns
+        .unwrap_or_else(|| DEFAULT_COLUMNS.to_vec())
+        .into_iter()
+        .filter(|column| cfg!(target_os = "linux") || !column.is_linux_only())
+        .unique()
+        .collect())
+}
+
+/// Returns what `--once` prints. Its processes are sorted and limited like the
+/// process widget's, and CPU usage is shown the same way.
+pub(crate) fn get_once_settings(args: &BottomArgs, config: &Config) -> OptionResult<TableSettings> {
+    let (sort_by, sort_order) = get_once_sort(args, config)?;
+
+    Ok(TableSettings {
+        format: get_once_format(args)?,
+        columns: get_once_columns(args, config)?,
+        sort_by,
+        sort_order,
+        top_n: get_process_top_n(args)?,
+        is_cpu_per_core: is_flag_enabled!(unnormalized_cpu, args.process, config),
+    })
+}
+
+/// Returns the column and order to sort `--once` tables by, which is CPU
+/// usage from highest to lowest if not set.
+fn get_once_sort(args: &BottomArgs, config: &Config) -> OptionResult<(ProcColumn, SortOrder)> {
+    // The columns of a table don't have to include the one it's sorted by.
+    let (sort_by, sort_order) = get_process_sort(args, config, &None)?;
+    let sort_by = match sort_by {
+        Some(ProcWidgetColumn::PidOrCount) => ProcColumn::Pid,
+        Some(ProcWidgetColumn::ProcNameOrCommand) => ProcColumn::Name,
+        Some(ProcWidgetColumn::Mem) => ProcColumn::MemPercent,
+        Some(ProcWidgetColumn::ReadPerSecond) => ProcColumn::ReadPerSecond,
+        Some(ProcWidgetColumn::WritePerSecond) => ProcColumn::WritePerSecond,
+        Some(ProcWidgetColumn::User) => ProcColumn::User,
+        Some(ProcWidgetColumn::State) => ProcColumn::State,
+        Some(ProcWidgetColumn::Time) => ProcColumn::Time,
+        _ => ProcColumn::CpuPercent,
+    };
+
+    Ok((
+        sort_by,
+        sort_order.unwrap_or_else(|| default_sort_order(sort_by)),
+    ))
+}
+
+/// Returns the column and order to sort processes by at first, if set.
+fn get_process_sort(
+    args: &BottomArgs, config: &Config, proc_columns: &Option<IndexSet<ProcWidgetColumn>>,
+) -> OptionResult<(Option<ProcWidgetColumn>, Option<SortOrder>)> {
+    let processes = config.processes.as_ref();
+
+    let sort_by = if let Some(sort_by) = &args.process.sort_by {
+        Some(ProcWidgetColumn::from_sort_by(sort_by).map_err(OptionError::arg)?)
+    } else if let Some(sort_by) = processes.and_then(|cfg| cfg.sort_by.as_deref()) {
+        Some(ProcWidgetColumn::from_sort_by(sort_by).map_err(OptionError::config)?)
+    } else {
+        None
+    };
+
+    if let (Some(sort_by), Some(columns)) = (sort_by, proc_columns) {
+        if !columns.is_empty() && !columns.contains(&sort_by) {
+            return Err(OptionError::other(
+                "The column set to sort processes by must be one of the columns in the process widget.",
+            ));
+        }
+    }
+
+    let sort_order = if args.process.sort_asc {
+        Some(SortOrder::Ascending)
+    } else if args.process.sort_desc {
+        Some(SortOrder::Descending)
+    } else if let Some(direction) = processes.and_then(|cfg| cfg.sort_direction.as_deref()) {
+        match direction.to_ascii_lowercase().trim() {
+            "asc" | "ascending" => Some(SortOrder::Ascending),
+            "desc" | "descending" => Some(SortOrder::Descending),
+            _ => return Err(OptionError::invalid_config_value("sort_direction")),
+        }
+    } else {
+        None
+    };
+
+    Ok((sort_by, sort_order))
+}
+
 #[inline]
 fn get_default_time_value(
     args: &BottomArgs, config: &Config, retention_ms: u64,
@@ -768,6 +1690,10 @@ fn get_time_interval(args: &BottomArgs, config: &Config, retention_ms: u64) -> O
 fn get_default_widget_and_count(
     args: &BottomArgs, config: &Config,
 ) -> OptionResult<(Option<BottomWidgetType>, u64)> {
+    if args.process.pid.is_some() {
+        return Ok((Some(BottomWidgetType::Proc), 1));
+    }
+
     let widget_type = if let Some(widget_type) = &args.general.default_widget_type {
         let parsed_widget = parse_arg_value!(widget_type.parse(), "default_widget_type")?;
         if let BottomWidgetType::Empty = parsed_widget {
@@ -843,9 +1769,418 @@ fn get_use_battery(_args: &BottomArgs, _config: &Config) -> bool {
     false
 }
 
-#[cfg(feature = "gpu")]
-fn get_enable_gpu(args: &BottomArgs, config: &Config) -> bool {
-    if args.gpu.disable_gpu {
+/// Loads the baseline snapshot for diff mode, if one is set.
+fn get_diff_baseline(args: &BottomArgs, config: &Config) -> Result<Option<DiffBaseline>> {
+    let cfg = config.diff.as_ref();
+    let path = match &args.general.diff {
+        Some(path) => Some(path.clone()),
+        None => cfg
+            .and_then(|cfg| cfg.baseline_file.as_ref())
+            .map(PathBuf::from),
+    };
+
+    let Some(path) = path else {
+        return Ok(None);
+    };
+
+    let threshold = get_diff_threshold(config)?;
+    let data = read_snapshot(&path)?;
+    let time = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+
+    Ok(Some(DiffBaseline {
+        data,
+        time,
+        threshold,
+    }))
+}
+
+/// Gets how much values have to change by to be highlighted in diff mode.
+fn get_diff_threshold(config: &Config) -> OptionResult<f64> {
+    match config.diff.as_ref().and_then(|cfg| cfg.threshold) {
+        Some(threshold) if threshold >= 0.0 => Ok(threshold),
+        Some(_) => Err(OptionError::invalid_config_value("threshold")),
+        None => Ok(DEFAULT_DIFF_THRESHOLD),
+    }
+}
+
+/// Gets the settings for exporting to InfluxDB, if a URL is set. Arguments
+/// take precedence over the config file.
+pub(crate) fn get_influxdb_settings(
+    args: &BottomArgs, config: &Config,
+) -> OptionResult<Option<InfluxDbSettings>> {
+    let cfg = config.influxdb.as_ref();
+    let general = &args.general;
+
+    let Some(url) = general
+        .influxdb_url
+        .clone()
+        .or_else(|| cfg.and_then(|cfg| cfg.url.clone()))
+    else {
+        return Ok(None);
+    };
+
+    let token = general
+        .influxdb_token
+        .clone()
+        .or_else(|| cfg.and_then(|cfg| cfg.token.clone()));
+    let org = general
+        .influxdb_org
+        .clone()
+        .or_else(|| cfg.and_then(|cfg| cfg.org.clone()))
+        .ok_or_else(|| {
+            OptionError::other("an InfluxDB organization must be set to export to InfluxDB.")
+        })?;
+    let bucket = general
+        .influxdb_bucket
+        .clone()
+        .or_else(|| cfg.and_then(|cfg| cfg.bucket.clone()))
+        .ok_or_else(|| {
+            OptionError::other("an InfluxDB bucket must be set to export to InfluxDB.")
+        })?;
+
+    let batch_size = match (
+        general.influxdb_batch_size,
+        cfg.and_then(|cfg| cfg.batch_size),
+    ) {
+        (Some(0), _) => return Err(OptionError::invalid_arg_value("influxdb_batch_size")),
+        (None, Some(0)) => return Err(OptionError::invalid_config_value("batch_size")),
+        (Some(size), _) | (None, Some(size)) => size,
+        (None, None) => DEFAULT_INFLUXDB_BATCH_SIZE,
+    };
+
+    Ok(Some(InfluxDbSettings {
+        url,
+        token,
+        org,
+        bucket,
+        batch_size,
+    }))
+}
+
+/// Gets how much scroll momentum is kept each tick, or [`None`] if it's
+/// disabled.
+fn get_scroll_friction(args: &BottomArgs, config: &Config) -> OptionResult<Option<f32>> {
+    if is_flag_enabled!(no_scroll_momentum, args.general, config) {
+        return Ok(None);
+    }
+
+    match config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.scroll_friction)
+    {
+        Some(friction) if (0.0..1.0).contains(&friction) => Ok(Some(friction)),
+        Some(_) => Err(OptionError::invalid_config_value("scroll_friction")),
+        None => Ok(Some(DEFAULT_SCROLL_FRICTION)),
+    }
+}
+
+/// Gets how much usage has to change by for a process to be highlighted, if
+/// highlighting changes at all.
+fn get_change_thresholds(
+    args: &BottomArgs, config: &Config,
+) -> OptionResult<Option<ChangeThresholds>> {
+    if !is_flag_enabled!(highlight_changes, args.process, config) {
+        return Ok(None);
+    }
+
+    let threshold = |threshold: Option<f32>, name: &str| match threshold {
+        Some(threshold) if threshold >= 0.0 => Ok(threshold),
+        Some(_) => Err(OptionError::invalid_config_value(name)),
+        None => Ok(DEFAULT_CHANGE_THRESHOLD),
+    };
+    let processes = config.processes.as_ref();
+
+    Ok(Some(ChangeThresholds {
+        cpu: threshold(
+            processes.and_then(|cfg| cfg.cpu_change_threshold),
+            "cpu_change_threshold",
+        )?,
+        mem: threshold(
+            processes.and_then(|cfg| cfg.mem_change_threshold),
+            "mem_change_threshold",
+        )?,
+    }))
+}
+
+/// Gets how many updates sorting by change lasts for.
+fn get_change_sort_ticks(config: &Config) -> OptionResult<u64> {
+    match config
+        .processes
+        .as_ref()
+        .and_then(|cfg| cfg.change_sort_ticks)
+    {
+        Some(0) => Err(OptionError::invalid_config_value("change_sort_ticks")),
+        Some(ticks) => Ok(ticks),
+        None => Ok(DEFAULT_CHANGE_SORT_TICKS),
+    }
+}
+
+/// Gets which hooks to run, if any are set.
+pub(crate) fn get_hook_settings(
+    args: &BottomArgs, config: &Config,
+) -> OptionResult<Option<HookSettings>> {
+    let Some(cfg) = &config.hooks else {
+        return Ok(None);
+    };
+
+    let on_update: Vec<String> = cfg.on_update.clone().map(Into::into).unwrap_or_default();
+    let on_alert: Vec<String> = cfg.on_alert.clone().map(Into::into).unwrap_or_default();
+    if on_update.is_empty() && on_alert.is_empty() {
+        return Ok(None);
+    }
+
+    let timeout_ms = match (args.general.hook_timeout_ms, cfg.timeout_ms) {
+        (Some(0), _) => return Err(OptionError::invalid_arg_value("hook_timeout_ms")),
+        (None, Some(0)) => return Err(OptionError::invalid_config_value("timeout_ms")),
+        (Some(timeout_ms), _) | (None, Some(timeout_ms)) => timeout_ms,
+        (None, None) => DEFAULT_HOOK_TIMEOUT_MS,
+    };
+
+    Ok(Some(HookSettings {
+        on_update,
+        on_alert,
+        timeout: Duration::from_millis(timeout_ms),
+    }))
+}
+
+/// Gets how long the `--exec` command can run for.
+pub(crate) fn get_exec_timeout(args: &BottomArgs) -> OptionResult<Duration> {
+    match args.general.exec_timeout_ms {
+        Some(0) => Err(OptionError::invalid_arg_value("exec_timeout_ms")),
+        Some(timeout_ms) => Ok(Duration::from_millis(timeout_ms)),
+        None => Ok(Duration::from_millis(DEFAULT_EXEC_TIMEOUT_MS)),
+    }
+}
+
+/// Converts a size in megabytes to bytes, or [`None`] if it doesn't fit in a
+/// [`u64`].
+fn megabytes_to_bytes(megabytes: u64) -> Option<u64> {
+    megabytes.checked_mul(1024 * 1024)
+}
+
+/// Gets the maximum size of a recording made with `--record`, in bytes.
+pub(crate) fn get_record_max_size(args: &BottomArgs) -> OptionResult<u64> {
+    let max_size_mb = args
+        .general
+        .record_max_size
+        .unwrap_or(DEFAULT_RECORD_MAX_SIZE_MB);
+
+    megabytes_to_bytes(max_size_mb)
+        .ok_or_else(|| OptionError::arg("'--record_max_size' is too large"))
+}
+
+/// Gets the files to follow with `--watch_file`, each keeping as many lines as
+/// set with `--watch_file_lines`.
+fn get_log_tails(args: &BottomArgs) -> OptionResult<Vec<LogTailBuffer>> {
+    let max_lines = match args.general.watch_file_lines {
+        Some(0) => return Err(OptionError::invalid_arg_value("watch_file_lines")),
+        Some(max_lines) => max_lines,
+        None => DEFAULT_LOG_TAIL_LINES,
+    };
+
+    Ok(args
+        .general
+        .watch_file
+        .iter()
+        .map(|path| LogTailBuffer::new(path.clone(), max_lines))
+        .collect())
+}
+
+/// Gets what to check with `--check`, if it's set.
+pub(crate) fn get_check_settings(args: &BottomArgs) -> OptionResult<Option<CheckSettings>> {
+    let check = &args.check;
+    if !check.check {
+        return Ok(None);
+    }
+
+    let percent = |value: Option<f64>, flag: &str| match value {
+        Some(percent) if !(0.0..=100.0).contains(&percent) => {
+            Err(OptionError::invalid_arg_value(flag))
+        }
+        _ => Ok(value),
+    };
+
+    let timeout = match &check.check_timeout {
+        Some(timeout) => match parse_arg_value!(try_parse_ms(timeout), "check_timeout")? {
+            0 => return Err(OptionError::invalid_arg_value("check_timeout")),
+            ms => Duration::from_millis(ms),
+        },
+        None => DEFAULT_CHECK_TIMEOUT,
+    };
+
+    Ok(Some(CheckSettings {
+        cpu: Thresholds {
+            warn: percent(check.cpu_warn, "cpu_warn")?,
+            crit: percent(check.cpu_crit, "cpu_crit")?,
+        },
+        mem: Thresholds {
+            warn: percent(check.mem_warn, "mem_warn")?,
+            crit: percent(check.mem_crit, "mem_crit")?,
+        },
+        disk: Thresholds {
+            warn: percent(check.disk_warn, "disk_warn")?,
+            crit: percent(check.disk_crit, "disk_crit")?,
+        },
+        temp: Thresholds {
+            warn: check.temp_warn,
+            crit: check.temp_crit,
+        },
+        format: match check.check_format.as_deref() {
+            Some("nagios") => CheckFormat::Nagios,
+            _ => CheckFormat::Plain,
+        },
+        timeout,
+    }))
+}
+
+/// Gets the permissions of the IPC socket, given in octal like `0660`.
+#[cfg(unix)]
+pub(crate) fn get_ipc_socket_mode(args: &BottomArgs) -> OptionResult<u32> {
+    let Some(mode) = &args.general.ipc_socket_mode else {
+        return Ok(crate::export::ipc::DEFAULT_IPC_SOCKET_MODE);
+    };
+
+    let mode = mode.trim();
+    match u32::from_str_radix(mode.strip_prefix("0o").unwrap_or(mode), 8) {
+        Ok(mode) if mode <= 0o777 => Ok(mode),
+        _ => Err(OptionError::invalid_arg_value("ipc_socket_mode")),
+    }
+}
+
+/// Gets the hosts to monitor from `--hosts`, each with the refresh rate to
+/// ask it for if one was given after an `@`, e.g. `host1:4567@500`.
+pub(crate) fn get_hosts(args: &BottomArgs) -> OptionResult<Option<Vec<(String, Option<u64>)>>> {
+    let Some(hosts) = &args.general.hosts else {
+        return Ok(None);
+    };
+
+    hosts
+        .iter()
+        .map(|host| match host.rsplit_once('@') {
+            Some((address, rate)) => {
+                let rate = parse_arg_value!(try_parse_ms(rate.trim()), "hosts")?;
+                if rate < 250 {
+                    return Err(OptionError::arg(format!(
+                        "the refresh rate of '{address}' in '--hosts' must be greater than 250ms"
+                    )));
+                }
+
+                Ok((address.to_string(), Some(rate)))
+            }
+            None => Ok((host.clone(), None)),
+        })
+        .collect::<OptionResult<_>>()
+        .map(Some)
+}
+
+/// Gets the address the WebSocket server listens on, if it's enabled. This is
+/// localhost unless `--ws_bind` is set.
+pub(crate) fn get_ws_address(args: &BottomArgs) -> Option<SocketAddr> {
+    let general = &args.general;
+    general.ws_port.map(|port| {
+        let ip = general.ws_bind.unwrap_or(Ipv4Addr::LOCALHOST.into());
+        SocketAddr::new(ip, port)
+    })
+}
+
+/// Gets the TLS config for the WebSocket server, if a certificate was given.
+pub(crate) fn get_ws_tls_config(args: &BottomArgs) -> anyhow::Result<Option<Arc<ServerConfig>>> {
+    let general = &args.general;
+    match (&general.ws_tls_cert, &general.ws_tls_key) {
+        (Some(cert), Some(key)) => {
+            tls::server_config(cert, key, general.ws_tls_client_ca.as_deref()).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Gets the TLS config for connecting to remote hosts, if TLS was asked for
+/// with `--hosts_ca` or `--hosts_skip_verify`.
+pub(crate) fn get_hosts_tls_config(args: &BottomArgs) -> anyhow::Result<Option<Arc<ClientConfig>>> {
+    let general = &args.general;
+    let client_auth = match (&general.hosts_client_cert, &general.hosts_client_key) {
+        (Some(cert), Some(key)) => Some((cert.as_path(), key.as_path())),
+        _ => None,
+    };
+
+    if general.hosts_ca.is_none() && !general.hosts_skip_verify {
+        if client_auth.is_some() {
+            return Err(OptionError::arg(
+                "'--hosts_client_cert' requires '--hosts_ca' or '--hosts_skip_verify'",
+            )
+            .into());
+        }
+
+        return Ok(None);
+    }
+
+    tls::client_config(general.hosts_ca.as_deref(), client_auth).map(Some)
+}
+
+/// Gets the settings for the JSON log, if one was asked for.
+#[cfg(feature = "logging")]
+pub(crate) fn get_json_log_settings(args: &BottomArgs) -> OptionResult<Option<JsonLogSettings>> {
+    let general = &args.general;
+    let Some(path) = &general.json_log else {
+        return Ok(None);
+    };
+
+    let level = match &general.log_level {
+        Some(level) => level
+            .parse::<log::LevelFilter>()
+            .map_err(|_| OptionError::invalid_arg_value("log_level"))?,
+        None => log::LevelFilter::Info,
+    };
+    let max_size_mb = general.log_max_size.unwrap_or(DEFAULT_LOG_MAX_SIZE_MB);
+    if max_size_mb == 0 {
+        return Err(OptionError::arg("'--log_max_size' must be greater than 0"));
+    }
+
+    let max_size = megabytes_to_bytes(max_size_mb)
+        .ok_or_else(|| OptionError::arg("'--log_max_size' is too large"))?;
+
+    Ok(Some(JsonLogSettings {
+        path: path.clone(),
+        level,
+        max_size,
+        keep: general.log_keep.unwrap_or(DEFAULT_LOG_KEEP),
+    }))
+}
+
+/// Gets the playback speed for replay mode, e.g. `2x` or `0.5`.
+pub(crate) fn get_replay_speed(args: &BottomArgs) -> OptionResult<f64> {
+    let Some(speed) = &args.general.replay_speed else {
+        return Ok(1.0);
+    };
+
+    let speed = speed.trim();
+    let speed = speed
+        .strip_suffix(['x', 'X'])
+        .unwrap_or(speed)
+        .parse::<f64>();
+
+    match speed {
+        Ok(speed) if speed.is_finite() && speed > 0.0 => Ok(speed),
+        _ => Err(OptionError::invalid_arg_value("replay_speed")),
+    }
+}
+
+/// Returns the size set with `--override_terminal_size`, if any.
+pub(crate) fn get_terminal_size_override(args: &BottomArgs) -> OptionResult<Option<(u16, u16)>> {
+    match &args.general.override_terminal_size {
+        Some(size) => terminal::parse_size(size)
+            .map(Some)
+            .ok_or_else(|| OptionError::invalid_arg_value("override_terminal_size")),
+        None => Ok(None),
+    }
+}
+
+#[cfg(feature = "gpu")]
+fn get_enable_gpu(args: &BottomArgs, config: &Config) -> bool {
+    if args.gpu.disable_gpu {
         return false;
     }
 
@@ -1011,14 +2346,40 @@ fn get_memory_legend_position(
 mod test {
     use clap::Parser;
 
-    use super::{get_time_interval, Config};
+    use std::time::Duration;
+
+    use super::{
+        get_change_sort_ticks, get_change_thresholds, get_column_presets, get_default_filter,
+        get_diff_threshold, get_log_tails, get_once_columns, get_once_settings, get_proc_columns,
+        get_process_sort, get_process_thresholds, get_process_top_n, get_scroll_friction,
+        get_smooth_n, get_synthetic_settings, get_time_interval, get_widget_layout, parse_bit_rate,
+        without_uncollected_widgets, Config, Row,
+    };
     use crate::{
-        app::App,
+        app::{exec::DEFAULT_EXEC_TIMEOUT_MS, layout_manager::BottomWidgetType, App},
         args::BottomArgs,
+        canvas::components::data_table::SortOrder,
+        check::{CheckFormat, Thresholds},
+        data_collection::{synthetic::SyntheticSettings, CollectionFlags},
+        export::{
+            hooks::HookSettings, influxdb::InfluxDbSettings, record::DEFAULT_RECORD_MAX_SIZE_MB,
+        },
         options::{
-            config::flags::FlagConfig, get_default_time_value, get_retention, get_update_rate,
-            try_parse_ms,
+            config::{
+                diff::DiffConfig,
+                flags::FlagConfig,
+                hooks::{HookCommands, HooksConfig},
+                influxdb::InfluxDbConfig,
+                process::ProcessesConfig,
+                timing::TimingConfig,
+            },
+            get_check_settings, get_default_time_value, get_exec_timeout, get_hook_settings,
+            get_hosts, get_influxdb_settings, get_jitter_pct, get_record_max_size,
+            get_replay_speed, get_retention, get_subsystem_retry_count, get_terminal_size_override,
+            get_update_rate, try_parse_ms,
         },
+        output::table::{OnceFormat, DEFAULT_COLUMNS},
+        widgets::{ChangeThresholds, ColumnPreset, ProcColumn, ProcThresholds, ProcWidgetColumn},
     };
 
     #[test]
@@ -1090,6 +2451,906 @@ mod test {
         }
     }
 
+    #[test]
+    fn replay_speeds() {
+        let speed = |value: &str| {
+            let arg = format!("--replay_speed={value}");
+            get_replay_speed(&BottomArgs::parse_from(["btm", arg.as_str()]))
+        };
+
+        assert_eq!(get_replay_speed(&BottomArgs::parse_from(["btm"])), Ok(1.0));
+        assert_eq!(speed("2x"), Ok(2.0));
+        assert_eq!(speed("0.5"), Ok(0.5));
+        assert!(speed("0x").is_err());
+        assert!(speed("-1").is_err());
+        assert!(speed("fast").is_err());
+    }
+
+    #[test]
+    fn terminal_size_overrides() {
+        let size = |value: &str| {
+            let arg = format!("--override-terminal-size={value}");
+            get_terminal_size_override(&BottomArgs::parse_from(["btm", arg.as_str()]))
+        };
+
+        assert_eq!(
+            get_terminal_size_override(&BottomArgs::parse_from(["btm"])),
+            Ok(None)
+        );
+        assert_eq!(size("120x40"), Ok(Some((120, 40))));
+        assert!(size("120").is_err());
+        assert!(size("0x40").is_err());
+    }
+
+    #[test]
+    fn host_rates() {
+        let hosts = |value: &str| {
+            let arg = format!("--hosts={value}");
+            get_hosts(&BottomArgs::parse_from(["btm", arg.as_str()]))
+        };
+
+        assert_eq!(get_hosts(&BottomArgs::parse_from(["btm"])), Ok(None));
+        assert_eq!(
+            hosts("host1:4567@500,[::1]:4567,host2:4567@2s"),
+            Ok(Some(vec![
+                ("host1:4567".to_string(), Some(500)),
+                ("[::1]:4567".to_string(), None),
+                ("host2:4567".to_string(), Some(2000)),
+            ]))
+        );
+        assert!(hosts("host1:4567@100").is_err());
+        assert!(hosts("host1:4567@fast").is_err());
+    }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn json_log_settings() {
+        use super::get_json_log_settings;
+
+        let settings = |args: &[&str]| {
+            get_json_log_settings(&BottomArgs::parse_from(
+                ["btm", "--json_log=bottom.ndjson"].iter().chain(args),
+            ))
+        };
+
+        let defaults = settings(&[]).unwrap().unwrap();
+        assert_eq!(defaults.level, log::LevelFilter::Info);
+        assert_eq!(defaults.max_size, 100 * 1024 * 1024);
+        assert_eq!(defaults.keep, 7);
+
+        let custom = settings(&["--log_level=debug", "--log_max_size=5", "--log_keep=2"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(custom.level, log::LevelFilter::Debug);
+        assert_eq!(custom.max_size, 5 * 1024 * 1024);
+        assert_eq!(custom.keep, 2);
+
+        assert!(settings(&["--log_level=loud"]).is_err());
+        assert!(settings(&["--log_max_size=0"]).is_err());
+        assert_eq!(
+            get_json_log_settings(&BottomArgs::parse_from(["btm"])),
+            Ok(None)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ipc_socket_modes() {
+        use super::get_ipc_socket_mode;
+
+        let mode = |value: &str| {
+            let arg = format!("--ipc_socket_mode={value}");
+            get_ipc_socket_mode(&BottomArgs::parse_from([
+                "btm",
+                "--ipc_socket=/tmp/bottom.sock",
+                arg.as_str(),
+            ]))
+        };
+
+        assert_eq!(
+            get_ipc_socket_mode(&BottomArgs::parse_from(["btm"])),
+            Ok(0o600)
+        );
+        assert_eq!(mode("0660"), Ok(0o660));
+        assert_eq!(mode("0o644"), Ok(0o644));
+        assert_eq!(mode("777"), Ok(0o777));
+        assert!(mode("0800").is_err());
+        assert!(mode("1777").is_err());
+        assert!(mode("rw").is_err());
+    }
+
+    #[test]
+    fn influxdb_settings() {
+        let config = Config {
+            influxdb: Some(InfluxDbConfig {
+                url: Some("http://config:8086".to_string()),
+                org: Some("org".to_string()),
+                bucket: Some("bucket".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_influxdb_settings(&BottomArgs::parse_from(["btm"]), &Config::default()),
+            Ok(None)
+        );
+
+        let args = BottomArgs::parse_from([
+            "btm",
+            "--influxdb_url",
+            "http://args:8086",
+            "--influxdb_batch_size",
+            "10",
+        ]);
+        assert_eq!(
+            get_influxdb_settings(&args, &config),
+            Ok(Some(InfluxDbSettings {
+                url: "http://args:8086".to_string(),
+                token: None,
+                org: "org".to_string(),
+                bucket: "bucket".to_string(),
+                batch_size: 10,
+            }))
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--influxdb_url", "http://args:8086"]);
+        assert!(get_influxdb_settings(&args, &Config::default()).is_err());
+    }
+
+    #[test]
+    fn hook_settings() {
+        let config = Config {
+            hooks: Some(HooksConfig {
+                on_update: Some(HookCommands::One("update.sh".to_string())),
+                on_alert: Some(HookCommands::Many(vec![
+                    "alert.sh".to_string(),
+                    "notify.sh".to_string(),
+                ])),
+                timeout_ms: Some(1000),
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_hook_settings(&BottomArgs::parse_from(["btm"]), &Config::default()),
+            Ok(None)
+        );
+
+        assert_eq!(
+            get_hook_settings(&BottomArgs::parse_from(["btm"]), &config),
+            Ok(Some(HookSettings {
+                on_update: vec!["update.sh".to_string()],
+                on_alert: vec!["alert.sh".to_string(), "notify.sh".to_string()],
+                timeout: Duration::from_millis(1000),
+            }))
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--hook_timeout_ms", "200"]);
+        assert_eq!(
+            get_hook_settings(&args, &config).map(|settings| settings.unwrap().timeout),
+            Ok(Duration::from_millis(200))
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--hook_timeout_ms", "0"]);
+        assert!(get_hook_settings(&args, &config).is_err());
+    }
+
+    #[test]
+    fn exec_layout() {
+        let exec_widgets = |args: &[&str], config: &Config| {
+            let args = BottomArgs::parse_from(args);
+            let (mut layout, ..) = get_widget_layout(&args, config).unwrap();
+            layout
+                .widgets_mut()
+                .filter(|widget| widget.widget_type == BottomWidgetType::Exec)
+                .count()
+        };
+
+        assert_eq!(exec_widgets(&["btm"], &Config::default()), 0);
+        assert_eq!(
+            exec_widgets(&["btm", "--exec", "uptime"], &Config::default()),
+            1
+        );
+
+        // An exec widget in the layout is used rather than adding another, and
+        // is left empty without a command.
+        let config: Config = toml_edit::de::from_str(
+            "[[row]]\n[[row.child]]\ntype = \"cpu\"\n[[row.child]]\ntype = \"exec\"\n",
+        )
+        .unwrap();
+        assert_eq!(exec_widgets(&["btm", "--exec", "uptime"], &config), 1);
+        assert_eq!(exec_widgets(&["btm"], &config), 0);
+
+        assert_eq!(
+            get_exec_timeout(&BottomArgs::parse_from(["btm"])),
+            Ok(Duration::from_millis(DEFAULT_EXEC_TIMEOUT_MS))
+        );
+        let args = BottomArgs::parse_from(["btm", "--exec", "uptime", "--exec_timeout_ms", "0"]);
+        assert!(get_exec_timeout(&args).is_err());
+    }
+
+    #[test]
+    fn record_max_size() {
+        assert_eq!(
+            get_record_max_size(&BottomArgs::parse_from(["btm"])),
+            Ok(DEFAULT_RECORD_MAX_SIZE_MB * 1024 * 1024)
+        );
+        assert_eq!(
+            get_record_max_size(&BottomArgs::parse_from(["btm", "--record_max_size", "5"])),
+            Ok(5 * 1024 * 1024)
+        );
+
+        let too_large = (u64::MAX / 1024).to_string();
+        let args = BottomArgs::parse_from(["btm", "--record_max_size", &too_large]);
+        assert!(get_record_max_size(&args).is_err());
+    }
+
+    #[test]
+    fn nproc_layout() {
+        let user_proc_widgets = |args: &[&str], config: &Config| {
+            let args = BottomArgs::parse_from(args);
+            let (mut layout, ..) = get_widget_layout(&args, config).unwrap();
+            layout
+                .widgets_mut()
+                .filter(|widget| widget.widget_type == BottomWidgetType::UserProcs)
+                .count()
+        };
+
+        assert_eq!(user_proc_widgets(&["btm"], &Config::default()), 0);
+        assert_eq!(
+            user_proc_widgets(&["btm", "--nproc"], &Config::default()),
+            1
+        );
+
+        let config: Config = toml_edit::de::from_str(
+            "[[row]]\n[[row.child]]\ntype = \"cpu\"\n[[row.child]]\ntype = \"nproc\"\n",
+        )
+        .unwrap();
+        assert_eq!(user_proc_widgets(&["btm", "--nproc"], &config), 1);
+        assert_eq!(user_proc_widgets(&["btm"], &config), 1);
+
+        // Both an exec row and a user processes row are added.
+        let args = BottomArgs::parse_from(["btm", "--exec", "uptime", "--nproc"]);
+        let (layout, ..) = get_widget_layout(&args, &Config::default()).unwrap();
+        let last_rows = layout.rows[layout.rows.len() - 2..]
+            .iter()
+            .map(|row| row.children[0].children[0].children[0].widget_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            last_rows,
+            vec![BottomWidgetType::Exec, BottomWidgetType::UserProcs]
+        );
+    }
+
+    #[test]
+    fn skip_subsystems() {
+        let widgets = |args: &[&str]| {
+            let args = BottomArgs::parse_from(args);
+            let (mut layout, ..) = get_widget_layout(&args, &Config::default()).unwrap();
+            let mut widgets = layout
+                .widgets_mut()
+                .map(|widget| widget.widget_type.clone())
+                .filter(|widget_type| *widget_type != BottomWidgetType::CpuLegend)
+                .collect::<Vec<_>>();
+            widgets.dedup();
+            widgets
+        };
+
+        assert_eq!(
+            widgets(&["btm", "--no-proc", "--no-temp"]),
+            vec![
+                BottomWidgetType::Cpu,
+                BottomWidgetType::Mem,
+                BottomWidgetType::Disk,
+                BottomWidgetType::Net,
+            ]
+        );
+        assert!(!widgets(&["btm", "--no_net", "--no_disk"])
+            .iter()
+            .any(|widget_type| matches!(
+                widget_type,
+                BottomWidgetType::Net | BottomWidgetType::Disk
+            )));
+        assert!(!widgets(&["btm", "--no_proc", "--nproc"]).contains(&BottomWidgetType::UserProcs));
+
+        // Leaving out every widget of a column drops the column.
+        let rows: Vec<Row> = toml_edit::de::from_str::<Config>(
+            "[[row]]\n[[row.child]]\ntype = \"cpu\"\n[[row.child]]\n[[row.child.child]]\ntype = \"proc\"\n\
+            [[row.child.child]]\ntype = \"temp\"\n[[row]]\n[[row.child]]\ntype = \"proc\"\n",
+        )
+        .unwrap()
+        .row
+        .unwrap();
+        let rows =
+            without_uncollected_widgets(rows, CollectionFlags::all() - CollectionFlags::PROCESSES);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].child.as_ref().unwrap().len(), 2);
+
+        let rows = without_uncollected_widgets(rows, CollectionFlags::empty());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].child.as_ref().unwrap().len(), 1);
+
+        let args = BottomArgs::parse_from(["btm", "--no_proc"]);
+        let app = create_app(args);
+        assert!(!app.used_widgets.use_proc);
+        assert!(!app
+            .app_config_fields
+            .collection_flags
+            .contains(CollectionFlags::PROCESSES));
+    }
+
+    #[test]
+    fn watch_file() {
+        let log_tail_widgets = |args: &BottomArgs| {
+            let (mut layout, ..) = get_widget_layout(args, &Config::default()).unwrap();
+            layout
+                .widgets_mut()
+                .filter(|widget| widget.widget_type == BottomWidgetType::LogTail)
+                .count()
+        };
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(log_tail_widgets(&args), 0);
+        assert!(get_log_tails(&args).unwrap().is_empty());
+
+        let args = BottomArgs::parse_from([
+            "btm",
+            "--watch_file",
+            "/var/log/syslog",
+            "--watch_file",
+            "app.log",
+            "--watch_file_lines",
+            "50",
+        ]);
+        assert_eq!(log_tail_widgets(&args), 1);
+        let log_tails = get_log_tails(&args).unwrap();
+        assert_eq!(
+            log_tails
+                .iter()
+                .map(|log_tail| log_tail.name())
+                .collect::<Vec<_>>(),
+            vec!["syslog", "app.log"]
+        );
+
+        let args =
+            BottomArgs::parse_from(["btm", "--watch_file", "app.log", "--watch_file_lines", "0"]);
+        assert!(get_log_tails(&args).is_err());
+    }
+
+    #[test]
+    fn jitter_pct() {
+        let config = Config {
+            timing: Some(TimingConfig {
+                jitter_pct: Some(10),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_jitter_pct(&BottomArgs::parse_from(["btm"]), &Config::default()),
+            Ok(0)
+        );
+        assert_eq!(
+            get_jitter_pct(&BottomArgs::parse_from(["btm"]), &config),
+            Ok(10)
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--interval_jitter", "25"]);
+        assert_eq!(get_jitter_pct(&args, &config), Ok(25));
+
+        let args = BottomArgs::parse_from(["btm", "--interval-jitter", "101"]);
+        assert!(get_jitter_pct(&args, &config).is_err());
+
+        let config = Config {
+            timing: Some(TimingConfig {
+                jitter_pct: Some(200),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(get_jitter_pct(&BottomArgs::parse_from(["btm"]), &config).is_err());
+    }
+
+    #[test]
+    fn subsystem_retry_count() {
+        let config_with = |count| Config {
+            timing: Some(TimingConfig {
+                subsystem_retry_count: Some(count),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(get_subsystem_retry_count(&Config::default()), Ok(3));
+        assert_eq!(get_subsystem_retry_count(&config_with(5)), Ok(5));
+        assert!(get_subsystem_retry_count(&config_with(0)).is_err());
+    }
+
+    #[test]
+    fn check_settings() {
+        assert_eq!(
+            get_check_settings(&BottomArgs::parse_from(["btm"])),
+            Ok(None)
+        );
+
+        let args = BottomArgs::parse_from([
+            "btm",
+            "--check",
+            "--cpu-warn",
+            "80",
+            "--mem_crit",
+            "95",
+            "--check_format",
+            "nagios",
+            "--check_timeout",
+            "5s",
+        ]);
+        let settings = get_check_settings(&args).unwrap().unwrap();
+        assert_eq!(
+            settings.cpu,
+            Thresholds {
+                warn: Some(80.0),
+                crit: None
+            }
+        );
+        assert_eq!(settings.mem.crit, Some(95.0));
+        assert_eq!(settings.disk, Thresholds::default());
+        assert_eq!(settings.format, CheckFormat::Nagios);
+        assert_eq!(settings.timeout, Duration::from_secs(5));
+
+        let args = BottomArgs::parse_from(["btm", "--check", "--disk_warn", "101"]);
+        assert!(get_check_settings(&args).is_err());
+
+        let args = BottomArgs::parse_from(["btm", "--check", "--check_timeout", "0"]);
+        assert!(get_check_settings(&args).is_err());
+
+        assert!(BottomArgs::try_parse_from(["btm", "--cpu_warn", "80"]).is_err());
+    }
+
+    #[test]
+    fn default_filter() {
+        let config = Config {
+            processes: Some(ProcessesConfig {
+                default_filter: Some("nginx".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(get_default_filter(&args, &Config::default()), None);
+        assert_eq!(get_default_filter(&args, &config).as_deref(), Some("nginx"));
+
+        let args = BottomArgs::parse_from(["btm", "--filter_processes", "nginx|postgres"]);
+        assert_eq!(
+            get_default_filter(&args, &config).as_deref(),
+            Some("nginx|postgres")
+        );
+
+        // An empty filter is the same as none.
+        let args = BottomArgs::parse_from(["btm", "--filter_processes", ""]);
+        assert_eq!(get_default_filter(&args, &Config::default()), None);
+
+        // Watching a process replaces the configured filter.
+        let args = BottomArgs::parse_from(["btm", "--pid", "42"]);
+        assert_eq!(
+            get_default_filter(&args, &config).as_deref(),
+            Some("pid = ^42$")
+        );
+
+        // Filtering by user applies on top of any other filter.
+        let args = BottomArgs::parse_from(["btm", "--filter_user", "root"]);
+        assert_eq!(
+            get_default_filter(&args, &Config::default()).as_deref(),
+            Some("user = ^root$")
+        );
+        assert_eq!(
+            get_default_filter(&args, &config).as_deref(),
+            Some("user = ^root$ and (nginx)")
+        );
+    }
+
+    #[test]
+    fn column_presets() {
+        let config = toml_edit::de::from_str::<Config>(
+            r#"
+            [presets]
+            sysadmin = ["PID", "Name", "CPU%", "Mem%", "State"]
+            io = ["PID", "Name", "R/s", "W/s"]
+            "#,
+        )
+        .unwrap();
+        let saved = vec![
+            ColumnPreset::new("io", vec![ProcColumn::Pid]),
+            ColumnPreset::new("preset 3", vec![ProcColumn::Name]),
+        ];
+
+        let presets = get_column_presets(&config, saved);
+        assert_eq!(
+            presets.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(),
+            vec!["sysadmin", "io", "preset 3"]
+        );
+        assert_eq!(
+            presets[1].columns,
+            vec![
+                ProcColumn::Pid,
+                ProcColumn::Name,
+                ProcColumn::ReadPerSecond,
+                ProcColumn::WritePerSecond
+            ]
+        );
+
+        assert!(get_column_presets(&Config::default(), vec![]).is_empty());
+        assert!(toml_edit::de::from_str::<Config>("[presets]\nbad = [\"FDs\"]").is_err());
+    }
+
+    #[test]
+    fn process_sort() {
+        let config = |sort_by: &str, sort_direction: &str| Config {
+            processes: Some(ProcessesConfig {
+                sort_by: Some(sort_by.to_string()),
+                sort_direction: Some(sort_direction.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let args = BottomArgs::parse_from(["btm"]);
+
+        assert_eq!(
+            get_process_sort(&args, &Config::default(), &None),
+            Ok((None, None))
+        );
+        assert_eq!(
+            get_process_sort(&args, &config("mem", "asc"), &None),
+            Ok((Some(ProcWidgetColumn::Mem), Some(SortOrder::Ascending)))
+        );
+        assert!(get_process_sort(&args, &config("nice", "asc"), &None).is_err());
+        assert!(get_process_sort(&args, &config("mem", "up"), &None).is_err());
+
+        // Arguments take precedence over the config file.
+        let args = BottomArgs::parse_from(["btm", "--sort_by", "pid", "--sort_desc"]);
+        assert_eq!(
+            get_process_sort(&args, &config("mem", "asc"), &None),
+            Ok((
+                Some(ProcWidgetColumn::PidOrCount),
+                Some(SortOrder::Descending)
+            ))
+        );
+
+        // The column has to be shown.
+        let columns = Some([ProcWidgetColumn::Cpu].into_iter().collect());
+        assert!(get_process_sort(&args, &Config::default(), &columns).is_err());
+
+        assert!(BottomArgs::try_parse_from(["btm", "--sort_asc", "--sort_desc"]).is_err());
+    }
+
+    #[test]
+    fn process_columns() {
+        let config = Config {
+            processes: Some(ProcessesConfig {
+                columns: vec![ProcColumn::Pid, ProcColumn::State],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let columns = |args: &[&str]| {
+            get_proc_columns(&BottomArgs::parse_from(args), &config)
+                .map(|columns| columns.map(|columns| columns.into_iter().collect::<Vec<_>>()))
+        };
+
+        assert_eq!(
+            columns(&["btm"]),
+            Ok(Some(vec![
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::State
+            ]))
+        );
+
+        // Arguments take precedence over the config file.
+        assert_eq!(
+            columns(&["btm", "--columns", "name,cpu"]),
+            Ok(Some(vec![
+                ProcWidgetColumn::ProcNameOrCommand,
+                ProcWidgetColumn::Cpu
+            ]))
+        );
+        assert_eq!(columns(&["btm", "--columns", "default"]), Ok(None));
+        assert!(columns(&["btm", "--columns", "pid,nice"]).is_err());
+    }
+
+    #[test]
+    fn once_columns() {
+        let config = Config {
+            processes: Some(ProcessesConfig {
+                columns: vec![ProcColumn::Pid, ProcColumn::State],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let columns = |args: &[&str], config: &Config| {
+            get_once_columns(&BottomArgs::parse_from(args), config)
+        };
+
+        assert_eq!(
+            columns(&["btm", "--once"], &config),
+            Ok(vec![ProcColumn::Pid, ProcColumn::State])
+        );
+        assert_eq!(
+            columns(&["btm", "--once", "--format", "table"], &Config::default()),
+            Ok(DEFAULT_COLUMNS.to_vec())
+        );
+
+        // Unlike in the widget, names and commands can be shown together.
+        assert_eq!(
+            columns(
+                &["btm", "--once", "--columns", "name,command,name"],
+                &config
+            ),
+            Ok(vec![ProcColumn::Name, ProcColumn::Command])
+        );
+
+        assert!(BottomArgs::try_parse_from(["btm", "--format", "table"]).is_err());
+        assert!(BottomArgs::try_parse_from(["btm", "--once", "--generate_config"]).is_err());
+    }
+
+    #[test]
+    fn once_settings() {
+        let settings = |args: &[&str], config: &str| {
+            get_once_settings(
+                &BottomArgs::parse_from(args),
+                &toml_edit::de::from_str(config).unwrap(),
+            )
+            .unwrap()
+        };
+
+        let default = settings(&["btm", "--once"], "");
+        assert_eq!(
+            (default.sort_by, default.sort_order, default.top_n),
+            (ProcColumn::CpuPercent, SortOrder::Descending, None)
+        );
+        assert!(!default.is_cpu_per_core);
+
+        let set = settings(
+            &[
+                "btm",
+                "--once",
+                "--top",
+                "3",
+                "--sort_by",
+                "pid",
+                "--sort_desc",
+                "-n",
+            ],
+            "",
+        );
+        assert_eq!(
+            (set.sort_by, set.sort_order, set.top_n),
+            (ProcColumn::Pid, SortOrder::Descending, Some(3))
+        );
+        assert!(set.is_cpu_per_core);
+
+        // Each column is sorted in the same order as in the widget if not set.
+        let from_config = settings(
+            &["btm", "--once"],
+            "[flags]\nunnormalized_cpu = true\n[processes]\nsort_by = \"name\"\n",
+        );
+        assert_eq!(
+            (from_config.sort_by, from_config.sort_order),
+            (ProcColumn::Name, SortOrder::Ascending)
+        );
+        assert!(from_config.is_cpu_per_core);
+
+        assert_eq!(default.format, OnceFormat::Table);
+        assert_eq!(
+            settings(&["btm", "--once", "--format", "json"], "").format,
+            OnceFormat::Json
+        );
+        assert!(get_once_settings(
+            &BottomArgs::parse_from(["btm", "--once", "--format", "toml"]),
+            &Config::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn process_thresholds() {
+        let config = |cpu: f64| Config {
+            processes: Some(ProcessesConfig {
+                threshold_cpu: Some(cpu),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let args = BottomArgs::parse_from(["btm"]);
+
+        assert_eq!(
+            get_process_thresholds(&args, &Config::default()),
+            Ok(ProcThresholds::default())
+        );
+        assert_eq!(
+            get_process_thresholds(&args, &config(1.5)),
+            Ok(ProcThresholds::new(Some(1.5), None))
+        );
+        assert_eq!(
+            get_process_thresholds(&args, &config(0.0)),
+            Ok(ProcThresholds::default())
+        );
+        assert!(get_process_thresholds(&args, &config(-1.0)).is_err());
+
+        // Arguments take precedence over the config file.
+        let args =
+            BottomArgs::parse_from(["btm", "--threshold_cpu", "2", "--threshold-mem", "0.5"]);
+        assert_eq!(
+            get_process_thresholds(&args, &config(1.5)),
+            Ok(ProcThresholds::new(Some(2.0), Some(0.5)))
+        );
+    }
+
+    #[test]
+    fn process_top_n() {
+        assert_eq!(
+            get_process_top_n(&BottomArgs::parse_from(["btm"])),
+            Ok(None)
+        );
+        assert_eq!(
+            get_process_top_n(&BottomArgs::parse_from(["btm", "--top", "10"])),
+            Ok(Some(10))
+        );
+        assert!(get_process_top_n(&BottomArgs::parse_from(["btm", "--top", "0"])).is_err());
+    }
+
+    #[test]
+    fn normalize_cpu_aliases() {
+        let unnormalized = |args: &[&str], config: &str| {
+            let args = BottomArgs::parse_from(args);
+            let config: Config = toml_edit::de::from_str(config).unwrap();
+            is_flag_enabled!(unnormalized_cpu, args.process, config)
+        };
+
+        assert!(!unnormalized(&["btm"], ""));
+        assert!(unnormalized(&["btm", "--normalize_cpu"], ""));
+        assert!(unnormalized(&["btm", "--normalize-cpu"], ""));
+        assert!(unnormalized(
+            &["btm"],
+            "[flags]\nnormalize_cpu_pct = true\n"
+        ));
+        assert!(unnormalized(&["btm"], "[flags]\nunnormalized_cpu = true\n"));
+    }
+
+    #[test]
+    fn scroll_friction() {
+        let config = |friction: f32| Config {
+            flags: Some(FlagConfig {
+                scroll_friction: Some(friction),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let args = BottomArgs::parse_from(["btm"]);
+
+        assert_eq!(
+            get_scroll_friction(&args, &Config::default()),
+            Ok(Some(0.8))
+        );
+        assert_eq!(get_scroll_friction(&args, &config(0.5)), Ok(Some(0.5)));
+        assert!(get_scroll_friction(&args, &config(1.0)).is_err());
+        assert!(get_scroll_friction(&args, &config(-0.1)).is_err());
+
+        let args = BottomArgs::parse_from(["btm", "--no_scroll_momentum"]);
+        assert_eq!(get_scroll_friction(&args, &config(0.5)), Ok(None));
+    }
+
+    #[test]
+    fn change_thresholds() {
+        let config = |cpu: Option<f32>, mem: Option<f32>| Config {
+            processes: Some(ProcessesConfig {
+                cpu_change_threshold: cpu,
+                mem_change_threshold: mem,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let args = BottomArgs::parse_from(["btm"]);
+        assert_eq!(
+            get_change_thresholds(&args, &config(Some(1.0), None)),
+            Ok(None)
+        );
+
+        let args = BottomArgs::parse_from(["btm", "--highlight_changes"]);
+        assert_eq!(
+            get_change_thresholds(&args, &Config::default()),
+            Ok(Some(ChangeThresholds { cpu: 5.0, mem: 5.0 }))
+        );
+        assert_eq!(
+            get_change_thresholds(&args, &config(Some(10.0), Some(0.0))),
+            Ok(Some(ChangeThresholds {
+                cpu: 10.0,
+                mem: 0.0
+            }))
+        );
+        assert!(get_change_thresholds(&args, &config(None, Some(-1.0))).is_err());
+    }
+
+    #[test]
+    fn change_sort_ticks() {
+        let config = |ticks: Option<u64>| Config {
+            processes: Some(ProcessesConfig {
+                change_sort_ticks: ticks,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(get_change_sort_ticks(&config(None)), Ok(5));
+        assert_eq!(get_change_sort_ticks(&config(Some(10))), Ok(10));
+        assert!(get_change_sort_ticks(&config(Some(0))).is_err());
+    }
+
+    #[test]
+    fn bit_rate() {
+        assert_eq!(parse_bit_rate("100mb"), Ok(100_000_000));
+        assert_eq!(parse_bit_rate("1.5Gbit"), Ok(1_500_000_000));
+        assert_eq!(parse_bit_rate("20kb/s"), Ok(20_000));
+        assert_eq!(parse_bit_rate("800"), Ok(800));
+        assert!(parse_bit_rate("fast").is_err());
+        assert!(parse_bit_rate("-1m").is_err());
+        assert!(parse_bit_rate("").is_err());
+    }
+
+    #[test]
+    fn synthetic_settings() {
+        let settings = |args: &[&str]| {
+            get_synthetic_settings(&BottomArgs::parse_from(["btm"].iter().chain(args).copied()))
+        };
+
+        assert_eq!(settings(&[]), Ok(None));
+        assert_eq!(
+            settings(&["--simulate-cpu", "95", "--simulate_net", "100mb"]),
+            Ok(Some(SyntheticSettings {
+                cpu_percent: 95.0,
+                mem_percent: SyntheticSettings::DEFAULT_MEM_PERCENT,
+                net_bits_per_sec: 100_000_000,
+                period: None,
+                num_processes: SyntheticSettings::DEFAULT_NUM_PROCESSES,
+            }))
+        );
+        assert_eq!(
+            settings(&["--simulate_period", "10s"]).map(|s| s.and_then(|s| s.period)),
+            Ok(Some(Duration::from_secs(10)))
+        );
+        assert!(settings(&["--simulate_mem", "101"]).is_err());
+        assert!(settings(&["--simulate_period", "0"]).is_err());
+        assert!(settings(&["--simulate_net", "lots"]).is_err());
+    }
+
+    #[test]
+    fn diff_threshold() {
+        let config = |threshold: f64| Config {
+            diff: Some(DiffConfig {
+                threshold: Some(threshold),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(get_diff_threshold(&Config::default()), Ok(5.0));
+        assert_eq!(get_diff_threshold(&config(10.0)), Ok(10.0));
+        assert_eq!(get_diff_threshold(&config(0.0)), Ok(0.0));
+        assert!(get_diff_threshold(&config(-1.0)).is_err());
+    }
+
+    #[test]
+    fn smooth_n() {
+        assert_eq!(get_smooth_n(None, None, "cpu_avg_n"), Ok(1));
+        assert_eq!(get_smooth_n(None, Some(4), "cpu_avg_n"), Ok(4));
+        assert_eq!(get_smooth_n(Some(3), Some(4), "cpu_avg_n"), Ok(3));
+        assert!(get_smooth_n(Some(0), None, "cpu_avg_n").is_err());
+        assert!(get_smooth_n(None, Some(0), "cpu_avg_n").is_err());
+    }
+
     #[test]
     fn config_human_times() {
         let args = BottomArgs::parse_from(["btm"]);
@@ -1180,6 +3441,37 @@ mod test {
         assert_eq!(get_retention(&args, &config), Ok(600000));
     }
 
+    #[test]
+    fn minimal_layout() {
+        let args = BottomArgs::parse_from(["btm", "--minimal"]);
+        let (app, layout, _) = super::init_app(args, Config::default()).unwrap();
+
+        // The initial layout only has the CPU, memory, and process widgets.
+        let widgets = layout
+            .rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+            .map(|widget| widget.widget_type.clone())
+            .collect::<Vec<_>>();
+        assert!(widgets.contains(&BottomWidgetType::Cpu));
+        assert!(widgets.contains(&BottomWidgetType::Mem));
+        assert!(widgets.contains(&BottomWidgetType::Proc));
+        assert!(!widgets.contains(&BottomWidgetType::Disk));
+        assert!(!widgets.contains(&BottomWidgetType::Temp));
+        assert!(!widgets.contains(&BottomWidgetType::Net));
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Proc);
+
+        // The full layout's widgets are kept around to switch to.
+        assert!(app.alternate_layout.is_some());
+        assert_eq!(app.states.proc_state.widget_states.len(), 2);
+        assert!(app.used_widgets.use_disk);
+
+        let basic = create_app(BottomArgs::parse_from(["btm", "--minimal", "--basic"]));
+        assert!(basic.alternate_layout.is_none());
+    }
+
     fn create_app(args: BottomArgs) -> App {
         let config = Config::default();
         super::init_app(args, config).unwrap().0
@@ -1195,8 +3487,37 @@ mod test {
         let default_app = create_app(BottomArgs::parse_from(["btm"]));
 
         // Skip battery since it's tricky to test depending on the platform/features
-        // we're testing with.
-        let skip = ["help", "version", "celsius", "battery", "generate_schema"];
+        // we're testing with. Sorting by CPU usage is descending by default, so
+        // --sort_desc doesn't change anything by itself. --no_color and
+        // --no_unicode_borders only change how things are drawn, which is
+        // tested separately. --daemon, --hosts_skip_verify, --bench, --generate_config, --check,
+        // --no_system_config, --system, --top_summary, --raw, and --backtrace don't
+        // affect the app itself. --nproc only adds to the layout, which is tested separately.
+        // --i_understand only allows --disk_bench, and --version_check and --once don't start the app.
+        let skip = [
+            "help",
+            "version",
+            "celsius",
+            "battery",
+            "generate_schema",
+            "sort_desc",
+            "no_color",
+            "no_unicode_borders",
+            "daemon",
+            "hosts_skip_verify",
+            "bench",
+            "generate_config",
+            "check",
+            "no_system_config",
+            "system",
+            "top_summary",
+            "raw",
+            "i_understand",
+            "version_check",
+            "once",
+            "backtrace",
+            "nproc",
+        ];
 
         for arg in app.get_arguments().collect::<Vec<_>>() {
             let arg_name = arg
@@ -1215,6 +3536,8 @@ mod test {
 
                 if (default_app.app_config_fields == testing_app.app_config_fields)
                     && default_app.is_expanded == testing_app.is_expanded
+                    && default_app.alternate_layout.is_some()
+                        == testing_app.alternate_layout.is_some()
                     && default_app
                         .states
                         .proc_state
@@ -1229,6 +3552,109 @@ mod test {
         }
     }
 
+    /// The names of the fields of a struct, read from its [`serde::Deserialize`]
+    /// implementation.
+    fn field_names<T: serde::de::DeserializeOwned>() -> &'static [&'static str] {
+        struct FieldNames(&'static [&'static str]);
+
+        impl<'de> serde::Deserializer<'de> for &mut FieldNames {
+            type Error = serde::de::value::Error;
+
+            fn deserialize_any<V: serde::de::Visitor<'de>>(
+                self, _visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                Err(serde::de::Error::custom("not a struct"))
+            }
+
+            fn deserialize_struct<V: serde::de::Visitor<'de>>(
+                self, _name: &'static str, fields: &'static [&'static str], _visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                self.0 = fields;
+                Err(serde::de::Error::custom("only reading the field names"))
+            }
+
+            serde::forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+                bytes byte_buf option unit unit_struct newtype_struct seq tuple
+                tuple_struct map enum identifier ignored_any
+            }
+        }
+
+        let mut names = FieldNames(&[]);
+        let _ = T::deserialize(&mut names);
+        names.0
+    }
+
+    /// Every setting should be in the generated config, either set to its
+    /// default or as an example, so that new settings aren't missed.
+    #[test]
+    fn generated_config_has_every_setting() {
+        use crate::options::config::{
+            cpu::CpuConfig, disk::DiskConfig, network::NetworkConfig, style::StyleConfig,
+            temperature::TempConfig, IgnoreList,
+        };
+
+        // Settings that aren't read anywhere yet.
+        const UNUSED: [(&str, &str); 1] = [("flags", "no_write")];
+        // Older names that are still read, but not suggested anymore.
+        const ALIASES: [(&str, &str); 1] = [("flags", "normalize_cpu_pct")];
+
+        let config = super::generated_config();
+
+        // Each setting, along with the table it's in.
+        let mut settings = hashbrown::HashSet::new();
+        let mut table = "";
+        for line in config.lines() {
+            let line = line.trim_start_matches('#').trim();
+
+            if line.starts_with('[') {
+                table = line
+                    .trim_start_matches('[')
+                    .split(']')
+                    .next()
+                    .unwrap_or_default();
+                let (parent, name) = table.rsplit_once('.').unwrap_or(("", table));
+                settings.insert((parent, name));
+            } else if let Some((key, _value)) = line.split_once('=') {
+                let key = key.trim();
+                if key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                {
+                    settings.insert((table, key));
+                }
+            }
+        }
+
+        let tables = [
+            ("", field_names::<Config>()),
+            ("flags", field_names::<FlagConfig>()),
+            ("styles", field_names::<StyleConfig>()),
+            ("processes", field_names::<ProcessesConfig>()),
+            ("cpu", field_names::<CpuConfig>()),
+            ("disk", field_names::<DiskConfig>()),
+            ("disk.name_filter", field_names::<IgnoreList>()),
+            ("temperature", field_names::<TempConfig>()),
+            ("network", field_names::<NetworkConfig>()),
+            ("diff", field_names::<DiffConfig>()),
+            ("influxdb", field_names::<InfluxDbConfig>()),
+            ("hooks", field_names::<HooksConfig>()),
+        ];
+
+        for (table, fields) in tables {
+            assert!(!fields.is_empty(), "{table} should have fields");
+
+            for field in fields {
+                assert!(
+                    settings.contains(&(table, *field))
+                        || UNUSED.contains(&(table, *field))
+                        || ALIASES.contains(&(table, *field)),
+                    "'{field}' in [{table}] should be in the default config"
+                );
+            }
+        }
+    }
+
     /// This one has slightly more complex behaviour due to `dirs` not respecting XDG on macOS, so we manually
     /// handle it. However, to ensure backwards-compatibility, we also have to do some special cases.
     #[cfg(target_os = "macos")]
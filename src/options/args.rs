@@ -5,7 +5,7 @@
 
 // TODO: New sections are misaligned! See if we can get that fixed.
 
-use std::path::PathBuf;
+use std::{net::IpAddr, path::PathBuf};
 
 use clap::{builder::PossibleValue, *};
 use indoc::indoc;
@@ -41,7 +41,7 @@ const CHART_WIDGET_POSITIONS: [&str; 9] = [
 ];
 
 /// Represents the arguments that can be passed in to bottom.
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(
     name = crate_name!(),
     version = VERSION,
@@ -83,6 +83,12 @@ pub struct BottomArgs {
     #[command(flatten)]
     pub style: StyleArgs,
 
+    #[command(flatten)]
+    pub check: CheckArgs,
+
+    #[command(flatten)]
+    pub simulate: SimulateArgs,
+
     #[command(flatten)]
     pub other: OtherArgs,
 }
@@ -91,6 +97,17 @@ pub struct BottomArgs {
 #[derive(Args, Clone, Debug)]
 #[command(next_help_heading = "General Options", rename_all = "snake_case")]
 pub struct GeneralArgs {
+    #[arg(
+        long,
+        alias = "absolute-timestamps",
+        action = ArgAction::SetTrue,
+        help = "Labels graph time scales with wall-clock times.",
+        long_help = "Labels the time scale of graphs with wall-clock times in UTC, rather than how long ago each \
+                point was. Dates are included once the shown time range is longer than a day. Replays always use \
+                wall-clock times if the recording has them."
+    )]
+    pub absolute_timestamps: bool,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -100,6 +117,16 @@ pub struct GeneralArgs {
     )]
     pub autohide_time: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Includes a backtrace if bottom crashes.",
+        long_help = "Includes a full backtrace in the message printed if bottom crashes, even in release builds. \
+                    Debug builds always include one, as does setting RUST_BACKTRACE. Release builds have their \
+                    symbols stripped, so function names may be missing."
+    )]
+    pub backtrace: bool,
+
     #[arg(
         short = 'b',
         long,
@@ -109,6 +136,17 @@ pub struct GeneralArgs {
     )]
     pub basic: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["daemon", "export", "hosts", "replay"],
+        help = "Times data collection and exits.",
+        long_help = "Collects everything for 20 ticks without starting the interface, and prints how long each tick \
+                    took to collect along with the mean, fastest, and slowest. Useful for comparing collection \
+                    performance between builds or systems."
+    )]
+    pub bench: bool,
+
     #[arg(
         short = 'C',
         long,
@@ -121,6 +159,19 @@ pub struct GeneralArgs {
     )]
     pub config_location: Option<PathBuf>,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["export", "hosts", "replay"],
+        help = "Runs in the background, streaming data as JSON.",
+        long_help = "Detaches from the terminal and keeps collecting data in the background without the interface. \
+                    Each update is written to stderr as a line of JSON, and sent to any exporters that are set up \
+                    (e.g. --influxdb_url, --ws_port, or --ipc_socket). Sending SIGHUP reloads the config file, and \
+                    SIGTERM sends any queued exports before exiting. See --log_file and --pid_file. Only supported \
+                    on Unix-like systems."
+    )]
+    pub daemon: bool,
+
     #[arg(
         short = 't',
         long,
@@ -192,6 +243,17 @@ pub struct GeneralArgs {
     )]
     pub default_widget_type: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Compares current data against a snapshot file.",
+        long_help = "Compares current data against a JSON snapshot file, such as one created with --export. \
+                    Widgets will show the difference from the snapshot's values, processes that only exist in \
+                    the snapshot are shown as disabled, and new processes are highlighted."
+    )]
+    pub diff: Option<PathBuf>,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -200,6 +262,21 @@ pub struct GeneralArgs {
     )]
     pub disable_click: bool,
 
+    #[arg(
+        long,
+        alias = "disk-bench",
+        value_name = "DEVICE",
+        requires = "i_understand",
+        conflicts_with_all = ["bench", "check", "daemon", "export", "hosts", "no_disk", "raw", "replay", "top_summary"],
+        help = "Benchmarks how fast a disk can be read. BENCHMARK MODE - DO NOT USE ON PRODUCTION SYSTEMS.",
+        long_help = "Reads the given device, like /dev/nvme0n1, sequentially for 5 seconds in the background, \
+                    and shows the read throughput in the disk widget and then in an overlay. The device is only \
+                    read, never written to, and reads bypass the page cache with O_DIRECT where it's available. \
+                    Reading a whole disk as fast as it can be read slows down everything else using it, so this \
+                    must not be used on production systems, and needs --i_understand to run."
+    )]
+    pub disk_bench: Option<String>,
+
     // TODO: Change this to accept a string with the type of marker.
     #[arg(
         short = 'm',
@@ -210,6 +287,29 @@ pub struct GeneralArgs {
     )]
     pub dot_marker: bool,
 
+    #[arg(
+        long,
+        value_name = "COMMAND",
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Runs a command on each update and shows its output.",
+        long_help = "Runs a shell command on each update, like `watch`, and shows what it printed in an exec \
+                    widget, with stderr in red below stdout. The title shows the exit code and when the command \
+                    last finished. An exec row is added to the bottom of the layout unless it already has an \
+                    'exec' widget. A run is skipped if the last one hasn't finished yet. See --exec_timeout_ms."
+    )]
+    pub exec: Option<String>,
+
+    #[arg(
+        long,
+        alias = "exec-timeout-ms",
+        value_name = "MS",
+        requires = "exec",
+        help = "How long --exec can run before it's killed.",
+        long_help = "How long in milliseconds the command set with --exec can run for before it's killed. \
+                    Defaults to 2000."
+    )]
+    pub exec_timeout_ms: Option<u64>,
+
     #[arg(
         short = 'e',
         long,
@@ -219,12 +319,505 @@ pub struct GeneralArgs {
     )]
     pub expanded: bool,
 
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Writes a snapshot of current data to a file and exits.",
+        long_help = "Collects a single snapshot of current data and writes it to the given path as JSON, exiting \
+                    without starting the interface. The snapshot can later be used with --diff."
+    )]
+    pub export: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "export-on-exit",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        conflicts_with = "export",
+        help = "Writes a snapshot when the interface exits.",
+        long_help = "Collects a single snapshot of current data and writes it to the given path as JSON after \
+                    quitting the interface, in the same format as --export. Nothing is written if bottom exits \
+                    because of an error."
+    )]
+    pub export_on_exit: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "export-once-on-start",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        conflicts_with = "export",
+        help = "Writes a snapshot before starting the interface.",
+        long_help = "Collects a single snapshot of current data and writes it to the given path as JSON, in the \
+                    same format as --export, then starts the interface as usual. If the snapshot can't be written, \
+                    the interface still starts and the error is added to the event log, but bottom exits with a \
+                    non-zero code when quit."
+    )]
+    pub export_once_on_start: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        requires = "formatted_output",
+        value_parser = ["toml", "json", "table"],
+        help = "Sets the format used by --generate_config or --once.",
+        long_help = "The format of the config file printed by --generate_config, either 'toml' (default) for a \
+                    config file, or 'json' for the JSON schema of the config file, which editors can use to check \
+                    and complete config files. With --once, either 'table' (default) for an aligned table, or 'json' for a \
+                    JSON array of the processes with every field they have in --export snapshots."
+    )]
+    pub format: Option<String>,
+
+    #[arg(
+        long,
+        alias = "freeze-on-alert",
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Freezes the display when usage crosses a threshold.",
+        long_help = "Automatically freezes the display when an alert is raised, such as CPU, disk, or temperature \
+                    usage crossing a threshold, and shows what triggered it. Data is still collected in the \
+                    background. Whether it stays frozen until manually unfrozen or only while usage is over the \
+                    threshold can be set with 'freeze_on_alert_mode' in the config file."
+    )]
+    pub freeze_on_alert: bool,
+
+    #[arg(
+        long,
+        alias = "generate-config",
+        action = ArgAction::SetTrue,
+        group = "formatted_output",
+        help = "Prints a fully commented default config and exits.",
+        long_help = "Prints a config file to stdout with every setting set to its default, along with comments \
+                    explaining each one, and exits. Settings without a default, like filters, are included as \
+                    commented out examples. See --format to print the JSON schema of the config file instead."
+    )]
+    pub generate_config: bool,
+
     #[arg(long, action = ArgAction::SetTrue, help = "Hides spacing between table headers and entries.")]
     pub hide_table_gap: bool,
 
     #[arg(long, action = ArgAction::SetTrue, help = "Hides the time scale from being shown.")]
     pub hide_time: bool,
 
+    #[arg(
+        long,
+        alias = "hook-timeout-ms",
+        value_name = "MS",
+        help = "How long a hook can run before it's killed.",
+        long_help = "How long in milliseconds a hook set in the [hooks] section of the config file can run for \
+                    before it's killed. Defaults to 500."
+    )]
+    pub hook_timeout_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "HOSTS",
+        value_delimiter = ',',
+        conflicts_with_all = ["export", "record", "replay", "ws_port"],
+        help = "Monitors remote bottom instances side by side.",
+        long_help = "Monitors a comma-separated list of remote bottom instances (e.g. host1:4567,host2:4567) side by \
+                    side instead of the local system. Each host must be running bottom with --ws_port set to the \
+                    given port, and --ws_bind set to an address this machine can reach. Add '@' and a refresh rate to a host (e.g. host1:4567@500) to receive updates from \
+                    it less often than it collects them. Use '[' and ']' to change which host is focused."
+    )]
+    pub hosts: Option<Vec<String>>,
+
+    #[arg(
+        long,
+        alias = "hosts-ca",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "hosts",
+        conflicts_with = "hosts_skip_verify",
+        help = "Connects to remote hosts with TLS, using a CA file.",
+        long_help = "Connects to the hosts given with --hosts using TLS, checking their certificates against the CA \
+                    certificates in the given PEM file. The hosts must be started with --ws_tls_cert."
+    )]
+    pub hosts_ca: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "hosts-client-cert",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "hosts_client_key",
+        help = "The client certificate to send to remote hosts.",
+        long_help = "The certificate, as a PEM file, to present to hosts that were started with --ws_tls_client_ca. \
+                    Requires --hosts_client_key, and either --hosts_ca or --hosts_skip_verify."
+    )]
+    pub hosts_client_cert: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "hosts-client-key",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "hosts_client_cert",
+        help = "The private key of the client certificate.",
+        long_help = "The private key, as a PEM file, of the certificate given with --hosts_client_cert."
+    )]
+    pub hosts_client_key: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "hosts-skip-verify",
+        action = ArgAction::SetTrue,
+        requires = "hosts",
+        help = "Uses TLS with remote hosts, skipping verification.",
+        long_help = "Connects to the hosts given with --hosts using TLS, but accepts any certificate. The connection \
+                    is still encrypted, but the hosts could be impersonated, so this is only meant for testing."
+    )]
+    pub hosts_skip_verify: bool,
+
+    #[arg(
+        long,
+        alias = "hosts-token",
+        value_name = "TOKEN",
+        requires = "hosts",
+        help = "The token to connect to remote hosts with.",
+        long_help = "The token to send when connecting to the hosts given with --hosts, if they were started with \
+                    --ws_token."
+    )]
+    pub hosts_token: Option<String>,
+
+    #[arg(
+        long,
+        alias = "i-understand",
+        action = ArgAction::SetTrue,
+        requires = "disk_bench",
+        help = "Confirms that --disk_bench can slow down the disk it reads.",
+        long_help = "Confirms that --disk_bench reads the disk as fast as it can for 5 seconds, which slows down \
+                    everything else using it, and that it's not being run on a production system."
+    )]
+    pub i_understand: bool,
+
+    #[arg(
+        long,
+        alias = "influxdb-batch-size",
+        value_name = "SIZE",
+        help = "The number of points sent to InfluxDB per request.",
+        long_help = "The maximum number of points sent to InfluxDB per request when exporting with --influxdb_url. \
+                    Defaults to 5000."
+    )]
+    pub influxdb_batch_size: Option<usize>,
+
+    #[arg(
+        long,
+        alias = "influxdb-bucket",
+        value_name = "BUCKET",
+        help = "The InfluxDB bucket to export data to.",
+        long_help = "The InfluxDB bucket to write data to when exporting with --influxdb_url."
+    )]
+    pub influxdb_bucket: Option<String>,
+
+    #[arg(
+        long,
+        alias = "influxdb-org",
+        value_name = "ORG",
+        help = "The InfluxDB organization to export data to.",
+        long_help = "The InfluxDB organization to write data to when exporting with --influxdb_url."
+    )]
+    pub influxdb_org: Option<String>,
+
+    #[arg(
+        long,
+        alias = "influxdb-token",
+        value_name = "TOKEN",
+        help = "The API token used to authenticate with InfluxDB.",
+        long_help = "The API token used to authenticate with InfluxDB when exporting with --influxdb_url."
+    )]
+    pub influxdb_token: Option<String>,
+
+    #[arg(
+        long,
+        alias = "influxdb-url",
+        value_name = "URL",
+        value_hint = ValueHint::Url,
+        help = "Exports collected data to an InfluxDB server.",
+        long_help = "Exports all collected data to the InfluxDB server at the given URL (e.g. http://localhost:8086) \
+                    using its line protocol, in the background. An organization and bucket must also be set with \
+                    --influxdb_org and --influxdb_bucket. https:// URLs are checked against the system's CA \
+                    certificates, or the bundle in SSL_CERT_FILE."
+    )]
+    pub influxdb_url: Option<String>,
+
+    #[arg(
+        long,
+        alias = "interval-jitter",
+        value_name = "PCT",
+        help = "Randomly moves each update by up to PCT% of rate.",
+        long_help = "Randomly moves each data update earlier or later by up to the given percentage of the \
+                    refresh rate, between 0 and 100, so that many instances started at the same time don't all \
+                    collect data at once. The refresh rate shown stays the one set with --rate. Defaults to 0."
+    )]
+    pub interval_jitter: Option<u64>,
+
+    #[arg(
+        long,
+        alias = "ipc-socket",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Answers queries for current data over a UNIX socket.",
+        long_help = "Creates a UNIX socket at the given path, which external tools can query for the latest \
+                    collected data as JSON by sending a line like 'GET cpu'. Supported requests are 'GET cpu', \
+                    'GET mem', 'GET processes', and 'GET all'. Use --query to query it from the command line. The \
+                    socket is removed when bottom exits. Only supported on Unix-like systems."
+    )]
+    pub ipc_socket: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "ipc-socket-mode",
+        value_name = "MODE",
+        requires = "ipc_socket",
+        help = "The permissions of the IPC socket, in octal.",
+        long_help = "The permissions of the socket created with --ipc_socket, in octal (e.g. 0660). Defaults to \
+                    0600, which only allows the current user to connect."
+    )]
+    pub ipc_socket_mode: Option<String>,
+
+    #[cfg(feature = "logging")]
+    #[arg(
+        long,
+        alias = "json-log",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Also writes log events to a file as JSON.",
+        long_help = "Also writes log events to the given file, one JSON object per line with the fields timestamp, \
+                    level, module, and message. A summary of each data update is logged at the info level. The \
+                    file is rotated daily, and whenever it grows past --log_max_size."
+    )]
+    pub json_log: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "log-file",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "daemon",
+        help = "The file that output is written to in daemon mode.",
+        long_help = "Appends the output of --daemon, both the data and any errors, to the given file. Otherwise, \
+                    it is discarded."
+    )]
+    pub log_file: Option<PathBuf>,
+
+    #[cfg(feature = "logging")]
+    #[arg(
+        long,
+        alias = "log-keep",
+        value_name = "N",
+        requires = "json_log",
+        help = "How many rotated JSON log files are kept.",
+        long_help = "How many rotated files of the --json_log are kept before the oldest is removed. Defaults to 7."
+    )]
+    pub log_keep: Option<usize>,
+
+    #[cfg(feature = "logging")]
+    #[arg(
+        long,
+        alias = "log-level",
+        value_name = "LEVEL",
+        requires = "json_log",
+        help = "The lowest level of events written to the JSON log.",
+        long_help = "The lowest level of events written to the --json_log, one of error, warn, info, debug, or \
+                    trace. Defaults to info."
+    )]
+    pub log_level: Option<String>,
+
+    #[cfg(feature = "logging")]
+    #[arg(
+        long,
+        alias = "log-max-size",
+        value_name = "MB",
+        requires = "json_log",
+        help = "The size in megabytes that the JSON log is rotated at.",
+        long_help = "The size in megabytes that the --json_log is rotated at. Defaults to 100."
+    )]
+    pub log_max_size: Option<u64>,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Uses a minimal CPU, memory, and process layout.",
+        long_help = "Starts with a minimal layout of a large CPU graph next to a memory graph, with the process \
+                    widget below. Pressing 'v' switches to the full layout (the default layout, or the one set in \
+                    the config file) and back. This flag has no effect in basic mode (--basic)."
+    )]
+    pub minimal: bool,
+
+    #[arg(
+        long,
+        alias = "no-clipboard",
+        action = ArgAction::SetTrue,
+        help = "Disables copying values to the clipboard.",
+        long_help = "Disables copying values to the clipboard with 'y' and Ctrl+Shift+C, or from the process \
+                    context menu. Useful where values like process command lines shouldn't end up in the \
+                    clipboard or event log."
+    )]
+    pub no_clipboard: bool,
+
+    #[arg(
+        long,
+        alias = "no-disk",
+        action = ArgAction::SetTrue,
+        help = "Doesn't collect disk data.",
+        long_help = "Skips reading disks and disk I/O, and leaves the disk widget out of the layout. Useful where \
+                    the disks can't be read, like some containers."
+    )]
+    pub no_disk: bool,
+
+    #[arg(
+        long,
+        alias = "no-net",
+        action = ArgAction::SetTrue,
+        help = "Doesn't collect network data.",
+        long_help = "Skips reading network interfaces, and leaves the network widget out of the layout. Useful \
+                    where the interfaces can't be read, like some containers."
+    )]
+    pub no_net: bool,
+
+    #[arg(
+        long,
+        alias = "no-proc",
+        action = ArgAction::SetTrue,
+        help = "Doesn't collect process data.",
+        long_help = "Skips reading processes, and leaves the process and user processes widgets out of the \
+                    layout. Useful where /proc isn't fully readable and reading processes fails or hangs, like \
+                    gVisor or containers with restricted seccomp profiles. In basic mode, the process widget is \
+                    kept but empty."
+    )]
+    pub no_proc: bool,
+
+    #[arg(
+        long,
+        alias = "no-scroll-momentum",
+        action = ArgAction::SetTrue,
+        help = "Scrolls tables one row per mouse wheel event.",
+        long_help = "Disables momentum when scrolling tables with the mouse wheel, so each wheel event moves \
+                    exactly one row. How quickly momentum slows down can be set with 'scroll_friction' in the \
+                    config file."
+    )]
+    pub no_scroll_momentum: bool,
+
+    #[arg(
+        long,
+        alias = "no-system-config",
+        action = ArgAction::SetTrue,
+        help = "Ignores the system-wide config file.",
+        long_help = "Only reads your own config file, ignoring the system-wide one at /etc/bottom/bottom.toml \
+                    that it's otherwise layered over."
+    )]
+    pub no_system_config: bool,
+
+    #[arg(
+        long,
+        alias = "no-temp",
+        action = ArgAction::SetTrue,
+        help = "Doesn't collect temperature data.",
+        long_help = "Skips reading sensors, and leaves the temperature widget out of the layout. Useful where \
+                    the sensors can't be read, like some containers."
+    )]
+    pub no_temp: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Shows how many processes and threads each user has.",
+        long_help = "Shows a user processes widget that lists how many processes and threads each user has, \
+                    with the users with the most processes first. Below it are the total number of processes and \
+                    threads, and how many processes are running, sleeping, stopped, and zombies. A row for it is \
+                    added to the bottom of the layout unless it already has a 'user_procs' widget."
+    )]
+    pub nproc: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        group = "formatted_output",
+        conflicts_with_all = ["bench", "check", "daemon", "export", "hosts", "replay"],
+        help = "Prints a table of processes and exits.",
+        long_help = "Collects a single sample without starting the interface, and prints its processes as a table \
+                    like `ps aux`, sorted by CPU usage. Numbers are right-aligned, long text is truncated, and \
+                    columns are separated by two spaces, so the output can be used with awk or cut. The columns \
+                    can be set with --columns, or with 'columns' in the '[processes]' section of the config file. \
+                    On a terminal, the table is shown with $PAGER, or less. --raw and --top_summary already print \
+                    a single sample and exit, so they're printed instead when given with this."
+    )]
+    pub once: bool,
+
+    #[arg(
+        long,
+        alias = "override-terminal-size",
+        value_name = "SIZE",
+        help = "Draws the interface at this size instead of the terminal's.",
+        long_help = "Draws the interface at the given size, as WIDTHxHEIGHT like 120x40, instead of the size of \
+                    the terminal. Inside tmux, screen, or zellij, bottom asks the multiplexer for the size of its \
+                    pane, as the terminal can report the size of the outer terminal instead. This is for when \
+                    neither is right, like when bottom is embedded in another program."
+    )]
+    pub override_terminal_size: Option<String>,
+
+    #[arg(
+        long,
+        alias = "pid-file",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "daemon",
+        help = "Writes the PID of the daemon to a file.",
+        long_help = "Writes the PID of the process started with --daemon to the given file, which is removed again \
+                    when it exits."
+    )]
+    pub pid_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "SECTION",
+        requires = "ipc_socket",
+        value_parser = ["cpu", "mem", "processes", "all"],
+        help = "Prints current data from another bottom's IPC socket and exits.",
+        long_help = "Connects to the socket given with --ipc_socket of another running instance of bottom, prints \
+                    the latest data it collected for the given section as JSON, and exits without starting the \
+                    interface."
+    )]
+    pub query: Option<String>,
+
+    #[arg(
+        short = 'q',
+        long,
+        action = ArgAction::SetTrue,
+        help = "Hides everything but the widgets.",
+        long_help = "Gives the whole terminal to the widgets by hiding the status line at the bottom of the screen, \
+                    the --show_header summary, and the heartbeat, and shortens widget titles to one word. Alerts, \
+                    like high CPU usage or a full disk, flash the border of the affected widget instead. Combine \
+                    with --minimal for the most compact display."
+    )]
+    pub quiet: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["bench", "check", "daemon", "export", "hosts", "replay", "top_summary"],
+        help = "Prints a sample as key=value lines and exits.",
+        long_help = "Collects a single sample without starting the interface, and prints every collected value as \
+                    a key=value line, with dot-separated keys like cpu.avg, mem.used_bytes, or proc.1234.name. \
+                    Numbers always use . as the decimal separator. Keys are kept stable across versions, so scripts \
+                    can parse the output without JSON. See --raw_filter."
+    )]
+    pub raw: bool,
+
+    #[arg(
+        long,
+        alias = "raw-filter",
+        value_name = "PATTERNS",
+        requires = "raw",
+        help = "Only prints the --raw keys matching these patterns.",
+        long_help = "Only prints the --raw keys matching any of a comma-separated list of glob patterns, like \
+                    cpu.*,mem.*. A * matches any run of characters, including dots, and a ? matches any single one."
+    )]
+    pub raw_filter: Option<String>,
+
     #[arg(
         short = 'r',
         long,
@@ -236,6 +829,45 @@ pub struct GeneralArgs {
     )]
     pub rate: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Records collected data to a file.",
+        long_help = "Records all collected data to the given file, which can be played back later with --replay. \
+                    Once the recording reaches its maximum size (see --record_max_size), the oldest data is dropped."
+    )]
+    pub record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "record-max-size",
+        value_name = "MB",
+        help = "The maximum size of a recording in megabytes.",
+        long_help = "The maximum size of a recording made with --record, in megabytes. Defaults to 50."
+    )]
+    pub record_max_size: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        conflicts_with = "record",
+        help = "Plays back a recording instead of collecting data.",
+        long_help = "Plays back a recording made with --record instead of collecting data from the system. Pressing \
+                    'f' pauses and resumes the playback."
+    )]
+    pub replay: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "replay-speed",
+        value_name = "SPEED",
+        help = "The playback speed of a replay.",
+        long_help = "The playback speed multiplier of a replay made with --replay (e.g. 2x, 0.5). Defaults to 1x."
+    )]
+    pub replay_speed: Option<String>,
+
     #[arg(
         long,
         value_name = "TIME",
@@ -246,6 +878,29 @@ pub struct GeneralArgs {
     )]
     pub retention: Option<String>,
 
+    #[arg(
+        long,
+        alias = "show-header",
+        action = ArgAction::SetTrue,
+        help = "Shows a summary header above the widgets.",
+        long_help = "Shows a three line summary header above the widgets, similar to the one shown by top. It \
+                    contains a breakdown of CPU usage, memory and swap usage, and how many processes are \
+                    in each state. The CPU breakdown is only available on Linux."
+    )]
+    pub show_header: bool,
+
+    #[arg(
+        long,
+        alias = "show-heartbeat",
+        action = ArgAction::SetTrue,
+        help = "Shows a spinner that advances on each update.",
+        long_help = "Always shows the status line at the bottom of the screen, with a spinner that advances each \
+                    time new data is collected. If no data has been collected for more than three times the \
+                    update rate, it's replaced with how long ago the last update was, so a stalled collection \
+                    can be told apart from a slow one."
+    )]
+    pub show_heartbeat: bool,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -253,6 +908,18 @@ pub struct GeneralArgs {
     )]
     pub show_table_scroll_position: bool,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        requires = "generate_config",
+        conflicts_with = "format",
+        help = "Writes --generate_config to the system config.",
+        long_help = "Writes the config file from --generate_config to /etc/bottom/bottom.toml, the system-wide \
+                    config file that every user's config is layered over, instead of printing it. This must be \
+                    run as root, and won't replace an existing file."
+    )]
+    pub system: bool,
+
     #[arg(
         short = 'd',
         long,
@@ -263,6 +930,135 @@ pub struct GeneralArgs {
                     defaults to 15s."
     )]
     pub time_delta: Option<String>,
+
+    #[arg(
+        long,
+        alias = "top-summary",
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["bench", "check", "daemon", "export", "hosts", "replay"],
+        help = "Prints a summary like the header of top and exits.",
+        long_help = "Collects a single sample without starting the interface, and prints it in the format of the \
+                    header of top: like macOS's `top -l 1` on macOS, and like procps's top elsewhere, followed by \
+                    network and disk totals. Doesn't need a terminal, so it can be used in scripts."
+    )]
+    pub top_summary: bool,
+
+    #[arg(
+        long,
+        alias = "update-on-key",
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Only refreshes data when a key is pressed.",
+        long_help = "Stops collecting data on a timer, and instead collects it once at startup and again every \
+                    time a key is pressed. Useful on slow or power-constrained systems where constantly polling \
+                    is wasteful. The refresh rate set with --rate is ignored."
+    )]
+    pub update_on_key: bool,
+
+    #[cfg(feature = "update-check")]
+    #[arg(
+        long,
+        alias = "version-check",
+        action = ArgAction::SetTrue,
+        help = "Checks whether a newer version is out and exits.",
+        long_help = "Asks GitHub for the latest release of bottom, and prints 'New version available: X.Y.Z' if \
+                    it's newer than this one, or 'Up to date' otherwise, then exits. Nothing is downloaded or \
+                    installed. Set check_for_updates in the config to check once a day at startup instead."
+    )]
+    pub version_check: bool,
+
+    #[arg(
+        long,
+        alias = "watch-file",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        action = ArgAction::Append,
+        help = "Shows the last lines of a file as they're written.",
+        long_help = "Follows a file, like `tail -F`, and shows its last lines in a log tail widget as they're written. \
+                    Can be given more than once, with each file in its own tab. Long lines are wrapped, and a file \
+                    that's replaced or truncated, as when logs are rotated, is read again from the start. A log tail \
+                    row is added to the bottom of the layout unless it already has a 'log_tail' widget. See \
+                    --watch_file_lines."
+    )]
+    pub watch_file: Vec<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "watch-file-lines",
+        value_name = "LINES",
+        requires = "watch_file",
+        help = "How many lines of each --watch_file are kept.",
+        long_help = "How many lines of each file followed with --watch_file are kept to scroll back through. \
+                    Defaults to 500."
+    )]
+    pub watch_file_lines: Option<usize>,
+
+    #[arg(
+        long,
+        alias = "ws-bind",
+        value_name = "ADDRESS",
+        requires = "ws_port",
+        help = "The address the WebSocket server listens on.",
+        long_help = "The IP address the WebSocket server listens on, such as 0.0.0.0 to accept connections on every \
+                    IPv4 interface. Defaults to 127.0.0.1, so only local clients can connect."
+    )]
+    pub ws_bind: Option<IpAddr>,
+
+    #[arg(
+        long,
+        alias = "ws-port",
+        value_name = "PORT",
+        help = "Streams data to WebSocket clients on a port.",
+        long_help = "Starts a WebSocket server at the given port, which sends every update of collected data to \
+                    connected clients as JSON. It only listens on localhost unless --ws_bind is set."
+    )]
+    pub ws_port: Option<u16>,
+
+    #[arg(
+        long,
+        alias = "ws-tls-cert",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires_all = ["ws_port", "ws_tls_key"],
+        help = "Encrypts the WebSocket server with a certificate.",
+        long_help = "Makes the WebSocket server use TLS, with the certificate chain in the given PEM file. Requires \
+                    --ws_tls_key. Without this, connections are in plaintext."
+    )]
+    pub ws_tls_cert: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "ws-tls-client-ca",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "ws_tls_cert",
+        help = "Requires WebSocket clients to have a certificate.",
+        long_help = "Requires WebSocket clients to present a certificate signed by one of the CA certificates in \
+                    the given PEM file, for mutual TLS."
+    )]
+    pub ws_tls_client_ca: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "ws-tls-key",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "ws_tls_cert",
+        help = "The private key of the WebSocket certificate.",
+        long_help = "The private key, as a PEM file, of the certificate given with --ws_tls_cert."
+    )]
+    pub ws_tls_key: Option<PathBuf>,
+
+    #[arg(
+        long,
+        alias = "ws-token",
+        value_name = "TOKEN",
+        requires = "ws_port",
+        help = "Requires a token for WebSocket clients.",
+        long_help = "Requires WebSocket clients to send the given token to connect, either with an \
+                    'Authorization: Bearer <TOKEN>' header or a 'token' query parameter."
+    )]
+    pub ws_token: Option<String>,
 }
 
 /// Process arguments/config options.
@@ -278,6 +1074,17 @@ pub struct ProcessArgs {
     )]
     pub case_sensitive: bool,
 
+    #[arg(
+        long,
+        value_name = "COLUMNS",
+        help = "Sets the columns of the process widget at first.",
+        long_help = "Sets the columns of the process widget at first, as a comma-separated list like \
+                    'pid,name,cpu%,mem%,r/s'. Columns are named as in the 'columns' list of the '[processes]' \
+                    section of the config file, or as with --sort_by. 'all' shows every column, and 'default' \
+                    shows the default ones. This overrides the columns set in the config file."
+    )]
+    pub columns: Option<String>,
+
     // TODO: Rename this.
     #[arg(
         short = 'u',
@@ -297,6 +1104,28 @@ pub struct ProcessArgs {
     )]
     pub disable_advanced_kill: bool,
 
+    #[arg(
+        long,
+        alias = "filter-processes",
+        value_name = "REGEX",
+        help = "Starts with the process widget filtered by a regex.",
+        long_help = "Starts with the process widget filtered by a regex, e.g. 'nginx|postgres'. The filter is shown \
+                    in the search bar, where it can be edited or cleared. Regex searching is turned on to match. \
+                    An empty filter is the same as none."
+    )]
+    pub filter_processes: Option<String>,
+
+    #[arg(
+        long,
+        alias = "filter-user",
+        value_name = "USER",
+        help = "Only shows processes owned by a user.",
+        long_help = "Starts with the process widget filtered to the processes owned by the given user name. The \
+                    filter is shown in the search bar as 'user = ^USER$', where it can be edited or cleared, and \
+                    is combined with --filter_processes or --pid if they're also given."
+    )]
+    pub filter_user: Option<String>,
+
     #[arg(
         short = 'g',
         long,
@@ -305,6 +1134,29 @@ pub struct ProcessArgs {
     )]
     pub group_processes: bool,
 
+    #[arg(
+        long,
+        alias = "highlight-changes",
+        action = ArgAction::SetTrue,
+        help = "Briefly highlights processes whose usage changed a lot.",
+        long_help = "Highlights a process for one redraw after an update if its CPU or memory usage changed by more \
+                    than a set amount since the previous update, 5 percentage points by default. The amounts can \
+                    be set with 'cpu_change_threshold' and 'mem_change_threshold' in the config file. Nothing is \
+                    highlighted while frozen."
+    )]
+    pub highlight_changes: bool,
+
+    #[arg(
+        long,
+        value_name = "PID or NAME",
+        conflicts_with = "filter_processes",
+        help = "Starts focused on a single process, given by its PID or name.",
+        long_help = "Starts with the process widget expanded and filtered to a single process, given by its PID or \
+                    exact name. If several processes share the name, they are all shown so one can be picked. \
+                    Until a matching process exists, the widget's title says it's waiting for it."
+    )]
+    pub pid: Option<String>,
+
     #[arg(
         long,
         action = ArgAction::SetTrue,
@@ -323,6 +1175,67 @@ pub struct ProcessArgs {
     #[arg(short = 'R', long, action = ArgAction::SetTrue, help = "Enables regex by default while searching.")]
     pub regex: bool,
 
+    #[arg(
+        long,
+        alias = "sort-asc",
+        action = ArgAction::SetTrue,
+        conflicts_with = "sort_desc",
+        help = "Sorts processes in ascending order at first.",
+        long_help = "Sorts processes in ascending order at first, instead of the default order of the sorted column."
+    )]
+    pub sort_asc: bool,
+
+    #[arg(
+        long,
+        alias = "sort-by",
+        value_name = "COLUMN",
+        help = "Sets the column processes are sorted by at first.",
+        long_help = "Sets the column processes are sorted by at first. One of pid, name, cpu, mem, io_read, \
+                    io_write, user, state, or runtime. Defaults to cpu, or pid in tree mode."
+    )]
+    pub sort_by: Option<String>,
+
+    #[arg(
+        long,
+        alias = "sort-desc",
+        action = ArgAction::SetTrue,
+        help = "Sorts processes in descending order at first.",
+        long_help = "Sorts processes in descending order at first, instead of the default order of the sorted column."
+    )]
+    pub sort_desc: bool,
+
+    #[arg(
+        long,
+        alias = "threshold-cpu",
+        value_name = "PERCENT",
+        help = "Hides processes using less CPU than this at first.",
+        long_help = "Hides processes using less than the given CPU%, e.g. 1.0. Hidden processes are counted below \
+                    the process table, and the threshold can be changed with '>'. 0 is the same as none."
+    )]
+    pub threshold_cpu: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "threshold-mem",
+        value_name = "PERCENT",
+        help = "Hides processes using less memory than this at first.",
+        long_help = "Hides processes using less than the given percentage of memory, e.g. 0.5. Hidden processes \
+                    are counted below the process table, and the threshold can be changed with '>'. 0 is the \
+                    same as none."
+    )]
+    pub threshold_mem: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only shows the top N processes.",
+        long_help = "Only shows the first N processes in the process widget, by the sorted column. By default that's \
+                    CPU usage, so e.g. '--top 10 --sort_by mem' shows the 10 processes using the most memory. The \
+                    title shows how many processes there are in total. Searches apply first, and tree mode shows \
+                    every process."
+    )]
+    pub top: Option<usize>,
+
     #[arg(
         short = 'T',
         long,
@@ -334,8 +1247,13 @@ pub struct ProcessArgs {
     #[arg(
         short = 'n',
         long,
+        alias = "normalize_cpu",
+        alias = "normalize-cpu",
         action = ArgAction::SetTrue,
-        help = "Show process CPU% usage without averaging over the number of CPU cores."
+        help = "Show process CPU% usage without averaging over the number of CPU cores.",
+        long_help = "Shows the CPU usage of processes as a percentage of a single core, so a process keeping one \
+                    core busy is at 100%, instead of as a percentage of all cores together. 'o' switches a process \
+                    widget between the two. --normalize_cpu is an older name for this."
     )]
     pub unnormalized_cpu: bool,
 
@@ -408,6 +1326,16 @@ impl ValueEnum for CpuDefault {
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "CPU Options", rename_all = "snake_case")]
 pub struct CpuArgs {
+    #[arg(
+        long,
+        alias = "cpu-avg-n",
+        value_name = "N",
+        help = "Smooths the CPU graph over the last N points.",
+        long_help = "Smooths the CPU chart by showing a moving average of the last N data points. Defaults to 1, \
+                    which disables smoothing."
+    )]
+    pub cpu_avg_n: Option<usize>,
+
     // TODO: Maybe rename this or fix this? Should this apply to all "left legends"?
     #[arg(
         short = 'l',
@@ -433,6 +1361,16 @@ pub struct CpuArgs {
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "Memory Options", rename_all = "snake_case")]
 pub struct MemoryArgs {
+    #[arg(
+        long,
+        alias = "mem-avg-n",
+        value_name = "N",
+        help = "Smooths the memory graph over the last N points.",
+        long_help = "Smooths the memory chart by showing a moving average of the last N data points. Defaults to 1, \
+                    which disables smoothing."
+    )]
+    pub mem_avg_n: Option<usize>,
+
     #[arg(
         long,
         value_parser = CHART_WIDGET_POSITIONS,
@@ -449,12 +1387,33 @@ pub struct MemoryArgs {
         help = "Enables collecting and displaying cache and buffer memory."
     )]
     pub enable_cache_memory: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "ignore-cgroup-limits",
+        help = "Shows RAM against the physical RAM even in a cgroup with a memory limit.",
+        long_help = "Shows RAM against the physical RAM even in a cgroup with a memory limit, like in a \
+                    container. By default, RAM is shown against the cgroup's limit if it's below the physical \
+                    RAM. This is only supported on Linux."
+    )]
+    pub ignore_cgroup_limits: bool,
 }
 
 /// Network arguments/config options.
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "Network Options", rename_all = "snake_case")]
 pub struct NetworkArgs {
+    #[arg(
+        long,
+        alias = "net-avg-n",
+        value_name = "N",
+        help = "Smooths the network graph over the last N points.",
+        long_help = "Smooths the network chart by showing a moving average of the last N data points. Defaults to \
+                    1, which disables smoothing."
+    )]
+    pub net_avg_n: Option<usize>,
+
     #[arg(
         long,
         value_parser = CHART_WIDGET_POSITIONS,
@@ -528,6 +1487,27 @@ pub struct GpuArgs {
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "Style Options", rename_all = "snake_case")]
 pub struct StyleArgs {
+    #[arg(
+        long,
+        alias = "no-color",
+        action = ArgAction::SetTrue,
+        help = "Draws everything without colors.",
+        long_help = "Draws everything without colors, only using bold and reversed text to mark what is selected. \
+                This is also the default if the NO_COLOR environment variable is set to anything but an empty value."
+    )]
+    pub no_color: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        alias = "no-unicode-borders",
+        help = "Draws borders with ASCII characters.",
+        long_help = "Draws the borders of widgets and dialogs with '+', '-', and '|' instead of box-drawing \
+                characters, for terminals or fonts that can't show them. Graphs are still drawn with braille \
+                and block characters, and widgets set to have no borders are left without them."
+    )]
+    pub no_unicode_borders: bool,
+
     #[arg(
         long,
         value_name = "SCHEME",
@@ -556,6 +1536,198 @@ pub struct StyleArgs {
     pub theme: Option<String>,
 }
 
+/// Health check arguments.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "Health Check Options", rename_all = "snake_case")]
+pub struct CheckArgs {
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        conflicts_with_all = ["bench", "daemon", "export", "generate_config", "hosts", "replay"],
+        help = "Checks usage against thresholds and exits with a status code.",
+        long_help = "Collects a single sample without starting the interface, compares it against the given \
+                    thresholds, prints a summary line, and exits with 0 if everything is OK, 1 if a warning \
+                    threshold was crossed, 2 if a critical threshold was crossed, or 3 if the check couldn't be \
+                    done. Thresholds that aren't given default to the ones the event log raises alerts at: a CPU \
+                    warning at 90%, a disk warning at 90% and critical at 95%, and a critical temperature at \
+                    90°C. This doesn't need a terminal, so it can be used from scripts and monitoring systems."
+    )]
+    pub check: bool,
+
+    #[arg(
+        long,
+        alias = "check-format",
+        value_name = "FORMAT",
+        requires = "check",
+        value_parser = ["plain", "nagios"],
+        help = "How the result of --check is printed.",
+        long_help = "How the result of --check is printed, either 'plain' (default) for just a summary line, or \
+                    'nagios' to also include performance data in the format Nagios and Icinga plugins use."
+    )]
+    pub check_format: Option<String>,
+
+    #[arg(
+        long,
+        alias = "check-timeout",
+        value_name = "TIME",
+        requires = "check",
+        help = "How long --check can take to collect data.",
+        long_help = "How long --check can take to collect data before giving up with a status of 3, either a \
+                    number in milliseconds or a 'human duration' (e.g. 5s). Defaults to 10s."
+    )]
+    pub check_timeout: Option<String>,
+
+    #[arg(
+        long,
+        alias = "cpu-crit",
+        value_name = "PERCENT",
+        requires = "check",
+        help = "Critical threshold for total CPU usage with --check.",
+        long_help = "The total CPU usage, in percent, at or above which --check is critical."
+    )]
+    pub cpu_crit: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "cpu-warn",
+        value_name = "PERCENT",
+        requires = "check",
+        help = "Warning threshold for total CPU usage with --check.",
+        long_help = "The total CPU usage, in percent, at or above which --check is a warning. Defaults to 90."
+    )]
+    pub cpu_warn: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "disk-crit",
+        value_name = "PERCENT",
+        requires = "check",
+        help = "Critical threshold for disk usage with --check.",
+        long_help = "The usage of the fullest disk, in percent, at or above which --check is critical. \
+                    Defaults to 95."
+    )]
+    pub disk_crit: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "disk-warn",
+        value_name = "PERCENT",
+        requires = "check",
+        help = "Warning threshold for disk usage with --check.",
+        long_help = "The usage of the fullest disk, in percent, at or above which --check is a warning. \
+                    Defaults to 90."
+    )]
+    pub disk_warn: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "mem-crit",
+        value_name = "PERCENT",
+        requires = "check",
+        help = "Critical threshold for memory usage with --check.",
+        long_help = "The memory usage, in percent, at or above which --check is critical."
+    )]
+    pub mem_crit: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "mem-warn",
+        value_name = "PERCENT",
+        requires = "check",
+        help = "Warning threshold for memory usage with --check.",
+        long_help = "The memory usage, in percent, at or above which --check is a warning."
+    )]
+    pub mem_warn: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "temp-crit",
+        value_name = "TEMP",
+        requires = "check",
+        help = "Critical threshold for temperatures with --check.",
+        long_help = "The temperature of the hottest sensor, in the unit temperatures are shown in, at or above which \
+                    --check is critical. Defaults to 90°C."
+    )]
+    pub temp_crit: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "temp-warn",
+        value_name = "TEMP",
+        requires = "check",
+        help = "Warning threshold for temperatures with --check.",
+        long_help = "The temperature of the hottest sensor, in the unit temperatures are shown in, at or above which \
+                    --check is a warning."
+    )]
+    pub temp_warn: Option<f64>,
+}
+
+/// Arguments for making up data instead of collecting it, for developing and
+/// testing the interface.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "Simulation Options", rename_all = "snake_case")]
+pub struct SimulateArgs {
+    #[arg(
+        long,
+        alias = "simulate-cpu",
+        value_name = "PERCENT",
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Shows made-up data with this CPU usage on each core.",
+        long_help = "Shows made-up data instead of collecting it, with each CPU core at this usage in percent. \
+                    Useful for seeing how high usage, alerts, and themes look without loading the system. Any of \
+                    the simulation options turn this on, and those that aren't given use their defaults: 50% CPU \
+                    and memory usage, 10Mb/s of network traffic, and 20 processes."
+    )]
+    pub simulate_cpu: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "simulate-mem",
+        value_name = "PERCENT",
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Shows made-up data with this memory usage.",
+        long_help = "Shows made-up data instead of collecting it, with this much of 16GiB of memory used, in \
+                    percent. See --simulate_cpu."
+    )]
+    pub simulate_mem: Option<f64>,
+
+    #[arg(
+        long,
+        alias = "simulate-net",
+        value_name = "RATE",
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Shows made-up data with this network traffic.",
+        long_help = "Shows made-up data instead of collecting it, with this much received and sent each second, \
+                    in bits. Takes a number with an optional k, m, or g unit, like 100mb or 1.5gbit. See \
+                    --simulate_cpu."
+    )]
+    pub simulate_net: Option<String>,
+
+    #[arg(
+        long,
+        alias = "simulate-period",
+        value_name = "TIME",
+        conflicts_with_all = ["hosts", "replay"],
+        help = "Makes simulated usage rise and fall over this long.",
+        long_help = "Makes the simulated usage rise from 0 to the given values and back down again like a sine \
+                    wave, taking this long for each cycle, rather than staying put. Each CPU core is offset from \
+                    the last. Either a number in milliseconds or a 'human duration' (e.g. 10s, 1m)."
+    )]
+    pub simulate_period: Option<String>,
+
+    #[arg(
+        long,
+        alias = "simulate-procs",
+        value_name = "N",
+        conflicts_with_all = ["hosts", "replay"],
+        help = "How many made-up processes to show.",
+        long_help = "How many made-up processes to show when simulating. The simulated CPU and memory usage are \
+                    shared between them, with the first process getting the most and each one after getting less. \
+                    See --simulate_cpu."
+    )]
+    pub simulate_procs: Option<usize>,
+}
+
 /// Other arguments. This just handle options that are for help/version
 /// displaying.
 #[derive(Args, Clone, Debug)]
@@ -609,6 +1781,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn kebab_case_aliases() {
+        let args = BottomArgs::parse_from([
+            "btm",
+            "--sort-by",
+            "mem",
+            "--sort-desc",
+            "--filter-processes",
+            "nginx",
+            "--ws-port",
+            "9091",
+            "--ws-token",
+            "secret",
+            "--influxdb-url",
+            "http://localhost:8086",
+            "--influxdb-org",
+            "org",
+            "--influxdb-bucket",
+            "bucket",
+        ]);
+
+        assert_eq!(args.process.sort_by.as_deref(), Some("mem"));
+        assert!(args.process.sort_desc);
+        assert_eq!(args.process.filter_processes.as_deref(), Some("nginx"));
+        assert_eq!(args.general.ws_port, Some(9091));
+        assert_eq!(args.general.ws_token.as_deref(), Some("secret"));
+        assert_eq!(
+            args.general.influxdb_url.as_deref(),
+            Some("http://localhost:8086")
+        );
+        assert_eq!(args.general.influxdb_org.as_deref(), Some("org"));
+    }
+
     #[test]
     fn catch_incorrect_long_args() {
         // Set this to allow certain ones through if needed.
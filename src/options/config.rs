@@ -1,36 +1,58 @@
 pub mod cpu;
+pub mod diff;
 pub mod disk;
 pub mod flags;
+pub mod hooks;
 mod ignore_list;
+pub mod influxdb;
 pub mod layout;
 pub mod network;
 pub mod process;
+#[cfg(feature = "generate_schema")]
+pub mod schema;
 pub mod style;
 pub mod temperature;
+pub mod timing;
+pub mod widgets;
 
+use anyhow::Context;
+use diff::DiffConfig;
 use disk::DiskConfig;
 use flags::FlagConfig;
+use hooks::HooksConfig;
+use indexmap::IndexMap;
+use influxdb::InfluxDbConfig;
 use network::NetworkConfig;
 use serde::{Deserialize, Serialize};
 use style::StyleConfig;
 use temperature::TempConfig;
+use timing::TimingConfig;
+use toml_edit::{DocumentMut, TableLike};
+use widgets::WidgetConfig;
 
 pub use self::ignore_list::IgnoreList;
 use self::{cpu::CpuConfig, layout::Row, process::ProcessesConfig};
+use crate::widgets::ProcColumn;
 
 /// Overall config for `bottom`.
 #[derive(Clone, Debug, Default, Deserialize)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
 pub struct Config {
     pub(crate) flags: Option<FlagConfig>,
     pub(crate) styles: Option<StyleConfig>,
     pub(crate) row: Option<Vec<Row>>,
     pub(crate) processes: Option<ProcessesConfig>,
+    pub(crate) presets: Option<IndexMap<String, Vec<ProcColumn>>>,
     pub(crate) disk: Option<DiskConfig>,
     pub(crate) temperature: Option<TempConfig>,
     pub(crate) network: Option<NetworkConfig>,
     pub(crate) cpu: Option<CpuConfig>,
+    pub(crate) diff: Option<DiffConfig>,
+    pub(crate) influxdb: Option<InfluxDbConfig>,
+    pub(crate) hooks: Option<HooksConfig>,
+    pub(crate) timing: Option<TimingConfig>,
+    pub(crate) widgets: Option<IndexMap<String, WidgetConfig>>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -54,8 +76,105 @@ impl From<u64> for StringOrNum {
     }
 }
 
+/// Parses the user's config, layered over the system-wide config if there is
+/// one. Each setting in the user's config replaces the same setting in the
+/// system config, while tables like `[flags]` are merged key by key. Arrays,
+/// like the `[[row]]` layout, are replaced as a whole.
+pub fn load_merged(system: Option<&str>, user: &str) -> anyhow::Result<Config> {
+    let Some(system) = system else {
+        return Ok(toml_edit::de::from_str(user)?);
+    };
+
+    // Check each file on its own first, so that errors say which file is wrong.
+    toml_edit::de::from_str::<Config>(system).context("the system config file is invalid")?;
+    toml_edit::de::from_str::<Config>(user).context("the config file is invalid")?;
+
+    let mut merged = system.parse::<DocumentMut>()?;
+    let user = user.parse::<DocumentMut>()?;
+    merge(merged.as_table_mut(), user.as_table());
+
+    Ok(toml_edit::de::from_str(&merged.to_string())?)
+}
+
+/// Puts every setting of `overrides` into `base`, merging tables that are in
+/// both.
+fn merge(base: &mut dyn TableLike, overrides: &dyn TableLike) {
+    for (key, item) in overrides.iter() {
+        match (
+            base.get_mut(key).and_then(|b| b.as_table_like_mut()),
+            item.as_table_like(),
+        ) {
+            (Some(base_table), Some(override_table)) => merge(base_table, override_table),
+            _ => {
+                base.insert(key, item.clone());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    fn merged_config_prefers_user_settings() {
+        let system = r#"
+            [flags]
+            rate = "2s"
+            hide_avg_cpu = true
+
+            [disk]
+            name_filter = { is_list_ignored = true, list = ["/dev/loop"] }
+        "#;
+        let user = r#"
+            [flags]
+            rate = "500ms"
+
+            [disk.name_filter]
+            list = ["/dev/sda"]
+        "#;
+
+        let config = load_merged(Some(system), user).unwrap();
+        let flags = config.flags.unwrap();
+        assert_eq!(flags.rate, Some(StringOrNum::String("500ms".to_string())));
+        assert_eq!(flags.hide_avg_cpu, Some(true));
+
+        let name_filter = config.disk.unwrap().name_filter.unwrap();
+        assert!(name_filter.is_list_ignored);
+        assert_eq!(name_filter.list, vec!["/dev/sda".to_string()]);
+    }
+
+    #[test]
+    fn merged_config_replaces_layout() {
+        let system = r#"
+            [[row]]
+              [[row.child]]
+              type = "cpu"
+            [[row]]
+              [[row.child]]
+              type = "disk"
+        "#;
+        let user = r#"
+            [[row]]
+              [[row.child]]
+              type = "proc"
+        "#;
+
+        let config = load_merged(Some(system), user).unwrap();
+        assert_eq!(config.row.unwrap().len(), 1);
+
+        let config = load_merged(Some(system), "").unwrap();
+        assert_eq!(config.row.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn merged_config_names_bad_file() {
+        let err = load_merged(Some("[flags]\nrate = []"), "").unwrap_err();
+        assert!(err.to_string().contains("system config"));
+
+        let err = load_merged(Some(""), "[flags]\nrate = []").unwrap_err();
+        assert!(!err.to_string().contains("system config"));
+    }
 
     // Test all valid configs in the integration test folder and ensure they are accepted.
     // We need this separated as only test library code sets `serde(deny_unknown_fields)`.
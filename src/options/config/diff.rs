@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+/// Diff mode configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
+pub(crate) struct DiffConfig {
+    /// A path to a JSON snapshot file to compare current data against.
+    pub(crate) baseline_file: Option<String>,
+
+    /// How many percentage points a value has to change by to be highlighted.
+    pub(crate) threshold: Option<f64>,
+}
@@ -32,7 +32,7 @@ mod test {
 
     #[test]
     fn valid_disk_column_settings() {
-        let config = r#"columns = ["disk", "mount", "used", "free", "total", "used%", "free%", "r/s", "w/s"]"#;
+        let config = r#"columns = ["disk", "mount", "used", "free", "total", "used%", "free%", "r/s", "w/s", "wear"]"#;
         toml_edit::de::from_str::<DiskConfig>(config).expect("Should succeed!");
     }
 
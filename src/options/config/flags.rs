@@ -4,7 +4,7 @@ use super::StringOrNum;
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
 pub(crate) struct FlagConfig {
     pub(crate) hide_avg_cpu: Option<bool>,
     pub(crate) dot_marker: Option<bool>,
@@ -12,16 +12,19 @@ pub(crate) struct FlagConfig {
     pub(crate) rate: Option<StringOrNum>,
     pub(crate) cpu_left_legend: Option<bool>,
     pub(crate) current_usage: Option<bool>,
+    #[serde(alias = "normalize_cpu_pct")]
     pub(crate) unnormalized_cpu: Option<bool>,
     pub(crate) group_processes: Option<bool>,
     pub(crate) case_sensitive: Option<bool>,
     pub(crate) whole_word: Option<bool>,
     pub(crate) regex: Option<bool>,
     pub(crate) basic: Option<bool>,
+    pub(crate) minimal: Option<bool>,
     pub(crate) default_time_value: Option<StringOrNum>,
     pub(crate) time_delta: Option<StringOrNum>,
     pub(crate) autohide_time: Option<bool>,
     pub(crate) hide_time: Option<bool>,
+    pub(crate) graph_timestamps: Option<String>,
     pub(crate) default_widget_type: Option<String>,
     pub(crate) default_widget_count: Option<u64>,
     pub(crate) expanded: Option<bool>,
@@ -29,19 +32,43 @@ pub(crate) struct FlagConfig {
     pub(crate) hide_table_gap: Option<bool>,
     pub(crate) battery: Option<bool>,
     pub(crate) disable_click: Option<bool>,
+    pub(crate) no_clipboard: Option<bool>,
+    pub(crate) no_proc: Option<bool>,
+    pub(crate) no_net: Option<bool>,
+    pub(crate) no_disk: Option<bool>,
+    pub(crate) no_temp: Option<bool>,
     pub(crate) no_write: Option<bool>,
     pub(crate) network_legend: Option<String>,
     pub(crate) memory_legend: Option<String>,
     pub(crate) process_memory_as_value: Option<bool>,
     pub(crate) tree: Option<bool>,
+    pub(crate) highlight_changes: Option<bool>,
     pub(crate) show_table_scroll_position: Option<bool>,
+    pub(crate) show_header: Option<bool>,
+    pub(crate) show_heartbeat: Option<bool>,
+    pub(crate) quiet: Option<bool>,
+    pub(crate) update_on_key: Option<bool>,
+    pub(crate) no_scroll_momentum: Option<bool>,
+    pub(crate) scroll_friction: Option<f32>,
     pub(crate) process_command: Option<bool>,
     pub(crate) disable_advanced_kill: Option<bool>,
     pub(crate) network_use_bytes: Option<bool>,
     pub(crate) network_use_log: Option<bool>,
     pub(crate) network_use_binary_prefix: Option<bool>,
+    pub(crate) show_ipv6: Option<bool>,
+    pub(crate) network_shared_axis: Option<bool>,
     pub(crate) disable_gpu: Option<bool>,
     pub(crate) enable_cache_memory: Option<bool>,
     pub(crate) retention: Option<StringOrNum>,
     pub(crate) average_cpu_row: Option<bool>,
+    pub(crate) cpu_avg_n: Option<usize>,
+    pub(crate) mem_avg_n: Option<usize>,
+    pub(crate) net_avg_n: Option<usize>,
+    pub(crate) freeze_on_alert: Option<bool>,
+    pub(crate) freeze_on_alert_mode: Option<String>,
+    pub(crate) swap_out_warning: Option<u64>,
+    pub(crate) compute_pss: Option<bool>,
+    pub(crate) ignore_cgroup_limits: Option<bool>,
+    pub(crate) show_graph_stats: Option<bool>,
+    pub(crate) check_for_updates: Option<bool>,
 }
@@ -0,0 +1,37 @@
+use serde::Deserialize;
+
+/// One or more commands to run for a hook.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub(crate) enum HookCommands {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<HookCommands> for Vec<String> {
+    fn from(commands: HookCommands) -> Self {
+        match commands {
+            HookCommands::One(command) => vec![command],
+            HookCommands::Many(commands) => commands,
+        }
+    }
+}
+
+/// Hook configuration. Hooks are shell commands that are sent the collected
+/// data as JSON on stdin.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct HooksConfig {
+    /// Commands to run on every data update.
+    pub(crate) on_update: Option<HookCommands>,
+
+    /// Commands to run when an alert is raised. The alert message is set in
+    /// the `BOTTOM_ALERT` environment variable.
+    pub(crate) on_alert: Option<HookCommands>,
+
+    /// How long a hook can run for in milliseconds before it's killed.
+    pub(crate) timeout_ms: Option<u64>,
+}
@@ -0,0 +1,22 @@
+use serde::Deserialize;
+
+/// InfluxDB export configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct InfluxDbConfig {
+    /// The URL of the InfluxDB server to send data to, e.g. `http://localhost:8086`.
+    pub(crate) url: Option<String>,
+
+    /// The API token used to authenticate with InfluxDB.
+    pub(crate) token: Option<String>,
+
+    /// The organization to write data to.
+    pub(crate) org: Option<String>,
+
+    /// The bucket to write data to.
+    pub(crate) bucket: Option<String>,
+
+    /// The maximum number of points sent per request.
+    pub(crate) batch_size: Option<usize>,
+}
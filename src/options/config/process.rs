@@ -5,11 +5,58 @@ use crate::widgets::ProcColumn;
 /// Process configuration.
 #[derive(Clone, Debug, Default, Deserialize)]
 #[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
-#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
 pub(crate) struct ProcessesConfig {
     /// A list of process widget columns.
     #[serde(default)]
     pub(crate) columns: Vec<ProcColumn>, // TODO: make this more composable(?) in the future, we might need to rethink how it's done for custom widgets
+
+    /// Processes with more major page faults per second than this are highlighted.
+    pub(crate) major_fault_threshold: Option<u64>,
+
+    /// Processes with more TCP connections open than this are highlighted.
+    pub(crate) tcp_connection_threshold: Option<u64>,
+
+    /// How long a process can go uncollected before it's removed, in
+    /// milliseconds. Defaults to twice the update rate.
+    pub(crate) stale_threshold_ms: Option<u64>,
+
+    /// How many percentage points the CPU usage of a process has to change by
+    /// between updates for it to be highlighted, when highlighting changes.
+    pub(crate) cpu_change_threshold: Option<f32>,
+
+    /// How many percentage points the memory usage of a process has to change
+    /// by between updates for it to be highlighted, when highlighting changes.
+    pub(crate) mem_change_threshold: Option<f32>,
+
+    /// How many updates sorting by change lasts for after turning it on.
+    pub(crate) change_sort_ticks: Option<u64>,
+
+    /// The columns whose top consumer is highlighted when highlighting top
+    /// consumers. Defaults to the sorted column.
+    #[serde(default)]
+    pub(crate) highlight_top: Vec<ProcColumn>,
+
+    /// The columns that stay in place when scrolling the table horizontally.
+    #[serde(default)]
+    pub(crate) pinned_columns: Vec<ProcColumn>,
+
+    /// The column to sort by at first.
+    pub(crate) sort_by: Option<String>,
+
+    /// The order to sort in at first, either "asc" or "desc".
+    pub(crate) sort_direction: Option<String>,
+
+    /// A regex to filter processes by at first.
+    pub(crate) default_filter: Option<String>,
+
+    /// Processes using less CPU than this, in percent, are hidden at first. 0
+    /// is the same as none.
+    pub(crate) threshold_cpu: Option<f64>,
+
+    /// Processes using less memory than this, in percent, are hidden at first.
+    /// 0 is the same as none.
+    pub(crate) threshold_mem: Option<f64>,
 }
 
 #[cfg(test)]
@@ -92,4 +139,108 @@ mod test {
             vec![ProcWidgetColumn::WritePerSecond; 3]
         );
     }
+
+    #[test]
+    fn page_fault_columns() {
+        let config = r#"
+            columns = ["MinFlt/s", "majflt"]
+            major_fault_threshold = 20
+        "#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![ProcWidgetColumn::MinorFaults, ProcWidgetColumn::MajorFaults]
+        );
+        assert_eq!(generated.major_fault_threshold, Some(20));
+    }
+
+    #[test]
+    fn linux_only_columns() {
+        let config = r#"columns = ["WChan", "wchan", "pid_ns", "NET NS", "mnt_ns", "user_ns", "Quota", "thrtl%", "container"]"#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![
+                ProcWidgetColumn::WChan,
+                ProcWidgetColumn::WChan,
+                ProcWidgetColumn::PidNamespace,
+                ProcWidgetColumn::NetNamespace,
+                ProcWidgetColumn::MntNamespace,
+                ProcWidgetColumn::UserNamespace,
+                ProcWidgetColumn::CpuQuota,
+                ProcWidgetColumn::CpuThrottled,
+                ProcWidgetColumn::Container,
+            ]
+        );
+    }
+
+    #[test]
+    fn tcp_column() {
+        let config = r#"
+            columns = ["PID", "TCP"]
+            tcp_connection_threshold = 500
+        "#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::TcpConnections
+            ]
+        );
+        assert_eq!(generated.tcp_connection_threshold, Some(500));
+    }
+
+    #[test]
+    fn inotify_column() {
+        let config = r#"
+            columns = ["PID", "inotify"]
+        "#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::InotifyWatches
+            ]
+        );
+    }
+
+    #[test]
+    fn sparkline_columns() {
+        let config = r#"
+            columns = ["cpu_sparkline", "Mem Hist"]
+        "#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.columns),
+            vec![
+                ProcWidgetColumn::CpuSparkline,
+                ProcWidgetColumn::MemSparkline
+            ]
+        );
+    }
+
+    #[test]
+    fn highlight_top_columns() {
+        let config = r#"highlight_top = ["CPU%", "mem"]"#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            generated.highlight_top,
+            vec![ProcColumn::CpuPercent, ProcColumn::MemPercent]
+        );
+    }
+
+    #[test]
+    fn pinned_columns() {
+        let config = r#"pinned_columns = ["PID", "Name"]"#;
+        let generated: ProcessesConfig = toml_edit::de::from_str(config).unwrap();
+        assert_eq!(
+            to_columns(generated.pinned_columns),
+            vec![
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::ProcNameOrCommand
+            ]
+        );
+    }
 }
@@ -0,0 +1,62 @@
+//! Generating the JSON schema of the config file.
+
+use itertools::Itertools;
+use strum::VariantArray;
+
+use super::Config;
+use crate::widgets;
+
+/// Generates the JSON schema for the config file of the given version of
+/// bottom, such as "nightly" or "v0.10".
+pub fn generate(version: &str) -> anyhow::Result<String> {
+    let mut schema = schemars::schema_for!(Config);
+    {
+        // TODO: Maybe make this case insensitive? See https://stackoverflow.com/a/68639341
+
+        let proc_columns = schema.definitions.get_mut("ProcColumn").unwrap();
+        match proc_columns {
+            schemars::schema::Schema::Object(proc_columns) => {
+                let enums = proc_columns.enum_values.as_mut().unwrap();
+                *enums = widgets::ProcColumn::VARIANTS
+                    .iter()
+                    .flat_map(|var| var.get_schema_names())
+                    .sorted()
+                    .map(|v| serde_json::Value::String(v.to_string()))
+                    .dedup()
+                    .collect();
+            }
+            _ => anyhow::bail!("missing proc columns definition"),
+        }
+
+        let disk_columns = schema.definitions.get_mut("DiskColumn").unwrap();
+        match disk_columns {
+            schemars::schema::Schema::Object(disk_columns) => {
+                let enums = disk_columns.enum_values.as_mut().unwrap();
+                *enums = widgets::DiskColumn::VARIANTS
+                    .iter()
+                    .flat_map(|var| var.get_schema_names())
+                    .sorted()
+                    .map(|v| serde_json::Value::String(v.to_string()))
+                    .dedup()
+                    .collect();
+            }
+            _ => anyhow::bail!("missing disk columns definition"),
+        }
+    }
+
+    let metadata = schema.schema.metadata.as_mut().unwrap();
+    metadata.id = Some(format!(
+        "https://github.com/ClementTsang/bottom/blob/main/schema/{version}/bottom.json"
+    ));
+    metadata.description = Some(format!(
+        "https://clementtsang.github.io/bottom/{}/configuration/config-file",
+        if version == "nightly" {
+            "nightly"
+        } else {
+            "stable"
+        }
+    ));
+    metadata.title = Some(format!("Schema for bottom's config file ({version})",));
+
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
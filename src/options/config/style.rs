@@ -1,6 +1,7 @@
 //! Config options around styling.
 
 mod battery;
+mod borders;
 mod cpu;
 mod graphs;
 mod memory;
@@ -10,16 +11,17 @@ mod themes;
 mod utils;
 mod widgets;
 
-use std::borrow::Cow;
+use std::{borrow::Cow, ffi::OsString};
 
 use battery::BatteryStyle;
+pub use borders::{BorderStyle, CanvasStyle};
 use cpu::CpuStyle;
 use graphs::GraphStyle;
 use memory::MemoryStyle;
 use network::NetworkStyle;
 use serde::{Deserialize, Serialize};
 use tables::TableStyle;
-use tui::style::Style;
+use tui::style::{Modifier, Style};
 use utils::{opt, set_colour, set_colour_list, set_style};
 use widgets::WidgetStyle;
 
@@ -122,6 +124,12 @@ pub struct ColourPalette {
     pub low_battery: Style,
     pub invalid_query_style: Style,
     pub disabled_text_style: Style,
+    pub highlighted_text_style: Style,
+    pub warning_text_style: Style,
+    pub top_consumer_text_style: Style,
+    pub improved_text_style: Style,
+    pub regressed_text_style: Style,
+    pub stopped_text_style: Style,
 }
 
 impl Default for ColourPalette {
@@ -145,9 +153,63 @@ impl ColourPalette {
             palette.set_colours_from_palette(style)?;
         }
 
+        if args.style.no_color || no_color_env(std::env::var_os("NO_COLOR")) {
+            palette.remove_colours();
+        }
+
         Ok(palette)
     }
 
+    /// Removes all colours from the palette, keeping only text modifiers. Since
+    /// selections are normally only marked by colour, selected text is reversed
+    /// and selected borders are bolded instead.
+    fn remove_colours(&mut self) {
+        fn strip(style: &mut Style) {
+            style.fg = None;
+            style.bg = None;
+        }
+
+        for style in [
+            &mut self.ram_style,
+            #[cfg(not(target_os = "windows"))]
+            &mut self.cache_style,
+            &mut self.swap_style,
+            &mut self.arc_style,
+            &mut self.rx_style,
+            &mut self.tx_style,
+            &mut self.total_rx_style,
+            &mut self.total_tx_style,
+            &mut self.all_cpu_colour,
+            &mut self.avg_cpu_colour,
+            &mut self.border_style,
+            &mut self.highlighted_border_style,
+            &mut self.text_style,
+            &mut self.selected_text_style,
+            &mut self.table_header_style,
+            &mut self.widget_title_style,
+            &mut self.graph_style,
+            &mut self.graph_legend_style,
+            &mut self.high_battery,
+            &mut self.medium_battery,
+            &mut self.low_battery,
+            &mut self.invalid_query_style,
+            &mut self.disabled_text_style,
+            &mut self.highlighted_text_style,
+            &mut self.warning_text_style,
+            &mut self.top_consumer_text_style,
+            &mut self.improved_text_style,
+            &mut self.regressed_text_style,
+            &mut self.stopped_text_style,
+        ] {
+            strip(style);
+        }
+        self.gpu_colours.iter_mut().for_each(strip);
+        self.cpu_colour_styles.iter_mut().for_each(strip);
+
+        self.selected_text_style = self.selected_text_style.add_modifier(Modifier::REVERSED);
+        self.highlighted_border_style = self.highlighted_border_style.add_modifier(Modifier::BOLD);
+    }
+
     fn from_theme(theme: &str) -> anyhow::Result<Self> {
         let lower_case = theme.to_lowercase();
         match lower_case.as_str() {
@@ -206,6 +268,20 @@ impl ColourPalette {
         set_style!(self.text_style, config.widgets, text);
         set_style!(self.selected_text_style, config.widgets, selected_text);
         set_style!(self.disabled_text_style, config.widgets, disabled_text);
+        set_style!(
+            self.highlighted_text_style,
+            config.widgets,
+            highlighted_text
+        );
+        set_style!(self.warning_text_style, config.widgets, warning_text);
+        set_style!(
+            self.top_consumer_text_style,
+            config.widgets,
+            top_consumer_text
+        );
+        set_style!(self.improved_text_style, config.widgets, improved_text);
+        set_style!(self.regressed_text_style, config.widgets, regressed_text);
+        set_style!(self.stopped_text_style, config.widgets, stopped_text);
 
         // Widget borders
         set_colour!(self.border_style, config.widgets, border_color);
@@ -219,13 +295,26 @@ impl ColourPalette {
     }
 }
 
+/// Whether the given value of the `NO_COLOR` environment variable asks for
+/// colours to be turned off, which is the case for any non-empty value. See
+/// <https://no-color.org>.
+fn no_color_env(value: Option<OsString>) -> bool {
+    value.is_some_and(|value| !value.is_empty())
+}
+
 #[cfg(test)]
 mod test {
 
-    use tui::style::{Color, Style};
+    use std::ffi::OsString;
+
+    use clap::Parser;
+    use tui::style::{Color, Modifier, Style};
 
-    use super::ColourPalette;
-    use crate::options::config::style::utils::str_to_colour;
+    use super::{no_color_env, ColourPalette};
+    use crate::options::{
+        args::BottomArgs,
+        config::{style::utils::str_to_colour, Config},
+    };
 
     #[test]
     fn default_selected_colour_works() {
@@ -266,4 +355,50 @@ mod test {
         ColourPalette::from_theme("nord").unwrap();
         ColourPalette::from_theme("nord-light").unwrap();
     }
+
+    #[test]
+    fn no_color_env_values() {
+        assert!(!no_color_env(None));
+        assert!(!no_color_env(Some(OsString::new())));
+        assert!(no_color_env(Some(OsString::from("1"))));
+        assert!(no_color_env(Some(OsString::from("0"))));
+    }
+
+    #[test]
+    fn no_color_removes_all_colours() {
+        let args = BottomArgs::parse_from(["btm", "--no_color", "--theme", "gruvbox"]);
+        let colours = ColourPalette::new(&args, &Config::default()).unwrap();
+
+        let styles = [
+            colours.ram_style,
+            colours.rx_style,
+            colours.avg_cpu_colour,
+            colours.border_style,
+            colours.highlighted_border_style,
+            colours.text_style,
+            colours.selected_text_style,
+            colours.table_header_style,
+            colours.graph_style,
+            colours.low_battery,
+            colours.invalid_query_style,
+            colours.regressed_text_style,
+        ];
+        for style in styles
+            .iter()
+            .chain(&colours.cpu_colour_styles)
+            .chain(&colours.gpu_colours)
+        {
+            assert_eq!((style.fg, style.bg), (None, None));
+        }
+
+        // Selections still have to be visible.
+        assert!(colours
+            .selected_text_style
+            .add_modifier
+            .contains(Modifier::REVERSED));
+        assert!(colours
+            .highlighted_border_style
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
 }
@@ -0,0 +1,301 @@
+use std::{collections::HashMap, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use tui::{
+    symbols::{border, line},
+    widgets::{Block, Borders, Padding},
+};
+
+use crate::{
+    app::layout_manager::BottomWidgetType,
+    constants::SIDE_BORDERS,
+    options::{args::BottomArgs, config::Config, OptionResult},
+};
+
+/// Borders drawn with only ASCII characters, for terminals and fonts without
+/// box-drawing characters.
+const ASCII_BORDERS: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// The lines a widget's borders are drawn with.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum BorderStyle {
+    #[default]
+    Single,
+    Double,
+    Rounded,
+    Bold,
+    /// Lines of `+`, `-`, and `|`.
+    Ascii,
+    /// No borders, just spacing around each widget and its title on a row of
+    /// its own.
+    None,
+}
+
+impl BorderStyle {
+    /// The characters to draw borders with. Widgets without borders still draw
+    /// the sides of the one that's selected, as plain lines.
+    pub fn border_set(self) -> border::Set {
+        match self {
+            BorderStyle::Single | BorderStyle::None => border::PLAIN,
+            BorderStyle::Double => border::DOUBLE,
+            BorderStyle::Rounded => border::ROUNDED,
+            BorderStyle::Bold => border::THICK,
+            BorderStyle::Ascii => ASCII_BORDERS,
+        }
+    }
+
+    /// The characters to draw boxes inside a widget with, like the legend of a
+    /// graph. These are plain lines, unless borders are ASCII.
+    pub fn inner_border_set(self) -> border::Set {
+        match self {
+            BorderStyle::Ascii => ASCII_BORDERS,
+            _ => border::PLAIN,
+        }
+    }
+
+    /// The horizontal line of the top border, to fill out titles with.
+    pub fn horizontal(self) -> &'static str {
+        match self {
+            BorderStyle::Single | BorderStyle::Rounded => line::HORIZONTAL,
+            BorderStyle::Double => line::DOUBLE_HORIZONTAL,
+            BorderStyle::Bold => line::THICK_HORIZONTAL,
+            BorderStyle::Ascii => "-",
+            BorderStyle::None => " ",
+        }
+    }
+
+    /// A block around a whole widget. Without borders, the title takes the
+    /// top row, and the selected widget is marked by its sides, which take up
+    /// the same space as the padding of the others.
+    pub fn block<'a>(self, is_selected: bool) -> Block<'a> {
+        match self {
+            BorderStyle::None if is_selected => Block::default().borders(SIDE_BORDERS),
+            BorderStyle::None => Block::default()
+                .borders(Borders::NONE)
+                .padding(Padding::horizontal(1)),
+            _ => Block::default()
+                .borders(Borders::ALL)
+                .border_set(self.border_set()),
+        }
+    }
+
+    /// The sides drawn around a selected widget in basic mode.
+    pub fn side_block<'a>(self) -> Block<'a> {
+        Block::default()
+            .borders(SIDE_BORDERS)
+            .border_set(self.border_set())
+    }
+}
+
+/// How widgets are drawn, from the `[styles.widgets]` and `[widgets]`
+/// sections of the config, and `--no_unicode_borders`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CanvasStyle {
+    /// The borders of every widget that doesn't override them.
+    pub border_style: BorderStyle,
+    /// The borders of each kind of widget set in `[widgets]`.
+    pub overrides: HashMap<BottomWidgetType, BorderStyle>,
+}
+
+impl CanvasStyle {
+    pub fn new(args: &BottomArgs, config: &Config) -> OptionResult<Self> {
+        let border_style = config
+            .styles
+            .as_ref()
+            .and_then(|styles| styles.widgets.as_ref())
+            .and_then(|widgets| widgets.border_style)
+            .unwrap_or_default();
+
+        let mut overrides = HashMap::new();
+        for (name, widget) in config.widgets.iter().flatten() {
+            let widget_type = BottomWidgetType::from_str(name)?;
+            if let Some(border_style) = widget.border_style {
+                overrides.insert(widget_type, border_style);
+            }
+        }
+
+        let mut style = Self {
+            border_style,
+            overrides,
+        };
+        if args.style.no_unicode_borders {
+            style.use_ascii_borders();
+        }
+
+        Ok(style)
+    }
+
+    /// Draws every border in ASCII instead. Widgets without borders are left
+    /// as they are.
+    fn use_ascii_borders(&mut self) {
+        let to_ascii = |border_style: &mut BorderStyle| {
+            if *border_style != BorderStyle::None {
+                *border_style = BorderStyle::Ascii;
+            }
+        };
+
+        to_ascii(&mut self.border_style);
+        self.overrides.values_mut().for_each(to_ascii);
+    }
+
+    /// The borders of dialogs, which are always drawn, and are ASCII if
+    /// widgets' borders are.
+    pub fn dialog_border_style(&self) -> BorderStyle {
+        match self.border_style {
+            BorderStyle::Ascii => BorderStyle::Ascii,
+            _ => BorderStyle::Single,
+        }
+    }
+
+    /// The borders of a widget. Parts of a widget, like the CPU legend or
+    /// basic mode's version of it, share its borders.
+    pub fn border_style(&self, widget_type: &BottomWidgetType) -> BorderStyle {
+        use BottomWidgetType::*;
+
+        let widget_type = match widget_type {
+            CpuLegend | BasicCpu => &Cpu,
+            BasicMem => &Mem,
+            BasicNet => &Net,
+            ProcSearch | ProcSort => &Proc,
+            other => other,
+        };
+
+        self.overrides
+            .get(widget_type)
+            .copied()
+            .unwrap_or(self.border_style)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn widget_overrides() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [styles.widgets]
+            border_style = "rounded"
+
+            [widgets.cpu]
+            border_style = "double"
+
+            [widgets.processes]
+            border_style = "none"
+
+            [widgets.disk]
+            "#,
+        )
+        .unwrap();
+        let style = CanvasStyle::new(&BottomArgs::parse_from(["btm"]), &config).unwrap();
+
+        assert_eq!(
+            style.border_style(&BottomWidgetType::Mem),
+            BorderStyle::Rounded
+        );
+        assert_eq!(
+            style.border_style(&BottomWidgetType::Disk),
+            BorderStyle::Rounded
+        );
+        assert_eq!(
+            style.border_style(&BottomWidgetType::Cpu),
+            BorderStyle::Double
+        );
+        assert_eq!(
+            style.border_style(&BottomWidgetType::CpuLegend),
+            BorderStyle::Double
+        );
+        assert_eq!(
+            style.border_style(&BottomWidgetType::ProcSearch),
+            BorderStyle::None
+        );
+
+        assert_eq!(
+            CanvasStyle::new(&BottomArgs::parse_from(["btm"]), &Config::default()).unwrap(),
+            CanvasStyle::default()
+        );
+
+        let config: Config = toml_edit::de::from_str("[widgets.cpus]").unwrap();
+        assert!(CanvasStyle::new(&BottomArgs::parse_from(["btm"]), &config).is_err());
+    }
+
+    #[test]
+    fn borderless_blocks() {
+        use tui::layout::Rect;
+
+        let area = Rect::new(0, 0, 20, 10);
+        let title = |block: Block<'static>| block.title(" CPU ").inner(area);
+
+        // The title gets a row of its own, and the sides are kept either way so
+        // selecting a widget doesn't move what's in it.
+        let unselected = title(BorderStyle::None.block(false));
+        assert_eq!(unselected, Rect::new(1, 1, 18, 9));
+        assert_eq!(title(BorderStyle::None.block(true)), unselected);
+
+        assert_eq!(
+            title(BorderStyle::Double.block(false)),
+            Rect::new(1, 1, 18, 8)
+        );
+        assert_eq!(BorderStyle::None.horizontal(), " ");
+    }
+
+    #[test]
+    fn ascii_borders() {
+        let config: Config = toml_edit::de::from_str(
+            r#"
+            [styles.widgets]
+            border_style = "double"
+
+            [widgets.cpu]
+            border_style = "rounded"
+
+            [widgets.processes]
+            border_style = "none"
+            "#,
+        )
+        .unwrap();
+        let args = BottomArgs::parse_from(["btm", "--no-unicode-borders"]);
+        let style = CanvasStyle::new(&args, &config).unwrap();
+
+        assert_eq!(
+            style.border_style(&BottomWidgetType::Mem),
+            BorderStyle::Ascii
+        );
+        assert_eq!(
+            style.border_style(&BottomWidgetType::Cpu),
+            BorderStyle::Ascii
+        );
+        assert_eq!(
+            style.border_style(&BottomWidgetType::Proc),
+            BorderStyle::None
+        );
+        assert_eq!(style.dialog_border_style(), BorderStyle::Ascii);
+        assert_eq!(
+            CanvasStyle::default().dialog_border_style(),
+            BorderStyle::Single
+        );
+
+        let area = tui::layout::Rect::new(0, 0, 4, 3);
+        let mut buf = tui::buffer::Buffer::empty(area);
+        tui::widgets::Widget::render(BorderStyle::Ascii.block(false), area, &mut buf);
+        assert_eq!(
+            buf,
+            tui::buffer::Buffer::with_lines(["+--+", "|  |", "+--+"])
+        );
+        assert_eq!(BorderStyle::Ascii.horizontal(), "-");
+    }
+}
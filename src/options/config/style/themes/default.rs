@@ -61,6 +61,12 @@ impl ColourPalette {
             low_battery: color!(Color::Red),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: color!(Color::DarkGray),
+            highlighted_text_style: color!(Color::Green),
+            warning_text_style: color!(Color::Rgb(255, 135, 0)),
+            top_consumer_text_style: color!(Color::LightMagenta),
+            improved_text_style: color!(Color::Green),
+            regressed_text_style: color!(Color::Red),
+            stopped_text_style: color!(Color::Yellow),
         }
     }
 
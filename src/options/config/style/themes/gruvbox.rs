@@ -61,6 +61,12 @@ impl ColourPalette {
             low_battery: hex!("#fb4934"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#665c54"),
+            highlighted_text_style: hex!("#b8bb26"),
+            warning_text_style: hex!("#fe8019"),
+            top_consumer_text_style: hex!("#d3869b"),
+            improved_text_style: hex!("#b8bb26"),
+            regressed_text_style: hex!("#fb4934"),
+            stopped_text_style: hex!("#fabd2f"),
         }
     }
 
@@ -121,6 +127,12 @@ impl ColourPalette {
             low_battery: hex!("#cc241d"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d5c4a1"),
+            highlighted_text_style: hex!("#79740e"),
+            warning_text_style: hex!("#af3a03"),
+            top_consumer_text_style: hex!("#8f3f71"),
+            improved_text_style: hex!("#79740e"),
+            regressed_text_style: hex!("#9d0006"),
+            stopped_text_style: hex!("#d79921"),
         }
     }
 }
@@ -49,6 +49,12 @@ impl ColourPalette {
             low_battery: hex!("#bf616a"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#4c566a"),
+            highlighted_text_style: hex!("#a3be8c"),
+            warning_text_style: hex!("#d08770"),
+            top_consumer_text_style: hex!("#b48ead"),
+            improved_text_style: hex!("#a3be8c"),
+            regressed_text_style: hex!("#bf616a"),
+            stopped_text_style: hex!("#ebcb8b"),
         }
     }
 
@@ -97,6 +103,12 @@ impl ColourPalette {
             low_battery: hex!("#bf616a"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d8dee9"),
+            highlighted_text_style: hex!("#a3be8c"),
+            warning_text_style: hex!("#d08770"),
+            top_consumer_text_style: hex!("#b48ead"),
+            improved_text_style: hex!("#a3be8c"),
+            regressed_text_style: hex!("#bf616a"),
+            stopped_text_style: hex!("#ebcb8b"),
         }
     }
 }
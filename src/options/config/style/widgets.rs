@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::{ColorStr, TextStyleConfig};
+use super::{BorderStyle, ColorStr, TextStyleConfig};
 
 /// General styling for generic widgets.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -15,6 +15,10 @@ pub(crate) struct WidgetStyle {
     #[serde(alias = "selected_border_colour")]
     pub(crate) selected_border_color: Option<ColorStr>,
 
+    /// The lines the widgets' borders are drawn with, unless overridden for a
+    /// widget in `[widgets]`.
+    pub(crate) border_style: Option<BorderStyle>,
+
     /// Text styling for a widget's title.
     pub(crate) widget_title: Option<TextStyleConfig>,
 
@@ -26,4 +30,22 @@ pub(crate) struct WidgetStyle {
 
     /// Text styling for text when representing something that is disabled.
     pub(crate) disabled_text: Option<TextStyleConfig>,
+
+    /// Text styling for text when representing something that is highlighted.
+    pub(crate) highlighted_text: Option<TextStyleConfig>,
+
+    /// Text styling for text when representing something that needs attention.
+    pub(crate) warning_text: Option<TextStyleConfig>,
+
+    /// Text styling for the top consumer in the process widget, when highlighted.
+    pub(crate) top_consumer_text: Option<TextStyleConfig>,
+
+    /// Text styling for values that improved compared to the baseline, in diff mode.
+    pub(crate) improved_text: Option<TextStyleConfig>,
+
+    /// Text styling for values that regressed compared to the baseline, in diff mode.
+    pub(crate) regressed_text: Option<TextStyleConfig>,
+
+    /// Text styling for the state of stopped processes in the process widget.
+    pub(crate) stopped_text: Option<TextStyleConfig>,
 }
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+/// Configuration for when data is collected.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
+pub(crate) struct TimingConfig {
+    /// How many percent of the refresh rate each update is randomly moved
+    /// earlier or later by, between 0 and 100.
+    pub(crate) jitter_pct: Option<u64>,
+    /// How many times in a row a part of data collection, like temperature
+    /// sensors, can fail before it's no longer read. At least 1.
+    pub(crate) subsystem_retry_count: Option<u32>,
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+use super::style::BorderStyle;
+
+/// Configuration for one kind of widget, in `[widgets.<name>]`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq))]
+pub(crate) struct WidgetConfig {
+    /// The lines this widget's borders are drawn with, over the one set in
+    /// `[styles.widgets]`.
+    pub(crate) border_style: Option<BorderStyle>,
+}
@@ -0,0 +1,5 @@
+//! Plain-text formats that a sample can be printed in instead of starting the
+//! interface.
+
+pub(crate) mod raw;
+pub(crate) mod table;
@@ -0,0 +1,471 @@
+//! A raw output mode, which collects a single sample and prints it as
+//! `key=value` lines for scripts that don't want to parse JSON.
+//!
+//! Keys are dot-separated, starting with a namespace like `cpu`, `mem`, or
+//! `proc`, and are kept stable across versions: renaming or removing one is a
+//! breaking change. Names that come from the system, like those of disks or
+//! sensors, have any dots, equals signs, and whitespace replaced with `_` so
+//! they can't be confused with the rest of the key.
+
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use crate::{
+    app::{event_log::total_cpu_usage, layout_manager::UsedWidgets, AppConfigFields, DataFilters},
+    data_collection::{cpu::CpuDataType, memory::MemHarvest, Data},
+    export::collect_once,
+};
+
+/// The patterns of the keys to print, from `--raw_filter`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RawFilter {
+    patterns: Vec<String>,
+}
+
+impl RawFilter {
+    /// Parses a comma-separated list of glob patterns, like `cpu.*,mem.*`.
+    pub(crate) fn new(patterns: &str) -> Self {
+        Self {
+            patterns: patterns
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// Whether a key matches any of the patterns.
+    fn matches(&self, key: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| glob_matches(pattern.as_bytes(), key.as_bytes()))
+    }
+}
+
+/// Matches a key against a glob pattern, where `*` matches any run of
+/// characters, dots included, and `?` matches any single one.
+fn glob_matches(pattern: &[u8], key: &[u8]) -> bool {
+    let (mut p, mut k) = (0, 0);
+    // Where the last `*` was, and where in the key it started matching from.
+    let mut backtrack = None;
+
+    while k < key.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, k));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == key[k] => {
+                p += 1;
+                k += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` match one more character.
+                Some((star, start)) => {
+                    p = star + 1;
+                    k = start + 1;
+                    backtrack = Some((star, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p.min(pattern.len())..].iter().all(|&c| c == b'*')
+}
+
+/// Makes a name from the system safe to use as part of a key.
+fn key_part(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c == '.' || c == '=' || c.is_whitespace() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// The lines of a sample, in the order they're printed.
+#[derive(Default)]
+struct RawLines(Vec<(String, String)>);
+
+impl RawLines {
+    /// Adds a line. Values are formatted with [`Display`], so numbers always
+    /// use `.` as the decimal separator whatever the locale. Newlines are
+    /// replaced with spaces so each value stays on its line.
+    fn push(&mut self, key: String, value: impl Display) {
+        self.0
+            .push((key, value.to_string().replace(['\n', '\r'], " ")));
+    }
+
+    /// Adds a percentage, rounded to one decimal place.
+    fn push_percent(&mut self, key: String, value: f64) {
+        self.push(key, format!("{value:.1}"));
+    }
+
+    fn push_mem(&mut self, namespace: &str, mem: &MemHarvest) {
+        self.push(format!("{namespace}.used_bytes"), mem.used_bytes);
+        self.push(format!("{namespace}.total_bytes"), mem.total_bytes);
+        if let Some(percent) = mem.use_percent {
+            self.push_percent(format!("{namespace}.percent"), percent);
+        }
+    }
+}
+
+/// Turns a sample into its lines, skipping whatever wasn't collected.
+fn raw_lines(data: &Data) -> RawLines {
+    let mut lines = RawLines::default();
+
+    if let Some(cpu) = &data.cpu {
+        if let Some(avg) = total_cpu_usage(cpu) {
+            lines.push_percent("cpu.avg".to_string(), avg);
+        }
+        for core in cpu {
+            if let CpuDataType::Cpu(index) = core.data_type {
+                lines.push_percent(format!("cpu.{index}"), core.cpu_usage);
+            }
+        }
+    }
+    if let Some(times) = &data.cpu_times {
+        for (name, value) in [
+            ("user", times.user),
+            ("system", times.system),
+            ("idle", times.idle),
+            ("iowait", times.iowait),
+            ("steal", times.steal),
+        ] {
+            lines.push_percent(format!("cpu.time.{name}"), value);
+        }
+    }
+    if let Some(load_avg) = &data.load_avg {
+        for (name, value) in ["1", "5", "15"].into_iter().zip(load_avg) {
+            lines.push(format!("load.{name}"), format!("{value:.2}"));
+        }
+    }
+    if let Some(tasks) = &data.sched_tasks {
+        lines.push("tasks.running".to_string(), tasks.running);
+        lines.push("tasks.total".to_string(), tasks.total);
+    }
+
+    if let Some(memory) = &data.memory {
+        lines.push_mem("mem", memory);
+    }
+    if let Some(limits) = &data.cgroup_memory {
+        lines.push("mem.cgroup.max_bytes".to_string(), limits.max_bytes);
+        if let Some(high_bytes) = limits.high_bytes {
+            lines.push("mem.cgroup.high_bytes".to_string(), high_bytes);
+        }
+        lines.push(
+            "mem.physical_total_bytes".to_string(),
+            limits.physical_total_bytes,
+        );
+    }
+    #[cfg(not(target_os = "windows"))]
+    if let Some(cache) = &data.cache {
+        lines.push_mem("cache", cache);
+    }
+    if let Some(swap) = &data.swap {
+        lines.push_mem("swap", swap);
+    }
+    #[cfg(feature = "zfs")]
+    if let Some(arc) = &data.arc {
+        lines.push_mem("arc", arc);
+    }
+    #[cfg(feature = "gpu")]
+    if let Some(gpus) = &data.gpu {
+        for (name, mem) in gpus {
+            lines.push_mem(&format!("gpu.{}", key_part(name)), mem);
+        }
+    }
+    #[cfg(feature = "gpu")]
+    if let (Some(gpus), Some(usage)) = (&data.gpu, &data.gpu_usage) {
+        for ((name, _), usage) in gpus.iter().zip(usage) {
+            let name = key_part(name);
+            if let Some(utilization) = usage.utilization {
+                lines.push(format!("gpu.{name}.utilization_percent"), utilization);
+            }
+            if let Some(power) = usage.power_watts {
+                lines.push(format!("gpu.{name}.power_watts"), format!("{power:.1}"));
+            }
+        }
+    }
+
+    if let Some(network) = &data.network {
+        lines.push("net.rx_bits_per_sec".to_string(), network.rx);
+        lines.push("net.tx_bits_per_sec".to_string(), network.tx);
+        lines.push("net.total_rx_bits".to_string(), network.total_rx);
+        lines.push("net.total_tx_bits".to_string(), network.total_tx);
+        for interface in &network.interfaces {
+            let name = key_part(&interface.name);
+            lines.push(
+                format!("net.interface.{name}.rx_bits_per_sec"),
+                interface.rx,
+            );
+            lines.push(
+                format!("net.interface.{name}.tx_bits_per_sec"),
+                interface.tx,
+            );
+        }
+    }
+
+    if let Some(disks) = &data.disks {
+        for disk in disks {
+            let name = key_part(&disk.name);
+            lines.push(format!("disk.{name}.mount_point"), &disk.mount_point);
+            for (field, value) in [
+                ("used_bytes", disk.used_space),
+                ("free_bytes", disk.free_space),
+                ("total_bytes", disk.total_space),
+            ] {
+                if let Some(value) = value {
+                    lines.push(format!("disk.{name}.{field}"), value);
+                }
+            }
+        }
+    }
+    if let Some(io) = &data.io {
+        let mut io = io
+            .iter()
+            .filter_map(|(name, io)| Some((name, io.as_ref()?)))
+            .collect::<Vec<_>>();
+        io.sort_by_key(|(name, _)| *name);
+
+        for (name, io) in io {
+            let name = key_part(name);
+            lines.push(format!("io.{name}.read_bytes"), io.read_bytes);
+            lines.push(format!("io.{name}.write_bytes"), io.write_bytes);
+        }
+    }
+
+    if let Some(sensors) = &data.temperature_sensors {
+        for sensor in sensors {
+            if let Some(temperature) = sensor.temperature {
+                lines.push(
+                    format!("temp.{}", key_part(&sensor.name)),
+                    format!("{temperature:.1}"),
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "battery")]
+    if let Some(batteries) = &data.list_of_batteries {
+        for (index, battery) in batteries.iter().enumerate() {
+            lines.push_percent(
+                format!("battery.{index}.charge_percent"),
+                battery.charge_percent,
+            );
+            lines.push_percent(
+                format!("battery.{index}.health_percent"),
+                battery.health_percent,
+            );
+            lines.push(
+                format!("battery.{index}.power_watts"),
+                format!("{:.2}", battery.power_consumption_rate_watts),
+            );
+        }
+    }
+
+    if let Some(processes) = &data.list_of_processes {
+        let mut processes = processes.iter().collect::<Vec<_>>();
+        processes.sort_by_key(|process| process.pid);
+
+        for process in processes {
+            let pid = process.pid;
+            lines.push(format!("proc.{pid}.name"), &process.name);
+            lines.push(format!("proc.{pid}.command"), &process.command);
+            if let Some(parent_pid) = process.parent_pid {
+                lines.push(format!("proc.{pid}.parent_pid"), parent_pid);
+            }
+            lines.push(format!("proc.{pid}.user"), &process.user);
+            lines.push(format!("proc.{pid}.state"), &process.process_state.0);
+            lines.push_percent(
+                format!("proc.{pid}.cpu"),
+                f64::from(process.cpu_usage_percent),
+            );
+            lines.push(format!("proc.{pid}.mem_bytes"), process.mem_usage_bytes);
+            lines.push_percent(
+                format!("proc.{pid}.mem_percent"),
+                f64::from(process.mem_usage_percent),
+            );
+            lines.push(
+                format!("proc.{pid}.read_bytes_per_sec"),
+                process.read_bytes_per_sec,
+            );
+            lines.push(
+                format!("proc.{pid}.write_bytes_per_sec"),
+                process.write_bytes_per_sec,
+            );
+            if let Some(num_threads) = process.num_threads {
+                lines.push(format!("proc.{pid}.threads"), num_threads);
+            }
+        }
+    }
+
+    lines
+}
+
+/// Writes the lines of a sample with keys matching the filter, or all of them
+/// without one.
+fn write_lines(data: &Data, filter: Option<&RawFilter>, writer: &mut dyn Write) -> io::Result<()> {
+    for (key, value) in raw_lines(data).0 {
+        if filter.map_or(true, |filter| filter.matches(&key)) {
+            writeln!(writer, "{key}={value}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes every line of a sample as `key=value`.
+pub fn print_raw(data: &Data, writer: &mut dyn Write) -> io::Result<()> {
+    write_lines(data, None, writer)
+}
+
+/// Collects a sample and prints it as `key=value` lines, without starting the
+/// interface.
+pub(crate) fn run(
+    app_config_fields: &AppConfigFields, filters: DataFilters, filter: Option<RawFilter>,
+) -> anyhow::Result<()> {
+    let data = collect_once(
+        app_config_fields,
+        filters,
+        UsedWidgets {
+            use_cpu: true,
+            use_mem: true,
+            use_cache: true,
+            use_net: true,
+            use_proc: true,
+            use_disk: true,
+            use_temp: true,
+            use_battery: true,
+            ..Default::default()
+        },
+    );
+
+    let mut stdout = io::stdout().lock();
+    match &filter {
+        Some(filter) => write_lines(&data, Some(filter), &mut stdout)?,
+        None => print_raw(&data, &mut stdout)?,
+    }
+    stdout.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data_collection::{
+        cpu::CpuData, network::NetworkHarvest, processes::ProcessHarvest, temperature::TempHarvest,
+    };
+
+    fn sample() -> Data {
+        Data {
+            cpu: Some(vec![
+                CpuData {
+                    data_type: CpuDataType::Cpu(0),
+                    cpu_usage: 30.0,
+                },
+                CpuData {
+                    data_type: CpuDataType::Cpu(1),
+                    cpu_usage: 38.4,
+                },
+            ]),
+            memory: Some(MemHarvest {
+                used_bytes: 4294967296,
+                total_bytes: 8589934592,
+                use_percent: Some(50.0),
+            }),
+            network: Some(NetworkHarvest {
+                rx: 800,
+                tx: 80,
+                ..Default::default()
+            }),
+            temperature_sensors: Some(vec![TempHarvest {
+                name: "k10temp: Tctl".to_string(),
+                temperature: Some(45.0),
+            }]),
+            list_of_processes: Some(vec![
+                ProcessHarvest {
+                    pid: 1234,
+                    name: "nginx".to_string(),
+                    command: "nginx -g daemon off;\n".to_string(),
+                    cpu_usage_percent: 12.3,
+                    ..Default::default()
+                },
+                ProcessHarvest {
+                    pid: 1,
+                    name: "init".to_string(),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn output(data: &Data, filter: Option<&RawFilter>) -> String {
+        let mut output = Vec::new();
+        write_lines(data, filter, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn lines() {
+        let output = output(&sample(), None);
+        let lines = output.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines[..6],
+            [
+                "cpu.avg=34.2",
+                "cpu.0=30.0",
+                "cpu.1=38.4",
+                "mem.used_bytes=4294967296",
+                "mem.total_bytes=8589934592",
+                "mem.percent=50.0",
+            ]
+        );
+        assert!(lines.contains(&"net.rx_bits_per_sec=800"));
+        assert!(lines.contains(&"temp.k10temp:_Tctl=45.0"));
+        assert!(lines.contains(&"proc.1234.name=nginx"));
+        assert!(lines.contains(&"proc.1234.cpu=12.3"));
+        assert!(lines.contains(&"proc.1234.command=nginx -g daemon off; "));
+
+        // Processes are sorted by PID.
+        let init = lines.iter().position(|line| *line == "proc.1.name=init");
+        let nginx = lines
+            .iter()
+            .position(|line| *line == "proc.1234.name=nginx");
+        assert!(init < nginx);
+    }
+
+    #[test]
+    fn filter() {
+        let filter = RawFilter::new("cpu.*, mem.used_bytes,proc.*.name");
+        assert_eq!(
+            output(&sample(), Some(&filter)),
+            "cpu.avg=34.2\ncpu.0=30.0\ncpu.1=38.4\nmem.used_bytes=4294967296\n\
+            proc.1.name=init\nproc.1234.name=nginx\n"
+        );
+    }
+
+    #[test]
+    fn globs() {
+        assert!(glob_matches(b"cpu.*", b"cpu.avg"));
+        assert!(glob_matches(b"*", b""));
+        assert!(glob_matches(b"proc.*.cpu", b"proc.1234.cpu"));
+        assert!(glob_matches(b"cpu.?", b"cpu.1"));
+        assert!(glob_matches(b"*_bytes", b"mem.used_bytes"));
+        assert!(glob_matches(b"a*b*c", b"aXbYbZc"));
+        assert!(!glob_matches(b"cpu.?", b"cpu.10"));
+        assert!(!glob_matches(b"cpu", b"cpu.avg"));
+        assert!(!glob_matches(b"proc.*.cpu", b"proc.1234.cpu_time"));
+        assert!(!glob_matches(b"mem.*", b"swap.used_bytes"));
+    }
+}
@@ -0,0 +1,486 @@
+//! A table output mode for `--once`, which collects a single sample and
+//! prints its processes like `ps aux` does, with the columns of the process
+//! widget.
+//!
+//! Each column is as wide as its widest value, numbers are right-aligned, and
+//! columns are separated by two spaces, so the output can be split with `awk`
+//! or `cut`. Long text is truncated, and line breaks in commands are replaced,
+//! so every process is on a single line.
+//!
+//! With `--format json`, the same processes are printed as JSON instead, with
+//! every field they have in exported snapshots.
+
+use std::{
+    collections::HashMap,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use unicode_ellipsis::truncate_str;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::{layout_manager::UsedWidgets, AppConfigFields, DataFilters},
+    canvas::components::data_table::{ColumnHeader, SortOrder, SortsRow},
+    data_collection::{processes::ProcessHarvest, Data},
+    data_conversion::binary_byte_string,
+    export::collect_once,
+    widgets::{ProcColumn, ProcWidgetData},
+};
+
+/// The columns shown without `--columns`, the same as the process widget's.
+pub(crate) const DEFAULT_COLUMNS: [ProcColumn; 11] = [
+    ProcColumn::Pid,
+    ProcColumn::Name,
+    ProcColumn::CpuPercent,
+    ProcColumn::MemPercent,
+    ProcColumn::ReadPerSecond,
+    ProcColumn::WritePerSecond,
+    ProcColumn::TotalRead,
+    ProcColumn::TotalWrite,
+    ProcColumn::User,
+    ProcColumn::State,
+    ProcColumn::Time,
+];
+
+/// How `--once` prints processes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum OnceFormat {
+    /// An aligned table of the chosen columns.
+    #[default]
+    Table,
+    /// A JSON array of the processes, in the format of exported snapshots.
+    Json,
+}
+
+/// What `--once` prints, and how.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TableSettings {
+    pub format: OnceFormat,
+    /// The columns of the table. JSON always has every field.
+    pub columns: Vec<ProcColumn>,
+    pub sort_by: ProcColumn,
+    pub sort_order: SortOrder,
+    /// How many processes are printed at most, if limited.
+    pub top_n: Option<usize>,
+    /// Whether CPU usage is a percentage of a single core, rather than of all
+    /// cores together.
+    pub is_cpu_per_core: bool,
+}
+
+/// How wide text columns can get before they're truncated.
+const MAX_TEXT_WIDTH: usize = 48;
+
+const SEPARATOR: &str = "  ";
+
+/// Whether a column holds numbers, which are right-aligned.
+fn is_numeric(column: &ProcColumn) -> bool {
+    !matches!(
+        column,
+        ProcColumn::Name
+            | ProcColumn::Command
+            | ProcColumn::State
+            | ProcColumn::User
+            | ProcColumn::CpuSparkline
+            | ProcColumn::MemSparkline
+            | ProcColumn::WChan
+            | ProcColumn::Container
+    )
+}
+
+/// The text of a process' cell. Unlike in the widget, names and commands, and
+/// memory as a value and as a percentage, can be shown side by side.
+fn cell(process: &ProcessHarvest, row: &ProcWidgetData, column: &ProcColumn) -> String {
+    let text = match column {
+        ProcColumn::Name => process.name.clone(),
+        ProcColumn::Command => process.command.clone(),
+        ProcColumn::MemValue => binary_byte_string(process.mem_usage_bytes),
+        ProcColumn::MemPercent => format!("{:.1}%", process.mem_usage_percent),
+        column => row.to_string(column),
+    };
+
+    let text = if text.contains(char::is_control) {
+        text.replace(char::is_control, " ")
+    } else {
+        text
+    };
+    if is_numeric(column) {
+        text
+    } else {
+        truncate_str(text.trim_end(), MAX_TEXT_WIDTH).into_owned()
+    }
+}
+
+/// Pads `text` to `width` columns of the terminal, on the left if it's a
+/// number.
+fn pad(text: &str, width: usize, is_numeric: bool) -> String {
+    let padding = " ".repeat(width.saturating_sub(text.width()));
+    if is_numeric {
+        padding + text
+    } else {
+        format!("{text}{padding}")
+    }
+}
+
+/// The processes of `data` with their rows, sorted and limited by `settings`.
+fn sorted_processes<'a>(
+    data: &'a Data, settings: &TableSettings,
+) -> Vec<(&'a ProcessHarvest, ProcWidgetData)> {
+    let processes = data
+        .list_of_processes
+        .iter()
+        .flatten()
+        .map(|process| (process.pid, process))
+        .collect::<HashMap<_, _>>();
+
+    let mut sorted = processes
+        .values()
+        .map(|process| {
+            let mut row = ProcWidgetData::from_data(process, false, true);
+            row.cpu_usage_percent = if settings.is_cpu_per_core {
+                process.cpu_usage_percent_per_core
+            } else {
+                process.cpu_usage_percent_of_total
+            };
+            row
+        })
+        .collect::<Vec<_>>();
+    // Sorting is stable, so ties are in PID order.
+    sorted.sort_by_key(|row| row.pid);
+    settings
+        .sort_by
+        .sort_data(&mut sorted, settings.sort_order == SortOrder::Descending);
+    if let Some(top_n) = settings.top_n {
+        sorted.truncate(top_n);
+    }
+
+    sorted
+        .into_iter()
+        .map(|row| (processes[&row.pid], row))
+        .collect()
+}
+
+/// Writes the processes of `data` as a table of the columns in `settings`,
+/// sorted and limited by them, with a header row.
+pub(crate) fn print_table(
+    data: &Data, settings: &TableSettings, writer: &mut dyn Write,
+) -> io::Result<()> {
+    let columns = settings.columns.as_slice();
+    let sorted = sorted_processes(data, settings);
+
+    let header = columns
+        .iter()
+        .map(|column| column.text().into_owned())
+        .collect::<Vec<_>>();
+    let rows = sorted
+        .iter()
+        .map(|(process, row)| {
+            columns
+                .iter()
+                .map(|column| cell(process, row, column))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let widths = (0..columns.len())
+        .map(|index| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[index].width())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(columns)
+            .zip(&widths)
+            .map(|((text, column), width)| pad(text, *width, is_numeric(column)))
+            .collect::<Vec<_>>()
+            .join(SEPARATOR);
+        writeln!(writer, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+/// Writes the processes of `data` as a JSON array, sorted and limited by
+/// `settings`. Each has its CPU usage both per core and of all cores.
+pub(crate) fn print_json(
+    data: &Data, settings: &TableSettings, writer: &mut dyn Write,
+) -> io::Result<()> {
+    let processes = sorted_processes(data, settings)
+        .into_iter()
+        .map(|(process, _)| process)
+        .collect::<Vec<_>>();
+
+    serde_json::to_writer_pretty(&mut *writer, &processes)?;
+    writeln!(writer)
+}
+
+/// Shows `output` in `$PAGER`, or `less` if that isn't set. Returns [`None`]
+/// if the pager couldn't be started.
+fn page(output: &[u8]) -> Option<io::Result<()>> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let mut parts = pager.split_whitespace();
+    let mut command = Command::new(parts.next()?);
+    command.args(parts).stdin(Stdio::piped());
+    // Like git, short tables are printed as is and long lines aren't wrapped.
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRSX");
+    }
+
+    let mut child = command.spawn().ok()?;
+    let written = child
+        .stdin
+        .take()
+        .map(|mut stdin| stdin.write_all(output))
+        .unwrap_or(Ok(()));
+
+    Some(written.and_then(|_| child.wait().map(|_| ())))
+}
+
+/// Quitting a pager or `head` early closes the pipe, which isn't an error.
+fn ignore_broken_pipe(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+pub(crate) fn run(
+    app_config_fields: &AppConfigFields, filters: DataFilters, settings: &TableSettings,
+) -> anyhow::Result<()> {
+    let data = collect_once(
+        app_config_fields,
+        filters,
+        UsedWidgets {
+            use_cpu: true,
+            use_mem: true,
+            use_proc: true,
+            ..Default::default()
+        },
+    );
+
+    let mut output = Vec::new();
+    match settings.format {
+        OnceFormat::Table => print_table(&data, settings, &mut output)?,
+        OnceFormat::Json => print_json(&data, settings, &mut output)?,
+    }
+
+    if io::stdout().is_terminal() {
+        if let Some(result) = page(&output) {
+            return Ok(ignore_broken_pipe(result)?);
+        }
+    }
+
+    let mut stdout = io::stdout().lock();
+    ignore_broken_pipe(stdout.write_all(&output).and_then(|_| stdout.flush()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn settings(columns: &[ProcColumn]) -> TableSettings {
+        TableSettings {
+            format: OnceFormat::Table,
+            columns: columns.to_vec(),
+            sort_by: ProcColumn::CpuPercent,
+            sort_order: SortOrder::Descending,
+            top_n: None,
+            is_cpu_per_core: false,
+        }
+    }
+
+    fn output(data: &Data, settings: &TableSettings) -> String {
+        let mut output = Vec::new();
+        print_table(data, settings, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn sample() -> Data {
+        let mut processes = vec![
+            ProcessHarvest {
+                pid: 1,
+                name: "init".to_string(),
+                command: "/sbin/init splash".to_string(),
+                cpu_usage_percent: 0.5,
+                mem_usage_bytes: 12 * 1024 * 1024,
+                mem_usage_percent: 0.1,
+                ..Default::default()
+            },
+            ProcessHarvest {
+                pid: 1234,
+                name: "nginx".to_string(),
+                command: "nginx -g daemon off;\n".to_string(),
+                cpu_usage_percent: 12.3,
+                mem_usage_percent: 1.5,
+                time: Duration::from_secs(75),
+                ..Default::default()
+            },
+            ProcessHarvest {
+                pid: 42,
+                name: "bash".to_string(),
+                command: "-bash".to_string(),
+                cpu_usage_percent: 2.0,
+                mem_usage_percent: 0.2,
+                ..Default::default()
+            },
+        ];
+        for process in &mut processes {
+            process.set_cpu_usage_modes(false, 4);
+        }
+
+        Data {
+            list_of_processes: Some(processes),
+            ..Default::default()
+        }
+    }
+
+    /// The PIDs of the rows of a table that starts with the PID column.
+    fn pids(output: &str) -> Vec<&str> {
+        output
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_whitespace().next())
+            .collect()
+    }
+
+    #[test]
+    fn aligned_columns() {
+        let settings = settings(&[
+            ProcColumn::Pid,
+            ProcColumn::Name,
+            ProcColumn::CpuPercent,
+            ProcColumn::MemPercent,
+            ProcColumn::Command,
+        ]);
+
+        assert_eq!(
+            output(&sample(), &settings).lines().collect::<Vec<_>>(),
+            [
+                " PID  Name    CPU%  Mem%  Command",
+                "1234  nginx  12.3%  1.5%  nginx -g daemon off;",
+                "  42  bash    2.0%  0.2%  -bash",
+                "   1  init    0.5%  0.1%  /sbin/init splash",
+            ]
+        );
+        assert_eq!(
+            output(&Data::default(), &settings),
+            "PID  Name  CPU%  Mem%  Command\n"
+        );
+    }
+
+    #[test]
+    fn truncated_text() {
+        let data = Data {
+            list_of_processes: Some(vec![ProcessHarvest {
+                pid: 7,
+                name: "a".repeat(100),
+                mem_usage_bytes: 2048,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        let output = output(&data, &settings(&[ProcColumn::Name, ProcColumn::MemValue]));
+        let lines = output.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines[1],
+            format!("{}…  2KiB", "a".repeat(MAX_TEXT_WIDTH - 1))
+        );
+        assert_eq!(
+            lines[0],
+            format!("Name{}Mem", " ".repeat(MAX_TEXT_WIDTH - 4 + 3))
+        );
+    }
+
+    #[test]
+    fn top_n() {
+        let settings = TableSettings {
+            top_n: Some(2),
+            ..settings(&[ProcColumn::Pid])
+        };
+        assert_eq!(pids(&output(&sample(), &settings)), ["1234", "42"]);
+
+        let settings = TableSettings {
+            top_n: Some(10),
+            ..settings
+        };
+        assert_eq!(pids(&output(&sample(), &settings)), ["1234", "42", "1"]);
+    }
+
+    #[test]
+    fn sorted_rows() {
+        let sorted = |sort_by, sort_order| {
+            let settings = TableSettings {
+                sort_by,
+                sort_order,
+                ..settings(&[ProcColumn::Pid, ProcColumn::Name])
+            };
+            pids(&output(&sample(), &settings))
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sorted(ProcColumn::Pid, SortOrder::Ascending),
+            ["1", "42", "1234"]
+        );
+        assert_eq!(
+            sorted(ProcColumn::Name, SortOrder::Ascending),
+            ["42", "1", "1234"]
+        );
+        assert_eq!(
+            sorted(ProcColumn::MemPercent, SortOrder::Descending),
+            ["1234", "42", "1"]
+        );
+        assert_eq!(
+            sorted(ProcColumn::CpuPercent, SortOrder::Ascending),
+            ["1", "42", "1234"]
+        );
+    }
+
+    #[test]
+    fn cpu_per_core() {
+        let columns = [ProcColumn::Pid, ProcColumn::CpuPercent];
+        let of_total = output(&sample(), &settings(&columns));
+        let per_core = output(
+            &sample(),
+            &TableSettings {
+                is_cpu_per_core: true,
+                ..settings(&columns)
+            },
+        );
+
+        assert_eq!(of_total.lines().nth(1), Some("1234  12.3%"));
+        assert_eq!(per_core.lines().nth(1), Some("1234  49.2%"));
+    }
+
+    #[test]
+    fn json() {
+        let settings = TableSettings {
+            format: OnceFormat::Json,
+            top_n: Some(2),
+            ..settings(&[ProcColumn::Pid])
+        };
+        let mut output = Vec::new();
+        print_json(&sample(), &settings, &mut output).unwrap();
+        let processes: Vec<serde_json::Value> = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0]["pid"], 1234);
+        assert_eq!(processes[0]["name"], "nginx");
+        assert_eq!(processes[1]["pid"], 42);
+        assert!(processes[0]["cpu_usage_percent_per_core"].as_f64().unwrap() > 49.0);
+        assert!(processes[0]["cpu_usage_percent_of_total"].as_f64().unwrap() < 12.5);
+    }
+}
@@ -0,0 +1,459 @@
+//! A summary mode, which collects a single sample and prints it in the format
+//! of the header of `top`, for scripts that already parse it.
+
+use time::{macros::format_description, OffsetDateTime};
+
+use crate::{
+    app::{event_log::total_cpu_usage, layout_manager::UsedWidgets, AppConfigFields, DataFilters},
+    data_collection::{disks::IoHarvest, memory::MemHarvest, processes::count_processes, Data},
+    export::collect_once,
+};
+
+/// Whose `top` the summary is formatted like.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SummaryStyle {
+    /// The `top` from procps, which is used on Linux and elsewhere.
+    Procps,
+    /// The `top` from macOS, as printed by `top -l 1`.
+    MacOs,
+}
+
+impl SummaryStyle {
+    fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            SummaryStyle::MacOs
+        } else {
+            SummaryStyle::Procps
+        }
+    }
+}
+
+/// Names of devices whose I/O is already counted under another device, like
+/// device mapper and loop devices.
+const VIRTUAL_DISK_PREFIXES: [&str; 5] = ["dm-", "loop", "md", "ram", "zram"];
+
+/// The bytes read from and written to physical disks. Partitions are skipped
+/// when the whole disk is also listed, so nothing is counted twice.
+fn disk_io_totals(io: &IoHarvest) -> (u64, u64) {
+    io.iter()
+        .filter(|(name, _)| {
+            !VIRTUAL_DISK_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+                && !io
+                    .keys()
+                    .any(|other| other != *name && name.starts_with(other.as_str()))
+        })
+        .filter_map(|(_, data)| data.as_ref())
+        .fold((0, 0), |(read, written), data| {
+            (read + data.read_bytes, written + data.write_bytes)
+        })
+}
+
+/// Formats a size like macOS's `top`, e.g. `15G` or `2366M`, switching to the
+/// next unit once there are at least 10 of it.
+fn compact_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+
+    let (value, unit) = UNITS
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, unit)| (bytes >> (10 * index), *unit))
+        .find(|(value, _)| *value >= 10)
+        .unwrap_or((bytes, UNITS[0]));
+
+    format!("{value}{unit}")
+}
+
+/// Formats an uptime in seconds like procps, e.g. `3 days,  2:01` or `5 min`.
+fn procps_uptime(secs: u64) -> String {
+    let days = secs / 86400;
+    let hours = secs / 3600 % 24;
+    let minutes = secs / 60 % 60;
+
+    let mut uptime = match days {
+        0 => String::new(),
+        1 => "1 day, ".to_string(),
+        days => format!("{days} days, "),
+    };
+    if hours > 0 {
+        uptime.push_str(&format!("{hours:2}:{minutes:02}"));
+    } else {
+        uptime.push_str(&format!("{minutes} min"));
+    }
+
+    uptime
+}
+
+/// The user, system, idle, I/O wait, and steal shares of CPU time, in percent.
+/// Without a breakdown, all of the usage is counted as user time.
+fn cpu_shares(data: &Data) -> [f64; 5] {
+    match &data.cpu_times {
+        Some(times) => [
+            times.user,
+            times.system,
+            times.idle,
+            times.iowait,
+            times.steal,
+        ],
+        None => {
+            let usage = data.cpu.as_deref().and_then(total_cpu_usage).unwrap_or(0.0);
+
+            [usage, 0.0, 100.0 - usage, 0.0, 0.0]
+        }
+    }
+}
+
+/// The used and total bytes of some memory, which are 0 if it wasn't
+/// collected.
+fn mem_usage(mem: Option<&MemHarvest>) -> (u64, u64) {
+    mem.map_or((0, 0), |mem| (mem.used_bytes, mem.total_bytes))
+}
+
+/// The cached bytes, which are only collected outside of Windows.
+fn cached_bytes(data: &Data) -> u64 {
+    #[cfg(not(target_os = "windows"))]
+    {
+        data.cache.as_ref().map_or(0, |cache| cache.used_bytes)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let _ = data;
+        0
+    }
+}
+
+/// The network and disk lines, which procps's `top` has no equivalent of, so
+/// they're the same for both styles.
+fn io_lines(data: &Data) -> [String; 2] {
+    let (rx, tx) = data.network.as_ref().map_or((0, 0), |network| {
+        (network.total_rx / 8, network.total_tx / 8)
+    });
+    let (read, written) = data.io.as_ref().map_or((0, 0), disk_io_totals);
+
+    [
+        format!(
+            "Networks: {} in, {} out.",
+            compact_size(rx),
+            compact_size(tx)
+        ),
+        format!(
+            "Disks: {} read, {} written.",
+            compact_size(read),
+            compact_size(written)
+        ),
+    ]
+}
+
+/// Formats a sample like the header of `top`, with the time in the offset of
+/// `now`.
+fn summarize(
+    data: &Data, style: SummaryStyle, now: OffsetDateTime, uptime_secs: u64,
+) -> Vec<String> {
+    const MIB: f64 = 1024.0 * 1024.0;
+
+    let counts = count_processes(data.list_of_processes.iter().flatten());
+    let [load_1, load_5, load_15] = data.load_avg.unwrap_or_default();
+    let [user, system, idle, iowait, steal] = cpu_shares(data);
+    let (mem_used, mem_total) = mem_usage(data.memory.as_ref());
+    let (swap_used, swap_total) = mem_usage(data.swap.as_ref());
+
+    let date = now
+        .format(format_description!("[year]/[month]/[day]"))
+        .unwrap_or_default();
+    let clock = now
+        .format(format_description!("[hour]:[minute]:[second]"))
+        .unwrap_or_default();
+
+    let mut lines = match style {
+        SummaryStyle::Procps => {
+            let cached = cached_bytes(data);
+            let mem_free = mem_total.saturating_sub(mem_used).saturating_sub(cached);
+            let mib = |bytes: u64| bytes as f64 / MIB;
+
+            vec![
+                format!(
+                    "top - {clock} up {},  load average: {load_1:.2}, {load_5:.2}, {load_15:.2}",
+                    procps_uptime(uptime_secs)
+                ),
+                format!(
+                    "Tasks: {:3} total, {:3} running, {:3} sleeping, {:3} stopped, {:3} zombie",
+                    counts.processes, counts.running, counts.sleeping, counts.stopped, counts.zombies
+                ),
+                // Nice time is counted as user time, and interrupts as system time.
+                format!(
+                    "%Cpu(s): {user:4.1} us, {system:4.1} sy,  0.0 ni, {idle:4.1} id, {iowait:4.1} wa,  \
+                     0.0 hi,  0.0 si, {steal:4.1} st"
+                ),
+                format!(
+                    "MiB Mem : {:8.1} total, {:8.1} free, {:8.1} used, {:8.1} buff/cache",
+                    mib(mem_total),
+                    mib(mem_free),
+                    mib(mem_used),
+                    mib(cached)
+                ),
+                format!(
+                    "MiB Swap: {:8.1} total, {:8.1} free, {:8.1} used. {:8.1} avail Mem",
+                    mib(swap_total),
+                    mib(swap_total.saturating_sub(swap_used)),
+                    mib(swap_used),
+                    mib(mem_total.saturating_sub(mem_used))
+                ),
+            ]
+        }
+        SummaryStyle::MacOs => {
+            let mut processes = format!(
+                "Processes: {} total, {} running, {} sleeping, ",
+                counts.processes, counts.running, counts.sleeping
+            );
+            if counts.stopped > 0 {
+                processes.push_str(&format!("{} stopped, ", counts.stopped));
+            }
+            processes.push_str(&format!("{} threads", counts.threads));
+
+            vec![
+                processes,
+                format!("{date} {clock}"),
+                format!("Load Avg: {load_1:.2}, {load_5:.2}, {load_15:.2}"),
+                format!(
+                    "CPU usage: {user:.2}% user, {:.2}% sys, {:.2}% idle",
+                    system + steal,
+                    idle + iowait
+                ),
+                format!(
+                    "PhysMem: {} used, {} unused.",
+                    compact_size(mem_used),
+                    compact_size(mem_total.saturating_sub(mem_used))
+                ),
+                format!(
+                    "VM: {} swap used, {} swap free.",
+                    compact_size(swap_used),
+                    compact_size(swap_total.saturating_sub(swap_used))
+                ),
+            ]
+        }
+    };
+    lines.extend(io_lines(data));
+
+    lines
+}
+
+/// Collects a sample and prints it like the header of `top`, without starting
+/// the interface.
+pub(crate) fn run(app_config_fields: &AppConfigFields, filters: DataFilters) -> anyhow::Result<()> {
+    // Like `top`, the time is local. The local offset can only be found while
+    // bottom has a single thread, so this is done before collecting, and falls
+    // back to UTC otherwise.
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+
+    let data = collect_once(
+        app_config_fields,
+        filters,
+        UsedWidgets {
+            use_cpu: true,
+            use_mem: true,
+            use_cache: true,
+            use_net: true,
+            use_proc: true,
+            use_disk: true,
+            ..Default::default()
+        },
+    );
+
+    let lines = summarize(
+        &data,
+        SummaryStyle::current(),
+        now,
+        sysinfo::System::uptime(),
+    );
+    for line in lines {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use hashbrown::HashMap;
+    use time::macros::{datetime, offset};
+
+    use super::*;
+    use crate::data_collection::{
+        cpu::CpuTimesHarvest,
+        disks::IoData,
+        network::NetworkHarvest,
+        processes::{ProcessHarvest, ProcessState},
+    };
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn process(state: char, num_threads: u32) -> ProcessHarvest {
+        ProcessHarvest {
+            process_state: (ProcessState::from(state).to_string(), state),
+            num_threads: Some(num_threads),
+            ..Default::default()
+        }
+    }
+
+    fn mem(used_bytes: u64, total_bytes: u64) -> MemHarvest {
+        MemHarvest {
+            used_bytes,
+            total_bytes,
+            use_percent: Some(used_bytes as f64 / total_bytes as f64 * 100.0),
+        }
+    }
+
+    fn io(read_bytes: u64, write_bytes: u64) -> Option<IoData> {
+        Some(IoData {
+            read_bytes,
+            write_bytes,
+        })
+    }
+
+    fn data() -> Data {
+        Data {
+            cpu_times: Some(CpuTimesHarvest {
+                user: 12.5,
+                system: 4.25,
+                idle: 80.0,
+                iowait: 2.0,
+                steal: 1.25,
+            }),
+            load_avg: Some([0.52, 0.58, 1.5]),
+            memory: Some(mem(6 * GIB, 16 * GIB)),
+            #[cfg(not(target_os = "windows"))]
+            cache: Some(mem(2 * GIB, 16 * GIB)),
+            swap: Some(mem(GIB / 2, 2 * GIB)),
+            network: Some(NetworkHarvest {
+                total_rx: 8 * 300 * 1024 * 1024,
+                total_tx: 8 * 20 * GIB,
+                ..Default::default()
+            }),
+            list_of_processes: Some(vec![
+                process('R', 4),
+                process('S', 1),
+                process('I', 1),
+                process('T', 2),
+                process('Z', 1),
+            ]),
+            io: Some(HashMap::from([
+                ("sda".to_string(), io(40 * GIB, 12 * GIB)),
+                ("sda1".to_string(), io(30 * GIB, 10 * GIB)),
+                ("dm-0".to_string(), io(30 * GIB, 10 * GIB)),
+                ("nvme0n1".to_string(), io(10 * GIB, 3 * GIB)),
+                ("sr0".to_string(), None),
+            ])),
+            ..Default::default()
+        }
+    }
+
+    fn now() -> OffsetDateTime {
+        datetime!(2024-03-05 14:03:12 UTC)
+    }
+
+    #[test]
+    fn procps_style() {
+        let uptime = 3 * 86400 + 2 * 3600 + 60 + 30;
+        let lines = summarize(&data(), SummaryStyle::Procps, now(), uptime);
+
+        let expected = [
+            "top - 14:03:12 up 3 days,  2:01,  load average: 0.52, 0.58, 1.50",
+            "Tasks:   5 total,   1 running,   2 sleeping,   1 stopped,   1 zombie",
+            "%Cpu(s): 12.5 us,  4.2 sy,  0.0 ni, 80.0 id,  2.0 wa,  0.0 hi,  0.0 si,  1.2 st",
+        ];
+        assert_eq!(lines[..3], expected);
+
+        #[cfg(not(target_os = "windows"))]
+        assert_eq!(
+            lines[3],
+            "MiB Mem :  16384.0 total,   8192.0 free,   6144.0 used,   2048.0 buff/cache"
+        );
+        assert_eq!(
+            lines[4],
+            "MiB Swap:   2048.0 total,   1536.0 free,    512.0 used.  10240.0 avail Mem"
+        );
+        assert_eq!(
+            lines[5..],
+            [
+                "Networks: 300M in, 20G out.",
+                "Disks: 50G read, 15G written."
+            ]
+        );
+    }
+
+    #[test]
+    fn macos_style() {
+        let lines = summarize(&data(), SummaryStyle::MacOs, now(), 0);
+
+        assert_eq!(
+            lines,
+            [
+                "Processes: 5 total, 1 running, 2 sleeping, 1 stopped, 9 threads",
+                "2024/03/05 14:03:12",
+                "Load Avg: 0.52, 0.58, 1.50",
+                "CPU usage: 12.50% user, 5.50% sys, 82.00% idle",
+                "PhysMem: 6144M used, 10G unused.",
+                "VM: 512M swap used, 1536M swap free.",
+                "Networks: 300M in, 20G out.",
+                "Disks: 50G read, 15G written.",
+            ]
+        );
+    }
+
+    #[test]
+    fn local_time() {
+        let now = now().to_offset(offset!(+10));
+
+        let lines = summarize(&data(), SummaryStyle::Procps, now, 60);
+        assert!(
+            lines[0].starts_with("top - 00:03:12 up 1 min,"),
+            "{}",
+            lines[0]
+        );
+
+        let lines = summarize(&data(), SummaryStyle::MacOs, now, 60);
+        assert_eq!(lines[1], "2024/03/06 00:03:12");
+    }
+
+    #[test]
+    fn uptimes() {
+        assert_eq!(procps_uptime(59), "0 min");
+        assert_eq!(procps_uptime(45 * 60), "45 min");
+        assert_eq!(procps_uptime(3600 + 5 * 60), " 1:05");
+        assert_eq!(procps_uptime(86400 + 12 * 3600), "1 day, 12:00");
+        assert_eq!(procps_uptime(2 * 86400 + 60), "2 days, 1 min");
+    }
+
+    #[test]
+    fn compact_sizes() {
+        assert_eq!(compact_size(0), "0B");
+        assert_eq!(compact_size(9), "9B");
+        assert_eq!(compact_size(9 * 1024), "9216B");
+        assert_eq!(compact_size(10 * 1024), "10K");
+        assert_eq!(compact_size(2366 * 1024 * 1024), "2366M");
+        assert_eq!(compact_size(15 * GIB + 1), "15G");
+    }
+
+    #[test]
+    fn cpu_without_breakdown() {
+        use crate::data_collection::cpu::{CpuData, CpuDataType};
+
+        let data = Data {
+            cpu: Some(vec![
+                CpuData {
+                    data_type: CpuDataType::Cpu(0),
+                    cpu_usage: 30.0,
+                },
+                CpuData {
+                    data_type: CpuDataType::Cpu(1),
+                    cpu_usage: 10.0,
+                },
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(cpu_shares(&data), [20.0, 0.0, 80.0, 0.0, 0.0]);
+    }
+}
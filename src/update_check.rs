@@ -0,0 +1,262 @@
+//! Checking whether a newer release of bottom is out, with `--version_check`,
+//! or once a day at startup with `check_for_updates`. Only the tag of the
+//! latest release is fetched from GitHub; nothing is ever downloaded or
+//! installed.
+
+use std::{
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::mpsc::Sender,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Context};
+use clap::crate_version;
+use serde::Deserialize;
+
+use crate::{app::saved_state::SavedState, event::BottomEvent, export::tls};
+
+const RELEASES_HOST: &str = "api.github.com";
+const LATEST_RELEASE_PATH: &str = "/repos/yretenai/bottom/releases/latest";
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait between the checks done at startup.
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long the status line shows that a newer version is out.
+pub(crate) const NOTICE_DURATION: Duration = Duration::from_secs(10);
+
+/// Whether the running version is the latest release.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum UpdateStatus {
+    UpToDate,
+    /// A newer release, without the leading `v` of its tag.
+    Available(String),
+}
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+}
+
+/// Parses a version like `0.10.2` or `v0.10.2`. Anything after a `-` or a
+/// `+`, like a pre-release, is ignored.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split(['-', '+']).next()?;
+    let mut parts = version.split('.').map(|part| part.parse().ok());
+
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+
+    Some((major, minor, patch))
+}
+
+/// Compares the tag of the latest release against the current version.
+fn compare(latest_tag: &str, current: &str) -> anyhow::Result<UpdateStatus> {
+    let latest = parse_version(latest_tag)
+        .with_context(|| format!("The latest release has an invalid version, '{latest_tag}'."))?;
+
+    if parse_version(current).is_some_and(|current| latest > current) {
+        Ok(UpdateStatus::Available(
+            latest_tag.trim().trim_start_matches('v').to_string(),
+        ))
+    } else {
+        Ok(UpdateStatus::UpToDate)
+    }
+}
+
+/// Joins the chunks of a body sent with `Transfer-Encoding: chunked`.
+fn dechunk(mut body: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut joined = Vec::new();
+
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .context("A chunk of the response was cut off.")?;
+        let size = std::str::from_utf8(&body[..line_end])?;
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .with_context(|| format!("The response has an invalid chunk size, '{size}'."))?;
+        if size == 0 {
+            return Ok(joined);
+        }
+
+        let chunk = body
+            .get(line_end + 2..line_end + 2 + size)
+            .context("A chunk of the response was cut off.")?;
+        joined.extend_from_slice(chunk);
+        body = body.get(line_end + 4 + size..).unwrap_or_default();
+    }
+}
+
+/// Gets the tag of the latest release from a response of the releases API.
+fn parse_response(response: &[u8]) -> anyhow::Result<String> {
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .context("GitHub sent an invalid response.")?;
+    let headers = String::from_utf8_lossy(&response[..header_end]);
+    let body = &response[header_end + 4..];
+
+    let status = headers
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .context("GitHub sent an invalid response.")?;
+    match status {
+        200 => {}
+        404 => bail!("No releases were found."),
+        403 | 429 => bail!("GitHub's rate limit was reached, try again later."),
+        status => bail!("GitHub responded with status {status}."),
+    }
+
+    let is_chunked = headers.lines().any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let release: Release = if is_chunked {
+        serde_json::from_slice(&dechunk(body)?)?
+    } else {
+        serde_json::from_slice(body)?
+    };
+
+    Ok(release.tag_name)
+}
+
+/// Fetches the tag of the latest release.
+fn fetch_latest_tag() -> anyhow::Result<String> {
+    let config = tls::system_client_config()?;
+    let address = (RELEASES_HOST, 443)
+        .to_socket_addrs()?
+        .next()
+        .with_context(|| format!("Could not resolve '{RELEASES_HOST}'."))?;
+
+    let stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let mut stream = tls::connect(stream, RELEASES_HOST, Some(&config))?;
+
+    let request = format!(
+        "GET {LATEST_RELEASE_PATH} HTTP/1.1\r\nHost: {RELEASES_HOST}\r\nUser-Agent: bottom/{}\r\nAccept: application/vnd.github+json\r\nConnection: close\r\n\r\n",
+        crate_version!()
+    );
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    // Servers don't always end TLS cleanly after the response, which is fine
+    // as the response has been read by then.
+    let mut response = Vec::new();
+    match stream.read_to_end(&mut response) {
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof && !response.is_empty() => {}
+        Err(err) => return Err(tls::readable_error(err)),
+    }
+
+    parse_response(&response)
+}
+
+/// Checks whether there's a newer release than the running version.
+pub(crate) fn check() -> anyhow::Result<UpdateStatus> {
+    compare(&fetch_latest_tag()?, crate_version!())
+}
+
+/// Prints whether there's a newer release, for `--version_check`.
+pub(crate) fn run() -> anyhow::Result<()> {
+    match check().context("Could not check for a newer version")? {
+        UpdateStatus::UpToDate => println!("Up to date"),
+        UpdateStatus::Available(version) => println!("New version available: {version}"),
+    }
+
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
+
+/// Whether a day has passed since the last check, or the clock went back.
+fn is_check_due(last_check: Option<u64>, now: u64) -> bool {
+    last_check.map_or(true, |last_check| {
+        now < last_check || now - last_check >= CHECK_INTERVAL.as_secs()
+    })
+}
+
+/// Checks for a newer release in the background if it hasn't been checked in
+/// the last day, sending its version to the main thread if there is one.
+/// Failed checks are ignored, and not retried until the next day.
+///
+/// The time of the check is saved on the calling thread, where every other
+/// change to the saved state is made, so the saves can't overwrite each other.
+pub(crate) fn spawn_daily_check(sender: Sender<BottomEvent>) -> anyhow::Result<()> {
+    let mut saved_state = SavedState::load();
+    let now = now_secs();
+    if !is_check_due(saved_state.last_update_check, now) {
+        return Ok(());
+    }
+
+    thread::spawn(move || {
+        if let Ok(UpdateStatus::Available(version)) = check() {
+            let _ = sender.send(BottomEvent::UpdateAvailable(version));
+        }
+    });
+
+    saved_state.last_update_check = Some(now);
+    saved_state.save()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn versions() {
+        assert_eq!(parse_version("v0.10.2"), Some((0, 10, 2)));
+        assert_eq!(parse_version("1.2"), Some((1, 2, 0)));
+        assert_eq!(parse_version("0.11.0-nightly"), Some((0, 11, 0)));
+        assert_eq!(parse_version("nightly"), None);
+
+        assert_eq!(
+            compare("v0.11.0", "0.10.2").unwrap(),
+            UpdateStatus::Available("0.11.0".to_string())
+        );
+        assert_eq!(compare("0.10.2", "0.10.2").unwrap(), UpdateStatus::UpToDate);
+        assert_eq!(compare("v0.9.7", "0.10.2").unwrap(), UpdateStatus::UpToDate);
+        assert!(compare("nightly", "0.10.2").is_err());
+    }
+
+    #[test]
+    fn responses() {
+        let response = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"tag_name\":\"v0.11.0\",\"name\":\"0.11.0\"}";
+        assert_eq!(parse_response(response).unwrap(), "v0.11.0");
+
+        let response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n10\r\n{\"tag_name\":\"v0.\r\n7\r\n11.0\"}\n\r\n0\r\n\r\n";
+        assert_eq!(parse_response(response).unwrap(), "v0.11.0");
+
+        let response = b"HTTP/1.1 403 rate limit exceeded\r\n\r\n{}";
+        assert!(parse_response(response)
+            .unwrap_err()
+            .to_string()
+            .contains("rate limit"));
+        assert!(parse_response(b"garbage").is_err());
+    }
+
+    #[test]
+    fn daily() {
+        let day = CHECK_INTERVAL.as_secs();
+
+        assert!(is_check_due(None, 1000));
+        assert!(!is_check_due(Some(1000), 1000 + day - 1));
+        assert!(is_check_due(Some(1000), 1000 + day));
+        assert!(is_check_due(Some(1000), 10));
+    }
+}
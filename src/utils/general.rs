@@ -1,4 +1,9 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
 
 #[inline]
 pub const fn sort_partial_fn<T: PartialOrd>(is_descending: bool) -> fn(T, T) -> Ordering {
@@ -62,6 +67,48 @@ macro_rules! clamp_num_impl {
 
 clamp_num_impl!(u8, u16, u32, u64, usize);
 
+/// A random number, which isn't cryptographically secure.
+pub fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// `interval_ms` randomly moved earlier or later by up to `jitter_pct` percent
+/// of it, so that many instances started together don't all wake up at once.
+pub fn jittered_interval(interval_ms: u64, jitter_pct: u64) -> Duration {
+    let max_offset = interval_ms.saturating_mul(jitter_pct) / 100;
+    if max_offset == 0 {
+        return Duration::from_millis(interval_ms);
+    }
+
+    let offset = random_u64() % (max_offset * 2 + 1);
+    Duration::from_millis(interval_ms - max_offset + offset)
+}
+
+/// Standard base64 encoding with padding.
+pub fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or_default(),
+            chunk.get(2).copied().unwrap_or_default(),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -90,6 +137,29 @@ mod test {
         assert_eq!(val.clamp_lower(50), 100);
     }
 
+    #[test]
+    fn test_jittered_interval() {
+        assert_eq!(jittered_interval(1000, 0), Duration::from_millis(1000));
+
+        let intervals = (0..100)
+            .map(|_| jittered_interval(1000, 10))
+            .collect::<Vec<_>>();
+        assert!(intervals
+            .iter()
+            .all(|interval| (900..=1100).contains(&interval.as_millis())));
+        assert!(intervals.iter().any(|interval| *interval != intervals[0]));
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64(b"1234"), "MTIzNA==");
+    }
+
     #[test]
     fn test_sort_partial_fn() {
         let mut x = vec![9, 5, 20, 15, 10, 5];
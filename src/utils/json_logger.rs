@@ -0,0 +1,280 @@
+//! Writing log events as JSON lines with `--json_log`, for tools like Loki or
+//! Logstash to ingest.
+//!
+//! The file is rotated daily and whenever it grows past a maximum size. Old
+//! files get a number added to their name, e.g. `bottom.ndjson.1` for the most
+//! recent one, and only a set number are kept.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use log::{LevelFilter, Log, Metadata, Record};
+use serde_json::json;
+use time::{format_description::well_known::Rfc3339, Date, OffsetDateTime};
+
+use super::logging::local_now;
+use crate::data_collection::{cpu::CpuDataType, Data};
+
+/// The target that metrics from each data update are logged to. These are
+/// only written to the JSON log.
+pub const METRICS_TARGET: &str = "bottom::metrics";
+
+pub const DEFAULT_LOG_MAX_SIZE_MB: u64 = 100;
+pub const DEFAULT_LOG_KEEP: usize = 7;
+
+/// Settings for the JSON log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonLogSettings {
+    pub path: PathBuf,
+    pub level: LevelFilter,
+    /// The size in bytes that the file is rotated at.
+    pub max_size: u64,
+    /// How many rotated files are kept.
+    pub keep: usize,
+}
+
+/// A logger that writes each event as a line of JSON.
+pub struct JsonLogger {
+    level: LevelFilter,
+    file: Mutex<RotatingFile>,
+}
+
+impl JsonLogger {
+    pub fn new(settings: JsonLogSettings) -> io::Result<Self> {
+        Ok(Self {
+            level: settings.level,
+            file: Mutex::new(RotatingFile::open(
+                settings.path,
+                settings.max_size,
+                settings.keep,
+            )?),
+        })
+    }
+}
+
+impl Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let now = local_now();
+        let line = entry(record, now).to_string();
+        if let Ok(mut file) = self.file.lock() {
+            // There's nowhere to report a failure to log to.
+            let _ = file.write_line(&line, now.date());
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.file.flush();
+        }
+    }
+}
+
+/// Builds the JSON for a log event. Metrics are kept as an object rather than
+/// a string, so they can be queried directly.
+fn entry(record: &Record<'_>, now: OffsetDateTime) -> serde_json::Value {
+    let timestamp = now.format(&Rfc3339).unwrap_or_default();
+    let module = record.module_path().unwrap_or(record.target());
+    let message = record.args().to_string();
+
+    if record.target() == METRICS_TARGET {
+        if let Ok(metrics) = serde_json::from_str::<serde_json::Value>(&message) {
+            return json!({
+                "timestamp": timestamp,
+                "level": record.level().as_str(),
+                "module": METRICS_TARGET,
+                "message": "update",
+                "metrics": metrics,
+            });
+        }
+    }
+
+    json!({
+        "timestamp": timestamp,
+        "level": record.level().as_str(),
+        "module": module,
+        "message": message,
+    })
+}
+
+/// Logs a summary of a data update to the JSON log, if it's enabled.
+pub fn log_metrics(data: &Data) {
+    if !log::log_enabled!(target: METRICS_TARGET, log::Level::Info) {
+        return;
+    }
+
+    let cpu = data.cpu.as_ref().and_then(|cpu| {
+        cpu.iter()
+            .find(|cpu| matches!(cpu.data_type, CpuDataType::Avg))
+            .map(|cpu| cpu.cpu_usage)
+    });
+    let metrics = json!({
+        "cpu_percent": cpu,
+        "mem_used_bytes": data.memory.as_ref().map(|mem| mem.used_bytes),
+        "mem_total_bytes": data.memory.as_ref().map(|mem| mem.total_bytes),
+        "swap_used_bytes": data.swap.as_ref().map(|swap| swap.used_bytes),
+        "rx_bits_per_sec": data.network.as_ref().map(|network| network.rx),
+        "tx_bits_per_sec": data.network.as_ref().map(|network| network.tx),
+        "processes": data.list_of_processes.as_ref().map(Vec::len),
+    });
+
+    log::info!(target: METRICS_TARGET, "{metrics}");
+}
+
+/// A file that is rotated when it gets too large or a new day starts.
+struct RotatingFile {
+    path: PathBuf,
+    max_size: u64,
+    keep: usize,
+    file: File,
+    size: u64,
+    /// The day that the current file was started on.
+    date: Date,
+}
+
+impl RotatingFile {
+    /// Opens the file to append to it. If it was last written to on an earlier
+    /// day, it's rotated on the next write.
+    fn open(path: PathBuf, max_size: u64, keep: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+        let now = local_now();
+        let date = metadata
+            .modified()
+            .map(|modified| {
+                OffsetDateTime::from(modified)
+                    .to_offset(now.offset())
+                    .date()
+            })
+            .unwrap_or(now.date());
+
+        Ok(Self {
+            path,
+            max_size,
+            keep,
+            file,
+            size: metadata.len(),
+            date,
+        })
+    }
+
+    fn write_line(&mut self, line: &str, today: Date) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && (self.size + len > self.max_size || today != self.date) {
+            self.rotate()?;
+        }
+        self.date = today;
+
+        writeln!(self.file, "{line}")?;
+        self.size += len;
+
+        Ok(())
+    }
+
+    /// Moves the current file to `<path>.1`, shifting older files along and
+    /// removing any past the number to keep, then starts a new file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        let _ = fs::remove_file(rotated_path(&self.path, self.keep.max(1)));
+        for i in (1..self.keep).rev() {
+            let from = rotated_path(&self.path, i);
+            if from.exists() {
+                fs::rename(from, rotated_path(&self.path, i + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        } else {
+            fs::remove_file(&self.path)?;
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{index}"));
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod test {
+    use time::macros::{date, datetime};
+
+    use super::*;
+
+    #[test]
+    fn entries() {
+        let now = datetime!(2024-05-01 12:30:00 UTC);
+        let args = format_args!("Something happened.");
+        let record = Record::builder()
+            .args(args)
+            .level(log::Level::Warn)
+            .target("bottom")
+            .module_path(Some("bottom::app"))
+            .build();
+
+        assert_eq!(
+            entry(&record, now),
+            json!({
+                "timestamp": "2024-05-01T12:30:00Z",
+                "level": "WARN",
+                "module": "bottom::app",
+                "message": "Something happened.",
+            })
+        );
+
+        let args = format_args!(r#"{{"cpu_percent":12.5}}"#);
+        let record = Record::builder()
+            .args(args)
+            .level(log::Level::Info)
+            .target(METRICS_TARGET)
+            .build();
+
+        assert_eq!(
+            entry(&record, now)["metrics"],
+            json!({ "cpu_percent": 12.5 })
+        );
+    }
+
+    #[test]
+    fn rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.ndjson");
+        let day = date!(2024 - 05 - 01);
+
+        let mut file = RotatingFile::open(path.clone(), 10, 2).unwrap();
+        file.date = day;
+        file.write_line("first", day).unwrap();
+        // This would go past the maximum size.
+        file.write_line("second", day).unwrap();
+        // A new day starts a new file even though this one isn't full.
+        file.write_line("third", day.next_day().unwrap()).unwrap();
+        file.write_line("fourth", day.next_day().unwrap()).unwrap();
+
+        let read = |path: &Path| fs::read_to_string(path).unwrap();
+        assert_eq!(read(&path), "fourth\n");
+        assert_eq!(read(&rotated_path(&path, 1)), "third\n");
+        assert_eq!(read(&rotated_path(&path, 2)), "second\n");
+        assert!(!rotated_path(&path, 3).exists());
+    }
+}
@@ -4,43 +4,48 @@ use std::sync::OnceLock;
 #[cfg(feature = "logging")]
 pub static OFFSET: OnceLock<time::UtcOffset> = OnceLock::new();
 
+/// The current local time, falling back to UTC if the local offset can't be
+/// found.
+#[cfg(feature = "logging")]
+pub fn local_now() -> time::OffsetDateTime {
+    let offset = OFFSET.get_or_init(|| {
+        use time::util::local_offset::Soundness;
+
+        // SAFETY: We only invoke this once, quickly, and it should be invoked in a
+        // single-thread context. We also should only ever hit this
+        // logging at all in a debug context which is generally fine,
+        // release builds should have this logging disabled entirely for now.
+        unsafe {
+            // XXX: If we ever DO add general logging as a release feature, evaluate this
+            // again and whether this is something we want enabled in
+            // release builds! What might be safe is falling back to the non-set-soundness
+            // mode when specifically using certain feature flags (e.g. dev-logging feature
+            // enables this behaviour).
+
+            time::util::local_offset::set_soundness(Soundness::Unsound);
+            let res = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+            time::util::local_offset::set_soundness(Soundness::Sound);
+
+            res
+        }
+    });
+
+    let utc = time::OffsetDateTime::now_utc();
+    utc.checked_to_offset(*offset).unwrap_or(utc)
+}
+
+/// Sets up logging to the debug file, or stdout if there isn't one. If JSON
+/// log settings are given, events are also written there.
 #[cfg(feature = "logging")]
 pub fn init_logger(
     min_level: log::LevelFilter, debug_file_name: Option<&std::ffi::OsStr>,
+    json_log: Option<super::json_logger::JsonLogSettings>,
 ) -> anyhow::Result<()> {
     let dispatch = fern::Dispatch::new()
         .format(|out, message, record| {
-            let offset = OFFSET.get_or_init(|| {
-                use time::util::local_offset::Soundness;
-
-                // SAFETY: We only invoke this once, quickly, and it should be invoked in a
-                // single-thread context. We also should only ever hit this
-                // logging at all in a debug context which is generally fine,
-                // release builds should have this logging disabled entirely for now.
-                unsafe {
-                    // XXX: If we ever DO add general logging as a release feature, evaluate this
-                    // again and whether this is something we want enabled in
-                    // release builds! What might be safe is falling back to the non-set-soundness
-                    // mode when specifically using certain feature flags (e.g. dev-logging feature
-                    // enables this behaviour).
-
-                    time::util::local_offset::set_soundness(Soundness::Unsound);
-                    let res =
-                        time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
-                    time::util::local_offset::set_soundness(Soundness::Sound);
-
-                    res
-                }
-            });
-
-            let offset_time = {
-                let utc = time::OffsetDateTime::now_utc();
-                utc.checked_to_offset(*offset).unwrap_or(utc)
-            };
-
             out.finish(format_args!(
                 "{}[{}][{}] {}",
-                offset_time
+                local_now()
                     .format(&time::macros::format_description!(
                         // The weird "[[[" is because we need to escape a bracket ("[[") to show
                         // one "[". See https://time-rs.github.io/book/api/format-description.html
@@ -52,12 +57,28 @@ pub fn init_logger(
                 message
             ))
         })
-        .level(min_level);
+        .level(min_level)
+        .level_for(super::json_logger::METRICS_TARGET, log::LevelFilter::Off);
 
-    if let Some(debug_file_name) = debug_file_name {
-        dispatch.chain(fern::log_file(debug_file_name)?).apply()?;
+    let dispatch = if let Some(debug_file_name) = debug_file_name {
+        dispatch.chain(fern::log_file(debug_file_name)?)
     } else {
-        dispatch.chain(std::io::stdout()).apply()?;
+        dispatch.chain(std::io::stdout())
+    };
+
+    match json_log {
+        Some(settings) => {
+            let json_level = settings.level;
+            let json_logger: Box<dyn log::Log> =
+                Box::new(super::json_logger::JsonLogger::new(settings)?);
+
+            fern::Dispatch::new()
+                .level(min_level.max(json_level))
+                .chain(dispatch)
+                .chain(json_logger)
+                .apply()?;
+        }
+        None => dispatch.apply()?,
     }
 
     Ok(())
@@ -135,7 +156,7 @@ mod test {
     #[cfg(feature = "logging")]
     #[test]
     fn test_logging_macros() {
-        super::init_logger(log::LevelFilter::Trace, None)
+        super::init_logger(log::LevelFilter::Trace, None, None)
             .expect("initializing the logger should succeed");
 
         error!("This is an error.");
@@ -0,0 +1,44 @@
+//! Rendering small inline charts using Unicode block characters.
+
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders the data as a sparkline, with one character per value.
+///
+/// Values are scaled between the smallest and largest value in the data, so
+/// that trends are visible even if the values themselves barely change. If all
+/// values are the same, the sparkline is flat at the lowest block.
+pub fn render(data: &[f64]) -> String {
+    let (min, max) = data
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+    let range = max - min;
+
+    data.iter()
+        .map(|&value| {
+            if range > 0.0 {
+                let scaled = (value - min) / range * (BLOCKS.len() - 1) as f64;
+                BLOCKS[(scaled.round() as usize).min(BLOCKS.len() - 1)]
+            } else {
+                BLOCKS[0]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sparkline_render() {
+        assert_eq!(render(&[]), "");
+        assert_eq!(render(&[5.0, 5.0, 5.0]), "▁▁▁");
+        assert_eq!(
+            render(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]),
+            "▁▂▃▄▅▆▇█"
+        );
+        assert_eq!(render(&[100.0, 200.0, 100.0]), "▁█▁");
+    }
+}
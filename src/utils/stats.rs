@@ -0,0 +1,75 @@
+//! Summary statistics for graphed data.
+
+use crate::canvas::components::time_chart::Point;
+
+/// The mean, minimum, maximum, and standard deviation of some values.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatSummary {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    /// The population standard deviation.
+    pub stddev: f64,
+}
+
+/// Summarizes `data`. Everything is 0 if there are no values.
+pub fn summarize(data: &[f64]) -> StatSummary {
+    if data.is_empty() {
+        return StatSummary::default();
+    }
+
+    let count = data.len() as f64;
+    let (sum, min, max) = data.iter().fold(
+        (0.0, f64::INFINITY, f64::NEG_INFINITY),
+        |(sum, min, max), &value| (sum + value, min.min(value), max.max(value)),
+    );
+    let mean = sum / count;
+    let variance = data.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / count;
+
+    StatSummary {
+        mean,
+        min,
+        max,
+        stddev: variance.sqrt(),
+    }
+}
+
+/// The values of the points in the last `display_time` milliseconds, which
+/// are the ones shown on a graph.
+pub fn visible_values(points: &[Point], display_time: u64) -> Vec<f64> {
+    let time_start = -(display_time as f64);
+    points
+        .iter()
+        .filter(|(time, _)| *time >= time_start)
+        .map(|(_, value)| *value)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let summary = summarize(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(
+            summary,
+            StatSummary {
+                mean: 5.0,
+                min: 2.0,
+                max: 9.0,
+                stddev: 2.0,
+            }
+        );
+
+        assert_eq!(summarize(&[3.0]).stddev, 0.0);
+        assert_eq!(summarize(&[]), StatSummary::default());
+    }
+
+    #[test]
+    fn visible() {
+        let points = [(-3000.0, 1.0), (-2000.0, 2.0), (-1000.0, 3.0), (0.0, 4.0)];
+        assert_eq!(visible_values(&points, 2000), vec![2.0, 3.0, 4.0]);
+        assert!(visible_values(&[], 2000).is_empty());
+    }
+}
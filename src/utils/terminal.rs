@@ -0,0 +1,120 @@
+//! Finding the size of the terminal, taking terminal multiplexers into
+//! account. Inside tmux, screen, or zellij, the size reported for the
+//! terminal can be the size of the outer terminal rather than the pane bottom
+//! is in, so the multiplexer is asked instead.
+
+use std::process::{Command, Stdio};
+
+/// The size used if it can't be found another way.
+const FALLBACK_SIZE: (u16, u16) = (80, 24);
+
+/// A terminal multiplexer that bottom is running inside of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Multiplexer {
+    Tmux,
+    Screen,
+    Zellij,
+}
+
+impl Multiplexer {
+    /// Finds which multiplexer is in use from the variables each sets in the
+    /// environment of its panes.
+    fn detect(is_set: impl Fn(&str) -> bool) -> Option<Self> {
+        if is_set("TMUX") {
+            Some(Multiplexer::Tmux)
+        } else if is_set("ZELLIJ") {
+            Some(Multiplexer::Zellij)
+        } else if is_set("STY") {
+            Some(Multiplexer::Screen)
+        } else {
+            None
+        }
+    }
+
+    /// Asks for the size of the current pane. tmux can be asked directly,
+    /// and the others through `tput`, which reads the pane's size from the
+    /// terminal.
+    fn pane_size(self) -> Option<(u16, u16)> {
+        match self {
+            Multiplexer::Tmux => {
+                let mut command = Command::new("tmux");
+                command.args(["display-message", "-p"]);
+                if let Some(pane) = std::env::var_os("TMUX_PANE") {
+                    command.arg("-t").arg(pane);
+                }
+                command.arg("#{pane_width}x#{pane_height}");
+
+                parse_size(&output(&mut command)?)
+            }
+            Multiplexer::Screen | Multiplexer::Zellij => {
+                let cols = output(Command::new("tput").arg("cols"))?;
+                let lines = output(Command::new("tput").arg("lines"))?;
+
+                parse_size(&format!("{cols}x{lines}"))
+            }
+        }
+    }
+}
+
+/// Runs a command, returning what it printed if it succeeded. Its stderr is
+/// left as the terminal, which `tput` uses to find the size when its output is
+/// captured.
+fn output(command: &mut Command) -> Option<String> {
+    let output = command
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Parses a size like `120x40`, as the width and height. Neither can be zero.
+pub(crate) fn parse_size(size: &str) -> Option<(u16, u16)> {
+    let (width, height) = size.trim().split_once(['x', 'X'])?;
+    let width = width.trim().parse().ok().filter(|width| *width > 0)?;
+    let height = height.trim().parse().ok().filter(|height| *height > 0)?;
+
+    Some((width, height))
+}
+
+/// Gets the width and height of the terminal. Inside a multiplexer, it's
+/// asked for the size of the pane, falling back to what crossterm reports.
+pub(crate) fn get_terminal_size() -> (u16, u16) {
+    Multiplexer::detect(|name| std::env::var_os(name).is_some_and(|value| !value.is_empty()))
+        .and_then(Multiplexer::pane_size)
+        .or_else(|| crossterm::terminal::size().ok())
+        .filter(|(width, height)| *width > 0 && *height > 0)
+        .unwrap_or(FALLBACK_SIZE)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(parse_size("120x40"), Some((120, 40)));
+        assert_eq!(parse_size(" 80 X 24\n"), Some((80, 24)));
+        assert_eq!(parse_size("0x24"), None);
+        assert_eq!(parse_size("80"), None);
+        assert_eq!(parse_size("80x"), None);
+        assert_eq!(parse_size("70000x24"), None);
+    }
+
+    #[test]
+    fn multiplexers() {
+        let detect = |names: &[&str]| Multiplexer::detect(|name| names.contains(&name));
+
+        assert_eq!(detect(&[]), None);
+        assert_eq!(detect(&["TMUX"]), Some(Multiplexer::Tmux));
+        assert_eq!(detect(&["STY"]), Some(Multiplexer::Screen));
+        assert_eq!(detect(&["ZELLIJ"]), Some(Multiplexer::Zellij));
+
+        // tmux is preferred, as it can be asked about the exact pane.
+        assert_eq!(detect(&["STY", "TMUX"]), Some(Multiplexer::Tmux));
+    }
+}
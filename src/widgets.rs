@@ -1,15 +1,23 @@
 pub mod battery_info;
 pub mod cpu_graph;
 pub mod disk_table;
+pub mod exec_output;
+pub mod log_tail;
 pub mod mem_graph;
 pub mod net_graph;
 pub mod process_table;
+pub mod startup_table;
 pub mod temperature_table;
+pub mod user_proc_table;
 
 pub use battery_info::*;
 pub use cpu_graph::*;
 pub use disk_table::*;
+pub use exec_output::*;
+pub use log_tail::*;
 pub use mem_graph::*;
 pub use net_graph::*;
 pub use process_table::*;
+pub use startup_table::*;
 pub use temperature_table::*;
+pub use user_proc_table::*;
@@ -15,11 +15,16 @@ use crate::{
     data_collection::cpu::CpuDataType,
     data_conversion::CpuWidgetData,
     options::config::{cpu::CpuDefault, style::ColourPalette},
+    utils::stats::{summarize, visible_values, StatSummary},
 };
 
 pub enum CpuWidgetColumn {
     Cpu,
     Use,
+    Avg,
+    Min,
+    Max,
+    StdDev,
 }
 
 impl ColumnHeader for CpuWidgetColumn {
@@ -27,29 +32,43 @@ impl ColumnHeader for CpuWidgetColumn {
         match self {
             CpuWidgetColumn::Cpu => "CPU".into(),
             CpuWidgetColumn::Use => "Use".into(),
+            CpuWidgetColumn::Avg => "Avg".into(),
+            CpuWidgetColumn::Min => "Min".into(),
+            CpuWidgetColumn::Max => "Max".into(),
+            CpuWidgetColumn::StdDev => "SD".into(),
         }
     }
 }
 
+/// Where the stats columns start in the legend.
+const STATS_COLUMNS_START: usize = 2;
+
 pub enum CpuWidgetTableData {
     All,
     Entry {
         data_type: CpuDataType,
         last_entry: f64,
+        /// The stats of the part of the graph that's shown, if they are.
+        stats: Option<StatSummary>,
     },
 }
 
 impl CpuWidgetTableData {
-    pub fn from_cpu_widget_data(data: &CpuWidgetData) -> CpuWidgetTableData {
+    /// Converts `data` to a legend entry, with the stats of the last
+    /// `stats_time` milliseconds if set.
+    pub fn from_cpu_widget_data(
+        data: &CpuWidgetData, stats_time: Option<u64>,
+    ) -> CpuWidgetTableData {
         match data {
             CpuWidgetData::All => CpuWidgetTableData::All,
             CpuWidgetData::Entry {
                 data_type,
-                data: _,
+                data,
                 last_entry,
             } => CpuWidgetTableData::Entry {
                 data_type: *data_type,
                 last_entry: *last_entry,
+                stats: stats_time.map(|time| summarize(&visible_values(data, time))),
             },
         }
     }
@@ -73,11 +92,12 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
         match &self {
             CpuWidgetTableData::All => match column {
                 CpuWidgetColumn::Cpu => Some("All".into()),
-                CpuWidgetColumn::Use => None,
+                _ => None,
             },
             CpuWidgetTableData::Entry {
                 data_type,
                 last_entry,
+                stats,
             } => {
                 if calculated_width == 0 {
                     None
@@ -97,6 +117,10 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
                             }
                         },
                         CpuWidgetColumn::Use => Some(format!("{:.0}%", last_entry.round()).into()),
+                        CpuWidgetColumn::Avg => stats.map(|stats| percent(stats.mean)),
+                        CpuWidgetColumn::Min => stats.map(|stats| percent(stats.min)),
+                        CpuWidgetColumn::Max => stats.map(|stats| percent(stats.max)),
+                        CpuWidgetColumn::StdDev => stats.map(|stats| percent(stats.stddev)),
                     }
                 }
             }
@@ -107,10 +131,7 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
         let style = match self {
             CpuWidgetTableData::All => painter.colours.all_cpu_colour,
-            CpuWidgetTableData::Entry {
-                data_type,
-                last_entry: _,
-            } => match data_type {
+            CpuWidgetTableData::Entry { data_type, .. } => match data_type {
                 CpuDataType::Avg => painter.colours.avg_cpu_colour,
                 CpuDataType::Cpu(index) => {
                     painter.colours.cpu_colour_styles
@@ -128,15 +149,23 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
     where
         Self: Sized,
     {
-        vec![1, 3]
+        vec![1, 3, 4, 4, 4, 4]
     }
 }
 
+fn percent(value: f64) -> Cow<'static, str> {
+    format!("{:.0}%", value.round()).into()
+}
+
 pub struct CpuWidgetState {
     pub current_display_time: u64,
     pub is_legend_hidden: bool,
     pub autohide_timer: Option<Instant>,
     pub table: DataTable<CpuWidgetTableData, CpuWidgetColumn>,
+    /// Whether the graph shows how fast usage changes rather than the usage.
+    pub is_delta: bool,
+    /// Whether the legend shows the stats of each entry's shown usage.
+    pub show_stats: bool,
 }
 
 impl CpuWidgetState {
@@ -144,13 +173,18 @@ impl CpuWidgetState {
         config: &AppConfigFields, default_selection: CpuDefault, current_display_time: u64,
         autohide_timer: Option<Instant>, colours: &ColourPalette,
     ) -> Self {
-        const COLUMNS: [Column<CpuWidgetColumn>; 2] = [
+        const COLUMNS: [Column<CpuWidgetColumn>; 6] = [
             Column::soft(CpuWidgetColumn::Cpu, Some(0.5)),
             Column::soft(CpuWidgetColumn::Use, Some(0.5)),
+            Column::hard(CpuWidgetColumn::Avg, 4),
+            Column::hard(CpuWidgetColumn::Min, 4),
+            Column::hard(CpuWidgetColumn::Max, 4),
+            Column::hard(CpuWidgetColumn::StdDev, 4),
         ];
 
         let props = DataTableProps {
             title: None,
+            footer: None,
             table_gap: config.table_gap,
             left_to_right: false,
             is_basic: false,
@@ -168,18 +202,43 @@ impl CpuWidgetState {
             }
         }
 
-        CpuWidgetState {
+        let mut state = CpuWidgetState {
             current_display_time,
             is_legend_hidden: false,
             autohide_timer,
             table,
+            is_delta: false,
+            show_stats: false,
+        };
+        state.set_show_stats(config.show_graph_stats);
+
+        state
+    }
+
+    pub fn toggle_delta(&mut self) {
+        self.is_delta = !self.is_delta;
+    }
+
+    pub fn toggle_stats(&mut self) {
+        self.set_show_stats(!self.show_stats);
+    }
+
+    /// Shows or hides the stats columns. While they're shown, columns are
+    /// fit from the left, so the stats are the first to be cut off rather
+    /// than the CPU names.
+    fn set_show_stats(&mut self, show_stats: bool) {
+        self.show_stats = show_stats;
+        self.table.props.left_to_right = show_stats;
+        for column in &mut self.table.columns[STATS_COLUMNS_START..] {
+            column.set_is_hidden(!show_stats);
         }
     }
 
     pub fn update_table(&mut self, data: &[CpuWidgetData]) {
+        let stats_time = self.show_stats.then_some(self.current_display_time);
         self.table.set_data(
             data.iter()
-                .map(CpuWidgetTableData::from_cpu_widget_data)
+                .map(|data| CpuWidgetTableData::from_cpu_widget_data(data, stats_time))
                 .collect(),
         );
     }
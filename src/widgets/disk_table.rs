@@ -8,11 +8,13 @@ use crate::{
         ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
         SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
+    data_collection::disks::SmartEndurance,
+    data_conversion::delta_string,
     options::config::style::ColourPalette,
     utils::{data_prefixes::get_decimal_bytes, general::sort_partial_fn},
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DiskWidgetData {
     pub name: Cow<'static, str>,
     pub mount_point: Cow<'static, str>,
@@ -22,6 +24,10 @@ pub struct DiskWidgetData {
     pub summed_total_bytes: Option<u64>,
     pub io_read: Cow<'static, str>,
     pub io_write: Cow<'static, str>,
+    /// How worn out the SSD the disk is on is, if its SMART data was read.
+    pub endurance: Option<SmartEndurance>,
+    /// The used space when the baseline snapshot was taken, in diff mode.
+    pub baseline_used_bytes: Option<u64>,
 }
 
 impl DiskWidgetData {
@@ -46,12 +52,51 @@ impl DiskWidgetData {
     fn used_space(&self) -> Cow<'static, str> {
         if let Some(used_bytes) = self.used_bytes {
             let converted_free_space = get_decimal_bytes(used_bytes);
-            format!("{:.0}{}", converted_free_space.0, converted_free_space.1).into()
+            let delta = self
+                .baseline_used_bytes
+                .map(|baseline| {
+                    delta_string(used_bytes, baseline, |bytes| {
+                        let (value, unit) = get_decimal_bytes(bytes);
+                        format!("{value:.0}{unit}")
+                    })
+                })
+                .unwrap_or_default();
+
+            format!(
+                "{:.0}{}{delta}",
+                converted_free_space.0, converted_free_space.1
+            )
+            .into()
         } else {
             "N/A".into()
         }
     }
 
+    /// A compact view of how worn out the disk is: the write amplification if
+    /// it's known, which is the case for some SATA SSDs, or else how much of
+    /// its life is left, as NVMe drives report.
+    fn wear(&self) -> Cow<'static, str> {
+        match self.endurance {
+            Some(SmartEndurance { waf: Some(waf), .. }) => format!("WAF {waf:.2}").into(),
+            Some(SmartEndurance {
+                remaining_life_pct: Some(life),
+                ..
+            }) => format!("{life}% left").into(),
+            _ => "N/A".into(),
+        }
+    }
+
+    /// The value the wear column is sorted by, where more worn out drives
+    /// are higher.
+    fn wear_value(&self) -> Option<f64> {
+        let endurance = self.endurance?;
+        endurance.waf.or_else(|| {
+            endurance
+                .remaining_life_pct
+                .map(|life| f64::from(100 - life))
+        })
+    }
+
     fn free_percent(&self) -> Option<f64> {
         if let (Some(free_bytes), Some(summed_total_bytes)) =
             (self.free_bytes, self.summed_total_bytes)
@@ -97,6 +142,7 @@ pub enum DiskColumn {
     FreePercent,
     IoRead,
     IoWrite,
+    Wear,
 }
 
 impl<'de> Deserialize<'de> for DiskColumn {
@@ -115,6 +161,7 @@ impl<'de> Deserialize<'de> for DiskColumn {
             "freepercent" | "free%" => Ok(DiskColumn::FreePercent),
             "r/s" => Ok(DiskColumn::IoRead),
             "w/s" => Ok(DiskColumn::IoWrite),
+            "wear" | "endurance" => Ok(DiskColumn::Wear),
             _ => Err(serde::de::Error::custom(
                 "doesn't match any disk column name",
             )),
@@ -136,6 +183,7 @@ impl DiskColumn {
             DiskColumn::FreePercent => &["Free%"],
             DiskColumn::IoRead => &["R/s", "Read", "Rps"],
             DiskColumn::IoWrite => &["W/s", "Write", "Wps"],
+            DiskColumn::Wear => &["Wear", "Endurance"],
         }
     }
 }
@@ -152,6 +200,7 @@ impl ColumnHeader for DiskColumn {
             DiskColumn::FreePercent => "Free%",
             DiskColumn::IoRead => "R/s(r)",
             DiskColumn::IoWrite => "W/s(w)",
+            DiskColumn::Wear => "Wear",
         }
         .into()
     }
@@ -178,6 +227,7 @@ impl DataToCell<DiskColumn> for DiskWidgetData {
             DiskColumn::Total => self.total_space(),
             DiskColumn::IoRead => self.io_read.clone(),
             DiskColumn::IoWrite => self.io_write.clone(),
+            DiskColumn::Wear => self.wear(),
         };
 
         Some(text)
@@ -239,6 +289,9 @@ impl SortsRow for DiskColumn {
             DiskColumn::IoWrite => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(&a.io_write, &b.io_write));
             }
+            DiskColumn::Wear => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.wear_value(), &b.wear_value()));
+            }
         }
     }
 }
@@ -258,6 +311,7 @@ const fn create_column(column_type: &DiskColumn) -> SortColumn<DiskColumn> {
         }
         DiskColumn::IoRead => SortColumn::hard(DiskColumn::IoRead, 10).default_descending(),
         DiskColumn::IoWrite => SortColumn::hard(DiskColumn::IoWrite, 11).default_descending(),
+        DiskColumn::Wear => SortColumn::hard(DiskColumn::Wear, 10).default_descending(),
     }
 }
 
@@ -281,6 +335,7 @@ impl DiskTableWidget {
         let props = SortDataTableProps {
             inner: DataTableProps {
                 title: Some(" Disks ".into()),
+                footer: None,
                 table_gap: config.table_gap,
                 left_to_right: true,
                 is_basic: config.use_basic_mode,
@@ -0,0 +1,70 @@
+/// The state of an exec widget, which shows what the `--exec` command printed
+/// the last time it ran.
+#[derive(Default)]
+pub struct ExecWidgetState {
+    /// How many lines the output is scrolled down by.
+    pub scroll_offset: usize,
+    /// The height of the widget's drawn area, used for paging.
+    pub height: u16,
+}
+
+impl ExecWidgetState {
+    /// The furthest the output can be scrolled, which leaves the last line at
+    /// the bottom of the widget.
+    fn max_scroll_offset(&self, num_lines: usize) -> usize {
+        num_lines.saturating_sub(self.height.into())
+    }
+
+    /// Scrolls by `amount` lines, returning the new offset if it changed.
+    pub fn scroll_by(&mut self, amount: i64, num_lines: usize) -> Option<usize> {
+        let max = self.max_scroll_offset(num_lines);
+        let new_offset = self
+            .scroll_offset
+            .saturating_add_signed(amount as isize)
+            .min(max);
+
+        (new_offset != self.scroll_offset).then(|| {
+            self.scroll_offset = new_offset;
+            new_offset
+        })
+    }
+
+    pub fn scroll_to_first(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    pub fn scroll_to_last(&mut self, num_lines: usize) {
+        self.scroll_offset = self.max_scroll_offset(num_lines);
+    }
+
+    /// Keeps the offset in range after the output has changed.
+    pub fn clamp(&mut self, num_lines: usize) {
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset(num_lines));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scrolling() {
+        let mut state = ExecWidgetState {
+            scroll_offset: 0,
+            height: 10,
+        };
+
+        assert_eq!(state.scroll_by(-1, 25), None);
+        assert_eq!(state.scroll_by(5, 25), Some(5));
+        assert_eq!(state.scroll_by(100, 25), Some(15));
+        assert_eq!(state.scroll_by(1, 25), None);
+
+        state.clamp(12);
+        assert_eq!(state.scroll_offset, 2);
+
+        state.scroll_to_first();
+        assert_eq!(state.scroll_offset, 0);
+        state.scroll_to_last(4);
+        assert_eq!(state.scroll_offset, 0);
+    }
+}
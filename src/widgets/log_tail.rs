@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// The state of a log tail widget, which shows the last lines of the files
+/// followed with `--watch_file`, one tab per file.
+#[derive(Default)]
+pub struct LogTailWidgetState {
+    /// The index of the file being shown.
+    pub selected_tab: usize,
+    /// How many lines the view is scrolled back by from the last line. At 0,
+    /// new lines are shown as they're written.
+    pub scroll_back: usize,
+    /// The height of the widget's drawn area, used for paging.
+    pub height: u16,
+    pub tab_click_locs: Option<Vec<((u16, u16), (u16, u16))>>,
+}
+
+impl LogTailWidgetState {
+    /// The furthest the view can be scrolled back, which leaves the first
+    /// line at the top of the widget.
+    fn max_scroll_back(&self, num_lines: usize) -> usize {
+        num_lines.saturating_sub(self.height.into())
+    }
+
+    /// Scrolls down by `amount` lines, or up if it's negative, returning the
+    /// new offset if it changed.
+    pub fn scroll_by(&mut self, amount: i64, num_lines: usize) -> Option<usize> {
+        let max = self.max_scroll_back(num_lines);
+        let new_scroll_back = self
+            .scroll_back
+            .saturating_add_signed(-amount as isize)
+            .min(max);
+
+        (new_scroll_back != self.scroll_back).then(|| {
+            self.scroll_back = new_scroll_back;
+            new_scroll_back
+        })
+    }
+
+    pub fn scroll_to_first(&mut self, num_lines: usize) {
+        self.scroll_back = self.max_scroll_back(num_lines);
+    }
+
+    pub fn scroll_to_last(&mut self) {
+        self.scroll_back = 0;
+    }
+
+    /// Keeps the same lines in view when `num_new_lines` are added to the
+    /// shown file while it's scrolled back, rather than following them.
+    pub fn on_new_lines(&mut self, num_new_lines: usize, num_lines: usize) {
+        if self.scroll_back > 0 {
+            self.scroll_back =
+                (self.scroll_back + num_new_lines).min(self.max_scroll_back(num_lines));
+        }
+    }
+
+    /// Shows the file at `index`, from its last line.
+    pub fn select_tab(&mut self, index: usize) {
+        if index != self.selected_tab {
+            self.selected_tab = index;
+            self.scroll_back = 0;
+        }
+    }
+}
+
+/// Splits `line` into rows of at most `width` columns.
+fn wrap_line(line: &str, width: usize) -> Vec<&str> {
+    if line.is_empty() || width == 0 {
+        return vec![line];
+    }
+
+    let mut rows = Vec::new();
+    let mut row_start = 0;
+    let mut row_width = 0;
+    for (index, grapheme) in line.grapheme_indices(true) {
+        let grapheme_width = grapheme.width();
+        if row_width + grapheme_width > width && row_width > 0 {
+            rows.push(&line[row_start..index]);
+            row_start = index;
+            row_width = 0;
+        }
+        row_width += grapheme_width;
+    }
+    rows.push(&line[row_start..]);
+
+    rows
+}
+
+/// The rows shown for `lines` in an area of `width` by `height`, with long
+/// lines wrapped, ending `scroll_back` lines from the last line.
+pub fn visible_rows(
+    lines: &VecDeque<String>, scroll_back: usize, width: u16, height: u16,
+) -> Vec<&str> {
+    let height = usize::from(height);
+    let end = lines.len().saturating_sub(scroll_back);
+
+    let mut rows = Vec::with_capacity(height);
+    for line in lines.range(..end).rev() {
+        if rows.len() >= height {
+            break;
+        }
+        for row in wrap_line(line, width.into()).into_iter().rev() {
+            if rows.len() >= height {
+                break;
+            }
+            rows.push(row);
+        }
+    }
+    rows.reverse();
+
+    rows
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(wrap_line("", 4), vec![""]);
+        assert_eq!(wrap_line("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_line("日本語", 4), vec!["日本", "語"]);
+
+        let lines = VecDeque::from([
+            "one".to_string(),
+            "twotwotwo".to_string(),
+            "three".to_string(),
+        ]);
+        assert_eq!(visible_rows(&lines, 0, 4, 3), vec!["o", "thre", "e"]);
+        assert_eq!(
+            visible_rows(&lines, 1, 4, 10),
+            vec!["one", "twot", "wotw", "o"]
+        );
+    }
+
+    #[test]
+    fn scrolling() {
+        let mut state = LogTailWidgetState {
+            height: 10,
+            ..Default::default()
+        };
+
+        assert_eq!(state.scroll_by(1, 25), None);
+        assert_eq!(state.scroll_by(-5, 25), Some(5));
+        assert_eq!(state.scroll_by(-100, 25), Some(15));
+
+        // Scrolled back, the same lines stay in view as more come in.
+        state.scroll_by(10, 25);
+        state.on_new_lines(3, 28);
+        assert_eq!(state.scroll_back, 8);
+
+        state.scroll_to_last();
+        state.on_new_lines(3, 31);
+        assert_eq!(state.scroll_back, 0);
+
+        state.scroll_to_first(31);
+        assert_eq!(state.scroll_back, 21);
+        state.select_tab(1);
+        assert_eq!(state.scroll_back, 0);
+    }
+}
@@ -3,13 +3,37 @@ use std::time::Instant;
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// Whether to show a table of each swap device under the graph.
+    pub show_swap_devices: bool,
+    /// Whether the graph shows how fast RAM usage changes rather than the
+    /// usage.
+    pub is_delta: bool,
+    /// Whether to show the stats of the shown usage over the graph.
+    pub show_stats: bool,
 }
 
 impl MemWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, show_stats: bool,
+    ) -> Self {
         MemWidgetState {
             current_display_time,
             autohide_timer,
+            show_swap_devices: false,
+            is_delta: false,
+            show_stats,
         }
     }
+
+    pub fn toggle_swap_devices(&mut self) {
+        self.show_swap_devices = !self.show_swap_devices;
+    }
+
+    pub fn toggle_delta(&mut self) {
+        self.is_delta = !self.is_delta;
+    }
+
+    pub fn toggle_stats(&mut self) {
+        self.show_stats = !self.show_stats;
+    }
 }
@@ -1,15 +1,67 @@
+pub mod nic_stats_panel;
+
 use std::time::Instant;
 
+use nic_stats_panel::NicStatsPanel;
+
+use crate::{app::AppConfigFields, options::config::style::ColourPalette};
+
 pub struct NetWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// Whether to show the stats of the shown rates over the graph.
+    pub show_stats: bool,
+    pub nic_stats_panel: NicStatsPanel,
 }
 
 impl NetWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub(crate) fn init(
+        config: &AppConfigFields, current_display_time: u64, autohide_timer: Option<Instant>,
+        palette: &ColourPalette,
+    ) -> Self {
         NetWidgetState {
             current_display_time,
             autohide_timer,
+            show_stats: config.show_graph_stats,
+            nic_stats_panel: NicStatsPanel::new(config, palette),
+        }
+    }
+
+    pub fn toggle_stats(&mut self) {
+        self.show_stats = !self.show_stats;
+    }
+
+    /// Opens the hardware statistics panel for the first of `interfaces`, or
+    /// closes it if it's open.
+    pub fn toggle_nic_stats_panel(&mut self, interfaces: &[String]) {
+        if self.nic_stats_panel.is_open() {
+            self.nic_stats_panel.close();
+        } else if let Some(interface) = interfaces.first() {
+            self.nic_stats_panel.open(interface);
+        }
+    }
+
+    /// Shows the hardware statistics of the interface before or after the
+    /// shown one in `interfaces`, if the panel is open.
+    pub fn switch_nic_stats_interface(&mut self, interfaces: &[String], is_next: bool) {
+        let Some(current) = &self.nic_stats_panel.interface else {
+            return;
+        };
+
+        let index = interfaces
+            .iter()
+            .position(|interface| interface == current)
+            .map_or(0, |index| {
+                if is_next {
+                    (index + 1).min(interfaces.len().saturating_sub(1))
+                } else {
+                    index.saturating_sub(1)
+                }
+            });
+        if let Some(interface) = interfaces.get(index) {
+            if interface != current {
+                self.nic_stats_panel.open(interface);
+            }
         }
     }
 }
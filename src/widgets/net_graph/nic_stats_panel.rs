@@ -0,0 +1,185 @@
+//! A panel listing the hardware statistics of a single network interface, as
+//! reported by its driver.
+
+use std::{borrow::Cow, cmp::max, collections::HashMap, num::NonZeroU16};
+
+use crate::{
+    app::AppConfigFields,
+    canvas::components::data_table::{
+        Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
+        DataToCell,
+    },
+    options::config::style::ColourPalette,
+};
+
+/// A single hardware statistic, named as the driver names it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NicStat {
+    pub name: String,
+    pub value: u64,
+}
+
+pub enum NicStatColumn {
+    Stat,
+    Value,
+}
+
+impl ColumnHeader for NicStatColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            NicStatColumn::Stat => "Statistic".into(),
+            NicStatColumn::Value => "Value".into(),
+        }
+    }
+}
+
+impl DataToCell<NicStatColumn> for NicStat {
+    fn to_cell(
+        &self, column: &NicStatColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(match column {
+            NicStatColumn::Stat => self.name.clone().into(),
+            NicStatColumn::Value => self.value.to_string().into(),
+        })
+    }
+
+    fn column_widths<C: DataTableColumn<NicStatColumn>>(
+        data: &[NicStat], _columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        let mut widths = vec![0; 2];
+
+        data.iter().for_each(|row| {
+            widths[0] = max(widths[0], row.name.len() as u16);
+            widths[1] = max(widths[1], row.value.to_string().len() as u16);
+        });
+
+        widths
+    }
+}
+
+/// Reads the driver and hardware statistics of an interface, or why they
+/// can't be read.
+#[cfg(all(target_os = "linux", feature = "ethtool"))]
+fn read_nic_stats(interface: &str) -> Result<(Option<String>, HashMap<String, u64>), &'static str> {
+    use crate::data_collection::network::ethtool::{get_nic_driver, get_nic_stats};
+
+    Ok((get_nic_driver(interface), get_nic_stats(interface)))
+}
+
+#[cfg(not(all(target_os = "linux", feature = "ethtool")))]
+fn read_nic_stats(
+    _interface: &str,
+) -> Result<(Option<String>, HashMap<String, u64>), &'static str> {
+    Err("needs the ethtool feature on Linux")
+}
+
+/// Sorts the statistics by name, since drivers return them in no particular
+/// order.
+fn sorted_stats(stats: HashMap<String, u64>) -> Vec<NicStat> {
+    let mut stats = stats
+        .into_iter()
+        .map(|(name, value)| NicStat { name, value })
+        .collect::<Vec<_>>();
+    stats.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    stats
+}
+
+/// The state of the hardware statistics panel, which is shown below the
+/// network graph while open. The statistics are read again on every update.
+pub struct NicStatsPanel {
+    /// The interface whose statistics are shown, if the panel is open.
+    pub interface: Option<String>,
+    pub table: DataTable<NicStat, NicStatColumn>,
+}
+
+impl NicStatsPanel {
+    pub(crate) fn new(config: &AppConfigFields, palette: &ColourPalette) -> Self {
+        const COLUMNS: [Column<NicStatColumn>; 2] = [
+            Column::soft(NicStatColumn::Stat, Some(0.7)),
+            Column::soft(NicStatColumn::Value, Some(0.3)),
+        ];
+
+        let props = DataTableProps {
+            title: None,
+            footer: None,
+            table_gap: config.table_gap,
+            left_to_right: true,
+            is_basic: false,
+            show_table_scroll_position: config.show_table_scroll_position,
+            show_current_entry_when_unfocused: false,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            interface: None,
+            table: DataTable::new(COLUMNS, props, styling),
+        }
+    }
+
+    /// Whether the panel is open.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.interface.is_some()
+    }
+
+    /// Opens the panel for the given interface.
+    pub fn open(&mut self, interface: &str) {
+        self.interface = Some(interface.to_string());
+        self.refresh();
+        self.table.scroll_to_first();
+    }
+
+    /// Reads the statistics of the shown interface again, keeping the same
+    /// position in the table.
+    pub fn refresh(&mut self) {
+        let Some(interface) = &self.interface else {
+            return;
+        };
+
+        let (title, stats) = match read_nic_stats(interface) {
+            Ok((_, stats)) if stats.is_empty() => (
+                format!(" {interface}: no statistics, which may need CAP_NET_ADMIN "),
+                vec![],
+            ),
+            Ok((Some(driver), stats)) => (format!(" {interface} ({driver}) "), sorted_stats(stats)),
+            Ok((None, stats)) => (format!(" {interface} "), sorted_stats(stats)),
+            Err(err) => (format!(" {interface}: hardware statistics {err} "), vec![]),
+        };
+
+        self.table.props.title = Some(format!("{title}(Esc to close) ").into());
+        self.table.set_data(stats);
+    }
+
+    /// Closes the panel.
+    pub fn close(&mut self) {
+        self.interface = None;
+        self.table.set_data(vec![]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sorting() {
+        let stats = HashMap::from([
+            ("tx_errors".to_string(), 2),
+            ("rx_missed_errors".to_string(), 5),
+            ("rx_queue_0_packets".to_string(), 100),
+        ]);
+
+        assert_eq!(
+            sorted_stats(stats)
+                .into_iter()
+                .map(|stat| stat.name)
+                .collect::<Vec<_>>(),
+            vec!["rx_missed_errors", "rx_queue_0_packets", "tx_errors"]
+        );
+    }
+}
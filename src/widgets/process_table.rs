@@ -1,28 +1,52 @@
+pub mod changes;
+pub mod column_preset;
 pub mod process_columns;
 pub mod process_data;
 pub mod query;
+pub mod rlimit_panel;
+pub mod security_panel;
+pub mod smaps_panel;
 mod sort_table;
+pub mod thread_panel;
+pub mod thresholds;
 
 use std::{borrow::Cow, collections::BTreeMap};
 
+use changes::{ChangeSort, ChangeTracker, EntryKey};
+pub use changes::{ChangeThresholds, DEFAULT_CHANGE_SORT_TICKS};
+pub use column_preset::{ColumnPreset, CUSTOM_PRESET_NAME};
 use hashbrown::{HashMap, HashSet};
 use indexmap::IndexSet;
 use itertools::Itertools;
 pub use process_columns::*;
 pub use process_data::*;
 use query::{parse_query, ProcessQuery};
+use rlimit_panel::RlimitPanel;
+use security_panel::SecurityPanel;
+use smaps_panel::SmapsPanel;
 use sort_table::SortTableColumn;
+use thread_panel::ThreadPanel;
+pub use thresholds::ProcThresholds;
+use unicode_segmentation::GraphemeCursor;
 
 use crate::{
     app::{
         data_farmer::{DataCollection, ProcessData},
+        visible_columns::VisibleColumns,
         AppConfigFields, AppSearchState,
     },
     canvas::components::data_table::{
         Column, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps,
         DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
-    data_collection::processes::{Pid, ProcessHarvest},
+    data_collection::{
+        processes::{
+            collect_threads, read_rlimits, read_security_flags, read_smaps, unusual_state_summary,
+            Pid, ProcessHarvest, ProcessNamespaces, ProcessState,
+        },
+        Data,
+    },
+    data_conversion::{convert_cpu_sparkline, convert_mem_sparkline},
     options::config::style::ColourPalette,
 };
 
@@ -89,6 +113,23 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
         User => SortColumn::soft(User, Some(0.05)),
         State => SortColumn::hard(State, 9),
         Time => SortColumn::new(Time),
+        CpuDelta => SortColumn::new(CpuDelta).default_descending(),
+        MemDelta => SortColumn::new(MemDelta).default_descending(),
+        MinorFaults => SortColumn::hard(MinorFaults, 8).default_descending(),
+        MajorFaults => SortColumn::hard(MajorFaults, 8).default_descending(),
+        CpuSparkline => SortColumn::hard(CpuSparkline, 10).default_descending(),
+        MemSparkline => SortColumn::hard(MemSparkline, 10).default_descending(),
+        WChan => SortColumn::soft(WChan, Some(0.15)),
+        PidNamespace => SortColumn::soft(PidNamespace, Some(0.15)),
+        NetNamespace => SortColumn::soft(NetNamespace, Some(0.15)),
+        MntNamespace => SortColumn::soft(MntNamespace, Some(0.15)),
+        UserNamespace => SortColumn::soft(UserNamespace, Some(0.15)),
+        CpuQuota => SortColumn::hard(CpuQuota, 13).default_descending(),
+        CpuThrottled => SortColumn::hard(CpuThrottled, 7).default_descending(),
+        Container => SortColumn::soft(Container, Some(0.15)),
+        Pss => SortColumn::hard(Pss, 8).default_descending(),
+        TcpConnections => SortColumn::hard(TcpConnections, 5).default_descending(),
+        InotifyWatches => SortColumn::hard(InotifyWatches, 7).default_descending(),
         #[cfg(feature = "gpu")]
         GpuMemValue => SortColumn::new(GpuMemValue).default_descending(),
         #[cfg(feature = "gpu")]
@@ -98,13 +139,67 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
     }
 }
 
-#[derive(Clone, Copy, Default)]
+/// The order a column is sorted in when it's picked to sort by.
+pub(crate) fn default_sort_order(column: ProcColumn) -> SortOrder {
+    make_column(column).default_order
+}
+
+#[derive(Clone, Default)]
 pub struct ProcTableConfig {
     pub is_case_sensitive: bool,
     pub is_match_whole_word: bool,
     pub is_use_regex: bool,
     pub show_memory_as_values: bool,
     pub is_command: bool,
+    pub show_diff: bool,
+    /// Changes of at most this many percentage points aren't highlighted in
+    /// diff mode.
+    pub diff_threshold: f64,
+    /// Names the snapshot being compared against in the title, in diff mode.
+    pub diff_label: Option<String>,
+    /// Rows are highlighted if their major page faults per second exceed this.
+    pub major_fault_threshold: u64,
+    /// Rows are highlighted if they have more TCP connections open than this.
+    pub tcp_connection_threshold: u64,
+    /// Rows are briefly highlighted if their usage changed by more than these
+    /// between updates, or not at all if [`None`].
+    pub change_thresholds: Option<ChangeThresholds>,
+    /// How many updates sorting by change lasts for.
+    pub change_sort_ticks: u64,
+    /// The columns whose top consumer is highlighted when highlighting top
+    /// consumers. If empty, the sorted column is used.
+    pub highlight_top: Vec<ProcColumn>,
+    /// The columns that stay in place when scrolling the table horizontally.
+    pub pinned_columns: Vec<ProcColumn>,
+    /// The order the columns were last moved into, if they ever were.
+    pub column_order: Vec<ProcColumn>,
+    /// The column to sort by at first, if not the default.
+    pub sort_by: Option<ProcWidgetColumn>,
+    /// The order to sort in at first, if not the column's default.
+    pub sort_order: Option<SortOrder>,
+    /// A regex to search for at first, if any.
+    pub default_filter: Option<String>,
+    /// The PID or name of the process being watched with `--pid`, if any.
+    pub watched_process: Option<String>,
+    /// The least usage processes need to be shown.
+    pub thresholds: ProcThresholds,
+}
+
+/// Returns a search that matches the process watched with `--pid`, either by
+/// its PID or by its exact name. The search is meant to be a regex.
+pub(crate) fn watched_process_filter(process: &str) -> String {
+    match process.parse::<Pid>() {
+        Ok(pid) => format!("pid = ^{pid}$"),
+        // Whitespace separates terms even within quotes, so it's matched with
+        // `\s` instead.
+        Err(_) => format!("\"^{}$\"", regex::escape(process).replace(' ', "\\s")),
+    }
+}
+
+/// Returns a search that matches the processes owned by the user given with
+/// `--filter_user`. The search is meant to be a regex.
+pub(crate) fn user_filter(user: &str) -> String {
+    format!("user = ^{}$", regex::escape(user).replace(' ', "\\s"))
 }
 
 /// A hacky workaround for now.
@@ -121,12 +216,72 @@ pub enum ProcWidgetColumn {
     User,
     State,
     Time,
+    CpuDelta,
+    MemDelta,
+    MinorFaults,
+    MajorFaults,
+    CpuSparkline,
+    MemSparkline,
+    WChan,
+    PidNamespace,
+    NetNamespace,
+    MntNamespace,
+    UserNamespace,
+    CpuQuota,
+    CpuThrottled,
+    Container,
+    Pss,
+    TcpConnections,
+    InotifyWatches,
     #[cfg(feature = "gpu")]
     GpuMem,
     #[cfg(feature = "gpu")]
     GpuUtil,
 }
 
+/// The columns that the process widget can be sorted by at first, by the name
+/// used to set them.
+const SORT_BY_COLUMNS: [(&str, ProcWidgetColumn); 9] = [
+    ("pid", ProcWidgetColumn::PidOrCount),
+    ("name", ProcWidgetColumn::ProcNameOrCommand),
+    ("cpu", ProcWidgetColumn::Cpu),
+    ("mem", ProcWidgetColumn::Mem),
+    ("io_read", ProcWidgetColumn::ReadPerSecond),
+    ("io_write", ProcWidgetColumn::WritePerSecond),
+    ("user", ProcWidgetColumn::User),
+    ("state", ProcWidgetColumn::State),
+    ("runtime", ProcWidgetColumn::Time),
+];
+
+impl ProcWidgetColumn {
+    /// Whether the column is anchored in place, so it can't be moved.
+    pub fn is_anchored(&self) -> bool {
+        matches!(
+            self,
+            ProcWidgetColumn::PidOrCount | ProcWidgetColumn::ProcNameOrCommand
+        )
+    }
+
+    /// Parses the name of a column to sort by at first, e.g. `mem`.
+    pub fn from_sort_by(name: &str) -> Result<Self, String> {
+        let lowercase = name.trim().to_ascii_lowercase();
+
+        SORT_BY_COLUMNS
+            .iter()
+            .find(|(sort_by, _)| *sort_by == lowercase)
+            .map(|(_, column)| *column)
+            .ok_or_else(|| {
+                format!(
+                    "'{name}' is not a valid column to sort by, it must be one of: {}",
+                    SORT_BY_COLUMNS
+                        .iter()
+                        .map(|(sort_by, _)| sort_by)
+                        .join(", ")
+                )
+            })
+    }
+}
+
 // This is temporary. Switch back to `ProcColumn` later!
 
 pub struct ProcWidgetState {
@@ -154,6 +309,82 @@ pub struct ProcWidgetState {
     /// The default sort order.
     default_sort_order: SortOrder,
 
+    /// Rows are highlighted if their major page faults per second exceed this.
+    major_fault_threshold: u64,
+
+    /// Rows are highlighted if they have more TCP connections open than this.
+    tcp_connection_threshold: u64,
+
+    /// Tracks usage between updates to highlight rows that changed a lot, if
+    /// enabled.
+    change_tracker: Option<ChangeTracker>,
+
+    /// Sorts entries by how much they changed since the previous update, when
+    /// turned on.
+    change_sort: ChangeSort,
+
+    /// Whether the top consumers are highlighted.
+    pub is_highlighting_top: bool,
+
+    /// The columns whose top consumer is highlighted. If empty, the sorted
+    /// column is used.
+    highlight_top_columns: Vec<ProcColumn>,
+
+    /// Changes of at most this many percentage points aren't highlighted in
+    /// diff mode.
+    diff_threshold: f64,
+
+    /// Names the snapshot being compared against, in diff mode.
+    diff_label: Option<String>,
+
+    /// Whether the diff columns are added, so they're kept when the columns
+    /// change.
+    show_diff: bool,
+
+    /// The columns that are pinned, if shown.
+    pinned_columns: Vec<ProcWidgetColumn>,
+
+    /// The name of the column preset being shown, if any.
+    preset_name: Option<String>,
+
+    /// The process being watched with `--pid`, so the title can say it's
+    /// being waited for until it exists.
+    watched_process: Option<String>,
+
+    /// Whether CPU usage is shown as a percentage of one core rather than of
+    /// all of them.
+    is_cpu_per_core: bool,
+
+    /// Whether CPU usage is collected as a percentage of one core, so it only
+    /// has to be scaled if that's not how it's shown.
+    is_cpu_collected_per_core: bool,
+
+    /// Whether the container column is hidden until a process in a container
+    /// is seen.
+    is_awaiting_containers: bool,
+
+    /// Whether the GPU memory column is hidden until a GPU is seen.
+    #[cfg(feature = "gpu")]
+    is_awaiting_gpus: bool,
+
+    /// The least usage processes need to be shown.
+    thresholds: ProcThresholds,
+
+    /// How many processes matching the search are hidden by the thresholds.
+    num_below_thresholds: usize,
+
+    /// How many entries are shown at most, outside of tree mode.
+    top_n: Option<usize>,
+
+    /// How many entries there were before only the top ones were kept.
+    num_before_top: usize,
+
+    /// The threads of the selected process, if open.
+    pub thread_panel: ThreadPanel,
+    pub rlimit_panel: RlimitPanel,
+    pub smaps_panel: SmapsPanel,
+    pub security_panel: SecurityPanel,
+
     pub is_sort_open: bool,
     pub force_rerender: bool,
     pub force_update_data: bool,
@@ -165,6 +396,7 @@ impl ProcWidgetState {
 
         let props = DataTableProps {
             title: None,
+            footer: None,
             table_gap: config.table_gap,
             left_to_right: true,
             is_basic: false,
@@ -182,6 +414,7 @@ impl ProcWidgetState {
     ) -> ProcessTable {
         let inner_props = DataTableProps {
             title: Some(" Processes ".into()),
+            footer: None,
             table_gap: config.table_gap,
             left_to_right: true,
             is_basic: config.use_basic_mode,
@@ -212,90 +445,248 @@ impl ProcWidgetState {
             if table_config.is_match_whole_word {
                 pss.search_toggle_whole_word();
             }
-            if table_config.is_use_regex {
+            // A filter set at startup is always a regex.
+            if table_config.is_use_regex || table_config.default_filter.is_some() {
                 pss.search_toggle_regex();
             }
 
+            if let Some(filter) = &table_config.default_filter {
+                pss.search_state.is_enabled = true;
+                pss.search_state.current_search_query = filter.clone();
+                pss.search_state.grapheme_cursor =
+                    GraphemeCursor::new(filter.len(), filter.len(), true);
+            }
+
             pss
         };
 
-        let columns: Vec<SortColumn<ProcColumn>> = {
-            use ProcColumn::*;
+        let columns = Self::make_columns(
+            config_columns.as_ref(),
+            matches!(mode, ProcWidgetMode::Grouped),
+            table_config.is_command,
+            table_config.show_memory_as_values,
+        );
+
+        let mut columns = if table_config.show_diff {
+            Self::with_diff_columns(columns)
+        } else {
+            columns
+        };
+        if !table_config.column_order.is_empty() {
+            columns = Self::with_column_order(columns, &table_config.column_order);
+        }
 
-            let is_count = matches!(mode, ProcWidgetMode::Grouped);
-            let is_command = table_config.is_command;
-            let mem_as_values = table_config.show_memory_as_values;
+        let column_mapping = Self::make_column_mapping(&columns);
 
-            match config_columns {
-                Some(columns) if !columns.is_empty() => columns
-                    .into_iter()
-                    .map(|c| {
-                        let col = match c {
-                            ProcWidgetColumn::PidOrCount => {
-                                if is_count {
-                                    Count
-                                } else {
-                                    Pid
-                                }
+        let pinned_columns = table_config
+            .pinned_columns
+            .iter()
+            .map(ProcWidgetColumn::from)
+            .collect::<Vec<_>>();
+        for (column, mapped) in columns.iter_mut().zip(&column_mapping) {
+            column.is_pinned = pinned_columns.contains(mapped);
+        }
+
+        let (default_sort_index, default_sort_order) =
+            Self::default_sort(&mode, &columns, &column_mapping);
+
+        // A column set to sort by takes precedence over the default.
+        let (default_sort_index, default_sort_order) = match table_config
+            .sort_by
+            .and_then(|column| column_mapping.get_index_of(&column))
+        {
+            Some(index) => (index, columns[index].default_order),
+            None => (default_sort_index, default_sort_order),
+        };
+        let default_sort_order = table_config.sort_order.unwrap_or(default_sort_order);
+
+        let is_awaiting_containers = columns
+            .iter()
+            .any(|column| *column.inner() == ProcColumn::Container && column.is_hidden);
+        #[cfg(feature = "gpu")]
+        let is_awaiting_gpus = columns.iter().any(|column| {
+            matches!(
+                column.inner(),
+                ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent
+            ) && column.is_hidden
+        });
+
+        let sort_table = Self::new_sort_table(config, colours);
+        let table = Self::new_process_table(
+            config,
+            colours,
+            columns,
+            default_sort_index,
+            default_sort_order,
+        );
+
+        let id_pid_map = HashMap::default();
+
+        let mut table = ProcWidgetState {
+            proc_search: process_search_state,
+            table,
+            sort_table,
+            id_pid_map,
+            column_mapping,
+            is_sort_open: false,
+            mode,
+            force_rerender: true,
+            force_update_data: false,
+            default_sort_index,
+            default_sort_order,
+            major_fault_threshold: table_config.major_fault_threshold,
+            tcp_connection_threshold: table_config.tcp_connection_threshold,
+            change_tracker: table_config.change_thresholds.map(ChangeTracker::new),
+            change_sort: ChangeSort::new(table_config.change_sort_ticks),
+            is_highlighting_top: false,
+            highlight_top_columns: table_config.highlight_top,
+            diff_threshold: table_config.diff_threshold,
+            diff_label: table_config.diff_label,
+            show_diff: table_config.show_diff,
+            pinned_columns,
+            preset_name: None,
+            watched_process: table_config.watched_process,
+            is_cpu_per_core: config.unnormalized_cpu,
+            is_cpu_collected_per_core: config.unnormalized_cpu,
+            is_awaiting_containers,
+            #[cfg(feature = "gpu")]
+            is_awaiting_gpus,
+            thresholds: table_config.thresholds,
+            num_below_thresholds: 0,
+            top_n: None,
+            num_before_top: 0,
+            thread_panel: ThreadPanel::new(config, colours),
+            rlimit_panel: RlimitPanel::new(config, colours),
+            smaps_panel: SmapsPanel::new(config, colours),
+            security_panel: SecurityPanel::new(config, colours),
+        };
+        table.sort_table.set_data(table.column_text());
+        table.update_query();
+
+        table
+    }
+
+    /// Makes the columns to show, which are either the given ones or the
+    /// default ones.
+    fn make_columns(
+        config_columns: Option<&IndexSet<ProcWidgetColumn>>, is_count: bool, is_command: bool,
+        mem_as_values: bool,
+    ) -> Vec<SortColumn<ProcColumn>> {
+        use ProcColumn::*;
+
+        match config_columns {
+            Some(columns) if !columns.is_empty() => columns
+                .into_iter()
+                .map(|c| {
+                    let col = match c {
+                        ProcWidgetColumn::PidOrCount => {
+                            if is_count {
+                                Count
+                            } else {
+                                Pid
                             }
-                            ProcWidgetColumn::ProcNameOrCommand => {
-                                if is_command {
-                                    Command
-                                } else {
-                                    Name
-                                }
+                        }
+                        ProcWidgetColumn::ProcNameOrCommand => {
+                            if is_command {
+                                Command
+                            } else {
+                                Name
                             }
-                            ProcWidgetColumn::Cpu => CpuPercent,
-                            ProcWidgetColumn::Mem => {
-                                if mem_as_values {
-                                    MemValue
-                                } else {
-                                    MemPercent
-                                }
+                        }
+                        ProcWidgetColumn::Cpu => CpuPercent,
+                        ProcWidgetColumn::Mem => {
+                            if mem_as_values {
+                                MemValue
+                            } else {
+                                MemPercent
                             }
-                            ProcWidgetColumn::ReadPerSecond => ReadPerSecond,
-                            ProcWidgetColumn::WritePerSecond => WritePerSecond,
-                            ProcWidgetColumn::TotalRead => TotalRead,
-                            ProcWidgetColumn::TotalWrite => TotalWrite,
-                            ProcWidgetColumn::User => User,
-                            ProcWidgetColumn::State => State,
-                            ProcWidgetColumn::Time => Time,
-                            #[cfg(feature = "gpu")]
-                            ProcWidgetColumn::GpuMem => {
-                                if mem_as_values {
-                                    GpuMemValue
-                                } else {
-                                    GpuMemPercent
-                                }
+                        }
+                        ProcWidgetColumn::ReadPerSecond => ReadPerSecond,
+                        ProcWidgetColumn::WritePerSecond => WritePerSecond,
+                        ProcWidgetColumn::TotalRead => TotalRead,
+                        ProcWidgetColumn::TotalWrite => TotalWrite,
+                        ProcWidgetColumn::User => User,
+                        ProcWidgetColumn::State => State,
+                        ProcWidgetColumn::Time => Time,
+                        ProcWidgetColumn::CpuDelta => CpuDelta,
+                        ProcWidgetColumn::MemDelta => MemDelta,
+                        ProcWidgetColumn::MinorFaults => MinorFaults,
+                        ProcWidgetColumn::MajorFaults => MajorFaults,
+                        ProcWidgetColumn::CpuSparkline => CpuSparkline,
+                        ProcWidgetColumn::MemSparkline => MemSparkline,
+                        ProcWidgetColumn::WChan => WChan,
+                        ProcWidgetColumn::PidNamespace => PidNamespace,
+                        ProcWidgetColumn::NetNamespace => NetNamespace,
+                        ProcWidgetColumn::MntNamespace => MntNamespace,
+                        ProcWidgetColumn::UserNamespace => UserNamespace,
+                        ProcWidgetColumn::CpuQuota => CpuQuota,
+                        ProcWidgetColumn::CpuThrottled => CpuThrottled,
+                        ProcWidgetColumn::Container => Container,
+                        ProcWidgetColumn::Pss => Pss,
+                        ProcWidgetColumn::TcpConnections => TcpConnections,
+                        ProcWidgetColumn::InotifyWatches => InotifyWatches,
+                        #[cfg(feature = "gpu")]
+                        ProcWidgetColumn::GpuMem => {
+                            if mem_as_values {
+                                GpuMemValue
+                            } else {
+                                GpuMemPercent
                             }
-                            #[cfg(feature = "gpu")]
-                            ProcWidgetColumn::GpuUtil => GpuUtilPercent,
-                        };
+                        }
+                        #[cfg(feature = "gpu")]
+                        ProcWidgetColumn::GpuUtil => GpuUtilPercent,
+                    };
 
-                        make_column(col)
-                    })
-                    .collect(),
-                _ => {
-                    let default_columns = [
-                        if is_count { Count } else { Pid },
-                        if is_command { Command } else { Name },
-                        CpuPercent,
-                        if mem_as_values { MemValue } else { MemPercent },
-                        ReadPerSecond,
-                        WritePerSecond,
-                        TotalRead,
-                        TotalWrite,
-                        User,
-                        State,
-                        Time,
-                    ];
-
-                    default_columns.into_iter().map(make_column).collect()
+                    make_column(col)
+                })
+                .collect(),
+            _ => {
+                let default_columns = [
+                    if is_count { Count } else { Pid },
+                    if is_command { Command } else { Name },
+                    CpuPercent,
+                    if mem_as_values { MemValue } else { MemPercent },
+                    ReadPerSecond,
+                    WritePerSecond,
+                    TotalRead,
+                    TotalWrite,
+                    User,
+                    State,
+                    Time,
+                ];
+
+                let mut columns = default_columns
+                    .into_iter()
+                    .map(make_column)
+                    .collect::<Vec<_>>();
+
+                // Only shown once a GPU is seen, after the memory column.
+                #[cfg(feature = "gpu")]
+                {
+                    let mut gpu_mem = make_column(if mem_as_values {
+                        GpuMemValue
+                    } else {
+                        GpuMemPercent
+                    });
+                    gpu_mem.is_hidden = true;
+                    columns.insert(4, gpu_mem);
+                }
+
+                // Only shown once a process in a container is seen.
+                if cfg!(target_os = "linux") {
+                    let mut container = make_column(Container);
+                    container.is_hidden = true;
+                    columns.push(container);
                 }
+
+                columns
             }
-        };
+        }
+    }
 
-        let column_mapping = columns
+    /// Maps each column to the kind of column it is.
+    fn make_column_mapping(columns: &[SortColumn<ProcColumn>]) -> IndexSet<ProcWidgetColumn> {
+        columns
             .iter()
             .map(|col| {
                 use ProcColumn::*;
@@ -312,54 +703,241 @@ impl ProcWidgetState {
                     State => ProcWidgetColumn::State,
                     User => ProcWidgetColumn::User,
                     Time => ProcWidgetColumn::Time,
+                    CpuDelta => ProcWidgetColumn::CpuDelta,
+                    MemDelta => ProcWidgetColumn::MemDelta,
+                    MinorFaults => ProcWidgetColumn::MinorFaults,
+                    MajorFaults => ProcWidgetColumn::MajorFaults,
+                    CpuSparkline => ProcWidgetColumn::CpuSparkline,
+                    MemSparkline => ProcWidgetColumn::MemSparkline,
+                    WChan => ProcWidgetColumn::WChan,
+                    PidNamespace => ProcWidgetColumn::PidNamespace,
+                    NetNamespace => ProcWidgetColumn::NetNamespace,
+                    MntNamespace => ProcWidgetColumn::MntNamespace,
+                    UserNamespace => ProcWidgetColumn::UserNamespace,
+                    CpuQuota => ProcWidgetColumn::CpuQuota,
+                    CpuThrottled => ProcWidgetColumn::CpuThrottled,
+                    Container => ProcWidgetColumn::Container,
+                    Pss => ProcWidgetColumn::Pss,
+                    TcpConnections => ProcWidgetColumn::TcpConnections,
+                    InotifyWatches => ProcWidgetColumn::InotifyWatches,
                     #[cfg(feature = "gpu")]
                     GpuMemValue | GpuMemPercent => ProcWidgetColumn::GpuMem,
                     #[cfg(feature = "gpu")]
                     GpuUtilPercent => ProcWidgetColumn::GpuUtil,
                 }
             })
-            .collect::<IndexSet<_>>();
+            .collect()
+    }
 
-        let (default_sort_index, default_sort_order) =
-            if matches!(mode, ProcWidgetMode::Tree { .. }) {
-                if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
-                    (index, columns[index].default_order)
-                } else {
-                    (0, columns[0].default_order)
-                }
-            } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
+    /// The column to sort by and its order if none was picked, which is the
+    /// PID in tree mode and CPU usage otherwise.
+    fn default_sort(
+        mode: &ProcWidgetMode, columns: &[SortColumn<ProcColumn>],
+        column_mapping: &IndexSet<ProcWidgetColumn>,
+    ) -> (usize, SortOrder) {
+        if matches!(mode, ProcWidgetMode::Tree { .. }) {
+            if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
                 (index, columns[index].default_order)
             } else {
                 (0, columns[0].default_order)
+            }
+        } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
+            (index, columns[index].default_order)
+        } else {
+            (0, columns[0].default_order)
+        }
+    }
+
+    /// Adds the diff columns right after their corresponding CPU and memory
+    /// columns, if they aren't already present.
+    fn with_diff_columns(columns: Vec<SortColumn<ProcColumn>>) -> Vec<SortColumn<ProcColumn>> {
+        let has_column = |column: ProcColumn| columns.iter().any(|c| *c.inner() == column);
+        let add_cpu_delta = !has_column(ProcColumn::CpuDelta);
+        let add_mem_delta = !has_column(ProcColumn::MemDelta);
+
+        let mut new_columns = Vec::with_capacity(columns.len() + 2);
+        for column in columns {
+            let delta = match column.inner() {
+                ProcColumn::CpuPercent if add_cpu_delta => Some(ProcColumn::CpuDelta),
+                ProcColumn::MemValue | ProcColumn::MemPercent if add_mem_delta => {
+                    Some(ProcColumn::MemDelta)
+                }
+                _ => None,
             };
 
-        let sort_table = Self::new_sort_table(config, colours);
-        let table = Self::new_process_table(
-            config,
-            colours,
-            columns,
-            default_sort_index,
-            default_sort_order,
-        );
+            new_columns.push(column);
+            new_columns.extend(delta.map(make_column));
+        }
 
-        let id_pid_map = HashMap::default();
+        new_columns
+    }
 
-        let mut table = ProcWidgetState {
-            proc_search: process_search_state,
-            table,
-            sort_table,
-            id_pid_map,
-            column_mapping,
-            is_sort_open: false,
-            mode,
-            force_rerender: true,
-            force_update_data: false,
-            default_sort_index,
-            default_sort_order,
+    /// Rearranges the columns that are in `order` to follow it. Other columns,
+    /// including the anchored PID and name columns, stay where they are.
+    fn with_column_order(
+        columns: Vec<SortColumn<ProcColumn>>, order: &[ProcColumn],
+    ) -> Vec<SortColumn<ProcColumn>> {
+        let order = order
+            .iter()
+            .map(ProcWidgetColumn::from)
+            .filter(|column| !column.is_anchored())
+            .collect::<Vec<_>>();
+        let position = |column: &SortColumn<ProcColumn>| {
+            order
+                .iter()
+                .position(|c| *c == ProcWidgetColumn::from(column.inner()))
         };
-        table.sort_table.set_data(table.column_text());
 
-        table
+        let (mut ordered, rest): (Vec<_>, Vec<_>) = columns
+            .into_iter()
+            .enumerate()
+            .partition(|(_, column)| position(column).is_some());
+        let slots = ordered.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        ordered.sort_by_key(|(_, column)| position(column));
+
+        let mut columns = rest;
+        columns.extend(slots.into_iter().zip(ordered.into_iter().map(|(_, c)| c)));
+        columns.sort_by_key(|(index, _)| *index);
+        columns.into_iter().map(|(_, column)| column).collect()
+    }
+
+    /// Moves the column selected in the sort menu one place to the left or
+    /// right, past any hidden columns, returning whether it moved. Hidden
+    /// columns keep their place so they come back where they were, and the
+    /// anchored PID and name columns can't be moved or moved past.
+    pub fn move_sort_table_column(&mut self, right: bool) -> bool {
+        let visible = (0..self.table.columns.len())
+            .filter(|index| !self.table.columns[*index].is_hidden)
+            .collect::<Vec<_>>();
+        let position = self.sort_table.current_index();
+        let target_position = if right {
+            position + 1
+        } else {
+            match position.checked_sub(1) {
+                Some(target) => target,
+                None => return false,
+            }
+        };
+        let (Some(&index), Some(&target)) = (visible.get(position), visible.get(target_position))
+        else {
+            return false;
+        };
+
+        let is_anchored = |index: usize| {
+            self.column_mapping
+                .get_index(index)
+                .is_some_and(ProcWidgetColumn::is_anchored)
+        };
+        if is_anchored(index) || is_anchored(target) {
+            return false;
+        }
+
+        self.table.columns.swap(index, target);
+        self.column_mapping.swap_indices(index, target);
+
+        // The sort follows the column it was on.
+        let swapped = |i: usize| match i {
+            i if i == index => target,
+            i if i == target => index,
+            i => i,
+        };
+        let order = self.table.order();
+        self.table.set_sort_index(swapped(self.table.sort_index()));
+        self.table.set_order(order);
+        self.default_sort_index = swapped(self.default_sort_index);
+
+        // The columns no longer match a preset.
+        if self.preset_name.is_some() {
+            self.preset_name = Some(CUSTOM_PRESET_NAME.to_string());
+        }
+
+        self.sort_table.set_data(self.column_text());
+        self.sort_table.set_position(target_position);
+        self.force_rerender_and_update();
+
+        true
+    }
+
+    /// The order of every column, including hidden ones.
+    pub fn column_order(&self) -> Vec<ProcColumn> {
+        self.table
+            .columns
+            .iter()
+            .map(|column| *column.inner())
+            .collect()
+    }
+
+    /// Replaces the columns with the given ones, such as those of a column
+    /// preset, which is named in the title. Whether the name column shows
+    /// commands and memory is shown as values carry over, and the sort goes
+    /// back to the default for the new columns.
+    pub fn set_columns(&mut self, columns: &IndexSet<ProcWidgetColumn>, preset: Option<String>) {
+        if columns.is_empty() {
+            return;
+        }
+
+        let mem_as_values = self
+            .table
+            .columns
+            .iter()
+            .any(|column| matches!(column.inner(), ProcColumn::MemValue));
+        let mut columns = Self::make_columns(
+            Some(columns),
+            matches!(self.mode, ProcWidgetMode::Grouped),
+            self.is_using_command(),
+            mem_as_values,
+        );
+        if self.show_diff {
+            columns = Self::with_diff_columns(columns);
+        }
+
+        let column_mapping = Self::make_column_mapping(&columns);
+        for (column, mapped) in columns.iter_mut().zip(&column_mapping) {
+            column.is_pinned = self.pinned_columns.contains(mapped);
+        }
+
+        let (sort_index, sort_order) = Self::default_sort(&self.mode, &columns, &column_mapping);
+        self.default_sort_index = sort_index;
+        self.default_sort_order = sort_order;
+        self.table.columns = columns;
+        self.column_mapping = column_mapping;
+        self.table.set_sort_index(sort_index);
+        self.table.set_order(sort_order);
+        self.is_awaiting_containers = false;
+        #[cfg(feature = "gpu")]
+        {
+            self.is_awaiting_gpus = false;
+        }
+        self.preset_name = preset;
+
+        if let ProcWidgetMode::Grouped = self.mode {
+            self.hide_column(ProcWidgetColumn::User);
+            self.hide_column(ProcWidgetColumn::State);
+        }
+
+        self.sort_table.set_data(self.column_text());
+        self.sort_table.set_position(0);
+        self.force_rerender_and_update();
+    }
+
+    /// The name of the column preset being shown, if any.
+    pub fn preset_name(&self) -> Option<&str> {
+        self.preset_name.as_deref()
+    }
+
+    pub fn set_preset_name(&mut self, preset: Option<String>) {
+        self.preset_name = preset;
+        self.force_rerender = true;
+    }
+
+    /// The columns that aren't hidden, in order.
+    pub fn shown_columns(&self) -> Vec<ProcColumn> {
+        self.table
+            .columns
+            .iter()
+            .filter(|column| !column.is_hidden)
+            .map(|column| *column.inner())
+            .collect()
     }
 
     pub fn is_using_command(&self) -> bool {
@@ -374,37 +952,584 @@ impl ProcWidgetState {
             .unwrap_or(false)
     }
 
-    pub fn is_mem_percent(&self) -> bool {
-        self.column_mapping
-            .get_index_of(&ProcWidgetColumn::Mem)
-            .and_then(|index| self.table.columns.get(index))
-            .map(|col| matches!(col.inner(), ProcColumn::MemPercent))
-            .unwrap_or(false)
-    }
+    pub fn is_mem_percent(&self) -> bool {
+        self.column_mapping
+            .get_index_of(&ProcWidgetColumn::Mem)
+            .and_then(|index| self.table.columns.get(index))
+            .map(|col| matches!(col.inner(), ProcColumn::MemPercent))
+            .unwrap_or(false)
+    }
+
+    fn get_query(&self) -> &Option<ProcessQuery> {
+        if self.proc_search.search_state.is_invalid_or_blank_search() {
+            &None
+        } else {
+            &self.proc_search.search_state.query
+        }
+    }
+
+    /// Update the current table data.
+    ///
+    /// This function *only* updates the displayed process data. If there is a
+    /// need to update the actual *stored* data, call it before this
+    /// function.
+    ///
+    /// If a baseline is given, each entry is also compared against it.
+    pub fn set_table_data(&mut self, data_collection: &DataCollection, baseline: Option<&Data>) {
+        if self.is_awaiting_containers
+            && data_collection
+                .process_data
+                .process_harvest
+                .values()
+                .any(|process| process.container.is_some())
+        {
+            self.is_awaiting_containers = false;
+            self.show_column(ProcWidgetColumn::Container);
+            self.sort_table.set_data(self.column_text());
+            self.force_rerender = true;
+        }
+
+        #[cfg(feature = "gpu")]
+        if self.is_awaiting_gpus
+            && (!data_collection.gpu_harvest.is_empty()
+                || data_collection
+                    .process_data
+                    .process_harvest
+                    .values()
+                    .any(|process| process.gpu_mem > 0))
+        {
+            self.is_awaiting_gpus = false;
+            self.show_column(ProcWidgetColumn::GpuMem);
+            self.sort_table.set_data(self.column_text());
+            self.force_rerender = true;
+        }
+
+        self.num_below_thresholds = self.count_below_thresholds(data_collection);
+        self.change_sort.update(data_collection.current_instant);
+        let mut data = match &self.mode {
+            ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
+                self.get_normal_data(&data_collection.process_data.process_harvest)
+            }
+            ProcWidgetMode::Tree { collapsed_pids } => {
+                self.get_tree_data(collapsed_pids, data_collection)
+            }
+        };
+
+        if let Some(baseline) = baseline.and_then(|b| b.list_of_processes.as_ref()) {
+            self.apply_baseline(
+                &mut data,
+                &data_collection.process_data.process_harvest,
+                baseline,
+            );
+        }
+
+        let cpu_factor = self.cpu_factor(data_collection.num_cpus);
+        if cpu_factor != 1.0 {
+            for row in &mut data {
+                row.scale_cpu(cpu_factor);
+            }
+        }
+
+        let major_fault_threshold = self.major_fault_threshold as f64;
+        let inotify_watch_threshold = data_collection.max_inotify_watches.map(|max| max / 2);
+        for row in &mut data {
+            row.is_high_major_faults = row.majflt_per_sec > major_fault_threshold;
+            row.is_high_tcp_connections = row
+                .tcp_connections
+                .is_some_and(|count| u64::from(count) > self.tcp_connection_threshold);
+            row.is_high_inotify_watches = row
+                .inotify_watches
+                .zip(inotify_watch_threshold)
+                .is_some_and(|(count, threshold)| count > threshold);
+        }
+
+        if let Some(tracker) = &mut self.change_tracker {
+            let harvest = &data_collection.process_data.process_harvest;
+            if tracker.update(data_collection.current_instant, harvest) {
+                for row in &mut data {
+                    row.is_changed = match self.mode {
+                        ProcWidgetMode::Grouped => self
+                            .id_pid_map
+                            .get(row.id.as_str())
+                            .is_some_and(|pids| pids.iter().any(|pid| tracker.is_changed(*pid))),
+                        _ => tracker.is_changed(row.pid),
+                    };
+                }
+            }
+        }
+
+        self.set_sparklines(&mut data, &data_collection.process_data);
+        self.set_namespace_shares(&mut data);
+        let states = data_collection
+            .process_data
+            .process_harvest
+            .values()
+            .map(|process| ProcessState::from(process.process_state.1));
+        self.set_title(&mut data, unusual_state_summary(states));
+        let num_high_inotify = data
+            .iter()
+            .filter(|row| row.is_high_inotify_watches)
+            .count();
+        self.table.props.footer =
+            self.footer(num_high_inotify, data_collection.max_inotify_watches);
+
+        self.table.set_data(data);
+    }
+
+    /// Returns the footer, which says how many processes are hidden by the
+    /// thresholds, and what the inotify watch limit is while the inotify column
+    /// is shown, warning if any process has more than half of it.
+    fn footer(
+        &self, num_high_inotify: usize, max_inotify_watches: Option<u32>,
+    ) -> Option<Cow<'static, str>> {
+        let mut parts = vec![];
+        if self.num_below_thresholds > 0 {
+            parts.push(format!("{} hidden", self.num_below_thresholds));
+        }
+
+        let shows_inotify = self.visible_columns().contains(VisibleColumns::INOTIFY);
+        if let Some(max) = max_inotify_watches.filter(|_| shows_inotify) {
+            parts.push(format!("inotify limit: {max}"));
+            if num_high_inotify > 0 {
+                parts.push(format!("⚠ {num_high_inotify} over half the inotify limit"));
+            }
+        }
+
+        (!parts.is_empty()).then(|| format!(" {} ", parts.join(" ─ ")).into())
+    }
+
+    /// Sets the least usage processes need to be shown.
+    pub fn set_thresholds(&mut self, thresholds: ProcThresholds) {
+        self.thresholds = thresholds;
+        self.force_data_update();
+    }
+
+    /// Sets how many entries are shown at most, keeping those that sort first.
+    pub fn set_top_n(&mut self, top_n: Option<usize>) {
+        self.top_n = top_n;
+        self.force_data_update();
+    }
+
+    /// Counts the processes that match the search but are hidden for using
+    /// less than the thresholds.
+    fn count_below_thresholds(&self, data_collection: &DataCollection) -> usize {
+        if !self.thresholds.is_set() {
+            return 0;
+        }
+
+        let search_query = self.get_query();
+        let is_using_command = self.is_using_command();
+
+        data_collection
+            .process_data
+            .process_harvest
+            .values()
+            .filter(|process| {
+                search_query
+                    .as_ref()
+                    .map(|query| query.check(process, is_using_command))
+                    .unwrap_or(true)
+                    && !self.thresholds.is_met(process)
+            })
+            .count()
+    }
+
+    /// Pauses or resumes highlighting rows whose usage changed, such as while
+    /// frozen.
+    pub fn pause_change_highlights(&mut self, is_paused: bool) {
+        if let Some(tracker) = &mut self.change_tracker {
+            if tracker.is_paused != is_paused {
+                tracker.is_paused = is_paused;
+                self.force_data_update();
+            }
+        }
+    }
+
+    /// Whether entries are being sorted by how much they changed, which isn't
+    /// done in tree mode.
+    fn is_sorting_by_change(&self) -> bool {
+        self.change_sort.is_active() && !matches!(self.mode, ProcWidgetMode::Tree { .. })
+    }
+
+    /// Sorts entries by how much the sorted column changed since the previous
+    /// update for the next few updates, or goes back to sorting normally.
+    pub fn toggle_change_sort(&mut self) {
+        if !matches!(self.mode, ProcWidgetMode::Tree { .. }) {
+            self.change_sort.toggle();
+            self.force_data_update();
+        }
+    }
+
+    /// Toggles highlighting the top consumers.
+    pub fn toggle_highlight_top(&mut self) {
+        self.is_highlighting_top = !self.is_highlighting_top;
+        self.force_data_update();
+    }
+
+    /// Opens the thread panel for the selected process, or closes it if it's
+    /// already showing that process. Grouped entries aren't a single process,
+    /// so they have no threads to show.
+    pub fn toggle_thread_panel(&mut self) {
+        if let ProcWidgetMode::Grouped = self.mode {
+            return;
+        }
+
+        let Some(process) = self.table.current_item() else {
+            return;
+        };
+
+        if self.thread_panel.pid == Some(process.pid) {
+            self.thread_panel.close();
+        } else {
+            self.rlimit_panel.close();
+            self.smaps_panel.close();
+            self.security_panel.close();
+            self.thread_panel.open(
+                process.pid,
+                process.id.as_str().to_string(),
+                process.container.clone(),
+            );
+            self.update_thread_panel();
+        }
+        self.force_rerender = true;
+    }
+
+    /// Opens the resource limit panel for the selected process, or closes it
+    /// if it's already showing that process. Like the thread panel, this isn't
+    /// available for grouped entries.
+    pub fn toggle_rlimit_panel(&mut self) {
+        if let ProcWidgetMode::Grouped = self.mode {
+            return;
+        }
+
+        let Some(process) = self.table.current_item() else {
+            return;
+        };
+
+        if self.rlimit_panel.pid == Some(process.pid) {
+            self.rlimit_panel.close();
+        } else {
+            let (pid, name) = (process.pid, process.id.as_str().to_string());
+            self.thread_panel.close();
+            self.smaps_panel.close();
+            self.security_panel.close();
+            self.rlimit_panel.open(pid, &name, read_rlimits(pid));
+        }
+        self.force_rerender = true;
+    }
+
+    /// Opens the memory map panel for the selected process, or closes it if
+    /// it's already showing that process. Like the other panels, this isn't
+    /// available for grouped entries.
+    pub fn toggle_smaps_panel(&mut self) {
+        if let ProcWidgetMode::Grouped = self.mode {
+            return;
+        }
+
+        let Some(process) = self.table.current_item() else {
+            return;
+        };
+
+        if self.smaps_panel.pid == Some(process.pid) {
+            self.smaps_panel.close();
+        } else {
+            let (pid, name) = (process.pid, process.id.as_str().to_string());
+            self.thread_panel.close();
+            self.rlimit_panel.close();
+            self.security_panel.close();
+            self.smaps_panel.open(pid, &name, read_smaps(pid));
+        }
+        self.force_rerender = true;
+    }
+
+    /// Opens the security panel for the selected process, or closes it if
+    /// it's already showing that process. Like the other panels, this isn't
+    /// available for grouped entries.
+    pub fn toggle_security_panel(&mut self) {
+        if let ProcWidgetMode::Grouped = self.mode {
+            return;
+        }
+
+        let Some(process) = self.table.current_item() else {
+            return;
+        };
+
+        if self.security_panel.pid == Some(process.pid) {
+            self.security_panel.close();
+        } else {
+            let (pid, name) = (process.pid, process.id.as_str().to_string());
+            self.thread_panel.close();
+            self.rlimit_panel.close();
+            self.smaps_panel.close();
+            self.security_panel
+                .open(pid, &name, read_security_flags(pid));
+        }
+        self.force_rerender = true;
+    }
+
+    /// Collects the threads of the process shown in the thread panel, if open.
+    pub fn update_thread_panel(&mut self) {
+        if let Some(pid) = self.thread_panel.pid {
+            self.thread_panel
+                .update(collect_threads(pid), std::time::Instant::now());
+        }
+    }
+
+    /// Sets the title, which summarizes processes in unusual states if there are
+    /// any. It also marks the entry with the highest value in each highlighted
+    /// column, and names them in the title so they can be found even when
+    /// scrolled out of view.
+    fn set_title(&mut self, data: &mut [ProcWidgetData], state_summary: Option<String>) {
+        let mut badges = Vec::from_iter(state_summary);
+
+        if let Some(process) = &self.watched_process {
+            if data.iter().all(|entry| entry.disabled) {
+                badges.push(format!("Waiting for process {process}..."));
+            }
+        }
+
+        if self.is_highlighting_top {
+            let columns = if self.highlight_top_columns.is_empty() {
+                self.table
+                    .columns
+                    .get(self.table.sort_index())
+                    .map(|column| vec![*column.inner()])
+                    .unwrap_or_default()
+            } else {
+                self.highlight_top_columns.clone()
+            };
+
+            for column in columns {
+                if let Some(index) = column.top_index(data) {
+                    let entry = &mut data[index];
+                    entry.is_top = true;
+                    badges.push(format!(
+                        "🔥 {} [top {}]",
+                        entry.id,
+                        column.text().trim_end_matches('%')
+                    ));
+                }
+            }
+        }
+
+        let mut name = match &self.diff_label {
+            Some(label) => format!("Processes ({label})"),
+            None => "Processes".to_string(),
+        };
+
+        if let Some(preset) = &self.preset_name {
+            name.push_str(&format!(" [{preset}]"));
+        }
+
+        if self.is_cpu_per_core {
+            name.push_str(" (normalized)");
+        }
+
+        if self.is_sorting_by_change() {
+            name.push_str(" (sorted by change)");
+        }
+
+        if let (Some(top_n), false) = (self.top_n, matches!(self.mode, ProcWidgetMode::Tree { .. }))
+        {
+            let shown = top_n.min(self.num_before_top);
+            name.push_str(&format!(" (top {shown} of {})", self.num_before_top));
+        }
+
+        self.table.props.title = Some(if badges.is_empty() {
+            format!(" {name} ").into()
+        } else {
+            format!(" {name} ── {} ", badges.join(" ")).into()
+        });
+    }
+
+    /// Counts how many entries share each of the namespaces of each entry, if
+    /// any namespace column is shown.
+    fn set_namespace_shares(&self, data: &mut [ProcWidgetData]) {
+        if !self
+            .table
+            .columns
+            .iter()
+            .any(|column| column.inner().is_namespace())
+        {
+            return;
+        }
+
+        let mut counts: [HashMap<u64, u64>; 4] = Default::default();
+        for entry in data.iter().filter(|entry| !entry.disabled) {
+            let ns = &entry.namespaces;
+            for (count, inode) in counts
+                .iter_mut()
+                .zip([ns.pid_ns, ns.net_ns, ns.mnt_ns, ns.user_ns])
+            {
+                *count.entry(inode).or_default() += 1;
+            }
+        }
+
+        for entry in data.iter_mut() {
+            let ns = &entry.namespaces;
+            let count = |index: usize, inode: u64| counts[index].get(&inode).copied().unwrap_or(0);
+            entry.namespace_shares = ProcessNamespaces {
+                pid_ns: count(0, ns.pid_ns),
+                net_ns: count(1, ns.net_ns),
+                mnt_ns: count(2, ns.mnt_ns),
+                user_ns: count(3, ns.user_ns),
+            };
+        }
+    }
+
+    /// Sets the sparklines of each entry if their columns are shown. Grouped
+    /// entries show the combined history of all processes in the group.
+    fn set_sparklines(&self, data: &mut [ProcWidgetData], process_data: &ProcessData) {
+        let has_column =
+            |column: ProcColumn| self.table.columns.iter().any(|c| *c.inner() == column);
+        let show_cpu = has_column(ProcColumn::CpuSparkline);
+        let show_mem = has_column(ProcColumn::MemSparkline);
+
+        if !show_cpu && !show_mem {
+            return;
+        }
+
+        for row in data {
+            let pids = match self.mode {
+                ProcWidgetMode::Grouped => self
+                    .id_pid_map
+                    .get(row.id.as_str())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                _ => std::slice::from_ref(&row.pid),
+            };
+
+            if show_cpu {
+                row.cpu_sparkline = convert_cpu_sparkline(
+                    pids.iter()
+                        .filter_map(|pid| process_data.cpu_history.get(pid)),
+                );
+            }
+            if show_mem {
+                row.mem_sparkline = convert_mem_sparkline(
+                    pids.iter()
+                        .filter_map(|pid| process_data.mem_history.get(pid)),
+                );
+            }
+        }
+    }
+
+    /// Compares the displayed data against a baseline set of processes,
+    /// setting the deltas of each entry. Processes that only exist in the
+    /// baseline are added as disabled entries, except in tree mode.
+    fn apply_baseline(
+        &self, data: &mut Vec<ProcWidgetData>, process_harvest: &BTreeMap<Pid, ProcessHarvest>,
+        baseline: &[ProcessHarvest],
+    ) {
+        let is_using_command = self.is_using_command();
+        let is_mem_percent = self.is_mem_percent();
+        let search_query = self.get_query();
+
+        let id = |process: &ProcessHarvest| {
+            if is_using_command {
+                process.command.clone()
+            } else {
+                process.name.clone()
+            }
+        };
+
+        // Current memory usage as a percentage, which may not be what is displayed.
+        let current_mem_percent = |row: &ProcWidgetData| match row.mem_usage {
+            MemUsage::Percent(percent) => percent,
+            MemUsage::Bytes(_) => {
+                if let ProcWidgetMode::Grouped = self.mode {
+                    self.id_pid_map
+                        .get(row.id.as_str())
+                        .map(|pids| {
+                            pids.iter()
+                                .filter_map(|pid| process_harvest.get(pid))
+                                .map(|p| p.mem_usage_percent)
+                                .sum()
+                        })
+                        .unwrap_or(0.0)
+                } else {
+                    process_harvest
+                        .get(&row.pid)
+                        .map(|p| p.mem_usage_percent)
+                        .unwrap_or(0.0)
+                }
+            }
+        };
+
+        let filtered_baseline = baseline.iter().filter(|process| {
+            search_query
+                .as_ref()
+                .map(|query| query.check(process, is_using_command))
+                .unwrap_or(true)
+                && self.thresholds.is_met(process)
+        });
 
-    fn get_query(&self) -> &Option<ProcessQuery> {
-        if self.proc_search.search_state.is_invalid_or_blank_search() {
-            &None
+        let mut gone = Vec::new();
+
+        if let ProcWidgetMode::Grouped = self.mode {
+            // Processes are matched by name (or command) when grouped.
+            let mut grouped_baseline: HashMap<String, (ProcessHarvest, u64)> = HashMap::default();
+            for process in filtered_baseline {
+                if let Some((grouped, count)) = grouped_baseline.get_mut(&id(process)) {
+                    grouped.add(process);
+                    *count += 1;
+                } else {
+                    grouped_baseline.insert(id(process), (process.clone(), 1));
+                }
+            }
+
+            for row in data.iter_mut() {
+                let mem_percent = current_mem_percent(row);
+                match grouped_baseline.remove(row.id.as_str()) {
+                    Some((base, _)) => row.set_delta(&base, mem_percent, self.diff_threshold),
+                    None => row.set_new(mem_percent),
+                }
+            }
+
+            for (base, count) in grouped_baseline.into_values() {
+                gone.push(
+                    ProcWidgetData::from_data(&base, is_using_command, is_mem_percent)
+                        .num_similar(count)
+                        .gone(base.mem_usage_percent),
+                );
+            }
         } else {
-            &self.proc_search.search_state.query
-        }
-    }
+            // Otherwise, processes are matched by PID, but only if the name is the same
+            // in case the PID was reused.
+            let mut pid_baseline: HashMap<Pid, &ProcessHarvest> = filtered_baseline
+                .map(|process| (process.pid, process))
+                .collect();
+
+            for row in data.iter_mut() {
+                let mem_percent = current_mem_percent(row);
+                let current_id = process_harvest.get(&row.pid).map(id);
+                match pid_baseline.remove(&row.pid) {
+                    Some(base) if current_id.as_ref() == Some(&id(base)) => {
+                        row.set_delta(base, mem_percent, self.diff_threshold);
+                    }
+                    _ => row.set_new(mem_percent),
+                }
+            }
 
-    /// Update the current table data.
-    ///
-    /// This function *only* updates the displayed process data. If there is a
-    /// need to update the actual *stored* data, call it before this
-    /// function.
-    pub fn set_table_data(&mut self, data_collection: &DataCollection) {
-        let data = match &self.mode {
-            ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
-                self.get_normal_data(&data_collection.process_data.process_harvest)
+            if let ProcWidgetMode::Normal = self.mode {
+                for base in pid_baseline.into_values() {
+                    if !process_harvest.contains_key(&base.pid) {
+                        gone.push(
+                            ProcWidgetData::from_data(base, is_using_command, is_mem_percent)
+                                .gone(base.mem_usage_percent),
+                        );
+                    }
+                }
             }
-            ProcWidgetMode::Tree { collapsed_pids } => {
-                self.get_tree_data(collapsed_pids, data_collection)
+        }
+
+        if !gone.is_empty() {
+            data.extend(gone);
+
+            if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+                sort_skip_pid_asc(column.inner(), data, self.table.order());
             }
-        };
-        self.table.set_data(data);
+        }
     }
 
     fn get_tree_data(
@@ -436,6 +1561,7 @@ impl ProcWidgetState {
                     .as_ref()
                     .map(|q| q.check(process, is_using_command))
                     .unwrap_or(true)
+                    && self.thresholds.is_met(process)
                 {
                     Some(*pid)
                 } else {
@@ -666,11 +1792,13 @@ impl ProcWidgetState {
         let is_using_command = self.is_using_command();
         let is_mem_percent = self.is_mem_percent();
 
+        let thresholds = self.thresholds;
         let filtered_iter = process_harvest.values().filter(|process| {
             search_query
                 .as_ref()
                 .map(|query| query.check(process, is_using_command))
                 .unwrap_or(true)
+                && thresholds.is_met(process)
         });
 
         let mut id_pid_map: HashMap<String, Vec<Pid>> = HashMap::default();
@@ -724,6 +1852,21 @@ impl ProcWidgetState {
 
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
             sort_skip_pid_asc(column.inner(), &mut filtered_data, self.table.order());
+
+            let is_grouped = matches!(self.mode, ProcWidgetMode::Grouped);
+            self.change_sort
+                .sort(*column.inner(), &mut filtered_data, |entry| {
+                    if is_grouped {
+                        EntryKey::Group(entry.id.as_str().to_string())
+                    } else {
+                        EntryKey::Pid(entry.pid)
+                    }
+                });
+        }
+
+        self.num_before_top = filtered_data.len();
+        if let Some(top_n) = self.top_n {
+            filtered_data.truncate(top_n);
         }
 
         filtered_data
@@ -734,6 +1877,25 @@ impl ProcWidgetState {
         self.table.columns.get_mut(index).map(|col| col.inner_mut())
     }
 
+    /// Switches between showing CPU usage as a percentage of one core and of
+    /// all of them.
+    pub fn toggle_cpu_per_core(&mut self) {
+        self.is_cpu_per_core = !self.is_cpu_per_core;
+        self.force_data_update();
+    }
+
+    /// What the collected CPU usage is multiplied by to show it. Scaling every
+    /// entry by the same amount keeps them in the same order, so sorting by
+    /// CPU usage doesn't change either way.
+    fn cpu_factor(&self, num_cpus: Option<usize>) -> f32 {
+        let num_cpus = num_cpus.unwrap_or(1).max(1) as f32;
+        match (self.is_cpu_collected_per_core, self.is_cpu_per_core) {
+            (false, true) => num_cpus,
+            (true, false) => 1.0 / num_cpus,
+            _ => 1.0,
+        }
+    }
+
     pub fn toggle_mem_percentage(&mut self) {
         if let Some(index) = self.column_mapping.get_index_of(&ProcWidgetColumn::Mem) {
             if let Some(mem) = self.get_mut_proc_col(index) {
@@ -783,6 +1945,25 @@ impl ProcWidgetState {
         self.force_update_data = true;
     }
 
+    /// The columns that need their data collected. This includes the
+    /// container column while it's hidden until a container is seen.
+    pub fn visible_columns(&self) -> VisibleColumns {
+        let visible = self
+            .table
+            .columns
+            .iter()
+            .zip(&self.column_mapping)
+            .filter(|(column, _)| !column.is_hidden)
+            .map(|(_, column)| VisibleColumns::from_column(column))
+            .collect::<VisibleColumns>();
+
+        if self.is_awaiting_containers {
+            visible | VisibleColumns::CONTAINERS
+        } else {
+            visible
+        }
+    }
+
     /// Marks the selected column as hidden, and automatically resets the
     /// selected column to the default sort index and order.
     fn hide_column(&mut self, column: ProcWidgetColumn) {
@@ -1006,6 +2187,10 @@ impl ProcWidgetState {
                     .iter()
                     .map(|c| c.header())
                     .collect::<Vec<_>>()
+            && self.table.sort_index() == other.table.sort_index()
+            && self.table.order() == other.table.order()
+            && self.change_tracker.is_some() == other.change_tracker.is_some()
+            && self.is_cpu_per_core == other.is_cpu_per_core
     }
 }
 
@@ -1022,10 +2207,12 @@ fn sort_skip_pid_asc(column: &ProcColumn, data: &mut [ProcWidgetData], order: So
 
 #[cfg(test)]
 mod test {
-    use std::time::Duration;
+    use std::{num::NonZeroU16, time::Duration};
 
     use super::*;
-    use crate::widgets::MemUsage;
+    use crate::{
+        app::diff::DiffChange, canvas::components::data_table::DataToCell, widgets::MemUsage,
+    };
 
     #[test]
     fn test_proc_sort() {
@@ -1048,8 +2235,34 @@ mod test {
             num_similar: 0,
             disabled: false,
             time: Duration::from_secs(0),
+            cpu_delta: 0.0,
+            mem_delta: 0.0,
+            cpu_baseline: 0.0,
+            mem_baseline: 0.0,
+            cpu_change: DiffChange::Unchanged,
+            mem_change: DiffChange::Unchanged,
+            is_new: false,
+            is_gone: false,
+            minflt_per_sec: 0.0,
+            majflt_per_sec: 0.0,
+            is_high_major_faults: false,
+            tcp_connections: None,
+            is_high_tcp_connections: false,
+            inotify_watches: None,
+            is_high_inotify_watches: false,
+            is_changed: false,
+            wchan: None,
+            is_wchan_stuck: false,
+            namespaces: Default::default(),
+            namespace_shares: Default::default(),
+            cgroup_cpu: None,
+            container: None,
+            pss: None,
+            is_top: false,
+            cpu_sparkline: String::new(),
+            mem_sparkline: String::new(),
             #[cfg(feature = "gpu")]
-            gpu_mem_usage: MemUsage::Percent(1.1),
+            gpu_mem_usage: Some(MemUsage::Percent(1.1)),
             #[cfg(feature = "gpu")]
             gpu_usage: 0,
         };
@@ -1146,6 +2359,203 @@ mod test {
         init_state(ProcTableConfig::default(), columns)
     }
 
+    #[test]
+    fn cpu_per_core() {
+        let mut state = init_default_state(&[ProcWidgetColumn::Cpu]);
+        assert_eq!(state.cpu_factor(Some(16)), 1.0);
+
+        state.toggle_cpu_per_core();
+        assert_eq!(state.cpu_factor(Some(16)), 16.0);
+        assert_eq!(state.cpu_factor(None), 1.0);
+        state.set_title(&mut [], None);
+        assert_eq!(
+            state.table.props.title.as_deref(),
+            Some(" Processes (normalized) ")
+        );
+
+        // Collected per core with --unnormalized_cpu, which is labelled from
+        // the start.
+        state.is_cpu_collected_per_core = true;
+        state.set_title(&mut [], None);
+        assert_eq!(
+            state.table.props.title.as_deref(),
+            Some(" Processes (normalized) ")
+        );
+
+        state.toggle_cpu_per_core();
+        assert_eq!(state.cpu_factor(Some(4)), 0.25);
+        state.set_title(&mut [], None);
+        assert_eq!(state.table.props.title.as_deref(), Some(" Processes "));
+    }
+
+    #[test]
+    fn footer() {
+        let mut state = init_default_state(&[ProcWidgetColumn::PidOrCount]);
+        assert_eq!(state.footer(0, Some(8192)), None);
+
+        state.num_below_thresholds = 3;
+        assert_eq!(state.footer(0, Some(8192)), Some(" 3 hidden ".into()));
+
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::InotifyWatches,
+        ]);
+        assert_eq!(state.footer(0, None), None);
+        assert_eq!(
+            state.footer(0, Some(8192)),
+            Some(" inotify limit: 8192 ".into())
+        );
+
+        state.num_below_thresholds = 3;
+        assert_eq!(
+            state.footer(2, Some(8192)),
+            Some(" 3 hidden ─ inotify limit: 8192 ─ ⚠ 2 over half the inotify limit ".into())
+        );
+    }
+
+    #[test]
+    fn initial_sort() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+        ];
+
+        let state = init_default_state(&init_columns);
+        assert_eq!(state.table.sort_index(), 2);
+        assert_eq!(state.table.order(), SortOrder::Descending);
+
+        let state = init_state(
+            ProcTableConfig {
+                sort_by: Some(ProcWidgetColumn::ProcNameOrCommand),
+                ..Default::default()
+            },
+            &init_columns,
+        );
+        assert_eq!(state.table.sort_index(), 1);
+        assert_eq!(state.table.order(), SortOrder::Ascending);
+
+        let state = init_state(
+            ProcTableConfig {
+                sort_by: Some(ProcWidgetColumn::Mem),
+                sort_order: Some(SortOrder::Ascending),
+                ..Default::default()
+            },
+            &init_columns,
+        );
+        assert_eq!(state.table.sort_index(), 3);
+        assert_eq!(state.table.order(), SortOrder::Ascending);
+
+        let state = init_state(
+            ProcTableConfig {
+                sort_order: Some(SortOrder::Ascending),
+                ..Default::default()
+            },
+            &init_columns,
+        );
+        assert_eq!(state.table.sort_index(), 2);
+        assert_eq!(state.table.order(), SortOrder::Ascending);
+    }
+
+    #[test]
+    fn default_filter() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+        ];
+
+        let state = init_default_state(&init_columns);
+        assert!(!state.is_search_enabled());
+        assert!(!state.proc_search.is_searching_with_regex);
+
+        let state = init_state(
+            ProcTableConfig {
+                default_filter: Some("nginx|postgres".to_string()),
+                ..Default::default()
+            },
+            &init_columns,
+        );
+        assert!(state.is_search_enabled());
+        assert!(state.proc_search.is_searching_with_regex);
+        assert_eq!(state.cursor_char_index(), "nginx|postgres".len());
+        assert!(state.proc_search.search_state.query.is_some());
+        assert!(!state.proc_search.search_state.is_invalid_search);
+
+        // Regex searching being on by default doesn't turn it off.
+        let state = init_state(
+            ProcTableConfig {
+                is_use_regex: true,
+                default_filter: Some("nginx".to_string()),
+                ..Default::default()
+            },
+            &init_columns,
+        );
+        assert!(state.proc_search.is_searching_with_regex);
+    }
+
+    #[test]
+    fn watched_process() {
+        assert_eq!(watched_process_filter("1234"), "pid = ^1234$");
+        assert_eq!(watched_process_filter("Web Content"), r#""^Web\sContent$""#);
+        assert_eq!(watched_process_filter("a.out"), r#""^a\.out$""#);
+
+        for process in ["1234", "Web Content", "a.out"] {
+            let state = init_state(
+                ProcTableConfig {
+                    default_filter: Some(watched_process_filter(process)),
+                    watched_process: Some(process.to_string()),
+                    ..Default::default()
+                },
+                &[
+                    ProcWidgetColumn::PidOrCount,
+                    ProcWidgetColumn::ProcNameOrCommand,
+                ],
+            );
+            assert!(!state.proc_search.search_state.is_invalid_search);
+        }
+    }
+
+    #[test]
+    fn filtered_by_user() {
+        assert_eq!(user_filter("root"), "user = ^root$");
+        assert_eq!(user_filter("john.doe"), r"user = ^john\.doe$");
+
+        for filter in [
+            user_filter("root"),
+            format!("{} and (nginx|postgres)", user_filter("www-data")),
+        ] {
+            let state = init_state(
+                ProcTableConfig {
+                    default_filter: Some(filter),
+                    ..Default::default()
+                },
+                &[ProcWidgetColumn::ProcNameOrCommand, ProcWidgetColumn::User],
+            );
+            assert!(!state.proc_search.search_state.is_invalid_search);
+        }
+    }
+
+    #[test]
+    fn sort_by_names() {
+        assert_eq!(
+            ProcWidgetColumn::from_sort_by("MEM"),
+            Ok(ProcWidgetColumn::Mem)
+        );
+        assert_eq!(
+            ProcWidgetColumn::from_sort_by("runtime"),
+            Ok(ProcWidgetColumn::Time)
+        );
+        assert_eq!(
+            ProcWidgetColumn::from_sort_by("nice"),
+            Err(
+                "'nice' is not a valid column to sort by, it must be one of: pid, name, cpu, mem, \
+                 io_read, io_write, user, state, runtime"
+                    .to_string()
+            )
+        );
+    }
+
     #[test]
     fn custom_columns() {
         let init_columns = vec![
@@ -1164,6 +2574,31 @@ mod test {
         assert_eq!(get_columns(&state.table), columns);
     }
 
+    #[test]
+    fn visible_columns() {
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::WChan,
+            ProcWidgetColumn::MajorFaults,
+        ]);
+        assert_eq!(
+            state.visible_columns(),
+            VisibleColumns::WCHAN | VisibleColumns::PAGE_FAULTS
+        );
+
+        state.hide_column(ProcWidgetColumn::WChan);
+        assert_eq!(state.visible_columns(), VisibleColumns::PAGE_FAULTS);
+
+        // Without set columns, containers are still looked for so that the
+        // hidden container column can be shown once one is found.
+        let state = init_default_state(&[]);
+        if cfg!(target_os = "linux") {
+            assert_eq!(state.visible_columns(), VisibleColumns::CONTAINERS);
+        } else {
+            assert_eq!(state.visible_columns(), VisibleColumns::empty());
+        }
+    }
+
     #[test]
     fn toggle_count_pid() {
         let init_columns = [
@@ -1494,4 +2929,356 @@ mod test {
         state.toggle_command();
         assert_eq!(get_columns(&state.table), original_columns);
     }
+
+    #[test]
+    fn diff_columns() {
+        let init_columns = vec![
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+        ];
+        let columns = vec![
+            ProcColumn::Pid,
+            ProcColumn::Name,
+            ProcColumn::CpuPercent,
+            ProcColumn::CpuDelta,
+            ProcColumn::MemPercent,
+            ProcColumn::MemDelta,
+        ];
+
+        let table_config = ProcTableConfig {
+            show_diff: true,
+            ..Default::default()
+        };
+        let state = init_state(table_config, &init_columns);
+        assert_eq!(get_columns(&state.table), columns);
+    }
+
+    #[test]
+    fn diff_baseline() {
+        fn process(pid: Pid, name: &str, cpu: f32, mem: f32) -> ProcessHarvest {
+            ProcessHarvest {
+                pid,
+                name: name.to_string(),
+                cpu_usage_percent: cpu,
+                mem_usage_percent: mem,
+                ..Default::default()
+            }
+        }
+
+        let init_columns = vec![
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+        ];
+        let table_config = ProcTableConfig {
+            show_diff: true,
+            diff_threshold: 1.0,
+            ..Default::default()
+        };
+        let mut state = init_state(table_config, &init_columns);
+
+        let current: BTreeMap<Pid, ProcessHarvest> = [
+            process(1, "a", 5.0, 2.0),
+            process(2, "b", 1.0, 1.0),
+            process(3, "c", 1.0, 1.0),
+        ]
+        .into_iter()
+        .map(|p| (p.pid, p))
+        .collect();
+        let baseline = vec![
+            process(1, "a", 2.0, 1.5),
+            process(3, "reused", 1.0, 1.0),
+            process(4, "d", 3.0, 4.0),
+        ];
+
+        let mut data = state.get_normal_data(&current);
+        state.apply_baseline(&mut data, &current, &baseline);
+
+        let row = |pid: Pid, disabled: bool| {
+            data.iter()
+                .find(|row| row.pid == pid && row.disabled == disabled)
+                .unwrap()
+        };
+
+        assert_eq!(data.len(), 4);
+
+        assert!(!row(1, false).is_new);
+        assert_eq!(row(1, false).cpu_delta, 3.0);
+        assert_eq!(row(1, false).mem_delta, 0.5);
+
+        assert!(row(2, false).is_new);
+        assert_eq!(row(2, false).cpu_delta, 1.0);
+
+        // A reused PID is treated as a new process.
+        assert!(row(3, false).is_new);
+
+        assert_eq!(row(4, true).cpu_delta, -3.0);
+        assert_eq!(row(4, true).mem_delta, -4.0);
+
+        // Only changes bigger than the threshold are significant.
+        assert_eq!(row(1, false).cpu_change, DiffChange::Regressed);
+        assert_eq!(row(1, false).mem_change, DiffChange::Unchanged);
+
+        let cell = |pid: Pid, disabled: bool, column: ProcColumn| {
+            row(pid, disabled)
+                .to_cell(&column, NonZeroU16::new(20).unwrap())
+                .unwrap()
+        };
+        assert_eq!(cell(1, false, ProcColumn::CpuDelta), "2.0% → 5.0%");
+        assert_eq!(cell(1, false, ProcColumn::MemDelta), "1.5% → 2.0%");
+        assert_eq!(cell(2, false, ProcColumn::CpuDelta), "(new)");
+        assert_eq!(cell(4, true, ProcColumn::MemDelta), "(gone)");
+    }
+
+    #[test]
+    fn top_n() {
+        let process = |pid: Pid, cpu: f32| ProcessHarvest {
+            pid,
+            name: format!("p{pid}"),
+            cpu_usage_percent: cpu,
+            ..Default::default()
+        };
+        let harvest: BTreeMap<Pid, ProcessHarvest> = [
+            process(1, 1.0),
+            process(2, 5.0),
+            process(3, 3.0),
+            process(4, 0.0),
+        ]
+        .into_iter()
+        .map(|p| (p.pid, p))
+        .collect();
+
+        let mut state = init_state(
+            ProcTableConfig {
+                sort_by: Some(ProcWidgetColumn::Cpu),
+                sort_order: Some(SortOrder::Descending),
+                ..Default::default()
+            },
+            &[
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::ProcNameOrCommand,
+                ProcWidgetColumn::Cpu,
+            ],
+        );
+        state.set_top_n(Some(2));
+
+        let mut data = state.get_normal_data(&harvest);
+        assert_eq!(data.iter().map(|row| row.pid).collect::<Vec<_>>(), [2, 3]);
+
+        state.set_title(&mut data, None);
+        assert_eq!(
+            state.table.props.title.as_deref(),
+            Some(" Processes (top 2 of 4) ")
+        );
+
+        state.set_top_n(None);
+        assert_eq!(state.get_normal_data(&harvest).len(), 4);
+    }
+
+    #[test]
+    fn set_columns() {
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+        ]);
+        state.toggle_mem_percentage();
+        assert_eq!(state.table.sort_index(), 2);
+
+        let preset = ColumnPreset::new(
+            "io",
+            vec![
+                ProcColumn::Pid,
+                ProcColumn::MemPercent,
+                ProcColumn::ReadPerSecond,
+            ],
+        );
+        state.set_columns(&preset.widget_columns(), Some(preset.name.clone()));
+        assert_eq!(
+            get_columns(&state.table),
+            [
+                ProcColumn::Pid,
+                ProcColumn::MemValue,
+                ProcColumn::ReadPerSecond
+            ]
+        );
+        assert_eq!(
+            state
+                .column_mapping
+                .get_index_of(&ProcWidgetColumn::ReadPerSecond),
+            Some(2)
+        );
+        assert_eq!(state.table.sort_index(), 0);
+
+        state.set_title(&mut [], None);
+        assert_eq!(state.table.props.title.as_deref(), Some(" Processes [io] "));
+
+        state.sort_table.set_position(1);
+        assert!(state.move_sort_table_column(true));
+        assert_eq!(state.preset_name(), Some(CUSTOM_PRESET_NAME));
+        assert_eq!(
+            state.shown_columns(),
+            [
+                ProcColumn::Pid,
+                ProcColumn::ReadPerSecond,
+                ProcColumn::MemValue
+            ]
+        );
+    }
+
+    #[test]
+    fn top_consumer_index() {
+        let process = |cpu: f32, rps: u64| ProcWidgetData {
+            cpu_usage_percent: cpu,
+            rps,
+            ..ProcWidgetData::from_data(&ProcessHarvest::default(), false, true)
+        };
+
+        let mut data = vec![process(5.0, 10), process(20.0, 0), process(1.0, 30)];
+        assert_eq!(ProcColumn::CpuPercent.top_index(&data), Some(1));
+        assert_eq!(ProcColumn::ReadPerSecond.top_index(&data), Some(2));
+
+        // Disabled entries are never the top consumer.
+        data[1].disabled = true;
+        assert_eq!(ProcColumn::CpuPercent.top_index(&data), Some(0));
+
+        // Columns without a meaningful "top" value have no top consumer.
+        assert_eq!(ProcColumn::Name.top_index(&data), None);
+        assert_eq!(ProcColumn::CpuPercent.top_index(&[]), None);
+    }
+
+    #[test]
+    fn namespace_shares() {
+        let process = |pid: Pid, net_ns: u64| ProcessHarvest {
+            pid,
+            namespaces: ProcessNamespaces {
+                net_ns,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let state = init_state(
+            ProcTableConfig::default(),
+            &[ProcWidgetColumn::PidOrCount, ProcWidgetColumn::NetNamespace],
+        );
+        let mut data = [
+            process(1, 4026532001),
+            process(2, 4026532001),
+            process(3, 0),
+            process(4, 4026531840),
+        ]
+        .iter()
+        .map(|p| ProcWidgetData::from_data(p, false, true))
+        .collect::<Vec<_>>();
+        state.set_namespace_shares(&mut data);
+
+        let cells = data
+            .iter()
+            .map(|row| {
+                row.to_cell(&ProcColumn::NetNamespace, NonZeroU16::new(20).unwrap())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cells,
+            vec![
+                "net:4026532001 (2)",
+                "net:4026532001 (2)",
+                "-",
+                "net:4026531840"
+            ]
+        );
+    }
+
+    #[test]
+    fn move_columns() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Mem,
+            ProcWidgetColumn::ReadPerSecond,
+            ProcWidgetColumn::User,
+            ProcWidgetColumn::State,
+        ];
+        let mut state = init_default_state(&init_columns);
+        assert_eq!(state.table.sort_index(), 2);
+
+        // The anchored columns can't move, and others can't move past them.
+        state.sort_table.set_position(1);
+        assert!(!state.move_sort_table_column(true));
+        state.sort_table.set_position(2);
+        assert!(!state.move_sort_table_column(false));
+
+        // The sort follows the CPU column as it moves.
+        assert!(state.move_sort_table_column(true));
+        assert_eq!(
+            state.column_order()[2..5],
+            [
+                ProcColumn::MemPercent,
+                ProcColumn::CpuPercent,
+                ProcColumn::ReadPerSecond
+            ]
+        );
+        assert_eq!(state.table.sort_index(), 3);
+        assert_eq!(state.table.order(), SortOrder::Descending);
+        assert_eq!(state.sort_table.current_index(), 3);
+
+        // Hidden columns are skipped over but keep their place.
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::User,
+            ProcWidgetColumn::ReadPerSecond,
+            ProcWidgetColumn::State,
+        ]);
+        state.toggle_tab();
+        state.sort_table.set_position(2);
+        assert!(state.move_sort_table_column(true));
+        assert!(!state.move_sort_table_column(true));
+        assert_eq!(
+            state.column_order(),
+            [
+                ProcColumn::Count,
+                ProcColumn::Name,
+                ProcColumn::ReadPerSecond,
+                ProcColumn::User,
+                ProcColumn::CpuPercent,
+                ProcColumn::State,
+            ]
+        );
+
+        // A saved order is restored, leaving other columns in place.
+        let state = init_state(
+            ProcTableConfig {
+                column_order: vec![
+                    ProcColumn::Name,
+                    ProcColumn::ReadPerSecond,
+                    ProcColumn::CpuPercent,
+                    ProcColumn::Pid,
+                ],
+                ..Default::default()
+            },
+            &init_columns,
+        );
+        assert_eq!(
+            state.column_order(),
+            [
+                ProcColumn::Pid,
+                ProcColumn::Name,
+                ProcColumn::ReadPerSecond,
+                ProcColumn::MemPercent,
+                ProcColumn::CpuPercent,
+                ProcColumn::User,
+                ProcColumn::State,
+            ]
+        );
+        assert_eq!(state.table.sort_index(), 4);
+    }
 }
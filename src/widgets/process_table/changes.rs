@@ -0,0 +1,287 @@
+//! Tracking how much each process' usage changed between updates.
+
+use std::{cmp::Reverse, collections::BTreeMap, time::Instant};
+
+use hashbrown::HashMap;
+
+use super::{ProcColumn, ProcWidgetData};
+use crate::data_collection::processes::{Pid, ProcessHarvest};
+
+/// The default number of updates that sorting by change lasts for.
+pub const DEFAULT_CHANGE_SORT_TICKS: u64 = 5;
+
+/// How many percentage points the CPU or memory usage of a process has to
+/// change by between updates for it to be highlighted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChangeThresholds {
+    pub cpu: f32,
+    pub mem: f32,
+}
+
+/// Remembers the CPU and memory usage of each process in the previous update,
+/// so rows whose usage changed a lot can be highlighted.
+#[derive(Debug)]
+pub struct ChangeTracker {
+    thresholds: ChangeThresholds,
+    /// When the data in `current` was collected.
+    instant: Option<Instant>,
+    previous: HashMap<Pid, (f32, f32)>,
+    current: HashMap<Pid, (f32, f32)>,
+    /// Whether highlighting is paused, such as while frozen.
+    pub is_paused: bool,
+}
+
+impl ChangeTracker {
+    pub fn new(thresholds: ChangeThresholds) -> Self {
+        Self {
+            thresholds,
+            instant: None,
+            previous: HashMap::default(),
+            current: HashMap::default(),
+            is_paused: false,
+        }
+    }
+
+    /// Records the usage of each process if the data is from a new update,
+    /// returning whether it is. Changes are only highlighted for the first
+    /// redraw after an update, so they stand out as a brief flash.
+    pub fn update(&mut self, instant: Instant, harvest: &BTreeMap<Pid, ProcessHarvest>) -> bool {
+        if self.instant == Some(instant) {
+            return false;
+        }
+
+        self.instant = Some(instant);
+        self.previous = harvest
+            .values()
+            .map(|process| {
+                (
+                    process.pid,
+                    (process.cpu_usage_percent, process.mem_usage_percent),
+                )
+            })
+            .collect();
+        std::mem::swap(&mut self.previous, &mut self.current);
+
+        !self.is_paused
+    }
+
+    /// Whether the usage of the process changed by more than the thresholds
+    /// since the previous update. New processes aren't counted as changed.
+    pub fn is_changed(&self, pid: Pid) -> bool {
+        match (self.previous.get(&pid), self.current.get(&pid)) {
+            (Some((prev_cpu, prev_mem)), Some((cpu, mem))) => {
+                (cpu - prev_cpu).abs() > self.thresholds.cpu
+                    || (mem - prev_mem).abs() > self.thresholds.mem
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Identifies an entry of the table across updates.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EntryKey {
+    Pid(Pid),
+    /// The name or command of a group, in grouped mode.
+    Group(String),
+}
+
+/// The values of a column for each entry in one update.
+#[derive(Debug)]
+struct ColumnValues {
+    column: ProcColumn,
+    values: HashMap<EntryKey, f64>,
+}
+
+/// Sorts entries by how much the sorted column changed since the previous
+/// update, for a few updates after being turned on.
+#[derive(Debug)]
+pub struct ChangeSort {
+    /// How many updates sorting by change lasts for.
+    ticks: u64,
+    /// How many more updates sorting by change lasts for, or 0 if it's off.
+    remaining_ticks: u64,
+    /// When the data in `current` was collected.
+    instant: Option<Instant>,
+    previous: Option<ColumnValues>,
+    current: Option<ColumnValues>,
+}
+
+impl ChangeSort {
+    pub fn new(ticks: u64) -> Self {
+        Self {
+            ticks,
+            remaining_ticks: 0,
+            instant: None,
+            previous: None,
+            current: None,
+        }
+    }
+
+    /// Whether entries are being sorted by change.
+    pub fn is_active(&self) -> bool {
+        self.remaining_ticks > 0
+    }
+
+    /// Turns sorting by change on for the next few updates, or off if it's
+    /// already on.
+    pub fn toggle(&mut self) {
+        self.remaining_ticks = if self.is_active() { 0 } else { self.ticks };
+    }
+
+    /// Moves the recorded values back by one if the data is from a new
+    /// update, counting down how long sorting by change has left.
+    pub fn update(&mut self, instant: Instant) {
+        if self.instant == Some(instant) {
+            return;
+        }
+
+        if self.instant.is_some() {
+            self.remaining_ticks = self.remaining_ticks.saturating_sub(1);
+        }
+        self.instant = Some(instant);
+        self.previous = self.current.take();
+    }
+
+    /// Records the value of the sorted column for each entry, and if sorting
+    /// by change, sorts the entries by how much that changed since the
+    /// previous update, largest first. New entries come before everything
+    /// else, and entries that changed by the same amount keep their order.
+    ///
+    /// Columns without a number, like names, are compared by CPU usage.
+    pub fn sort(
+        &mut self, column: ProcColumn, data: &mut [ProcWidgetData],
+        key: impl Fn(&ProcWidgetData) -> EntryKey,
+    ) {
+        let column = if data.iter().any(|entry| column.value(entry).is_some()) {
+            column
+        } else {
+            ProcColumn::CpuPercent
+        };
+
+        let values = data
+            .iter()
+            .filter_map(|entry| Some((key(entry), column.value(entry)?)))
+            .collect();
+        let current = ColumnValues { column, values };
+
+        if self.is_active() {
+            if let Some(previous) = self.previous.as_ref().filter(|prev| prev.column == column) {
+                data.sort_by_cached_key(|entry| {
+                    let entry_key = key(entry);
+                    let change = match (
+                        current.values.get(&entry_key),
+                        previous.values.get(&entry_key),
+                    ) {
+                        (Some(value), Some(prev)) => (value - prev).abs(),
+                        (Some(_), None) => f64::INFINITY,
+                        (None, _) => 0.0,
+                    };
+
+                    // Floats that aren't negative order the same as their bits.
+                    Reverse(change.to_bits())
+                });
+            }
+        }
+
+        self.current = Some(current);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn harvest(usage: &[(Pid, f32, f32)]) -> BTreeMap<Pid, ProcessHarvest> {
+        usage
+            .iter()
+            .map(|&(pid, cpu, mem)| {
+                (
+                    pid,
+                    ProcessHarvest {
+                        pid,
+                        cpu_usage_percent: cpu,
+                        mem_usage_percent: mem,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn changes() {
+        let mut tracker = ChangeTracker::new(ChangeThresholds { cpu: 5.0, mem: 1.0 });
+        let start = Instant::now();
+
+        assert!(tracker.update(start, &harvest(&[(1, 10.0, 2.0), (2, 10.0, 2.0)])));
+        assert!(!tracker.is_changed(1));
+
+        let next = start + Duration::from_secs(1);
+        let data = harvest(&[(1, 16.0, 2.0), (2, 12.0, 3.5), (3, 50.0, 0.0)]);
+        assert!(tracker.update(next, &data));
+        assert!(tracker.is_changed(1));
+        assert!(tracker.is_changed(2));
+        assert!(!tracker.is_changed(3));
+
+        // Redrawing the same update doesn't flash again.
+        assert!(!tracker.update(next, &data));
+
+        tracker.is_paused = true;
+        let next = next + Duration::from_secs(1);
+        assert!(!tracker.update(next, &harvest(&[(1, 80.0, 2.0)])));
+    }
+
+    fn entries(usage: &[(Pid, f32)]) -> Vec<ProcWidgetData> {
+        harvest(
+            &usage
+                .iter()
+                .map(|&(pid, cpu)| (pid, cpu, 0.0))
+                .collect::<Vec<_>>(),
+        )
+        .values()
+        .map(|process| ProcWidgetData::from_data(process, false, true))
+        .collect()
+    }
+
+    fn pids(data: &[ProcWidgetData]) -> Vec<Pid> {
+        data.iter().map(|entry| entry.pid).collect()
+    }
+
+    #[test]
+    fn sort_by_change() {
+        let key = |entry: &ProcWidgetData| EntryKey::Pid(entry.pid);
+        let mut change_sort = ChangeSort::new(3);
+        let start = Instant::now();
+
+        change_sort.update(start);
+        let mut data = entries(&[(1, 10.0), (2, 20.0), (3, 30.0)]);
+        change_sort.sort(ProcColumn::CpuPercent, &mut data, key);
+        assert_eq!(pids(&data), vec![1, 2, 3]);
+
+        change_sort.toggle();
+        assert!(change_sort.is_active());
+
+        let next = start + Duration::from_secs(1);
+        change_sort.update(next);
+        let mut data = entries(&[(1, 10.0), (2, 25.0), (3, 15.0), (4, 0.0)]);
+        change_sort.sort(ProcColumn::CpuPercent, &mut data, key);
+        assert_eq!(pids(&data), vec![4, 3, 2, 1]);
+
+        // Redrawing the same update doesn't count down.
+        change_sort.update(next);
+        assert!(change_sort.is_active());
+
+        // Names aren't numbers, so they're compared by CPU usage.
+        let next = next + Duration::from_secs(1);
+        change_sort.update(next);
+        let mut data = entries(&[(1, 50.0), (2, 25.0), (3, 14.0), (4, 0.0)]);
+        change_sort.sort(ProcColumn::Name, &mut data, key);
+        assert_eq!(pids(&data), vec![1, 3, 2, 4]);
+
+        change_sort.update(next + Duration::from_secs(1));
+        assert!(!change_sort.is_active());
+    }
+}
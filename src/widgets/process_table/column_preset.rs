@@ -0,0 +1,193 @@
+//! Named sets of process columns that can be switched between at runtime.
+
+use indexmap::IndexSet;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::{ProcColumn, ProcWidgetColumn, ALL_PROC_COLUMNS, SORT_BY_COLUMNS};
+use crate::canvas::components::data_table::ColumnHeader;
+
+/// What the columns are called once they've been changed at runtime, so they
+/// no longer match a preset.
+pub const CUSTOM_PRESET_NAME: &str = "custom";
+
+/// A named set of process columns.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnPreset {
+    pub name: String,
+    pub columns: Vec<ProcColumn>,
+}
+
+impl ColumnPreset {
+    pub fn new(name: impl Into<String>, columns: Vec<ProcColumn>) -> Self {
+        Self {
+            name: name.into(),
+            columns,
+        }
+    }
+
+    /// The preset's columns as the widget's kinds of columns.
+    pub fn widget_columns(&self) -> IndexSet<ProcWidgetColumn> {
+        widget_columns(&self.columns)
+    }
+}
+
+/// Maps columns to the widget's kinds of columns, dropping duplicates and,
+/// outside of Linux, the Linux-only columns.
+pub fn widget_columns(columns: &[ProcColumn]) -> IndexSet<ProcWidgetColumn> {
+    columns
+        .iter()
+        .filter(|column| cfg!(target_os = "linux") || !column.is_linux_only())
+        .map(ProcWidgetColumn::from)
+        .collect()
+}
+
+/// Parses a comma-separated list of columns, as given to `--columns`. Columns
+/// are named like in the config file or like with `--sort_by`, or the list can
+/// be `all` for every column or `default` for the default columns, which is
+/// [`None`].
+pub fn parse_column_list(list: &str) -> Result<Option<IndexSet<ProcWidgetColumn>>, String> {
+    Ok(parse_proc_columns(list)?.map(|columns| widget_columns(&columns)))
+}
+
+/// Parses a list of columns like [`parse_column_list`], but keeps them as the
+/// columns that are shown, in the order they're given.
+pub fn parse_proc_columns(list: &str) -> Result<Option<Vec<ProcColumn>>, String> {
+    let columns = match list.trim().to_ascii_lowercase().as_str() {
+        "all" => ALL_PROC_COLUMNS.to_vec(),
+        "default" => return Ok(None),
+        _ => {
+            let mut columns = vec![];
+            for name in list.split(',').map(str::trim) {
+                if let Some(column) = ProcColumn::from_name(name) {
+                    columns.push(column);
+                } else if let Ok(column) = ProcWidgetColumn::from_sort_by(name) {
+                    // The widget's columns are mapped back to the ones shown by
+                    // default, so the rest of the list can be handled the same.
+                    columns.extend(
+                        ALL_PROC_COLUMNS
+                            .iter()
+                            .find(|c| ProcWidgetColumn::from(*c) == column),
+                    );
+                } else {
+                    return Err(format!(
+                        "'{name}' is not a process column, it must be one of: {}, or 'all' or 'default'",
+                        column_names().join(", ")
+                    ));
+                }
+            }
+            columns
+        }
+    };
+
+    Ok(Some(columns))
+}
+
+/// The names of every column that can be given to [`parse_column_list`].
+fn column_names() -> Vec<String> {
+    ALL_PROC_COLUMNS
+        .iter()
+        .map(|column| column.text().to_lowercase())
+        .chain(SORT_BY_COLUMNS.iter().map(|(name, _)| name.to_string()))
+        .unique()
+        .collect()
+}
+
+/// The presets from the config file, followed by the ones saved at runtime.
+/// Saved presets with the same name as one in the config file are dropped.
+pub fn merge_presets(config: Vec<ColumnPreset>, saved: Vec<ColumnPreset>) -> Vec<ColumnPreset> {
+    let mut presets = config;
+    for preset in saved {
+        if presets.iter().all(|p| p.name != preset.name) {
+            presets.push(preset);
+        }
+    }
+
+    presets
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merging() {
+        let config = vec![
+            ColumnPreset::new("sysadmin", vec![ProcColumn::Pid, ProcColumn::CpuPercent]),
+            ColumnPreset::new("io", vec![ProcColumn::Pid, ProcColumn::ReadPerSecond]),
+        ];
+        let saved = vec![
+            ColumnPreset::new("io", vec![ProcColumn::Name]),
+            ColumnPreset::new("preset 3", vec![ProcColumn::Name, ProcColumn::State]),
+        ];
+
+        let presets = merge_presets(config.clone(), saved);
+        assert_eq!(presets.len(), 3);
+        assert_eq!(presets[..2], config[..]);
+        assert_eq!(presets[2].name, "preset 3");
+    }
+
+    #[test]
+    fn preset_widget_columns() {
+        let preset = ColumnPreset::new(
+            "dupes",
+            vec![
+                ProcColumn::Pid,
+                ProcColumn::MemPercent,
+                ProcColumn::MemValue,
+                ProcColumn::WChan,
+            ],
+        );
+
+        let mut expected = vec![ProcWidgetColumn::PidOrCount, ProcWidgetColumn::Mem];
+        if cfg!(target_os = "linux") {
+            expected.push(ProcWidgetColumn::WChan);
+        }
+        assert_eq!(
+            preset.widget_columns().into_iter().collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn column_lists() {
+        assert_eq!(
+            parse_column_list("pid,NAME, cpu ,mem%,r/s")
+                .unwrap()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![
+                ProcWidgetColumn::PidOrCount,
+                ProcWidgetColumn::ProcNameOrCommand,
+                ProcWidgetColumn::Cpu,
+                ProcWidgetColumn::Mem,
+                ProcWidgetColumn::ReadPerSecond,
+            ]
+        );
+        assert_eq!(parse_column_list("Default").unwrap(), None);
+
+        let all = parse_column_list("all").unwrap().unwrap();
+        assert_eq!(all.first(), Some(&ProcWidgetColumn::PidOrCount));
+        assert!(all.contains(&ProcWidgetColumn::MemSparkline));
+        assert_eq!(
+            all.contains(&ProcWidgetColumn::WChan),
+            cfg!(target_os = "linux")
+        );
+
+        let err = parse_column_list("pid,bogus").unwrap_err();
+        assert!(err.starts_with("'bogus' is not a process column"));
+        assert!(err.contains("cpu%, mem, mem%, "));
+        assert!(err.contains("io_read"));
+    }
+
+    #[test]
+    fn column_names_parse() {
+        for name in column_names() {
+            assert!(
+                parse_column_list(&name).is_ok(),
+                "'{name}' should be a valid column"
+            );
+        }
+    }
+}
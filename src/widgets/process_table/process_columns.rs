@@ -1,10 +1,14 @@
-use std::{borrow::Cow, cmp::Reverse};
+use std::{
+    borrow::Cow,
+    cmp::{Ordering, Reverse},
+};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{ProcWidgetColumn, ProcWidgetData};
 use crate::{
     canvas::components::data_table::{ColumnHeader, SortsRow},
+    data_collection::processes::ProcessNamespaces,
     utils::general::sort_partial_fn,
 };
 
@@ -29,6 +33,23 @@ pub enum ProcColumn {
     State,
     User,
     Time,
+    CpuDelta,
+    MemDelta,
+    MinorFaults,
+    MajorFaults,
+    CpuSparkline,
+    MemSparkline,
+    WChan,
+    PidNamespace,
+    NetNamespace,
+    MntNamespace,
+    UserNamespace,
+    CpuQuota,
+    CpuThrottled,
+    Container,
+    Pss,
+    TcpConnections,
+    InotifyWatches,
     #[cfg(feature = "gpu")]
     GpuMemValue,
     #[cfg(feature = "gpu")]
@@ -37,6 +58,47 @@ pub enum ProcColumn {
     GpuUtilPercent,
 }
 
+/// Every column, in the order `--columns all` shows them in.
+pub const ALL_PROC_COLUMNS: &[ProcColumn] = &[
+    ProcColumn::Pid,
+    ProcColumn::Count,
+    ProcColumn::Name,
+    ProcColumn::Command,
+    ProcColumn::CpuPercent,
+    ProcColumn::MemValue,
+    ProcColumn::MemPercent,
+    #[cfg(feature = "gpu")]
+    ProcColumn::GpuMemValue,
+    #[cfg(feature = "gpu")]
+    ProcColumn::GpuMemPercent,
+    #[cfg(feature = "gpu")]
+    ProcColumn::GpuUtilPercent,
+    ProcColumn::ReadPerSecond,
+    ProcColumn::WritePerSecond,
+    ProcColumn::TotalRead,
+    ProcColumn::TotalWrite,
+    ProcColumn::User,
+    ProcColumn::State,
+    ProcColumn::Time,
+    ProcColumn::CpuDelta,
+    ProcColumn::MemDelta,
+    ProcColumn::MinorFaults,
+    ProcColumn::MajorFaults,
+    ProcColumn::CpuSparkline,
+    ProcColumn::MemSparkline,
+    ProcColumn::WChan,
+    ProcColumn::PidNamespace,
+    ProcColumn::NetNamespace,
+    ProcColumn::MntNamespace,
+    ProcColumn::UserNamespace,
+    ProcColumn::CpuQuota,
+    ProcColumn::CpuThrottled,
+    ProcColumn::Container,
+    ProcColumn::Pss,
+    ProcColumn::TcpConnections,
+    ProcColumn::InotifyWatches,
+];
+
 impl ProcColumn {
     /// An ugly hack to generate the JSON schema.
     #[cfg(feature = "generate_schema")]
@@ -56,6 +118,23 @@ impl ProcColumn {
             ProcColumn::State => &["State"],
             ProcColumn::User => &["User"],
             ProcColumn::Time => &["Time"],
+            ProcColumn::CpuDelta => &["ΔCPU%", "DCPU%"],
+            ProcColumn::MemDelta => &["ΔMem%", "DMem%"],
+            ProcColumn::MinorFaults => &["MinFlt/s", "MinFlt"],
+            ProcColumn::MajorFaults => &["MajFlt/s", "MajFlt"],
+            ProcColumn::CpuSparkline => &["CPU Hist", "CPU_Sparkline"],
+            ProcColumn::MemSparkline => &["Mem Hist", "Mem_Sparkline"],
+            ProcColumn::WChan => &["WChan"],
+            ProcColumn::PidNamespace => &["PID NS", "PID_NS"],
+            ProcColumn::NetNamespace => &["NET NS", "NET_NS"],
+            ProcColumn::MntNamespace => &["MNT NS", "MNT_NS"],
+            ProcColumn::UserNamespace => &["USER NS", "USER_NS"],
+            ProcColumn::CpuQuota => &["Quota", "CPU_Quota"],
+            ProcColumn::CpuThrottled => &["Thrtl%", "CPU_Throttled_Pct"],
+            ProcColumn::Container => &["Container"],
+            ProcColumn::Pss => &["PSS"],
+            ProcColumn::TcpConnections => &["TCP"],
+            ProcColumn::InotifyWatches => &["Inotify"],
             #[cfg(feature = "gpu")]
             // TODO: Change this
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => &["GMem", "GMem%"],
@@ -82,6 +161,23 @@ impl ColumnHeader for ProcColumn {
             ProcColumn::State => "State",
             ProcColumn::User => "User",
             ProcColumn::Time => "Time",
+            ProcColumn::CpuDelta => "ΔCPU%",
+            ProcColumn::MemDelta => "ΔMem%",
+            ProcColumn::MinorFaults => "MinFlt/s",
+            ProcColumn::MajorFaults => "MajFlt/s",
+            ProcColumn::CpuSparkline => "CPU Hist",
+            ProcColumn::MemSparkline => "Mem Hist",
+            ProcColumn::WChan => "WChan",
+            ProcColumn::PidNamespace => "PID NS",
+            ProcColumn::NetNamespace => "NET NS",
+            ProcColumn::MntNamespace => "MNT NS",
+            ProcColumn::UserNamespace => "USER NS",
+            ProcColumn::CpuQuota => "Quota",
+            ProcColumn::CpuThrottled => "Thrtl%",
+            ProcColumn::Container => "Container",
+            ProcColumn::Pss => "PSS",
+            ProcColumn::TcpConnections => "TCP",
+            ProcColumn::InotifyWatches => "Inotify",
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue => "GMem",
             #[cfg(feature = "gpu")]
@@ -110,12 +206,12 @@ impl SortsRow for ProcColumn {
 
     fn sort_data(&self, data: &mut [ProcWidgetData], descending: bool) {
         match self {
-            ProcColumn::CpuPercent => {
+            ProcColumn::CpuPercent | ProcColumn::CpuSparkline => {
                 data.sort_by(|a, b| {
                     sort_partial_fn(descending)(a.cpu_usage_percent, b.cpu_usage_percent)
                 });
             }
-            ProcColumn::MemValue | ProcColumn::MemPercent => {
+            ProcColumn::MemValue | ProcColumn::MemPercent | ProcColumn::MemSparkline => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(&a.mem_usage, &b.mem_usage));
             }
             ProcColumn::Pid => {
@@ -160,6 +256,70 @@ impl SortsRow for ProcColumn {
             ProcColumn::Time => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.time, b.time));
             }
+            ProcColumn::CpuDelta => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.cpu_delta, b.cpu_delta));
+            }
+            ProcColumn::MemDelta => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.mem_delta, b.mem_delta));
+            }
+            ProcColumn::MinorFaults => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.minflt_per_sec, b.minflt_per_sec)
+                });
+            }
+            ProcColumn::MajorFaults => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.majflt_per_sec, b.majflt_per_sec)
+                });
+            }
+            ProcColumn::WChan => {
+                if descending {
+                    data.sort_by_cached_key(|pd| Reverse(pd.wchan.clone()));
+                } else {
+                    data.sort_by_cached_key(|pd| pd.wchan.clone());
+                }
+            }
+            ProcColumn::Container => {
+                if descending {
+                    data.sort_by_cached_key(|pd| Reverse(pd.container.clone()));
+                } else {
+                    data.sort_by_cached_key(|pd| pd.container.clone());
+                }
+            }
+            ProcColumn::PidNamespace
+            | ProcColumn::NetNamespace
+            | ProcColumn::MntNamespace
+            | ProcColumn::UserNamespace => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(
+                        self.namespace(&a.namespaces),
+                        self.namespace(&b.namespaces),
+                    )
+                });
+            }
+            ProcColumn::CpuQuota => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.cpu_quota_ratio(), b.cpu_quota_ratio())
+                });
+            }
+            ProcColumn::CpuThrottled => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.cpu_throttled(), b.cpu_throttled())
+                });
+            }
+            ProcColumn::Pss => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.pss, b.pss));
+            }
+            ProcColumn::TcpConnections => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.tcp_connections, b.tcp_connections)
+                });
+            }
+            ProcColumn::InotifyWatches => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.inotify_watches, b.inotify_watches)
+                });
+            }
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
                 data.sort_by(|a, b| {
@@ -174,37 +334,219 @@ impl SortsRow for ProcColumn {
     }
 }
 
-impl<'de> Deserialize<'de> for ProcColumn {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let value = String::deserialize(deserializer)?.to_lowercase();
-        match value.as_str() {
-            "cpu%" => Ok(ProcColumn::CpuPercent),
+impl ProcColumn {
+    /// Returns the index of the entry with the highest value in this column,
+    /// skipping disabled entries. Columns that don't measure usage, like names
+    /// or PIDs, have no top entry.
+    pub fn top_index(&self, data: &[ProcWidgetData]) -> Option<usize> {
+        fn max_index<'a, T: PartialOrd>(
+            data: &'a [ProcWidgetData], value: impl Fn(&'a ProcWidgetData) -> T,
+        ) -> Option<usize> {
+            data.iter()
+                .enumerate()
+                .filter(|(_, entry)| !entry.disabled)
+                .fold(None, |top: Option<(usize, T)>, (index, entry)| {
+                    let entry_value = value(entry);
+                    match &top {
+                        Some((_, top_value))
+                            if entry_value.partial_cmp(top_value) != Some(Ordering::Greater) =>
+                        {
+                            top
+                        }
+                        _ => Some((index, entry_value)),
+                    }
+                })
+                .map(|(index, _)| index)
+        }
+
+        match self {
+            ProcColumn::CpuPercent | ProcColumn::CpuSparkline => {
+                max_index(data, |d| d.cpu_usage_percent)
+            }
+            ProcColumn::MemValue | ProcColumn::MemPercent | ProcColumn::MemSparkline => {
+                max_index(data, |d| &d.mem_usage)
+            }
+            ProcColumn::Count => max_index(data, |d| d.num_similar),
+            ProcColumn::ReadPerSecond => max_index(data, |d| d.rps),
+            ProcColumn::WritePerSecond => max_index(data, |d| d.wps),
+            ProcColumn::TotalRead => max_index(data, |d| d.total_read),
+            ProcColumn::TotalWrite => max_index(data, |d| d.total_write),
+            ProcColumn::Time => max_index(data, |d| d.time),
+            ProcColumn::CpuDelta => max_index(data, |d| d.cpu_delta),
+            ProcColumn::MemDelta => max_index(data, |d| d.mem_delta),
+            ProcColumn::MinorFaults => max_index(data, |d| d.minflt_per_sec),
+            ProcColumn::MajorFaults => max_index(data, |d| d.majflt_per_sec),
+            ProcColumn::CpuQuota => max_index(data, |d| d.cpu_quota_ratio()),
+            ProcColumn::CpuThrottled => max_index(data, |d| d.cpu_throttled()),
+            ProcColumn::Pss => max_index(data, |d| d.pss),
+            ProcColumn::TcpConnections => max_index(data, |d| d.tcp_connections),
+            ProcColumn::InotifyWatches => max_index(data, |d| d.inotify_watches),
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
+                max_index(data, |d| &d.gpu_mem_usage)
+            }
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuUtilPercent => max_index(data, |d| d.gpu_usage),
+            ProcColumn::Pid
+            | ProcColumn::Name
+            | ProcColumn::Command
+            | ProcColumn::State
+            | ProcColumn::User
+            | ProcColumn::WChan
+            | ProcColumn::Container
+            | ProcColumn::PidNamespace
+            | ProcColumn::NetNamespace
+            | ProcColumn::MntNamespace
+            | ProcColumn::UserNamespace => None,
+        }
+    }
+
+    /// Returns the value of this column for an entry as a number, for columns
+    /// that show one.
+    pub fn value(&self, data: &ProcWidgetData) -> Option<f64> {
+        match self {
+            ProcColumn::CpuPercent | ProcColumn::CpuSparkline => {
+                Some(data.cpu_usage_percent.into())
+            }
+            ProcColumn::MemValue | ProcColumn::MemPercent | ProcColumn::MemSparkline => {
+                Some(data.mem_usage.value())
+            }
+            ProcColumn::Count => Some(data.num_similar as f64),
+            ProcColumn::ReadPerSecond => Some(data.rps as f64),
+            ProcColumn::WritePerSecond => Some(data.wps as f64),
+            ProcColumn::TotalRead => Some(data.total_read as f64),
+            ProcColumn::TotalWrite => Some(data.total_write as f64),
+            ProcColumn::Time => Some(data.time.as_secs_f64()),
+            ProcColumn::CpuDelta => Some(data.cpu_delta.into()),
+            ProcColumn::MemDelta => Some(data.mem_delta.into()),
+            ProcColumn::MinorFaults => Some(data.minflt_per_sec),
+            ProcColumn::MajorFaults => Some(data.majflt_per_sec),
+            ProcColumn::CpuQuota => Some(data.cpu_quota_ratio()),
+            ProcColumn::CpuThrottled => Some(data.cpu_throttled()),
+            ProcColumn::Pss => data.pss.map(|pss| pss as f64),
+            ProcColumn::TcpConnections => data.tcp_connections.map(|count| count as f64),
+            ProcColumn::InotifyWatches => data.inotify_watches.map(|count| count as f64),
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
+                data.gpu_mem_usage.as_ref().map(|usage| usage.value())
+            }
+            #[cfg(feature = "gpu")]
+            ProcColumn::GpuUtilPercent => Some(data.gpu_usage.into()),
+            ProcColumn::Pid
+            | ProcColumn::Name
+            | ProcColumn::Command
+            | ProcColumn::State
+            | ProcColumn::User
+            | ProcColumn::WChan
+            | ProcColumn::Container
+            | ProcColumn::PidNamespace
+            | ProcColumn::NetNamespace
+            | ProcColumn::MntNamespace
+            | ProcColumn::UserNamespace => None,
+        }
+    }
+
+    /// Returns the kind and inode of the namespace shown by this column, if it
+    /// shows one.
+    pub fn namespace(&self, namespaces: &ProcessNamespaces) -> Option<(&'static str, u64)> {
+        match self {
+            ProcColumn::PidNamespace => Some(("pid", namespaces.pid_ns)),
+            ProcColumn::NetNamespace => Some(("net", namespaces.net_ns)),
+            ProcColumn::MntNamespace => Some(("mnt", namespaces.mnt_ns)),
+            ProcColumn::UserNamespace => Some(("user", namespaces.user_ns)),
+            _ => None,
+        }
+    }
+
+    /// Whether this column shows a namespace.
+    pub fn is_namespace(&self) -> bool {
+        matches!(
+            self,
+            ProcColumn::PidNamespace
+                | ProcColumn::NetNamespace
+                | ProcColumn::MntNamespace
+                | ProcColumn::UserNamespace
+        )
+    }
+
+    /// Parses a column by the name it's given in the config file, ignoring
+    /// case, e.g. `cpu%` or `r/s`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "cpu%" => Some(ProcColumn::CpuPercent),
             // TODO: Maybe change this in the future.
-            "mem" | "mem%" => Ok(ProcColumn::MemPercent),
-            "pid" => Ok(ProcColumn::Pid),
-            "count" => Ok(ProcColumn::Count),
-            "name" => Ok(ProcColumn::Name),
-            "command" => Ok(ProcColumn::Command),
-            "read" | "r/s" | "rps" => Ok(ProcColumn::ReadPerSecond),
-            "write" | "w/s" | "wps" => Ok(ProcColumn::WritePerSecond),
-            "tread" | "t.read" => Ok(ProcColumn::TotalRead),
-            "twrite" | "t.write" => Ok(ProcColumn::TotalWrite),
-            "state" => Ok(ProcColumn::State),
-            "user" => Ok(ProcColumn::User),
-            "time" => Ok(ProcColumn::Time),
+            "mem" | "mem%" => Some(ProcColumn::MemPercent),
+            "pid" => Some(ProcColumn::Pid),
+            "count" => Some(ProcColumn::Count),
+            "name" => Some(ProcColumn::Name),
+            "command" => Some(ProcColumn::Command),
+            "read" | "r/s" | "rps" => Some(ProcColumn::ReadPerSecond),
+            "write" | "w/s" | "wps" => Some(ProcColumn::WritePerSecond),
+            "tread" | "t.read" => Some(ProcColumn::TotalRead),
+            "twrite" | "t.write" => Some(ProcColumn::TotalWrite),
+            "state" => Some(ProcColumn::State),
+            "user" => Some(ProcColumn::User),
+            "time" => Some(ProcColumn::Time),
+            "δcpu%" | "dcpu%" => Some(ProcColumn::CpuDelta),
+            "δmem%" | "dmem%" => Some(ProcColumn::MemDelta),
+            "minflt" | "minflt/s" => Some(ProcColumn::MinorFaults),
+            "majflt" | "majflt/s" => Some(ProcColumn::MajorFaults),
+            "cpu hist" | "cpu_sparkline" => Some(ProcColumn::CpuSparkline),
+            "mem hist" | "mem_sparkline" => Some(ProcColumn::MemSparkline),
+            "wchan" => Some(ProcColumn::WChan),
+            "pid ns" | "pid_ns" => Some(ProcColumn::PidNamespace),
+            "net ns" | "net_ns" => Some(ProcColumn::NetNamespace),
+            "mnt ns" | "mnt_ns" => Some(ProcColumn::MntNamespace),
+            "user ns" | "user_ns" => Some(ProcColumn::UserNamespace),
+            "quota" | "cpu_quota" => Some(ProcColumn::CpuQuota),
+            "thrtl%" | "cpu_throttled_pct" => Some(ProcColumn::CpuThrottled),
+            "container" => Some(ProcColumn::Container),
+            "pss" => Some(ProcColumn::Pss),
+            "tcp" => Some(ProcColumn::TcpConnections),
+            "inotify" => Some(ProcColumn::InotifyWatches),
             #[cfg(feature = "gpu")]
             // TODO: Maybe change this in the future.
-            "gmem" | "gmem%" => Ok(ProcColumn::GpuMemPercent),
+            "gmem" | "gmem%" => Some(ProcColumn::GpuMemPercent),
             #[cfg(feature = "gpu")]
-            "gpu%" => Ok(ProcColumn::GpuUtilPercent),
-            _ => Err(serde::de::Error::custom(
-                "doesn't match any process column name",
-            )),
+            "gpu%" => Some(ProcColumn::GpuUtilPercent),
+            _ => None,
         }
     }
+
+    /// Whether this column is only supported on Linux.
+    pub fn is_linux_only(&self) -> bool {
+        matches!(
+            self,
+            ProcColumn::WChan
+                | ProcColumn::CpuQuota
+                | ProcColumn::CpuThrottled
+                | ProcColumn::Container
+                | ProcColumn::Pss
+                | ProcColumn::TcpConnections
+                | ProcColumn::InotifyWatches
+        ) || self.is_namespace()
+    }
+}
+
+impl<'de> Deserialize<'de> for ProcColumn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        ProcColumn::from_name(&value)
+            .ok_or_else(|| serde::de::Error::custom("doesn't match any process column name"))
+    }
+}
+
+/// Columns are written as their names, which [`Deserialize`] reads back.
+impl Serialize for ProcColumn {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.text())
+    }
 }
 
 impl From<&ProcColumn> for ProcWidgetColumn {
@@ -221,6 +563,23 @@ impl From<&ProcColumn> for ProcWidgetColumn {
             ProcColumn::State => ProcWidgetColumn::State,
             ProcColumn::User => ProcWidgetColumn::User,
             ProcColumn::Time => ProcWidgetColumn::Time,
+            ProcColumn::CpuDelta => ProcWidgetColumn::CpuDelta,
+            ProcColumn::MemDelta => ProcWidgetColumn::MemDelta,
+            ProcColumn::MinorFaults => ProcWidgetColumn::MinorFaults,
+            ProcColumn::MajorFaults => ProcWidgetColumn::MajorFaults,
+            ProcColumn::CpuSparkline => ProcWidgetColumn::CpuSparkline,
+            ProcColumn::MemSparkline => ProcWidgetColumn::MemSparkline,
+            ProcColumn::WChan => ProcWidgetColumn::WChan,
+            ProcColumn::PidNamespace => ProcWidgetColumn::PidNamespace,
+            ProcColumn::NetNamespace => ProcWidgetColumn::NetNamespace,
+            ProcColumn::MntNamespace => ProcWidgetColumn::MntNamespace,
+            ProcColumn::UserNamespace => ProcWidgetColumn::UserNamespace,
+            ProcColumn::CpuQuota => ProcWidgetColumn::CpuQuota,
+            ProcColumn::CpuThrottled => ProcWidgetColumn::CpuThrottled,
+            ProcColumn::Container => ProcWidgetColumn::Container,
+            ProcColumn::Pss => ProcWidgetColumn::Pss,
+            ProcColumn::TcpConnections => ProcWidgetColumn::TcpConnections,
+            ProcColumn::InotifyWatches => ProcWidgetColumn::InotifyWatches,
             #[cfg(feature = "gpu")]
             ProcColumn::GpuMemPercent | ProcColumn::GpuMemValue => ProcWidgetColumn::GpuMem,
             #[cfg(feature = "gpu")]
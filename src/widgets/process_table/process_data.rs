@@ -7,18 +7,61 @@ use std::{
 };
 
 use concat_string::concat_string;
-use tui::widgets::Row;
+use tui::{
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Cell, Row},
+};
 
 use super::process_columns::ProcColumn;
 use crate::{
+    app::diff::DiffChange,
     canvas::{
         components::data_table::{DataTableColumn, DataToCell},
         Painter,
     },
-    data_collection::processes::{Pid, ProcessHarvest},
-    data_conversion::{binary_byte_string, dec_bytes_per_second_string, dec_bytes_string},
+    data_collection::{
+        cgroups::CgroupCpu,
+        processes::{Pid, ProcessHarvest, ProcessNamespaces, ProcessState},
+    },
+    data_conversion::{
+        binary_byte_string, convert_diff_line, dec_bytes_per_second_string, dec_bytes_string,
+        DIFF_ARROW,
+    },
 };
 
+/// How long a process can wait in the same wait channel before it's
+/// highlighted as possibly being stuck.
+const STUCK_WCHAN_TIME: Duration = Duration::from_secs(30);
+
+/// Shown in the cgroup quota columns for processes whose cgroup has no quota.
+const NO_QUOTA: &str = "—";
+
+/// How many characters wide the bar of how much of a cgroup's CPU quota is
+/// used is, not counting the brackets around it.
+const QUOTA_BAR_WIDTH: usize = 5;
+
+/// The characters used to draw a partially filled cell of a quota bar, by how
+/// many eighths of it are filled.
+const QUOTA_BAR_EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Draws a bar of how much of a cgroup's CPU quota is used, like `[██▌  ]`.
+fn quota_bar(ratio: f64) -> String {
+    let eighths = (ratio.clamp(0.0, 1.0) * (QUOTA_BAR_WIDTH * 8) as f64).round() as usize;
+    let mut bar = String::with_capacity(QUOTA_BAR_WIDTH * 3 + 2);
+
+    bar.push('[');
+    for cell in 0..QUOTA_BAR_WIDTH {
+        match eighths.saturating_sub(cell * 8).min(8) {
+            0 => bar.push(' '),
+            filled => bar.push(QUOTA_BAR_EIGHTHS[filled - 1]),
+        }
+    }
+    bar.push(']');
+
+    bar
+}
+
 #[derive(Clone, Debug)]
 enum IdType {
     Name(String),
@@ -100,6 +143,16 @@ impl PartialOrd for MemUsage {
     }
 }
 
+impl MemUsage {
+    /// The percentage or number of bytes used.
+    pub fn value(&self) -> f64 {
+        match self {
+            MemUsage::Percent(percent) => (*percent).into(),
+            MemUsage::Bytes(bytes) => *bytes as f64,
+        }
+    }
+}
+
 impl Display for MemUsage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -184,8 +237,66 @@ pub struct ProcWidgetData {
     pub num_similar: u64,
     pub disabled: bool,
     pub time: Duration,
+    /// The change in CPU usage compared to a baseline, in diff mode.
+    pub cpu_delta: f32,
+    /// The change in memory usage as a percentage compared to a baseline, in
+    /// diff mode.
+    pub mem_delta: f32,
+    /// The CPU usage in the baseline, in diff mode.
+    pub cpu_baseline: f32,
+    /// The memory usage as a percentage in the baseline, in diff mode.
+    pub mem_baseline: f32,
+    /// Whether the CPU usage changed significantly since the baseline, in
+    /// diff mode.
+    pub cpu_change: DiffChange,
+    /// Whether the memory usage changed significantly since the baseline, in
+    /// diff mode.
+    pub mem_change: DiffChange,
+    /// Whether this process is not in the baseline, in diff mode.
+    pub is_new: bool,
+    /// Whether this process is only in the baseline, in diff mode.
+    pub is_gone: bool,
+    pub minflt_per_sec: f64,
+    pub majflt_per_sec: f64,
+    /// Whether the major page fault rate is over the configured threshold.
+    pub is_high_major_faults: bool,
+    /// How many TCP connections the process has open, if they were counted.
+    pub tcp_connections: Option<u32>,
+    /// Whether the number of TCP connections is over the configured threshold.
+    pub is_high_tcp_connections: bool,
+    /// How many inotify watches the process has, if they were counted.
+    pub inotify_watches: Option<u32>,
+    /// Whether the process has more than half of the system's inotify watches.
+    pub is_high_inotify_watches: bool,
+    /// Whether the CPU or memory usage changed a lot in the latest update, when
+    /// highlighting changes.
+    pub is_changed: bool,
+    /// The kernel function the process is waiting in, if any.
+    pub wchan: Option<String>,
+    /// Whether the process has been waiting in the same wait channel for a
+    /// while.
+    pub is_wchan_stuck: bool,
+    pub namespaces: ProcessNamespaces,
+    /// How many entries share each of this entry's namespaces, stored in
+    /// place of each inode. Only set if a namespace column is shown.
+    pub namespace_shares: ProcessNamespaces,
+    /// The CPU quota of the process' cgroup, if it has one.
+    pub cgroup_cpu: Option<CgroupCpu>,
+    /// The name of the container the process is in, if any.
+    pub container: Option<String>,
+    /// The proportional set size of the process in bytes, if it was read.
+    pub pss: Option<u64>,
+    /// Whether this entry has the highest value in a highlighted column, when
+    /// highlighting top consumers.
+    pub is_top: bool,
+    /// A sparkline of recent CPU usage, if the column is shown.
+    pub cpu_sparkline: String,
+    /// A sparkline of recent memory usage, if the column is shown.
+    pub mem_sparkline: String,
     #[cfg(feature = "gpu")]
-    pub gpu_mem_usage: MemUsage,
+    /// The GPU memory used by the process, or [`None`] if it has none
+    /// allocated.
+    pub gpu_mem_usage: Option<MemUsage>,
     #[cfg(feature = "gpu")]
     pub gpu_usage: u32,
 }
@@ -223,12 +334,38 @@ impl ProcWidgetData {
             num_similar: 1,
             disabled: false,
             time: process.time,
+            cpu_delta: 0.0,
+            mem_delta: 0.0,
+            cpu_baseline: 0.0,
+            mem_baseline: 0.0,
+            cpu_change: DiffChange::Unchanged,
+            mem_change: DiffChange::Unchanged,
+            is_new: false,
+            is_gone: false,
+            minflt_per_sec: process.minflt_per_sec,
+            majflt_per_sec: process.majflt_per_sec,
+            is_high_major_faults: false,
+            tcp_connections: process.tcp_connections,
+            is_high_tcp_connections: false,
+            inotify_watches: process.inotify_watches,
+            is_high_inotify_watches: false,
+            is_changed: false,
+            wchan: process.wchan.clone(),
+            is_wchan_stuck: process.wchan.is_some() && process.wchan_time > STUCK_WCHAN_TIME,
+            namespaces: process.namespaces,
+            namespace_shares: ProcessNamespaces::default(),
+            cgroup_cpu: process.cgroup_cpu,
+            container: process.container.clone(),
+            pss: process.pss_bytes,
+            is_top: false,
+            cpu_sparkline: String::new(),
+            mem_sparkline: String::new(),
             #[cfg(feature = "gpu")]
-            gpu_mem_usage: if is_mem_percent {
+            gpu_mem_usage: (process.gpu_mem > 0).then_some(if is_mem_percent {
                 MemUsage::Percent(process.gpu_mem_percent)
             } else {
                 MemUsage::Bytes(process.gpu_mem)
-            },
+            }),
             #[cfg(feature = "gpu")]
             gpu_usage: process.gpu_util,
         }
@@ -249,6 +386,90 @@ impl ProcWidgetData {
         self
     }
 
+    /// Sets the deltas against a baseline process, given the current memory
+    /// usage percentage. Changes of at most `threshold` percentage points
+    /// aren't treated as significant.
+    pub fn set_delta(&mut self, baseline: &ProcessHarvest, mem_percent: f32, threshold: f64) {
+        self.cpu_baseline = baseline.cpu_usage_percent;
+        self.mem_baseline = baseline.mem_usage_percent;
+        self.cpu_delta = self.cpu_usage_percent - baseline.cpu_usage_percent;
+        self.mem_delta = mem_percent - baseline.mem_usage_percent;
+        self.cpu_change = DiffChange::new(
+            baseline.cpu_usage_percent.into(),
+            self.cpu_usage_percent.into(),
+            threshold,
+            true,
+        );
+        self.mem_change = DiffChange::new(
+            baseline.mem_usage_percent.into(),
+            mem_percent.into(),
+            threshold,
+            true,
+        );
+        self.is_new = false;
+    }
+
+    /// Scales the CPU usage and its deltas, for showing it per core instead of
+    /// of all cores or the other way around.
+    pub fn scale_cpu(&mut self, factor: f32) {
+        self.cpu_usage_percent *= factor;
+        self.cpu_delta *= factor;
+        self.cpu_baseline *= factor;
+    }
+
+    /// Marks this process as not being in the baseline, given the current
+    /// memory usage percentage.
+    pub fn set_new(&mut self, mem_percent: f32) {
+        self.cpu_delta = self.cpu_usage_percent;
+        self.mem_delta = mem_percent;
+        self.is_new = true;
+    }
+
+    /// Marks this baseline-only process as being gone, given its memory
+    /// usage percentage.
+    pub fn gone(mut self, mem_percent: f32) -> Self {
+        self.cpu_baseline = self.cpu_usage_percent;
+        self.mem_baseline = mem_percent;
+        self.cpu_delta = -self.cpu_usage_percent;
+        self.mem_delta = -mem_percent;
+        self.disabled = true;
+        self.is_gone = true;
+        self
+    }
+
+    /// The baseline and current values of a diff column, or [`None`] if this
+    /// process is new or gone.
+    fn diff_values(&self, column: &ProcColumn) -> Option<(String, String, DiffChange)> {
+        if self.is_new || self.is_gone {
+            return None;
+        }
+
+        let (baseline, delta, change) = match column {
+            ProcColumn::CpuDelta => (self.cpu_baseline, self.cpu_delta, self.cpu_change),
+            ProcColumn::MemDelta => (self.mem_baseline, self.mem_delta, self.mem_change),
+            _ => return None,
+        };
+
+        Some((
+            format!("{baseline:.1}%"),
+            format!("{:.1}%", baseline + delta),
+            change,
+        ))
+    }
+
+    /// The text of a diff column.
+    fn diff_string(&self, column: &ProcColumn) -> String {
+        if self.is_new {
+            "(new)".to_string()
+        } else if self.is_gone {
+            "(gone)".to_string()
+        } else {
+            self.diff_values(column)
+                .map(|(baseline, current, _)| concat_string!(baseline, DIFF_ARROW, current))
+                .unwrap_or_default()
+        }
+    }
+
     pub fn add(&mut self, other: &Self) {
         self.cpu_usage_percent += other.cpu_usage_percent;
         self.mem_usage = match (&self.mem_usage, &other.mem_usage) {
@@ -264,21 +485,107 @@ impl ProcWidgetData {
         self.total_read += other.total_read;
         self.total_write += other.total_write;
         self.time = self.time.max(other.time);
+        self.minflt_per_sec += other.minflt_per_sec;
+        self.majflt_per_sec += other.majflt_per_sec;
+        if self.wchan != other.wchan {
+            self.wchan = None;
+        }
+        self.is_wchan_stuck = self.wchan.is_some() && self.is_wchan_stuck && other.is_wchan_stuck;
+        self.namespaces.intersect(&other.namespaces);
+        if self.cgroup_cpu != other.cgroup_cpu {
+            self.cgroup_cpu = None;
+        }
+        if self.container != other.container {
+            self.container = None;
+        }
+        self.pss = match (self.pss, other.pss) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        self.tcp_connections = match (self.tcp_connections, other.tcp_connections) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+        self.inotify_watches = match (self.inotify_watches, other.inotify_watches) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
         #[cfg(feature = "gpu")]
         {
             self.gpu_mem_usage = match (&self.gpu_mem_usage, &other.gpu_mem_usage) {
-                (MemUsage::Percent(a), MemUsage::Percent(b)) => MemUsage::Percent(a + b),
-                (MemUsage::Bytes(a), MemUsage::Bytes(b)) => MemUsage::Bytes(a + b),
-                (MemUsage::Percent(_), MemUsage::Bytes(_))
-                | (MemUsage::Bytes(_), MemUsage::Percent(_)) => {
+                (Some(MemUsage::Percent(a)), Some(MemUsage::Percent(b))) => {
+                    Some(MemUsage::Percent(a + b))
+                }
+                (Some(MemUsage::Bytes(a)), Some(MemUsage::Bytes(b))) => {
+                    Some(MemUsage::Bytes(a + b))
+                }
+                (Some(MemUsage::Percent(_)), Some(MemUsage::Bytes(_)))
+                | (Some(MemUsage::Bytes(_)), Some(MemUsage::Percent(_))) => {
                     unreachable!("trying to add together two different memory usage types!")
                 }
+                (usage, None) | (None, usage) => usage.clone(),
             };
             self.gpu_usage += other.gpu_usage;
         }
     }
 
-    fn to_string(&self, column: &ProcColumn) -> String {
+    /// Returns a short identifier for the namespace shown by a namespace
+    /// column, like `net:4026532001`, along with how many entries share it.
+    fn namespace_string(&self, column: &ProcColumn) -> String {
+        match (
+            column.namespace(&self.namespaces),
+            column.namespace(&self.namespace_shares),
+        ) {
+            (Some((_, 0)), _) | (None, _) => "-".into(),
+            (Some((kind, inode)), Some((_, shares))) if shares > 1 => {
+                format!("{kind}:{inode} ({shares})")
+            }
+            (Some((kind, inode)), _) => format!("{kind}:{inode}"),
+        }
+    }
+
+    /// How much of its cgroup's CPU quota is used, or -1 if there is no quota
+    /// so these sort below any with one.
+    pub fn cpu_quota_ratio(&self) -> f64 {
+        self.cgroup_cpu.map_or(-1.0, |cpu| cpu.usage_ratio())
+    }
+
+    /// How much CPU time its cgroup was throttled for, or -1 if there is no
+    /// quota.
+    pub fn cpu_throttled(&self) -> f64 {
+        self.cgroup_cpu.map_or(-1.0, |cpu| cpu.throttled_percent)
+    }
+
+    /// Returns the text of a cgroup quota column.
+    fn cgroup_string(&self, column: &ProcColumn) -> String {
+        match (self.cgroup_cpu, column) {
+            (Some(cpu), ProcColumn::CpuQuota) => {
+                format!("{} {:.0}%", quota_bar(cpu.usage_ratio()), cpu.quota_percent)
+            }
+            (Some(cpu), _) => format!("{:.1}%", cpu.throttled_percent),
+            (None, _) => NO_QUOTA.into(),
+        }
+    }
+
+    /// Whether the process' cgroup is close to using all of its CPU quota.
+    fn is_near_cpu_quota(&self) -> bool {
+        self.cgroup_cpu.is_some_and(|cpu| cpu.is_near_quota())
+    }
+
+    /// The style of the process' state, which stands out more the more unusual
+    /// the state is.
+    fn state_style(&self, painter: &Painter) -> Style {
+        let colours = &painter.colours;
+        match ProcessState::from(self.process_char) {
+            ProcessState::Running => colours.improved_text_style,
+            ProcessState::DiskSleep => colours.warning_text_style,
+            ProcessState::Stopped | ProcessState::TracingStop => colours.stopped_text_style,
+            ProcessState::Zombie => colours.regressed_text_style,
+            _ => colours.disabled_text_style,
+        }
+    }
+
+    pub(crate) fn to_string(&self, column: &ProcColumn) -> String {
         match column {
             ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent),
             ProcColumn::MemValue | ProcColumn::MemPercent => self.mem_usage.to_string(),
@@ -292,8 +599,30 @@ impl ProcWidgetData {
             ProcColumn::State => self.process_char.to_string(),
             ProcColumn::User => self.user.clone(),
             ProcColumn::Time => format_time(self.time),
+            ProcColumn::CpuDelta | ProcColumn::MemDelta => self.diff_string(column),
+            ProcColumn::MinorFaults => format!("{:.1}", self.minflt_per_sec),
+            ProcColumn::MajorFaults => format!("{:.1}", self.majflt_per_sec),
+            ProcColumn::CpuSparkline => self.cpu_sparkline.clone(),
+            ProcColumn::MemSparkline => self.mem_sparkline.clone(),
+            ProcColumn::WChan => self.wchan.clone().unwrap_or_else(|| "-".into()),
+            ProcColumn::PidNamespace
+            | ProcColumn::NetNamespace
+            | ProcColumn::MntNamespace
+            | ProcColumn::UserNamespace => self.namespace_string(column),
+            ProcColumn::CpuQuota | ProcColumn::CpuThrottled => self.cgroup_string(column),
+            ProcColumn::Container => self.container.clone().unwrap_or_else(|| "-".into()),
+            ProcColumn::Pss => self.pss.map_or_else(|| "-".into(), binary_byte_string),
+            ProcColumn::TcpConnections => self
+                .tcp_connections
+                .map_or_else(|| "-".into(), |count| count.to_string()),
+            ProcColumn::InotifyWatches => self
+                .inotify_watches
+                .map_or_else(|| "-".into(), |count| count.to_string()),
             #[cfg(feature = "gpu")]
-            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => self.gpu_mem_usage.to_string(),
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => match &self.gpu_mem_usage {
+                Some(usage) => usage.to_string(),
+                None => "-".into(),
+            },
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage),
         }
@@ -328,24 +657,97 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
             }
             ProcColumn::User => self.user.clone().into(),
             ProcColumn::Time => format_time(self.time).into(),
+            ProcColumn::CpuDelta | ProcColumn::MemDelta => self.diff_string(column).into(),
+            ProcColumn::MinorFaults => format!("{:.1}", self.minflt_per_sec).into(),
+            ProcColumn::MajorFaults => format!("{:.1}", self.majflt_per_sec).into(),
+            ProcColumn::CpuSparkline => self.cpu_sparkline.clone().into(),
+            ProcColumn::MemSparkline => self.mem_sparkline.clone().into(),
+            ProcColumn::WChan => match &self.wchan {
+                Some(wchan) => wchan.clone().into(),
+                None => "-".into(),
+            },
+            ProcColumn::PidNamespace
+            | ProcColumn::NetNamespace
+            | ProcColumn::MntNamespace
+            | ProcColumn::UserNamespace => self.namespace_string(column).into(),
+            ProcColumn::CpuQuota | ProcColumn::CpuThrottled => self.cgroup_string(column).into(),
+            ProcColumn::Container => match &self.container {
+                Some(container) => container.clone().into(),
+                None => "-".into(),
+            },
+            ProcColumn::Pss => match self.pss {
+                Some(pss) => binary_byte_string(pss).into(),
+                None => "-".into(),
+            },
+            ProcColumn::TcpConnections => match self.tcp_connections {
+                Some(count) => count.to_string().into(),
+                None => "-".into(),
+            },
+            ProcColumn::InotifyWatches => match self.inotify_watches {
+                Some(count) => count.to_string().into(),
+                None => "-".into(),
+            },
             #[cfg(feature = "gpu")]
-            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => {
-                self.gpu_mem_usage.to_string().into()
-            }
+            ProcColumn::GpuMemValue | ProcColumn::GpuMemPercent => match &self.gpu_mem_usage {
+                Some(usage) => usage.to_string().into(),
+                None => "-".into(),
+            },
             #[cfg(feature = "gpu")]
             ProcColumn::GpuUtilPercent => format!("{:.1}%", self.gpu_usage).into(),
         })
     }
 
+    fn to_styled_cell(
+        &self, column: &ProcColumn, calculated_width: NonZeroU16, painter: &Painter,
+    ) -> Option<Line<'static>> {
+        let (baseline, current, change) = self.diff_values(column)?;
+        let line = convert_diff_line(baseline, current, change, &painter.colours);
+
+        // Fall back to the truncated plain text if it doesn't fit.
+        (line.width() <= usize::from(calculated_width.get())).then_some(line)
+    }
+
     #[inline(always)]
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
         if self.disabled {
             row.style(painter.colours.disabled_text_style)
+        } else if self.is_top {
+            row.style(painter.colours.top_consumer_text_style)
+        } else if self.is_new {
+            row.style(painter.colours.highlighted_text_style)
+        } else if self.is_changed {
+            row.style(
+                painter
+                    .colours
+                    .warning_text_style
+                    .add_modifier(Modifier::REVERSED),
+            )
+        } else if self.is_high_major_faults
+            || self.is_high_tcp_connections
+            || self.is_high_inotify_watches
+            || self.is_near_cpu_quota()
+        {
+            row.style(painter.colours.warning_text_style)
         } else {
             row
         }
     }
 
+    #[inline(always)]
+    fn style_cell<'a>(&self, column: &ProcColumn, cell: Cell<'a>, painter: &Painter) -> Cell<'a> {
+        if self.disabled {
+            return cell;
+        }
+
+        match column {
+            ProcColumn::WChan if self.is_wchan_stuck => {
+                cell.style(painter.colours.warning_text_style)
+            }
+            ProcColumn::State => cell.style(self.state_style(painter)),
+            _ => cell,
+        }
+    }
+
     fn column_widths<C: DataTableColumn<ProcColumn>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
         Self: Sized,
@@ -366,7 +768,7 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
 mod test {
     use std::time::Duration;
 
-    use crate::widgets::process_data::format_time;
+    use crate::widgets::process_data::{format_time, quota_bar};
 
     #[test]
     fn test_format_time() {
@@ -398,4 +800,13 @@ mod test {
             "364d 23h 59m"
         );
     }
+
+    #[test]
+    fn test_quota_bar() {
+        assert_eq!(quota_bar(0.0), "[     ]");
+        assert_eq!(quota_bar(0.5), "[██▌  ]");
+        assert_eq!(quota_bar(0.9), "[████▌]");
+        assert_eq!(quota_bar(1.0), "[█████]");
+        assert_eq!(quota_bar(2.0), "[█████]");
+    }
 }
@@ -0,0 +1,219 @@
+//! A panel listing the resource limits of a single process.
+
+use std::{borrow::Cow, cmp::max, num::NonZeroU16};
+
+use tui::widgets::Row;
+
+use crate::{
+    app::AppConfigFields,
+    canvas::{
+        components::data_table::{
+            Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
+            DataToCell,
+        },
+        Painter,
+    },
+    data_collection::processes::{Pid, RlimitEntry},
+    options::config::style::ColourPalette,
+};
+
+/// How much of a soft limit the process has to use for it to be flagged as
+/// near the limit.
+const NEAR_LIMIT_RATIO: f64 = 0.8;
+
+/// How close the usage of a resource is to its soft limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LimitUsage {
+    Normal,
+    Near,
+    AtOrOver,
+}
+
+fn limit_usage(entry: &RlimitEntry) -> LimitUsage {
+    match (entry.usage, entry.soft) {
+        (Some(usage), Some(soft)) if usage >= soft => LimitUsage::AtOrOver,
+        (Some(usage), Some(soft)) if usage as f64 > soft as f64 * NEAR_LIMIT_RATIO => {
+            LimitUsage::Near
+        }
+        _ => LimitUsage::Normal,
+    }
+}
+
+/// Shows `RLIM_INFINITY` as unlimited.
+fn limit_string(limit: Option<u64>) -> String {
+    match limit {
+        Some(limit) => limit.to_string(),
+        None => "unlimited".to_string(),
+    }
+}
+
+fn usage_string(entry: &RlimitEntry) -> String {
+    match (entry.usage, entry.soft) {
+        (Some(usage), Some(soft)) if soft > 0 => {
+            format!("{usage} ({:.0}%)", usage as f64 / soft as f64 * 100.0)
+        }
+        (Some(usage), _) => usage.to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+pub enum RlimitColumn {
+    Limit,
+    Soft,
+    Hard,
+    Units,
+    Usage,
+}
+
+impl ColumnHeader for RlimitColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            RlimitColumn::Limit => "Limit".into(),
+            RlimitColumn::Soft => "Soft".into(),
+            RlimitColumn::Hard => "Hard".into(),
+            RlimitColumn::Units => "Units".into(),
+            RlimitColumn::Usage => "Usage".into(),
+        }
+    }
+}
+
+impl DataToCell<RlimitColumn> for RlimitEntry {
+    fn to_cell(
+        &self, column: &RlimitColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(match column {
+            RlimitColumn::Limit => self.name.clone().into(),
+            RlimitColumn::Soft => limit_string(self.soft).into(),
+            RlimitColumn::Hard => limit_string(self.hard).into(),
+            RlimitColumn::Units => self.units.clone().into(),
+            RlimitColumn::Usage => usage_string(self).into(),
+        })
+    }
+
+    fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
+        match limit_usage(self) {
+            LimitUsage::Normal => row,
+            LimitUsage::Near => row.style(painter.colours.warning_text_style),
+            LimitUsage::AtOrOver => row.style(painter.colours.regressed_text_style),
+        }
+    }
+
+    fn column_widths<C: DataTableColumn<RlimitColumn>>(
+        data: &[RlimitEntry], _columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        let mut widths = vec![0; 5];
+
+        data.iter().for_each(|row| {
+            widths[0] = max(widths[0], row.name.len() as u16);
+            widths[1] = max(widths[1], limit_string(row.soft).len() as u16);
+            widths[2] = max(widths[2], limit_string(row.hard).len() as u16);
+            widths[3] = max(widths[3], row.units.len() as u16);
+            widths[4] = max(widths[4], usage_string(row).len() as u16);
+        });
+
+        widths
+    }
+}
+
+/// The state of the resource limit panel, which is shown below the process
+/// table while open. Limits rarely change, so they're only read when the
+/// panel is opened.
+pub struct RlimitPanel {
+    /// The process whose limits are shown, if the panel is open.
+    pub pid: Option<Pid>,
+    pub table: DataTable<RlimitEntry, RlimitColumn>,
+}
+
+impl RlimitPanel {
+    pub(crate) fn new(config: &AppConfigFields, palette: &ColourPalette) -> Self {
+        const COLUMNS: [Column<RlimitColumn>; 5] = [
+            Column::soft(RlimitColumn::Limit, Some(0.4)),
+            Column::soft(RlimitColumn::Soft, Some(0.2)),
+            Column::soft(RlimitColumn::Hard, Some(0.2)),
+            Column::soft(RlimitColumn::Units, Some(0.1)),
+            Column::soft(RlimitColumn::Usage, Some(0.2)),
+        ];
+
+        let props = DataTableProps {
+            title: None,
+            footer: None,
+            table_gap: config.table_gap,
+            left_to_right: true,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_current_entry_when_unfocused: false,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            pid: None,
+            table: DataTable::new(COLUMNS, props, styling),
+        }
+    }
+
+    /// Whether the panel is open.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    /// Opens the panel for the given process, showing the given limits.
+    pub fn open(&mut self, pid: Pid, name: &str, limits: Vec<RlimitEntry>) {
+        let flagged = limits
+            .iter()
+            .filter(|entry| limit_usage(entry) != LimitUsage::Normal)
+            .count();
+        let flagged = if flagged > 0 {
+            format!(", {flagged} near or at the soft limit")
+        } else {
+            String::new()
+        };
+
+        self.pid = Some(pid);
+        self.table.props.title =
+            Some(format!(" Limits of {name} ({pid}){flagged} (Esc to close) ").into());
+        self.table.set_data(limits);
+        self.table.scroll_to_first();
+    }
+
+    /// Closes the panel.
+    pub fn close(&mut self) {
+        self.pid = None;
+        self.table.set_data(vec![]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(soft: Option<u64>, usage: Option<u64>) -> RlimitEntry {
+        RlimitEntry {
+            name: "Max open files".to_string(),
+            soft,
+            hard: None,
+            units: "files".to_string(),
+            usage,
+        }
+    }
+
+    #[test]
+    fn usage() {
+        assert_eq!(limit_usage(&entry(Some(100), Some(80))), LimitUsage::Normal);
+        assert_eq!(limit_usage(&entry(Some(100), Some(81))), LimitUsage::Near);
+        assert_eq!(
+            limit_usage(&entry(Some(100), Some(100))),
+            LimitUsage::AtOrOver
+        );
+        assert_eq!(limit_usage(&entry(None, Some(100))), LimitUsage::Normal);
+        assert_eq!(limit_usage(&entry(Some(100), None)), LimitUsage::Normal);
+
+        assert_eq!(usage_string(&entry(Some(200), Some(50))), "50 (25%)");
+        assert_eq!(usage_string(&entry(None, Some(50))), "50");
+        assert_eq!(limit_string(None), "unlimited");
+    }
+}
@@ -0,0 +1,240 @@
+//! A panel showing which memory safety features a single process has.
+
+use std::{borrow::Cow, cmp::max, num::NonZeroU16};
+
+use tui::widgets::Row;
+
+use crate::{
+    app::AppConfigFields,
+    canvas::{
+        components::data_table::{
+            Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
+            DataToCell,
+        },
+        Painter,
+    },
+    data_collection::processes::{Pid, SecurityFlags},
+    options::config::style::ColourPalette,
+};
+
+/// The rows are always the same four features, so the panel only needs room
+/// for them, the header, and the borders.
+pub const SECURITY_PANEL_HEIGHT: u16 = 8;
+
+/// A single feature and whether the process has it, or [`None`] if unknown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityCheck {
+    pub name: &'static str,
+    pub enabled: Option<bool>,
+    pub detail: &'static str,
+}
+
+/// Lists the features in `flags`, explaining what each means for the process.
+fn security_checks(flags: &SecurityFlags) -> Vec<SecurityCheck> {
+    let check =
+        |name, enabled: Option<bool>, [yes, no, unknown]: [&'static str; 3]| SecurityCheck {
+            name,
+            enabled,
+            detail: match enabled {
+                Some(true) => yes,
+                Some(false) => no,
+                None => unknown,
+            },
+        };
+
+    vec![
+        check(
+            "ASLR",
+            Some(flags.aslr),
+            [
+                "Addresses are randomized",
+                "Turned off for the system or the process",
+                "",
+            ],
+        ),
+        check(
+            "PIE",
+            Some(flags.pie),
+            [
+                "The executable is randomized too",
+                "The executable is always at the same address",
+                "",
+            ],
+        ),
+        check(
+            "NX",
+            Some(flags.nx),
+            ["The stack isn't executable", "The stack is executable", ""],
+        ),
+        check(
+            "Stack canary",
+            flags.stack_canary,
+            [
+                "Imports __stack_chk_fail",
+                "Doesn't import __stack_chk_fail",
+                "Not dynamically linked, so can't be told",
+            ],
+        ),
+    ]
+}
+
+fn status_string(enabled: Option<bool>) -> &'static str {
+    match enabled {
+        Some(true) => "✓",
+        Some(false) => "✗",
+        None => "?",
+    }
+}
+
+pub enum SecurityColumn {
+    Feature,
+    Status,
+    Detail,
+}
+
+impl ColumnHeader for SecurityColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            SecurityColumn::Feature => "Feature".into(),
+            SecurityColumn::Status => "Status".into(),
+            SecurityColumn::Detail => "Detail".into(),
+        }
+    }
+}
+
+impl DataToCell<SecurityColumn> for SecurityCheck {
+    fn to_cell(
+        &self, column: &SecurityColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(match column {
+            SecurityColumn::Feature => self.name.into(),
+            SecurityColumn::Status => status_string(self.enabled).into(),
+            SecurityColumn::Detail => self.detail.into(),
+        })
+    }
+
+    fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
+        match self.enabled {
+            Some(true) => row.style(painter.colours.improved_text_style),
+            Some(false) => row.style(painter.colours.regressed_text_style),
+            None => row,
+        }
+    }
+
+    fn column_widths<C: DataTableColumn<SecurityColumn>>(
+        data: &[SecurityCheck], _columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        let mut widths = vec![0; 3];
+
+        data.iter().for_each(|row| {
+            widths[0] = max(widths[0], row.name.len() as u16);
+            widths[1] = max(widths[1], 1);
+            widths[2] = max(widths[2], row.detail.len() as u16);
+        });
+
+        widths
+    }
+}
+
+/// The state of the security panel, which is shown below the process table
+/// while open. Like limits, these rarely change, so they're only read when the
+/// panel is opened.
+pub struct SecurityPanel {
+    /// The process whose features are shown, if the panel is open.
+    pub pid: Option<Pid>,
+    pub table: DataTable<SecurityCheck, SecurityColumn>,
+}
+
+impl SecurityPanel {
+    pub(crate) fn new(config: &AppConfigFields, palette: &ColourPalette) -> Self {
+        const COLUMNS: [Column<SecurityColumn>; 3] = [
+            Column::soft(SecurityColumn::Feature, Some(0.2)),
+            Column::soft(SecurityColumn::Status, Some(0.1)),
+            Column::soft(SecurityColumn::Detail, Some(0.7)),
+        ];
+
+        let props = DataTableProps {
+            title: None,
+            footer: None,
+            table_gap: config.table_gap,
+            left_to_right: true,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_current_entry_when_unfocused: false,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            pid: None,
+            table: DataTable::new(COLUMNS, props, styling),
+        }
+    }
+
+    /// Whether the panel is open.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    /// Opens the panel for the given process, showing the given features, or
+    /// why there are none if they couldn't be read.
+    pub fn open(&mut self, pid: Pid, name: &str, flags: Option<SecurityFlags>) {
+        let (title, checks) = match flags {
+            Some(flags) => (
+                format!(" Security of {name} ({pid}) "),
+                security_checks(&flags),
+            ),
+            None => (
+                format!(
+                    " Security of {name} ({pid}): can't read the executable, which may need root "
+                ),
+                vec![],
+            ),
+        };
+
+        self.pid = Some(pid);
+        self.table.props.title = Some(format!("{title}(Esc to close) ").into());
+        self.table.set_data(checks);
+        self.table.scroll_to_first();
+    }
+
+    /// Closes the panel.
+    pub fn close(&mut self) {
+        self.pid = None;
+        self.table.set_data(vec![]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checks() {
+        let checks = security_checks(&SecurityFlags {
+            aslr: true,
+            stack_canary: None,
+            nx: false,
+            pie: true,
+        });
+
+        assert_eq!(
+            checks
+                .iter()
+                .map(|check| (check.name, status_string(check.enabled)))
+                .collect::<Vec<_>>(),
+            vec![
+                ("ASLR", "✓"),
+                ("PIE", "✓"),
+                ("NX", "✗"),
+                ("Stack canary", "?")
+            ]
+        );
+        assert_eq!(checks[2].detail, "The stack is executable");
+        assert_eq!(checks[3].detail, "Not dynamically linked, so can't be told");
+    }
+}
@@ -0,0 +1,291 @@
+//! A panel listing how much memory a single process has mapped from each file.
+
+use std::{borrow::Cow, cmp::max, num::NonZeroU16};
+
+use hashbrown::HashMap;
+use tui::widgets::Row;
+
+use crate::{
+    app::AppConfigFields,
+    canvas::{
+        components::data_table::{
+            Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
+            DataToCell,
+        },
+        Painter,
+    },
+    data_collection::processes::{Pid, SmapRegion},
+    data_conversion::binary_byte_string,
+    options::config::style::ColourPalette,
+};
+
+/// Sums the file-backed regions of a process by the file backing them, with
+/// the files using the most memory first. Anonymous regions and pseudo-paths
+/// like `[heap]` are left out.
+fn group_by_file(regions: Vec<SmapRegion>) -> Vec<SmapRegion> {
+    let mut files: HashMap<String, SmapRegion> = HashMap::new();
+    for region in regions.into_iter().filter(|r| r.path.starts_with('/')) {
+        let file = files
+            .entry(region.path.clone())
+            .or_insert_with(|| SmapRegion {
+                path: region.path.clone(),
+                ..Default::default()
+            });
+
+        file.rss += region.rss;
+        file.pss += region.pss;
+        file.shared_clean += region.shared_clean;
+        file.shared_dirty += region.shared_dirty;
+        file.private_clean += region.private_clean;
+        file.private_dirty += region.private_dirty;
+    }
+
+    let mut files: Vec<SmapRegion> = files.into_values().collect();
+    files.sort_by(|a, b| b.rss.cmp(&a.rss).then_with(|| a.path.cmp(&b.path)));
+    files
+}
+
+/// PSS is a share of RSS, so it should never be larger. If it is, the kernel's
+/// accounting is off, and the row is flagged rather than silently trusted.
+fn is_anomalous(file: &SmapRegion) -> bool {
+    file.pss > file.rss
+}
+
+pub enum SmapsColumn {
+    File,
+    Rss,
+    PrivateDirty,
+    PrivateClean,
+    SharedClean,
+    Pss,
+}
+
+impl ColumnHeader for SmapsColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            SmapsColumn::File => "File".into(),
+            SmapsColumn::Rss => "RSS".into(),
+            SmapsColumn::PrivateDirty => "Priv Dirty".into(),
+            SmapsColumn::PrivateClean => "Priv Clean".into(),
+            SmapsColumn::SharedClean => "Shr Clean".into(),
+            SmapsColumn::Pss => "PSS".into(),
+        }
+    }
+}
+
+impl DataToCell<SmapsColumn> for SmapRegion {
+    fn to_cell(
+        &self, column: &SmapsColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(match column {
+            SmapsColumn::File => self.path.clone().into(),
+            SmapsColumn::Rss => binary_byte_string(self.rss).into(),
+            SmapsColumn::PrivateDirty => binary_byte_string(self.private_dirty).into(),
+            SmapsColumn::PrivateClean => binary_byte_string(self.private_clean).into(),
+            SmapsColumn::SharedClean => binary_byte_string(self.shared_clean).into(),
+            SmapsColumn::Pss => binary_byte_string(self.pss).into(),
+        })
+    }
+
+    fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
+        if is_anomalous(self) {
+            row.style(painter.colours.regressed_text_style)
+        } else {
+            row
+        }
+    }
+
+    fn column_widths<C: DataTableColumn<SmapsColumn>>(
+        data: &[SmapRegion], _columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        let mut widths = vec![0; 6];
+
+        data.iter().for_each(|row| {
+            widths[0] = max(widths[0], row.path.len() as u16);
+            for (width, value) in widths[1..].iter_mut().zip([
+                row.rss,
+                row.private_dirty,
+                row.private_clean,
+                row.shared_clean,
+                row.pss,
+            ]) {
+                *width = max(*width, binary_byte_string(value).len() as u16);
+            }
+        });
+
+        widths
+    }
+}
+
+/// The state of the memory map panel, which is shown below the process table
+/// while open. Reading `smaps` is slow for processes with many mappings, so
+/// it's only read when the panel is opened.
+pub struct SmapsPanel {
+    /// The process whose memory map is shown, if the panel is open.
+    pub pid: Option<Pid>,
+    pub table: DataTable<SmapRegion, SmapsColumn>,
+
+    /// Only files with a path containing this, ignoring case, are shown.
+    pub filter: String,
+
+    /// Whether typed characters go to the filter.
+    pub is_filtering: bool,
+
+    name: String,
+    files: Vec<SmapRegion>,
+}
+
+impl SmapsPanel {
+    pub(crate) fn new(config: &AppConfigFields, palette: &ColourPalette) -> Self {
+        const COLUMNS: [Column<SmapsColumn>; 6] = [
+            Column::soft(SmapsColumn::File, Some(0.5)),
+            Column::soft(SmapsColumn::Rss, Some(0.1)),
+            Column::soft(SmapsColumn::PrivateDirty, Some(0.1)),
+            Column::soft(SmapsColumn::PrivateClean, Some(0.1)),
+            Column::soft(SmapsColumn::SharedClean, Some(0.1)),
+            Column::soft(SmapsColumn::Pss, Some(0.1)),
+        ];
+
+        let props = DataTableProps {
+            title: None,
+            footer: None,
+            table_gap: config.table_gap,
+            left_to_right: true,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_current_entry_when_unfocused: false,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            pid: None,
+            table: DataTable::new(COLUMNS, props, styling),
+            filter: String::new(),
+            is_filtering: false,
+            name: String::new(),
+            files: vec![],
+        }
+    }
+
+    /// Whether the panel is open.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    /// Opens the panel for the given process, grouping the given regions by
+    /// file.
+    pub fn open(&mut self, pid: Pid, name: &str, regions: Vec<SmapRegion>) {
+        self.pid = Some(pid);
+        self.name = name.to_string();
+        self.files = group_by_file(regions);
+        self.filter.clear();
+        self.is_filtering = false;
+        self.update_table();
+    }
+
+    /// Closes the panel.
+    pub fn close(&mut self) {
+        self.pid = None;
+        self.files.clear();
+        self.filter.clear();
+        self.is_filtering = false;
+        self.table.set_data(vec![]);
+    }
+
+    pub fn push_filter(&mut self, c: char) {
+        self.filter.push(c);
+        self.update_table();
+    }
+
+    pub fn pop_filter(&mut self) {
+        self.filter.pop();
+        self.update_table();
+    }
+
+    /// Shows the files matching the filter, with the totals of those files in
+    /// the title.
+    fn update_table(&mut self) {
+        let filter = self.filter.to_lowercase();
+        let files: Vec<SmapRegion> = self
+            .files
+            .iter()
+            .filter(|file| file.path.to_lowercase().contains(&filter))
+            .cloned()
+            .collect();
+
+        let rss = files.iter().map(|file| file.rss).sum();
+        let pss = files.iter().map(|file| file.pss).sum();
+        let anomalies = files.iter().filter(|file| is_anomalous(file)).count();
+        let anomalies = if anomalies > 0 {
+            format!(", {anomalies} with PSS > RSS")
+        } else {
+            String::new()
+        };
+        let filter = if self.is_filtering || !self.filter.is_empty() {
+            format!(" [/{}]", self.filter)
+        } else {
+            String::new()
+        };
+
+        if let Some(pid) = self.pid {
+            self.table.props.title = Some(
+                format!(
+                    " Memory map of {} ({pid}): RSS {}, PSS {}{anomalies}{filter} (Esc to close) ",
+                    self.name,
+                    binary_byte_string(rss),
+                    binary_byte_string(pss),
+                )
+                .into(),
+            );
+        }
+        self.table.set_data(files);
+        self.table.scroll_to_first();
+    }
+
+    /// Starts or stops typing into the filter.
+    pub fn set_filtering(&mut self, is_filtering: bool) {
+        self.is_filtering = is_filtering;
+        self.update_table();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn region(path: &str, rss: u64, pss: u64) -> SmapRegion {
+        SmapRegion {
+            path: path.to_string(),
+            rss,
+            pss,
+            private_dirty: rss / 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn grouping() {
+        let files = group_by_file(vec![
+            region("/usr/lib/libc.so.6", 100, 20),
+            region("[heap]", 5000, 5000),
+            region("/usr/bin/bash", 400, 300),
+            region("/usr/lib/libc.so.6", 200, 40),
+            region("", 1000, 1000),
+        ]);
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "/usr/bin/bash");
+        assert_eq!(files[1].path, "/usr/lib/libc.so.6");
+        assert_eq!(files[1].rss, 300);
+        assert_eq!(files[1].pss, 60);
+        assert_eq!(files[1].private_dirty, 150);
+
+        assert!(!is_anomalous(&files[0]));
+        assert!(is_anomalous(&region("/usr/bin/bash", 10, 20)));
+    }
+}
@@ -0,0 +1,275 @@
+//! A panel listing the threads of a single process.
+
+use std::{borrow::Cow, cmp::max, num::NonZeroU16, time::Instant};
+
+use hashbrown::HashMap;
+
+use crate::{
+    app::AppConfigFields,
+    canvas::components::data_table::{
+        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
+        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    },
+    data_collection::processes::{Pid, ThreadInfo},
+    options::config::style::ColourPalette,
+    utils::general::sort_partial_fn,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadData {
+    pub tid: Pid,
+    pub name: String,
+    pub state: char,
+    /// CPU usage since the last update, as a percentage of a single core.
+    pub cpu_usage_percent: f64,
+}
+
+impl ThreadData {
+    fn cpu_usage(&self) -> String {
+        format!("{:.1}%", self.cpu_usage_percent)
+    }
+}
+
+pub enum ThreadColumn {
+    Tid,
+    Name,
+    CpuPercent,
+    State,
+}
+
+impl ColumnHeader for ThreadColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            ThreadColumn::Tid => "TID".into(),
+            ThreadColumn::Name => "Name".into(),
+            ThreadColumn::CpuPercent => "CPU%".into(),
+            ThreadColumn::State => "State".into(),
+        }
+    }
+}
+
+impl DataToCell<ThreadColumn> for ThreadData {
+    fn to_cell(
+        &self, column: &ThreadColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(match column {
+            ThreadColumn::Tid => self.tid.to_string().into(),
+            ThreadColumn::Name => self.name.clone().into(),
+            ThreadColumn::CpuPercent => self.cpu_usage().into(),
+            ThreadColumn::State => self.state.to_string().into(),
+        })
+    }
+
+    fn column_widths<C: DataTableColumn<ThreadColumn>>(
+        data: &[ThreadData], _columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        let mut widths = vec![0; 4];
+
+        data.iter().for_each(|row| {
+            widths[0] = max(widths[0], row.tid.to_string().len() as u16);
+            widths[1] = max(widths[1], row.name.len() as u16);
+            widths[2] = max(widths[2], row.cpu_usage().len() as u16);
+        });
+
+        widths
+    }
+}
+
+impl SortsRow for ThreadColumn {
+    type DataType = ThreadData;
+
+    fn sort_data(&self, data: &mut [ThreadData], descending: bool) {
+        match self {
+            ThreadColumn::Tid => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.tid, b.tid));
+            }
+            ThreadColumn::Name => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.name, &b.name));
+            }
+            ThreadColumn::CpuPercent => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.cpu_usage_percent, b.cpu_usage_percent)
+                });
+            }
+            ThreadColumn::State => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.state, b.state));
+            }
+        }
+    }
+}
+
+/// The state of the thread panel, which is shown below the process table
+/// while open.
+pub struct ThreadPanel {
+    /// The process whose threads are shown, if the panel is open.
+    pub pid: Option<Pid>,
+    pub table: SortDataTable<ThreadData, ThreadColumn>,
+    /// The name of the process whose threads are shown.
+    name: String,
+    /// The name of the container the process is in, if any.
+    container: Option<String>,
+    /// The CPU time of each thread as of the last update, in seconds.
+    prev_cpu_times: HashMap<Pid, f64>,
+    last_update: Option<Instant>,
+}
+
+impl ThreadPanel {
+    pub(crate) fn new(config: &AppConfigFields, palette: &ColourPalette) -> Self {
+        let columns = [
+            SortColumn::new(ThreadColumn::Tid),
+            SortColumn::soft(ThreadColumn::Name, Some(0.5)),
+            SortColumn::new(ThreadColumn::CpuPercent).default_descending(),
+            SortColumn::new(ThreadColumn::State),
+        ];
+
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: None,
+                footer: None,
+                table_gap: config.table_gap,
+                left_to_right: true,
+                is_basic: false,
+                show_table_scroll_position: false,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 2,
+            order: SortOrder::Descending,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            pid: None,
+            table: SortDataTable::new_sortable(columns, props, styling),
+            name: String::new(),
+            container: None,
+            prev_cpu_times: HashMap::default(),
+            last_update: None,
+        }
+    }
+
+    /// Whether the panel is open.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    /// Opens the panel for the given process.
+    pub fn open(&mut self, pid: Pid, name: String, container: Option<String>) {
+        self.pid = Some(pid);
+        self.name = name;
+        self.container = container;
+        self.prev_cpu_times.clear();
+        self.last_update = None;
+        self.table.set_data(vec![]);
+        self.table.scroll_to_first();
+    }
+
+    /// Closes the panel.
+    pub fn close(&mut self) {
+        self.pid = None;
+        self.prev_cpu_times.clear();
+        self.table.set_data(vec![]);
+    }
+
+    /// Updates the shown threads. CPU usage is based on how much CPU time each
+    /// thread used since the last update, as a fraction of the time between
+    /// updates, so it's only known from the second update onwards.
+    pub fn update(&mut self, threads: Vec<ThreadInfo>, now: Instant) {
+        let Some(pid) = self.pid else {
+            return;
+        };
+
+        let elapsed = self
+            .last_update
+            .map(|last_update| now.saturating_duration_since(last_update).as_secs_f64())
+            .unwrap_or_default();
+        let prev_cpu_times = std::mem::take(&mut self.prev_cpu_times);
+
+        let mut data = threads
+            .into_iter()
+            .map(|thread| {
+                let cpu_time = thread.cpu_time.as_secs_f64();
+                let cpu_usage_percent = match prev_cpu_times.get(&thread.tid) {
+                    Some(prev_cpu_time) if elapsed > 0.0 => {
+                        (cpu_time - prev_cpu_time).max(0.0) / elapsed * 100.0
+                    }
+                    _ => 0.0,
+                };
+                self.prev_cpu_times.insert(thread.tid, cpu_time);
+
+                ThreadData {
+                    tid: thread.tid,
+                    name: thread.name,
+                    state: thread.state,
+                    cpu_usage_percent,
+                }
+            })
+            .collect::<Vec<_>>();
+        self.last_update = Some(now);
+
+        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+            column.sort_by(&mut data, self.table.order());
+        }
+
+        let container = match &self.container {
+            Some(container) => format!(" in {container}"),
+            None => String::new(),
+        };
+        self.table.props.title = Some(
+            format!(
+                " Threads of {} ({pid}){container}: {} (Esc to close) ",
+                self.name,
+                data.len()
+            )
+            .into(),
+        );
+        self.table.set_data(data);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn thread(tid: Pid, cpu_secs: u64) -> ThreadInfo {
+        ThreadInfo {
+            tid,
+            name: format!("worker-{tid}"),
+            state: 'S',
+            cpu_time: Duration::from_secs(cpu_secs),
+        }
+    }
+
+    #[test]
+    fn cpu_usage_since_last_update() {
+        let mut panel = ThreadPanel::new(&AppConfigFields::default(), &ColourPalette::default());
+        let now = Instant::now();
+
+        // Closed panels aren't updated.
+        panel.update(vec![thread(1, 0)], now);
+        assert_eq!(panel.table.current_item(), None);
+
+        panel.open(1, "server".to_string(), None);
+        panel.update(vec![thread(1, 10), thread(2, 10)], now);
+        assert_eq!(panel.table.current_item().unwrap().cpu_usage_percent, 0.0);
+
+        // Thread 2 used a whole second of CPU time in the two seconds since.
+        // Thread 3 is new, so its usage isn't known yet.
+        panel.update(
+            vec![thread(1, 10), thread(2, 11), thread(3, 5)],
+            now + Duration::from_secs(2),
+        );
+        let top = panel.table.current_item().unwrap();
+        assert_eq!(top.tid, 2);
+        assert_eq!(top.cpu_usage_percent, 50.0);
+
+        panel.close();
+        assert!(!panel.is_open());
+    }
+}
@@ -0,0 +1,64 @@
+//! The least CPU and memory usage processes need to be shown.
+
+use crate::data_collection::processes::ProcessHarvest;
+
+/// The least CPU and memory usage, in percent, that a process needs to be
+/// shown. Processes below either threshold are hidden.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ProcThresholds {
+    pub cpu: Option<f64>,
+    pub mem: Option<f64>,
+}
+
+impl ProcThresholds {
+    /// Creates thresholds from the given values. A threshold of 0 is the same
+    /// as none.
+    pub fn new(cpu: Option<f64>, mem: Option<f64>) -> Self {
+        Self {
+            cpu: cpu.filter(|cpu| *cpu > 0.0),
+            mem: mem.filter(|mem| *mem > 0.0),
+        }
+    }
+
+    /// Whether either threshold is set.
+    pub fn is_set(&self) -> bool {
+        self.cpu.is_some() || self.mem.is_some()
+    }
+
+    /// Whether a process uses enough CPU and memory to be shown.
+    pub fn is_met(&self, process: &ProcessHarvest) -> bool {
+        self.cpu
+            .map_or(true, |cpu| f64::from(process.cpu_usage_percent) >= cpu)
+            && self
+                .mem
+                .map_or(true, |mem| f64::from(process.mem_usage_percent) >= mem)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(cpu: f32, mem: f32) -> ProcessHarvest {
+        ProcessHarvest {
+            cpu_usage_percent: cpu,
+            mem_usage_percent: mem,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn thresholds() {
+        let thresholds = ProcThresholds::new(Some(1.0), Some(0.5));
+        assert!(thresholds.is_met(&process(1.0, 0.5)));
+        assert!(!thresholds.is_met(&process(0.9, 10.0)));
+        assert!(!thresholds.is_met(&process(10.0, 0.4)));
+
+        let cpu_only = ProcThresholds::new(Some(1.0), Some(0.0));
+        assert_eq!(cpu_only.mem, None);
+        assert!(cpu_only.is_met(&process(2.0, 0.0)));
+
+        assert!(!ProcThresholds::new(Some(0.0), None).is_set());
+        assert!(ProcThresholds::default().is_met(&process(0.0, 0.0)));
+    }
+}
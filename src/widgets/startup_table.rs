@@ -0,0 +1,194 @@
+use std::{borrow::Cow, cmp::max, num::NonZeroU16};
+
+use crate::{
+    app::AppConfigFields,
+    canvas::components::data_table::{
+        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
+        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    },
+    data_collection::processes::Pid,
+    data_conversion::binary_byte_string,
+    options::config::style::ColourPalette,
+    utils::general::sort_partial_fn,
+};
+
+/// A user unit started at login, along with the usage of its main process if
+/// it's running.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StartupWidgetData {
+    pub unit: Cow<'static, str>,
+    pub description: Cow<'static, str>,
+    pub enabled: bool,
+    pub active: Cow<'static, str>,
+    pub pid: Option<Pid>,
+    pub cpu_usage_percent: Option<f32>,
+    pub mem_usage_bytes: Option<u64>,
+}
+
+impl StartupWidgetData {
+    fn enabled(&self) -> Cow<'static, str> {
+        if self.enabled { "yes" } else { "no" }.into()
+    }
+
+    fn pid(&self) -> Cow<'static, str> {
+        self.pid.map_or("-".into(), |pid| pid.to_string().into())
+    }
+
+    fn cpu(&self) -> Cow<'static, str> {
+        self.cpu_usage_percent
+            .map_or("-".into(), |cpu| format!("{cpu:.1}%").into())
+    }
+
+    fn mem(&self) -> Cow<'static, str> {
+        self.mem_usage_bytes
+            .map_or("-".into(), |mem| binary_byte_string(mem).into())
+    }
+}
+
+pub enum StartupWidgetColumn {
+    Unit,
+    Description,
+    Enabled,
+    Active,
+    Pid,
+    Cpu,
+    Mem,
+}
+
+impl ColumnHeader for StartupWidgetColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            StartupWidgetColumn::Unit => "Unit",
+            StartupWidgetColumn::Description => "Description",
+            StartupWidgetColumn::Enabled => "Enabled",
+            StartupWidgetColumn::Active => "State",
+            StartupWidgetColumn::Pid => "PID",
+            StartupWidgetColumn::Cpu => "CPU%",
+            StartupWidgetColumn::Mem => "Mem",
+        }
+        .into()
+    }
+}
+
+impl DataToCell<StartupWidgetColumn> for StartupWidgetData {
+    fn to_cell(
+        &self, column: &StartupWidgetColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(match column {
+            StartupWidgetColumn::Unit => self.unit.clone(),
+            StartupWidgetColumn::Description => self.description.clone(),
+            StartupWidgetColumn::Enabled => self.enabled(),
+            StartupWidgetColumn::Active => self.active.clone(),
+            StartupWidgetColumn::Pid => self.pid(),
+            StartupWidgetColumn::Cpu => self.cpu(),
+            StartupWidgetColumn::Mem => self.mem(),
+        })
+    }
+
+    fn column_widths<C: DataTableColumn<StartupWidgetColumn>>(
+        data: &[StartupWidgetData], _columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        let mut widths = vec![0; 7];
+
+        data.iter().for_each(|row| {
+            widths[0] = max(widths[0], row.unit.len() as u16);
+            widths[1] = max(widths[1], row.description.len() as u16);
+            widths[3] = max(widths[3], row.active.len() as u16);
+            widths[4] = max(widths[4], row.pid().len() as u16);
+        });
+
+        widths
+    }
+}
+
+impl SortsRow for StartupWidgetColumn {
+    type DataType = StartupWidgetData;
+
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool) {
+        match self {
+            StartupWidgetColumn::Unit => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.unit, &b.unit));
+            }
+            StartupWidgetColumn::Description => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.description, &b.description));
+            }
+            StartupWidgetColumn::Enabled => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.enabled, b.enabled));
+            }
+            StartupWidgetColumn::Active => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.active, &b.active));
+            }
+            StartupWidgetColumn::Pid => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.pid, b.pid));
+            }
+            StartupWidgetColumn::Cpu => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.cpu_usage_percent, b.cpu_usage_percent)
+                });
+            }
+            StartupWidgetColumn::Mem => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.mem_usage_bytes, b.mem_usage_bytes)
+                });
+            }
+        }
+    }
+}
+
+pub struct StartupWidgetState {
+    pub table: SortDataTable<StartupWidgetData, StartupWidgetColumn>,
+    pub force_update_data: bool,
+}
+
+impl StartupWidgetState {
+    pub(crate) fn new(config: &AppConfigFields, palette: &ColourPalette) -> Self {
+        let columns = [
+            SortColumn::soft(StartupWidgetColumn::Unit, Some(0.3)),
+            SortColumn::soft(StartupWidgetColumn::Description, Some(0.3)),
+            SortColumn::hard(StartupWidgetColumn::Enabled, 8),
+            SortColumn::soft(StartupWidgetColumn::Active, Some(0.1)),
+            SortColumn::soft(StartupWidgetColumn::Pid, None),
+            SortColumn::hard(StartupWidgetColumn::Cpu, 8).default_descending(),
+            SortColumn::hard(StartupWidgetColumn::Mem, 8).default_descending(),
+        ];
+
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some(" Startup ".into()),
+                footer: None,
+                table_gap: config.table_gap,
+                left_to_right: true,
+                is_basic: config.use_basic_mode,
+                show_table_scroll_position: config.show_table_scroll_position,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 0,
+            order: SortOrder::Ascending,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            table: SortDataTable::new_sortable(columns, props, styling),
+            force_update_data: false,
+        }
+    }
+
+    /// Forces an update of the data stored.
+    #[inline]
+    pub fn force_data_update(&mut self) {
+        self.force_update_data = true;
+    }
+
+    /// Update the current table data.
+    pub fn set_table_data(&mut self, data: &[StartupWidgetData]) {
+        let mut data = data.to_vec();
+        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+            column.sort_by(&mut data, self.table.order());
+        }
+        self.table.set_data(data);
+    }
+}
@@ -4,11 +4,15 @@ use concat_string::concat_string;
 
 use crate::{
     app::AppConfigFields,
-    canvas::components::data_table::{
-        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
-        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    canvas::components::{
+        data_table::{
+            ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell,
+            SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        },
+        time_chart::Point,
     },
     data_collection::temperature::TemperatureType,
+    data_conversion::delta_string,
     options::config::style::ColourPalette,
     utils::general::sort_partial_fn,
 };
@@ -18,11 +22,18 @@ pub struct TempWidgetData {
     pub sensor: Cow<'static, str>,
     pub temperature_value: Option<u64>,
     pub temperature_type: TemperatureType,
+    /// A sparkline of recent readings.
+    pub sparkline: String,
+    /// Recent readings, relative to the current time.
+    pub history: Vec<Point>,
+    /// The reading when the baseline snapshot was taken, in diff mode.
+    pub baseline_value: Option<u64>,
 }
 
 pub enum TempWidgetColumn {
     Sensor,
     Temp,
+    History,
 }
 
 impl ColumnHeader for TempWidgetColumn {
@@ -30,6 +41,7 @@ impl ColumnHeader for TempWidgetColumn {
         match self {
             TempWidgetColumn::Sensor => "Sensor(s)".into(),
             TempWidgetColumn::Temp => "Temp(t)".into(),
+            TempWidgetColumn::History => "History".into(),
         }
     }
 }
@@ -38,25 +50,40 @@ impl TempWidgetData {
     pub fn temperature(&self) -> Cow<'static, str> {
         match self.temperature_value {
             Some(temp_val) => {
-                let temp_type = match self.temperature_type {
-                    TemperatureType::Celsius => "°C",
-                    TemperatureType::Kelvin => "K",
-                    TemperatureType::Fahrenheit => "°F",
-                };
-                concat_string!(temp_val.to_string(), temp_type).into()
+                let unit = self.temperature_type.to_string();
+                let delta = self
+                    .baseline_value
+                    .map(|baseline| {
+                        delta_string(temp_val, baseline, |difference| {
+                            concat_string!(difference.to_string(), unit)
+                        })
+                    })
+                    .unwrap_or_default();
+
+                concat_string!(temp_val.to_string(), unit, delta).into()
             }
             None => "N/A".to_string().into(),
         }
     }
+
+    /// The most recent readings of the sparkline that fit in the given width,
+    /// aligned to the right.
+    fn sparkline(&self, width: usize) -> String {
+        let len = self.sparkline.chars().count();
+        let shown = self.sparkline.chars().skip(len.saturating_sub(width));
+
+        format!("{:>width$}", shown.collect::<String>())
+    }
 }
 
 impl DataToCell<TempWidgetColumn> for TempWidgetData {
     fn to_cell(
-        &self, column: &TempWidgetColumn, _calculated_width: NonZeroU16,
+        &self, column: &TempWidgetColumn, calculated_width: NonZeroU16,
     ) -> Option<Cow<'static, str>> {
         Some(match column {
             TempWidgetColumn::Sensor => self.sensor.clone(),
             TempWidgetColumn::Temp => self.temperature(),
+            TempWidgetColumn::History => self.sparkline(calculated_width.get().into()).into(),
         })
     }
 
@@ -66,11 +93,12 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
     where
         Self: Sized,
     {
-        let mut widths = vec![0; 2];
+        let mut widths = vec![0; 3];
 
         data.iter().for_each(|row| {
             widths[0] = max(widths[0], row.sensor.len() as u16);
             widths[1] = max(widths[1], row.temperature().len() as u16);
+            widths[2] = max(widths[2], row.sparkline.chars().count() as u16);
         });
 
         widths
@@ -85,7 +113,7 @@ impl SortsRow for TempWidgetColumn {
             TempWidgetColumn::Sensor => {
                 data.sort_by(move |a, b| sort_partial_fn(descending)(&a.sensor, &b.sensor));
             }
-            TempWidgetColumn::Temp => {
+            TempWidgetColumn::Temp | TempWidgetColumn::History => {
                 data.sort_by(|a, b| {
                     sort_partial_fn(descending)(a.temperature_value, b.temperature_value)
                 });
@@ -104,11 +132,13 @@ impl TempWidgetState {
         let columns = [
             SortColumn::soft(TempWidgetColumn::Sensor, Some(0.8)),
             SortColumn::soft(TempWidgetColumn::Temp, None).default_descending(),
+            SortColumn::soft(TempWidgetColumn::History, Some(0.4)).default_descending(),
         ];
 
         let props = SortDataTableProps {
             inner: DataTableProps {
-                title: Some(" Temperatures ".into()),
+                title: Some(Self::title(config.temperature_type)),
+                footer: None,
                 table_gap: config.table_gap,
                 left_to_right: false,
                 is_basic: config.use_basic_mode,
@@ -127,6 +157,15 @@ impl TempWidgetState {
         }
     }
 
+    fn title(temperature_type: TemperatureType) -> Cow<'static, str> {
+        format!(" Temperatures [{temperature_type}] ").into()
+    }
+
+    /// Shows the unit the temperatures are now in.
+    pub fn set_temperature_type(&mut self, temperature_type: TemperatureType) {
+        self.table.props.title = Some(Self::title(temperature_type));
+    }
+
     /// Forces an update of the data stored.
     #[inline]
     pub fn force_data_update(&mut self) {
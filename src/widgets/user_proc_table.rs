@@ -0,0 +1,177 @@
+use std::{borrow::Cow, cmp::max, num::NonZeroU16};
+
+use crate::{
+    app::AppConfigFields,
+    canvas::components::data_table::{
+        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
+        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    },
+    data_collection::processes::{ProcessCounts, UserProcCount},
+    options::config::style::ColourPalette,
+    utils::general::sort_partial_fn,
+};
+
+pub enum UserProcWidgetColumn {
+    User,
+    Procs,
+    Threads,
+    #[cfg(target_family = "unix")]
+    Uid,
+}
+
+impl ColumnHeader for UserProcWidgetColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            UserProcWidgetColumn::User => "User",
+            UserProcWidgetColumn::Procs => "Procs",
+            UserProcWidgetColumn::Threads => "Threads",
+            #[cfg(target_family = "unix")]
+            UserProcWidgetColumn::Uid => "UID",
+        }
+        .into()
+    }
+}
+
+impl DataToCell<UserProcWidgetColumn> for UserProcCount {
+    fn to_cell(
+        &self, column: &UserProcWidgetColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(match column {
+            UserProcWidgetColumn::User => self.username.clone().into(),
+            UserProcWidgetColumn::Procs => self.proc_count.to_string().into(),
+            UserProcWidgetColumn::Threads => self.thread_count.to_string().into(),
+            #[cfg(target_family = "unix")]
+            UserProcWidgetColumn::Uid => self.uid.to_string().into(),
+        })
+    }
+
+    fn column_widths<C: DataTableColumn<UserProcWidgetColumn>>(
+        data: &[UserProcCount], columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        let mut widths = vec![0; columns.len()];
+
+        data.iter().for_each(|row| {
+            widths[0] = max(widths[0], row.username.len() as u16);
+        });
+
+        widths
+    }
+}
+
+impl SortsRow for UserProcWidgetColumn {
+    type DataType = UserProcCount;
+
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool) {
+        match self {
+            UserProcWidgetColumn::User => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.username, &b.username));
+            }
+            UserProcWidgetColumn::Procs => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.proc_count, b.proc_count));
+            }
+            UserProcWidgetColumn::Threads => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.thread_count, b.thread_count));
+            }
+            #[cfg(target_family = "unix")]
+            UserProcWidgetColumn::Uid => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.uid, b.uid));
+            }
+        }
+    }
+}
+
+/// The totals shown below the table.
+fn totals_text(counts: &ProcessCounts) -> String {
+    let ProcessCounts {
+        processes,
+        threads,
+        running,
+        sleeping,
+        stopped,
+        zombies,
+    } = counts;
+
+    format!(
+        " {processes} procs, {threads} threads: {running} running, {sleeping} sleeping, \
+         {stopped} stopped, {zombies} zombie "
+    )
+}
+
+pub struct UserProcWidgetState {
+    pub table: SortDataTable<UserProcCount, UserProcWidgetColumn>,
+    pub force_update_data: bool,
+}
+
+impl UserProcWidgetState {
+    pub(crate) fn new(config: &AppConfigFields, palette: &ColourPalette) -> Self {
+        let columns = vec![
+            SortColumn::soft(UserProcWidgetColumn::User, Some(0.4)),
+            SortColumn::hard(UserProcWidgetColumn::Procs, 8).default_descending(),
+            SortColumn::hard(UserProcWidgetColumn::Threads, 8).default_descending(),
+            #[cfg(target_family = "unix")]
+            SortColumn::hard(UserProcWidgetColumn::Uid, 8),
+        ];
+
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some(" User Processes ".into()),
+                footer: None,
+                table_gap: config.table_gap,
+                left_to_right: true,
+                is_basic: config.use_basic_mode,
+                show_table_scroll_position: config.show_table_scroll_position,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 1,
+            order: SortOrder::Descending,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            table: SortDataTable::new_sortable(columns, props, styling),
+            force_update_data: false,
+        }
+    }
+
+    /// Forces an update of the data stored.
+    #[inline]
+    pub fn force_data_update(&mut self) {
+        self.force_update_data = true;
+    }
+
+    /// Update the current table data, along with the totals.
+    pub fn set_table_data(&mut self, data: &[UserProcCount], counts: &ProcessCounts) {
+        let mut data = data.to_vec();
+        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+            column.sort_by(&mut data, self.table.order());
+        }
+        self.table.set_data(data);
+        self.table.props.footer = Some(totals_text(counts).into());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn totals() {
+        let counts = ProcessCounts {
+            processes: 312,
+            threads: 1204,
+            running: 2,
+            sleeping: 300,
+            stopped: 0,
+            zombies: 1,
+        };
+
+        assert_eq!(
+            totals_text(&counts),
+            " 312 procs, 1204 threads: 2 running, 300 sleeping, 0 stopped, 1 zombie "
+        );
+    }
+}